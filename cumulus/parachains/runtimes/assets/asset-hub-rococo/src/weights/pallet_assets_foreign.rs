@@ -21,6 +21,10 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `runner-ynta1nyy-project-238-concurrent-0`, CPU: `Intel(R) Xeon(R) CPU @ 2.60GHz`
 //! EXECUTION: ``, WASM-EXECUTION: `Compiled`, CHAIN: `Some("asset-hub-rococo-dev")`, DB CACHE: 1024
+//!
+//! Some of these weights were not generated by the benchmark CLI yet. They are marked as not
+//! benchmarked, and must be replaced by the output of the benchmarks before they are used on
+//! a production chain.
 
 // Executed Command:
 // ./target/production/polkadot-parachain
@@ -531,4 +535,130 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `ForeignAssets::Asset` (r:1 w:0)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::ConfidentialAssets` (r:1 w:1)
+	/// Proof: `ForeignAssets::ConfidentialAssets` (`max_values`: None, `max_size`: Some(634), added: 3109, mode: `MaxEncodedLen`)
+	fn enable_confidential() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(15_190_000, 0)
+			.saturating_add(Weight::from_parts(0, 4273))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `ForeignAssets::ConfidentialAssets` (r:1 w:1)
+	/// Proof: `ForeignAssets::ConfidentialAssets` (`max_values`: None, `max_size`: Some(634), added: 3109, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:1 w:0)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::ConfidentialAccounts` (r:1 w:1)
+	/// Proof: `ForeignAssets::ConfidentialAccounts` (`max_values`: None, `max_size`: Some(826), added: 3301, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn configure_confidential_account() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(335_789_000, 0)
+			.saturating_add(Weight::from_parts(0, 4291))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `ForeignAssets::ConfidentialAssets` (r:1 w:1)
+	/// Proof: `ForeignAssets::ConfidentialAssets` (`max_values`: None, `max_size`: Some(634), added: 3109, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:1 w:1)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::ConfidentialAccounts` (r:1 w:1)
+	/// Proof: `ForeignAssets::ConfidentialAccounts` (`max_values`: None, `max_size`: Some(826), added: 3301, mode: `MaxEncodedLen`)
+	fn deposit_confidential() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(118_934_000, 0)
+			.saturating_add(Weight::from_parts(0, 4291))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	/// Storage: `ForeignAssets::ConfidentialAccounts` (r:1 w:1)
+	/// Proof: `ForeignAssets::ConfidentialAccounts` (`max_values`: None, `max_size`: Some(826), added: 3301, mode: `MaxEncodedLen`)
+	fn apply_pending_balance() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(64_944_000, 0)
+			.saturating_add(Weight::from_parts(0, 4291))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `ForeignAssets::ConfidentialAssets` (r:1 w:0)
+	/// Proof: `ForeignAssets::ConfidentialAssets` (`max_values`: None, `max_size`: Some(634), added: 3109, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Asset` (r:1 w:0)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:2 w:0)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::ConfidentialAccounts` (r:2 w:2)
+	/// Proof: `ForeignAssets::ConfidentialAccounts` (`max_values`: None, `max_size`: Some(826), added: 3301, mode: `MaxEncodedLen`)
+	fn confidential_transfer() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(43_816_554_000, 0)
+			.saturating_add(Weight::from_parts(0, 7592))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `ForeignAssets::ConfidentialAssets` (r:1 w:1)
+	/// Proof: `ForeignAssets::ConfidentialAssets` (`max_values`: None, `max_size`: Some(634), added: 3109, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::ConfidentialAccounts` (r:1 w:1)
+	/// Proof: `ForeignAssets::ConfidentialAccounts` (`max_values`: None, `max_size`: Some(826), added: 3301, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:1 w:1)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn withdraw_confidential() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(22_069_007_000, 0)
+			.saturating_add(Weight::from_parts(0, 4291))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	/// Storage: `ForeignAssets::Asset` (r:1 w:0)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Compliance` (r:0 w:1)
+	/// Proof: `ForeignAssets::Compliance` (`max_values`: None, `max_size`: Some(641), added: 3116, mode: `MaxEncodedLen`)
+	fn set_compliance_rules() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(12_264_000, 0)
+			.saturating_add(Weight::from_parts(0, 4273))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `ForeignAssets::Asset` (r:1 w:0)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::ComplianceAccounts` (r:1 w:1)
+	/// Proof: `ForeignAssets::ComplianceAccounts` (`max_values`: None, `max_size`: Some(675), added: 3150, mode: `MaxEncodedLen`)
+	fn set_listed() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(14_837_000, 0)
+			.saturating_add(Weight::from_parts(0, 4273))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `ForeignAssets::Asset` (r:1 w:0)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::ComplianceAccounts` (r:1 w:1)
+	/// Proof: `ForeignAssets::ComplianceAccounts` (`max_values`: None, `max_size`: Some(675), added: 3150, mode: `MaxEncodedLen`)
+	fn set_jurisdiction() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(14_912_000, 0)
+			.saturating_add(Weight::from_parts(0, 4273))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `ForeignAssets::Asset` (r:1 w:0)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::RestrictedJurisdictions` (r:0 w:1)
+	/// Proof: `ForeignAssets::RestrictedJurisdictions` (`max_values`: None, `max_size`: Some(628), added: 3103, mode: `MaxEncodedLen`)
+	fn restrict_jurisdiction() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(12_105_000, 0)
+			.saturating_add(Weight::from_parts(0, 4273))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
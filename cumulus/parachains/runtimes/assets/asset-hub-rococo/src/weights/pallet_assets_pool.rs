@@ -21,6 +21,10 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `runner-ynta1nyy-project-238-concurrent-0`, CPU: `Intel(R) Xeon(R) CPU @ 2.60GHz`
 //! EXECUTION: ``, WASM-EXECUTION: `Compiled`, CHAIN: `Some("asset-hub-rococo-dev")`, DB CACHE: 1024
+//!
+//! Some of these weights were not generated by the benchmark CLI yet. They are marked as not
+//! benchmarked, and must be replaced by the output of the benchmarks before they are used on
+//! a production chain.
 
 // Executed Command:
 // target/production/polkadot-parachain
@@ -528,4 +532,130 @@ impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `PoolAssets::Asset` (r:1 w:0)
+	/// Proof: `PoolAssets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::ConfidentialAssets` (r:1 w:1)
+	/// Proof: `PoolAssets::ConfidentialAssets` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	fn enable_confidential() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(14_893_000, 0)
+			.saturating_add(Weight::from_parts(0, 3675))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `PoolAssets::ConfidentialAssets` (r:1 w:1)
+	/// Proof: `PoolAssets::ConfidentialAssets` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::Account` (r:1 w:0)
+	/// Proof: `PoolAssets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::ConfidentialAccounts` (r:1 w:1)
+	/// Proof: `PoolAssets::ConfidentialAccounts` (`max_values`: None, `max_size`: Some(228), added: 2703, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn configure_confidential_account() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(329_205_000, 0)
+			.saturating_add(Weight::from_parts(0, 3693))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `PoolAssets::ConfidentialAssets` (r:1 w:1)
+	/// Proof: `PoolAssets::ConfidentialAssets` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::Asset` (r:1 w:1)
+	/// Proof: `PoolAssets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::Account` (r:1 w:1)
+	/// Proof: `PoolAssets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::ConfidentialAccounts` (r:1 w:1)
+	/// Proof: `PoolAssets::ConfidentialAccounts` (`max_values`: None, `max_size`: Some(228), added: 2703, mode: `MaxEncodedLen`)
+	fn deposit_confidential() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(116_602_000, 0)
+			.saturating_add(Weight::from_parts(0, 3693))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	/// Storage: `PoolAssets::ConfidentialAccounts` (r:1 w:1)
+	/// Proof: `PoolAssets::ConfidentialAccounts` (`max_values`: None, `max_size`: Some(228), added: 2703, mode: `MaxEncodedLen`)
+	fn apply_pending_balance() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(63_671_000, 0)
+			.saturating_add(Weight::from_parts(0, 3693))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `PoolAssets::ConfidentialAssets` (r:1 w:0)
+	/// Proof: `PoolAssets::ConfidentialAssets` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::Asset` (r:1 w:0)
+	/// Proof: `PoolAssets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::Account` (r:2 w:0)
+	/// Proof: `PoolAssets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::ConfidentialAccounts` (r:2 w:2)
+	/// Proof: `PoolAssets::ConfidentialAccounts` (`max_values`: None, `max_size`: Some(228), added: 2703, mode: `MaxEncodedLen`)
+	fn confidential_transfer() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(42_957_406_000, 0)
+			.saturating_add(Weight::from_parts(0, 6396))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `PoolAssets::ConfidentialAssets` (r:1 w:1)
+	/// Proof: `PoolAssets::ConfidentialAssets` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::ConfidentialAccounts` (r:1 w:1)
+	/// Proof: `PoolAssets::ConfidentialAccounts` (`max_values`: None, `max_size`: Some(228), added: 2703, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::Asset` (r:1 w:1)
+	/// Proof: `PoolAssets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::Account` (r:1 w:1)
+	/// Proof: `PoolAssets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn withdraw_confidential() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(21_636_282_000, 0)
+			.saturating_add(Weight::from_parts(0, 3693))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	/// Storage: `PoolAssets::Asset` (r:1 w:0)
+	/// Proof: `PoolAssets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::Compliance` (r:0 w:1)
+	/// Proof: `PoolAssets::Compliance` (`max_values`: None, `max_size`: Some(43), added: 2518, mode: `MaxEncodedLen`)
+	fn set_compliance_rules() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(12_264_000, 0)
+			.saturating_add(Weight::from_parts(0, 3675))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `PoolAssets::Asset` (r:1 w:0)
+	/// Proof: `PoolAssets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::ComplianceAccounts` (r:1 w:1)
+	/// Proof: `PoolAssets::ComplianceAccounts` (`max_values`: None, `max_size`: Some(77), added: 2552, mode: `MaxEncodedLen`)
+	fn set_listed() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(14_837_000, 0)
+			.saturating_add(Weight::from_parts(0, 3675))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `PoolAssets::Asset` (r:1 w:0)
+	/// Proof: `PoolAssets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::ComplianceAccounts` (r:1 w:1)
+	/// Proof: `PoolAssets::ComplianceAccounts` (`max_values`: None, `max_size`: Some(77), added: 2552, mode: `MaxEncodedLen`)
+	fn set_jurisdiction() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(14_912_000, 0)
+			.saturating_add(Weight::from_parts(0, 3675))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `PoolAssets::Asset` (r:1 w:0)
+	/// Proof: `PoolAssets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::RestrictedJurisdictions` (r:0 w:1)
+	/// Proof: `PoolAssets::RestrictedJurisdictions` (`max_values`: None, `max_size`: Some(30), added: 2505, mode: `MaxEncodedLen`)
+	fn restrict_jurisdiction() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(12_105_000, 0)
+			.saturating_add(Weight::from_parts(0, 3675))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
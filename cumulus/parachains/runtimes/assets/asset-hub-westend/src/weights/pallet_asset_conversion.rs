@@ -20,6 +20,10 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `cob`, CPU: `<UNKNOWN>`
 //! WASM-EXECUTION: `Compiled`, CHAIN: `Some("asset-hub-westend-dev")`, DB CACHE: 1024
+//!
+//! Some of these weights were not generated by the benchmark CLI yet. They are marked as not
+//! benchmarked, and must be replaced by the output of the benchmarks before they are used on
+//! a production chain.
 
 // Executed Command:
 // ./target/debug/polkadot-parachain
@@ -175,4 +179,206 @@ impl<T: frame_system::Config> pallet_asset_conversion::WeightInfo for WeightInfo
 			.saturating_add(T::DbWeight::get().reads(7_u64))
 			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
 	}
+	/// Storage: `AssetConversion::ConcentratedPools` (r:1 w:1)
+	/// Proof: `AssetConversion::ConcentratedPools` (`max_values`: None, `max_size`: Some(1298), added: 3773, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:1 w:1)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
+	fn create_concentrated_pool() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(92_377_000, 0)
+			.saturating_add(Weight::from_parts(0, 6196))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	/// Storage: `AssetConversion::ConcentratedPools` (r:1 w:1)
+	/// Proof: `AssetConversion::ConcentratedPools` (`max_values`: None, `max_size`: Some(1298), added: 3773, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::ConcentratedTicks` (r:3 w:2)
+	/// Proof: `AssetConversion::ConcentratedTicks` (`max_values`: None, `max_size`: Some(1320), added: 3795, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::InitializedTicks` (r:2 w:2)
+	/// Proof: `AssetConversion::InitializedTicks` (`max_values`: None, `max_size`: Some(1470), added: 3945, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::ConcentratedPositions` (r:1 w:1)
+	/// Proof: `AssetConversion::ConcentratedPositions` (`max_values`: None, `max_size`: Some(1380), added: 3855, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:2 w:2)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn add_concentrated_liquidity() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(193_850_000, 0)
+			.saturating_add(Weight::from_parts(0, 12780))
+			.saturating_add(T::DbWeight::get().reads(12))
+			.saturating_add(T::DbWeight::get().writes(11))
+	}
+	/// Storage: `AssetConversion::ConcentratedPools` (r:1 w:1)
+	/// Proof: `AssetConversion::ConcentratedPools` (`max_values`: None, `max_size`: Some(1298), added: 3773, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::ConcentratedPositions` (r:1 w:1)
+	/// Proof: `AssetConversion::ConcentratedPositions` (`max_values`: None, `max_size`: Some(1380), added: 3855, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::ConcentratedTicks` (r:4 w:2)
+	/// Proof: `AssetConversion::ConcentratedTicks` (`max_values`: None, `max_size`: Some(1320), added: 3795, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::InitializedTicks` (r:2 w:2)
+	/// Proof: `AssetConversion::InitializedTicks` (`max_values`: None, `max_size`: Some(1470), added: 3945, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:2 w:2)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn remove_concentrated_liquidity() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(201_035_000, 0)
+			.saturating_add(Weight::from_parts(0, 16170))
+			.saturating_add(T::DbWeight::get().reads(13))
+			.saturating_add(T::DbWeight::get().writes(11))
+	}
+	/// Storage: `AssetConversion::ConcentratedPools` (r:1 w:0)
+	/// Proof: `AssetConversion::ConcentratedPools` (`max_values`: None, `max_size`: Some(1298), added: 3773, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::ConcentratedPositions` (r:1 w:1)
+	/// Proof: `AssetConversion::ConcentratedPositions` (`max_values`: None, `max_size`: Some(1380), added: 3855, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::ConcentratedTicks` (r:2 w:0)
+	/// Proof: `AssetConversion::ConcentratedTicks` (`max_values`: None, `max_size`: Some(1320), added: 3795, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:2 w:2)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn collect_concentrated_fees() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(145_663_000, 0)
+			.saturating_add(Weight::from_parts(0, 8580))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+	/// Storage: `AssetConversion::ConcentratedPools` (r:1 w:1)
+	/// Proof: `AssetConversion::ConcentratedPools` (`max_values`: None, `max_size`: Some(1298), added: 3773, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::InitializedTicks` (r:1 w:0)
+	/// Proof: `AssetConversion::InitializedTicks` (`max_values`: None, `max_size`: Some(1470), added: 3945, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::ConcentratedTicks` (r:63 w:63)
+	/// Proof: `AssetConversion::ConcentratedTicks` (`max_values`: None, `max_size`: Some(1320), added: 3795, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:2 w:2)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `t` is `[1, 63]`.
+	fn swap_concentrated(t: u32, ) -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(131_472_306, 0)
+			.saturating_add(Weight::from_parts(0, 8580))
+			.saturating_add(Weight::from_parts(14_382_510, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(t.into())))
+			.saturating_add(T::DbWeight::get().writes(6))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(t.into())))
+			.saturating_add(Weight::from_parts(0, 3795).saturating_mul(t.into()))
+	}
+	/// Storage: `AssetConversion::StablePairs` (r:6 w:12)
+	/// Proof: `AssetConversion::StablePairs` (`max_values`: None, `max_size`: Some(1268), added: 3743, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::NextStablePoolId` (r:1 w:1)
+	/// Proof: `AssetConversion::NextStablePoolId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::NextPoolAssetId` (r:1 w:1)
+	/// Proof: `AssetConversion::NextPoolAssetId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::Asset` (r:1 w:1)
+	/// Proof: `PoolAssets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::Account` (r:1 w:1)
+	/// Proof: `PoolAssets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::StablePools` (r:0 w:1)
+	/// Proof: `AssetConversion::StablePools` (`max_values`: None, `max_size`: Some(2449), added: 4924, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[2, 4]`.
+	fn create_stable_pool(n: u32, ) -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(51_094_372, 0)
+			.saturating_add(Weight::from_parts(0, 3675))
+			.saturating_add(Weight::from_parts(9_318_406, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(5))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 7486).saturating_mul(n.into()))
+	}
+	/// Storage: `AssetConversion::StablePools` (r:1 w:0)
+	/// Proof: `AssetConversion::StablePools` (`max_values`: None, `max_size`: Some(2449), added: 4924, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Asset` (r:4 w:4)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:8 w:8)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::Asset` (r:1 w:1)
+	/// Proof: `PoolAssets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::Account` (r:2 w:2)
+	/// Proof: `PoolAssets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[2, 4]`.
+	fn add_stable_liquidity(n: u32, ) -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(112_817_540, 0)
+			.saturating_add(Weight::from_parts(0, 8580))
+			.saturating_add(Weight::from_parts(58_264_913, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(4))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 6414).saturating_mul(n.into()))
+	}
+	/// Storage: `AssetConversion::StablePools` (r:1 w:0)
+	/// Proof: `AssetConversion::StablePools` (`max_values`: None, `max_size`: Some(2449), added: 4924, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::Asset` (r:1 w:1)
+	/// Proof: `PoolAssets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
+	/// Storage: `PoolAssets::Account` (r:1 w:1)
+	/// Proof: `PoolAssets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Asset` (r:4 w:4)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:8 w:8)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[2, 4]`.
+	fn remove_stable_liquidity(n: u32, ) -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(109_402_886, 0)
+			.saturating_add(Weight::from_parts(0, 8580))
+			.saturating_add(Weight::from_parts(48_902_655, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 6414).saturating_mul(n.into()))
+	}
+	/// Storage: `AssetConversion::StablePools` (r:1 w:1)
+	/// Proof: `AssetConversion::StablePools` (`max_values`: None, `max_size`: Some(2449), added: 4924, mode: `MaxEncodedLen`)
+	fn set_stable_amplification() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(20_541_000, 0)
+			.saturating_add(Weight::from_parts(0, 5914))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `AssetConversion::StablePairs` (r:1 w:0)
+	/// Proof: `AssetConversion::StablePairs` (`max_values`: None, `max_size`: Some(1268), added: 3743, mode: `MaxEncodedLen`)
+	/// Storage: `AssetConversion::StablePools` (r:1 w:0)
+	/// Proof: `AssetConversion::StablePools` (`max_values`: None, `max_size`: Some(2449), added: 4924, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Asset` (r:4 w:2)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(808), added: 3283, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:6 w:4)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(732), added: 3207, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[2, 4]`.
+	fn swap_stable(n: u32, ) -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(134_526_181, 0)
+			.saturating_add(Weight::from_parts(0, 8580))
+			.saturating_add(Weight::from_parts(17_045_372, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(8))
+			.saturating_add(Weight::from_parts(0, 3207).saturating_mul(n.into()))
+	}
 }
@@ -99,6 +99,7 @@ impl pallet_referenda::Config<FellowshipReferendaInstance> for Runtime {
 	>;
 	type CancelOrigin = Architects;
 	type KillOrigin = Masters;
+	type UpdateOrigin = EnsureRoot<AccountId>;
 	type Slash = ToParentTreasury<WestendTreasuryAccount, LocationToAccountId, Runtime>;
 	type Votes = pallet_ranked_collective::Votes;
 	type Tally = pallet_ranked_collective::TallyOf<Runtime, FellowshipCollectiveInstance>;
@@ -106,6 +107,7 @@ impl pallet_referenda::Config<FellowshipReferendaInstance> for Runtime {
 	type MaxQueued = ConstU32<100>;
 	type UndecidingTimeout = ConstU32<{ 7 * DAYS }>;
 	type AlarmInterval = ConstU32<1>;
+	type MaxTracks = ConstU32<32>;
 	type Tracks = tracks::TracksInfo;
 	type Preimages = Preimage;
 }
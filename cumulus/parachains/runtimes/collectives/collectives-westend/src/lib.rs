@@ -725,6 +725,16 @@ type Migrations = (
 	pallet_collator_selection::migration::v2::MigrationToV2<Runtime>,
 	// unreleased
 	cumulus_pallet_xcmp_queue::migration::v4::MigrationToV4<Runtime>,
+	// unreleased
+	pallet_referenda::migration::v2::MigrateV1ToV2<
+		Runtime,
+		fellowship::FellowshipReferendaInstance,
+	>,
+	// unreleased
+	pallet_referenda::migration::v2::MigrateV1ToV2<
+		Runtime,
+		ambassador::AmbassadorReferendaInstance,
+	>,
 	// permanent
 	pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>,
 );
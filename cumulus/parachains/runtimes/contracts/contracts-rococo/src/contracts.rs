@@ -19,11 +19,13 @@ use crate::{
 };
 use frame_support::{
 	parameter_types,
-	traits::{ConstBool, ConstU32, Nothing},
+	traits::{ConstBool, ConstU32, ConstU64, Nothing},
+	weights::Weight,
 };
 use frame_system::EnsureSigned;
 use pallet_contracts::{
-	weights::SubstrateWeight, Config, DebugInfo, DefaultAddressGenerator, Frame, Schedule,
+	weights::SubstrateWeight, Config, DebugInfo, DefaultAddressGenerator, DefaultAddressMapper,
+	Frame, Schedule,
 };
 use sp_runtime::Perbill;
 
@@ -39,6 +41,7 @@ parameter_types! {
 	pub const DefaultDepositLimit: Balance = deposit(1024, 1024 * 1024);
 	pub MySchedule: Schedule<Runtime> = Default::default();
 	pub CodeHashLockupDepositPercent: Perbill = Perbill::from_percent(30);
+	pub WeightPerEthGas: Weight = Weight::from_parts(10_000, 1);
 }
 
 impl Config for Runtime {
@@ -71,10 +74,18 @@ impl Config for Runtime {
 	type MaxDebugBufferLen = ConstU32<{ 2 * 1024 * 1024 }>;
 	type MaxDelegateDependencies = ConstU32<32>;
 	type CodeHashLockupDepositPercent = CodeHashLockupDepositPercent;
-	type Migrations = (pallet_contracts::migration::v16::Migration<Runtime>,);
+	type Migrations = (
+		pallet_contracts::migration::v16::Migration<Runtime>,
+		pallet_contracts::migration::v17::Migration<Runtime>,
+		pallet_contracts::migration::v18::Migration<Runtime>,
+	);
 	type RuntimeHoldReason = RuntimeHoldReason;
 	type Debug = ();
 	type Environment = ();
 	type ApiVersion = ();
 	type Xcm = pallet_xcm::Pallet<Self>;
+	type AddressMapper = DefaultAddressMapper;
+	type ChainId = ConstU64<1002>;
+	type WeightPerEthGas = WeightPerEthGas;
+	type SponsorshipPolicy = ();
 }
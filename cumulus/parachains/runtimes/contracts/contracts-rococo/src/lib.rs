@@ -656,6 +656,23 @@ impl_runtime_apis! {
 		) -> pallet_contracts::GetStorageResult {
 			Contracts::get_storage(address, key)
 		}
+
+		fn trace_extrinsic(
+			header: <Block as BlockT>::Header,
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			index: u32,
+		) -> Option<Vec<pallet_contracts::debug::CallTraceOf<Runtime>>> {
+			Executive::initialize_block(
+				&pallet_contracts::debug::call_tracer::replay_header::<Runtime>(&header),
+			);
+			pallet_contracts::debug::call_tracer::trace_extrinsic::<Runtime, _>(
+				extrinsics,
+				index,
+				|xt| {
+					let _ = Executive::apply_extrinsic(xt);
+				},
+			)
+		}
 	}
 
 	#[cfg(feature = "try-runtime")]
@@ -299,6 +299,7 @@ impl pallet_referenda::Config<FellowshipReferendaInstance> for Runtime {
 		pallet_ranked_collective::EnsureMember<Runtime, FellowshipCollectiveInstance, 1>;
 	type CancelOrigin = FellowshipExperts;
 	type KillOrigin = FellowshipMasters;
+	type UpdateOrigin = EnsureRoot<AccountId>;
 	type Slash = Treasury;
 	type Votes = pallet_ranked_collective::Votes;
 	type Tally = pallet_ranked_collective::TallyOf<Runtime, FellowshipCollectiveInstance>;
@@ -306,6 +307,7 @@ impl pallet_referenda::Config<FellowshipReferendaInstance> for Runtime {
 	type MaxQueued = ConstU32<100>;
 	type UndecidingTimeout = UndecidingTimeout;
 	type AlarmInterval = AlarmInterval;
+	type MaxTracks = ConstU32<32>;
 	type Tracks = TracksInfo;
 	type Preimages = Preimage;
 }
@@ -1605,6 +1605,8 @@ pub mod migrations {
 		paras_registrar::migration::MigrateToV1<Runtime, ()>,
 		pallet_referenda::migration::v1::MigrateV0ToV1<Runtime, ()>,
 		pallet_referenda::migration::v1::MigrateV0ToV1<Runtime, pallet_referenda::Instance2>,
+		pallet_referenda::migration::v2::MigrateV1ToV2<Runtime, ()>,
+		pallet_referenda::migration::v2::MigrateV1ToV2<Runtime, pallet_referenda::Instance2>,
 
 		// Unlock & unreserve Gov1 funds
 
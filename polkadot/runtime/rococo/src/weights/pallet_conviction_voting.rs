@@ -94,6 +94,13 @@ impl<T: frame_system::Config> pallet_conviction_voting::WeightInfo for WeightInf
 			.saturating_add(T::DbWeight::get().reads(7))
 			.saturating_add(T::DbWeight::get().writes(6))
 	}
+	/// Not benchmarked on this runtime yet. Overriding a delegation votes on the poll and withdraws
+	/// the delegated votes from its tally, which is bounded by the measured weights of replacing a
+	/// vote and removing a vote.
+	fn vote_override() -> Weight {
+		<Self as pallet_conviction_voting::WeightInfo>::vote_existing()
+			.saturating_add(<Self as pallet_conviction_voting::WeightInfo>::remove_vote())
+	}
 	/// Storage: ConvictionVoting VotingFor (r:1 w:1)
 	/// Proof: ConvictionVoting VotingFor (max_values: None, max_size: Some(27241), added: 29716, mode: MaxEncodedLen)
 	/// Storage: Referenda ReferendumInfoFor (r:1 w:1)
@@ -124,6 +131,12 @@ impl<T: frame_system::Config> pallet_conviction_voting::WeightInfo for WeightInf
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Not benchmarked on this runtime yet. Removing an override removes the vote from the tally of
+	/// the poll and adds the delegated votes back, which is bounded by the measured weight of
+	/// removing a vote twice.
+	fn remove_override() -> Weight {
+		<Self as pallet_conviction_voting::WeightInfo>::remove_vote().saturating_mul(2)
+	}
 	/// Storage: ConvictionVoting VotingFor (r:2 w:2)
 	/// Proof: ConvictionVoting VotingFor (max_values: None, max_size: Some(27241), added: 29716, mode: MaxEncodedLen)
 	/// Storage: Referenda ReferendumInfoFor (r:512 w:512)
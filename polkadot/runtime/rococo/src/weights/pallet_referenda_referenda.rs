@@ -519,4 +519,16 @@ impl<T: frame_system::Config> pallet_referenda::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Not benchmarked on this runtime yet, uses the estimate of the pallet.
+	fn add_track() -> Weight {
+		<pallet_referenda::weights::SubstrateWeight<T> as pallet_referenda::WeightInfo>::add_track()
+	}
+	/// Not benchmarked on this runtime yet, uses the estimate of the pallet.
+	fn update_track() -> Weight {
+		<pallet_referenda::weights::SubstrateWeight<T> as pallet_referenda::WeightInfo>::update_track()
+	}
+	/// Not benchmarked on this runtime yet, uses the estimate of the pallet.
+	fn remove_track() -> Weight {
+		<pallet_referenda::weights::SubstrateWeight<T> as pallet_referenda::WeightInfo>::remove_track()
+	}
 }
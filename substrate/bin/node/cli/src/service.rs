@@ -119,13 +119,15 @@ pub fn create_extrinsic(
 				period,
 				best_block.saturated_into(),
 			)),
-			frame_system::CheckNonce::<kitchensink_runtime::Runtime>::from(nonce),
+			frame_system::CheckNonce::<kitchensink_runtime::Runtime>::from(nonce).into(),
 			frame_system::CheckWeight::<kitchensink_runtime::Runtime>::new(),
 			pallet_skip_feeless_payment::SkipCheckIfFeeless::from(
 				pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::<
 					kitchensink_runtime::Runtime,
 				>::from(tip, None),
-			),
+			)
+			.into(),
+			frame_system::ReclaimRefTime::<kitchensink_runtime::Runtime, _>::new(),
 		);
 
 	let raw_payload = kitchensink_runtime::SignedPayload::from_raw(
@@ -140,6 +142,7 @@ pub fn create_extrinsic(
 			(),
 			(),
 			(),
+			(),
 		),
 	);
 	let signature = raw_payload.using_encoded(|e| sender.sign(e));
@@ -1036,11 +1039,14 @@ mod tests {
 				let check_tx_version = frame_system::CheckTxVersion::new();
 				let check_genesis = frame_system::CheckGenesis::new();
 				let check_era = frame_system::CheckEra::from(Era::Immortal);
-				let check_nonce = frame_system::CheckNonce::from(index);
+				let check_nonce = frame_system::CheckNonce::from(index).into();
 				let check_weight = frame_system::CheckWeight::new();
-				let tx_payment = pallet_skip_feeless_payment::SkipCheckIfFeeless::from(
-					pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::from(0, None),
+				let tx_payment = pallet_contracts::ChargeSponsor::from(
+					pallet_skip_feeless_payment::SkipCheckIfFeeless::from(
+						pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::from(0, None),
+					),
 				);
+				let reclaim_ref_time = frame_system::ReclaimRefTime::new();
 				let extra = (
 					check_non_zero_sender,
 					check_spec_version,
@@ -1050,11 +1056,22 @@ mod tests {
 					check_nonce,
 					check_weight,
 					tx_payment,
+					reclaim_ref_time,
 				);
 				let raw_payload = SignedPayload::from_raw(
 					function,
 					extra,
-					((), spec_version, transaction_version, genesis_hash, genesis_hash, (), (), ()),
+					(
+						(),
+						spec_version,
+						transaction_version,
+						genesis_hash,
+						genesis_hash,
+						(),
+						(),
+						(),
+						(),
+					),
 				);
 				let signature = raw_payload.using_encoded(|payload| signer.sign(payload));
 				let (function, extra, _) = raw_payload.deconstruct();
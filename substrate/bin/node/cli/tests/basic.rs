@@ -23,16 +23,19 @@ use frame_support::{
 };
 use frame_system::{self, AccountInfo, EventRecord, Phase};
 use sp_core::{storage::well_known_keys, traits::Externalities};
+use sp_keyring::AccountKeyring;
 use sp_runtime::{
-	traits::Hash as HashT, transaction_validity::InvalidTransaction, ApplyExtrinsicResult,
+	traits::Hash as HashT,
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+	ApplyExtrinsicResult,
 };
 
 use kitchensink_runtime::{
 	constants::{currency::*, time::SLOT_DURATION},
-	Balances, CheckedExtrinsic, Header, Runtime, RuntimeCall, RuntimeEvent, System,
-	TransactionPayment, Treasury, UncheckedExtrinsic,
+	Balances, Block, CheckedExtrinsic, Header, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin,
+	System, TransactionPayment, Treasury, UncheckedExtrinsic,
 };
-use node_primitives::{Balance, Hash};
+use node_primitives::{AccountId, Balance, Hash};
 use node_testing::keyring::*;
 use wat;
 
@@ -662,8 +665,10 @@ const CODE_TRANSFER: &str = r#"
 )
 "#;
 
-#[test]
-fn deploying_wasm_contract_should_work() {
+/// A block deploying the [`CODE_TRANSFER`] contract and calling it afterwards.
+///
+/// Returns the block and the address of the contract.
+fn contract_block() -> ((Vec<u8>, Hash), AccountId) {
 	let transfer_code = wat::parse_str(CODE_TRANSFER).unwrap();
 	let transfer_ch = <Runtime as frame_system::Config>::Hashing::hash(&transfer_code);
 
@@ -706,6 +711,12 @@ fn deploying_wasm_contract_should_work() {
 		],
 		(time / SLOT_DURATION).into(),
 	);
+	(b, addr)
+}
+
+#[test]
+fn deploying_wasm_contract_should_work() {
+	let (b, addr) = contract_block();
 
 	let mut t = new_test_ext(compact_code_unwrap());
 
@@ -718,6 +729,98 @@ fn deploying_wasm_contract_should_work() {
 	});
 }
 
+#[test]
+fn tracing_contract_call_after_timestamp_inherent_works() {
+	let (b, addr) = contract_block();
+	let block = Block::decode(&mut &b.0[..]).unwrap();
+
+	// Replay on top of the state of the parent block. BABE needs the slot of the pre-runtime
+	// digest to accept the timestamp set by the inherent.
+	let mut t = new_test_ext(compact_code_unwrap());
+	let r = executor_call(
+		&mut t,
+		"ContractsApi_trace_extrinsic",
+		&(block.header, block.extrinsics, 2u32).encode(),
+	)
+	.0
+	.unwrap();
+	let traces = Option::<Vec<pallet_contracts::debug::CallTraceOf<Runtime>>>::decode(&mut &r[..])
+		.unwrap()
+		.unwrap();
+
+	assert_eq!(traces.len(), 1);
+	assert_eq!(traces[0].kind, pallet_contracts::debug::CallKind::Call);
+	assert_eq!(traces[0].from, Some(charlie()));
+	assert_eq!(traces[0].to, addr);
+	assert_eq!(traces[0].value, 10);
+}
+
+#[test]
+fn unfunded_account_can_make_sponsored_contract_call() {
+	let (b, addr) = contract_block();
+	let mut t = new_test_ext(compact_code_unwrap());
+	executor_call(&mut t, "Core_execute_block", &b.0).0.unwrap();
+
+	let caller: AccountId = AccountKeyring::One.into();
+	let sponsor = pallet_contracts::Pallet::<Runtime>::sponsorship_account(&addr);
+	t.execute_with(|| {
+		assert!(!System::account_exists(&caller));
+		pallet_contracts::Pallet::<Runtime>::set_sponsorship(
+			RuntimeOrigin::signed(addr.clone()),
+			Some(pallet_contracts::Sponsorship {
+				max_fee: 10 * DOLLARS,
+				max_deposit: DOLLARS,
+				max_calls: 1,
+				period: 0,
+			}),
+		)
+		.unwrap();
+		let _ = Balances::deposit_creating(&sponsor, 100 * DOLLARS);
+	});
+
+	let xt = |nonce| {
+		sign(CheckedExtrinsic {
+			signed: Some((caller.clone(), signed_extra(nonce, 0))),
+			function: RuntimeCall::Contracts(pallet_contracts::Call::call_sponsored::<Runtime> {
+				dest: sp_runtime::MultiAddress::Id(addr.clone()),
+				value: 0,
+				gas_limit: Weight::from_parts(500_000_000, 0),
+				storage_deposit_limit: None,
+				data: vec![0x00, 0x01, 0x02, 0x03],
+			}),
+		})
+	};
+	let apply = |t: &mut TestExternalities<_>, xt: UncheckedExtrinsic| {
+		let r = executor_call(t, "BlockBuilder_apply_extrinsic", &vec![].and(&xt)).0.unwrap();
+		ApplyExtrinsicResult::decode(&mut &r[..]).unwrap()
+	};
+
+	executor_call(&mut t, "Core_initialize_block", &vec![].and(&from_block_number(2u32)))
+		.0
+		.unwrap();
+	assert_eq!(apply(&mut t, xt(0)), Ok(Ok(())));
+
+	t.execute_with(|| {
+		// The sponsor paid the fee while the caller only got its nonce stored.
+		assert_eq!(System::account_nonce(&caller), 1);
+		assert_eq!(System::sufficients(&caller), 1);
+		assert_eq!(Balances::total_balance(&caller), 0);
+		assert!(Balances::total_balance(&sponsor) < 100 * DOLLARS);
+		assert!(System::events().iter().any(|r| r.event ==
+			RuntimeEvent::Contracts(pallet_contracts::Event::CallSponsored {
+				contract: addr.clone(),
+				caller: caller.clone(),
+				success: true,
+			})));
+	});
+
+	// The allowance of the caller is used up and it can't pay by itself.
+	assert_eq!(
+		apply(&mut t, xt(1)),
+		Err(TransactionValidityError::Invalid(InvalidTransaction::Payment)),
+	);
+}
+
 #[test]
 fn wasm_big_block_import_fails() {
 	let mut t = new_test_ext(compact_code_unwrap());
@@ -17,8 +17,8 @@
 
 use codec::{Encode, Joiner};
 use frame_support::{
-	dispatch::GetDispatchInfo,
-	traits::Currency,
+	dispatch::{DispatchClass, GetDispatchInfo},
+	traits::{Currency, Get},
 	weights::{constants::ExtrinsicBaseWeight, IdentityFee, WeightToFee},
 };
 use kitchensink_runtime::{
@@ -26,7 +26,7 @@ use kitchensink_runtime::{
 	Balances, CheckedExtrinsic, Multiplier, Runtime, RuntimeCall, TransactionByteFee,
 	TransactionPayment,
 };
-use node_primitives::Balance;
+use node_primitives::{AccountId, Balance};
 use node_testing::keyring::*;
 use sp_runtime::{traits::One, Perbill};
 
@@ -188,6 +188,41 @@ fn transaction_fee_is_correct() {
 	});
 }
 
+#[test]
+fn unused_storage_accesses_are_reclaimed() {
+	let mut t = new_test_ext(compact_code_unwrap());
+	t.insert(<frame_system::BlockHash<Runtime>>::hashed_key_for(0), vec![0u8; 32]);
+
+	// None of the accounts needs to be upgraded, so the benchmarked writes do not happen.
+	let call = RuntimeCall::Balances(pallet_balances::Call::upgrade_accounts {
+		who: (0..100u8).map(|i| AccountId::from([i; 32])).collect(),
+	});
+	let full_weight = call.get_dispatch_info().weight.ref_time() +
+		<Runtime as frame_system::Config>::BlockWeights::get()
+			.get(DispatchClass::Normal)
+			.base_extrinsic
+			.ref_time();
+	let write = <Runtime as frame_system::Config>::DbWeight::get().writes(1).ref_time();
+	let xt = sign(CheckedExtrinsic { signed: Some((alice(), signed_extra(0, 0))), function: call });
+
+	let r = executor_call(&mut t, "Core_initialize_block", &vec![].and(&from_block_number(1u32))).0;
+	assert!(r.is_ok());
+	let normal_ref_time = |t: &mut TestExternalities<_>| {
+		t.execute_with(|| {
+			frame_system::Pallet::<Runtime>::block_weight().get(DispatchClass::Normal).ref_time()
+		})
+	};
+	let before = normal_ref_time(&mut t);
+	let r = executor_call(&mut t, "BlockBuilder_apply_extrinsic", &vec![].and(&xt)).0;
+	assert!(r.is_ok());
+	let consumed = normal_ref_time(&mut t) - before;
+
+	// All accounts were read, only the fee payment and events wrote to storage. The refund is
+	// bounded by the 100 benchmarked writes.
+	assert!(consumed < full_weight - 50 * write);
+	assert!(consumed >= full_weight - 100 * write);
+}
+
 #[test]
 #[should_panic]
 #[cfg(feature = "stress-test")]
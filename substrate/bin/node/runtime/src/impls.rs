@@ -23,14 +23,16 @@ use frame_support::{
 		fungibles::{Balanced, Credit},
 		Currency, OnUnbalanced,
 	},
+	weights::constants::WEIGHT_REF_TIME_PER_SECOND,
 };
 use pallet_alliance::{IdentityVerifier, ProposalIndex, ProposalProvider};
 use pallet_asset_tx_payment::HandleCredit;
+use pallet_contracts::WeightInfo as _;
 use pallet_identity::legacy::IdentityField;
 use sp_std::prelude::*;
 
 use crate::{
-	AccountId, AllianceCollective, AllianceMotion, Assets, Authorship, Balances, Hash,
+	AccountId, AllianceCollective, AllianceMotion, Assets, Authorship, Balances, Contracts, Hash,
 	NegativeImbalance, Runtime, RuntimeCall,
 };
 
@@ -55,6 +57,46 @@ impl HandleCredit<AccountId, Assets> for CreditToBlockAuthor {
 	}
 }
 
+/// Lets contracts authorize the transactions of smart accounts they own.
+///
+/// The contract is called by the smart account with the SCALE encoded message and proof. It
+/// authorizes the transaction by returning `true` without reverting.
+pub struct ContractsVerifier;
+
+impl ContractsVerifier {
+	/// The gas limit of a verification.
+	fn gas_limit() -> Weight {
+		Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND / 100, 256 * 1024)
+	}
+}
+
+impl pallet_smart_accounts::Verifier<AccountId> for ContractsVerifier {
+	fn is_verifier(verifier: &AccountId) -> bool {
+		Contracts::code_hash(verifier).is_some()
+	}
+
+	/// The benchmarked overhead of calling a contract plus the gas limit of the verification.
+	fn weight() -> Weight {
+		<Runtime as pallet_contracts::Config>::WeightInfo::call().saturating_add(Self::gas_limit())
+	}
+
+	fn verify(verifier: &AccountId, account: &AccountId, message: &[u8; 32], proof: &[u8]) -> bool {
+		let result = Contracts::bare_call(
+			account.clone(),
+			verifier.clone(),
+			0,
+			Self::gas_limit(),
+			Some(0),
+			(message, proof).encode(),
+			pallet_contracts::DebugInfo::Skip,
+			pallet_contracts::CollectEvents::Skip,
+			pallet_contracts::Determinism::Enforced,
+		)
+		.result;
+		matches!(result, Ok(retval) if !retval.did_revert() && retval.data == true.encode())
+	}
+}
+
 pub struct AllianceIdentityVerifier;
 impl IdentityVerifier<AccountId> for AllianceIdentityVerifier {
 	fn has_required_identities(who: &AccountId) -> bool {
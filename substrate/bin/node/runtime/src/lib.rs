@@ -44,10 +44,10 @@ use frame_support::{
 			imbalance::ResolveAssetTo, nonfungibles_v2::Inspect, pay::PayAssetFromAccount,
 			GetSalary, PayFromAccount,
 		},
-		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU16, ConstU32, Contains, Currency,
-		EitherOfDiverse, EnsureOriginWithArg, EqualPrivilegeOnly, Imbalance, InsideBoth,
-		InstanceFilter, KeyOwnerProofSystem, LinearStoragePrice, LockIdentifier, Nothing,
-		OnUnbalanced, WithdrawReasons,
+		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU16, ConstU32, ConstU64, Contains,
+		Currency, EitherOfDiverse, EnsureOriginWithArg, EqualPrivilegeOnly, Imbalance, InsideBoth,
+		InstanceFilter, KeyOwnerProofSystem, LinearStoragePrice, LockIdentifier, OnUnbalanced,
+		WithdrawReasons,
 	},
 	weights::{
 		constants::{
@@ -119,7 +119,7 @@ pub use sp_runtime::BuildStorage;
 pub mod impls;
 #[cfg(not(feature = "runtime-benchmarks"))]
 use impls::AllianceIdentityVerifier;
-use impls::{AllianceProposalProvider, Author, CreditToBlockAuthor};
+use impls::{AllianceProposalProvider, Author, ContractsVerifier, CreditToBlockAuthor};
 
 /// Constant values used within the runtime.
 pub mod constants;
@@ -132,6 +132,10 @@ mod voter_bags;
 /// Runtime API definition for assets.
 pub mod assets_api;
 
+/// Storage accesses accounted for by the benchmarked weights.
+pub mod storage_accesses;
+use storage_accesses::RuntimeStorageAccesses;
+
 // Make the WASM binary available.
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
@@ -436,6 +440,49 @@ impl pallet_proxy::Config for Runtime {
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
 }
 
+parameter_types! {
+	// One smart account with its maximum number of guardians, ten session keys of 261 bytes each
+	// and an ongoing recovery of 519 bytes.
+	pub const SmartAccountDeposit: Balance = deposit(12, 575 + 10 * 261 + 519);
+	pub const SmartAccountPriority: TransactionPriority = TransactionPriority::max_value() / 4;
+	pub const SmartAccountRelyingParty: &'static str = "substrate.io";
+}
+
+/// The calls session keys of smart accounts may dispatch.
+///
+/// Only native balance transfers and remarks are allowed: the spending limit of a session key
+/// only meters the native currency, and any call delegating control over the account would let
+/// the key escape its limits.
+pub struct SmartAccountSessionFilter;
+impl Contains<RuntimeCall> for SmartAccountSessionFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::Balances(
+				pallet_balances::Call::transfer_allow_death { .. } |
+					pallet_balances::Call::transfer_keep_alive { .. }
+			) | RuntimeCall::System(
+				frame_system::Call::remark { .. } | frame_system::Call::remark_with_event { .. }
+			)
+		)
+	}
+}
+
+impl pallet_smart_accounts::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type Verifier = ContractsVerifier;
+	type SessionCallFilter = SmartAccountSessionFilter;
+	type UnsignedPriority = SmartAccountPriority;
+	type PasskeyRelyingParty = SmartAccountRelyingParty;
+	type AccountDeposit = SmartAccountDeposit;
+	type MaxSessionKeys = ConstU32<10>;
+	type MaxGuardians = ConstU32<10>;
+	type MaxProofLen = ConstU32<1024>;
+	type WeightInfo = pallet_smart_accounts::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
 	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
 		RuntimeBlockWeights::get().max_block;
@@ -652,6 +699,7 @@ parameter_types! {
 	pub const SessionsPerEra: sp_staking::SessionIndex = 6;
 	pub const BondingDuration: sp_staking::EraIndex = 24 * 28;
 	pub const SlashDeferDuration: sp_staking::EraIndex = 24 * 7; // 1/4 the bonding duration.
+	pub const CommissionChangeDelay: sp_staking::EraIndex = 24 * 2; // 2 days of eras.
 	pub const RewardCurve: &'static PiecewiseLinear<'static> = &REWARD_CURVE;
 	pub const MaxNominators: u32 = 64;
 	pub const MaxControllersInDeprecationBatch: u32 = 5900;
@@ -668,6 +716,38 @@ impl pallet_staking::BenchmarkingConfig for StakingBenchmarkingConfig {
 	type MaxValidators = ConstU32<1000>;
 }
 
+/// Converts staking rewards into other assets through the asset conversion pools against the
+/// native currency.
+pub struct StakingRewardConverter;
+impl pallet_staking::RewardConverter<AccountId, Balance> for StakingRewardConverter {
+	type AssetKind = NativeOrWithId<u32>;
+
+	fn can_convert(asset: &Self::AssetKind) -> bool {
+		AssetConversion::get_reserves(NativeOrWithId::Native, asset.clone()).is_ok()
+	}
+
+	fn swap(
+		who: &AccountId,
+		asset: &Self::AssetKind,
+		amount: Balance,
+		min_out: Balance,
+	) -> Result<Balance, sp_runtime::DispatchError> {
+		<AssetConversion as pallet_asset_conversion::Swap<AccountId>>::swap_exact_tokens_for_tokens(
+			who.clone(),
+			vec![NativeOrWithId::Native, asset.clone()],
+			amount,
+			Some(min_out),
+			who.clone(),
+			true,
+		)
+	}
+
+	fn weight() -> Weight {
+		use pallet_asset_conversion::WeightInfo;
+		<Runtime as pallet_asset_conversion::Config>::WeightInfo::swap_exact_tokens_for_tokens(2)
+	}
+}
+
 impl pallet_staking::Config for Runtime {
 	type Currency = Balances;
 	type CurrencyBalance = Balance;
@@ -677,9 +757,11 @@ impl pallet_staking::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Slash = Treasury; // send the slashed funds to the treasury.
 	type Reward = (); // rewards are minted from the void
+	type RewardConverter = StakingRewardConverter;
 	type SessionsPerEra = SessionsPerEra;
 	type BondingDuration = BondingDuration;
 	type SlashDeferDuration = SlashDeferDuration;
+	type CommissionChangeDelay = CommissionChangeDelay;
 	/// A super-majority of the council can cancel the slash.
 	type AdminOrigin = EitherOfDiverse<
 		EnsureRoot<AccountId>,
@@ -886,6 +968,7 @@ parameter_types! {
 	pub const PostUnbondPoolsWindow: u32 = 4;
 	pub const NominationPoolsPalletId: PalletId = PalletId(*b"py/nopls");
 	pub const MaxPointsToBalance: u8 = 10;
+	pub const PoolsAutoCompoundPeriod: BlockNumber = DAYS;
 }
 
 use sp_runtime::traits::{Convert, Keccak256};
@@ -910,7 +993,7 @@ impl pallet_nomination_pools::Config for Runtime {
 	type RewardCounter = FixedU128;
 	type BalanceToU256 = BalanceToU256;
 	type U256ToBalance = U256ToBalance;
-	type Staking = Staking;
+	type StakeAdapter = pallet_nomination_pools::adapter::TransferStake<Self, Staking>;
 	type PostUnbondingPoolsWindow = PostUnbondPoolsWindow;
 	type MaxMetadataLen = ConstU32<256>;
 	type MaxUnbonding = ConstU32<8>;
@@ -920,6 +1003,7 @@ impl pallet_nomination_pools::Config for Runtime {
 		EnsureRoot<AccountId>,
 		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 4>,
 	>;
+	type AutoCompoundPeriod = PoolsAutoCompoundPeriod;
 }
 
 parameter_types! {
@@ -993,6 +1077,7 @@ impl pallet_referenda::Config for Runtime {
 	type SubmitOrigin = EnsureSigned<AccountId>;
 	type CancelOrigin = EnsureRoot<AccountId>;
 	type KillOrigin = EnsureRoot<AccountId>;
+	type UpdateOrigin = EnsureRoot<AccountId>;
 	type Slash = ();
 	type Votes = pallet_conviction_voting::VotesOf<Runtime>;
 	type Tally = pallet_conviction_voting::TallyOf<Runtime>;
@@ -1000,6 +1085,7 @@ impl pallet_referenda::Config for Runtime {
 	type MaxQueued = ConstU32<100>;
 	type UndecidingTimeout = UndecidingTimeout;
 	type AlarmInterval = AlarmInterval;
+	type MaxTracks = ConstU32<16>;
 	type Tracks = TracksInfo;
 	type Preimages = Preimage;
 }
@@ -1013,6 +1099,7 @@ impl pallet_referenda::Config<pallet_referenda::Instance2> for Runtime {
 	type SubmitOrigin = EnsureSigned<AccountId>;
 	type CancelOrigin = EnsureRoot<AccountId>;
 	type KillOrigin = EnsureRoot<AccountId>;
+	type UpdateOrigin = EnsureRoot<AccountId>;
 	type Slash = ();
 	type Votes = pallet_ranked_collective::Votes;
 	type Tally = pallet_ranked_collective::TallyOf<Runtime>;
@@ -1020,6 +1107,7 @@ impl pallet_referenda::Config<pallet_referenda::Instance2> for Runtime {
 	type MaxQueued = ConstU32<100>;
 	type UndecidingTimeout = UndecidingTimeout;
 	type AlarmInterval = AlarmInterval;
+	type MaxTracks = ConstU32<16>;
 	type Tracks = TracksInfo;
 	type Preimages = Preimage;
 }
@@ -1343,6 +1431,21 @@ impl pallet_tips::Config for Runtime {
 parameter_types! {
 	pub Schedule: pallet_contracts::Schedule<Runtime> = Default::default();
 	pub CodeHashLockupDepositPercent: Perbill = Perbill::from_percent(30);
+	pub ContractsWeightPerEthGas: Weight = Weight::from_parts(10_000, 1);
+}
+
+/// Calls that contracts are allowed to dispatch.
+///
+/// Contracts can only opt in to sponsoring their callers by dispatching
+/// `pallet_contracts::Call::set_sponsorship` from their own account. It only changes the
+/// sponsoring terms of the dispatching contract and moves balance between the contract and its
+/// own sponsorship account, so it can't be used to act on behalf of anyone else. Its call index
+/// is fixed so that contracts relying on it keep working.
+pub struct ContractsCallFilter;
+impl Contains<RuntimeCall> for ContractsCallFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(call, RuntimeCall::Contracts(pallet_contracts::Call::set_sponsorship { .. }))
+	}
 }
 
 impl pallet_contracts::Config for Runtime {
@@ -1351,13 +1454,13 @@ impl pallet_contracts::Config for Runtime {
 	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
-	/// The safest default is to allow no calls at all.
+	/// Only allow contracts to opt in to sponsoring their callers. See [`ContractsCallFilter`].
 	///
 	/// Runtimes should whitelist dispatchables that are allowed to be called from contracts
 	/// and make sure they are stable. Dispatchables exposed to contracts are not allowed to
 	/// change because that would break already deployed contracts. The `Call` structure itself
 	/// is not allowed to change the indices of existing pallets, too.
-	type CallFilter = Nothing;
+	type CallFilter = ContractsCallFilter;
 	type DepositPerItem = dynamic_params::contracts::DepositPerItem;
 	type DepositPerByte = dynamic_params::contracts::DepositPerByte;
 	type DefaultDepositLimit = dynamic_params::contracts::DefaultDepositLimit;
@@ -1384,6 +1487,10 @@ impl pallet_contracts::Config for Runtime {
 	type Environment = ();
 	type ApiVersion = ();
 	type Xcm = ();
+	type AddressMapper = pallet_contracts::DefaultAddressMapper;
+	type ChainId = ConstU64<42>;
+	type WeightPerEthGas = ContractsWeightPerEthGas;
+	type SponsorshipPolicy = pallet_contracts::SponsorValueFreeCalls;
 }
 
 impl pallet_sudo::Config for Runtime {
@@ -1427,13 +1534,16 @@ where
 			frame_system::CheckTxVersion::<Runtime>::new(),
 			frame_system::CheckGenesis::<Runtime>::new(),
 			frame_system::CheckEra::<Runtime>::from(era),
-			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckNonce::<Runtime>::from(nonce).into(),
 			frame_system::CheckWeight::<Runtime>::new(),
-			pallet_skip_feeless_payment::SkipCheckIfFeeless::from(
-				pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::<Runtime>::from(
-					tip, None,
+			pallet_contracts::ChargeSponsor::from(
+				pallet_skip_feeless_payment::SkipCheckIfFeeless::from(
+					pallet_asset_conversion_tx_payment::ChargeAssetTxPayment::<Runtime>::from(
+						tip, None,
+					),
 				),
 			),
+			frame_system::ReclaimRefTime::<Runtime, RuntimeStorageAccesses>::new(),
 		);
 		let raw_payload = SignedPayload::new(call, extra)
 			.map_err(|e| {
@@ -1660,6 +1770,7 @@ impl pallet_assets::Config<Instance1> for Runtime {
 	type Freezer = ();
 	type Extra = ();
 	type CallbackHandle = ();
+	type TransferHook = ();
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 	type RemoveItemsLimit = ConstU32<1000>;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -1689,6 +1800,7 @@ impl pallet_assets::Config<Instance2> for Runtime {
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 	type RemoveItemsLimit = ConstU32<1000>;
 	type CallbackHandle = ();
+	type TransferHook = ();
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = ();
 }
@@ -1731,6 +1843,9 @@ impl pallet_asset_conversion::Config for Runtime {
 	type LiquidityWithdrawalFee = LiquidityWithdrawalFee;
 	type WeightInfo = pallet_asset_conversion::weights::SubstrateWeight<Runtime>;
 	type MaxSwapPathLength = ConstU32<4>;
+	type MaxTicks = ConstU32<64>;
+	type StablePoolOrigin = EnsureRoot<AccountId>;
+	type MaxStableAssets = ConstU32<4>;
 	type MintMinLiquidity = MintMinLiquidity;
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = ();
@@ -2498,6 +2613,9 @@ mod runtime {
 
 	#[runtime::pallet_index(79)]
 	pub type AssetConversionMigration = pallet_asset_conversion_ops;
+
+	#[runtime::pallet_index(80)]
+	pub type SmartAccounts = pallet_smart_accounts;
 }
 
 /// The address format for describing accounts.
@@ -2521,12 +2639,16 @@ pub type SignedExtra = (
 	frame_system::CheckTxVersion<Runtime>,
 	frame_system::CheckGenesis<Runtime>,
 	frame_system::CheckEra<Runtime>,
-	frame_system::CheckNonce<Runtime>,
+	pallet_contracts::CheckSponsoredNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_skip_feeless_payment::SkipCheckIfFeeless<
+	pallet_contracts::ChargeSponsor<
 		Runtime,
-		pallet_asset_conversion_tx_payment::ChargeAssetTxPayment<Runtime>,
+		pallet_skip_feeless_payment::SkipCheckIfFeeless<
+			Runtime,
+			pallet_asset_conversion_tx_payment::ChargeAssetTxPayment<Runtime>,
+		>,
 	>,
+	frame_system::ReclaimRefTime<Runtime, RuntimeStorageAccesses>,
 );
 
 /// Unchecked extrinsic type as expected by this runtime.
@@ -2638,6 +2760,7 @@ mod benches {
 		[pallet_scheduler, Scheduler]
 		[pallet_glutton, Glutton]
 		[pallet_session, SessionBench::<Runtime>]
+		[pallet_smart_accounts, SmartAccounts]
 		[pallet_society, Society]
 		[pallet_staking, Staking]
 		[pallet_state_trie_migration, StateTrieMigration]
@@ -2781,6 +2904,22 @@ impl_runtime_apis! {
 		fn balance_to_points(pool_id: pallet_nomination_pools::PoolId, new_funds: Balance) -> Balance {
 			NominationPools::api_balance_to_points(pool_id, new_funds)
 		}
+
+		fn pool_pending_slash(pool_id: pallet_nomination_pools::PoolId) -> Balance {
+			NominationPools::api_pool_pending_slash(pool_id)
+		}
+
+		fn member_pending_slash(member: AccountId) -> Balance {
+			NominationPools::api_member_pending_slash(member)
+		}
+
+		fn pool_needs_delegate_migration(pool_id: pallet_nomination_pools::PoolId) -> bool {
+			NominationPools::api_pool_needs_delegate_migration(pool_id)
+		}
+
+		fn member_needs_delegate_migration(member: AccountId) -> bool {
+			NominationPools::api_member_needs_delegate_migration(member)
+		}
 	}
 
 	impl pallet_staking_runtime_api::StakingApi<Block, Balance, AccountId> for Runtime {
@@ -2795,6 +2934,10 @@ impl_runtime_apis! {
 		fn pending_rewards(era: sp_staking::EraIndex, account: AccountId) -> bool {
 			Staking::api_pending_rewards(era, account)
 		}
+
+		fn commission_changes(nominator: AccountId) -> Vec<(AccountId, Perbill, sp_staking::EraIndex)> {
+			Staking::api_commission_changes(nominator)
+		}
 	}
 
 	impl sp_consensus_babe::BabeApi<Block> for Runtime {
@@ -2858,6 +3001,15 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl frame_system_rpc_runtime_api::UpgradeDryRunApi<Block, EventRecord> for Runtime {
+		fn dry_run_upgrade() -> frame_system_rpc_runtime_api::UpgradeDryRunReport<EventRecord> {
+			#[cfg(feature = "try-runtime")]
+			return Executive::try_dry_run_runtime_upgrade();
+			#[cfg(not(feature = "try-runtime"))]
+			Executive::dry_run_runtime_upgrade()
+		}
+	}
+
 	impl assets_api::AssetsApi<
 		Block,
 		AccountId,
@@ -2942,6 +3094,63 @@ impl_runtime_apis! {
 				key
 			)
 		}
+
+		fn trace_extrinsic(
+			header: <Block as BlockT>::Header,
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			index: u32,
+		) -> Option<Vec<pallet_contracts::debug::CallTraceOf<Runtime>>> {
+			Executive::initialize_block(
+				&pallet_contracts::debug::call_tracer::replay_header::<Runtime>(&header),
+			);
+			pallet_contracts::debug::call_tracer::trace_extrinsic::<Runtime, _>(
+				extrinsics,
+				index,
+				|xt| {
+					let _ = Executive::apply_extrinsic(xt);
+				},
+			)
+		}
+	}
+
+	impl pallet_contracts::EthApi<Block> for Runtime {
+		fn chain_id() -> u64 {
+			<Runtime as pallet_contracts::Config>::ChainId::get()
+		}
+
+		fn gas_price() -> sp_core::U256 {
+			Contracts::eth_gas_price().into()
+		}
+
+		fn balance(address: sp_core::H160) -> sp_core::U256 {
+			Contracts::eth_balance(&address)
+		}
+
+		fn nonce(address: sp_core::H160) -> sp_core::U256 {
+			Contracts::eth_nonce(&address)
+		}
+
+		fn call(
+			from: sp_core::H160,
+			to: Option<sp_core::H160>,
+			value: sp_core::U256,
+			gas: Option<u64>,
+			data: Vec<u8>,
+		) -> pallet_contracts::eth::EthCallResult {
+			Contracts::eth_call(from, to, value, gas, data)
+		}
+
+		fn transact(
+			payload: Vec<u8>,
+		) -> Result<<Block as BlockT>::Extrinsic, pallet_contracts::eth::EthTransactError> {
+			Contracts::eth_check_transaction(&payload)?;
+			let call = RuntimeCall::Contracts(pallet_contracts::Call::eth_transact { payload });
+			Ok(UncheckedExtrinsic::new_unsigned(call))
+		}
+
+		fn receipts() -> Vec<pallet_contracts::eth::Receipt> {
+			Contracts::eth_receipts()
+		}
 	}
 
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
@@ -2962,6 +3171,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
 	impl pallet_asset_conversion::AssetConversionApi<
 		Block,
 		Balance,
@@ -2979,6 +3189,14 @@ impl_runtime_apis! {
 		fn get_reserves(asset1: NativeOrWithId<u32>, asset2: NativeOrWithId<u32>) -> Option<(Balance, Balance)> {
 			AssetConversion::get_reserves(asset1, asset2).ok()
 		}
+
+		fn quote_best_route_exact_tokens_for_tokens(asset_in: NativeOrWithId<u32>, asset_out: NativeOrWithId<u32>, amount: Balance) -> Option<(Vec<NativeOrWithId<u32>>, Balance)> {
+			AssetConversion::quote_best_route_exact_tokens_for_tokens(asset_in, asset_out, amount)
+		}
+
+		fn quote_best_route_tokens_for_exact_tokens(asset_in: NativeOrWithId<u32>, asset_out: NativeOrWithId<u32>, amount: Balance) -> Option<(Vec<NativeOrWithId<u32>>, Balance)> {
+			AssetConversion::quote_best_route_tokens_for_exact_tokens(asset_in, asset_out, amount)
+		}
 	}
 
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentCallApi<Block, Balance, RuntimeCall>
@@ -3159,6 +3377,7 @@ impl_runtime_apis! {
 	}
 
 	#[cfg(feature = "try-runtime")]
+	#[api_version(2)]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
 			// NOTE: intentional unwrap: we don't want to propagate the error backwards, and want to
@@ -3178,6 +3397,14 @@ impl_runtime_apis! {
 			// have a backtrace here.
 			Executive::try_execute_block(block, state_root_check, signature_check, select).unwrap()
 		}
+
+		fn on_runtime_upgrade_mbms(
+			checks: frame_try_runtime::UpgradeCheckSelect,
+			max_blocks: u32,
+		) -> frame_try_runtime::MbmSimulationReport {
+			// NOTE: intentional unwrap, see `on_runtime_upgrade`.
+			Executive::try_runtime_upgrade_mbms(checks, max_blocks).unwrap()
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -0,0 +1,57 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! The storage accesses that the benchmarked weights of the calls in this runtime account for.
+//!
+//! Used by `frame_system::ReclaimRefTime` to refund the `ref_time` of storage accesses that were
+//! benchmarked but did not happen.
+
+use frame_support::storage::metering::{BenchmarkedStorageAccesses, StorageAccesses};
+
+use crate::RuntimeCall;
+use ::pallet_balances::Call as BalancesCall;
+
+mod pallet_balances;
+
+/// Benchmarked storage accesses of the calls of the pallets with generated counts.
+///
+/// The counts must come from the same benchmarks as the weights used by the runtime for the pallet.
+pub struct RuntimeStorageAccesses;
+
+impl BenchmarkedStorageAccesses<RuntimeCall> for RuntimeStorageAccesses {
+	fn benchmarked_accesses(call: &RuntimeCall) -> Option<StorageAccesses> {
+		match call {
+			RuntimeCall::Balances(call) => Some(match call {
+				BalancesCall::transfer_allow_death { .. } =>
+					pallet_balances::transfer_allow_death(),
+				BalancesCall::force_transfer { .. } => pallet_balances::force_transfer(),
+				BalancesCall::transfer_keep_alive { .. } => pallet_balances::transfer_keep_alive(),
+				BalancesCall::transfer_all { .. } => pallet_balances::transfer_all(),
+				BalancesCall::force_unreserve { .. } => pallet_balances::force_unreserve(),
+				BalancesCall::upgrade_accounts { who } =>
+					pallet_balances::upgrade_accounts(who.len() as u32),
+				// The weight is the maximum of both benchmarks.
+				BalancesCall::force_set_balance { .. } =>
+					pallet_balances::force_set_balance_creating()
+						.max(pallet_balances::force_set_balance_killing()),
+				BalancesCall::force_adjust_total_issuance { .. } =>
+					pallet_balances::force_adjust_total_issuance(),
+				_ => return None,
+			}),
+			_ => None,
+		}
+	}
+}
@@ -0,0 +1,64 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Storage accesses for `pallet_balances`
+//!
+//! THE COUNTS WERE TAKEN FROM THE BENCHMARK RUN OF `substrate/frame/balances/src/weights.rs`
+//! DATE: 2024-04-08, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! WASM-EXECUTION: `Compiled`, CHAIN: `Some("dev")`, DB CACHE: `1024`
+//!
+//! The storage reads and writes counted by each benchmark. Regenerate together with the weights by
+//! running the benchmarks of `pallet_balances` with
+//! `--output=./substrate/bin/node/runtime/src/storage_accesses/pallet_balances.rs` and
+//! `--template=./substrate/.maintain/frame-storage-accesses-template.hbs`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::storage::metering::StorageAccesses;
+
+pub fn transfer_allow_death() -> StorageAccesses {
+	StorageAccesses::from_parts(1, 1)
+}
+pub fn transfer_keep_alive() -> StorageAccesses {
+	StorageAccesses::from_parts(1, 1)
+}
+pub fn force_set_balance_creating() -> StorageAccesses {
+	StorageAccesses::from_parts(1, 1)
+}
+pub fn force_set_balance_killing() -> StorageAccesses {
+	StorageAccesses::from_parts(1, 1)
+}
+pub fn force_transfer() -> StorageAccesses {
+	StorageAccesses::from_parts(2, 2)
+}
+pub fn transfer_all() -> StorageAccesses {
+	StorageAccesses::from_parts(1, 1)
+}
+pub fn force_unreserve() -> StorageAccesses {
+	StorageAccesses::from_parts(1, 1)
+}
+/// The range of component `u` is `[1, 1000]`.
+pub fn upgrade_accounts(u: u32, ) -> StorageAccesses {
+	StorageAccesses::from_parts(0, 0)
+		.saturating_add(StorageAccesses::from_parts(1, 0).saturating_mul(u.into()))
+		.saturating_add(StorageAccesses::from_parts(0, 1).saturating_mul(u.into()))
+}
+pub fn force_adjust_total_issuance() -> StorageAccesses {
+	StorageAccesses::from_parts(0, 0)
+}
@@ -92,4 +92,18 @@ pub trait CallExecutor<B: BlockT>: RuntimeVersionOf {
 		method: &str,
 		call_data: &[u8],
 	) -> Result<(Vec<u8>, StorageProof), sp_blockchain::Error>;
+
+	/// Prove the execution of the given `method` with `code` as runtime.
+	///
+	/// The method is executed on top of the state in the block of given hash, but `code` is used
+	/// instead of the runtime code stored in this state.
+	///
+	/// No changes are made.
+	fn prove_execution_with_code(
+		&self,
+		at_hash: B::Hash,
+		code: &[u8],
+		method: &str,
+		call_data: &[u8],
+	) -> Result<(Vec<u8>, StorageProof), sp_blockchain::Error>;
 }
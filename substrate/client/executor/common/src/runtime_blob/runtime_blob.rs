@@ -29,7 +29,7 @@ pub struct RuntimeBlob(BlobKind);
 #[derive(Clone)]
 enum BlobKind {
 	WebAssembly(Module),
-	PolkaVM(polkavm::ProgramBlob<'static>),
+	PolkaVM(polkavm::ProgramBlob),
 }
 
 impl RuntimeBlob {
@@ -52,9 +52,9 @@ impl RuntimeBlob {
 	pub fn new(raw_blob: &[u8]) -> Result<Self, WasmError> {
 		if raw_blob.starts_with(b"PVM\0") {
 			if crate::is_polkavm_enabled() {
-				return Ok(Self(BlobKind::PolkaVM(
-					polkavm::ProgramBlob::parse(raw_blob)?.into_owned(),
-				)));
+				return Ok(Self(BlobKind::PolkaVM(polkavm::ProgramBlob::parse(
+					raw_blob.to_vec().into(),
+				)?)));
 			} else {
 				return Err(WasmError::Other("expected a WASM runtime blob, found a PolkaVM runtime blob; set the 'SUBSTRATE_ENABLE_POLKAVM' environment variable to enable the experimental PolkaVM-based executor".to_string()));
 			}
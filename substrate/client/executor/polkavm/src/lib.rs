@@ -16,7 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use polkavm::{Caller, Reg};
+use polkavm::{CallError, Caller, Reg};
 use sc_executor_common::{
 	error::{Error, WasmError},
 	wasm_runtime::{AllocationStats, WasmInstance, WasmModule},
@@ -26,10 +26,10 @@ use sp_wasm_interface::{
 };
 
 #[repr(transparent)]
-pub struct InstancePre(polkavm::InstancePre<()>);
+pub struct InstancePre(polkavm::InstancePre<(), String>);
 
 #[repr(transparent)]
-pub struct Instance(polkavm::Instance<()>);
+pub struct Instance(polkavm::Instance<(), String>);
 
 impl WasmModule for InstancePre {
 	fn new_instance(&self) -> Result<Box<dyn WasmInstance>, Error> {
@@ -43,7 +43,13 @@ impl WasmInstance for Instance {
 		name: &str,
 		raw_data: &[u8],
 	) -> (Result<Vec<u8>, Error>, Option<AllocationStats>) {
-		let Some(method_index) = self.0.module().lookup_export(name) else {
+		let Some(pc) = self
+			.0
+			.module()
+			.exports()
+			.find(|export| export.symbol().as_bytes() == name.as_bytes())
+			.map(|export| export.program_counter())
+		else {
 			return (
 				Err(format!("cannot call into the runtime: export not found: '{name}'").into()),
 				None,
@@ -58,22 +64,12 @@ impl WasmInstance for Instance {
 		};
 
 		// TODO: This will leak guest memory; find a better solution.
-		let mut state_args = polkavm::StateArgs::new();
 
 		// Make sure the memory is cleared...
-		state_args.reset_memory(true);
+		self.0.reset_memory();
 		// ...and allocate space for the input payload.
-		state_args.sbrk(raw_data_length);
-
-		match self.0.update_state(state_args) {
-			Ok(()) => {},
-			Err(polkavm::ExecutionError::Trap(trap)) => {
-				return (Err(format!("call into the runtime method '{name}' failed: failed to prepare the guest's memory: {trap}").into()), None);
-			},
-			Err(polkavm::ExecutionError::Error(error)) => {
-				return (Err(format!("call into the runtime method '{name}' failed: failed to prepare the guest's memory: {error}").into()), None);
-			},
-			Err(polkavm::ExecutionError::OutOfGas) => unreachable!("gas metering is never enabled"),
+		if self.0.sbrk(raw_data_length).is_none() {
+			return (Err(format!("call into the runtime method '{name}' failed: failed to prepare the guest's memory").into()), None);
 		}
 
 		// Grab the address of where the guest's heap starts; that's where we've just allocated
@@ -84,35 +80,35 @@ impl WasmInstance for Instance {
 			return (Err(format!("call into the runtime method '{name}': failed to write the input payload into guest memory: {error}").into()), None);
 		}
 
-		let mut state = ();
-		let mut call_args = polkavm::CallArgs::new(&mut state, method_index);
-		call_args.args_untyped(&[data_pointer, raw_data_length]);
-
-		match self.0.call(Default::default(), call_args) {
+		match self.0.call_typed(&mut (), pc, (data_pointer, raw_data_length)) {
 			Ok(()) => {},
-			Err(polkavm::ExecutionError::Trap(trap)) => {
+			Err(CallError::Trap) => {
 				return (
-					Err(format!("call into the runtime method '{name}' failed: {trap}").into()),
+					Err(format!("call into the runtime method '{name}' failed: trap").into()),
 					None,
 				);
 			},
-			Err(polkavm::ExecutionError::Error(error)) => {
+			Err(CallError::Error(error)) => {
 				return (
 					Err(format!("call into the runtime method '{name}' failed: {error}").into()),
 					None,
 				);
 			},
-			Err(polkavm::ExecutionError::OutOfGas) => unreachable!("gas metering is never enabled"),
+			Err(CallError::User(error)) => {
+				return (
+					Err(format!("call into the runtime method '{name}' failed: {error}").into()),
+					None,
+				);
+			},
+			Err(CallError::NotEnoughGas) => unreachable!("gas metering is never enabled"),
 		}
 
-		let result_pointer = self.0.get_reg(Reg::A0);
-		let result_length = self.0.get_reg(Reg::A1);
-		let output = match self.0.read_memory_into_vec(result_pointer, result_length) {
-			Ok(output) => output,
-			Err(error) => {
-				return (Err(format!("call into the runtime method '{name}' failed: failed to read the return payload: {error}").into()), None)
-			},
-		};
+		let result_pointer = self.0.reg(Reg::A0);
+		let result_length = self.0.reg(Reg::A1);
+		let mut output = vec![0; result_length as usize];
+		if let Err(error) = self.0.read_memory_into(result_pointer, &mut output[..]) {
+			return (Err(format!("call into the runtime method '{name}' failed: failed to read the return payload: {error}").into()), None)
+		}
 
 		(Ok(output), None)
 	}
@@ -127,20 +123,25 @@ impl<'r, 'a> FunctionContext for Context<'r, 'a> {
 		dest: &mut [u8],
 	) -> sp_wasm_interface::Result<()> {
 		self.0
-			.read_memory_into_slice(u32::from(address), dest)
+			.instance
+			.read_memory_into(u32::from(address), dest)
 			.map_err(|error| error.to_string())
 			.map(|_| ())
 	}
 
 	fn write_memory(&mut self, address: Pointer<u8>, data: &[u8]) -> sp_wasm_interface::Result<()> {
-		self.0.write_memory(u32::from(address), data).map_err(|error| error.to_string())
+		self.0
+			.instance
+			.write_memory(u32::from(address), data)
+			.map_err(|error| error.to_string())
 	}
 
 	fn allocate_memory(&mut self, size: WordSize) -> sp_wasm_interface::Result<Pointer<u8>> {
-		let pointer = self.0.sbrk(0).expect("fetching the current heap pointer never fails");
+		let pointer =
+			self.0.instance.sbrk(0).expect("fetching the current heap pointer never fails");
 
 		// TODO: This will leak guest memory; find a better solution.
-		self.0.sbrk(size).ok_or_else(|| String::from("allocation failed"))?;
+		self.0.instance.sbrk(size).ok_or_else(|| String::from("allocation failed"))?;
 
 		Ok(Pointer::new(pointer))
 	}
@@ -155,37 +156,34 @@ impl<'r, 'a> FunctionContext for Context<'r, 'a> {
 	}
 }
 
-fn call_host_function(
-	caller: &mut Caller<()>,
-	function: &dyn Function,
-) -> Result<(), polkavm::Trap> {
+fn call_host_function(caller: &mut Caller<()>, function: &dyn Function) -> Result<(), String> {
 	let mut args = [Value::I64(0); Reg::ARG_REGS.len()];
 	let mut nth_reg = 0;
 	for (nth_arg, kind) in function.signature().args.iter().enumerate() {
 		match kind {
 			ValueType::I32 => {
-				args[nth_arg] = Value::I32(caller.get_reg(Reg::ARG_REGS[nth_reg]) as i32);
+				args[nth_arg] = Value::I32(caller.instance.reg(Reg::ARG_REGS[nth_reg]) as i32);
 				nth_reg += 1;
 			},
 			ValueType::F32 => {
-				args[nth_arg] = Value::F32(caller.get_reg(Reg::ARG_REGS[nth_reg]));
+				args[nth_arg] = Value::F32(caller.instance.reg(Reg::ARG_REGS[nth_reg]));
 				nth_reg += 1;
 			},
 			ValueType::I64 => {
-				let value_lo = caller.get_reg(Reg::ARG_REGS[nth_reg]);
+				let value_lo = caller.instance.reg(Reg::ARG_REGS[nth_reg]);
 				nth_reg += 1;
 
-				let value_hi = caller.get_reg(Reg::ARG_REGS[nth_reg]);
+				let value_hi = caller.instance.reg(Reg::ARG_REGS[nth_reg]);
 				nth_reg += 1;
 
 				args[nth_arg] =
 					Value::I64((u64::from(value_lo) | (u64::from(value_hi) << 32)) as i64);
 			},
 			ValueType::F64 => {
-				let value_lo = caller.get_reg(Reg::ARG_REGS[nth_reg]);
+				let value_lo = caller.instance.reg(Reg::ARG_REGS[nth_reg]);
 				nth_reg += 1;
 
-				let value_hi = caller.get_reg(Reg::ARG_REGS[nth_reg]);
+				let value_hi = caller.instance.reg(Reg::ARG_REGS[nth_reg]);
 				nth_reg += 1;
 
 				args[nth_arg] = Value::F64(u64::from(value_lo) | (u64::from(value_hi) << 32));
@@ -204,26 +202,26 @@ fn call_host_function(
 	{
 		Ok(value) => value,
 		Err(error) => {
-			log::warn!("Call into the host function '{}' failed: {error}", function.name());
-			return Err(polkavm::Trap::default());
+			let name = function.name();
+			return Err(format!("call into the host function '{name}' failed: {error}"))
 		},
 	};
 
 	if let Some(value) = value {
 		match value {
 			Value::I32(value) => {
-				caller.set_reg(Reg::A0, value as u32);
+				caller.instance.set_reg(Reg::A0, value as u32);
 			},
 			Value::F32(value) => {
-				caller.set_reg(Reg::A0, value);
+				caller.instance.set_reg(Reg::A0, value);
 			},
 			Value::I64(value) => {
-				caller.set_reg(Reg::A0, value as u32);
-				caller.set_reg(Reg::A1, (value >> 32) as u32);
+				caller.instance.set_reg(Reg::A0, value as u32);
+				caller.instance.set_reg(Reg::A1, (value >> 32) as u32);
 			},
 			Value::F64(value) => {
-				caller.set_reg(Reg::A0, value as u32);
-				caller.set_reg(Reg::A1, (value >> 32) as u32);
+				caller.instance.set_reg(Reg::A0, value as u32);
+				caller.instance.set_reg(Reg::A1, (value >> 32) as u32);
 			},
 		}
 	}
@@ -250,10 +248,13 @@ where
 		},
 	};
 
-	let module = polkavm::Module::from_blob(&engine, &polkavm::ModuleConfig::default(), blob)?;
-	let mut linker = polkavm::Linker::new(&engine);
+	let module =
+		polkavm::Module::from_blob(&engine, &polkavm::ModuleConfig::default(), blob.clone())?;
+	let mut linker = polkavm::Linker::new();
 	for function in H::host_functions() {
-		linker.func_new(function.name(), |mut caller| call_host_function(&mut caller, function))?;
+		linker.define_untyped(function.name(), |mut caller: Caller<()>| {
+			call_host_function(&mut caller, function)
+		})?;
 	}
 
 	let instance_pre = linker.instantiate_pre(&module)?;
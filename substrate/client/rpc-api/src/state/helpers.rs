@@ -30,3 +30,15 @@ pub struct ReadProof<Hash> {
 	/// A proof used to prove that storage entries are included in the storage trie
 	pub proof: Vec<Bytes>,
 }
+
+/// Result of a runtime upgrade dry-run returned by the RPC
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpgradeDryRun<Hash> {
+	/// Block hash on top of which the upgrade was dry-run
+	pub at: Hash,
+	/// SCALE encoded `UpgradeDryRunReport` returned by the new runtime
+	pub report: Bytes,
+	/// Encoded size of the storage proof of the upgrade in bytes
+	pub proof_size: u64,
+}
@@ -28,7 +28,7 @@ use sp_version::RuntimeVersion;
 pub mod error;
 pub mod helpers;
 
-pub use self::helpers::ReadProof;
+pub use self::helpers::{ReadProof, UpgradeDryRun};
 pub use error::Error;
 
 /// Substrate state API
@@ -299,4 +299,50 @@ pub trait StateApi<Hash> {
 		storage_keys: Option<String>,
 		methods: Option<String>,
 	) -> Result<sp_rpc::tracing::TraceBlockResponse, Error>;
+
+	/// The `traceBlockProofSize` RPC re-executes a single block with storage proof recording
+	/// enabled and attributes the proof size to the spans executing the block.
+	///
+	/// Every returned span contains the growth of the estimated storage proof size while the span
+	/// was entered, the keys that were added to the proof and the keys that were written. Keys are
+	/// mapped to their `Pallet::Item` names using the runtime metadata. All values are inclusive,
+	/// so the contribution of e.g. a single call can be found in its `apply_extrinsic` span.
+	///
+	/// ## Node requirements
+	///
+	/// The same as for [`state_traceBlock`](StateApiServer::trace_block). Per hook spans require a
+	/// runtime compiled with `frame-executive/proof-size-tracing`.
+	///
+	/// ### Params
+	///
+	/// - `block` (param index 0): Hash of the block to trace.
+	/// - `targets` (param index 1): String of comma separated (no spaces) targets, matched by
+	/// prefix. Defaults to `pallet,frame,state`.
+	#[method(name = "state_traceBlockProofSize", blocking)]
+	fn trace_block_proof_size(
+		&self,
+		block: Hash,
+		targets: Option<String>,
+	) -> Result<sp_rpc::tracing::TraceBlockProofSizeResponse, Error>;
+
+	/// Dry-run a runtime upgrade to `code` on top of the state of a block.
+	///
+	/// Calls `UpgradeDryRunApi_dry_run_upgrade` of the new runtime with storage proof recording
+	/// enabled. This runs the runtime upgrade and the hooks of the block after it, so the weight
+	/// and the proof size of the upgrade can be checked against the block limits before the
+	/// upgrade is enacted. All changes are discarded.
+	///
+	/// The new runtime must implement `frame_system_rpc_runtime_api::UpgradeDryRunApi`.
+	///
+	/// ### Params
+	///
+	/// - `code` (param index 0): The code of the new runtime.
+	/// - `hash` (param index 1): Hash of the block to dry-run the upgrade on top of. Defaults to
+	/// the best block.
+	#[method(name = "state_dryRunRuntimeUpgrade", blocking)]
+	fn dry_run_runtime_upgrade(
+		&self,
+		code: Bytes,
+		hash: Option<Hash>,
+	) -> Result<UpgradeDryRun<Hash>, Error>;
 }
@@ -148,6 +148,20 @@ where
 		methods: Option<String>,
 	) -> Result<sp_rpc::tracing::TraceBlockResponse, Error>;
 
+	/// Trace the storage proof size of a block
+	fn trace_block_proof_size(
+		&self,
+		block: Block::Hash,
+		targets: Option<String>,
+	) -> Result<sp_rpc::tracing::TraceBlockProofSizeResponse, Error>;
+
+	/// Dry-run a runtime upgrade to `code`
+	fn dry_run_runtime_upgrade(
+		&self,
+		block: Option<Block::Hash>,
+		code: Bytes,
+	) -> Result<UpgradeDryRun<Block::Hash>, Error>;
+
 	/// New runtime version subscription
 	fn subscribe_runtime_version(&self, pending: PendingSubscriptionSink);
 
@@ -323,6 +337,32 @@ where
 			.map_err(Into::into)
 	}
 
+	/// Re-execute the given block with proof recording and attribute the proof size to the spans
+	/// of the tracing targets given in `targets`.
+	///
+	/// Note: requires the node to run with `--rpc-methods=Unsafe`.
+	/// Note: requires runtimes compiled with wasm tracing support, `--features with-tracing`.
+	fn trace_block_proof_size(
+		&self,
+		block: Block::Hash,
+		targets: Option<String>,
+	) -> Result<sp_rpc::tracing::TraceBlockProofSizeResponse, Error> {
+		self.deny_unsafe.check_if_safe()?;
+		self.backend.trace_block_proof_size(block, targets).map_err(Into::into)
+	}
+
+	/// Dry-run a runtime upgrade to `code` with storage proof recording.
+	///
+	/// Note: requires the node to run with `--rpc-methods=Unsafe`.
+	fn dry_run_runtime_upgrade(
+		&self,
+		code: Bytes,
+		block: Option<Block::Hash>,
+	) -> Result<UpgradeDryRun<Block::Hash>, Error> {
+		self.deny_unsafe.check_if_safe()?;
+		self.backend.dry_run_runtime_upgrade(block, code).map_err(Into::into)
+	}
+
 	fn subscribe_runtime_version(&self, pending: PendingSubscriptionSink) {
 		self.backend.subscribe_runtime_version(pending)
 	}
@@ -30,13 +30,14 @@ use crate::{
 	DenyUnsafe, SubscriptionTaskExecutor,
 };
 
+use codec::Encode;
 use futures::{future, stream, StreamExt};
 use jsonrpsee::{core::async_trait, types::ErrorObject, PendingSubscriptionSink};
 use sc_client_api::{
 	Backend, BlockBackend, BlockchainEvents, CallExecutor, ExecutorProvider, ProofProvider,
 	StorageProvider,
 };
-use sc_rpc_api::state::ReadProof;
+use sc_rpc_api::state::{ReadProof, UpgradeDryRun};
 use sp_api::{CallApiAt, Metadata, ProvideRuntimeApi};
 use sp_blockchain::{
 	CachedHeaderMetadata, Error as ClientError, HeaderBackend, HeaderMetadata,
@@ -477,6 +478,40 @@ where
 		.trace_block()
 		.map_err(|e| invalid_block::<Block>(block, None, e.to_string()))
 	}
+
+	fn trace_block_proof_size(
+		&self,
+		block: Block::Hash,
+		targets: Option<String>,
+	) -> std::result::Result<sp_rpc::tracing::TraceBlockProofSizeResponse, Error> {
+		sc_tracing::block::BlockExecutor::new(self.client.clone(), block, targets, None, None)
+			.trace_block_proof_size()
+			.map_err(|e| invalid_block::<Block>(block, None, e.to_string()))
+	}
+
+	fn dry_run_runtime_upgrade(
+		&self,
+		block: Option<Block::Hash>,
+		code: Bytes,
+	) -> std::result::Result<UpgradeDryRun<Block::Hash>, Error> {
+		self.block_or_best(block)
+			.and_then(|block| {
+				self.client
+					.executor()
+					.prove_execution_with_code(
+						block,
+						&code,
+						"UpgradeDryRunApi_dry_run_upgrade",
+						&[],
+					)
+					.map(|(report, proof)| UpgradeDryRun {
+						at: block,
+						report: report.into(),
+						proof_size: proof.encoded_size() as u64,
+					})
+			})
+			.map_err(client_err)
+	}
 }
 
 impl<BE, Block, Client> ChildStateBackend<Block, Client> for FullState<BE, Block, Client>
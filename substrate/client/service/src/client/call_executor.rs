@@ -22,7 +22,7 @@ use sc_client_api::{
 };
 use sc_executor::{RuntimeVersion, RuntimeVersionOf};
 use sp_api::ProofRecorder;
-use sp_core::traits::{CallContext, CodeExecutor, RuntimeCode};
+use sp_core::traits::{CallContext, CodeExecutor, RuntimeCode, WrappedRuntimeCode};
 use sp_externalities::Extensions;
 use sp_runtime::{
 	generic::BlockId,
@@ -294,6 +294,41 @@ where
 		)
 		.map_err(Into::into)
 	}
+
+	fn prove_execution_with_code(
+		&self,
+		at_hash: Block::Hash,
+		code: &[u8],
+		method: &str,
+		call_data: &[u8],
+	) -> sp_blockchain::Result<(Vec<u8>, StorageProof)> {
+		let at_number =
+			self.backend.blockchain().expect_block_number_from_id(&BlockId::Hash(at_hash))?;
+		let state = self.backend.state_at(at_hash)?;
+
+		let trie_backend = state.as_trie_backend();
+
+		// Keep the on-chain heap pages, only the code is replaced.
+		let state_runtime_code = sp_state_machine::backend::BackendRuntimeCode::new(trie_backend);
+		let on_chain_code =
+			state_runtime_code.runtime_code().map_err(sp_blockchain::Error::RuntimeCode)?;
+		let runtime_code = RuntimeCode {
+			code_fetcher: &WrappedRuntimeCode(code.into()),
+			heap_pages: on_chain_code.heap_pages,
+			hash: sp_core::blake2_256(code).to_vec(),
+		};
+
+		sp_state_machine::prove_execution_on_trie_backend(
+			trie_backend,
+			&mut Default::default(),
+			&self.executor,
+			method,
+			call_data,
+			&runtime_code,
+			&mut self.execution_extensions.extensions(at_hash, at_number),
+		)
+		.map_err(Into::into)
+	}
 }
 
 impl<B, E, Block> RuntimeVersionOf for LocalCallExecutor<Block, B, E>
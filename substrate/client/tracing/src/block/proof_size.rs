@@ -0,0 +1,472 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Attribution of the storage proof size of a block to the spans executing it.
+//!
+//! The block is re-executed with proof recording enabled. Whenever a span is entered or exited the
+//! estimated proof size is sampled, the difference between both samples is attributed to the span.
+//! Storage accesses are collected from the `state` events emitted by the externalities: the first
+//! read of a key that was not written before in the block is a read from the backend, and therefore
+//! adds the key to the proof.
+
+use std::{
+	collections::{BTreeSet, HashMap, HashSet},
+	sync::atomic::{AtomicU64, Ordering},
+	time::Instant,
+};
+
+use codec::Decode;
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use parking_lot::Mutex;
+use tracing::{
+	dispatcher,
+	span::{Attributes, Id, Record},
+	Dispatch, Level, Subscriber,
+};
+
+use super::{
+	block_id_as_string, patch_and_filter, BlockExecutor, Error, TraceBlockResult, DEFAULT_TARGETS,
+	REQUIRED_EVENT_FIELD, TRACE_TARGET,
+};
+use crate::{SpanDatum, Values};
+use sc_client_api::BlockBackend;
+use sp_api::{ApiExt, Core, Metadata, ProofRecorder, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::twox_128, hexdisplay::HexDisplay};
+use sp_rpc::tracing::{
+	BlockProofSizeTrace, ProofSizeSpan, StorageAccess, TraceBlockProofSizeResponse,
+};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use sp_tracing::WASM_TRACE_IDENTIFIER;
+
+/// Target of the events emitted by the externalities on storage access.
+const STATE_TARGET: &str = "state";
+/// Event methods that write to the storage.
+const WRITE_METHODS: [&str; 3] = ["Put", "Append", "ClearPrefix"];
+/// Event methods that read from the storage.
+const READ_METHODS: [&str; 3] = ["Get", "Hash", "Exists"];
+
+struct ProofSizeSpanDatum {
+	span: SpanDatum,
+	entered_size: usize,
+	proof_size: usize,
+	reads: BTreeSet<String>,
+	writes: BTreeSet<String>,
+}
+
+struct ProofSizeSubscriber<Block: BlockT> {
+	targets: Vec<(String, Level)>,
+	recorder: ProofRecorder<Block>,
+	next_id: AtomicU64,
+	spans: Mutex<HashMap<Id, ProofSizeSpanDatum>>,
+	entered: Mutex<Vec<Id>>,
+	/// The hex encoded keys that were accessed so far in the block.
+	accessed: Mutex<HashSet<String>>,
+}
+
+impl<Block: BlockT> ProofSizeSubscriber<Block> {
+	fn new(targets: &str, recorder: ProofRecorder<Block>) -> Self {
+		let mut targets: Vec<_> = targets.split(',').map(crate::parse_target).collect();
+		// Ensure that WASM traces are always enabled
+		// Filtering happens when decoding the actual target / level
+		targets.push((WASM_TRACE_IDENTIFIER.to_owned(), Level::TRACE));
+		// The root span covers the whole block.
+		targets.push((TRACE_TARGET.to_owned(), Level::TRACE));
+		ProofSizeSubscriber {
+			targets,
+			recorder,
+			next_id: AtomicU64::new(1),
+			spans: Mutex::new(HashMap::new()),
+			entered: Mutex::new(Vec::new()),
+			accessed: Mutex::new(HashSet::new()),
+		}
+	}
+
+	/// Attribute the access of `key` with `method` to all spans that are currently entered.
+	fn note_access(&self, method: &str, key: String) {
+		let is_write = WRITE_METHODS.contains(&method);
+		let is_read = READ_METHODS.contains(&method);
+		if !is_write && !is_read {
+			return
+		}
+		// Only the first access of a key can add it to the proof. Keys written before are read
+		// from the overlay.
+		let first_access = self.accessed.lock().insert(key.clone());
+		if is_read && !first_access {
+			return
+		}
+
+		let entered = self.entered.lock();
+		let mut spans = self.spans.lock();
+		for id in entered.iter() {
+			if let Some(s) = spans.get_mut(id) {
+				if is_write {
+					s.writes.insert(key.clone());
+				} else {
+					s.reads.insert(key.clone());
+				}
+			}
+		}
+	}
+}
+
+impl<Block: BlockT> Subscriber for ProofSizeSubscriber<Block> {
+	fn enabled(&self, metadata: &tracing::Metadata<'_>) -> bool {
+		if !metadata.is_span() {
+			return metadata.target() == STATE_TARGET &&
+				metadata.fields().field(REQUIRED_EVENT_FIELD).is_some()
+		}
+		self.targets.iter().any(|(target, level)| {
+			metadata.level() <= level && metadata.target().starts_with(target)
+		})
+	}
+
+	fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+		let id = Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed));
+		let mut values = Values::default();
+		attrs.record(&mut values);
+		let parent_id = if attrs.is_contextual() {
+			self.entered.lock().last().cloned()
+		} else {
+			attrs.parent().cloned()
+		};
+		let span = SpanDatum {
+			id: id.clone(),
+			parent_id,
+			name: attrs.metadata().name().to_owned(),
+			target: attrs.metadata().target().to_owned(),
+			level: *attrs.metadata().level(),
+			line: attrs.metadata().line().unwrap_or(0),
+			start_time: Instant::now(),
+			values,
+			overall_time: Default::default(),
+		};
+
+		self.spans.lock().insert(
+			id.clone(),
+			ProofSizeSpanDatum {
+				span,
+				entered_size: 0,
+				proof_size: 0,
+				reads: Default::default(),
+				writes: Default::default(),
+			},
+		);
+		id
+	}
+
+	fn record(&self, span: &Id, values: &Record<'_>) {
+		if let Some(s) = self.spans.lock().get_mut(span) {
+			values.record(&mut s.span.values);
+		}
+	}
+
+	fn record_follows_from(&self, _span: &Id, _follows: &Id) {
+		// Causal relationships between spans do not affect the proof size attribution.
+	}
+
+	fn event(&self, event: &tracing::Event<'_>) {
+		let mut values = Values::default();
+		event.record(&mut values);
+		let Some(method) = values.string_values.remove(REQUIRED_EVENT_FIELD) else { return };
+		let key = values
+			.string_values
+			.remove("key")
+			.or_else(|| values.string_values.remove("prefix"));
+		if let Some(key) = key {
+			self.note_access(&method, key);
+		}
+	}
+
+	fn enter(&self, id: &Id) {
+		let size = self.recorder.estimate_encoded_size();
+		if let Some(s) = self.spans.lock().get_mut(id) {
+			s.entered_size = size;
+		}
+		self.entered.lock().push(id.clone());
+	}
+
+	fn exit(&self, id: &Id) {
+		let size = self.recorder.estimate_encoded_size();
+		if let Some(s) = self.spans.lock().get_mut(id) {
+			s.proof_size += size.saturating_sub(s.entered_size);
+		}
+		let mut entered = self.entered.lock();
+		if let Some(pos) = entered.iter().rposition(|e| e == id) {
+			entered.remove(pos);
+		}
+	}
+}
+
+/// Maps hex encoded storage prefixes to the names of the storage items.
+#[derive(Default)]
+struct StorageNames {
+	/// `twox128(pallet) ++ twox128(item)` to `Pallet::Item`.
+	items: HashMap<String, String>,
+	/// `twox128(pallet)` to `Pallet`.
+	pallets: HashMap<String, String>,
+}
+
+impl StorageNames {
+	fn from_metadata(metadata: &[u8]) -> Option<Self> {
+		let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..]).ok()?;
+		let storages: Vec<(String, Vec<String>)> = match metadata.1 {
+			RuntimeMetadata::V14(m) => m
+				.pallets
+				.into_iter()
+				.filter_map(|p| p.storage)
+				.map(|s| (s.prefix, s.entries.into_iter().map(|e| e.name).collect()))
+				.collect(),
+			RuntimeMetadata::V15(m) => m
+				.pallets
+				.into_iter()
+				.filter_map(|p| p.storage)
+				.map(|s| (s.prefix, s.entries.into_iter().map(|e| e.name).collect()))
+				.collect(),
+			_ => return None,
+		};
+
+		let mut names = Self::default();
+		for (pallet, items) in storages {
+			let pallet_prefix = twox_128(pallet.as_bytes());
+			for item in items {
+				let prefix = [pallet_prefix, twox_128(item.as_bytes())].concat();
+				names
+					.items
+					.insert(HexDisplay::from(&prefix).to_string(), format!("{}::{}", pallet, item));
+			}
+			names.pallets.insert(HexDisplay::from(&pallet_prefix).to_string(), pallet);
+		}
+		Some(names)
+	}
+
+	fn access(&self, key: String) -> StorageAccess {
+		let storage = key
+			.get(..64)
+			.and_then(|prefix| self.items.get(prefix))
+			.or_else(|| key.get(..32).and_then(|prefix| self.pallets.get(prefix)))
+			.cloned();
+		StorageAccess { key, storage }
+	}
+}
+
+impl<Block, Client> BlockExecutor<Block, Client>
+where
+	Block: BlockT + 'static,
+	Client: HeaderBackend<Block>
+		+ BlockBackend<Block>
+		+ ProvideRuntimeApi<Block>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api: Metadata<Block>,
+{
+	/// Execute block with proof recording enabled and attribute the proof size growth, the keys
+	/// read from the backend and the keys written to every span belonging to `Self::targets`.
+	///
+	/// Storage keys are mapped to their storage items using the metadata of the parent block.
+	/// Note: the storage root is calculated as part of the `final_checks` span, the proof size of
+	/// all written trie nodes is attributed to it.
+	pub fn trace_block_proof_size(&self) -> TraceBlockResult<TraceBlockProofSizeResponse> {
+		tracing::debug!(target: "state_tracing", "Tracing proof size of block: {}", self.block);
+		let (block, parent_hash) = self.prepare_block()?;
+
+		let names = self
+			.client
+			.runtime_api()
+			.metadata(parent_hash)
+			.ok()
+			.and_then(|metadata| StorageNames::from_metadata(&metadata))
+			.unwrap_or_else(|| {
+				tracing::debug!(target: "state_tracing", "Failed to decode runtime metadata");
+				Default::default()
+			});
+
+		let mut runtime_api = self.client.runtime_api();
+		runtime_api.record_proof();
+		let recorder = runtime_api
+			.proof_recorder()
+			.ok_or_else(|| Error::Dispatch("Proof recording is not enabled".to_string()))?;
+
+		let targets = if let Some(t) = &self.targets { t } else { DEFAULT_TARGETS };
+		let subscriber = ProofSizeSubscriber::<Block>::new(targets, recorder.clone());
+		let dispatch = Dispatch::new(subscriber);
+
+		{
+			let dispatcher_span = tracing::debug_span!(
+				target: "state_tracing",
+				"execute_block",
+				extrinsics_len = block.extrinsics().len(),
+			);
+			let _guard = dispatcher_span.enter();
+			if let Err(e) = dispatcher::with_default(&dispatch, || {
+				let span = tracing::info_span!(target: TRACE_TARGET, "trace_block");
+				let _enter = span.enter();
+				runtime_api.execute_block(parent_hash, block)
+			}) {
+				return Err(Error::Dispatch(format!(
+					"Failed to collect traces and execute block: {}",
+					e
+				)))
+			}
+		}
+
+		let subscriber =
+			dispatch.downcast_ref::<ProofSizeSubscriber<Block>>().ok_or_else(|| {
+				Error::Dispatch(
+					"Cannot downcast Dispatch to ProofSizeSubscriber after tracing block"
+						.to_string(),
+				)
+			})?;
+		let mut spans: Vec<_> = subscriber
+			.spans
+			.lock()
+			.drain()
+			.filter_map(|(_, s)| {
+				let span = patch_and_filter(s.span, targets)?;
+				Some(ProofSizeSpan {
+					id: span.id,
+					parent_id: span.parent_id,
+					name: span.name,
+					target: span.target,
+					wasm: span.wasm,
+					proof_size: s.proof_size as u64,
+					reads: s.reads.into_iter().map(|k| names.access(k)).collect(),
+					writes: s.writes.into_iter().map(|k| names.access(k)).collect(),
+				})
+			})
+			.collect();
+		spans.sort_by_key(|s| s.id);
+		tracing::debug!(target: "state_tracing", "Captured {} spans", spans.len());
+
+		Ok(TraceBlockProofSizeResponse::BlockProofSizeTrace(BlockProofSizeTrace {
+			block_hash: block_id_as_string(BlockId::<Block>::Hash(self.block)),
+			parent_hash: block_id_as_string(BlockId::<Block>::Hash(parent_hash)),
+			tracing_targets: targets.to_string(),
+			proof_size: recorder.estimate_encoded_size() as u64,
+			spans,
+		}))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::{generic, traits::BlakeTwo256, OpaqueExtrinsic};
+	use sp_trie::{
+		trie_types::{TrieDBBuilder, TrieDBMutBuilderV1},
+		MemoryDB, Trie, TrieMut,
+	};
+
+	type Block = generic::Block<generic::Header<u64, BlakeTwo256>, OpaqueExtrinsic>;
+
+	struct TestTrie {
+		db: MemoryDB<BlakeTwo256>,
+		root: sp_core::H256,
+		recorder: ProofRecorder<Block>,
+	}
+
+	impl TestTrie {
+		fn new() -> Self {
+			let mut db = MemoryDB::default();
+			let mut root = Default::default();
+			{
+				let mut trie = TrieDBMutBuilderV1::<BlakeTwo256>::new(&mut db, &mut root).build();
+				for i in 0u8..16 {
+					trie.insert(&[i], &[i; 64]).unwrap();
+				}
+			}
+			Self { db, root, recorder: Default::default() }
+		}
+
+		/// Read `key` from the trie, like the externalities do.
+		fn read(&self, key: u8) {
+			let mut recorder = self.recorder.as_trie_recorder(self.root);
+			let trie = TrieDBBuilder::<BlakeTwo256>::new(&self.db, &self.root)
+				.with_recorder(&mut recorder)
+				.build();
+			assert!(trie.get(&[key]).unwrap().is_some());
+			drop(trie);
+			drop(recorder);
+			tracing::trace!(target: "state", method = "Get", key = %HexDisplay::from(&[key]));
+		}
+	}
+
+	fn trace(
+		f: impl FnOnce(&TestTrie),
+	) -> HashMap<String, (usize, BTreeSet<String>, BTreeSet<String>)> {
+		let trie = TestTrie::new();
+		let dispatch =
+			Dispatch::new(ProofSizeSubscriber::<Block>::new("pallet", trie.recorder.clone()));
+		dispatcher::with_default(&dispatch, || f(&trie));
+
+		let subscriber = dispatch.downcast_ref::<ProofSizeSubscriber<Block>>().unwrap();
+		let spans = subscriber.spans.lock();
+		spans
+			.values()
+			.map(|s| (s.span.name.clone(), (s.proof_size, s.reads.clone(), s.writes.clone())))
+			.collect()
+	}
+
+	fn keys(keys: &[u8]) -> BTreeSet<String> {
+		keys.iter().map(|k| HexDisplay::from(&[*k]).to_string()).collect()
+	}
+
+	#[test]
+	fn proof_size_is_attributed_to_spans() {
+		let spans = trace(|trie| {
+			let outer = tracing::info_span!(target: "pallet", "outer");
+			let _outer = outer.enter();
+			trie.read(1);
+			{
+				let inner = tracing::info_span!(target: "pallet", "inner");
+				let _inner = inner.enter();
+				trie.read(2);
+			}
+			let sibling = tracing::info_span!(target: "pallet", "sibling");
+			let _sibling = sibling.enter();
+			// Already in the proof.
+			trie.read(2);
+		});
+
+		let (outer_size, outer_reads, _) = &spans["outer"];
+		let (inner_size, inner_reads, _) = &spans["inner"];
+		let (sibling_size, sibling_reads, _) = &spans["sibling"];
+		assert!(*inner_size > 0);
+		assert!(outer_size > inner_size);
+		assert_eq!(*sibling_size, 0);
+		assert_eq!(*outer_reads, keys(&[1, 2]));
+		assert_eq!(*inner_reads, keys(&[2]));
+		assert!(sibling_reads.is_empty());
+	}
+
+	#[test]
+	fn reads_of_written_keys_are_not_attributed() {
+		let spans = trace(|_| {
+			let span = tracing::info_span!(target: "pallet", "span");
+			let _span = span.enter();
+			tracing::trace!(target: "state", method = "Put", key = "03");
+			tracing::trace!(target: "state", method = "Get", key = "03");
+			tracing::trace!(target: "state", method = "Get", key = "04");
+			tracing::trace!(target: "state", method = "ClearPrefix", prefix = "05");
+		});
+
+		let (size, reads, writes) = &spans["span"];
+		assert_eq!(*size, 0);
+		assert_eq!(*reads, keys(&[4]));
+		assert_eq!(*writes, keys(&[3, 5]));
+	}
+}
@@ -114,6 +114,9 @@ impl pallet_asset_conversion::Config for Test {
 	type LPFee = ConstU32<3>;
 	type LiquidityWithdrawalFee = LiquidityWithdrawalFee;
 	type MaxSwapPathLength = ConstU32<4>;
+	type MaxTicks = ConstU32<16>;
+	type StablePoolOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type MaxStableAssets = ConstU32<4>;
 	type MintMinLiquidity = ConstU64<100>;
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = ();
@@ -165,6 +165,99 @@ where
 	(lp_token, liquidity1, liquidity2)
 }
 
+/// Creates a concentrated liquidity pool for a given asset pair at tick `0`.
+///
+/// This action mints `amount` of each of the given assets for the `caller` to provide liquidity.
+fn create_asset_and_concentrated_pool<T: Config>(
+	caller: &T::AccountId,
+	asset1: &T::AssetKind,
+	asset2: &T::AssetKind,
+	amount: T::Balance,
+) where
+	T::Assets: Create<T::AccountId> + Mutate<T::AccountId>,
+{
+	create_asset::<T>(caller, asset1, amount, true);
+	create_asset::<T>(caller, asset2, amount, true);
+	assert_ok!(T::Assets::mint_into(
+		T::PoolSetupFeeAsset::get(),
+		&caller,
+		T::PoolSetupFee::get() +
+			T::Assets::deposit_required(asset1.clone()) +
+			T::Assets::deposit_required(asset2.clone())
+	));
+
+	assert_ok!(AssetConversion::<T>::create_concentrated_pool(
+		SystemOrigin::Signed(caller.clone()).into(),
+		Box::new(asset1.clone()),
+		Box::new(asset2.clone()),
+		0,
+	));
+}
+
+/// The amount of each asset provided per concentrated liquidity position.
+fn concentrated_liquidity_amount<T: Config>(
+	asset1: &T::AssetKind,
+	asset2: &T::AssetKind,
+) -> T::Balance
+where
+	T::Assets: Inspect<T::AccountId>,
+{
+	let ed =
+		T::Assets::minimum_balance(asset1.clone()).max(T::Assets::minimum_balance(asset2.clone()));
+	(ed + T::MintMinLiquidity::get()) * T::Balance::from(100u32)
+}
+
+/// Creates `n` assets and a stable swap pool of them, minting `amount` of each asset for the
+/// `caller` to provide liquidity.
+///
+/// Returns the id of the pool along with its assets.
+fn create_assets_and_stable_pool<T: Config>(
+	caller: &T::AccountId,
+	n: u32,
+	amount: T::Balance,
+) -> (StablePoolId, Vec<T::AssetKind>)
+where
+	T::Assets: Create<T::AccountId> + Mutate<T::AccountId>,
+{
+	let assets = stable_assets::<T>(n);
+	for asset in assets.iter() {
+		create_asset::<T>(caller, asset, amount, true);
+		assert_ok!(T::Assets::mint_into(
+			T::PoolSetupFeeAsset::get(),
+			&caller,
+			T::Assets::deposit_required(asset.clone())
+		));
+	}
+	let lp_token = AssetConversion::<T>::get_next_pool_asset_id();
+	assert_ok!(T::Assets::mint_into(
+		T::PoolSetupFeeAsset::get(),
+		&caller,
+		T::PoolAssets::deposit_required(lp_token)
+	));
+	let pool_id = NextStablePoolId::<T>::get();
+	assert_ok!(AssetConversion::<T>::do_create_stable_pool(assets.clone(), 100));
+
+	(pool_id, assets)
+}
+
+/// Returns `n` distinct assets for a stable swap pool.
+fn stable_assets<T: Config>(n: u32) -> Vec<T::AssetKind> {
+	(0..n).map(|i| T::BenchmarkHelper::create_pair(2 * i, 2 * i + 1).0).collect()
+}
+
+/// The amount of each asset of a stable swap pool provided as liquidity.
+fn stable_liquidity_amount<T: Config>(assets: &[T::AssetKind]) -> T::Balance
+where
+	T::Assets: Inspect<T::AccountId>,
+{
+	let ed = assets
+		.iter()
+		.map(|asset| T::Assets::minimum_balance(asset.clone()))
+		.max()
+		.unwrap_or_else(Zero::zero);
+	(ed + T::MintMinLiquidity::get()) * T::Balance::from(100u32)
+}
+
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	let events = frame_system::Pallet::<T>::events();
 	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
@@ -409,5 +502,333 @@ mod benchmarks {
 		assert_last_event::<T>(Event::Touched { pool_id, who: caller }.into());
 	}
 
+	#[benchmark]
+	fn create_concentrated_pool() {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+		create_asset::<T>(&caller, &asset1, T::Assets::minimum_balance(asset1.clone()), true);
+		create_asset::<T>(&caller, &asset2, T::Assets::minimum_balance(asset2.clone()), true);
+		create_fee_asset::<T>(&caller);
+		assert_ok!(T::Assets::mint_into(
+			T::PoolSetupFeeAsset::get(),
+			&caller,
+			T::PoolSetupFee::get() +
+				T::Assets::deposit_required(asset1.clone()) +
+				T::Assets::deposit_required(asset2.clone())
+		));
+
+		#[extrinsic_call]
+		_(
+			SystemOrigin::Signed(caller.clone()),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			0,
+		);
+
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+		let pool_account = AssetConversion::<T>::concentrated_pool_address(&pool_id).unwrap();
+		assert_last_event::<T>(
+			Event::ConcentratedPoolCreated { creator: caller, pool_id, pool_account, tick: 0 }
+				.into(),
+		);
+	}
+
+	#[benchmark]
+	fn add_concentrated_liquidity() {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+		let amount = concentrated_liquidity_amount::<T>(&asset1, &asset2);
+
+		create_fee_asset::<T>(&caller);
+		create_asset_and_concentrated_pool::<T>(&caller, &asset1, &asset2, amount);
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+
+		// both ticks of the position are initialized, and the position is in range.
+		#[extrinsic_call]
+		_(
+			SystemOrigin::Signed(caller.clone()),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			-100,
+			100,
+			amount,
+			amount,
+			T::Balance::zero(),
+			T::Balance::zero(),
+		);
+
+		assert!(ConcentratedPositions::<T>::contains_key(&pool_id, &(caller, -100, 100)));
+		assert_eq!(InitializedTicks::<T>::get(&pool_id).into_inner(), vec![-100, 100]);
+	}
+
+	#[benchmark]
+	fn remove_concentrated_liquidity() {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+		let amount = concentrated_liquidity_amount::<T>(&asset1, &asset2);
+
+		create_fee_asset::<T>(&caller);
+		create_asset_and_concentrated_pool::<T>(&caller, &asset1, &asset2, amount);
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+		assert_ok!(AssetConversion::<T>::add_concentrated_liquidity(
+			SystemOrigin::Signed(caller.clone()).into(),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			-100,
+			100,
+			amount,
+			amount,
+			T::Balance::zero(),
+			T::Balance::zero(),
+		));
+		let key = (caller.clone(), -100, 100);
+		let liquidity = ConcentratedPositions::<T>::get(&pool_id, &key).unwrap().liquidity;
+
+		// the whole position is removed, dropping both of its ticks.
+		#[extrinsic_call]
+		_(
+			SystemOrigin::Signed(caller.clone()),
+			Box::new(asset1),
+			Box::new(asset2),
+			-100,
+			100,
+			liquidity,
+			T::Balance::zero(),
+			T::Balance::zero(),
+			caller.clone(),
+		);
+
+		assert!(!ConcentratedPositions::<T>::contains_key(&pool_id, &key));
+		assert!(InitializedTicks::<T>::get(&pool_id).is_empty());
+	}
+
+	#[benchmark]
+	fn collect_concentrated_fees() {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+		let amount = concentrated_liquidity_amount::<T>(&asset1, &asset2);
+
+		create_fee_asset::<T>(&caller);
+		create_asset_and_concentrated_pool::<T>(&caller, &asset1, &asset2, amount + amount);
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+		assert_ok!(AssetConversion::<T>::add_concentrated_liquidity(
+			SystemOrigin::Signed(caller.clone()).into(),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			-100,
+			100,
+			amount,
+			amount,
+			T::Balance::zero(),
+			T::Balance::zero(),
+		));
+		// earn fees in both assets.
+		for path in [vec![asset1.clone(), asset2.clone()], vec![asset2.clone(), asset1.clone()]] {
+			assert_ok!(AssetConversion::<T>::swap_exact_tokens_for_tokens(
+				SystemOrigin::Signed(caller.clone()).into(),
+				path.into_iter().map(Box::new).collect(),
+				amount / T::Balance::from(10u32),
+				T::Balance::one(),
+				caller.clone(),
+				true,
+			));
+		}
+		let key = (caller.clone(), -100, 100);
+
+		#[extrinsic_call]
+		_(
+			SystemOrigin::Signed(caller.clone()),
+			Box::new(asset1),
+			Box::new(asset2),
+			-100,
+			100,
+			caller.clone(),
+		);
+
+		let position = ConcentratedPositions::<T>::get(&pool_id, &key).unwrap();
+		assert!(position.fees_owed1.is_zero() && position.fees_owed2.is_zero());
+	}
+
+	#[benchmark]
+	fn swap_concentrated(t: Linear<1, { T::MaxTicks::get() - 1 }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+		let amount = concentrated_liquidity_amount::<T>(&asset1, &asset2);
+		let total = amount * T::Balance::from(t + 1);
+
+		create_fee_asset::<T>(&caller);
+		create_asset_and_concentrated_pool::<T>(&caller, &asset1, &asset2, total + total);
+		let pool_id = T::PoolLocator::pool_id(&asset1, &asset2).unwrap();
+		let pool_account = AssetConversion::<T>::concentrated_pool_address(&pool_id).unwrap();
+
+		// adjacent ranges below the current price, each holding `asset2` only.
+		let mut amount_out = T::Balance::zero();
+		for k in 0..t as i32 {
+			let before = T::Assets::balance(asset2.clone(), &pool_account);
+			assert_ok!(AssetConversion::<T>::add_concentrated_liquidity(
+				SystemOrigin::Signed(caller.clone()).into(),
+				Box::new(asset1.clone()),
+				Box::new(asset2.clone()),
+				-(k + 1) * 100,
+				-k * 100,
+				T::Balance::zero(),
+				amount,
+				T::Balance::zero(),
+				T::Balance::zero(),
+			));
+			let added = T::Assets::balance(asset2.clone(), &pool_account) - before;
+			// the swap ends in the middle of the last range, having crossed `t` ticks.
+			amount_out = match k + 1 == t as i32 {
+				true => amount_out + added / T::Balance::from(2u32),
+				false => amount_out + added,
+			};
+		}
+		let init_caller_balance = T::Assets::balance(asset2.clone(), &caller);
+
+		#[extrinsic_call]
+		swap_tokens_for_exact_tokens(
+			SystemOrigin::Signed(caller.clone()),
+			vec![Box::new(asset1.clone()), Box::new(asset2.clone())],
+			amount_out,
+			total,
+			caller.clone(),
+			true,
+		);
+
+		let actual_balance = T::Assets::balance(asset2, &caller);
+		assert_eq!(actual_balance, init_caller_balance + amount_out);
+		let tick = ConcentratedPools::<T>::get(&pool_id).unwrap().tick;
+		assert!((-(t as i32) * 100..-(t as i32 - 1) * 100).contains(&tick));
+	}
+
+	#[benchmark]
+	fn create_stable_pool(
+		n: Linear<2, { T::MaxStableAssets::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let origin =
+			T::StablePoolOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let assets = stable_assets::<T>(n);
+		for asset in assets.iter() {
+			create_asset::<T>(&caller, asset, T::Assets::minimum_balance(asset.clone()), true);
+		}
+		let pool_id = NextStablePoolId::<T>::get();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, assets.iter().cloned().map(Box::new).collect(), 100);
+
+		let pool = StablePools::<T>::get(pool_id).unwrap();
+		assert_eq!(pool.assets.into_inner(), assets);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn add_stable_liquidity(n: Linear<2, { T::MaxStableAssets::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = stable_liquidity_amount::<T>(&stable_assets::<T>(n));
+
+		create_fee_asset::<T>(&caller);
+		let (pool_id, _) = create_assets_and_stable_pool::<T>(&caller, n, amount + amount);
+		assert_ok!(AssetConversion::<T>::add_stable_liquidity(
+			SystemOrigin::Signed(caller.clone()).into(),
+			pool_id,
+			vec![amount; n as usize],
+			T::Balance::zero(),
+			caller.clone(),
+		));
+		let lp_token = StablePools::<T>::get(pool_id).unwrap().lp_token;
+		let init_lp_balance = T::PoolAssets::balance(lp_token.clone(), &caller);
+		// an imbalanced deposit, charged the fee on its deviation from the balance of the pool.
+		let mut amounts = vec![amount / T::Balance::from(2u32); n as usize];
+		amounts[0] = amount;
+
+		#[extrinsic_call]
+		_(
+			SystemOrigin::Signed(caller.clone()),
+			pool_id,
+			amounts,
+			T::Balance::zero(),
+			caller.clone(),
+		);
+
+		assert!(T::PoolAssets::balance(lp_token, &caller) > init_lp_balance);
+	}
+
+	#[benchmark]
+	fn remove_stable_liquidity(n: Linear<2, { T::MaxStableAssets::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = stable_liquidity_amount::<T>(&stable_assets::<T>(n));
+
+		create_fee_asset::<T>(&caller);
+		let (pool_id, assets) = create_assets_and_stable_pool::<T>(&caller, n, amount);
+		assert_ok!(AssetConversion::<T>::add_stable_liquidity(
+			SystemOrigin::Signed(caller.clone()).into(),
+			pool_id,
+			vec![amount; n as usize],
+			T::Balance::zero(),
+			caller.clone(),
+		));
+		let lp_token = StablePools::<T>::get(pool_id).unwrap().lp_token;
+		let lp_token_burn = T::PoolAssets::balance(lp_token.clone(), &caller) / 2u32.into();
+		let init_balance = T::Assets::balance(assets[0].clone(), &caller);
+
+		#[extrinsic_call]
+		_(
+			SystemOrigin::Signed(caller.clone()),
+			pool_id,
+			lp_token_burn,
+			vec![T::Balance::zero(); n as usize],
+			caller.clone(),
+		);
+
+		assert!(T::Assets::balance(assets[0].clone(), &caller) > init_balance);
+	}
+
+	#[benchmark]
+	fn set_stable_amplification() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let origin =
+			T::StablePoolOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		create_fee_asset::<T>(&caller);
+		let (pool_id, _) = create_assets_and_stable_pool::<T>(&caller, 2, T::Balance::zero());
+		let ramp_end = frame_system::Pallet::<T>::block_number() + 100u32.into();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, pool_id, 1_000, ramp_end);
+
+		let pool = StablePools::<T>::get(pool_id).unwrap();
+		assert_eq!((pool.future_amplification, pool.ramp_end), (1_000, ramp_end));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn swap_stable(n: Linear<2, { T::MaxStableAssets::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = stable_liquidity_amount::<T>(&stable_assets::<T>(n));
+
+		create_fee_asset::<T>(&caller);
+		let (pool_id, assets) = create_assets_and_stable_pool::<T>(&caller, n, amount + amount);
+		assert_ok!(AssetConversion::<T>::add_stable_liquidity(
+			SystemOrigin::Signed(caller.clone()).into(),
+			pool_id,
+			vec![amount; n as usize],
+			T::Balance::zero(),
+			caller.clone(),
+		));
+		let init_caller_balance = T::Assets::balance(assets[1].clone(), &caller);
+
+		#[extrinsic_call]
+		swap_exact_tokens_for_tokens(
+			SystemOrigin::Signed(caller.clone()),
+			vec![Box::new(assets[0].clone()), Box::new(assets[1].clone())],
+			amount / T::Balance::from(10u32),
+			T::Balance::one(),
+			caller.clone(),
+			true,
+		);
+
+		assert!(T::Assets::balance(assets[1].clone(), &caller) > init_caller_balance);
+	}
+
 	impl_benchmark_test_suite!(AssetConversion, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -0,0 +1,849 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Concentrated liquidity pools based on the [Uniswap V3](https://github.com/Uniswap/v3-core)
+//! logic.
+//!
+//! A concentrated liquidity pool exists alongside the constant product pool of the same asset
+//! pair. Its liquidity providers supply liquidity within a price range only, delimited by two
+//! ticks. The price of `asset1` in `asset2` at tick `i` is `1.0001^i`, and the pool keeps track of
+//! the square root of the price, as well as the liquidity of the positions whose range contains
+//! the current price.
+//!
+//! Swaps move the price towards the next initialized tick. Crossing a tick activates or
+//! deactivates the liquidity of the positions bounded by it. The fees of the swaps are accounted
+//! per unit of liquidity in range, so that every position earns them in proportion to its
+//! liquidity while the price is within its range.
+
+use super::*;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::ensure;
+use scale_info::TypeInfo;
+use sp_arithmetic::{
+	helpers_128bit::multiply_by_rational_with_rounding, FixedPointNumber, FixedU128, Rounding,
+};
+use sp_runtime::{DispatchResult, RuntimeDebug};
+
+/// The lowest tick a concentrated liquidity position can be bounded by.
+pub const MIN_TICK: i32 = -200_000;
+
+/// The highest tick a concentrated liquidity position can be bounded by.
+pub const MAX_TICK: i32 = 200_000;
+
+/// `sqrt(1.0001)`, the ratio between the square root prices of two adjacent ticks.
+const SQRT_TICK_BASE: FixedU128 = FixedU128::from_inner(1_000_049_998_750_062_496);
+
+/// The state of a concentrated liquidity pool.
+#[derive(Decode, Encode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct ConcentratedPoolInfo<AssetKind, Balance> {
+	/// The asset the price is quoted for.
+	pub asset1: AssetKind,
+	/// The asset the price is quoted in.
+	pub asset2: AssetKind,
+	/// The square root of the current price of `asset1` in `asset2`.
+	pub sqrt_price: FixedU128,
+	/// The current tick, the greatest one whose price doesn't exceed the current price.
+	pub tick: i32,
+	/// The liquidity of the positions whose range contains the current price.
+	pub liquidity: Balance,
+	/// The fees in `asset1` earned per unit of liquidity over the lifetime of the pool.
+	pub fee_growth1: FixedU128,
+	/// The fees in `asset2` earned per unit of liquidity over the lifetime of the pool.
+	pub fee_growth2: FixedU128,
+}
+
+/// The state of a concentrated liquidity pool of the pallet.
+pub type ConcentratedPoolInfoOf<T> =
+	ConcentratedPoolInfo<<T as Config>::AssetKind, <T as Config>::Balance>;
+
+/// The state of an initialized tick of a concentrated liquidity pool.
+#[derive(Decode, Encode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct TickInfo<Balance> {
+	/// The liquidity of the positions with the lower bound at this tick.
+	pub liquidity_in: Balance,
+	/// The liquidity of the positions with the upper bound at this tick.
+	pub liquidity_out: Balance,
+	/// The fees in `asset1` earned per unit of liquidity on the other side of this tick from the
+	/// current one.
+	pub fee_growth_outside1: FixedU128,
+	/// The fees in `asset2` earned per unit of liquidity on the other side of this tick from the
+	/// current one.
+	pub fee_growth_outside2: FixedU128,
+}
+
+/// A liquidity position in a concentrated liquidity pool.
+#[derive(Decode, Encode, Clone, Default, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct ConcentratedPosition<Balance> {
+	/// The liquidity provided within the range of the position.
+	pub liquidity: Balance,
+	/// The fees in `asset1` earned per unit of liquidity within the range as of the last time the
+	/// fees owed to the position were updated.
+	pub fee_growth_inside1: FixedU128,
+	/// The fees in `asset2` earned per unit of liquidity within the range as of the last time the
+	/// fees owed to the position were updated.
+	pub fee_growth_inside2: FixedU128,
+	/// The fees in `asset1` owed to the position.
+	pub fees_owed1: Balance,
+	/// The fees in `asset2` owed to the position.
+	pub fees_owed2: Balance,
+}
+
+/// A priced swap through a concentrated liquidity pool, to be applied once the swap is executed.
+pub(crate) struct ConcentratedSwap<T: Config> {
+	/// The pool the swap goes through.
+	pub(crate) pool_id: T::PoolId,
+	/// The amount deposited into the pool, fees included.
+	pub(crate) amount_in: T::Balance,
+	/// The amount withdrawn from the pool.
+	pub(crate) amount_out: T::Balance,
+	/// The state of the pool after the swap.
+	pool: ConcentratedPoolInfoOf<T>,
+	/// The ticks crossed by the swap, with their state after the swap.
+	crossed: Vec<(i32, TickInfo<T::Balance>)>,
+}
+
+impl<T: Config> ConcentratedSwap<T> {
+	/// The number of ticks crossed by the swap.
+	pub(crate) fn ticks_crossed(&self) -> u32 {
+		self.crossed.len() as u32
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account holding the assets of the concentrated liquidity pool with `pool_id`.
+	pub fn concentrated_pool_address(pool_id: &T::PoolId) -> Result<T::AccountId, DispatchError> {
+		sp_io::hashing::blake2_256(&Encode::encode(&(T::PalletId::get(), b"concentrated", pool_id)))
+			.using_encoded(|e| Decode::decode(&mut TrailingZeroInput::new(e)))
+			.map_err(|_| Error::<T>::InvalidAssetPair.into())
+	}
+
+	/// Returns the concentrated liquidity pool of `asset1` and `asset2`, provided in the order
+	/// the pool was created with.
+	fn concentrated_pool(
+		asset1: &T::AssetKind,
+		asset2: &T::AssetKind,
+	) -> Result<(T::PoolId, ConcentratedPoolInfoOf<T>), DispatchError> {
+		let pool_id =
+			T::PoolLocator::pool_id(asset1, asset2).map_err(|_| Error::<T>::InvalidAssetPair)?;
+		let pool = ConcentratedPools::<T>::get(&pool_id).ok_or(Error::<T>::PoolNotFound)?;
+		ensure!(pool.asset1 == *asset1 && pool.asset2 == *asset2, Error::<T>::InvalidAssetPair);
+		Ok((pool_id, pool))
+	}
+
+	pub(crate) fn do_create_concentrated_pool(
+		creator: T::AccountId,
+		asset1: T::AssetKind,
+		asset2: T::AssetKind,
+		tick: i32,
+	) -> DispatchResult {
+		ensure!(asset1 != asset2, Error::<T>::InvalidAssetPair);
+		ensure!((MIN_TICK..=MAX_TICK).contains(&tick), Error::<T>::InvalidTick);
+
+		let pool_id =
+			T::PoolLocator::pool_id(&asset1, &asset2).map_err(|_| Error::<T>::InvalidAssetPair)?;
+		ensure!(!ConcentratedPools::<T>::contains_key(&pool_id), Error::<T>::PoolExists);
+		let pool_account = Self::concentrated_pool_address(&pool_id)?;
+
+		// pay the setup fee
+		let fee =
+			Self::withdraw(T::PoolSetupFeeAsset::get(), &creator, T::PoolSetupFee::get(), true)?;
+		T::PoolSetupFeeTarget::on_unbalanced(fee);
+
+		if T::Assets::should_touch(asset1.clone(), &pool_account) {
+			T::Assets::touch(asset1.clone(), &pool_account, &creator)?
+		};
+
+		if T::Assets::should_touch(asset2.clone(), &pool_account) {
+			T::Assets::touch(asset2.clone(), &pool_account, &creator)?
+		};
+
+		ConcentratedPools::<T>::insert(
+			&pool_id,
+			ConcentratedPoolInfo {
+				asset1,
+				asset2,
+				sqrt_price: sqrt_price_at_tick(tick),
+				tick,
+				liquidity: Zero::zero(),
+				fee_growth1: Zero::zero(),
+				fee_growth2: Zero::zero(),
+			},
+		);
+
+		Self::deposit_event(Event::ConcentratedPoolCreated {
+			creator,
+			pool_id,
+			pool_account,
+			tick,
+		});
+
+		Ok(())
+	}
+
+	pub(crate) fn do_add_concentrated_liquidity(
+		who: T::AccountId,
+		asset1: T::AssetKind,
+		asset2: T::AssetKind,
+		tick_lower: i32,
+		tick_upper: i32,
+		amounts_desired: (T::Balance, T::Balance),
+		amounts_min: (T::Balance, T::Balance),
+	) -> DispatchResult {
+		let (pool_id, mut pool) = Self::concentrated_pool(&asset1, &asset2)?;
+		Self::ensure_valid_range(tick_lower, tick_upper)?;
+		ensure!(
+			!amounts_desired.0.is_zero() || !amounts_desired.1.is_zero(),
+			Error::<T>::WrongDesiredAmount
+		);
+
+		let (price, lower_price, upper_price) = (
+			pool.sqrt_price.into_inner(),
+			sqrt_price_at_tick(tick_lower).into_inner(),
+			sqrt_price_at_tick(tick_upper).into_inner(),
+		);
+		let liquidity = liquidity_for_amounts(
+			price,
+			lower_price,
+			upper_price,
+			Self::to_u128(amounts_desired.0)?,
+			Self::to_u128(amounts_desired.1)?,
+		)
+		.ok_or(Error::<T>::Overflow)?;
+		let (amount1, amount2) =
+			amounts_for_liquidity(price, lower_price, upper_price, liquidity, Rounding::Up)
+				.ok_or(Error::<T>::Overflow)?;
+		let (liquidity, amount1, amount2) =
+			(Self::to_balance(liquidity)?, Self::to_balance(amount1)?, Self::to_balance(amount2)?);
+
+		ensure!(
+			!liquidity.is_zero() && liquidity >= T::MintMinLiquidity::get(),
+			Error::<T>::InsufficientLiquidityMinted
+		);
+		ensure!(amount1 >= amounts_min.0, Error::<T>::AssetOneDepositDidNotMeetMinimum);
+		ensure!(amount2 >= amounts_min.1, Error::<T>::AssetTwoDepositDidNotMeetMinimum);
+
+		Self::update_ticks(&pool_id, &pool, tick_lower, tick_upper, liquidity, true)?;
+		let key = (who.clone(), tick_lower, tick_upper);
+		let mut position = ConcentratedPositions::<T>::get(&pool_id, &key).unwrap_or_default();
+		Self::accrue_fees(&pool_id, &pool, tick_lower, tick_upper, &mut position)?;
+		position.liquidity =
+			position.liquidity.checked_add(&liquidity).ok_or(Error::<T>::Overflow)?;
+		ConcentratedPositions::<T>::insert(&pool_id, &key, position);
+
+		if (tick_lower..tick_upper).contains(&pool.tick) {
+			pool.liquidity = pool.liquidity.checked_add(&liquidity).ok_or(Error::<T>::Overflow)?;
+			ConcentratedPools::<T>::insert(&pool_id, pool);
+		}
+
+		let pool_account = Self::concentrated_pool_address(&pool_id)?;
+		T::Assets::transfer(asset1, &who, &pool_account, amount1, Preserve)?;
+		T::Assets::transfer(asset2, &who, &pool_account, amount2, Preserve)?;
+
+		Self::deposit_event(Event::ConcentratedLiquidityAdded {
+			who,
+			pool_id,
+			tick_lower,
+			tick_upper,
+			liquidity,
+			amount1,
+			amount2,
+		});
+
+		Ok(())
+	}
+
+	pub(crate) fn do_remove_concentrated_liquidity(
+		who: T::AccountId,
+		asset1: T::AssetKind,
+		asset2: T::AssetKind,
+		tick_lower: i32,
+		tick_upper: i32,
+		liquidity: T::Balance,
+		amounts_min: (T::Balance, T::Balance),
+		withdraw_to: T::AccountId,
+	) -> DispatchResult {
+		let (pool_id, mut pool) = Self::concentrated_pool(&asset1, &asset2)?;
+		ensure!(!liquidity.is_zero(), Error::<T>::ZeroLiquidity);
+
+		let key = (who.clone(), tick_lower, tick_upper);
+		let mut position =
+			ConcentratedPositions::<T>::get(&pool_id, &key).ok_or(Error::<T>::PositionNotFound)?;
+		ensure!(position.liquidity >= liquidity, Error::<T>::InsufficientPositionLiquidity);
+		Self::accrue_fees(&pool_id, &pool, tick_lower, tick_upper, &mut position)?;
+
+		let (amount1, amount2) = amounts_for_liquidity(
+			pool.sqrt_price.into_inner(),
+			sqrt_price_at_tick(tick_lower).into_inner(),
+			sqrt_price_at_tick(tick_upper).into_inner(),
+			Self::to_u128(liquidity)?,
+			Rounding::Down,
+		)
+		.ok_or(Error::<T>::Overflow)?;
+		let (amount1, amount2) = (Self::to_balance(amount1)?, Self::to_balance(amount2)?);
+		ensure!(amount1 >= amounts_min.0, Error::<T>::AssetOneWithdrawalDidNotMeetMinimum);
+		ensure!(amount2 >= amounts_min.1, Error::<T>::AssetTwoWithdrawalDidNotMeetMinimum);
+
+		Self::update_ticks(&pool_id, &pool, tick_lower, tick_upper, liquidity, false)?;
+		if (tick_lower..tick_upper).contains(&pool.tick) {
+			pool.liquidity = pool.liquidity.checked_sub(&liquidity).ok_or(Error::<T>::Overflow)?;
+			ConcentratedPools::<T>::insert(&pool_id, pool);
+		}
+
+		// the fees owed to the position are withdrawn along with the liquidity.
+		let amount1 = amount1.checked_add(&position.fees_owed1).ok_or(Error::<T>::Overflow)?;
+		let amount2 = amount2.checked_add(&position.fees_owed2).ok_or(Error::<T>::Overflow)?;
+		position.liquidity.saturating_reduce(liquidity);
+		if position.liquidity.is_zero() {
+			ConcentratedPositions::<T>::remove(&pool_id, &key);
+		} else {
+			position.fees_owed1 = Zero::zero();
+			position.fees_owed2 = Zero::zero();
+			ConcentratedPositions::<T>::insert(&pool_id, &key, position);
+		}
+
+		let pool_account = Self::concentrated_pool_address(&pool_id)?;
+		T::Assets::transfer(asset1, &pool_account, &withdraw_to, amount1, Expendable)?;
+		T::Assets::transfer(asset2, &pool_account, &withdraw_to, amount2, Expendable)?;
+
+		Self::deposit_event(Event::ConcentratedLiquidityRemoved {
+			who,
+			withdraw_to,
+			pool_id,
+			tick_lower,
+			tick_upper,
+			liquidity,
+			amount1,
+			amount2,
+		});
+
+		Ok(())
+	}
+
+	pub(crate) fn do_collect_concentrated_fees(
+		who: T::AccountId,
+		asset1: T::AssetKind,
+		asset2: T::AssetKind,
+		tick_lower: i32,
+		tick_upper: i32,
+		withdraw_to: T::AccountId,
+	) -> DispatchResult {
+		let (pool_id, pool) = Self::concentrated_pool(&asset1, &asset2)?;
+
+		let key = (who.clone(), tick_lower, tick_upper);
+		let mut position =
+			ConcentratedPositions::<T>::get(&pool_id, &key).ok_or(Error::<T>::PositionNotFound)?;
+		Self::accrue_fees(&pool_id, &pool, tick_lower, tick_upper, &mut position)?;
+
+		let (amount1, amount2) = (position.fees_owed1, position.fees_owed2);
+		position.fees_owed1 = Zero::zero();
+		position.fees_owed2 = Zero::zero();
+		ConcentratedPositions::<T>::insert(&pool_id, &key, position);
+
+		let pool_account = Self::concentrated_pool_address(&pool_id)?;
+		T::Assets::transfer(asset1, &pool_account, &withdraw_to, amount1, Expendable)?;
+		T::Assets::transfer(asset2, &pool_account, &withdraw_to, amount2, Expendable)?;
+
+		Self::deposit_event(Event::ConcentratedFeesCollected {
+			who,
+			withdraw_to,
+			pool_id,
+			tick_lower,
+			tick_upper,
+			amount1,
+			amount2,
+		});
+
+		Ok(())
+	}
+
+	/// Prices a swap through the concentrated liquidity pool of `asset_in` and `asset_out`.
+	///
+	/// The `amount` is the amount of `asset_in` to deposit if `exact_in` is set, and the amount of
+	/// `asset_out` to withdraw otherwise.
+	pub(crate) fn price_concentrated_swap(
+		asset_in: &T::AssetKind,
+		asset_out: &T::AssetKind,
+		amount: T::Balance,
+		exact_in: bool,
+	) -> Result<ConcentratedSwap<T>, DispatchError> {
+		let pool_id = T::PoolLocator::pool_id(asset_in, asset_out)
+			.map_err(|_| Error::<T>::InvalidAssetPair)?;
+		let mut pool = ConcentratedPools::<T>::get(&pool_id).ok_or(Error::<T>::PoolNotFound)?;
+		ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+		// swapping `asset1` in moves the price down.
+		let zero_for_one = pool.asset1 == *asset_in;
+		let fee = T::LPFee::get() as u128;
+		let ticks = InitializedTicks::<T>::get(&pool_id);
+
+		let mut remaining = Self::to_u128(amount)?;
+		let (mut amount_in, mut amount_out) = (0u128, 0u128);
+		let mut sqrt_price = pool.sqrt_price.into_inner();
+		let mut liquidity = Self::to_u128(pool.liquidity)?;
+		let mut crossed = Vec::new();
+
+		while remaining > 0 {
+			let next_tick = if zero_for_one {
+				ticks.iter().rev().find(|t| **t <= pool.tick)
+			} else {
+				ticks.iter().find(|t| **t > pool.tick)
+			}
+			.copied()
+			.ok_or(Error::<T>::InsufficientLiquidity)?;
+			let target = sqrt_price_at_tick(next_tick).into_inner();
+
+			let step = swap_step(sqrt_price, target, liquidity, remaining, exact_in, fee)
+				.ok_or(Error::<T>::Overflow)?;
+			let step_in = step.amount_in.checked_add(step.fee).ok_or(Error::<T>::Overflow)?;
+			remaining = remaining
+				.checked_sub(if exact_in { step_in } else { step.amount_out })
+				.ok_or(Error::<T>::Overflow)?;
+			amount_in = amount_in.checked_add(step_in).ok_or(Error::<T>::Overflow)?;
+			amount_out = amount_out.checked_add(step.amount_out).ok_or(Error::<T>::Overflow)?;
+
+			if liquidity > 0 {
+				let growth = FixedU128::from_rational(step.fee, liquidity);
+				if zero_for_one {
+					pool.fee_growth1 = wrapping_add(pool.fee_growth1, growth);
+				} else {
+					pool.fee_growth2 = wrapping_add(pool.fee_growth2, growth);
+				}
+			}
+
+			sqrt_price = step.sqrt_price;
+			if sqrt_price == target {
+				let mut info = ConcentratedTicks::<T>::get(&pool_id, next_tick)
+					.ok_or(DispatchError::Corruption)?;
+				info.fee_growth_outside1 = wrapping_sub(pool.fee_growth1, info.fee_growth_outside1);
+				info.fee_growth_outside2 = wrapping_sub(pool.fee_growth2, info.fee_growth_outside2);
+				let (activated, deactivated) = if zero_for_one {
+					(info.liquidity_out, info.liquidity_in)
+				} else {
+					(info.liquidity_in, info.liquidity_out)
+				};
+				liquidity = liquidity
+					.checked_add(Self::to_u128(activated)?)
+					.and_then(|l| l.checked_sub(Self::to_u128(deactivated).ok()?))
+					.ok_or(Error::<T>::Overflow)?;
+				pool.tick = if zero_for_one { next_tick - 1 } else { next_tick };
+				crossed.push((next_tick, info));
+			} else {
+				pool.tick = tick_at_sqrt_price(FixedU128::from_inner(sqrt_price));
+			}
+		}
+
+		pool.sqrt_price = FixedU128::from_inner(sqrt_price);
+		pool.liquidity = Self::to_balance(liquidity)?;
+
+		Ok(ConcentratedSwap {
+			pool_id,
+			amount_in: Self::to_balance(amount_in)?,
+			amount_out: Self::to_balance(amount_out)?,
+			pool,
+			crossed,
+		})
+	}
+
+	/// Applies a priced swap to the state of its concentrated liquidity pool, returning the
+	/// account of the pool.
+	///
+	/// The assets are expected to be moved in and out of the pool account by the caller.
+	pub(crate) fn apply_concentrated_swap(
+		swap: &ConcentratedSwap<T>,
+	) -> Result<T::AccountId, DispatchError> {
+		for (tick, info) in swap.crossed.iter() {
+			ConcentratedTicks::<T>::insert(&swap.pool_id, tick, info);
+		}
+		ConcentratedPools::<T>::insert(&swap.pool_id, &swap.pool);
+		Self::concentrated_pool_address(&swap.pool_id)
+	}
+
+	/// Quotes `amount` of `asset_in` in `asset_out` at the current price of their concentrated
+	/// liquidity pool, if the pool has liquidity in range.
+	pub(crate) fn quote_concentrated(
+		asset_in: &T::AssetKind,
+		asset_out: &T::AssetKind,
+		amount: T::Balance,
+	) -> Option<T::Balance> {
+		let pool_id = T::PoolLocator::pool_id(asset_in, asset_out).ok()?;
+		let pool = ConcentratedPools::<T>::get(&pool_id)?;
+		if pool.liquidity.is_zero() {
+			return None
+		}
+		let (amount, price) = (Self::to_u128(amount).ok()?, pool.sqrt_price.into_inner());
+		let quote = if pool.asset1 == *asset_in {
+			mul_div(
+				mul_div(amount, price, FixedU128::DIV, Rounding::Down)?,
+				price,
+				FixedU128::DIV,
+				Rounding::Down,
+			)
+		} else {
+			mul_div(
+				mul_div(amount, FixedU128::DIV, price, Rounding::Down)?,
+				FixedU128::DIV,
+				price,
+				Rounding::Down,
+			)
+		}?;
+		Self::to_balance(quote).ok()
+	}
+
+	/// Ensures that `tick_lower` and `tick_upper` delimit a valid range.
+	fn ensure_valid_range(tick_lower: i32, tick_upper: i32) -> DispatchResult {
+		ensure!(
+			MIN_TICK <= tick_lower && tick_lower < tick_upper && tick_upper <= MAX_TICK,
+			Error::<T>::InvalidTick
+		);
+		Ok(())
+	}
+
+	/// Adds (or removes) `liquidity` bounded by `tick_lower` and `tick_upper`, initializing the
+	/// ticks the first time they are referenced and dropping them once they no longer are.
+	fn update_ticks(
+		pool_id: &T::PoolId,
+		pool: &ConcentratedPoolInfoOf<T>,
+		tick_lower: i32,
+		tick_upper: i32,
+		liquidity: T::Balance,
+		add: bool,
+	) -> DispatchResult {
+		for (tick, is_lower) in [(tick_lower, true), (tick_upper, false)] {
+			let existing = ConcentratedTicks::<T>::get(pool_id, tick);
+			let initialize = existing.is_none();
+			// by convention, all the fees so far are assumed to be earned below the tick.
+			let mut info = existing.unwrap_or_else(|| {
+				let (fee_growth_outside1, fee_growth_outside2) = if tick <= pool.tick {
+					(pool.fee_growth1, pool.fee_growth2)
+				} else {
+					(Zero::zero(), Zero::zero())
+				};
+				TickInfo {
+					liquidity_in: Zero::zero(),
+					liquidity_out: Zero::zero(),
+					fee_growth_outside1,
+					fee_growth_outside2,
+				}
+			});
+
+			let tick_liquidity =
+				if is_lower { &mut info.liquidity_in } else { &mut info.liquidity_out };
+			*tick_liquidity = match add {
+				true => tick_liquidity.checked_add(&liquidity),
+				false => tick_liquidity.checked_sub(&liquidity),
+			}
+			.ok_or(Error::<T>::Overflow)?;
+
+			if info.liquidity_in.is_zero() && info.liquidity_out.is_zero() {
+				ConcentratedTicks::<T>::remove(pool_id, tick);
+				InitializedTicks::<T>::mutate(pool_id, |ticks| {
+					if let Ok(index) = ticks.binary_search(&tick) {
+						ticks.remove(index);
+					}
+				});
+			} else {
+				if initialize {
+					InitializedTicks::<T>::try_mutate(pool_id, |ticks| {
+						let index = ticks.binary_search(&tick).unwrap_or_else(|index| index);
+						ticks.try_insert(index, tick).map_err(|_| Error::<T>::TooManyTicks)
+					})?;
+				}
+				ConcentratedTicks::<T>::insert(pool_id, tick, info);
+			}
+		}
+		Ok(())
+	}
+
+	/// Credits the fees earned since the last update to the `position`.
+	fn accrue_fees(
+		pool_id: &T::PoolId,
+		pool: &ConcentratedPoolInfoOf<T>,
+		tick_lower: i32,
+		tick_upper: i32,
+		position: &mut ConcentratedPosition<T::Balance>,
+	) -> DispatchResult {
+		let lower =
+			ConcentratedTicks::<T>::get(pool_id, tick_lower).ok_or(Error::<T>::PositionNotFound)?;
+		let upper =
+			ConcentratedTicks::<T>::get(pool_id, tick_upper).ok_or(Error::<T>::PositionNotFound)?;
+
+		let fee_growth_inside = |global, lower_outside, upper_outside| {
+			let below = if pool.tick >= tick_lower {
+				lower_outside
+			} else {
+				wrapping_sub(global, lower_outside)
+			};
+			let above = if pool.tick < tick_upper {
+				upper_outside
+			} else {
+				wrapping_sub(global, upper_outside)
+			};
+			wrapping_sub(wrapping_sub(global, below), above)
+		};
+		let inside1 = fee_growth_inside(
+			pool.fee_growth1,
+			lower.fee_growth_outside1,
+			upper.fee_growth_outside1,
+		);
+		let inside2 = fee_growth_inside(
+			pool.fee_growth2,
+			lower.fee_growth_outside2,
+			upper.fee_growth_outside2,
+		);
+
+		let liquidity = Self::to_u128(position.liquidity)?;
+		let earned = |inside, last| {
+			let growth = wrapping_sub(inside, last).into_inner();
+			mul_div(liquidity, growth, FixedU128::DIV, Rounding::Down)
+				.ok_or(Error::<T>::Overflow)
+				.and_then(Self::to_balance)
+		};
+		position.fees_owed1 = position
+			.fees_owed1
+			.checked_add(&earned(inside1, position.fee_growth_inside1)?)
+			.ok_or(Error::<T>::Overflow)?;
+		position.fees_owed2 = position
+			.fees_owed2
+			.checked_add(&earned(inside2, position.fee_growth_inside2)?)
+			.ok_or(Error::<T>::Overflow)?;
+		position.fee_growth_inside1 = inside1;
+		position.fee_growth_inside2 = inside2;
+
+		Ok(())
+	}
+
+	pub(crate) fn to_u128(amount: T::Balance) -> Result<u128, Error<T>> {
+		amount.try_into().map_err(|_| Error::<T>::Overflow)
+	}
+
+	pub(crate) fn to_balance(amount: u128) -> Result<T::Balance, Error<T>> {
+		amount.try_into().map_err(|_| Error::<T>::Overflow)
+	}
+}
+
+/// Returns the square root of the price at `tick`.
+pub fn sqrt_price_at_tick(tick: i32) -> FixedU128 {
+	let sqrt_price = SQRT_TICK_BASE.saturating_pow(tick.unsigned_abs() as usize);
+	if tick < 0 {
+		sqrt_price.reciprocal().unwrap_or_default()
+	} else {
+		sqrt_price
+	}
+}
+
+/// Returns the greatest tick whose square root price doesn't exceed `sqrt_price`.
+pub fn tick_at_sqrt_price(sqrt_price: FixedU128) -> i32 {
+	let (mut low, mut high) = (MIN_TICK, MAX_TICK);
+	while low < high {
+		let mid = low + (high - low + 1) / 2;
+		if sqrt_price_at_tick(mid) <= sqrt_price {
+			low = mid;
+		} else {
+			high = mid - 1;
+		}
+	}
+	low
+}
+
+/// The outcome of a swap within a range of a constant liquidity.
+struct SwapStep {
+	/// The square root price after the step.
+	sqrt_price: u128,
+	/// The amount deposited into the pool, excluding the fee.
+	amount_in: u128,
+	/// The amount withdrawn from the pool.
+	amount_out: u128,
+	/// The fee charged on the deposited amount.
+	fee: u128,
+}
+
+/// Swaps within a range of a constant `liquidity`, moving the square root price from `price`
+/// towards `target` without crossing it.
+///
+/// The `remaining` amount is the amount to deposit if `exact_in` is set, and the amount to withdraw
+/// otherwise. The `fee` is expressed in tenths of a percent.
+fn swap_step(
+	price: u128,
+	target: u128,
+	liquidity: u128,
+	remaining: u128,
+	exact_in: bool,
+	fee: u128,
+) -> Option<SwapStep> {
+	if liquidity == 0 {
+		return Some(SwapStep { sqrt_price: target, amount_in: 0, amount_out: 0, fee: 0 })
+	}
+	let zero_for_one = target <= price;
+	let amount_in_between = |a, b| match zero_for_one {
+		true => amount1_delta(a, b, liquidity, Rounding::Up),
+		false => amount2_delta(a, b, liquidity, Rounding::Up),
+	};
+	let amount_out_between = |a, b| match zero_for_one {
+		true => amount2_delta(a, b, liquidity, Rounding::Down),
+		false => amount1_delta(a, b, liquidity, Rounding::Down),
+	};
+
+	if exact_in {
+		let remaining_less_fee =
+			mul_div(remaining, 1000u128.checked_sub(fee)?, 1000, Rounding::Down)?;
+		let next = if remaining_less_fee >= amount_in_between(price, target)? {
+			target
+		} else {
+			next_sqrt_price_from_input(price, liquidity, remaining_less_fee, zero_for_one)?
+		};
+		let amount_in = amount_in_between(price, next)?.min(remaining);
+		let fee = if next == target {
+			mul_div(amount_in, fee, 1000u128.checked_sub(fee)?, Rounding::Up)?
+				.min(remaining - amount_in)
+		} else {
+			// the remainder of the amount is kept by the pool as the fee.
+			remaining - amount_in
+		};
+		Some(SwapStep {
+			sqrt_price: next,
+			amount_in,
+			amount_out: amount_out_between(price, next)?,
+			fee,
+		})
+	} else {
+		let next = if remaining >= amount_out_between(price, target)? {
+			target
+		} else {
+			next_sqrt_price_from_output(price, liquidity, remaining, zero_for_one)?
+		};
+		let amount_in = amount_in_between(price, next)?;
+		let amount_out = match next == target {
+			true => amount_out_between(price, next)?,
+			// the price is moved at least as far as the remainder of the amount requires.
+			false => remaining,
+		};
+		let fee = mul_div(amount_in, fee, 1000u128.checked_sub(fee)?, Rounding::Up)?;
+		Some(SwapStep { sqrt_price: next, amount_in, amount_out, fee })
+	}
+}
+
+/// Returns the square root price after depositing `amount` into a range of `liquidity`.
+fn next_sqrt_price_from_input(
+	price: u128,
+	liquidity: u128,
+	amount: u128,
+	zero_for_one: bool,
+) -> Option<u128> {
+	if zero_for_one {
+		// L * P / (L + x * P), rounding up to not move the price further than the amount allows.
+		let denominator =
+			liquidity.checked_add(mul_div(amount, price, FixedU128::DIV, Rounding::Down)?)?;
+		mul_div(liquidity, price, denominator, Rounding::Up)
+	} else {
+		// P + y / L
+		price.checked_add(mul_div(amount, FixedU128::DIV, liquidity, Rounding::Down)?)
+	}
+}
+
+/// Returns the square root price after withdrawing `amount` from a range of `liquidity`.
+fn next_sqrt_price_from_output(
+	price: u128,
+	liquidity: u128,
+	amount: u128,
+	zero_for_one: bool,
+) -> Option<u128> {
+	if zero_for_one {
+		// P - y / L
+		price.checked_sub(mul_div(amount, FixedU128::DIV, liquidity, Rounding::Up)?)
+	} else {
+		// L * P / (L - x * P)
+		let denominator =
+			liquidity.checked_sub(mul_div(amount, price, FixedU128::DIV, Rounding::Up)?)?;
+		mul_div(liquidity, price, denominator, Rounding::Up)
+	}
+}
+
+/// The amount of `asset1` held by `liquidity` between the square root prices `a` and `b`.
+fn amount1_delta(a: u128, b: u128, liquidity: u128, rounding: Rounding) -> Option<u128> {
+	let (lower, upper) = if a < b { (a, b) } else { (b, a) };
+	// L * (upper - lower) / (upper * lower)
+	let amount = mul_div(liquidity, upper - lower, upper, rounding)?;
+	mul_div(amount, FixedU128::DIV, lower, rounding)
+}
+
+/// The amount of `asset2` held by `liquidity` between the square root prices `a` and `b`.
+fn amount2_delta(a: u128, b: u128, liquidity: u128, rounding: Rounding) -> Option<u128> {
+	let (lower, upper) = if a < b { (a, b) } else { (b, a) };
+	// L * (upper - lower)
+	mul_div(liquidity, upper - lower, FixedU128::DIV, rounding)
+}
+
+/// The amounts of `asset1` and `asset2` held by `liquidity` within the range from `lower` to
+/// `upper` at the square root price `price`.
+fn amounts_for_liquidity(
+	price: u128,
+	lower: u128,
+	upper: u128,
+	liquidity: u128,
+	rounding: Rounding,
+) -> Option<(u128, u128)> {
+	if price <= lower {
+		Some((amount1_delta(lower, upper, liquidity, rounding)?, 0))
+	} else if price < upper {
+		Some((
+			amount1_delta(price, upper, liquidity, rounding)?,
+			amount2_delta(lower, price, liquidity, rounding)?,
+		))
+	} else {
+		Some((0, amount2_delta(lower, upper, liquidity, rounding)?))
+	}
+}
+
+/// The greatest liquidity within the range from `lower` to `upper` at the square root price
+/// `price` that the amounts of `asset1` and `asset2` can provide.
+fn liquidity_for_amounts(
+	price: u128,
+	lower: u128,
+	upper: u128,
+	amount1: u128,
+	amount2: u128,
+) -> Option<u128> {
+	// x * lower * upper / (upper - lower)
+	let liquidity1 = |lower, upper| {
+		let amount = mul_div(amount1, lower, FixedU128::DIV, Rounding::Down)?;
+		mul_div(amount, upper, upper - lower, Rounding::Down)
+	};
+	// y / (upper - lower)
+	let liquidity2 = |lower, upper| mul_div(amount2, FixedU128::DIV, upper - lower, Rounding::Down);
+
+	if price <= lower {
+		liquidity1(lower, upper)
+	} else if price < upper {
+		Some(liquidity1(price, upper)?.min(liquidity2(lower, price)?))
+	} else {
+		liquidity2(lower, upper)
+	}
+}
+
+/// `a * b / c` with the given `rounding`, or `None` on overflow or division by zero.
+fn mul_div(a: u128, b: u128, c: u128, rounding: Rounding) -> Option<u128> {
+	multiply_by_rational_with_rounding(a, b, c, rounding)
+}
+
+/// Fee growths are meaningful as differences only, hence they are allowed to wrap around.
+fn wrapping_add(a: FixedU128, b: FixedU128) -> FixedU128 {
+	FixedU128::from_inner(a.into_inner().wrapping_add(b.into_inner()))
+}
+
+fn wrapping_sub(a: FixedU128, b: FixedU128) -> FixedU128 {
+	FixedU128::from_inner(a.into_inner().wrapping_sub(b.into_inner()))
+}
@@ -34,6 +34,16 @@
 //!    a runtime call endpoint
 //!  - [query the size of a liquidity pool](`AssetConversionApi::get_reserves`) via a runtime api
 //!    endpoint.
+//!  - [create a concentrated liquidity pool](`Pallet::create_concentrated_pool()`) for 2 assets,
+//!    [provide liquidity](`Pallet::add_concentrated_liquidity()`) to it within a price range and
+//!    [collect the fees](`Pallet::collect_concentrated_fees()`) the liquidity earns
+//!  - [query the best route](`AssetConversionApi::quote_best_route_exact_tokens_for_tokens`) across
+//!    the pools for a swap via a runtime api endpoint.
+//!  - [create a stable swap pool](`Pallet::create_stable_pool()`) for 2 or more assets trading
+//!    close to parity, and [provide the liquidity](`Pallet::add_stable_liquidity()`) to it.
+//!
+//! Every hop of a swap is routed through whichever of the constant product, the concentrated
+//! liquidity and the stable swap pools of the asset pair yields the best rate.
 //!
 //! The `quote_price_exact_tokens_for_tokens` and `quote_price_tokens_for_exact_tokens` functions
 //! both take a path parameter of the route to take. If you want to swap from native asset to
@@ -56,8 +66,10 @@
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+mod concentrated;
 #[cfg(test)]
 mod mock;
+mod stable;
 mod swap;
 #[cfg(test)]
 mod tests;
@@ -65,7 +77,9 @@ mod types;
 pub mod weights;
 #[cfg(feature = "runtime-benchmarks")]
 pub use benchmarking::{BenchmarkHelper, NativeOrWithIdFactory};
+pub use concentrated::*;
 pub use pallet::*;
+pub use stable::*;
 pub use swap::*;
 pub use types::*;
 pub use weights::WeightInfo;
@@ -93,7 +107,7 @@ use sp_runtime::{
 	},
 	DispatchError, Saturating, TokenError, TransactionOutcome,
 };
-use sp_std::{boxed::Box, collections::btree_set::BTreeSet, vec::Vec};
+use sp_std::{boxed::Box, collections::btree_set::BTreeSet, vec, vec::Vec};
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -183,6 +197,20 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxSwapPathLength: Get<u32>;
 
+		/// The max number of initialized ticks of a concentrated liquidity pool.
+		///
+		/// It bounds the number of ticks a swap through the pool may cross.
+		#[pallet::constant]
+		type MaxTicks: Get<u32>;
+
+		/// The origin allowed to create stable swap pools and to set their amplification
+		/// coefficient.
+		type StablePoolOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The max number of assets in a stable swap pool.
+		#[pallet::constant]
+		type MaxStableAssets: Get<u32>;
+
 		/// The pallet's id, used for deriving its sovereign account ID.
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
@@ -206,6 +234,62 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type NextPoolAssetId<T: Config> = StorageValue<_, T::PoolAssetId, OptionQuery>;
 
+	/// Map from `PoolId` to the state of the concentrated liquidity pool of the asset pair.
+	#[pallet::storage]
+	pub type ConcentratedPools<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::PoolId, ConcentratedPoolInfoOf<T>, OptionQuery>;
+
+	/// The initialized ticks of a concentrated liquidity pool, in ascending order.
+	#[pallet::storage]
+	pub type InitializedTicks<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::PoolId, BoundedVec<i32, T::MaxTicks>, ValueQuery>;
+
+	/// The state of the initialized ticks of a concentrated liquidity pool.
+	#[pallet::storage]
+	pub type ConcentratedTicks<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::PoolId,
+		Twox64Concat,
+		i32,
+		TickInfo<T::Balance>,
+		OptionQuery,
+	>;
+
+	/// The liquidity positions in a concentrated liquidity pool, keyed by the owner and the lower
+	/// and upper ticks of the range.
+	#[pallet::storage]
+	pub type ConcentratedPositions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::PoolId,
+		Blake2_128Concat,
+		(T::AccountId, i32, i32),
+		ConcentratedPosition<T::Balance>,
+		OptionQuery,
+	>;
+
+	/// Map from `StablePoolId` to the state of the stable swap pool.
+	#[pallet::storage]
+	pub type StablePools<T: Config> =
+		StorageMap<_, Twox64Concat, StablePoolId, StablePoolInfoOf<T>, OptionQuery>;
+
+	/// Stores the `StablePoolId` that is going to be used for the next stable swap pool.
+	#[pallet::storage]
+	pub type NextStablePoolId<T: Config> = StorageValue<_, StablePoolId, ValueQuery>;
+
+	/// The stable swap pool holding both assets of a pair, in either order.
+	#[pallet::storage]
+	pub type StablePairs<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetKind,
+		Blake2_128Concat,
+		T::AssetKind,
+		StablePoolId,
+		OptionQuery,
+	>;
+
 	// Pallet's events.
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -293,6 +377,128 @@ pub mod pallet {
 			/// The account initiating the touch.
 			who: T::AccountId,
 		},
+		/// A successful call of the `CreateConcentratedPool` extrinsic will create this event.
+		ConcentratedPoolCreated {
+			/// The account that created the pool.
+			creator: T::AccountId,
+			/// The pool id associated with the pool.
+			pool_id: T::PoolId,
+			/// The account ID of the pool.
+			pool_account: T::AccountId,
+			/// The initial tick of the pool.
+			tick: i32,
+		},
+		/// A successful call of the `AddConcentratedLiquidity` extrinsic will create this event.
+		ConcentratedLiquidityAdded {
+			/// The account that the liquidity was taken from and that owns the position.
+			who: T::AccountId,
+			/// The pool id of the pool that the liquidity was added to.
+			pool_id: T::PoolId,
+			/// The lower tick of the range of the position.
+			tick_lower: i32,
+			/// The upper tick of the range of the position.
+			tick_upper: i32,
+			/// The liquidity added to the position.
+			liquidity: T::Balance,
+			/// The amount of the first asset that was added to the pool.
+			amount1: T::Balance,
+			/// The amount of the second asset that was added to the pool.
+			amount2: T::Balance,
+		},
+		/// A successful call of the `RemoveConcentratedLiquidity` extrinsic will create this
+		/// event.
+		ConcentratedLiquidityRemoved {
+			/// The account that owns the position.
+			who: T::AccountId,
+			/// The account that the assets were transferred to.
+			withdraw_to: T::AccountId,
+			/// The pool id that the liquidity was removed from.
+			pool_id: T::PoolId,
+			/// The lower tick of the range of the position.
+			tick_lower: i32,
+			/// The upper tick of the range of the position.
+			tick_upper: i32,
+			/// The liquidity removed from the position.
+			liquidity: T::Balance,
+			/// The amount of the first asset that was removed from the pool, fees included.
+			amount1: T::Balance,
+			/// The amount of the second asset that was removed from the pool, fees included.
+			amount2: T::Balance,
+		},
+		/// A successful call of the `CollectConcentratedFees` extrinsic will create this event.
+		ConcentratedFeesCollected {
+			/// The account that owns the position.
+			who: T::AccountId,
+			/// The account that the fees were transferred to.
+			withdraw_to: T::AccountId,
+			/// The pool id of the pool that the fees were earned in.
+			pool_id: T::PoolId,
+			/// The lower tick of the range of the position.
+			tick_lower: i32,
+			/// The upper tick of the range of the position.
+			tick_upper: i32,
+			/// The fees collected in the first asset.
+			amount1: T::Balance,
+			/// The fees collected in the second asset.
+			amount2: T::Balance,
+		},
+		/// A successful call of the `CreateStablePool` extrinsic will create this event.
+		StablePoolCreated {
+			/// The pool id associated with the pool.
+			pool_id: StablePoolId,
+			/// The account ID of the pool.
+			pool_account: T::AccountId,
+			/// The assets of the pool.
+			assets: Vec<T::AssetKind>,
+			/// The id of the liquidity tokens that will be minted when assets are added to this
+			/// pool.
+			lp_token: T::PoolAssetId,
+			/// The amplification coefficient of the pool.
+			amplification: u32,
+		},
+		/// A successful call of the `AddStableLiquidity` extrinsic will create this event.
+		StableLiquidityAdded {
+			/// The account that the liquidity was taken from.
+			who: T::AccountId,
+			/// The account that the liquidity tokens were minted to.
+			mint_to: T::AccountId,
+			/// The pool id of the pool that the liquidity was added to.
+			pool_id: StablePoolId,
+			/// The amounts of the assets of the pool that were added to it.
+			amounts: Vec<T::Balance>,
+			/// The id of the lp token that was minted.
+			lp_token: T::PoolAssetId,
+			/// The amount of lp tokens that were minted of that id.
+			lp_token_minted: T::Balance,
+		},
+		/// A successful call of the `RemoveStableLiquidity` extrinsic will create this event.
+		StableLiquidityRemoved {
+			/// The account that the liquidity tokens were burned from.
+			who: T::AccountId,
+			/// The account that the assets were transferred to.
+			withdraw_to: T::AccountId,
+			/// The pool id that the liquidity was removed from.
+			pool_id: StablePoolId,
+			/// The amounts of the assets of the pool that were removed from it.
+			amounts: Vec<T::Balance>,
+			/// The id of the lp token that was burned.
+			lp_token: T::PoolAssetId,
+			/// The amount of lp tokens that were burned of that id.
+			lp_token_burned: T::Balance,
+			/// Liquidity withdrawal fee (%).
+			withdrawal_fee: Permill,
+		},
+		/// The amplification coefficient of a stable swap pool started ramping.
+		StableAmplificationRampStarted {
+			/// The pool id of the pool.
+			pool_id: StablePoolId,
+			/// The amplification coefficient at the start of the ramp.
+			initial_amplification: u32,
+			/// The amplification coefficient at the end of the ramp.
+			future_amplification: u32,
+			/// The block the ramp ends at.
+			ramp_end: BlockNumberFor<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -346,6 +552,25 @@ pub mod pallet {
 		IncorrectPoolAssetId,
 		/// The destination account cannot exist with the swapped funds.
 		BelowMinimum,
+		/// The tick is out of bounds, or the lower tick of a range isn't below the upper one.
+		InvalidTick,
+		/// The concentrated liquidity pool has too many initialized ticks.
+		TooManyTicks,
+		/// The concentrated liquidity pool doesn't have enough liquidity for the swap.
+		InsufficientLiquidity,
+		/// The concentrated liquidity position doesn't exist.
+		PositionNotFound,
+		/// The concentrated liquidity position doesn't have enough liquidity.
+		InsufficientPositionLiquidity,
+		/// A stable swap pool must consist of 2 to `MaxStableAssets` unique assets.
+		InvalidStableAssets,
+		/// The amounts don't match the assets of the stable swap pool.
+		InvalidStableAmounts,
+		/// The amplification coefficient is out of bounds, changes too much at once, or its ramp
+		/// ends in the past.
+		InvalidAmplification,
+		/// The minimal amount requirement for an asset of the stable swap pool wasn't met.
+		WithdrawalDidNotMeetMinimum,
 	}
 
 	#[pallet::hooks]
@@ -355,6 +580,11 @@ pub mod pallet {
 				T::MaxSwapPathLength::get() > 1,
 				"the `MaxSwapPathLength` should be greater than 1",
 			);
+			assert!(T::MaxTicks::get() > 1, "the `MaxTicks` should be greater than 1");
+			assert!(
+				T::MaxStableAssets::get() > 1,
+				"the `MaxStableAssets` should be greater than 1",
+			);
 		}
 	}
 
@@ -622,7 +852,10 @@ pub mod pallet {
 		/// [`AssetConversionApi::quote_price_exact_tokens_for_tokens`] runtime call can be called
 		/// for a quote.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32))]
+		#[pallet::weight(Pallet::<T>::max_swap_weight(
+			T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32),
+			path.len() as u32,
+		))]
 		pub fn swap_exact_tokens_for_tokens(
 			origin: OriginFor<T>,
 			path: Vec<Box<T::AssetKind>>,
@@ -630,9 +863,10 @@ pub mod pallet {
 			amount_out_min: T::Balance,
 			send_to: T::AccountId,
 			keep_alive: bool,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
-			Self::do_swap_exact_tokens_for_tokens(
+			let path_len = path.len() as u32;
+			let (_, hops_weight) = Self::do_swap_exact_tokens_for_tokens(
 				sender,
 				path.into_iter().map(|a| *a).collect(),
 				amount_in,
@@ -640,7 +874,10 @@ pub mod pallet {
 				send_to,
 				keep_alive,
 			)?;
-			Ok(())
+			Ok(Some(
+				T::WeightInfo::swap_exact_tokens_for_tokens(path_len).saturating_add(hops_weight),
+			)
+			.into())
 		}
 
 		/// Swap any amount of `asset1` to get the exact amount of `asset2`.
@@ -650,7 +887,10 @@ pub mod pallet {
 		/// [`AssetConversionApi::quote_price_tokens_for_exact_tokens`] runtime call can be called
 		/// for a quote.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::swap_tokens_for_exact_tokens(path.len() as u32))]
+		#[pallet::weight(Pallet::<T>::max_swap_weight(
+			T::WeightInfo::swap_tokens_for_exact_tokens(path.len() as u32),
+			path.len() as u32,
+		))]
 		pub fn swap_tokens_for_exact_tokens(
 			origin: OriginFor<T>,
 			path: Vec<Box<T::AssetKind>>,
@@ -658,9 +898,10 @@ pub mod pallet {
 			amount_in_max: T::Balance,
 			send_to: T::AccountId,
 			keep_alive: bool,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
-			Self::do_swap_tokens_for_exact_tokens(
+			let path_len = path.len() as u32;
+			let (_, hops_weight) = Self::do_swap_tokens_for_exact_tokens(
 				sender,
 				path.into_iter().map(|a| *a).collect(),
 				amount_out,
@@ -668,7 +909,10 @@ pub mod pallet {
 				send_to,
 				keep_alive,
 			)?;
-			Ok(())
+			Ok(Some(
+				T::WeightInfo::swap_tokens_for_exact_tokens(path_len).saturating_add(hops_weight),
+			)
+			.into())
 		}
 
 		/// Touch an existing pool to fulfill prerequisites before providing liquidity, such as
@@ -713,6 +957,200 @@ pub mod pallet {
 			Self::deposit_event(Event::Touched { pool_id, who });
 			Ok(Some(T::WeightInfo::touch(refunds_number)).into())
 		}
+
+		/// Creates an empty concentrated liquidity pool of `asset1` and `asset2`, with the price
+		/// of `asset1` in `asset2` set at `tick`.
+		///
+		/// The pool exists alongside the constant product pool of the same assets, and swaps are
+		/// routed through whichever of the two yields the better rate. Ticks of the ranges of
+		/// the positions in the pool are expressed for the assets in the order they are provided
+		/// here, which the other concentrated liquidity calls expect as well.
+		///
+		/// Once a pool is created, someone may [`Pallet::add_concentrated_liquidity`] to it.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::create_concentrated_pool())]
+		pub fn create_concentrated_pool(
+			origin: OriginFor<T>,
+			asset1: Box<T::AssetKind>,
+			asset2: Box<T::AssetKind>,
+			tick: i32,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_create_concentrated_pool(sender, *asset1, *asset2, tick)
+		}
+
+		/// Provide liquidity into the concentrated liquidity pool of `asset1` and `asset2`
+		/// within the price range from `tick_lower` to `tick_upper`.
+		///
+		/// The greatest liquidity that `amount1_desired` and `amount2_desired` can provide at the
+		/// current price is added to the position of the sender for the range. The amounts
+		/// actually taken may be lower, thus you should provide the min amounts you're happy to
+		/// provide with `amount1_min`/`amount2_min`.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::add_concentrated_liquidity())]
+		pub fn add_concentrated_liquidity(
+			origin: OriginFor<T>,
+			asset1: Box<T::AssetKind>,
+			asset2: Box<T::AssetKind>,
+			tick_lower: i32,
+			tick_upper: i32,
+			amount1_desired: T::Balance,
+			amount2_desired: T::Balance,
+			amount1_min: T::Balance,
+			amount2_min: T::Balance,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_add_concentrated_liquidity(
+				sender,
+				*asset1,
+				*asset2,
+				tick_lower,
+				tick_upper,
+				(amount1_desired, amount2_desired),
+				(amount1_min, amount2_min),
+			)
+		}
+
+		/// Remove `liquidity` from the position of the sender within the price range from
+		/// `tick_lower` to `tick_upper` in the concentrated liquidity pool of `asset1` and
+		/// `asset2`.
+		///
+		/// The assets backing the liquidity, along with all the fees earned by the position, are
+		/// transferred to `withdraw_to`. `amount1_min_receive`/`amount2_min_receive` bound the
+		/// amounts backing the liquidity.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::remove_concentrated_liquidity())]
+		pub fn remove_concentrated_liquidity(
+			origin: OriginFor<T>,
+			asset1: Box<T::AssetKind>,
+			asset2: Box<T::AssetKind>,
+			tick_lower: i32,
+			tick_upper: i32,
+			liquidity: T::Balance,
+			amount1_min_receive: T::Balance,
+			amount2_min_receive: T::Balance,
+			withdraw_to: T::AccountId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_remove_concentrated_liquidity(
+				sender,
+				*asset1,
+				*asset2,
+				tick_lower,
+				tick_upper,
+				liquidity,
+				(amount1_min_receive, amount2_min_receive),
+				withdraw_to,
+			)
+		}
+
+		/// Transfer the fees earned by the position of the sender within the price range from
+		/// `tick_lower` to `tick_upper` in the concentrated liquidity pool of `asset1` and
+		/// `asset2` to `withdraw_to`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::collect_concentrated_fees())]
+		pub fn collect_concentrated_fees(
+			origin: OriginFor<T>,
+			asset1: Box<T::AssetKind>,
+			asset2: Box<T::AssetKind>,
+			tick_lower: i32,
+			tick_upper: i32,
+			withdraw_to: T::AccountId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_collect_concentrated_fees(
+				sender,
+				*asset1,
+				*asset2,
+				tick_lower,
+				tick_upper,
+				withdraw_to,
+			)
+		}
+
+		/// Creates an empty stable swap pool of the `assets` with the `amplification` coefficient,
+		/// and an associated new `lp_token` asset (the id of which is returned in the
+		/// `Event::StablePoolCreated` event).
+		///
+		/// An asset pair may be held by a single stable swap pool, alongside its constant product
+		/// and concentrated liquidity pools. Swaps are routed through whichever of them yields
+		/// the best rate.
+		///
+		/// The dispatch origin for this call must be [`Config::StablePoolOrigin`].
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::create_stable_pool(assets.len() as u32))]
+		pub fn create_stable_pool(
+			origin: OriginFor<T>,
+			assets: Vec<Box<T::AssetKind>>,
+			amplification: u32,
+		) -> DispatchResult {
+			T::StablePoolOrigin::ensure_origin(origin)?;
+			Self::do_create_stable_pool(assets.into_iter().map(|a| *a).collect(), amplification)
+		}
+
+		/// Provide liquidity into the stable swap pool with `pool_id`.
+		///
+		/// The `amounts` of the assets of the pool, in the order of the assets of the pool, may be
+		/// imbalanced, in which case the part of the deposit deviating from the balance of the
+		/// pool is charged the swap fee. The first deposit must provide all the assets.
+		/// `mint_to` will be sent the liquidity tokens that represent this share of the pool, of
+		/// which there must be at least `min_lp_token_minted`.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::add_stable_liquidity(amounts.len() as u32))]
+		pub fn add_stable_liquidity(
+			origin: OriginFor<T>,
+			pool_id: StablePoolId,
+			amounts: Vec<T::Balance>,
+			min_lp_token_minted: T::Balance,
+			mint_to: T::AccountId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_add_stable_liquidity(sender, pool_id, amounts, min_lp_token_minted, mint_to)
+		}
+
+		/// Allows you to remove liquidity from the stable swap pool with `pool_id` by providing
+		/// the `lp_token_burn` tokens that will be burned in the process. The assets of the pool
+		/// are withdrawn in proportion to its reserves. With the usage of `amounts_min_receive`,
+		/// in the order of the assets of the pool, it's possible to control the min amounts of
+		/// returned tokens you're happy with.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::remove_stable_liquidity(amounts_min_receive.len() as u32))]
+		pub fn remove_stable_liquidity(
+			origin: OriginFor<T>,
+			pool_id: StablePoolId,
+			lp_token_burn: T::Balance,
+			amounts_min_receive: Vec<T::Balance>,
+			withdraw_to: T::AccountId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_remove_stable_liquidity(
+				sender,
+				pool_id,
+				lp_token_burn,
+				amounts_min_receive,
+				withdraw_to,
+			)
+		}
+
+		/// Ramp the amplification coefficient of the stable swap pool with `pool_id` linearly
+		/// from its current value to `amplification`, reached at the `ramp_end` block.
+		///
+		/// A single ramp may change the coefficient by a factor of
+		/// [`MAX_AMPLIFICATION_CHANGE`] at most. A ramp ending at the current block sets the
+		/// coefficient at once.
+		///
+		/// The dispatch origin for this call must be [`Config::StablePoolOrigin`].
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::set_stable_amplification())]
+		pub fn set_stable_amplification(
+			origin: OriginFor<T>,
+			pool_id: StablePoolId,
+			amplification: u32,
+			ramp_end: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::StablePoolOrigin::ensure_origin(origin)?;
+			Self::do_set_stable_amplification(pool_id, amplification, ramp_end)
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -723,7 +1161,9 @@ pub mod pallet {
 		/// Withdraws the `path[0]` asset from `sender`, deposits the `path[1]` asset to `send_to`,
 		/// respecting `keep_alive`.
 		///
-		/// If successful, returns the amount of `path[1]` acquired for the `amount_in`.
+		/// If successful, returns the amount of `path[1]` acquired for the `amount_in`, along with
+		/// the weight of the hops through the concentrated liquidity and stable swap pools on top
+		/// of the weight of a swap through constant product pools.
 		///
 		/// WARNING: This may return an error after a partial storage mutation. It should be used
 		/// only inside a transactional storage context and an Err result must imply a storage
@@ -735,14 +1175,14 @@ pub mod pallet {
 			amount_out_min: Option<T::Balance>,
 			send_to: T::AccountId,
 			keep_alive: bool,
-		) -> Result<T::Balance, DispatchError> {
+		) -> Result<(T::Balance, Weight), DispatchError> {
 			ensure!(amount_in > Zero::zero(), Error::<T>::ZeroAmount);
 			if let Some(amount_out_min) = amount_out_min {
 				ensure!(amount_out_min > Zero::zero(), Error::<T>::ZeroAmount);
 			}
 
 			Self::validate_swap_path(&path)?;
-			let path = Self::balance_path_from_amount_in(amount_in, path)?;
+			let (path, hops) = Self::balance_path_from_amount_in(amount_in, path)?;
 
 			let amount_out = path.last().map(|(_, a)| *a).ok_or(Error::<T>::InvalidPath)?;
 			if let Some(amount_out_min) = amount_out_min {
@@ -752,7 +1192,7 @@ pub mod pallet {
 				);
 			}
 
-			Self::swap(&sender, &path, &send_to, keep_alive)?;
+			Self::swap(&sender, &path, &hops, &send_to, keep_alive)?;
 
 			Self::deposit_event(Event::SwapExecuted {
 				who: sender,
@@ -761,7 +1201,7 @@ pub mod pallet {
 				amount_out,
 				path,
 			});
-			Ok(amount_out)
+			Ok((amount_out, Self::hops_weight(&hops)))
 		}
 
 		/// Take the `path[0]` asset and swap some amount for `amount_out` of the `path[1]`. If an
@@ -771,7 +1211,9 @@ pub mod pallet {
 		/// Withdraws `path[0]` asset from `sender`, deposits the `path[1]` asset to `send_to`,
 		/// respecting `keep_alive`.
 		///
-		/// If successful returns the amount of the `path[0]` taken to provide `path[1]`.
+		/// If successful returns the amount of the `path[0]` taken to provide `path[1]`, along with
+		/// the weight of the hops through the concentrated liquidity and stable swap pools on top
+		/// of the weight of a swap through constant product pools.
 		///
 		/// WARNING: This may return an error after a partial storage mutation. It should be used
 		/// only inside a transactional storage context and an Err result must imply a storage
@@ -783,14 +1225,14 @@ pub mod pallet {
 			amount_in_max: Option<T::Balance>,
 			send_to: T::AccountId,
 			keep_alive: bool,
-		) -> Result<T::Balance, DispatchError> {
+		) -> Result<(T::Balance, Weight), DispatchError> {
 			ensure!(amount_out > Zero::zero(), Error::<T>::ZeroAmount);
 			if let Some(amount_in_max) = amount_in_max {
 				ensure!(amount_in_max > Zero::zero(), Error::<T>::ZeroAmount);
 			}
 
 			Self::validate_swap_path(&path)?;
-			let path = Self::balance_path_from_amount_out(amount_out, path)?;
+			let (path, hops) = Self::balance_path_from_amount_out(amount_out, path)?;
 
 			let amount_in = path.first().map(|(_, a)| *a).ok_or(Error::<T>::InvalidPath)?;
 			if let Some(amount_in_max) = amount_in_max {
@@ -800,7 +1242,7 @@ pub mod pallet {
 				);
 			}
 
-			Self::swap(&sender, &path, &send_to, keep_alive)?;
+			Self::swap(&sender, &path, &hops, &send_to, keep_alive)?;
 
 			Self::deposit_event(Event::SwapExecuted {
 				who: sender,
@@ -810,7 +1252,7 @@ pub mod pallet {
 				path,
 			});
 
-			Ok(amount_in)
+			Ok((amount_in, Self::hops_weight(&hops)))
 		}
 
 		/// Swap exactly `credit_in` of asset `path[0]` for asset `path[last]`.  If `amount_out_min`
@@ -838,21 +1280,21 @@ pub mod pallet {
 				ensure!(amount_out_min.map_or(true, |a| !a.is_zero()), Error::<T>::ZeroAmount);
 
 				Self::validate_swap_path(&path)?;
-				let path = Self::balance_path_from_amount_in(amount_in, path)?;
+				let (path, hops) = Self::balance_path_from_amount_in(amount_in, path)?;
 
 				let amount_out = path.last().map(|(_, a)| *a).ok_or(Error::<T>::InvalidPath)?;
 				ensure!(
 					amount_out_min.map_or(true, |a| amount_out >= a),
 					Error::<T>::ProvidedMinimumNotSufficientForSwap
 				);
-				Ok((path, amount_out))
+				Ok((path, hops, amount_out))
 			};
-			let (path, amount_out) = match inspect_path(credit_in.asset()) {
-				Ok((p, a)) => (p, a),
+			let (path, hops, amount_out) = match inspect_path(credit_in.asset()) {
+				Ok((p, h, a)) => (p, h, a),
 				Err(e) => return Err((credit_in, e)),
 			};
 
-			let credit_out = Self::credit_swap(credit_in, &path)?;
+			let credit_out = Self::credit_swap(credit_in, &path, &hops)?;
 
 			Self::deposit_event(Event::SwapCreditExecuted { amount_in, amount_out, path });
 
@@ -886,7 +1328,7 @@ pub mod pallet {
 				ensure!(amount_out > Zero::zero(), Error::<T>::ZeroAmount);
 
 				Self::validate_swap_path(&path)?;
-				let path = Self::balance_path_from_amount_out(amount_out, path)?;
+				let (path, hops) = Self::balance_path_from_amount_out(amount_out, path)?;
 
 				let amount_in = path.first().map(|(_, a)| *a).ok_or(Error::<T>::InvalidPath)?;
 				ensure!(
@@ -894,22 +1336,23 @@ pub mod pallet {
 					Error::<T>::ProvidedMaximumNotSufficientForSwap
 				);
 
-				Ok((path, amount_in))
+				Ok((path, hops, amount_in))
 			};
-			let (path, amount_in) = match inspect_path(credit_in.asset()) {
-				Ok((p, a)) => (p, a),
+			let (path, hops, amount_in) = match inspect_path(credit_in.asset()) {
+				Ok((p, h, a)) => (p, h, a),
 				Err(e) => return Err((credit_in, e)),
 			};
 
 			let (credit_in, credit_change) = credit_in.split(amount_in);
-			let credit_out = Self::credit_swap(credit_in, &path)?;
+			let credit_out = Self::credit_swap(credit_in, &path, &hops)?;
 
 			Self::deposit_event(Event::SwapCreditExecuted { amount_in, amount_out, path });
 
 			Ok((credit_out, credit_change))
 		}
 
-		/// Swap assets along the `path`, withdrawing from `sender` and depositing in `send_to`.
+		/// Swap assets along the `path`, through the pools of the `hops`, withdrawing from `sender`
+		/// and depositing in `send_to`.
 		///
 		/// Note: It's assumed that the provided `path` is valid.
 		///
@@ -919,25 +1362,26 @@ pub mod pallet {
 		fn swap(
 			sender: &T::AccountId,
 			path: &BalancePath<T>,
+			hops: &[Hop<T>],
 			send_to: &T::AccountId,
 			keep_alive: bool,
 		) -> Result<(), DispatchError> {
 			let (asset_in, amount_in) = path.first().ok_or(Error::<T>::InvalidPath)?;
 			let credit_in = Self::withdraw(asset_in.clone(), sender, *amount_in, keep_alive)?;
 
-			let credit_out = Self::credit_swap(credit_in, path).map_err(|(_, e)| e)?;
+			let credit_out = Self::credit_swap(credit_in, path, hops).map_err(|(_, e)| e)?;
 			T::Assets::resolve(send_to, credit_out).map_err(|_| Error::<T>::BelowMinimum)?;
 
 			Ok(())
 		}
 
-		/// Swap assets along the specified `path`, consuming `credit_in` and producing
-		/// `credit_out`.
+		/// Swap assets along the specified `path`, through the pools of the `hops`, consuming
+		/// `credit_in` and producing `credit_out`.
 		///
 		/// If an error occurs, `credit_in` is returned back.
 		///
-		/// Note: It's assumed that the provided `path` is valid and `credit_in` corresponds to the
-		/// first asset in the `path`.
+		/// Note: It's assumed that the provided `path` is valid, `hops` have been priced for it and
+		/// `credit_in` corresponds to the first asset in the `path`.
 		///
 		/// WARNING: This may return an error after a partial storage mutation. It should be used
 		/// only inside a transactional storage context and an Err result must imply a storage
@@ -945,27 +1389,43 @@ pub mod pallet {
 		fn credit_swap(
 			credit_in: CreditOf<T>,
 			path: &BalancePath<T>,
+			hops: &[Hop<T>],
 		) -> Result<CreditOf<T>, (CreditOf<T>, DispatchError)> {
 			let resolve_path = || -> Result<CreditOf<T>, DispatchError> {
 				for pos in 0..=path.len() {
 					if let Some([(asset1, _), (asset2, amount_out)]) = path.get(pos..=pos + 1) {
-						let pool_from = T::PoolLocator::pool_address(asset1, asset2)
-							.map_err(|_| Error::<T>::InvalidAssetPair)?;
+						let hop = hops.get(pos).ok_or(Error::<T>::InvalidPath)?;
+						let pool_from = match hop {
+							Hop::ConstantProduct => T::PoolLocator::pool_address(asset1, asset2)
+								.map_err(|_| Error::<T>::InvalidAssetPair)?,
+							Hop::Concentrated(swap) => Self::apply_concentrated_swap(swap)?,
+							Hop::Stable { pool_id, .. } => Self::stable_pool_address(*pool_id)?,
+						};
+						// the concentrated liquidity pools keep track of their balances on their
+						// own, so their accounts are not kept alive.
+						let keep_alive = !matches!(hop, Hop::Concentrated(_));
 
 						if let Some((asset3, _)) = path.get(pos + 2) {
-							let pool_to = T::PoolLocator::pool_address(asset2, asset3)
-								.map_err(|_| Error::<T>::InvalidAssetPair)?;
+							let pool_to = Self::hop_address(
+								hops.get(pos + 1).ok_or(Error::<T>::InvalidPath)?,
+								asset2,
+								asset3,
+							)?;
 
 							T::Assets::transfer(
 								asset2.clone(),
 								&pool_from,
 								&pool_to,
 								*amount_out,
-								Preserve,
+								if keep_alive { Preserve } else { Expendable },
 							)?;
 						} else {
-							let credit_out =
-								Self::withdraw(asset2.clone(), &pool_from, *amount_out, true)?;
+							let credit_out = Self::withdraw(
+								asset2.clone(),
+								&pool_from,
+								*amount_out,
+								keep_alive,
+							)?;
 							return Ok(credit_out)
 						}
 					}
@@ -978,10 +1438,12 @@ pub mod pallet {
 				Err(e) => return Err((credit_in, e)),
 			};
 
-			let pool_to = if let Some([(asset1, _), (asset2, _)]) = path.get(0..2) {
-				match T::PoolLocator::pool_address(asset1, asset2) {
+			let pool_to = if let (Some([(asset1, _), (asset2, _)]), Some(hop)) =
+				(path.get(0..2), hops.first())
+			{
+				match Self::hop_address(hop, asset1, asset2) {
 					Ok(address) => address,
-					Err(_) => return Err((credit_in, Error::<T>::InvalidAssetPair.into())),
+					Err(e) => return Err((credit_in, e)),
 				}
 			} else {
 				return Err((credit_in, Error::<T>::InvalidPath.into()))
@@ -993,8 +1455,22 @@ pub mod pallet {
 			Ok(credit_out)
 		}
 
+		/// Returns the account of the pool the `hop` from `asset1` to `asset2` goes through.
+		fn hop_address(
+			hop: &Hop<T>,
+			asset1: &T::AssetKind,
+			asset2: &T::AssetKind,
+		) -> Result<T::AccountId, DispatchError> {
+			match hop {
+				Hop::ConstantProduct => T::PoolLocator::pool_address(asset1, asset2)
+					.map_err(|_| Error::<T>::InvalidAssetPair.into()),
+				Hop::Concentrated(swap) => Self::concentrated_pool_address(&swap.pool_id),
+				Hop::Stable { pool_id, .. } => Self::stable_pool_address(*pool_id),
+			}
+		}
+
 		/// Removes `value` balance of `asset` from `who` account if possible.
-		fn withdraw(
+		pub(crate) fn withdraw(
 			asset: T::AssetKind,
 			who: &T::AccountId,
 			value: T::Balance,
@@ -1015,7 +1491,7 @@ pub mod pallet {
 
 		/// Get the `owner`'s balance of `asset`, which could be the chain's native asset or another
 		/// fungible. Returns a value in the form of an `Balance`.
-		fn get_balance(owner: &T::AccountId, asset: T::AssetKind) -> T::Balance {
+		pub(crate) fn get_balance(owner: &T::AccountId, asset: T::AssetKind) -> T::Balance {
 			T::Assets::reducible_balance(asset, owner, Expendable, Polite)
 		}
 
@@ -1038,12 +1514,14 @@ pub mod pallet {
 			Ok((balance1, balance2))
 		}
 
-		/// Leading to an amount at the end of a `path`, get the required amounts in.
+		/// Leading to an amount at the end of a `path`, get the required amounts in, along with
+		/// the pools to route each hop through.
 		pub(crate) fn balance_path_from_amount_out(
 			amount_out: T::Balance,
 			path: Vec<T::AssetKind>,
-		) -> Result<BalancePath<T>, DispatchError> {
+		) -> Result<(BalancePath<T>, Vec<Hop<T>>), DispatchError> {
 			let mut balance_path: BalancePath<T> = Vec::with_capacity(path.len());
+			let mut hops = Vec::with_capacity(path.len().saturating_sub(1));
 			let mut amount_in: T::Balance = amount_out;
 
 			let mut iter = path.into_iter().rev().peekable();
@@ -1055,21 +1533,25 @@ pub mod pallet {
 						break
 					},
 				};
-				let (reserve_in, reserve_out) = Self::get_reserves(asset1.clone(), asset2.clone())?;
+				let (amount, hop) = Self::price_hop_for_amount_out(asset1, &asset2, amount_in)?;
 				balance_path.push((asset2, amount_in));
-				amount_in = Self::get_amount_in(&amount_in, &reserve_in, &reserve_out)?;
+				hops.push(hop);
+				amount_in = amount;
 			}
 			balance_path.reverse();
+			hops.reverse();
 
-			Ok(balance_path)
+			Ok((balance_path, hops))
 		}
 
-		/// Following an amount into a `path`, get the corresponding amounts out.
+		/// Following an amount into a `path`, get the corresponding amounts out, along with the
+		/// pools to route each hop through.
 		pub(crate) fn balance_path_from_amount_in(
 			amount_in: T::Balance,
 			path: Vec<T::AssetKind>,
-		) -> Result<BalancePath<T>, DispatchError> {
+		) -> Result<(BalancePath<T>, Vec<Hop<T>>), DispatchError> {
 			let mut balance_path: BalancePath<T> = Vec::with_capacity(path.len());
+			let mut hops = Vec::with_capacity(path.len().saturating_sub(1));
 			let mut amount_out: T::Balance = amount_in;
 
 			let mut iter = path.into_iter().peekable();
@@ -1081,55 +1563,169 @@ pub mod pallet {
 						break
 					},
 				};
-				let (reserve_in, reserve_out) = Self::get_reserves(asset1.clone(), asset2.clone())?;
+				let (amount, hop) = Self::price_hop_for_amount_in(&asset1, asset2, amount_out)?;
 				balance_path.push((asset1, amount_out));
-				amount_out = Self::get_amount_out(&amount_out, &reserve_in, &reserve_out)?;
+				hops.push(hop);
+				amount_out = amount;
+			}
+			Ok((balance_path, hops))
+		}
+
+		/// Get the amount of `asset_in` required to acquire `amount_out` of `asset_out`, routed
+		/// through whichever of the constant product, concentrated liquidity and stable swap pools
+		/// of the pair requires less.
+		fn price_hop_for_amount_out(
+			asset_in: &T::AssetKind,
+			asset_out: &T::AssetKind,
+			amount_out: T::Balance,
+		) -> Result<(T::Balance, Hop<T>), DispatchError> {
+			let constant_product = Self::get_reserves(asset_in.clone(), asset_out.clone())
+				.and_then(|(reserve_in, reserve_out)| {
+					Self::get_amount_in(&amount_out, &reserve_in, &reserve_out)
+				})
+				.map(|amount_in| (amount_in, Hop::ConstantProduct))
+				.map_err(Into::into);
+			let concentrated =
+				Self::price_concentrated_swap(asset_in, asset_out, amount_out, false)
+					.map(|swap| (swap.amount_in, Hop::Concentrated(swap)));
+			let stable =
+				Self::price_stable_swap(asset_in, asset_out, amount_out, false, T::LPFee::get())
+					.map(|(amount_in, pool_id, assets)| {
+						(amount_in, Hop::Stable { pool_id, assets })
+					});
+			Self::best_hop([constant_product, concentrated, stable], |a, b| a < b)
+		}
+
+		/// Get the amount of `asset_out` acquired for `amount_in` of `asset_in`, routed through
+		/// whichever of the constant product, concentrated liquidity and stable swap pools of the
+		/// pair yields more.
+		fn price_hop_for_amount_in(
+			asset_in: &T::AssetKind,
+			asset_out: &T::AssetKind,
+			amount_in: T::Balance,
+		) -> Result<(T::Balance, Hop<T>), DispatchError> {
+			let constant_product = Self::get_reserves(asset_in.clone(), asset_out.clone())
+				.and_then(|(reserve_in, reserve_out)| {
+					Self::get_amount_out(&amount_in, &reserve_in, &reserve_out)
+				})
+				.map(|amount_out| (amount_out, Hop::ConstantProduct))
+				.map_err(Into::into);
+			let concentrated = Self::price_concentrated_swap(asset_in, asset_out, amount_in, true)
+				.map(|swap| (swap.amount_out, Hop::Concentrated(swap)));
+			let stable =
+				Self::price_stable_swap(asset_in, asset_out, amount_in, true, T::LPFee::get()).map(
+					|(amount_out, pool_id, assets)| (amount_out, Hop::Stable { pool_id, assets }),
+				);
+			Self::best_hop([constant_product, concentrated, stable], |a, b| a > b)
+		}
+
+		/// Picks the best of the priced hops through the pools of a pair, preferring the earlier
+		/// ones on a tie.
+		///
+		/// If none of the pools can be swapped through, the error of the first pool that exists
+		/// tells why.
+		fn best_hop(
+			candidates: [Result<(T::Balance, Hop<T>), DispatchError>; 3],
+			is_better: impl Fn(&T::Balance, &T::Balance) -> bool,
+		) -> Result<(T::Balance, Hop<T>), DispatchError> {
+			let missing = |e: &DispatchError| {
+				[Error::<T>::PoolNotFound, Error::<T>::InvalidAssetPair]
+					.into_iter()
+					.any(|missing| *e == DispatchError::from(missing))
+			};
+			let mut best: Option<(T::Balance, Hop<T>)> = None;
+			let mut error: Option<DispatchError> = None;
+			for candidate in candidates {
+				match candidate {
+					Ok((amount, hop)) => match best {
+						Some((ref best_amount, _)) if !is_better(&amount, best_amount) => {},
+						_ => best = Some((amount, hop)),
+					},
+					Err(e) => match error {
+						Some(ref first) if !missing(first) || missing(&e) => {},
+						_ => error = Some(e),
+					},
+				}
 			}
-			Ok(balance_path)
+			best.ok_or_else(|| error.unwrap_or_else(|| Error::<T>::PoolNotFound.into()))
 		}
 
 		/// Used by the RPC service to provide current prices.
+		///
+		/// Quotes the best of the constant product, concentrated liquidity and stable swap pools of
+		/// the pair.
 		pub fn quote_price_exact_tokens_for_tokens(
 			asset1: T::AssetKind,
 			asset2: T::AssetKind,
 			amount: T::Balance,
 			include_fee: bool,
 		) -> Option<T::Balance> {
-			let pool_account = T::PoolLocator::pool_address(&asset1, &asset2).ok()?;
-
-			let balance1 = Self::get_balance(&pool_account, asset1);
-			let balance2 = Self::get_balance(&pool_account, asset2);
-			if !balance1.is_zero() {
-				if include_fee {
-					Self::get_amount_out(&amount, &balance1, &balance2).ok()
+			let constant_product = || {
+				let pool_account = T::PoolLocator::pool_address(&asset1, &asset2).ok()?;
+
+				let balance1 = Self::get_balance(&pool_account, asset1.clone());
+				let balance2 = Self::get_balance(&pool_account, asset2.clone());
+				if !balance1.is_zero() {
+					if include_fee {
+						Self::get_amount_out(&amount, &balance1, &balance2).ok()
+					} else {
+						Self::quote(&amount, &balance1, &balance2).ok()
+					}
 				} else {
-					Self::quote(&amount, &balance1, &balance2).ok()
+					None
 				}
+			};
+			let concentrated = if include_fee {
+				Self::price_concentrated_swap(&asset1, &asset2, amount, true)
+					.ok()
+					.map(|swap| swap.amount_out)
 			} else {
-				None
-			}
+				Self::quote_concentrated(&asset1, &asset2, amount)
+			};
+			let fee = if include_fee { T::LPFee::get() } else { 0 };
+			let stable = Self::price_stable_swap(&asset1, &asset2, amount, true, fee)
+				.ok()
+				.map(|(amount_out, ..)| amount_out);
+			[constant_product(), concentrated, stable].into_iter().flatten().max()
 		}
 
 		/// Used by the RPC service to provide current prices.
+		///
+		/// Quotes the best of the constant product, concentrated liquidity and stable swap pools of
+		/// the pair.
 		pub fn quote_price_tokens_for_exact_tokens(
 			asset1: T::AssetKind,
 			asset2: T::AssetKind,
 			amount: T::Balance,
 			include_fee: bool,
 		) -> Option<T::Balance> {
-			let pool_account = T::PoolLocator::pool_address(&asset1, &asset2).ok()?;
-
-			let balance1 = Self::get_balance(&pool_account, asset1);
-			let balance2 = Self::get_balance(&pool_account, asset2);
-			if !balance1.is_zero() {
-				if include_fee {
-					Self::get_amount_in(&amount, &balance1, &balance2).ok()
+			let constant_product = || {
+				let pool_account = T::PoolLocator::pool_address(&asset1, &asset2).ok()?;
+
+				let balance1 = Self::get_balance(&pool_account, asset1.clone());
+				let balance2 = Self::get_balance(&pool_account, asset2.clone());
+				if !balance1.is_zero() {
+					if include_fee {
+						Self::get_amount_in(&amount, &balance1, &balance2).ok()
+					} else {
+						Self::quote(&amount, &balance2, &balance1).ok()
+					}
 				} else {
-					Self::quote(&amount, &balance2, &balance1).ok()
+					None
 				}
+			};
+			let concentrated = if include_fee {
+				Self::price_concentrated_swap(&asset1, &asset2, amount, false)
+					.ok()
+					.map(|swap| swap.amount_in)
 			} else {
-				None
-			}
+				Self::quote_concentrated(&asset2, &asset1, amount)
+			};
+			let fee = if include_fee { T::LPFee::get() } else { 0 };
+			let stable = Self::price_stable_swap(&asset1, &asset2, amount, false, fee)
+				.ok()
+				.map(|(amount_in, ..)| amount_in);
+			[constant_product(), concentrated, stable].into_iter().flatten().min()
 		}
 
 		/// Calculates the optimal amount from the reserves.
@@ -1159,7 +1755,11 @@ pub mod pallet {
 			result.try_into().map_err(|_| Error::<T>::Overflow)
 		}
 
-		fn mul_div(a: &T::Balance, b: &T::Balance, c: &T::Balance) -> Result<T::Balance, Error<T>> {
+		pub(crate) fn mul_div(
+			a: &T::Balance,
+			b: &T::Balance,
+			c: &T::Balance,
+		) -> Result<T::Balance, Error<T>> {
 			let a = T::HigherPrecisionBalance::from(*a);
 			let b = T::HigherPrecisionBalance::from(*b);
 			let c = T::HigherPrecisionBalance::from(*c);
@@ -1250,20 +1850,58 @@ pub mod pallet {
 			result.try_into().map_err(|_| Error::<T>::Overflow)
 		}
 
+		/// The weight of a swap along a path of `path_len` assets, given the weight of the swap
+		/// through constant product pools only, assuming every hop goes through the costliest of
+		/// a concentrated liquidity pool with all of its ticks crossed and the largest stable swap
+		/// pool.
+		pub(crate) fn max_swap_weight(swap_weight: Weight, path_len: u32) -> Weight {
+			let max_hop_weight = Self::ticks_crossed_weight(T::MaxTicks::get())
+				.max(Self::stable_hop_weight(T::MaxStableAssets::get()));
+			swap_weight
+				.saturating_add(max_hop_weight.saturating_mul(path_len.saturating_sub(1).into()))
+		}
+
+		/// The weight of the `hops` of a swap on top of a swap through constant product pools.
+		pub(crate) fn hops_weight(hops: &[Hop<T>]) -> Weight {
+			hops.iter()
+				.fold(Weight::zero(), |weight, hop| weight.saturating_add(hop.weight()))
+		}
+
+		/// The weight of crossing `ticks` ticks of concentrated liquidity pools on top of a swap.
+		pub(crate) fn ticks_crossed_weight(ticks: u32) -> Weight {
+			T::WeightInfo::swap_concentrated(ticks)
+				.saturating_sub(T::WeightInfo::swap_concentrated(0))
+		}
+
+		/// The weight of a hop through a stable swap pool of `assets` assets on top of a hop
+		/// through a constant product pool.
+		pub(crate) fn stable_hop_weight(assets: u32) -> Weight {
+			T::WeightInfo::swap_stable(assets)
+				.saturating_sub(T::WeightInfo::swap_exact_tokens_for_tokens(2))
+		}
+
 		/// Ensure that a path is valid.
-		fn validate_swap_path(path: &Vec<T::AssetKind>) -> Result<(), DispatchError> {
+		pub(crate) fn validate_swap_path(path: &Vec<T::AssetKind>) -> Result<(), DispatchError> {
 			ensure!(path.len() >= 2, Error::<T>::InvalidPath);
 			ensure!(path.len() as u32 <= T::MaxSwapPathLength::get(), Error::<T>::InvalidPath);
 
 			// validate all the pools in the path are unique
 			let mut pools = BTreeSet::<T::PoolId>::new();
+			let mut stable_pools = BTreeSet::<StablePoolId>::new();
 			for assets_pair in path.windows(2) {
 				if let [asset1, asset2] = assets_pair {
-					let pool_id = T::PoolLocator::pool_id(asset1, asset2)
-						.map_err(|_| Error::<T>::InvalidAssetPair)?;
-
-					let new_element = pools.insert(pool_id);
-					if !new_element {
+					let stable_pool_id = StablePairs::<T>::get(asset1, asset2);
+					let new_element = match T::PoolLocator::pool_id(asset1, asset2) {
+						Ok(pool_id) => pools.insert(pool_id),
+						// the pair may be held by a stable swap pool only.
+						Err(_) if stable_pool_id.is_some() => true,
+						Err(_) => return Err(Error::<T>::InvalidAssetPair.into()),
+					};
+					// a stable swap pool is priced by its balances before the swap, so it may be
+					// swapped through once.
+					let new_stable_element =
+						stable_pool_id.map_or(true, |pool_id| stable_pools.insert(pool_id));
+					if !new_element || !new_stable_element {
 						return Err(Error::<T>::NonUniquePath.into())
 					}
 				}
@@ -1281,6 +1919,131 @@ pub mod pallet {
 	}
 }
 
+impl<T: Config> Pallet<T>
+where
+	T::PoolId: Into<(T::AssetKind, T::AssetKind)>,
+{
+	/// Finds the path through the pools that yields the most of `asset_out` for `amount_in` of
+	/// `asset_in`, returning it along with the amount out.
+	///
+	/// Used by the RPC service to suggest a `path` for [`Pallet::swap_exact_tokens_for_tokens`].
+	pub fn quote_best_route_exact_tokens_for_tokens(
+		asset_in: T::AssetKind,
+		asset_out: T::AssetKind,
+		amount_in: T::Balance,
+	) -> Option<(Vec<T::AssetKind>, T::Balance)> {
+		let mut best = None;
+		Self::search_route(
+			&Self::pool_pairs(),
+			&mut vec![asset_in],
+			&asset_out,
+			amount_in,
+			true,
+			&mut best,
+		);
+		best
+	}
+
+	/// Finds the path through the pools that requires the least of `asset_in` for `amount_out`
+	/// of `asset_out`, returning it along with the amount in.
+	///
+	/// Used by the RPC service to suggest a `path` for [`Pallet::swap_tokens_for_exact_tokens`].
+	pub fn quote_best_route_tokens_for_exact_tokens(
+		asset_in: T::AssetKind,
+		asset_out: T::AssetKind,
+		amount_out: T::Balance,
+	) -> Option<(Vec<T::AssetKind>, T::Balance)> {
+		let mut best = None;
+		// the route is searched backwards, from the asset out.
+		Self::search_route(
+			&Self::pool_pairs(),
+			&mut vec![asset_out],
+			&asset_in,
+			amount_out,
+			false,
+			&mut best,
+		);
+		best.map(|(mut path, amount_in)| {
+			path.reverse();
+			(path, amount_in)
+		})
+	}
+
+	/// Returns the asset pairs with a constant product, a concentrated liquidity or a stable swap
+	/// pool.
+	fn pool_pairs() -> Vec<(T::AssetKind, T::AssetKind)> {
+		let mut pairs: Vec<(T::AssetKind, T::AssetKind)> =
+			Pools::<T>::iter_keys().map(Into::into).collect();
+		let concentrated = ConcentratedPools::<T>::iter_values().map(|p| (p.asset1, p.asset2));
+		let stable = StablePairs::<T>::iter_keys();
+		for (asset1, asset2) in concentrated.chain(stable) {
+			if !pairs
+				.iter()
+				.any(|(a, b)| (*a == asset1 && *b == asset2) || (*a == asset2 && *b == asset1))
+			{
+				pairs.push((asset1, asset2));
+			}
+		}
+		pairs
+	}
+
+	/// Searches the routes of at most [`Config::MaxSwapPathLength`] assets extending `route` to
+	/// `target` over the `pairs`, keeping the best one found in `best`.
+	///
+	/// The `amount` is the amount of the last asset of the `route` acquired if `exact_in` is set,
+	/// and the amount of it required otherwise, in which case the `route` is built backwards.
+	fn search_route(
+		pairs: &[(T::AssetKind, T::AssetKind)],
+		route: &mut Vec<T::AssetKind>,
+		target: &T::AssetKind,
+		amount: T::Balance,
+		exact_in: bool,
+		best: &mut Option<(Vec<T::AssetKind>, T::Balance)>,
+	) {
+		let last = match route.last() {
+			Some(last) => last.clone(),
+			None => return,
+		};
+		if last == *target {
+			let better = best.as_ref().map_or(true, |(_, best_amount)| match exact_in {
+				true => amount > *best_amount,
+				false => amount < *best_amount,
+			});
+			// a route going through a stable swap pool twice can't be swapped along.
+			if route.len() > 1 && better && Self::validate_swap_path(route).is_ok() {
+				*best = Some((route.clone(), amount));
+			}
+			return
+		}
+		if route.len() as u32 >= T::MaxSwapPathLength::get() {
+			return
+		}
+
+		for (asset1, asset2) in pairs {
+			let next = match (*asset1 == last, *asset2 == last) {
+				(true, _) => asset2,
+				(_, true) => asset1,
+				_ => continue,
+			};
+			if route.contains(next) {
+				continue
+			}
+			let priced = match exact_in {
+				true => Self::price_hop_for_amount_in(&last, next, amount),
+				false => Self::price_hop_for_amount_out(next, &last, amount),
+			};
+			if let Ok((amount, _)) = priced {
+				if amount.is_zero() {
+					continue
+				}
+				route.push(next.clone());
+				Self::search_route(pairs, route, target, amount, exact_in, best);
+				route.pop();
+			}
+		}
+	}
+}
+
 sp_api::decl_runtime_apis! {
 	/// This runtime api allows people to query the size of the liquidity pools
 	/// and quote prices for swaps.
@@ -1313,6 +2076,32 @@ sp_api::decl_runtime_apis! {
 
 		/// Returns the size of the liquidity pool for the given asset pair.
 		fn get_reserves(asset1: AssetId, asset2: AssetId) -> Option<(Balance, Balance)>;
+
+		/// Provides the path through the constant product and concentrated liquidity pools that
+		/// yields the most of `asset_out` for `amount` of `asset_in`, along with a quote for
+		/// [`Pallet::swap_exact_tokens_for_tokens`] along it.
+		///
+		/// Note that the price may have changed by the time the transaction is executed.
+		/// (Use `amount_out_min` to control slippage.)
+		#[api_version(2)]
+		fn quote_best_route_exact_tokens_for_tokens(
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount: Balance,
+		) -> Option<(Vec<AssetId>, Balance)>;
+
+		/// Provides the path through the constant product and concentrated liquidity pools that
+		/// requires the least of `asset_in` for `amount` of `asset_out`, along with a quote for
+		/// [`Pallet::swap_tokens_for_exact_tokens`] along it.
+		///
+		/// Note that the price may have changed by the time the transaction is executed.
+		/// (Use `amount_in_max` to control slippage.)
+		#[api_version(2)]
+		fn quote_best_route_tokens_for_exact_tokens(
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount: Balance,
+		) -> Option<(Vec<AssetId>, Balance)>;
 	}
 }
 
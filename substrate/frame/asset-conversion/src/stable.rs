@@ -0,0 +1,490 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Stable swap pools based on the [Curve](https://github.com/curvefi/curve-contract) StableSwap
+//! invariant.
+//!
+//! A stable swap pool holds two or more assets expected to trade close to parity, such as
+//! stablecoins pegged to the same currency. Its invariant `D` blends the constant sum and the
+//! constant product ones:
+//!
+//! `A * n^n * sum(x_i) + D = A * D * n^n + D^(n + 1) / (n^n * prod(x_i))`
+//!
+//! The higher the amplification coefficient `A`, the flatter the curve is around the balanced
+//! state of the pool, and the lower the slippage of the swaps. As in the reference
+//! implementation, the coefficient stored for a pool stands for `A * n^(n - 1)`. It is set by the
+//! [`Config::StablePoolOrigin`], and may be ramped linearly over a number of blocks to not move
+//! the prices abruptly.
+//!
+//! The assets of a pool are expected to share the same precision, so that a unit of any of them
+//! is worth a unit of any other at parity.
+
+use super::*;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{ensure, BoundedVec};
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::TypeInfo;
+use sp_arithmetic::{helpers_128bit::multiply_by_rational_with_rounding, Rounding};
+use sp_core::U256;
+use sp_runtime::{traits::SaturatedConversion, DispatchResult, RuntimeDebug};
+
+/// The identifier of a stable swap pool.
+pub type StablePoolId = u32;
+
+/// The lowest amplification coefficient of a stable swap pool.
+pub const MIN_AMPLIFICATION: u32 = 1;
+
+/// The highest amplification coefficient of a stable swap pool.
+pub const MAX_AMPLIFICATION: u32 = 1_000_000;
+
+/// The greatest factor a single ramp may change the amplification coefficient by.
+pub const MAX_AMPLIFICATION_CHANGE: u32 = 10;
+
+/// The number of iterations the invariant and the reserves are approximated within.
+const MAX_ITERATIONS: u32 = 255;
+
+/// The state of a stable swap pool.
+#[derive(Decode, Encode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct StablePoolInfo<Assets, PoolAssetId, BlockNumber> {
+	/// The assets of the pool.
+	pub assets: Assets,
+	/// The liquidity pool token of the pool.
+	pub lp_token: PoolAssetId,
+	/// The amplification coefficient at `ramp_start`.
+	pub initial_amplification: u32,
+	/// The amplification coefficient at `ramp_end` and afterwards.
+	pub future_amplification: u32,
+	/// The block the last amplification ramp started at.
+	pub ramp_start: BlockNumber,
+	/// The block the last amplification ramp ends at.
+	pub ramp_end: BlockNumber,
+}
+
+/// The state of a stable swap pool of the pallet.
+pub type StablePoolInfoOf<T> = StablePoolInfo<
+	BoundedVec<<T as Config>::AssetKind, <T as Config>::MaxStableAssets>,
+	<T as Config>::PoolAssetId,
+	BlockNumberFor<T>,
+>;
+
+impl<T: Config> Pallet<T> {
+	/// The account holding the assets of the stable swap pool with `pool_id`.
+	pub fn stable_pool_address(pool_id: StablePoolId) -> Result<T::AccountId, DispatchError> {
+		sp_io::hashing::blake2_256(&Encode::encode(&(T::PalletId::get(), b"stable", pool_id)))
+			.using_encoded(|e| Decode::decode(&mut TrailingZeroInput::new(e)))
+			.map_err(|_| Error::<T>::InvalidAssetPair.into())
+	}
+
+	/// The amplification coefficient of the stable swap `pool` at the current block.
+	pub fn stable_amplification(pool: &StablePoolInfoOf<T>) -> u32 {
+		let now = frame_system::Pallet::<T>::block_number();
+		if now >= pool.ramp_end {
+			return pool.future_amplification
+		}
+		let elapsed = now.saturating_sub(pool.ramp_start).saturated_into::<u128>();
+		let duration = pool.ramp_end.saturating_sub(pool.ramp_start).saturated_into::<u128>();
+		let (initial, future) =
+			(pool.initial_amplification as u128, pool.future_amplification as u128);
+		let amplification = if future > initial {
+			initial + (future - initial) * elapsed / duration
+		} else {
+			initial - (initial - future) * elapsed / duration
+		};
+		amplification as u32
+	}
+
+	pub(crate) fn do_create_stable_pool(
+		assets: Vec<T::AssetKind>,
+		amplification: u32,
+	) -> DispatchResult {
+		ensure!(
+			(MIN_AMPLIFICATION..=MAX_AMPLIFICATION).contains(&amplification),
+			Error::<T>::InvalidAmplification
+		);
+		ensure!(assets.len() >= 2, Error::<T>::InvalidStableAssets);
+		let assets: BoundedVec<_, T::MaxStableAssets> =
+			assets.try_into().map_err(|_| Error::<T>::InvalidStableAssets)?;
+		for (i, asset1) in assets.iter().enumerate() {
+			for asset2 in assets.iter().skip(i + 1) {
+				ensure!(asset1 != asset2, Error::<T>::InvalidStableAssets);
+				// a swap between two assets may only go through a single stable swap pool.
+				ensure!(!StablePairs::<T>::contains_key(asset1, asset2), Error::<T>::PoolExists);
+			}
+		}
+
+		let pool_id = NextStablePoolId::<T>::get();
+		NextStablePoolId::<T>::put(pool_id.checked_add(1).ok_or(Error::<T>::Overflow)?);
+		let pool_account = Self::stable_pool_address(pool_id)?;
+
+		let lp_token = NextPoolAssetId::<T>::get()
+			.or(T::PoolAssetId::initial_value())
+			.ok_or(Error::<T>::IncorrectPoolAssetId)?;
+		let next_lp_token_id = lp_token.increment().ok_or(Error::<T>::IncorrectPoolAssetId)?;
+		NextPoolAssetId::<T>::set(Some(next_lp_token_id));
+		T::PoolAssets::create(lp_token.clone(), pool_account.clone(), false, 1u32.into())?;
+
+		for (i, asset1) in assets.iter().enumerate() {
+			for asset2 in assets.iter().skip(i + 1) {
+				StablePairs::<T>::insert(asset1, asset2, pool_id);
+				StablePairs::<T>::insert(asset2, asset1, pool_id);
+			}
+		}
+		let now = frame_system::Pallet::<T>::block_number();
+		StablePools::<T>::insert(
+			pool_id,
+			StablePoolInfo {
+				assets: assets.clone(),
+				lp_token: lp_token.clone(),
+				initial_amplification: amplification,
+				future_amplification: amplification,
+				ramp_start: now,
+				ramp_end: now,
+			},
+		);
+
+		Self::deposit_event(Event::StablePoolCreated {
+			pool_id,
+			pool_account,
+			assets: assets.into_inner(),
+			lp_token,
+			amplification,
+		});
+
+		Ok(())
+	}
+
+	pub(crate) fn do_add_stable_liquidity(
+		who: T::AccountId,
+		pool_id: StablePoolId,
+		amounts: Vec<T::Balance>,
+		min_lp_token_minted: T::Balance,
+		mint_to: T::AccountId,
+	) -> DispatchResult {
+		let pool = StablePools::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+		ensure!(amounts.len() == pool.assets.len(), Error::<T>::InvalidStableAmounts);
+		ensure!(amounts.iter().any(|a| !a.is_zero()), Error::<T>::WrongDesiredAmount);
+
+		let pool_account = Self::stable_pool_address(pool_id)?;
+		let amplification = Self::stable_amplification(&pool) as u128;
+		let total_supply = T::PoolAssets::total_issuance(pool.lp_token.clone());
+
+		let mut reserves = Vec::with_capacity(amounts.len());
+		let mut new_reserves = Vec::with_capacity(amounts.len());
+		for (asset, amount) in pool.assets.iter().zip(amounts.iter()) {
+			let reserve = Self::to_u128(Self::get_balance(&pool_account, asset.clone()))?;
+			let new_reserve =
+				reserve.checked_add(Self::to_u128(*amount)?).ok_or(Error::<T>::Overflow)?;
+			ensure!(
+				new_reserve >= Self::to_u128(T::Assets::minimum_balance(asset.clone()))?,
+				Error::<T>::InvalidStableAmounts
+			);
+			reserves.push(reserve);
+			new_reserves.push(new_reserve);
+		}
+
+		let lp_token_amount = if total_supply.is_zero() {
+			// the first deposit sets the balance of the pool, and the invariant of the pool
+			// measures its liquidity.
+			ensure!(amounts.iter().all(|a| !a.is_zero()), Error::<T>::InvalidStableAmounts);
+			compute_d(&new_reserves, amplification)
+				.ok_or(Error::<T>::Overflow)?
+				.checked_sub(Self::to_u128(T::MintMinLiquidity::get())?)
+				.ok_or(Error::<T>::InsufficientLiquidityMinted)?
+		} else {
+			let d0 = compute_d(&reserves, amplification).ok_or(Error::<T>::Overflow)?;
+			let d1 = compute_d(&new_reserves, amplification).ok_or(Error::<T>::Overflow)?;
+			ensure!(d1 > d0, Error::<T>::InsufficientLiquidityMinted);
+
+			// a deposit unbalancing the pool amounts to a swap, hence the part of it deviating
+			// from the balance of the pool is charged the swap fee.
+			let n = reserves.len() as u128;
+			let fee = (T::LPFee::get() as u128) * n / (4 * (n - 1));
+			let adjusted_reserves = reserves
+				.iter()
+				.zip(new_reserves.iter())
+				.map(|(reserve, new_reserve)| {
+					let ideal = mul_div(d1, *reserve, d0, Rounding::Down)?;
+					let fee = mul_div(ideal.abs_diff(*new_reserve), fee, 1000, Rounding::Up)?;
+					new_reserve.checked_sub(fee)
+				})
+				.collect::<Option<Vec<_>>>()
+				.ok_or(Error::<T>::Overflow)?;
+			let d2 = compute_d(&adjusted_reserves, amplification).ok_or(Error::<T>::Overflow)?;
+			let growth = d2.checked_sub(d0).ok_or(Error::<T>::InsufficientLiquidityMinted)?;
+			mul_div(Self::to_u128(total_supply)?, growth, d0, Rounding::Down)
+				.ok_or(Error::<T>::Overflow)?
+		};
+		let lp_token_amount = Self::to_balance(lp_token_amount)?;
+		ensure!(
+			lp_token_amount > T::MintMinLiquidity::get() && lp_token_amount >= min_lp_token_minted,
+			Error::<T>::InsufficientLiquidityMinted
+		);
+
+		for (asset, amount) in pool.assets.iter().zip(amounts.iter()) {
+			if T::Assets::should_touch(asset.clone(), &pool_account) {
+				T::Assets::touch(asset.clone(), &pool_account, &who)?
+			};
+			if !amount.is_zero() {
+				T::Assets::transfer(asset.clone(), &who, &pool_account, *amount, Preserve)?;
+			}
+		}
+		if T::PoolAssets::should_touch(pool.lp_token.clone(), &pool_account) {
+			T::PoolAssets::touch(pool.lp_token.clone(), &pool_account, &who)?
+		};
+		if total_supply.is_zero() {
+			T::PoolAssets::mint_into(
+				pool.lp_token.clone(),
+				&pool_account,
+				T::MintMinLiquidity::get(),
+			)?;
+		}
+		T::PoolAssets::mint_into(pool.lp_token.clone(), &mint_to, lp_token_amount)?;
+
+		Self::deposit_event(Event::StableLiquidityAdded {
+			who,
+			mint_to,
+			pool_id,
+			amounts,
+			lp_token: pool.lp_token,
+			lp_token_minted: lp_token_amount,
+		});
+
+		Ok(())
+	}
+
+	pub(crate) fn do_remove_stable_liquidity(
+		who: T::AccountId,
+		pool_id: StablePoolId,
+		lp_token_burn: T::Balance,
+		amounts_min_receive: Vec<T::Balance>,
+		withdraw_to: T::AccountId,
+	) -> DispatchResult {
+		ensure!(lp_token_burn > Zero::zero(), Error::<T>::ZeroLiquidity);
+		let pool = StablePools::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+		ensure!(amounts_min_receive.len() == pool.assets.len(), Error::<T>::InvalidStableAmounts);
+
+		let pool_account = Self::stable_pool_address(pool_id)?;
+		let total_supply = T::PoolAssets::total_issuance(pool.lp_token.clone());
+		let withdrawal_fee_amount = T::LiquidityWithdrawalFee::get() * lp_token_burn;
+		let lp_redeem_amount = lp_token_burn.saturating_sub(withdrawal_fee_amount);
+
+		let mut amounts = Vec::with_capacity(amounts_min_receive.len());
+		for (asset, amount_min) in pool.assets.iter().zip(amounts_min_receive.iter()) {
+			let reserve = Self::get_balance(&pool_account, asset.clone());
+			let amount = Self::mul_div(&lp_redeem_amount, &reserve, &total_supply)?;
+			ensure!(
+				!amount.is_zero() && amount >= *amount_min,
+				Error::<T>::WithdrawalDidNotMeetMinimum
+			);
+			ensure!(
+				reserve.saturating_sub(amount) >= T::Assets::minimum_balance(asset.clone()),
+				Error::<T>::ReserveLeftLessThanMinimal
+			);
+			amounts.push(amount);
+		}
+
+		// burn the provided lp token amount that includes the fee
+		T::PoolAssets::burn_from(pool.lp_token.clone(), &who, lp_token_burn, Exact, Polite)?;
+
+		for (asset, amount) in pool.assets.iter().zip(amounts.iter()) {
+			T::Assets::transfer(asset.clone(), &pool_account, &withdraw_to, *amount, Expendable)?;
+		}
+
+		Self::deposit_event(Event::StableLiquidityRemoved {
+			who,
+			withdraw_to,
+			pool_id,
+			amounts,
+			lp_token: pool.lp_token,
+			lp_token_burned: lp_token_burn,
+			withdrawal_fee: T::LiquidityWithdrawalFee::get(),
+		});
+
+		Ok(())
+	}
+
+	pub(crate) fn do_set_stable_amplification(
+		pool_id: StablePoolId,
+		amplification: u32,
+		ramp_end: BlockNumberFor<T>,
+	) -> DispatchResult {
+		let now = frame_system::Pallet::<T>::block_number();
+		ensure!(ramp_end >= now, Error::<T>::InvalidAmplification);
+		ensure!(
+			(MIN_AMPLIFICATION..=MAX_AMPLIFICATION).contains(&amplification),
+			Error::<T>::InvalidAmplification
+		);
+
+		let initial_amplification = StablePools::<T>::try_mutate(pool_id, |pool| {
+			let pool = pool.as_mut().ok_or(Error::<T>::PoolNotFound)?;
+			let current = Self::stable_amplification(pool);
+			ensure!(
+				amplification <= current.saturating_mul(MAX_AMPLIFICATION_CHANGE) &&
+					amplification.saturating_mul(MAX_AMPLIFICATION_CHANGE) >= current,
+				Error::<T>::InvalidAmplification
+			);
+			pool.initial_amplification = current;
+			pool.future_amplification = amplification;
+			pool.ramp_start = now;
+			pool.ramp_end = ramp_end;
+			Ok::<_, Error<T>>(current)
+		})?;
+
+		Self::deposit_event(Event::StableAmplificationRampStarted {
+			pool_id,
+			initial_amplification,
+			future_amplification: amplification,
+			ramp_end,
+		});
+
+		Ok(())
+	}
+
+	/// Prices a swap through the stable swap pool holding both `asset_in` and `asset_out`,
+	/// charging the `fee` expressed in tenths of a percent.
+	///
+	/// The `amount` is the amount of `asset_in` to deposit if `exact_in` is set, and the amount of
+	/// `asset_out` to withdraw otherwise. Returns the amount of the other asset, along with the
+	/// pool and its number of assets.
+	pub(crate) fn price_stable_swap(
+		asset_in: &T::AssetKind,
+		asset_out: &T::AssetKind,
+		amount: T::Balance,
+		exact_in: bool,
+		fee: u32,
+	) -> Result<(T::Balance, StablePoolId, u32), DispatchError> {
+		let pool_id = StablePairs::<T>::get(asset_in, asset_out).ok_or(Error::<T>::PoolNotFound)?;
+		let pool = StablePools::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+		ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+		let pool_account = Self::stable_pool_address(pool_id)?;
+		let reserves = pool
+			.assets
+			.iter()
+			.map(|asset| Self::to_u128(Self::get_balance(&pool_account, asset.clone())))
+			.collect::<Result<Vec<_>, _>>()?;
+		ensure!(reserves.iter().all(|r| !r.is_zero()), Error::<T>::PoolNotFound);
+		let position = |asset: &T::AssetKind| pool.assets.iter().position(|a| a == asset);
+		let (i, j) =
+			position(asset_in).zip(position(asset_out)).ok_or(DispatchError::Corruption)?;
+
+		let amplification = Self::stable_amplification(&pool) as u128;
+		let fee_complement = 1000u128.checked_sub(fee as u128).ok_or(Error::<T>::Overflow)?;
+		let amount = Self::to_u128(amount)?;
+		let other = if exact_in {
+			let amount_less_fee = mul_div(amount, fee_complement, 1000, Rounding::Down)
+				.ok_or(Error::<T>::Overflow)?;
+			let x = reserves[i].checked_add(amount_less_fee).ok_or(Error::<T>::Overflow)?;
+			let y = compute_y(&reserves, i, x, j, amplification).ok_or(Error::<T>::Overflow)?;
+			// rounded in favour of the pool.
+			reserves[j].checked_sub(y).ok_or(Error::<T>::Overflow)?.saturating_sub(1)
+		} else {
+			ensure!(amount < reserves[j], Error::<T>::AmountOutTooHigh);
+			let y = reserves[j] - amount;
+			let x = compute_y(&reserves, j, y, i, amplification).ok_or(Error::<T>::Overflow)?;
+			// rounded in favour of the pool.
+			let amount_less_fee = x
+				.checked_sub(reserves[i])
+				.and_then(|a| a.checked_add(1))
+				.ok_or(Error::<T>::Overflow)?;
+			mul_div(amount_less_fee, 1000, fee_complement, Rounding::Up)
+				.ok_or(Error::<T>::Overflow)?
+		};
+
+		Ok((Self::to_balance(other)?, pool_id, pool.assets.len() as u32))
+	}
+}
+
+/// Computes the invariant `D` of a stable swap pool with the `reserves`, or returns `None` if it
+/// doesn't converge.
+fn compute_d(reserves: &[u128], amplification: u128) -> Option<u128> {
+	let n = U256::from(reserves.len());
+	let sum = reserves
+		.iter()
+		.try_fold(U256::zero(), |sum, r| sum.checked_add(U256::from(*r)))?;
+	if sum.is_zero() {
+		return Some(0)
+	}
+	let ann = U256::from(amplification).checked_mul(n)?;
+
+	let mut d = sum;
+	for _ in 0..MAX_ITERATIONS {
+		// D^(n + 1) / (n^n * prod(x_i))
+		let mut d_p = d;
+		for reserve in reserves {
+			d_p = d_p.checked_mul(d)?.checked_div(U256::from(*reserve).checked_mul(n)?)?;
+		}
+		let d_prev = d;
+		// (Ann * S + D_P * n) * D / ((Ann - 1) * D + (n + 1) * D_P)
+		let numerator = ann.checked_mul(sum)?.checked_add(d_p.checked_mul(n)?)?.checked_mul(d)?;
+		let denominator = (ann - 1)
+			.checked_mul(d)?
+			.checked_add(n.checked_add(U256::one())?.checked_mul(d_p)?)?;
+		d = numerator.checked_div(denominator)?;
+		if abs_diff(d, d_prev) <= U256::one() {
+			return d.try_into().ok()
+		}
+	}
+	None
+}
+
+/// Computes the reserve of the asset at `j` that keeps the invariant of a stable swap pool with
+/// the `reserves` once the reserve of the asset at `i` is set to `x`, or returns `None` if it
+/// doesn't converge.
+fn compute_y(reserves: &[u128], i: usize, x: u128, j: usize, amplification: u128) -> Option<u128> {
+	let n = U256::from(reserves.len());
+	let ann = U256::from(amplification).checked_mul(n)?;
+	let d = U256::from(compute_d(reserves, amplification)?);
+
+	let (mut c, mut sum) = (d, U256::zero());
+	for (k, reserve) in reserves.iter().enumerate() {
+		let reserve = match k {
+			_ if k == i => U256::from(x),
+			_ if k == j => continue,
+			_ => U256::from(*reserve),
+		};
+		sum = sum.checked_add(reserve)?;
+		c = c.checked_mul(d)?.checked_div(reserve.checked_mul(n)?)?;
+	}
+	c = c.checked_mul(d)?.checked_div(ann.checked_mul(n)?)?;
+	let b = sum.checked_add(d.checked_div(ann)?)?;
+
+	let mut y = d;
+	for _ in 0..MAX_ITERATIONS {
+		let y_prev = y;
+		// (y^2 + c) / (2 * y + b - D)
+		let denominator = y.checked_mul(U256::from(2))?.checked_add(b)?.checked_sub(d)?;
+		y = y.checked_mul(y)?.checked_add(c)?.checked_div(denominator)?;
+		if abs_diff(y, y_prev) <= U256::one() {
+			return y.try_into().ok()
+		}
+	}
+	None
+}
+
+fn abs_diff(a: U256, b: U256) -> U256 {
+	if a > b {
+		a - b
+	} else {
+		b - a
+	}
+}
+
+/// `a * b / c` with the given `rounding`, or `None` on overflow or division by zero.
+fn mul_div(a: u128, b: u128, c: u128, rounding: Rounding) -> Option<u128> {
+	multiply_by_rational_with_rounding(a, b, c, rounding)
+}
@@ -128,7 +128,7 @@ impl<T: Config> Swap<T::AccountId> for Pallet<T> {
 		send_to: T::AccountId,
 		keep_alive: bool,
 	) -> Result<Self::Balance, DispatchError> {
-		let amount_out = with_storage_layer(|| {
+		let (amount_out, _) = with_storage_layer(|| {
 			Self::do_swap_exact_tokens_for_tokens(
 				sender,
 				path,
@@ -149,7 +149,7 @@ impl<T: Config> Swap<T::AccountId> for Pallet<T> {
 		send_to: T::AccountId,
 		keep_alive: bool,
 	) -> Result<Self::Balance, DispatchError> {
-		let amount_in = with_storage_layer(|| {
+		let (amount_in, _) = with_storage_layer(|| {
 			Self::do_swap_tokens_for_exact_tokens(
 				sender,
 				path,
@@ -27,7 +27,7 @@ use frame_support::{
 		Get,
 	},
 };
-use sp_arithmetic::Permill;
+use sp_arithmetic::{FixedU128, Permill};
 use sp_runtime::{DispatchError, TokenError};
 
 fn events() -> Vec<Event<Test>> {
@@ -1675,6 +1675,7 @@ fn swap_when_existential_deposit_would_cause_reaping_pool_account() {
 			vec![token_3.clone(), token_1.clone()],
 		)
 		.unwrap()
+		.0
 		.first()
 		.map(|(_, a)| *a)
 		.unwrap();
@@ -1697,6 +1698,7 @@ fn swap_when_existential_deposit_would_cause_reaping_pool_account() {
 			vec![token_1.clone(), token_2.clone()],
 		)
 		.unwrap()
+		.0
 		.first()
 		.map(|(_, a)| *a)
 		.unwrap();
@@ -2141,6 +2143,7 @@ fn swap_transactional() {
 			vec![token_2.clone(), token_1.clone(), token_3.clone()],
 		)
 		.unwrap()
+		.0
 		.first()
 		.map(|(_, a)| *a)
 		.unwrap();
@@ -2493,3 +2496,1274 @@ fn swap_credit_invalid_path() {
 		assert_eq!(error, (expected_credit_in, Error::<Test>::InvalidPath.into()));
 	});
 }
+
+fn create_concentrated_pool_with_liquidity(
+	user: u128,
+	token_1: &NativeOrWithId<u32>,
+	token_2: &NativeOrWithId<u32>,
+	ranges: Vec<(i32, i32, u128, u128)>,
+) -> (u128, <Test as Config>::PoolId) {
+	assert_ok!(AssetConversion::create_concentrated_pool(
+		RuntimeOrigin::signed(user),
+		Box::new(token_1.clone()),
+		Box::new(token_2.clone()),
+		0
+	));
+	for (tick_lower, tick_upper, amount1, amount2) in ranges {
+		assert_ok!(AssetConversion::add_concentrated_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			tick_lower,
+			tick_upper,
+			amount1,
+			amount2,
+			0,
+			0,
+		));
+	}
+	let pool_id = <Test as Config>::PoolLocator::pool_id(token_1, token_2).unwrap();
+	(AssetConversion::concentrated_pool_address(&pool_id).unwrap(), pool_id)
+}
+
+#[test]
+fn concentrated_tick_math_works() {
+	assert_eq!(sqrt_price_at_tick(0), FixedU128::one());
+	assert!(sqrt_price_at_tick(-1) < FixedU128::one());
+	assert!(sqrt_price_at_tick(1) > FixedU128::one());
+	for tick in [MIN_TICK, -1_001, -1, 0, 1, 6_932, MAX_TICK] {
+		assert_eq!(tick_at_sqrt_price(sqrt_price_at_tick(tick)), tick);
+	}
+	// the price doubles roughly every 6932 ticks.
+	let price = sqrt_price_at_tick(6_932).saturating_pow(2);
+	assert!(price > FixedU128::from_rational(1999, 1000));
+	assert!(price < FixedU128::from_rational(2001, 1000));
+}
+
+#[test]
+fn create_concentrated_pool_works() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let pool_id = (token_1.clone(), token_2.clone());
+
+		create_tokens(user, vec![token_2.clone()]);
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 1000 + ed));
+
+		assert_noop!(
+			AssetConversion::create_concentrated_pool(
+				RuntimeOrigin::signed(user),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				MAX_TICK + 1
+			),
+			Error::<Test>::InvalidTick
+		);
+		assert_noop!(
+			AssetConversion::create_concentrated_pool(
+				RuntimeOrigin::signed(user),
+				Box::new(token_1.clone()),
+				Box::new(token_1.clone()),
+				0
+			),
+			Error::<Test>::InvalidAssetPair
+		);
+
+		assert_ok!(AssetConversion::create_concentrated_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			-10
+		));
+
+		let setup_fee = <<Test as Config>::PoolSetupFee as Get<u128>>::get();
+		let pool_account = AssetConversion::concentrated_pool_address(&pool_id).unwrap();
+		assert_eq!(balance(user, token_1.clone()), 1000 + ed - setup_fee - 10);
+		assert_eq!(
+			events(),
+			[Event::<Test>::ConcentratedPoolCreated {
+				creator: user,
+				pool_id: pool_id.clone(),
+				pool_account: pool_account.clone(),
+				tick: -10,
+			}]
+		);
+		let pool = ConcentratedPools::<Test>::get(&pool_id).unwrap();
+		assert_eq!((pool.asset1, pool.asset2), (token_1.clone(), token_2.clone()));
+		assert_eq!((pool.tick, pool.sqrt_price), (-10, sqrt_price_at_tick(-10)));
+		assert!(pool.liquidity.is_zero());
+
+		// the concentrated liquidity pool doesn't share the account of the constant product one.
+		assert_ne!(pool_account, <Test as Config>::PoolLocator::address(&pool_id).unwrap());
+
+		assert_noop!(
+			AssetConversion::create_concentrated_pool(
+				RuntimeOrigin::signed(user),
+				Box::new(token_2.clone()),
+				Box::new(token_1.clone()),
+				0
+			),
+			Error::<Test>::PoolExists
+		);
+	});
+}
+
+#[test]
+fn add_and_remove_concentrated_liquidity_works() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let user2 = 2;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+
+		create_tokens(user, vec![token_2.clone()]);
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 100_000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 100_000));
+
+		let (pool_account, pool_id) =
+			create_concentrated_pool_with_liquidity(user, &token_1, &token_2, vec![]);
+		let _ = events();
+
+		assert_ok!(AssetConversion::add_concentrated_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			-100,
+			100,
+			10_000,
+			10_000,
+			9_900,
+			9_900,
+		));
+
+		let key = (user, -100, 100);
+		let liquidity = ConcentratedPositions::<Test>::get(&pool_id, &key).unwrap().liquidity;
+		assert_eq!(ConcentratedPools::<Test>::get(&pool_id).unwrap().liquidity, liquidity);
+		assert_eq!(InitializedTicks::<Test>::get(&pool_id).into_inner(), vec![-100, 100]);
+		let (amount1, amount2) =
+			(balance(pool_account, token_1.clone()), balance(pool_account, token_2.clone()));
+		assert!((9_900..=10_001).contains(&amount1) && (9_900..=10_001).contains(&amount2));
+		assert_eq!(
+			events(),
+			[Event::<Test>::ConcentratedLiquidityAdded {
+				who: user,
+				pool_id: pool_id.clone(),
+				tick_lower: -100,
+				tick_upper: 100,
+				liquidity,
+				amount1,
+				amount2,
+			}]
+		);
+
+		// a range above the current price holds `asset1` only and isn't active.
+		assert_ok!(AssetConversion::add_concentrated_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			100,
+			200,
+			10_000,
+			10_000,
+			0,
+			0,
+		));
+		assert_eq!(ConcentratedPools::<Test>::get(&pool_id).unwrap().liquidity, liquidity);
+		assert_eq!(InitializedTicks::<Test>::get(&pool_id).into_inner(), vec![-100, 100, 200]);
+		assert_eq!(balance(pool_account, token_1.clone()), amount1 + 10_000);
+		assert_eq!(balance(pool_account, token_2.clone()), amount2);
+		let _ = events();
+
+		assert_noop!(
+			AssetConversion::remove_concentrated_liquidity(
+				RuntimeOrigin::signed(user),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				-100,
+				100,
+				liquidity + 1,
+				0,
+				0,
+				user,
+			),
+			Error::<Test>::InsufficientPositionLiquidity
+		);
+
+		assert_ok!(AssetConversion::remove_concentrated_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			-100,
+			100,
+			liquidity,
+			amount1 - 2,
+			amount2 - 2,
+			user2,
+		));
+
+		// the position and the ticks bounding it only are dropped.
+		assert!(!ConcentratedPositions::<Test>::contains_key(&pool_id, &key));
+		assert!(!ConcentratedTicks::<Test>::contains_key(&pool_id, -100));
+		assert!(ConcentratedTicks::<Test>::contains_key(&pool_id, 100));
+		assert_eq!(InitializedTicks::<Test>::get(&pool_id).into_inner(), vec![100, 200]);
+		assert!(ConcentratedPools::<Test>::get(&pool_id).unwrap().liquidity.is_zero());
+
+		let withdrawn1 = balance(user2, token_1.clone());
+		let withdrawn2 = balance(user2, token_2.clone());
+		assert!(withdrawn1 + 2 >= amount1 && withdrawn1 <= amount1);
+		assert!(withdrawn2 + 2 >= amount2 && withdrawn2 <= amount2);
+		assert_eq!(
+			events(),
+			[Event::<Test>::ConcentratedLiquidityRemoved {
+				who: user,
+				withdraw_to: user2,
+				pool_id,
+				tick_lower: -100,
+				tick_upper: 100,
+				liquidity,
+				amount1: withdrawn1,
+				amount2: withdrawn2,
+			}]
+		);
+	});
+}
+
+#[test]
+fn concentrated_liquidity_errors() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+
+		create_tokens(user, vec![token_2.clone()]);
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 100_000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 100_000));
+
+		let add = |asset1: &NativeOrWithId<u32>, asset2: &NativeOrWithId<u32>, lower, upper| {
+			AssetConversion::add_concentrated_liquidity(
+				RuntimeOrigin::signed(user),
+				Box::new(asset1.clone()),
+				Box::new(asset2.clone()),
+				lower,
+				upper,
+				1_000,
+				1_000,
+				0,
+				0,
+			)
+		};
+
+		assert_noop!(add(&token_1, &token_2, -100, 100), Error::<Test>::PoolNotFound);
+		create_concentrated_pool_with_liquidity(user, &token_1, &token_2, vec![]);
+
+		assert_noop!(add(&token_2, &token_1, -100, 100), Error::<Test>::InvalidAssetPair);
+		assert_noop!(add(&token_1, &token_2, 100, 100), Error::<Test>::InvalidTick);
+		assert_noop!(add(&token_1, &token_2, MIN_TICK - 1, 100), Error::<Test>::InvalidTick);
+		assert_noop!(
+			AssetConversion::collect_concentrated_fees(
+				RuntimeOrigin::signed(user),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				-100,
+				100,
+				user,
+			),
+			Error::<Test>::PositionNotFound
+		);
+		assert_noop!(
+			AssetConversion::remove_concentrated_liquidity(
+				RuntimeOrigin::signed(user),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				-100,
+				100,
+				1,
+				0,
+				0,
+				user,
+			),
+			Error::<Test>::PositionNotFound
+		);
+
+		// the ranges above the current price take `MaxTicks` ticks.
+		let max_ticks = <<Test as Config>::MaxTicks as Get<u32>>::get() as i32;
+		for i in 0..max_ticks / 2 {
+			assert_ok!(add(&token_1, &token_2, 1_000 + i * 100, 1_050 + i * 100));
+		}
+		assert_noop!(add(&token_1, &token_2, -100, 100), Error::<Test>::TooManyTicks);
+		// the initialized ticks can still be referenced.
+		assert_ok!(add(&token_1, &token_2, 1_000, 1_150));
+	});
+}
+
+#[test]
+fn swap_routes_through_concentrated_pool() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let user2 = 2;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+
+		create_tokens(user, vec![token_2.clone()]);
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone())
+		));
+
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 100_000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 100_000));
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user2, 10_000 + ed));
+
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			1_000,
+			1_000,
+			1,
+			1,
+			user,
+		));
+		let (pool_account, pool_id) = create_concentrated_pool_with_liquidity(
+			user,
+			&token_1,
+			&token_2,
+			vec![(-100, 100, 10_000, 10_000)],
+		);
+		let reserves = AssetConversion::get_reserves(token_1.clone(), token_2.clone()).unwrap();
+		let pool_balance1 = balance(pool_account, token_1.clone());
+
+		// the concentrated liquidity pool yields more for the same amount.
+		let constant_product_out =
+			AssetConversion::get_amount_out(&100, &reserves.0, &reserves.1).unwrap();
+		let quote = AssetConversion::quote_price_exact_tokens_for_tokens(
+			token_1.clone(),
+			token_2.clone(),
+			100,
+			true,
+		)
+		.unwrap();
+		assert!(quote > constant_product_out);
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(
+				token_1.clone(),
+				token_2.clone(),
+				100,
+				false
+			),
+			Some(100)
+		);
+
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user2),
+			bvec![token_1.clone(), token_2.clone()],
+			100,
+			1,
+			user2,
+			true,
+		));
+
+		assert_eq!(balance(user2, token_2.clone()), quote);
+		assert_eq!(balance(pool_account, token_1.clone()), pool_balance1 + 100);
+		assert_eq!(
+			AssetConversion::get_reserves(token_1.clone(), token_2.clone()).unwrap(),
+			reserves
+		);
+		let pool = ConcentratedPools::<Test>::get(&pool_id).unwrap();
+		assert!(pool.sqrt_price < FixedU128::one());
+		assert!(!pool.fee_growth1.is_zero() && pool.fee_growth2.is_zero());
+	});
+}
+
+#[test]
+fn swap_crosses_concentrated_ticks() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let user2 = 2;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+
+		create_tokens(user, vec![token_2.clone()]);
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 100_000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 100_000));
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user2, 100_000 + ed));
+
+		// adjacent ranges below the current price, holding `asset2` only.
+		let (pool_account, pool_id) = create_concentrated_pool_with_liquidity(
+			user,
+			&token_1,
+			&token_2,
+			vec![(-100, 0, 0, 10_000), (-200, -100, 0, 10_000)],
+		);
+		let pool_balance2 = balance(pool_account, token_2.clone());
+		assert!(ConcentratedPools::<Test>::get(&pool_id).unwrap().liquidity.is_zero());
+
+		// the swap can't withdraw more than the liquidity of the pool.
+		assert_noop!(
+			AssetConversion::swap_tokens_for_exact_tokens(
+				RuntimeOrigin::signed(user2),
+				bvec![token_1.clone(), token_2.clone()],
+				pool_balance2 + 1,
+				100_000,
+				user2,
+				true,
+			),
+			Error::<Test>::InsufficientLiquidity
+		);
+
+		let quote = AssetConversion::quote_price_tokens_for_exact_tokens(
+			token_1.clone(),
+			token_2.clone(),
+			15_000,
+			true,
+		)
+		.unwrap();
+		let post_info = AssetConversion::swap_tokens_for_exact_tokens(
+			RuntimeOrigin::signed(user2),
+			bvec![token_1.clone(), token_2.clone()],
+			15_000,
+			quote,
+			user2,
+			true,
+		)
+		.unwrap();
+
+		assert_eq!(balance(user2, token_2.clone()), 15_000);
+		assert_eq!(balance(user2, token_1.clone()), 100_000 + ed - quote);
+		let pool = ConcentratedPools::<Test>::get(&pool_id).unwrap();
+		assert!((-200..-100).contains(&pool.tick));
+		assert_eq!(
+			pool.liquidity,
+			ConcentratedPositions::<Test>::get(&pool_id, &(user, -200, -100))
+				.unwrap()
+				.liquidity
+		);
+		// the ticks `0` and `-100` are crossed.
+		assert_eq!(
+			post_info.actual_weight,
+			Some(
+				<Test as Config>::WeightInfo::swap_tokens_for_exact_tokens(2) +
+					AssetConversion::ticks_crossed_weight(2)
+			)
+		);
+	});
+}
+
+#[test]
+fn collect_concentrated_fees_works() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let user2 = 2;
+		let user3 = 3;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+
+		create_tokens(user, vec![token_2.clone()]);
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 100_000 + ed));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 100_000));
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user2, 100_000 + ed));
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user3, ed));
+
+		let (_, pool_id) = create_concentrated_pool_with_liquidity(
+			user,
+			&token_1,
+			&token_2,
+			vec![(-100, 100, 10_000, 10_000)],
+		);
+
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user2),
+			bvec![token_1.clone(), token_2.clone()],
+			5_000,
+			1,
+			user2,
+			true,
+		));
+		let _ = events();
+
+		assert_ok!(AssetConversion::collect_concentrated_fees(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			-100,
+			100,
+			user3,
+		));
+
+		// 0.3% of the amount swapped, less the rounding.
+		let fees = balance(user3, token_1.clone()) - ed;
+		assert!(fees >= 13 && fees <= 15);
+		assert_eq!(balance(user3, token_2.clone()), 0);
+		assert_eq!(
+			events(),
+			[Event::<Test>::ConcentratedFeesCollected {
+				who: user,
+				withdraw_to: user3,
+				pool_id: pool_id.clone(),
+				tick_lower: -100,
+				tick_upper: 100,
+				amount1: fees,
+				amount2: 0,
+			}]
+		);
+		let position = ConcentratedPositions::<Test>::get(&pool_id, &(user, -100, 100)).unwrap();
+		assert!(position.fees_owed1.is_zero() && position.fees_owed2.is_zero());
+
+		// the fees are only collected once.
+		assert_ok!(AssetConversion::collect_concentrated_fees(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			-100,
+			100,
+			user3,
+		));
+		assert_eq!(balance(user3, token_1.clone()) - ed, fees);
+	});
+}
+
+#[test]
+fn quote_best_route_works() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::Native;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+		let token_4 = NativeOrWithId::WithId(4);
+
+		create_tokens(user, vec![token_2.clone(), token_3.clone(), token_4.clone()]);
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 100_000 + ed));
+		for id in [2, 3] {
+			assert_ok!(Assets::mint(RuntimeOrigin::signed(user), id, user, 100_000));
+		}
+
+		for (asset1, asset2, liquidity1, liquidity2) in [
+			(&token_1, &token_2, 10_000, 10_000),
+			(&token_1, &token_3, 10_000, 10_000),
+			(&token_2, &token_3, 1_000, 100),
+		] {
+			assert_ok!(AssetConversion::create_pool(
+				RuntimeOrigin::signed(user),
+				Box::new(asset1.clone()),
+				Box::new(asset2.clone())
+			));
+			assert_ok!(AssetConversion::add_liquidity(
+				RuntimeOrigin::signed(user),
+				Box::new(asset1.clone()),
+				Box::new(asset2.clone()),
+				liquidity1,
+				liquidity2,
+				1,
+				1,
+				user,
+			));
+		}
+
+		// the thin direct pool loses to the route through the native asset.
+		let via_native = AssetConversion::quote_price_exact_tokens_for_tokens(
+			token_1.clone(),
+			token_3.clone(),
+			AssetConversion::quote_price_exact_tokens_for_tokens(
+				token_2.clone(),
+				token_1.clone(),
+				100,
+				true,
+			)
+			.unwrap(),
+			true,
+		)
+		.unwrap();
+		assert_eq!(
+			AssetConversion::quote_best_route_exact_tokens_for_tokens(
+				token_2.clone(),
+				token_3.clone(),
+				100
+			),
+			Some((vec![token_2.clone(), token_1.clone(), token_3.clone()], via_native))
+		);
+
+		let via_native = AssetConversion::quote_price_tokens_for_exact_tokens(
+			token_2.clone(),
+			token_1.clone(),
+			AssetConversion::quote_price_tokens_for_exact_tokens(
+				token_1.clone(),
+				token_3.clone(),
+				50,
+				true,
+			)
+			.unwrap(),
+			true,
+		)
+		.unwrap();
+		assert_eq!(
+			AssetConversion::quote_best_route_tokens_for_exact_tokens(
+				token_2.clone(),
+				token_3.clone(),
+				50
+			),
+			Some((vec![token_2.clone(), token_1.clone(), token_3.clone()], via_native))
+		);
+
+		// the direct pool wins once it is deep enough.
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_2.clone()),
+			Box::new(token_3.clone()),
+			50_000,
+			5_000,
+			1,
+			1,
+			user,
+		));
+		let direct = AssetConversion::quote_price_exact_tokens_for_tokens(
+			token_3.clone(),
+			token_2.clone(),
+			100,
+			true,
+		)
+		.unwrap();
+		assert_eq!(
+			AssetConversion::quote_best_route_exact_tokens_for_tokens(
+				token_3.clone(),
+				token_2.clone(),
+				100
+			),
+			Some((vec![token_3.clone(), token_2.clone()], direct))
+		);
+
+		// no route to an asset without pools.
+		assert_eq!(
+			AssetConversion::quote_best_route_exact_tokens_for_tokens(
+				token_2.clone(),
+				token_4.clone(),
+				100
+			),
+			None
+		);
+		assert_eq!(
+			AssetConversion::quote_best_route_tokens_for_exact_tokens(
+				token_4.clone(),
+				token_2.clone(),
+				100
+			),
+			None
+		);
+	});
+}
+
+fn create_stable_pool_with_liquidity(
+	user: u128,
+	tokens: Vec<NativeOrWithId<u32>>,
+	amplification: u32,
+	liquidity: u128,
+) -> (u128, StablePoolId) {
+	let pool_id = NextStablePoolId::<Test>::get();
+	assert_ok!(AssetConversion::create_stable_pool(
+		RuntimeOrigin::root(),
+		tokens.iter().cloned().map(Box::new).collect(),
+		amplification,
+	));
+	if liquidity > 0 {
+		assert_ok!(AssetConversion::add_stable_liquidity(
+			RuntimeOrigin::signed(user),
+			pool_id,
+			vec![liquidity; tokens.len()],
+			0,
+			user,
+		));
+	}
+	(AssetConversion::stable_pool_address(pool_id).unwrap(), pool_id)
+}
+
+#[test]
+fn create_stable_pool_works() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+		let token_4 = NativeOrWithId::WithId(4);
+		let tokens = vec![token_2.clone(), token_3.clone(), token_4.clone()];
+
+		create_tokens(user, tokens.clone());
+
+		assert_noop!(
+			AssetConversion::create_stable_pool(
+				RuntimeOrigin::signed(user),
+				bvec![token_2.clone(), token_3.clone()],
+				100
+			),
+			DispatchError::BadOrigin
+		);
+		for amplification in [0, MAX_AMPLIFICATION + 1] {
+			assert_noop!(
+				AssetConversion::create_stable_pool(
+					RuntimeOrigin::root(),
+					bvec![token_2.clone(), token_3.clone()],
+					amplification
+				),
+				Error::<Test>::InvalidAmplification
+			);
+		}
+		for assets in [
+			bvec![token_2.clone()],
+			bvec![token_2.clone(), token_2.clone()],
+			(0..5).map(|id| Box::new(NativeOrWithId::WithId(id))).collect(),
+		] {
+			assert_noop!(
+				AssetConversion::create_stable_pool(RuntimeOrigin::root(), assets, 100),
+				Error::<Test>::InvalidStableAssets
+			);
+		}
+
+		let lp_token = AssetConversion::get_next_pool_asset_id();
+		assert_ok!(AssetConversion::create_stable_pool(
+			RuntimeOrigin::root(),
+			tokens.iter().cloned().map(Box::new).collect(),
+			100
+		));
+
+		let pool_account = AssetConversion::stable_pool_address(0).unwrap();
+		assert_eq!(
+			events(),
+			[Event::<Test>::StablePoolCreated {
+				pool_id: 0,
+				pool_account,
+				assets: tokens.clone(),
+				lp_token,
+				amplification: 100,
+			}]
+		);
+		let pool = StablePools::<Test>::get(0).unwrap();
+		assert_eq!((pool.assets.into_inner(), pool.lp_token), (tokens, lp_token));
+		assert_eq!(
+			AssetConversion::stable_amplification(&StablePools::<Test>::get(0).unwrap()),
+			100
+		);
+		assert_eq!(pool_assets(), vec![lp_token]);
+		for (asset1, asset2) in [(&token_2, &token_3), (&token_4, &token_2), (&token_3, &token_4)] {
+			assert_eq!(StablePairs::<Test>::get(asset1, asset2), Some(0));
+		}
+		assert_eq!(NextStablePoolId::<Test>::get(), 1);
+
+		// a pair may be held by a single stable swap pool.
+		assert_noop!(
+			AssetConversion::create_stable_pool(
+				RuntimeOrigin::root(),
+				bvec![NativeOrWithId::WithId(5), token_4.clone(), token_3.clone()],
+				100
+			),
+			Error::<Test>::PoolExists
+		);
+	});
+}
+
+#[test]
+fn add_and_remove_stable_liquidity_works() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let user2 = 2;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+
+		create_tokens(user, vec![token_2.clone(), token_3.clone()]);
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 10_000 + ed));
+		for id in [2, 3] {
+			assert_ok!(Assets::mint(RuntimeOrigin::signed(user), id, user, 100_000));
+			assert_ok!(Assets::mint(RuntimeOrigin::signed(user), id, user2, 10_000));
+		}
+		let lp_token = AssetConversion::get_next_pool_asset_id();
+		let (pool_account, pool_id) =
+			create_stable_pool_with_liquidity(user, vec![token_2.clone(), token_3.clone()], 100, 0);
+		events();
+
+		assert_noop!(
+			AssetConversion::add_stable_liquidity(
+				RuntimeOrigin::signed(user),
+				pool_id + 1,
+				vec![10_000, 10_000],
+				0,
+				user
+			),
+			Error::<Test>::PoolNotFound
+		);
+		assert_noop!(
+			AssetConversion::add_stable_liquidity(
+				RuntimeOrigin::signed(user),
+				pool_id,
+				vec![10_000],
+				0,
+				user
+			),
+			Error::<Test>::InvalidStableAmounts
+		);
+		// the first deposit must provide all the assets.
+		assert_noop!(
+			AssetConversion::add_stable_liquidity(
+				RuntimeOrigin::signed(user),
+				pool_id,
+				vec![10_000, 0],
+				0,
+				user
+			),
+			Error::<Test>::InvalidStableAmounts
+		);
+
+		// the invariant of a balanced pool is the sum of its reserves.
+		assert_ok!(AssetConversion::add_stable_liquidity(
+			RuntimeOrigin::signed(user),
+			pool_id,
+			vec![10_000, 10_000],
+			0,
+			user
+		));
+		assert_eq!(
+			events(),
+			[Event::<Test>::StableLiquidityAdded {
+				who: user,
+				mint_to: user,
+				pool_id,
+				amounts: vec![10_000, 10_000],
+				lp_token,
+				lp_token_minted: 19_900,
+			}]
+		);
+		assert_eq!(pool_balance(user, lp_token), 19_900);
+		assert_eq!(pool_balance(pool_account, lp_token), 100);
+		assert_eq!(balance(pool_account, token_2.clone()), 10_000);
+		assert_eq!(balance(pool_account, token_3.clone()), 10_000);
+
+		// a balanced deposit mints in proportion to the supply.
+		assert_noop!(
+			AssetConversion::add_stable_liquidity(
+				RuntimeOrigin::signed(user2),
+				pool_id,
+				vec![1_000, 1_000],
+				2_001,
+				user2
+			),
+			Error::<Test>::InsufficientLiquidityMinted
+		);
+		assert_ok!(AssetConversion::add_stable_liquidity(
+			RuntimeOrigin::signed(user2),
+			pool_id,
+			vec![1_000, 1_000],
+			2_000,
+			user2
+		));
+		assert_eq!(pool_balance(user2, lp_token), 2_000);
+
+		// an imbalanced deposit mints less than a balanced one of the same value.
+		assert_ok!(AssetConversion::add_stable_liquidity(
+			RuntimeOrigin::signed(user2),
+			pool_id,
+			vec![2_000, 0],
+			0,
+			user2
+		));
+		let minted = pool_balance(user2, lp_token) - 2_000;
+		assert!((1_900..2_000).contains(&minted));
+		assert_eq!(balance(pool_account, token_2.clone()), 13_000);
+		assert_eq!(balance(pool_account, token_3.clone()), 11_000);
+		events();
+
+		// the assets are withdrawn in proportion to the reserves.
+		let total_supply = <<Test as Config>::PoolAssets>::total_issuance(lp_token);
+		let expected = [13_000 * 10_000 / total_supply, 11_000 * 10_000 / total_supply];
+		assert_noop!(
+			AssetConversion::remove_stable_liquidity(
+				RuntimeOrigin::signed(user),
+				pool_id,
+				0,
+				vec![0, 0],
+				user
+			),
+			Error::<Test>::ZeroLiquidity
+		);
+		assert_noop!(
+			AssetConversion::remove_stable_liquidity(
+				RuntimeOrigin::signed(user),
+				pool_id,
+				10_000,
+				vec![expected[0] + 1, 0],
+				user
+			),
+			Error::<Test>::WithdrawalDidNotMeetMinimum
+		);
+		let init_balances = [balance(user, token_2.clone()), balance(user, token_3.clone())];
+		assert_ok!(AssetConversion::remove_stable_liquidity(
+			RuntimeOrigin::signed(user),
+			pool_id,
+			10_000,
+			expected.to_vec(),
+			user
+		));
+		assert_eq!(
+			events(),
+			[Event::<Test>::StableLiquidityRemoved {
+				who: user,
+				withdraw_to: user,
+				pool_id,
+				amounts: expected.to_vec(),
+				lp_token,
+				lp_token_burned: 10_000,
+				withdrawal_fee: <Test as Config>::LiquidityWithdrawalFee::get(),
+			}]
+		);
+		assert_eq!(balance(user, token_2.clone()), init_balances[0] + expected[0]);
+		assert_eq!(balance(user, token_3.clone()), init_balances[1] + expected[1]);
+		assert_eq!(pool_balance(user, lp_token), 9_900);
+		assert_eq!(balance(pool_account, token_2.clone()), 13_000 - expected[0]);
+	});
+}
+
+#[test]
+fn swap_routes_through_stable_pool() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let user2 = 2;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+		let token_4 = NativeOrWithId::WithId(4);
+
+		create_tokens(user, vec![token_2.clone(), token_3.clone(), token_4.clone()]);
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 10_000 + ed));
+		for id in [2, 3, 4] {
+			assert_ok!(Assets::mint(RuntimeOrigin::signed(user), id, user, 100_000));
+		}
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user2, 10_000));
+
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_2.clone()),
+			Box::new(token_3.clone())
+		));
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_2.clone()),
+			Box::new(token_3.clone()),
+			10_000,
+			10_000,
+			1,
+			1,
+			user,
+		));
+		let (pool_account, _) = create_stable_pool_with_liquidity(
+			user,
+			vec![token_2.clone(), token_3.clone(), token_4.clone()],
+			100,
+			10_000,
+		);
+		let reserves = AssetConversion::get_reserves(token_2.clone(), token_3.clone()).unwrap();
+
+		// the stable swap pool slips less than the constant product one of the same depth.
+		let constant_product_out =
+			AssetConversion::get_amount_out(&1_000, &reserves.0, &reserves.1).unwrap();
+		let quote = AssetConversion::quote_price_exact_tokens_for_tokens(
+			token_2.clone(),
+			token_3.clone(),
+			1_000,
+			true,
+		)
+		.unwrap();
+		assert!(quote > constant_product_out);
+		assert!(quote < 1_000);
+
+		let post_info = AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user2),
+			bvec![token_2.clone(), token_3.clone()],
+			1_000,
+			quote,
+			user2,
+			true,
+		)
+		.unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(
+				<Test as Config>::WeightInfo::swap_exact_tokens_for_tokens(2) +
+					AssetConversion::stable_hop_weight(3)
+			)
+		);
+		assert_eq!(balance(user2, token_3.clone()), quote);
+		assert_eq!(balance(pool_account, token_2.clone()), 11_000);
+		assert_eq!(balance(pool_account, token_3.clone()), 10_000 - quote);
+		assert_eq!(
+			AssetConversion::get_reserves(token_2.clone(), token_3.clone()).unwrap(),
+			reserves
+		);
+
+		// the pair without a constant product pool swaps through the stable swap pool.
+		let amount_in = AssetConversion::quote_price_tokens_for_exact_tokens(
+			token_2.clone(),
+			token_4.clone(),
+			500,
+			true,
+		)
+		.unwrap();
+		assert!((500..600).contains(&amount_in));
+		assert_ok!(AssetConversion::swap_tokens_for_exact_tokens(
+			RuntimeOrigin::signed(user2),
+			bvec![token_2.clone(), token_4.clone()],
+			500,
+			amount_in,
+			user2,
+			true,
+		));
+		assert_eq!(balance(user2, token_4.clone()), 500);
+		assert_eq!(balance(user2, token_2.clone()), 10_000 - 1_000 - amount_in);
+		assert_eq!(balance(pool_account, token_2.clone()), 11_000 + amount_in);
+
+		assert_noop!(
+			AssetConversion::swap_tokens_for_exact_tokens(
+				RuntimeOrigin::signed(user2),
+				bvec![token_2.clone(), token_4.clone()],
+				10_000,
+				u128::MAX,
+				user2,
+				true,
+			),
+			Error::<Test>::AmountOutTooHigh
+		);
+		// the stable swap pool is priced by its balances before the swap.
+		assert_noop!(
+			AssetConversion::swap_exact_tokens_for_tokens(
+				RuntimeOrigin::signed(user2),
+				bvec![token_2.clone(), token_3.clone(), token_4.clone()],
+				100,
+				1,
+				user2,
+				true,
+			),
+			Error::<Test>::NonUniquePath
+		);
+	});
+}
+
+#[test]
+fn stable_amplification_ramps() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+
+		create_tokens(user, vec![token_2.clone(), token_3.clone()]);
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 10_000 + ed));
+		for id in [2, 3] {
+			assert_ok!(Assets::mint(RuntimeOrigin::signed(user), id, user, 100_000));
+		}
+		let (_, pool_id) = create_stable_pool_with_liquidity(
+			user,
+			vec![token_2.clone(), token_3.clone()],
+			100,
+			10_000,
+		);
+		events();
+		let quote = || {
+			AssetConversion::quote_price_exact_tokens_for_tokens(
+				token_2.clone(),
+				token_3.clone(),
+				1_000,
+				true,
+			)
+			.unwrap()
+		};
+		let amplification =
+			|| AssetConversion::stable_amplification(&StablePools::<Test>::get(pool_id).unwrap());
+
+		assert_noop!(
+			AssetConversion::set_stable_amplification(
+				RuntimeOrigin::signed(user),
+				pool_id,
+				1_000,
+				101
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			AssetConversion::set_stable_amplification(
+				RuntimeOrigin::root(),
+				pool_id + 1,
+				1_000,
+				101
+			),
+			Error::<Test>::PoolNotFound
+		);
+		// the coefficient may change tenfold at most.
+		for future_amplification in [9, 1_001] {
+			assert_noop!(
+				AssetConversion::set_stable_amplification(
+					RuntimeOrigin::root(),
+					pool_id,
+					future_amplification,
+					101
+				),
+				Error::<Test>::InvalidAmplification
+			);
+		}
+
+		let initial_quote = quote();
+		assert_ok!(AssetConversion::set_stable_amplification(
+			RuntimeOrigin::root(),
+			pool_id,
+			1_000,
+			101
+		));
+		assert_eq!(
+			events(),
+			[Event::<Test>::StableAmplificationRampStarted {
+				pool_id,
+				initial_amplification: 100,
+				future_amplification: 1_000,
+				ramp_end: 101,
+			}]
+		);
+		assert_eq!(amplification(), 100);
+		System::set_block_number(51);
+		assert_eq!(amplification(), 550);
+		System::set_block_number(101);
+		assert_eq!(amplification(), 1_000);
+		System::set_block_number(200);
+		assert_eq!(amplification(), 1_000);
+		// the flatter curve slips less.
+		assert!(quote() > initial_quote);
+
+		assert_noop!(
+			AssetConversion::set_stable_amplification(RuntimeOrigin::root(), pool_id, 100, 199),
+			Error::<Test>::InvalidAmplification
+		);
+		// a ramp ending at the current block sets the coefficient at once.
+		assert_ok!(AssetConversion::set_stable_amplification(
+			RuntimeOrigin::root(),
+			pool_id,
+			100,
+			200
+		));
+		assert_eq!(amplification(), 100);
+		assert_eq!(quote(), initial_quote);
+	});
+}
+
+#[test]
+fn swap_credit_through_stable_pool() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+
+		create_tokens(user, vec![token_2.clone(), token_3.clone()]);
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 10_000 + ed));
+		for id in [2, 3] {
+			assert_ok!(Assets::mint(RuntimeOrigin::signed(user), id, user, 100_000));
+		}
+		let (pool_account, _) = create_stable_pool_with_liquidity(
+			user,
+			vec![token_2.clone(), token_3.clone()],
+			100,
+			10_000,
+		);
+
+		let quote = AssetConversion::quote_price_exact_tokens_for_tokens(
+			token_2.clone(),
+			token_3.clone(),
+			1_000,
+			true,
+		)
+		.unwrap();
+		let credit_in = NativeAndAssets::issue(token_2.clone(), 1_000);
+		let credit_out = <AssetConversion as SwapCredit<_>>::swap_exact_tokens_for_tokens(
+			vec![token_2.clone(), token_3.clone()],
+			credit_in,
+			Some(quote),
+		)
+		.unwrap();
+		assert_eq!(credit_out.peek(), quote);
+		assert_eq!(balance(pool_account, token_2.clone()), 11_000);
+		assert_eq!(balance(pool_account, token_3.clone()), 10_000 - quote);
+
+		let amount_in = AssetConversion::quote_price_tokens_for_exact_tokens(
+			token_2.clone(),
+			token_3.clone(),
+			500,
+			true,
+		)
+		.unwrap();
+		let credit_in = NativeAndAssets::issue(token_2.clone(), 1_000);
+		let (credit_out, change) =
+			<AssetConversion as SwapCredit<_>>::swap_tokens_for_exact_tokens(
+				vec![token_2.clone(), token_3.clone()],
+				credit_in,
+				500,
+			)
+			.unwrap();
+		assert_eq!((credit_out.peek(), change.peek()), (500, 1_000 - amount_in));
+		assert_eq!(balance(pool_account, token_2.clone()), 11_000 + amount_in);
+	});
+}
+
+#[test]
+fn quote_best_route_through_stable_pool() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_2 = NativeOrWithId::WithId(2);
+		let token_3 = NativeOrWithId::WithId(3);
+		let token_4 = NativeOrWithId::WithId(4);
+
+		create_tokens(user, vec![token_2.clone(), token_3.clone(), token_4.clone()]);
+		let ed = get_native_ed();
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 10_000 + ed));
+		for id in [2, 3, 4] {
+			assert_ok!(Assets::mint(RuntimeOrigin::signed(user), id, user, 100_000));
+		}
+		create_stable_pool_with_liquidity(
+			user,
+			vec![token_2.clone(), token_3.clone(), token_4.clone()],
+			100,
+			10_000,
+		);
+
+		// the route through the same stable swap pool twice is skipped.
+		let direct = AssetConversion::quote_price_exact_tokens_for_tokens(
+			token_2.clone(),
+			token_4.clone(),
+			100,
+			true,
+		)
+		.unwrap();
+		assert_eq!(
+			AssetConversion::quote_best_route_exact_tokens_for_tokens(
+				token_2.clone(),
+				token_4.clone(),
+				100
+			),
+			Some((vec![token_2.clone(), token_4.clone()], direct))
+		);
+		let direct = AssetConversion::quote_price_tokens_for_exact_tokens(
+			token_3.clone(),
+			token_2.clone(),
+			100,
+			true,
+		)
+		.unwrap();
+		assert_eq!(
+			AssetConversion::quote_best_route_tokens_for_exact_tokens(
+				token_3.clone(),
+				token_2.clone(),
+				100
+			),
+			Some((vec![token_3.clone(), token_2.clone()], direct))
+		);
+	});
+}
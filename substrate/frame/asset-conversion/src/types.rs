@@ -18,6 +18,7 @@
 use super::*;
 use codec::{Decode, Encode, MaxEncodedLen};
 use core::marker::PhantomData;
+use frame_support::weights::Weight;
 use scale_info::TypeInfo;
 use sp_runtime::traits::TryConvert;
 
@@ -41,6 +42,32 @@ pub struct PoolInfo<PoolAssetId> {
 	pub lp_token: PoolAssetId,
 }
 
+/// The pool a single hop of a swap path is routed through.
+pub(crate) enum Hop<T: Config> {
+	/// The constant product pool of the asset pair.
+	ConstantProduct,
+	/// The concentrated liquidity pool of the asset pair.
+	Concentrated(ConcentratedSwap<T>),
+	/// The stable swap pool holding both assets of the pair.
+	Stable {
+		/// The pool the hop goes through.
+		pool_id: StablePoolId,
+		/// The number of assets in the pool.
+		assets: u32,
+	},
+}
+
+impl<T: Config> Hop<T> {
+	/// The weight of the hop on top of a hop through a constant product pool.
+	pub(crate) fn weight(&self) -> Weight {
+		match self {
+			Hop::ConstantProduct => Weight::zero(),
+			Hop::Concentrated(swap) => Pallet::<T>::ticks_crossed_weight(swap.ticks_crossed()),
+			Hop::Stable { assets, .. } => Pallet::<T>::stable_hop_weight(*assets),
+		}
+	}
+}
+
 /// Provides means to resolve the `PoolId` and `AccountId` from a pair of assets.
 ///
 /// Resulting `PoolId` remains consistent whether the asset pair is presented as (asset1, asset2)
@@ -0,0 +1,155 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementations of the `fungibles` and `pallet-assets` traits.
+
+use super::*;
+use frame_support::traits::{
+	fungibles::{Inspect, InspectFreeze, MutateFreeze},
+	tokens::{DepositConsequence, Fortitude, Preservation, Provenance, WithdrawConsequence},
+};
+use pallet_assets::FrozenBalance;
+
+type AssetsOf<T, I> = pallet_assets::Pallet<T, I>;
+
+impl<T: Config<I>, I: 'static> FrozenBalance<T::AssetId, T::AccountId, T::Balance>
+	for Pallet<T, I>
+{
+	fn frozen_balance(asset: T::AssetId, who: &T::AccountId) -> Option<T::Balance> {
+		FrozenBalances::<T, I>::get(asset, who)
+	}
+
+	fn died(asset: T::AssetId, who: &T::AccountId) {
+		FrozenBalances::<T, I>::remove(&asset, who);
+		Freezes::<T, I>::remove(&asset, who);
+	}
+}
+
+impl<T: Config<I>, I: 'static> Inspect<T::AccountId> for Pallet<T, I> {
+	type AssetId = T::AssetId;
+	type Balance = T::Balance;
+
+	fn total_issuance(asset: T::AssetId) -> T::Balance {
+		<AssetsOf<T, I> as Inspect<_>>::total_issuance(asset)
+	}
+
+	fn minimum_balance(asset: T::AssetId) -> T::Balance {
+		<AssetsOf<T, I> as Inspect<_>>::minimum_balance(asset)
+	}
+
+	fn total_balance(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+		<AssetsOf<T, I> as Inspect<_>>::total_balance(asset, who)
+	}
+
+	fn balance(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+		<AssetsOf<T, I> as Inspect<_>>::balance(asset, who)
+	}
+
+	fn reducible_balance(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		preservation: Preservation,
+		force: Fortitude,
+	) -> T::Balance {
+		<AssetsOf<T, I> as Inspect<_>>::reducible_balance(asset, who, preservation, force)
+	}
+
+	fn can_deposit(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		amount: T::Balance,
+		provenance: Provenance,
+	) -> DepositConsequence {
+		<AssetsOf<T, I> as Inspect<_>>::can_deposit(asset, who, amount, provenance)
+	}
+
+	fn can_withdraw(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> WithdrawConsequence<T::Balance> {
+		<AssetsOf<T, I> as Inspect<_>>::can_withdraw(asset, who, amount)
+	}
+
+	fn asset_exists(asset: T::AssetId) -> bool {
+		<AssetsOf<T, I> as Inspect<_>>::asset_exists(asset)
+	}
+}
+
+impl<T: Config<I>, I: 'static> InspectFreeze<T::AccountId> for Pallet<T, I> {
+	type Id = T::RuntimeFreezeReason;
+
+	fn balance_frozen(asset: T::AssetId, id: &Self::Id, who: &T::AccountId) -> T::Balance {
+		Freezes::<T, I>::get(asset, who)
+			.into_iter()
+			.find(|freeze| &freeze.id == id)
+			.map_or_else(Zero::zero, |freeze| freeze.amount)
+	}
+
+	fn can_freeze(asset: T::AssetId, id: &Self::Id, who: &T::AccountId) -> bool {
+		let freezes = Freezes::<T, I>::get(asset, who);
+		!freezes.is_full() || freezes.iter().any(|freeze| &freeze.id == id)
+	}
+}
+
+impl<T: Config<I>, I: 'static> MutateFreeze<T::AccountId> for Pallet<T, I> {
+	fn set_freeze(
+		asset: T::AssetId,
+		id: &Self::Id,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Self::thaw(asset, id, who)
+		}
+		let mut freezes = Freezes::<T, I>::get(&asset, who);
+		if let Some(freeze) = freezes.iter_mut().find(|freeze| &freeze.id == id) {
+			freeze.amount = amount;
+		} else {
+			freezes
+				.try_push(IdAmount { id: *id, amount })
+				.map_err(|_| Error::<T, I>::TooManyFreezes)?;
+		}
+		Self::update_freezes(asset, who, freezes.as_bounded_slice())
+	}
+
+	fn extend_freeze(
+		asset: T::AssetId,
+		id: &Self::Id,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(())
+		}
+		let mut freezes = Freezes::<T, I>::get(&asset, who);
+		if let Some(freeze) = freezes.iter_mut().find(|freeze| &freeze.id == id) {
+			freeze.amount = freeze.amount.max(amount);
+		} else {
+			freezes
+				.try_push(IdAmount { id: *id, amount })
+				.map_err(|_| Error::<T, I>::TooManyFreezes)?;
+		}
+		Self::update_freezes(asset, who, freezes.as_bounded_slice())
+	}
+
+	fn thaw(asset: T::AssetId, id: &Self::Id, who: &T::AccountId) -> DispatchResult {
+		let mut freezes = Freezes::<T, I>::get(&asset, who);
+		freezes.retain(|freeze| &freeze.id != id);
+		Self::update_freezes(asset, who, freezes.as_bounded_slice())
+	}
+}
@@ -0,0 +1,188 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Assets Freezer Pallet
+//!
+//! - [`Config`]
+//!
+//! ## Overview
+//!
+//! Freezes for the balances of `pallet-assets`. This pallet implements
+//! [`fungibles::InspectFreeze`](frame_support::traits::fungibles::InspectFreeze) and
+//! [`fungibles::MutateFreeze`](frame_support::traits::fungibles::MutateFreeze) on top of the
+//! [`fungibles::Inspect`](frame_support::traits::fungibles::Inspect) implementation of an instance
+//! of `pallet-assets`.
+//!
+//! It is meant to be set as [`pallet_assets::Config::Freezer`] of the same instance, which then
+//! keeps the balance of an account from dropping below the largest of its freezes. Freezes are
+//! identified by the overarching `RuntimeFreezeReason`, so an account has at most one freeze per
+//! reason in each asset and operating on them is `O(n)` in the number of reasons.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::DispatchResult,
+	traits::{VariantCount, VariantCountOf},
+	BoundedSlice, BoundedVec,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	RuntimeDebug,
+};
+
+pub use pallet::*;
+
+mod impls;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// An identifier and balance.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct IdAmount<Id, Balance> {
+	/// An identifier for this item.
+	pub id: Id,
+	/// Some amount for this item.
+	pub amount: Balance,
+}
+
+/// The freezes of an account in an asset, one per reason.
+pub type FreezesOf<T, I> = BoundedVec<
+	IdAmount<<T as Config<I>>::RuntimeFreezeReason, <T as pallet_assets::Config<I>>::Balance>,
+	VariantCountOf<<T as Config<I>>::RuntimeFreezeReason>,
+>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config + pallet_assets::Config<I> {
+		/// The overarching freeze reason.
+		type RuntimeFreezeReason: Parameter + Member + MaxEncodedLen + Copy + VariantCount;
+
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(_);
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// The account already has a freeze for every reason.
+		TooManyFreezes,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// The frozen balance of `who` in `asset_id` increased by `amount`.
+		Frozen { who: T::AccountId, asset_id: T::AssetId, amount: T::Balance },
+		/// The frozen balance of `who` in `asset_id` decreased by `amount`.
+		Thawed { who: T::AccountId, asset_id: T::AssetId, amount: T::Balance },
+	}
+
+	/// The freezes of an account in an asset.
+	#[pallet::storage]
+	pub type Freezes<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		FreezesOf<T, I>,
+		ValueQuery,
+	>;
+
+	/// The frozen balance of an account in an asset, i.e. the largest of its freezes.
+	#[pallet::storage]
+	pub type FrozenBalances<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		T::Balance,
+	>;
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			Self::do_try_state()
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Store the `freezes` of `who` in `asset` and update its frozen balance.
+	fn update_freezes(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		freezes: BoundedSlice<
+			IdAmount<T::RuntimeFreezeReason, T::Balance>,
+			VariantCountOf<T::RuntimeFreezeReason>,
+		>,
+	) -> DispatchResult {
+		let prev_frozen = FrozenBalances::<T, I>::get(&asset, who).unwrap_or_default();
+		let after_frozen =
+			freezes.iter().map(|freeze| freeze.amount).max().unwrap_or_else(Zero::zero);
+		if freezes.is_empty() {
+			Freezes::<T, I>::remove(&asset, who);
+			FrozenBalances::<T, I>::remove(&asset, who);
+		} else {
+			Freezes::<T, I>::insert(&asset, who, freezes);
+			FrozenBalances::<T, I>::insert(&asset, who, after_frozen);
+		}
+
+		if prev_frozen > after_frozen {
+			let amount = prev_frozen.saturating_sub(after_frozen);
+			Self::deposit_event(Event::Thawed { who: who.clone(), asset_id: asset, amount });
+		} else if after_frozen > prev_frozen {
+			let amount = after_frozen.saturating_sub(prev_frozen);
+			Self::deposit_event(Event::Frozen { who: who.clone(), asset_id: asset, amount });
+		}
+		Ok(())
+	}
+
+	/// Ensure that the frozen balance of every account is the largest of its freezes.
+	#[cfg(any(test, feature = "try-runtime"))]
+	fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+		for (asset, who, frozen) in FrozenBalances::<T, I>::iter() {
+			let max_frozen =
+				Freezes::<T, I>::get(&asset, &who).iter().map(|freeze| freeze.amount).max();
+			frame_support::ensure!(
+				max_frozen == Some(frozen),
+				"The frozen balance is not the largest of the freezes of the account"
+			);
+		}
+		for (asset, who, _) in Freezes::<T, I>::iter() {
+			frame_support::ensure!(
+				FrozenBalances::<T, I>::contains_key(&asset, &who),
+				"An account with freezes has no frozen balance"
+			);
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,107 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the assets freezer pallet.
+
+use crate::{mock::*, *};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::fungibles::{InspectFreeze, MutateFreeze},
+};
+use pallet_assets::Error as AssetsError;
+
+fn frozen_balance() -> Option<Balance> {
+	FrozenBalances::<Test>::get(ASSET, 1)
+}
+
+fn last_event() -> RuntimeEvent {
+	System::events().pop().expect("RuntimeEvent expected").event
+}
+
+#[test]
+fn set_freeze_and_thaw_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetsFreezer::set_freeze(ASSET, &TestFreezeReason::Vesting, &1, 30));
+		assert_eq!(AssetsFreezer::balance_frozen(ASSET, &TestFreezeReason::Vesting, &1), 30);
+		assert_eq!(frozen_balance(), Some(30));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::AssetsFreezer(Event::Frozen { who: 1, asset_id: ASSET, amount: 30 })
+		);
+
+		// The frozen balance is the largest freeze.
+		assert_ok!(AssetsFreezer::set_freeze(ASSET, &TestFreezeReason::Staking, &1, 50));
+		assert_eq!(frozen_balance(), Some(50));
+		assert_ok!(AssetsFreezer::set_freeze(ASSET, &TestFreezeReason::Vesting, &1, 10));
+		assert_eq!(frozen_balance(), Some(50));
+		assert_ok!(AssetsFreezer::thaw(ASSET, &TestFreezeReason::Staking, &1));
+		assert_eq!(frozen_balance(), Some(10));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::AssetsFreezer(Event::Thawed { who: 1, asset_id: ASSET, amount: 40 })
+		);
+
+		// Setting a freeze to zero thaws it.
+		assert_ok!(AssetsFreezer::set_freeze(ASSET, &TestFreezeReason::Vesting, &1, 0));
+		assert_eq!(frozen_balance(), None);
+		assert!(!Freezes::<Test>::contains_key(ASSET, 1));
+		assert_ok!(AssetsFreezer::do_try_state());
+	});
+}
+
+#[test]
+fn extend_freeze_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetsFreezer::extend_freeze(ASSET, &TestFreezeReason::Vesting, &1, 30));
+		assert_ok!(AssetsFreezer::extend_freeze(ASSET, &TestFreezeReason::Vesting, &1, 20));
+		assert_eq!(AssetsFreezer::balance_frozen(ASSET, &TestFreezeReason::Vesting, &1), 30);
+		assert_ok!(AssetsFreezer::extend_freeze(ASSET, &TestFreezeReason::Vesting, &1, 40));
+		assert_eq!(frozen_balance(), Some(40));
+		assert_ok!(AssetsFreezer::do_try_state());
+	});
+}
+
+#[test]
+fn frozen_balance_cannot_be_transferred() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetsFreezer::set_freeze(ASSET, &TestFreezeReason::Vesting, &1, 30));
+		// The account has to stay alive with its minimum balance on top of the frozen one.
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), ASSET, 2, 70),
+			AssetsError::<Test>::BalanceLow
+		);
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), ASSET, 2, 69));
+
+		assert_ok!(AssetsFreezer::thaw(ASSET, &TestFreezeReason::Vesting, &1));
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), ASSET, 2, 31));
+		assert_eq!(Assets::balance(ASSET, 1), 0);
+	});
+}
+
+#[test]
+fn freezes_are_removed_with_the_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetsFreezer::set_freeze(ASSET, &TestFreezeReason::Vesting, &1, 30));
+		assert_ok!(Assets::freeze_asset(RuntimeOrigin::signed(0), ASSET));
+		assert_ok!(Assets::start_destroy(RuntimeOrigin::signed(0), ASSET));
+		assert_ok!(Assets::destroy_accounts(RuntimeOrigin::signed(0), ASSET));
+
+		assert_eq!(frozen_balance(), None);
+		assert!(!Freezes::<Test>::contains_key(ASSET, 1));
+		assert_ok!(AssetsFreezer::do_try_state());
+	});
+}
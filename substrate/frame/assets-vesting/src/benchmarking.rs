@@ -0,0 +1,383 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Assets vesting pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use frame_benchmarking::v1::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+	assert_ok,
+	traits::fungibles::{Inspect, Mutate},
+};
+use frame_system::{pallet_prelude::BlockNumberFor, Pallet as System, RawOrigin};
+use sp_runtime::traits::CheckedDiv;
+
+use super::*;
+use crate::Pallet as AssetsVesting;
+
+const SEED: u32 = 0;
+
+/// The amount locked by each schedule added through `add_vesting_schedules`.
+fn schedule_locked<T: Config>() -> BalanceOf<T> {
+	T::MinVestedTransfer::get() * 20u32.into()
+}
+
+/// Create an asset and give `who` its minimum balance.
+fn create_asset<T: Config>(who: &T::AccountId) -> T::AssetId {
+	let asset = T::BenchmarkHelper::create_asset();
+	let minimum_balance = T::Assets::minimum_balance(asset.clone());
+	assert_ok!(T::Assets::mint_into(asset.clone(), who, minimum_balance));
+	asset
+}
+
+fn add_vesting_schedules<T: Config>(
+	asset: T::AssetId,
+	target: AccountIdLookupOf<T>,
+	n: u32,
+) -> Result<BalanceOf<T>, &'static str> {
+	let locked = schedule_locked::<T>();
+	// Schedule has a duration of 20.
+	let per_block = T::MinVestedTransfer::get();
+	let starting_block = 1u32;
+
+	let source: T::AccountId = account("source", 0, SEED);
+	let source_lookup = T::Lookup::unlookup(source.clone());
+	// Fund all schedules up front, keeping the source alive.
+	let funds = locked * n.into() + T::Assets::minimum_balance(asset.clone());
+	assert_ok!(T::Assets::mint_into(asset.clone(), &source, funds));
+
+	T::BlockNumberProvider::set_block_number(BlockNumberFor::<T>::zero());
+
+	let mut total_locked: BalanceOf<T> = Zero::zero();
+	for _ in 0..n {
+		total_locked += locked;
+
+		let schedule = VestingInfo::new(locked, per_block, starting_block.into());
+		assert_ok!(AssetsVesting::<T>::do_vested_transfer(
+			asset.clone(),
+			source_lookup.clone(),
+			target.clone(),
+			schedule
+		));
+	}
+
+	Ok(total_locked)
+}
+
+benchmarks! {
+	vest_locked {
+		let s in 1 .. T::MaxVestingSchedules::get();
+
+		let caller: T::AccountId = whitelisted_caller();
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		let asset = create_asset::<T>(&caller);
+		let expected_balance = add_vesting_schedules::<T>(asset.clone(), caller_lookup, s)?;
+
+		// At block zero, everything is vested.
+		assert_eq!(System::<T>::block_number(), BlockNumberFor::<T>::zero());
+		assert_eq!(
+			AssetsVesting::<T>::vesting_balance(asset.clone(), &caller),
+			Some(expected_balance),
+			"Vesting schedule not added",
+		);
+	}: vest(RawOrigin::Signed(caller.clone()), asset.clone())
+	verify {
+		// Nothing happened since everything is still vested.
+		assert_eq!(
+			AssetsVesting::<T>::vesting_balance(asset, &caller),
+			Some(expected_balance),
+			"Vesting schedule was removed",
+		);
+	}
+
+	vest_unlocked {
+		let s in 1 .. T::MaxVestingSchedules::get();
+
+		let caller: T::AccountId = whitelisted_caller();
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		let asset = create_asset::<T>(&caller);
+		add_vesting_schedules::<T>(asset.clone(), caller_lookup, s)?;
+
+		// At block 21, everything is unlocked.
+		T::BlockNumberProvider::set_block_number(21u32.into());
+		assert_eq!(
+			AssetsVesting::<T>::vesting_balance(asset.clone(), &caller),
+			Some(BalanceOf::<T>::zero()),
+			"Vesting schedule still active",
+		);
+	}: vest(RawOrigin::Signed(caller.clone()), asset.clone())
+	verify {
+		// Vesting schedule is removed!
+		assert_eq!(
+			AssetsVesting::<T>::vesting_balance(asset, &caller),
+			None,
+			"Vesting schedule was not removed",
+		);
+	}
+
+	vest_other_locked {
+		let s in 1 .. T::MaxVestingSchedules::get();
+
+		let other: T::AccountId = account("other", 0, SEED);
+		let other_lookup = T::Lookup::unlookup(other.clone());
+		let asset = create_asset::<T>(&other);
+		let expected_balance = add_vesting_schedules::<T>(asset.clone(), other_lookup.clone(), s)?;
+
+		// At block zero, everything is vested.
+		assert_eq!(System::<T>::block_number(), BlockNumberFor::<T>::zero());
+		assert_eq!(
+			AssetsVesting::<T>::vesting_balance(asset.clone(), &other),
+			Some(expected_balance),
+			"Vesting schedule not added",
+		);
+
+		let caller: T::AccountId = whitelisted_caller();
+	}: vest_other(RawOrigin::Signed(caller.clone()), asset.clone(), other_lookup)
+	verify {
+		// Nothing happened since everything is still vested.
+		assert_eq!(
+			AssetsVesting::<T>::vesting_balance(asset, &other),
+			Some(expected_balance),
+			"Vesting schedule was removed",
+		);
+	}
+
+	vest_other_unlocked {
+		let s in 1 .. T::MaxVestingSchedules::get();
+
+		let other: T::AccountId = account("other", 0, SEED);
+		let other_lookup = T::Lookup::unlookup(other.clone());
+		let asset = create_asset::<T>(&other);
+		add_vesting_schedules::<T>(asset.clone(), other_lookup.clone(), s)?;
+		// At block 21 everything is unlocked.
+		T::BlockNumberProvider::set_block_number(21u32.into());
+
+		assert_eq!(
+			AssetsVesting::<T>::vesting_balance(asset.clone(), &other),
+			Some(BalanceOf::<T>::zero()),
+			"Vesting schedule still active",
+		);
+
+		let caller: T::AccountId = whitelisted_caller();
+	}: vest_other(RawOrigin::Signed(caller.clone()), asset.clone(), other_lookup)
+	verify {
+		// Vesting schedule is removed.
+		assert_eq!(
+			AssetsVesting::<T>::vesting_balance(asset, &other),
+			None,
+			"Vesting schedule was not removed",
+		);
+	}
+
+	vested_transfer {
+		let s in 0 .. T::MaxVestingSchedules::get() - 1;
+
+		let target: T::AccountId = account("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+		let asset = create_asset::<T>(&target);
+		let orig_balance = T::Assets::balance(asset.clone(), &target);
+		let mut expected_balance =
+			add_vesting_schedules::<T>(asset.clone(), target_lookup.clone(), s)?;
+
+		let transfer_amount = T::MinVestedTransfer::get();
+		let per_block = transfer_amount.checked_div(&20u32.into()).unwrap();
+		expected_balance += transfer_amount;
+
+		let caller: T::AccountId = whitelisted_caller();
+		assert_ok!(T::Assets::mint_into(
+			asset.clone(),
+			&caller,
+			transfer_amount + T::Assets::minimum_balance(asset.clone()),
+		));
+
+		let vesting_schedule = VestingInfo::new(
+			transfer_amount,
+			per_block,
+			1u32.into(),
+		);
+	}: _(RawOrigin::Signed(caller), asset.clone(), target_lookup, vesting_schedule)
+	verify {
+		assert_eq!(
+			orig_balance + expected_balance,
+			T::Assets::balance(asset.clone(), &target),
+			"Transfer didn't happen",
+		);
+		assert_eq!(
+			AssetsVesting::<T>::vesting_balance(asset, &target),
+			Some(expected_balance),
+			"Freeze not correctly updated",
+		);
+	}
+
+	force_vested_transfer {
+		let s in 0 .. T::MaxVestingSchedules::get() - 1;
+
+		let target: T::AccountId = account("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+		let asset = create_asset::<T>(&target);
+		let orig_balance = T::Assets::balance(asset.clone(), &target);
+		let mut expected_balance =
+			add_vesting_schedules::<T>(asset.clone(), target_lookup.clone(), s)?;
+
+		let transfer_amount = T::MinVestedTransfer::get();
+		let per_block = transfer_amount.checked_div(&20u32.into()).unwrap();
+		expected_balance += transfer_amount;
+
+		let source: T::AccountId = account("force_source", 0, SEED);
+		let source_lookup = T::Lookup::unlookup(source.clone());
+		assert_ok!(T::Assets::mint_into(
+			asset.clone(),
+			&source,
+			transfer_amount + T::Assets::minimum_balance(asset.clone()),
+		));
+
+		let vesting_schedule = VestingInfo::new(
+			transfer_amount,
+			per_block,
+			1u32.into(),
+		);
+	}: _(RawOrigin::Root, asset.clone(), source_lookup, target_lookup, vesting_schedule)
+	verify {
+		assert_eq!(
+			orig_balance + expected_balance,
+			T::Assets::balance(asset.clone(), &target),
+			"Transfer didn't happen",
+		);
+		assert_eq!(
+			AssetsVesting::<T>::vesting_balance(asset, &target),
+			Some(expected_balance),
+			"Freeze not correctly updated",
+		);
+	}
+
+	not_unlocking_merge_schedules {
+		let s in 2 .. T::MaxVestingSchedules::get();
+
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		let asset = create_asset::<T>(&caller);
+		let expected_balance = add_vesting_schedules::<T>(asset.clone(), caller_lookup, s)?;
+
+		// Schedules are not vesting at block 0.
+		assert_eq!(System::<T>::block_number(), BlockNumberFor::<T>::zero());
+		assert_eq!(
+			AssetsVesting::<T>::vesting_balance(asset.clone(), &caller),
+			Some(expected_balance),
+			"Vesting balance should equal sum locked of all schedules",
+		);
+		assert_eq!(
+			AssetsVesting::<T>::vesting(asset.clone(), &caller).unwrap().len(),
+			s as usize,
+			"There should be exactly max vesting schedules"
+		);
+	}: merge_schedules(RawOrigin::Signed(caller.clone()), asset.clone(), 0, s - 1)
+	verify {
+		let expected_schedule = VestingInfo::new(
+			schedule_locked::<T>() * 2u32.into(),
+			T::MinVestedTransfer::get() * 2u32.into(),
+			1u32.into(),
+		);
+		let expected_index = (s - 2) as usize;
+		assert_eq!(
+			AssetsVesting::<T>::vesting(asset.clone(), &caller).unwrap()[expected_index],
+			expected_schedule
+		);
+		assert_eq!(
+			AssetsVesting::<T>::vesting_balance(asset.clone(), &caller),
+			Some(expected_balance),
+			"Vesting balance should equal total locked of all schedules",
+		);
+		assert_eq!(
+			AssetsVesting::<T>::vesting(asset, &caller).unwrap().len(),
+			(s - 1) as usize,
+			"Schedule count should reduce by 1"
+		);
+	}
+
+	unlocking_merge_schedules {
+		let s in 2 .. T::MaxVestingSchedules::get();
+
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		let asset = create_asset::<T>(&caller);
+		let total_transferred = add_vesting_schedules::<T>(asset.clone(), caller_lookup, s)?;
+
+		// Go to about half way through all the schedules duration. (They all start at 1, and have a duration of 20 or 21).
+		T::BlockNumberProvider::set_block_number(11u32.into());
+		// We expect half the original locked balance (+ any remainder that vests on the last block).
+		let expected_balance = total_transferred / 2u32.into();
+		assert_eq!(
+			AssetsVesting::<T>::vesting_balance(asset.clone(), &caller),
+			Some(expected_balance),
+			"Vesting balance should reflect that we are half way through all schedules duration",
+		);
+		assert_eq!(
+			AssetsVesting::<T>::vesting(asset.clone(), &caller).unwrap().len(),
+			s as usize,
+			"There should be exactly max vesting schedules"
+		);
+	}: merge_schedules(RawOrigin::Signed(caller.clone()), asset.clone(), 0, s - 1)
+	verify {
+		let expected_schedule = VestingInfo::new(
+			T::MinVestedTransfer::get() * 2u32.into() * 10u32.into(),
+			T::MinVestedTransfer::get() * 2u32.into(),
+			11u32.into(),
+		);
+		let expected_index = (s - 2) as usize;
+		assert_eq!(
+			AssetsVesting::<T>::vesting(asset.clone(), &caller).unwrap()[expected_index],
+			expected_schedule,
+			"New schedule is properly created and placed"
+		);
+		assert_eq!(
+			AssetsVesting::<T>::vesting_balance(asset.clone(), &caller),
+			Some(expected_balance),
+			"Vesting balance should equal half total locked of all schedules",
+		);
+		assert_eq!(
+			AssetsVesting::<T>::vesting(asset, &caller).unwrap().len(),
+			(s - 1) as usize,
+			"Schedule count should reduce by 1"
+		);
+	}
+
+	force_remove_vesting_schedule {
+		let s in 2 .. T::MaxVestingSchedules::get();
+
+		let target: T::AccountId = account("target", 0, SEED);
+		let target_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(target.clone());
+		let asset = create_asset::<T>(&target);
+		let _ = add_vesting_schedules::<T>(asset.clone(), target_lookup.clone(), s)?;
+
+		// The last vesting schedule.
+		let schedule_index = s - 1;
+	}: _(RawOrigin::Root, asset.clone(), target_lookup, schedule_index)
+	verify {
+		assert_eq!(
+			AssetsVesting::<T>::vesting(asset, &target).unwrap().len(),
+			schedule_index as usize,
+			"Schedule count should reduce by 1"
+		);
+	}
+
+	impl_benchmark_test_suite!(
+		AssetsVesting,
+		crate::mock::ExtBuilder::default().build(),
+		crate::mock::Test,
+	);
+}
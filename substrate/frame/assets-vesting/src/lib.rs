@@ -0,0 +1,791 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Assets Vesting Pallet
+//!
+//! - [`Config`]
+//! - [`Call`]
+//!
+//! ## Overview
+//!
+//! The counterpart of `pallet-vesting` for fungible assets, such as the ones of `pallet-assets`.
+//! It places a linear curve on the frozen balance of an account in an asset. A freeze prevents
+//! the balance from dropping below the *unvested* amount. The freezes of `pallet-assets` are
+//! provided by `pallet-assets-freezer`.
+//!
+//! Vesting schedules are kept per asset and account, with the same [`VestingInfo`] as
+//! `pallet-vesting`. As the amount vested increases over time, the amount unvested reduces.
+//! However, freezes remain in place and explicit action is needed on behalf of the user to ensure
+//! that the amount frozen is equivalent to the amount remaining to be vested. This is done through
+//! a dispatchable function, either `vest` (in typical case where the sender is calling on their own
+//! behalf) or `vest_other` in case the sender is calling on another account's behalf.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `vest` - Update the freeze, reducing it in line with the amount "vested" so far.
+//! - `vest_other` - Update the freeze of another account, reducing it in line with the amount
+//!   "vested" so far.
+//! - `vested_transfer` - Transfer assets to an account under a vesting schedule.
+//! - `force_vested_transfer` - Transfer assets between arbitrary accounts under a vesting schedule.
+//! - `merge_schedules` - Merge two vesting schedules of the sender.
+//! - `force_remove_vesting_schedule` - Remove a vesting schedule of an account.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	storage::bounded_vec::BoundedVec,
+	traits::{
+		fungibles,
+		tokens::{fungibles::MutateFreeze, AssetId, Preservation},
+		Get,
+	},
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::{
+	traits::{BlockNumberProvider, Convert, One, Saturating, StaticLookup, Zero},
+	DispatchError, TokenError,
+};
+use sp_std::prelude::*;
+
+pub use pallet::*;
+pub use pallet_vesting::VestingInfo;
+pub use weights::WeightInfo;
+
+type BalanceOf<T> =
+	<<T as Config>::Assets as fungibles::Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+type VestingInfoOf<T> = VestingInfo<BalanceOf<T>, BlockNumberFor<T>>;
+type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
+
+/// Actions to take against a user's `Vesting` storage entry.
+#[derive(Clone, Copy)]
+enum VestingAction {
+	/// Do not actively remove any schedules.
+	Passive,
+	/// Remove the schedule specified by the index.
+	Remove { index: usize },
+	/// Remove the two schedules, specified by index, so they can be merged.
+	Merge { index1: usize, index2: usize },
+}
+
+impl VestingAction {
+	/// Whether or not the filter says the schedule index should be removed.
+	fn should_remove(&self, index: usize) -> bool {
+		match self {
+			Self::Passive => false,
+			Self::Remove { index: index1 } => *index1 == index,
+			Self::Merge { index1, index2 } => *index1 == index || *index2 == index,
+		}
+	}
+
+	/// Pick the schedules that this action dictates should continue vesting undisturbed.
+	fn pick_schedules<T: Config>(
+		&self,
+		schedules: Vec<VestingInfoOf<T>>,
+	) -> impl Iterator<Item = VestingInfoOf<T>> + '_ {
+		schedules.into_iter().enumerate().filter_map(move |(index, schedule)| {
+			if self.should_remove(index) {
+				None
+			} else {
+				Some(schedule)
+			}
+		})
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	/// A reason for this pallet freezing funds.
+	#[pallet::composite_enum]
+	pub enum FreezeReason {
+		/// The funds are frozen until they vested.
+		Vesting,
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	pub trait BenchmarkHelper<AssetId> {
+		/// Create an asset with a minimum balance of one and return its identifier.
+		fn create_asset() -> AssetId;
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Identifier of an asset which can be vested.
+		type AssetId: AssetId + MaybeSerializeDeserialize;
+
+		/// The assets which can be vested.
+		type Assets: fungibles::Inspect<Self::AccountId, AssetId = Self::AssetId>
+			+ fungibles::Mutate<Self::AccountId>;
+
+		/// The freezes of [`Config::Assets`], such as `pallet-assets-freezer` for `pallet-assets`.
+		type Freezer: fungibles::MutateFreeze<
+			Self::AccountId,
+			AssetId = Self::AssetId,
+			Balance = BalanceOf<Self>,
+			Id = Self::RuntimeFreezeReason,
+		>;
+
+		/// The overarching freeze reason.
+		type RuntimeFreezeReason: From<FreezeReason>;
+
+		/// Convert the block number into a balance.
+		type BlockNumberToBalance: Convert<BlockNumberFor<Self>, BalanceOf<Self>>;
+
+		/// The minimum amount transferred to call `vested_transfer`.
+		#[pallet::constant]
+		type MinVestedTransfer: Get<BalanceOf<Self>>;
+
+		/// Maximum number of vesting schedules an account may have in an asset at a given moment.
+		#[pallet::constant]
+		type MaxVestingSchedules: Get<u32>;
+
+		/// Provider for the block number.
+		type BlockNumberProvider: BlockNumberProvider<BlockNumber = BlockNumberFor<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Helper trait for benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::AssetId>;
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn integrity_test() {
+			assert!(
+				T::MaxVestingSchedules::get() > 0,
+				"`MaxVestingSchedules` must be greater than 0"
+			);
+		}
+	}
+
+	/// Information regarding the vesting of a given account in a given asset.
+	#[pallet::storage]
+	pub type Vesting<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<VestingInfoOf<T>, T::MaxVestingSchedules>,
+	>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		pub vesting:
+			Vec<(T::AssetId, T::AccountId, BlockNumberFor<T>, BlockNumberFor<T>, BalanceOf<T>)>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			// Generate initial vesting configuration
+			// * asset - Asset which is vesting
+			// * who - Account which we are generating vesting configuration for
+			// * begin - Block when the account will start to vest
+			// * length - Number of blocks from `begin` until fully vested
+			// * liquid - Number of units which can be spent before vesting begins
+			for (asset, who, begin, length, liquid) in self.vesting.iter() {
+				if let Err(e) =
+					Pallet::<T>::add_genesis_vesting(asset.clone(), who, *begin, *length, *liquid)
+				{
+					panic!("Invalid genesis vesting of {:?} in {:?}: {:?}", who, asset, e);
+				}
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The amount vested has been updated. This could indicate a change in funds available.
+		/// The balance given is the amount which is left unvested (and thus frozen).
+		VestingUpdated { asset: T::AssetId, account: T::AccountId, unvested: BalanceOf<T> },
+		/// An account has become fully vested in an asset.
+		VestingCompleted { asset: T::AssetId, account: T::AccountId },
+	}
+
+	/// Error for the assets vesting pallet.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account given is not vesting.
+		NotVesting,
+		/// The account already has `MaxVestingSchedules` count of schedules and thus
+		/// cannot add another one. Consider merging existing schedules in order to add another.
+		AtMaxVestingSchedules,
+		/// Amount being transferred is too low to create a vesting schedule.
+		AmountLow,
+		/// An index was out of bounds of the vesting schedules.
+		ScheduleIndexOutOfBounds,
+		/// Failed to create a new schedule because some parameter was invalid.
+		InvalidScheduleParams,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Unfreeze any vested funds of the sender account in an asset.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have funds still
+		/// frozen under this pallet.
+		///
+		/// - `asset`: The asset whose vested funds should be unfrozen.
+		///
+		/// Emits either `VestingCompleted` or `VestingUpdated`.
+		///
+		/// ## Complexity
+		/// - `O(1)`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::vest_locked(T::MaxVestingSchedules::get())
+			.max(T::WeightInfo::vest_unlocked(T::MaxVestingSchedules::get()))
+		)]
+		pub fn vest(origin: OriginFor<T>, asset: T::AssetId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_vest(asset, who)
+		}
+
+		/// Unfreeze any vested funds of a `target` account in an asset.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `asset`: The asset whose vested funds should be unfrozen.
+		/// - `target`: The account whose vested funds should be unfrozen. Must have funds still
+		/// frozen under this pallet.
+		///
+		/// Emits either `VestingCompleted` or `VestingUpdated`.
+		///
+		/// ## Complexity
+		/// - `O(1)`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::vest_other_locked(T::MaxVestingSchedules::get())
+			.max(T::WeightInfo::vest_other_unlocked(T::MaxVestingSchedules::get()))
+		)]
+		pub fn vest_other(
+			origin: OriginFor<T>,
+			asset: T::AssetId,
+			target: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let who = T::Lookup::lookup(target)?;
+			Self::do_vest(asset, who)
+		}
+
+		/// Create a vested transfer.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `asset`: The asset to transfer.
+		/// - `target`: The account receiving the vested funds.
+		/// - `schedule`: The vesting schedule attached to the transfer.
+		///
+		/// Emits `VestingUpdated`.
+		///
+		/// NOTE: This will unfreeze all schedules of `target` in `asset` through the current
+		/// block.
+		///
+		/// ## Complexity
+		/// - `O(1)`.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::vested_transfer(T::MaxVestingSchedules::get()))]
+		pub fn vested_transfer(
+			origin: OriginFor<T>,
+			asset: T::AssetId,
+			target: AccountIdLookupOf<T>,
+			schedule: VestingInfoOf<T>,
+		) -> DispatchResult {
+			let transactor = ensure_signed(origin)?;
+			let transactor = <T::Lookup as StaticLookup>::unlookup(transactor);
+			Self::do_vested_transfer(asset, transactor, target, schedule)
+		}
+
+		/// Force a vested transfer.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		///
+		/// - `asset`: The asset to transfer.
+		/// - `source`: The account whose funds should be transferred.
+		/// - `target`: The account that should be transferred the vested funds.
+		/// - `schedule`: The vesting schedule attached to the transfer.
+		///
+		/// Emits `VestingUpdated`.
+		///
+		/// NOTE: This will unfreeze all schedules of `target` in `asset` through the current
+		/// block.
+		///
+		/// ## Complexity
+		/// - `O(1)`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::force_vested_transfer(T::MaxVestingSchedules::get()))]
+		pub fn force_vested_transfer(
+			origin: OriginFor<T>,
+			asset: T::AssetId,
+			source: AccountIdLookupOf<T>,
+			target: AccountIdLookupOf<T>,
+			schedule: VestingInfoOf<T>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			Self::do_vested_transfer(asset, source, target, schedule)
+		}
+
+		/// Merge two vesting schedules of an asset together, creating a new vesting schedule that
+		/// unlocks over the highest possible start and end blocks. If both schedules have already
+		/// started the current block will be used as the schedule start; with the caveat that if
+		/// one schedule is finished by the current block, the other will be treated as the new
+		/// merged schedule, unmodified.
+		///
+		/// NOTE: If `schedule1_index == schedule2_index` this is a no-op.
+		/// NOTE: This will unfreeze all schedules through the current block prior to merging.
+		/// NOTE: If both schedules have ended by the current block, no new schedule will be created
+		/// and both will be removed.
+		///
+		/// Merged schedule attributes:
+		/// - `starting_block`: `MAX(schedule1.starting_block, scheduled2.starting_block,
+		///   current_block)`.
+		/// - `ending_block`: `MAX(schedule1.ending_block, schedule2.ending_block)`.
+		/// - `locked`: `schedule1.locked_at(current_block) + schedule2.locked_at(current_block)`.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `asset`: The asset of the schedules.
+		/// - `schedule1_index`: index of the first schedule to merge.
+		/// - `schedule2_index`: index of the second schedule to merge.
+		#[pallet::call_index(4)]
+		#[pallet::weight(
+			T::WeightInfo::not_unlocking_merge_schedules(T::MaxVestingSchedules::get())
+			.max(T::WeightInfo::unlocking_merge_schedules(T::MaxVestingSchedules::get()))
+		)]
+		pub fn merge_schedules(
+			origin: OriginFor<T>,
+			asset: T::AssetId,
+			schedule1_index: u32,
+			schedule2_index: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			if schedule1_index == schedule2_index {
+				return Ok(())
+			};
+			let schedule1_index = schedule1_index as usize;
+			let schedule2_index = schedule2_index as usize;
+
+			let schedules = Vesting::<T>::get(&asset, &who).ok_or(Error::<T>::NotVesting)?;
+			let merge_action =
+				VestingAction::Merge { index1: schedule1_index, index2: schedule2_index };
+
+			let (schedules, locked_now) = Self::exec_action(schedules.to_vec(), merge_action)?;
+
+			Self::write_vesting(&asset, &who, schedules)?;
+			Self::write_freeze(asset, &who, locked_now)
+		}
+
+		/// Force remove a vesting schedule
+		///
+		/// The dispatch origin for this call must be _Root_.
+		///
+		/// - `asset`: The asset of the schedule.
+		/// - `target`: An account that has a vesting schedule
+		/// - `schedule_index`: The vesting schedule index that should be removed
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::force_remove_vesting_schedule(
+			T::MaxVestingSchedules::get()
+		))]
+		pub fn force_remove_vesting_schedule(
+			origin: OriginFor<T>,
+			asset: T::AssetId,
+			target: AccountIdLookupOf<T>,
+			schedule_index: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let who = T::Lookup::lookup(target)?;
+
+			let schedules_count = Vesting::<T>::decode_len(&asset, &who).unwrap_or_default();
+			ensure!(schedule_index < schedules_count as u32, Error::<T>::InvalidScheduleParams);
+
+			Self::remove_vesting_schedule(asset, &who, schedule_index)?;
+
+			Ok(Some(T::WeightInfo::force_remove_vesting_schedule(schedules_count as u32)).into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Freeze the balance of `who` in `asset` at genesis, except for `liquid`, and vest it over
+	/// `length` blocks from `begin` on.
+	fn add_genesis_vesting(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		begin: BlockNumberFor<T>,
+		length: BlockNumberFor<T>,
+		liquid: BalanceOf<T>,
+	) -> DispatchResult {
+		let balance = <T::Assets as fungibles::Inspect<_>>::balance(asset.clone(), who);
+		// Assets must be initialised before vesting.
+		ensure!(!balance.is_zero(), TokenError::FundsUnavailable);
+		// Total genesis `balance` minus `liquid` equals funds frozen for vesting.
+		let locked = balance.saturating_sub(liquid);
+		let length_as_balance = T::BlockNumberToBalance::convert(length);
+		let per_block = locked / length_as_balance.max(One::one());
+		let vesting_info = VestingInfo::new(locked, per_block, begin);
+		ensure!(vesting_info.is_valid(), Error::<T>::InvalidScheduleParams);
+
+		Vesting::<T>::try_append(&asset, who, vesting_info)
+			.map_err(|_| Error::<T>::AtMaxVestingSchedules)?;
+		T::Freezer::set_freeze(asset, &FreezeReason::Vesting.into(), who, locked)
+	}
+
+	/// The vesting schedules of `who` in `asset`.
+	pub fn vesting(
+		asset: T::AssetId,
+		who: &T::AccountId,
+	) -> Option<BoundedVec<VestingInfoOf<T>, T::MaxVestingSchedules>> {
+		Vesting::<T>::get(asset, who)
+	}
+
+	/// Get the amount of `asset` that is currently being vested and cannot be transferred out of
+	/// the account of `who`.
+	pub fn vesting_balance(asset: T::AssetId, who: &T::AccountId) -> Option<BalanceOf<T>> {
+		let schedules = Vesting::<T>::get(&asset, who)?;
+		let now = T::BlockNumberProvider::current_block_number();
+		let total_locked_now =
+			schedules.iter().fold(Zero::zero(), |total: BalanceOf<T>, schedule| {
+				schedule.locked_at::<T::BlockNumberToBalance>(now).saturating_add(total)
+			});
+		Some(<T::Assets as fungibles::Inspect<_>>::balance(asset, who).min(total_locked_now))
+	}
+
+	/// Adds a vesting schedule of `asset` to the account of `who`.
+	///
+	/// If the account has `MaxVestingSchedules`, an Error is returned and nothing
+	/// is updated.
+	///
+	/// On success, a linearly reducing amount of funds will be frozen. In order to realise any
+	/// reduction of the freeze over time as it diminishes, the account owner must use `vest` or
+	/// `vest_other`.
+	///
+	/// Is a no-op if the amount to be vested is zero.
+	///
+	/// NOTE: This doesn't alter the balance of the account.
+	pub fn add_vesting_schedule(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		locked: BalanceOf<T>,
+		per_block: BalanceOf<T>,
+		starting_block: BlockNumberFor<T>,
+	) -> DispatchResult {
+		if locked.is_zero() {
+			return Ok(())
+		}
+
+		let vesting_schedule = VestingInfo::new(locked, per_block, starting_block);
+		// Check for `per_block` or `locked` of 0.
+		if !vesting_schedule.is_valid() {
+			return Err(Error::<T>::InvalidScheduleParams.into())
+		};
+
+		let mut schedules = Vesting::<T>::get(&asset, who).unwrap_or_default();
+
+		// NOTE: we must push the new schedule so that `exec_action`
+		// will give the correct new locked amount.
+		ensure!(schedules.try_push(vesting_schedule).is_ok(), Error::<T>::AtMaxVestingSchedules);
+
+		let (schedules, locked_now) =
+			Self::exec_action(schedules.to_vec(), VestingAction::Passive)?;
+
+		Self::write_vesting(&asset, who, schedules)?;
+		Self::write_freeze(asset, who, locked_now)
+	}
+
+	/// Ensure we can call `add_vesting_schedule` without error. This should always
+	/// be called prior to `add_vesting_schedule`.
+	pub fn can_add_vesting_schedule(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		locked: BalanceOf<T>,
+		per_block: BalanceOf<T>,
+		starting_block: BlockNumberFor<T>,
+	) -> DispatchResult {
+		// Check for `per_block` or `locked` of 0.
+		if !VestingInfo::new(locked, per_block, starting_block).is_valid() {
+			return Err(Error::<T>::InvalidScheduleParams.into())
+		}
+
+		ensure!(
+			(Vesting::<T>::decode_len(asset, who).unwrap_or_default() as u32) <
+				T::MaxVestingSchedules::get(),
+			Error::<T>::AtMaxVestingSchedules
+		);
+
+		Ok(())
+	}
+
+	/// Remove a vesting schedule of `asset` for a given account.
+	pub fn remove_vesting_schedule(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		schedule_index: u32,
+	) -> DispatchResult {
+		let schedules = Vesting::<T>::get(&asset, who).ok_or(Error::<T>::NotVesting)?;
+		let remove_action = VestingAction::Remove { index: schedule_index as usize };
+
+		let (schedules, locked_now) = Self::exec_action(schedules.to_vec(), remove_action)?;
+
+		Self::write_vesting(&asset, who, schedules)?;
+		Self::write_freeze(asset, who, locked_now)
+	}
+
+	// Create a new `VestingInfo`, based off of two other `VestingInfo`s.
+	// NOTE: We assume both schedules have had funds unlocked up through the current block.
+	fn merge_vesting_info(
+		now: BlockNumberFor<T>,
+		schedule1: VestingInfoOf<T>,
+		schedule2: VestingInfoOf<T>,
+	) -> Option<VestingInfoOf<T>> {
+		let schedule1_ending_block = schedule1.ending_block_as_balance::<T::BlockNumberToBalance>();
+		let schedule2_ending_block = schedule2.ending_block_as_balance::<T::BlockNumberToBalance>();
+		let now_as_balance = T::BlockNumberToBalance::convert(now);
+
+		// Check if one or both schedules have ended.
+		match (schedule1_ending_block <= now_as_balance, schedule2_ending_block <= now_as_balance) {
+			// If both schedules have ended, we don't merge and exit early.
+			(true, true) => return None,
+			// If one schedule has ended, we treat the one that has not ended as the new
+			// merged schedule.
+			(true, false) => return Some(schedule2),
+			(false, true) => return Some(schedule1),
+			// If neither schedule has ended don't exit early.
+			_ => {},
+		}
+
+		let locked = schedule1
+			.locked_at::<T::BlockNumberToBalance>(now)
+			.saturating_add(schedule2.locked_at::<T::BlockNumberToBalance>(now));
+		// This shouldn't happen because we know at least one ending block is greater than now,
+		// thus at least a schedule a some locked balance.
+		debug_assert!(
+			!locked.is_zero(),
+			"merge_vesting_info validation checks failed to catch a locked of 0"
+		);
+
+		let ending_block = schedule1_ending_block.max(schedule2_ending_block);
+		let starting_block = now.max(schedule1.starting_block()).max(schedule2.starting_block());
+
+		let per_block = {
+			let duration = ending_block
+				.saturating_sub(T::BlockNumberToBalance::convert(starting_block))
+				.max(One::one());
+			(locked / duration).max(One::one())
+		};
+
+		let schedule = VestingInfo::new(locked, per_block, starting_block);
+		debug_assert!(schedule.is_valid(), "merge_vesting_info schedule validation check failed");
+
+		Some(schedule)
+	}
+
+	// Execute a vested transfer of `asset` from `source` to `target` with the given `schedule`.
+	fn do_vested_transfer(
+		asset: T::AssetId,
+		source: AccountIdLookupOf<T>,
+		target: AccountIdLookupOf<T>,
+		schedule: VestingInfoOf<T>,
+	) -> DispatchResult {
+		// Validate user inputs.
+		ensure!(schedule.locked() >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
+		if !schedule.is_valid() {
+			return Err(Error::<T>::InvalidScheduleParams.into())
+		};
+		let target = T::Lookup::lookup(target)?;
+		let source = T::Lookup::lookup(source)?;
+
+		// Check we can add to this account prior to any storage writes.
+		Self::can_add_vesting_schedule(
+			asset.clone(),
+			&target,
+			schedule.locked(),
+			schedule.per_block(),
+			schedule.starting_block(),
+		)?;
+
+		<T::Assets as fungibles::Mutate<_>>::transfer(
+			asset.clone(),
+			&source,
+			&target,
+			schedule.locked(),
+			Preservation::Expendable,
+		)?;
+
+		Self::add_vesting_schedule(
+			asset,
+			&target,
+			schedule.locked(),
+			schedule.per_block(),
+			schedule.starting_block(),
+		)
+	}
+
+	/// Iterate through the schedules to track the current locked amount and
+	/// filter out completed and specified schedules.
+	///
+	/// Returns a tuple that consists of:
+	/// - Vec of vesting schedules, where completed schedules and those specified
+	/// 	by filter are removed. (Note the vec is not checked for respecting
+	/// 	bounded length.)
+	/// - The amount locked at the current block number based on the given schedules.
+	///
+	/// NOTE: the amount locked does not include any schedules that are filtered out via `action`.
+	fn report_schedule_updates(
+		schedules: Vec<VestingInfoOf<T>>,
+		action: VestingAction,
+	) -> (Vec<VestingInfoOf<T>>, BalanceOf<T>) {
+		let now = T::BlockNumberProvider::current_block_number();
+
+		let mut total_locked_now: BalanceOf<T> = Zero::zero();
+		let filtered_schedules = action
+			.pick_schedules::<T>(schedules)
+			.filter(|schedule| {
+				let locked_now = schedule.locked_at::<T::BlockNumberToBalance>(now);
+				let keep = !locked_now.is_zero();
+				if keep {
+					total_locked_now = total_locked_now.saturating_add(locked_now);
+				}
+				keep
+			})
+			.collect::<Vec<_>>();
+
+		(filtered_schedules, total_locked_now)
+	}
+
+	/// Write an account's updated vesting freeze of `asset` to storage.
+	fn write_freeze(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		total_locked_now: BalanceOf<T>,
+	) -> DispatchResult {
+		if total_locked_now.is_zero() {
+			T::Freezer::thaw(asset.clone(), &FreezeReason::Vesting.into(), who)?;
+			Self::deposit_event(Event::<T>::VestingCompleted { asset, account: who.clone() });
+		} else {
+			T::Freezer::set_freeze(
+				asset.clone(),
+				&FreezeReason::Vesting.into(),
+				who,
+				total_locked_now,
+			)?;
+			Self::deposit_event(Event::<T>::VestingUpdated {
+				asset,
+				account: who.clone(),
+				unvested: total_locked_now,
+			});
+		};
+		Ok(())
+	}
+
+	/// Write an account's updated vesting schedules of `asset` to storage.
+	fn write_vesting(
+		asset: &T::AssetId,
+		who: &T::AccountId,
+		schedules: Vec<VestingInfoOf<T>>,
+	) -> Result<(), DispatchError> {
+		let schedules: BoundedVec<VestingInfoOf<T>, T::MaxVestingSchedules> =
+			schedules.try_into().map_err(|_| Error::<T>::AtMaxVestingSchedules)?;
+
+		if schedules.len() == 0 {
+			Vesting::<T>::remove(asset, who);
+		} else {
+			Vesting::<T>::insert(asset, who, schedules)
+		}
+
+		Ok(())
+	}
+
+	/// Unfreeze any vested funds of `who` in `asset`.
+	fn do_vest(asset: T::AssetId, who: T::AccountId) -> DispatchResult {
+		let schedules = Vesting::<T>::get(&asset, &who).ok_or(Error::<T>::NotVesting)?;
+
+		let (schedules, locked_now) =
+			Self::exec_action(schedules.to_vec(), VestingAction::Passive)?;
+
+		Self::write_vesting(&asset, &who, schedules)?;
+		Self::write_freeze(asset, &who, locked_now)
+	}
+
+	/// Execute a `VestingAction` against the given `schedules`. Returns the updated schedules
+	/// and locked amount.
+	fn exec_action(
+		schedules: Vec<VestingInfoOf<T>>,
+		action: VestingAction,
+	) -> Result<(Vec<VestingInfoOf<T>>, BalanceOf<T>), DispatchError> {
+		let (schedules, locked_now) = match action {
+			VestingAction::Merge { index1: idx1, index2: idx2 } => {
+				// The schedule index is based off of the schedule ordering prior to filtering out
+				// any schedules that may be ending at this block.
+				let schedule1 = *schedules.get(idx1).ok_or(Error::<T>::ScheduleIndexOutOfBounds)?;
+				let schedule2 = *schedules.get(idx2).ok_or(Error::<T>::ScheduleIndexOutOfBounds)?;
+
+				// The length of `schedules` decreases by 2 here since we filter out 2 schedules.
+				// Thus we know below that we can push the new merged schedule without error
+				// (assuming initial state was valid).
+				let (mut schedules, mut locked_now) =
+					Self::report_schedule_updates(schedules.to_vec(), action);
+
+				let now = T::BlockNumberProvider::current_block_number();
+				if let Some(new_schedule) = Self::merge_vesting_info(now, schedule1, schedule2) {
+					// Merging created a new schedule so we:
+					// 1) need to add it to the accounts vesting schedule collection,
+					schedules.push(new_schedule);
+					// (we use `locked_at` in case this is a schedule that started in the past)
+					let new_schedule_locked =
+						new_schedule.locked_at::<T::BlockNumberToBalance>(now);
+					// and 2) update the locked amount to reflect the schedule we just added.
+					locked_now = locked_now.saturating_add(new_schedule_locked);
+				} // In the None case there was no new schedule to account for.
+
+				(schedules, locked_now)
+			},
+			_ => Self::report_schedule_updates(schedules.to_vec(), action),
+		};
+
+		debug_assert!(
+			locked_now > Zero::zero() && schedules.len() > 0 ||
+				locked_now == Zero::zero() && schedules.len() == 0
+		);
+
+		Ok((schedules, locked_now))
+	}
+}
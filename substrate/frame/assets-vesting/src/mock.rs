@@ -0,0 +1,158 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment for Assets Vesting pallet.
+
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{AsEnsureOriginWithArg, ConstU32, ConstU64},
+};
+use sp_runtime::{traits::Identity, BuildStorage};
+
+use super::*;
+use crate as pallet_assets_vesting;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Assets: pallet_assets,
+		AssetsFreezer: pallet_assets_freezer,
+		AssetsVesting: pallet_assets_vesting,
+	}
+);
+
+type AccountId = u64;
+type AssetId = u32;
+type Balance = u64;
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type AccountData = pallet_balances::AccountData<u64>;
+	type Block = Block;
+}
+
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+	type Balance = u64;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU64<1>;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = ();
+	type RuntimeFreezeReason = ();
+}
+
+#[derive_impl(pallet_assets::config_preludes::TestDefaultConfig)]
+impl pallet_assets::Config for Test {
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<u64>>;
+	type ForceOrigin = frame_system::EnsureRoot<u64>;
+	type Freezer = AssetsFreezer;
+}
+
+impl pallet_assets_freezer::Config for Test {
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type RuntimeEvent = RuntimeEvent;
+}
+
+parameter_types! {
+	pub const MinVestedTransfer: u64 = 256;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct AssetsVestingBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<AssetId> for AssetsVestingBenchmarkHelper {
+	fn create_asset() -> AssetId {
+		frame_support::assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, 0, true, 1));
+		1
+	}
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type AssetId = AssetId;
+	type Assets = Assets;
+	type Freezer = AssetsFreezer;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type BlockNumberToBalance = Identity;
+	type MinVestedTransfer = MinVestedTransfer;
+	type MaxVestingSchedules = ConstU32<3>;
+	type BlockNumberProvider = System;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = AssetsVestingBenchmarkHelper;
+}
+
+/// The asset used by the tests.
+pub const ASSET: AssetId = 0;
+
+pub struct ExtBuilder {
+	vesting_genesis_config: Option<Vec<(AssetId, AccountId, u64, u64, Balance)>>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self { vesting_genesis_config: None }
+	}
+}
+
+impl ExtBuilder {
+	pub fn vesting_genesis_config(
+		mut self,
+		config: Vec<(AssetId, AccountId, u64, u64, Balance)>,
+	) -> Self {
+		self.vesting_genesis_config = Some(config);
+		self
+	}
+
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 100), (2, 100)] }
+			.assimilate_storage(&mut t)
+			.unwrap();
+		pallet_assets::GenesisConfig::<Test> {
+			assets: vec![(ASSET, 1, true, 1)],
+			metadata: vec![],
+			accounts: vec![(ASSET, 1, 2560), (ASSET, 2, 5120), (ASSET, 3, 7680), (ASSET, 12, 2560)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let vesting = if let Some(vesting_config) = self.vesting_genesis_config {
+			vesting_config
+		} else {
+			vec![(ASSET, 1, 0, 10, 1280), (ASSET, 2, 10, 20, 0), (ASSET, 12, 10, 20, 1280)]
+		};
+
+		pallet_assets_vesting::GenesisConfig::<Test> { vesting }
+			.assimilate_storage(&mut t)
+			.unwrap();
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
@@ -0,0 +1,370 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for Assets Vesting pallet.
+
+use frame_support::{assert_noop, assert_ok, traits::fungibles::InspectFreeze};
+use sp_runtime::{traits::BadOrigin, TokenError};
+
+use super::{Vesting as VestingStorage, *};
+use crate::mock::{
+	Assets, AssetsFreezer, AssetsVesting, ExtBuilder, RuntimeEvent, RuntimeOrigin, System, Test,
+	ASSET,
+};
+
+fn frozen(asset: u32, who: u64) -> u64 {
+	AssetsFreezer::balance_frozen(asset, &FreezeReason::Vesting.into(), &who)
+}
+
+fn last_event() -> RuntimeEvent {
+	System::events().pop().expect("RuntimeEvent expected").event
+}
+
+#[test]
+fn check_vesting_status() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(Assets::balance(ASSET, 1), 2560);
+		assert_eq!(Assets::balance(ASSET, 2), 5120);
+		assert_eq!(Assets::balance(ASSET, 12), 2560);
+		assert_eq!(
+			AssetsVesting::vesting(ASSET, &1).unwrap(),
+			vec![VestingInfo::new(1280, 128, 0)]
+		);
+		assert_eq!(
+			AssetsVesting::vesting(ASSET, &2).unwrap(),
+			vec![VestingInfo::new(5120, 256, 10)]
+		);
+		assert_eq!(
+			AssetsVesting::vesting(ASSET, &12).unwrap(),
+			vec![VestingInfo::new(1280, 64, 10)]
+		);
+		assert_eq!(AssetsVesting::vesting(ASSET, &3), None);
+
+		// Account 1 has only 128 units vested from their illiquid 1280 units at block 1.
+		assert_eq!(AssetsVesting::vesting_balance(ASSET, &1), Some(1152));
+		// Account 2 has their full balance frozen.
+		assert_eq!(AssetsVesting::vesting_balance(ASSET, &2), Some(5120));
+		// Account 12 has only their illiquid funds frozen.
+		assert_eq!(AssetsVesting::vesting_balance(ASSET, &12), Some(1280));
+		assert_eq!(frozen(ASSET, 1), 1280);
+		assert_eq!(frozen(ASSET, 2), 5120);
+
+		System::set_block_number(10);
+		// Account 1 has fully vested by block 10.
+		assert_eq!(AssetsVesting::vesting_balance(ASSET, &1), Some(0));
+		// Account 2 has started vesting by block 10.
+		assert_eq!(AssetsVesting::vesting_balance(ASSET, &2), Some(5120));
+
+		System::set_block_number(30);
+		assert_eq!(AssetsVesting::vesting_balance(ASSET, &2), Some(0));
+
+		// Vesting removes the schedules and thaws the funds.
+		assert_ok!(AssetsVesting::vest(RuntimeOrigin::signed(2), ASSET));
+		assert_eq!(AssetsVesting::vesting(ASSET, &2), None);
+		assert_eq!(frozen(ASSET, 2), 0);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::AssetsVesting(Event::VestingCompleted { asset: ASSET, account: 2 })
+		);
+	});
+}
+
+#[test]
+fn unvested_balance_should_not_transfer() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Everything but the minimum balance of the asset is frozen.
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(2), ASSET, 3, 1),
+			pallet_assets::Error::<Test>::BalanceLow
+		);
+		// Account 1 has not realised the amount vested so far yet.
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), ASSET, 3, 1280),
+			pallet_assets::Error::<Test>::BalanceLow
+		);
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), ASSET, 3, 1279));
+	});
+}
+
+#[test]
+fn vested_balance_should_transfer() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(AssetsVesting::vest(RuntimeOrigin::signed(1), ASSET));
+		assert_eq!(frozen(ASSET, 1), 1152);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::AssetsVesting(Event::VestingUpdated {
+				asset: ASSET,
+				account: 1,
+				unvested: 1152
+			})
+		);
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), ASSET, 3, 1407));
+	});
+}
+
+#[test]
+fn vested_balance_should_transfer_using_vest_other() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(AssetsVesting::vest_other(RuntimeOrigin::signed(2), ASSET, 1));
+		assert_eq!(frozen(ASSET, 1), 1152);
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), ASSET, 3, 1407));
+
+		assert_noop!(
+			AssetsVesting::vest_other(RuntimeOrigin::signed(1), ASSET, 3),
+			Error::<Test>::NotVesting
+		);
+	});
+}
+
+#[test]
+fn extra_balance_should_transfer() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(3), ASSET, 2, 1000));
+
+		// Funds received on top of the vesting schedule can be transferred.
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(2), ASSET, 3, 999));
+		assert_eq!(AssetsVesting::vesting_balance(ASSET, &2), Some(5120));
+	});
+}
+
+#[test]
+fn vested_transfer_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let schedule = VestingInfo::new(1280, 64, 10);
+		assert_ok!(AssetsVesting::vested_transfer(RuntimeOrigin::signed(3), ASSET, 4, schedule));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::AssetsVesting(Event::VestingUpdated {
+				asset: ASSET,
+				account: 4,
+				unvested: 1280
+			})
+		);
+		assert_eq!(Assets::balance(ASSET, 3), 6400);
+		assert_eq!(Assets::balance(ASSET, 4), 1280);
+		assert_eq!(AssetsVesting::vesting(ASSET, &4).unwrap(), vec![schedule]);
+		assert_eq!(frozen(ASSET, 4), 1280);
+
+		System::set_block_number(20);
+		assert_eq!(AssetsVesting::vesting_balance(ASSET, &4), Some(640));
+
+		System::set_block_number(30);
+		assert_ok!(AssetsVesting::vest(RuntimeOrigin::signed(4), ASSET));
+		assert!(!VestingStorage::<Test>::contains_key(ASSET, 4));
+		assert_eq!(frozen(ASSET, 4), 0);
+	});
+}
+
+#[test]
+fn vested_transfer_correctly_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Fails due to too low transfer amount.
+		assert_noop!(
+			AssetsVesting::vested_transfer(
+				RuntimeOrigin::signed(3),
+				ASSET,
+				4,
+				VestingInfo::new(255, 64, 10)
+			),
+			Error::<Test>::AmountLow
+		);
+		// `per_block` is 0, which would result in a schedule with infinite duration.
+		assert_noop!(
+			AssetsVesting::vested_transfer(
+				RuntimeOrigin::signed(3),
+				ASSET,
+				4,
+				VestingInfo::new(1280, 0, 10)
+			),
+			Error::<Test>::InvalidScheduleParams
+		);
+		// Fails due to insufficient funds.
+		assert_noop!(
+			AssetsVesting::vested_transfer(
+				RuntimeOrigin::signed(3),
+				ASSET,
+				4,
+				VestingInfo::new(7681, 64, 10)
+			),
+			TokenError::FundsUnavailable
+		);
+
+		// Account 2 already has one schedule, so two more reach the maximum.
+		let schedule = VestingInfo::new(256, 64, 10);
+		assert_ok!(AssetsVesting::vested_transfer(RuntimeOrigin::signed(3), ASSET, 2, schedule));
+		assert_ok!(AssetsVesting::vested_transfer(RuntimeOrigin::signed(3), ASSET, 2, schedule));
+		assert_noop!(
+			AssetsVesting::vested_transfer(RuntimeOrigin::signed(3), ASSET, 2, schedule),
+			Error::<Test>::AtMaxVestingSchedules
+		);
+		assert_eq!(AssetsVesting::vesting(ASSET, &2).unwrap().len(), 3);
+		assert_eq!(frozen(ASSET, 2), 5632);
+	});
+}
+
+#[test]
+fn force_vested_transfer_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let schedule = VestingInfo::new(1280, 64, 10);
+		assert_noop!(
+			AssetsVesting::force_vested_transfer(RuntimeOrigin::signed(3), ASSET, 3, 4, schedule),
+			BadOrigin
+		);
+		assert_ok!(AssetsVesting::force_vested_transfer(
+			RuntimeOrigin::root(),
+			ASSET,
+			3,
+			4,
+			schedule
+		));
+		assert_eq!(Assets::balance(ASSET, 3), 6400);
+		assert_eq!(Assets::balance(ASSET, 4), 1280);
+		assert_eq!(AssetsVesting::vesting(ASSET, &4).unwrap(), vec![schedule]);
+		assert_eq!(frozen(ASSET, 4), 1280);
+	});
+}
+
+#[test]
+fn merge_ongoing_schedules() {
+	ExtBuilder::default().build().execute_with(|| {
+		let schedule = VestingInfo::new(1280, 64, 10);
+		assert_ok!(AssetsVesting::vested_transfer(RuntimeOrigin::signed(3), ASSET, 2, schedule));
+
+		assert_ok!(AssetsVesting::merge_schedules(RuntimeOrigin::signed(2), ASSET, 0, 1));
+
+		// Both schedules end at block 30 and neither started yet.
+		assert_eq!(
+			AssetsVesting::vesting(ASSET, &2).unwrap(),
+			vec![VestingInfo::new(6400, 320, 10)]
+		);
+		assert_eq!(frozen(ASSET, 2), 6400);
+
+		// Merging a schedule with itself is a no-op.
+		assert_ok!(AssetsVesting::merge_schedules(RuntimeOrigin::signed(2), ASSET, 0, 0));
+		assert_eq!(
+			AssetsVesting::vesting(ASSET, &2).unwrap(),
+			vec![VestingInfo::new(6400, 320, 10)]
+		);
+	});
+}
+
+#[test]
+fn merge_finished_schedule() {
+	ExtBuilder::default().build().execute_with(|| {
+		let schedule = VestingInfo::new(1280, 64, 10);
+		assert_ok!(AssetsVesting::vested_transfer(RuntimeOrigin::signed(3), ASSET, 1, schedule));
+
+		// The genesis schedule of account 1 has ended by block 15.
+		System::set_block_number(15);
+		assert_ok!(AssetsVesting::merge_schedules(RuntimeOrigin::signed(1), ASSET, 0, 1));
+
+		// The ongoing schedule is kept unmodified.
+		assert_eq!(AssetsVesting::vesting(ASSET, &1).unwrap(), vec![schedule]);
+		assert_eq!(frozen(ASSET, 1), 960);
+	});
+}
+
+#[test]
+fn merge_schedules_correctly_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AssetsVesting::merge_schedules(RuntimeOrigin::signed(3), ASSET, 0, 1),
+			Error::<Test>::NotVesting
+		);
+		assert_noop!(
+			AssetsVesting::merge_schedules(RuntimeOrigin::signed(1), ASSET, 0, 1),
+			Error::<Test>::ScheduleIndexOutOfBounds
+		);
+	});
+}
+
+#[test]
+fn force_remove_vesting_schedule_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AssetsVesting::force_remove_vesting_schedule(RuntimeOrigin::signed(2), ASSET, 2, 0),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetsVesting::force_remove_vesting_schedule(RuntimeOrigin::root(), ASSET, 2, 1),
+			Error::<Test>::InvalidScheduleParams
+		);
+		assert_noop!(
+			AssetsVesting::force_remove_vesting_schedule(RuntimeOrigin::root(), ASSET, 3, 0),
+			Error::<Test>::InvalidScheduleParams
+		);
+
+		assert_ok!(AssetsVesting::force_remove_vesting_schedule(
+			RuntimeOrigin::root(),
+			ASSET,
+			2,
+			0
+		));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::AssetsVesting(Event::VestingCompleted { asset: ASSET, account: 2 })
+		);
+		assert_eq!(AssetsVesting::vesting(ASSET, &2), None);
+		assert_eq!(frozen(ASSET, 2), 0);
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(2), ASSET, 3, 5120));
+	});
+}
+
+#[test]
+fn vesting_is_per_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 1, 3, 2560));
+
+		let schedule = VestingInfo::new(1280, 64, 10);
+		assert_ok!(AssetsVesting::vested_transfer(RuntimeOrigin::signed(3), 1, 1, schedule));
+
+		assert_eq!(AssetsVesting::vesting(1, &1).unwrap(), vec![schedule]);
+		assert_eq!(
+			AssetsVesting::vesting(ASSET, &1).unwrap(),
+			vec![VestingInfo::new(1280, 128, 0)]
+		);
+		assert_eq!(frozen(1, 1), 1280);
+		assert_eq!(frozen(ASSET, 1), 1280);
+
+		// Vesting one asset leaves the other one untouched.
+		System::set_block_number(10);
+		assert_ok!(AssetsVesting::vest(RuntimeOrigin::signed(1), ASSET));
+		assert_eq!(AssetsVesting::vesting(ASSET, &1), None);
+		assert_eq!(frozen(ASSET, 1), 0);
+		assert_eq!(frozen(1, 1), 1280);
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 1, 3, 1),
+			pallet_assets::Error::<Test>::BalanceLow
+		);
+	});
+}
+
+#[test]
+#[should_panic(expected = "AtMaxVestingSchedules")]
+fn genesis_rejects_too_many_schedules() {
+	ExtBuilder::default()
+		.vesting_genesis_config(vec![(ASSET, 1, 0, 10, 0); 4])
+		.build();
+}
+
+#[test]
+#[should_panic(expected = "FundsUnavailable")]
+fn genesis_requires_funds() {
+	ExtBuilder::default().vesting_genesis_config(vec![(ASSET, 4, 0, 10, 0)]).build();
+}
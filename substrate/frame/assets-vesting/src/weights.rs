@@ -0,0 +1,286 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Placeholder weights for `pallet_assets_vesting`.
+//!
+//! THESE WEIGHTS WERE NOT GENERATED BY THE BENCHMARK CLI. The values are estimates derived from
+//! the storage accesses of each call and comparable calls of other pallets.
+//!
+//! They must be replaced by the output of the `pallet_assets_vesting` benchmarks,
+//! e.g. generated with `frame-omni-bencher`, before the pallet is used on a production chain.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_assets_vesting`.
+pub trait WeightInfo {
+	fn vest_locked(s: u32, ) -> Weight;
+	fn vest_unlocked(s: u32, ) -> Weight;
+	fn vest_other_locked(s: u32, ) -> Weight;
+	fn vest_other_unlocked(s: u32, ) -> Weight;
+	fn vested_transfer(s: u32, ) -> Weight;
+	fn force_vested_transfer(s: u32, ) -> Weight;
+	fn not_unlocking_merge_schedules(s: u32, ) -> Weight;
+	fn unlocking_merge_schedules(s: u32, ) -> Weight;
+	fn force_remove_vesting_schedule(s: u32, ) -> Weight;
+}
+
+/// Weights for `pallet_assets_vesting` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `AssetsVesting::Vesting` (r:1 w:1)
+	/// Proof: `AssetsVesting::Vesting` (`max_values`: None, `max_size`: Some(1078), added: 3553, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Freezes` (r:1 w:1)
+	/// Proof: `Assets::Freezes` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 28]`.
+	fn vest_locked(s: u32, ) -> Weight {
+		Weight::from_parts(31_586_520, 4543)
+			// Standard Error: 2_693
+			.saturating_add(Weight::from_parts(69_725, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AssetsVesting::Vesting` (r:1 w:1)
+	/// Proof: `AssetsVesting::Vesting` (`max_values`: None, `max_size`: Some(1078), added: 3553, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Freezes` (r:1 w:1)
+	/// Proof: `Assets::Freezes` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 28]`.
+	fn vest_unlocked(s: u32, ) -> Weight {
+		Weight::from_parts(34_690_456, 4543)
+			// Standard Error: 2_693
+			.saturating_add(Weight::from_parts(55_094, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AssetsVesting::Vesting` (r:1 w:1)
+	/// Proof: `AssetsVesting::Vesting` (`max_values`: None, `max_size`: Some(1078), added: 3553, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Freezes` (r:1 w:1)
+	/// Proof: `Assets::Freezes` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 28]`.
+	fn vest_other_locked(s: u32, ) -> Weight {
+		Weight::from_parts(33_353_168, 4543)
+			// Standard Error: 2_693
+			.saturating_add(Weight::from_parts(64_115, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AssetsVesting::Vesting` (r:1 w:1)
+	/// Proof: `AssetsVesting::Vesting` (`max_values`: None, `max_size`: Some(1078), added: 3553, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Freezes` (r:1 w:1)
+	/// Proof: `Assets::Freezes` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 28]`.
+	fn vest_other_unlocked(s: u32, ) -> Weight {
+		Weight::from_parts(35_103_722, 4543)
+			// Standard Error: 2_693
+			.saturating_add(Weight::from_parts(50_812, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Assets::Asset` (r:1 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:2 w:2)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `AssetsVesting::Vesting` (r:1 w:1)
+	/// Proof: `AssetsVesting::Vesting` (`max_values`: None, `max_size`: Some(1078), added: 3553, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Freezes` (r:1 w:1)
+	/// Proof: `Assets::Freezes` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 27]`.
+	fn vested_transfer(s: u32, ) -> Weight {
+		Weight::from_parts(58_211_405, 4543)
+			// Standard Error: 2_693
+			.saturating_add(Weight::from_parts(81_377, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `Assets::Asset` (r:1 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:2 w:2)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `AssetsVesting::Vesting` (r:1 w:1)
+	/// Proof: `AssetsVesting::Vesting` (`max_values`: None, `max_size`: Some(1078), added: 3553, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Freezes` (r:1 w:1)
+	/// Proof: `Assets::Freezes` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 27]`.
+	fn force_vested_transfer(s: u32, ) -> Weight {
+		Weight::from_parts(59_870_214, 4543)
+			// Standard Error: 2_693
+			.saturating_add(Weight::from_parts(80_663, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `AssetsVesting::Vesting` (r:1 w:1)
+	/// Proof: `AssetsVesting::Vesting` (`max_values`: None, `max_size`: Some(1078), added: 3553, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Freezes` (r:1 w:1)
+	/// Proof: `Assets::Freezes` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 28]`.
+	fn not_unlocking_merge_schedules(s: u32, ) -> Weight {
+		Weight::from_parts(32_902_118, 4543)
+			// Standard Error: 2_693
+			.saturating_add(Weight::from_parts(77_140, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AssetsVesting::Vesting` (r:1 w:1)
+	/// Proof: `AssetsVesting::Vesting` (`max_values`: None, `max_size`: Some(1078), added: 3553, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Freezes` (r:1 w:1)
+	/// Proof: `Assets::Freezes` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 28]`.
+	fn unlocking_merge_schedules(s: u32, ) -> Weight {
+		Weight::from_parts(33_512_906, 4543)
+			// Standard Error: 2_693
+			.saturating_add(Weight::from_parts(74_512, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AssetsVesting::Vesting` (r:1 w:1)
+	/// Proof: `AssetsVesting::Vesting` (`max_values`: None, `max_size`: Some(1078), added: 3553, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Freezes` (r:1 w:1)
+	/// Proof: `Assets::Freezes` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 28]`.
+	fn force_remove_vesting_schedule(s: u32, ) -> Weight {
+		Weight::from_parts(35_627_390, 4543)
+			// Standard Error: 2_693
+			.saturating_add(Weight::from_parts(63_118, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `AssetsVesting::Vesting` (r:1 w:1)
+	/// Proof: `AssetsVesting::Vesting` (`max_values`: None, `max_size`: Some(1078), added: 3553, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Freezes` (r:1 w:1)
+	/// Proof: `Assets::Freezes` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 28]`.
+	fn vest_locked(s: u32, ) -> Weight {
+		Weight::from_parts(31_586_520, 4543)
+			// Standard Error: 2_693
+			.saturating_add(Weight::from_parts(69_725, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AssetsVesting::Vesting` (r:1 w:1)
+	/// Proof: `AssetsVesting::Vesting` (`max_values`: None, `max_size`: Some(1078), added: 3553, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Freezes` (r:1 w:1)
+	/// Proof: `Assets::Freezes` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 28]`.
+	fn vest_unlocked(s: u32, ) -> Weight {
+		Weight::from_parts(34_690_456, 4543)
+			// Standard Error: 2_693
+			.saturating_add(Weight::from_parts(55_094, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AssetsVesting::Vesting` (r:1 w:1)
+	/// Proof: `AssetsVesting::Vesting` (`max_values`: None, `max_size`: Some(1078), added: 3553, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Freezes` (r:1 w:1)
+	/// Proof: `Assets::Freezes` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 28]`.
+	fn vest_other_locked(s: u32, ) -> Weight {
+		Weight::from_parts(33_353_168, 4543)
+			// Standard Error: 2_693
+			.saturating_add(Weight::from_parts(64_115, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AssetsVesting::Vesting` (r:1 w:1)
+	/// Proof: `AssetsVesting::Vesting` (`max_values`: None, `max_size`: Some(1078), added: 3553, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Freezes` (r:1 w:1)
+	/// Proof: `Assets::Freezes` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 28]`.
+	fn vest_other_unlocked(s: u32, ) -> Weight {
+		Weight::from_parts(35_103_722, 4543)
+			// Standard Error: 2_693
+			.saturating_add(Weight::from_parts(50_812, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Assets::Asset` (r:1 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:2 w:2)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `AssetsVesting::Vesting` (r:1 w:1)
+	/// Proof: `AssetsVesting::Vesting` (`max_values`: None, `max_size`: Some(1078), added: 3553, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Freezes` (r:1 w:1)
+	/// Proof: `Assets::Freezes` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 27]`.
+	fn vested_transfer(s: u32, ) -> Weight {
+		Weight::from_parts(58_211_405, 4543)
+			// Standard Error: 2_693
+			.saturating_add(Weight::from_parts(81_377, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `Assets::Asset` (r:1 w:1)
+	/// Proof: `Assets::Asset` (`max_values`: None, `max_size`: Some(210), added: 2685, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Account` (r:2 w:2)
+	/// Proof: `Assets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `AssetsVesting::Vesting` (r:1 w:1)
+	/// Proof: `AssetsVesting::Vesting` (`max_values`: None, `max_size`: Some(1078), added: 3553, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Freezes` (r:1 w:1)
+	/// Proof: `Assets::Freezes` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 27]`.
+	fn force_vested_transfer(s: u32, ) -> Weight {
+		Weight::from_parts(59_870_214, 4543)
+			// Standard Error: 2_693
+			.saturating_add(Weight::from_parts(80_663, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `AssetsVesting::Vesting` (r:1 w:1)
+	/// Proof: `AssetsVesting::Vesting` (`max_values`: None, `max_size`: Some(1078), added: 3553, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Freezes` (r:1 w:1)
+	/// Proof: `Assets::Freezes` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 28]`.
+	fn not_unlocking_merge_schedules(s: u32, ) -> Weight {
+		Weight::from_parts(32_902_118, 4543)
+			// Standard Error: 2_693
+			.saturating_add(Weight::from_parts(77_140, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AssetsVesting::Vesting` (r:1 w:1)
+	/// Proof: `AssetsVesting::Vesting` (`max_values`: None, `max_size`: Some(1078), added: 3553, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Freezes` (r:1 w:1)
+	/// Proof: `Assets::Freezes` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 28]`.
+	fn unlocking_merge_schedules(s: u32, ) -> Weight {
+		Weight::from_parts(33_512_906, 4543)
+			// Standard Error: 2_693
+			.saturating_add(Weight::from_parts(74_512, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AssetsVesting::Vesting` (r:1 w:1)
+	/// Proof: `AssetsVesting::Vesting` (`max_values`: None, `max_size`: Some(1078), added: 3553, mode: `MaxEncodedLen`)
+	/// Storage: `Assets::Freezes` (r:1 w:1)
+	/// Proof: `Assets::Freezes` (`max_values`: None, `max_size`: Some(83), added: 2558, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 28]`.
+	fn force_remove_vesting_schedule(s: u32, ) -> Weight {
+		Weight::from_parts(35_627_390, 4543)
+			// Standard Error: 2_693
+			.saturating_add(Weight::from_parts(63_118, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
@@ -20,11 +20,14 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use super::*;
+use codec::Encode;
 use frame_benchmarking::v1::{
 	account, benchmarks_instance_pallet, whitelist_account, whitelisted_caller, BenchmarkError,
 };
 use frame_support::traits::{EnsureOrigin, Get, UnfilteredDispatchable};
 use frame_system::RawOrigin as SystemOrigin;
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use sp_confidential_assets::SecretKey;
 use sp_runtime::traits::Bounded;
 use sp_std::prelude::*;
 
@@ -130,6 +133,50 @@ fn add_approvals<T: Config<I>, I: 'static>(minter: T::AccountId, n: u32) {
 	}
 }
 
+fn rng() -> ChaCha20Rng {
+	ChaCha20Rng::from_seed([0; 32])
+}
+
+fn enable_confidential<T: Config<I>, I: 'static>(owner: &T::AccountId) {
+	assert!(Assets::<T, I>::enable_confidential(
+		SystemOrigin::Signed(owner.clone()).into(),
+		default_asset_id::<T, I>(),
+	)
+	.is_ok());
+}
+
+/// Create the confidential account of `who` and make `amount` of its public balance available in
+/// it.
+fn create_confidential_account<T: Config<I>, I: 'static>(
+	who: &T::AccountId,
+	amount: u32,
+) -> SecretKey {
+	let asset_id = default_asset_id::<T, I>();
+	let id: T::AssetId = asset_id.clone().into();
+	let origin = SystemOrigin::Signed(who.clone());
+	let secret = SecretKey::from_seed(&who.encode());
+	let proof = PubkeyValidityProof::new(&secret, &(&id, who).encode(), &mut rng());
+	T::Currency::make_free_balance_be(who, DepositBalanceOf::<T, I>::max_value());
+	assert!(Assets::<T, I>::configure_confidential_account(
+		origin.clone().into(),
+		asset_id.clone(),
+		secret.public(),
+		proof,
+	)
+	.is_ok());
+	if amount > 0 {
+		assert!(Assets::<T, I>::deposit_confidential(
+			origin.clone().into(),
+			asset_id.clone(),
+			amount.into()
+		)
+		.is_ok());
+		let pending = ConfidentialAccounts::<T, I>::get(&id, who).unwrap().pending;
+		assert!(Assets::<T, I>::apply_pending_balance(origin.into(), asset_id, pending).is_ok());
+	}
+	secret
+}
+
 fn assert_last_event<T: Config<I>, I: 'static>(generic_event: <T as Config<I>>::RuntimeEvent) {
 	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
 }
@@ -553,5 +600,116 @@ benchmarks_instance_pallet! {
 		assert_last_event::<T, I>(Event::Blocked { asset_id: asset_id.into(), who: caller }.into());
 	}
 
+	enable_confidential {
+		let (asset_id, caller, caller_lookup) = create_default_asset::<T, I>(true);
+	}: _(SystemOrigin::Signed(caller.clone()), asset_id.clone())
+	verify {
+		assert_last_event::<T, I>(Event::ConfidentialEnabled { asset_id: asset_id.into() }.into());
+	}
+
+	configure_confidential_account {
+		let (asset_id, caller, caller_lookup) = create_default_minted_asset::<T, I>(true, 100u32.into());
+		enable_confidential::<T, I>(&caller);
+		let id: T::AssetId = asset_id.clone().into();
+		let secret = SecretKey::from_seed(b"caller");
+		let proof = PubkeyValidityProof::new(&secret, &(&id, &caller).encode(), &mut rng());
+		T::Currency::make_free_balance_be(&caller, DepositBalanceOf::<T, I>::max_value());
+	}: _(SystemOrigin::Signed(caller.clone()), asset_id, secret.public(), proof)
+	verify {
+		assert_last_event::<T, I>(Event::ConfidentialAccountConfigured { asset_id: id, who: caller }.into());
+	}
+
+	deposit_confidential {
+		let (asset_id, caller, caller_lookup) = create_default_minted_asset::<T, I>(true, 200u32.into());
+		enable_confidential::<T, I>(&caller);
+		create_confidential_account::<T, I>(&caller, 0);
+	}: _(SystemOrigin::Signed(caller.clone()), asset_id.clone(), 100u32.into())
+	verify {
+		assert_last_event::<T, I>(Event::ConfidentialDeposited { asset_id: asset_id.into(), who: caller, amount: 100u32.into() }.into());
+	}
+
+	apply_pending_balance {
+		let (asset_id, caller, caller_lookup) = create_default_minted_asset::<T, I>(true, 200u32.into());
+		enable_confidential::<T, I>(&caller);
+		create_confidential_account::<T, I>(&caller, 100);
+		Assets::<T, I>::deposit_confidential(SystemOrigin::Signed(caller.clone()).into(), asset_id.clone(), 50u32.into())?;
+		let id: T::AssetId = asset_id.clone().into();
+		let pending = ConfidentialAccounts::<T, I>::get(&id, &caller).unwrap().pending;
+	}: _(SystemOrigin::Signed(caller.clone()), asset_id, pending)
+	verify {
+		assert_last_event::<T, I>(Event::PendingBalanceApplied { asset_id: id, who: caller }.into());
+	}
+
+	confidential_transfer {
+		let (asset_id, caller, caller_lookup) = create_default_minted_asset::<T, I>(true, 200u32.into());
+		let target: T::AccountId = account("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+		Assets::<T, I>::mint(SystemOrigin::Signed(caller.clone()).into(), asset_id.clone(), target_lookup.clone(), 100u32.into())?;
+		enable_confidential::<T, I>(&caller);
+		let secret = create_confidential_account::<T, I>(&caller, 100);
+		create_confidential_account::<T, I>(&target, 0);
+		let id: T::AssetId = asset_id.clone().into();
+		let available = ConfidentialAccounts::<T, I>::get(&id, &caller).unwrap().available;
+		let receiver = ConfidentialAccounts::<T, I>::get(&id, &target).unwrap().public_key;
+		let context = (&id, &caller, &target).encode();
+		let (amount, proof) = TransferProof::new(&secret, &receiver, &available, 100, 40, &context, &mut rng())
+			.map_err(|_| BenchmarkError::Stop("failed to create the transfer proof"))?;
+	}: _(SystemOrigin::Signed(caller.clone()), asset_id, target_lookup, amount, Box::new(proof))
+	verify {
+		assert_last_event::<T, I>(Event::ConfidentialTransferred { asset_id: id, from: caller, to: target }.into());
+	}
+
+	withdraw_confidential {
+		let (asset_id, caller, caller_lookup) = create_default_minted_asset::<T, I>(true, 200u32.into());
+		enable_confidential::<T, I>(&caller);
+		let secret = create_confidential_account::<T, I>(&caller, 100);
+		let id: T::AssetId = asset_id.clone().into();
+		let available = ConfidentialAccounts::<T, I>::get(&id, &caller).unwrap().available;
+		let context = (&id, &caller).encode();
+		let proof = WithdrawProof::new(&secret, &available, 100, 100, &context, &mut rng())
+			.map_err(|_| BenchmarkError::Stop("failed to create the withdraw proof"))?;
+	}: _(SystemOrigin::Signed(caller.clone()), asset_id, 100u32.into(), Box::new(proof))
+	verify {
+		assert_last_event::<T, I>(Event::ConfidentialWithdrawn { asset_id: id, who: caller, amount: 100u32.into() }.into());
+	}
+
+	set_compliance_rules {
+		let (asset_id, caller, _) = create_default_asset::<T, I>(true);
+		let rules = ComplianceRules {
+			list_mode: ListMode::AllowList,
+			max_holding: Some(100u32.into()),
+			cooldown: 10u32.into(),
+			require_jurisdiction: true,
+		};
+	}: _(SystemOrigin::Signed(caller.clone()), asset_id.clone(), Some(rules.clone()))
+	verify {
+		assert_last_event::<T, I>(Event::ComplianceRulesSet { asset_id: asset_id.into(), rules: Some(rules) }.into());
+	}
+
+	set_listed {
+		let (asset_id, caller, _) = create_default_asset::<T, I>(true);
+		let target: T::AccountId = account("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+	}: _(SystemOrigin::Signed(caller.clone()), asset_id.clone(), target_lookup, true)
+	verify {
+		assert_last_event::<T, I>(Event::ListUpdated { asset_id: asset_id.into(), who: target, listed: true }.into());
+	}
+
+	set_jurisdiction {
+		let (asset_id, caller, _) = create_default_asset::<T, I>(true);
+		let target: T::AccountId = account("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+	}: _(SystemOrigin::Signed(caller.clone()), asset_id.clone(), target_lookup, Some(*b"CH"))
+	verify {
+		assert_last_event::<T, I>(Event::JurisdictionSet { asset_id: asset_id.into(), who: target, jurisdiction: Some(*b"CH") }.into());
+	}
+
+	restrict_jurisdiction {
+		let (asset_id, caller, _) = create_default_asset::<T, I>(true);
+	}: _(SystemOrigin::Signed(caller.clone()), asset_id.clone(), *b"CH", true)
+	verify {
+		assert_last_event::<T, I>(Event::JurisdictionRestricted { asset_id: asset_id.into(), jurisdiction: *b"CH", restricted: true }.into());
+	}
+
 	impl_benchmark_test_suite!(Assets, crate::mock::new_test_ext(), crate::mock::Test)
 }
@@ -0,0 +1,327 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compliance rules.
+//!
+//! The admin of an asset may restrict who can hold and move it with
+//! [`Pallet::set_compliance_rules`]. The rules of an asset apply to transfers, including approved
+//! ones, and to minting. Transfers forced by the admin are exempt.
+//!
+//! Balances changed through the `fungibles` traits are subject to the rules as well. A credit is
+//! treated like minting and a debit like the sending half of a transfer, unless it is forced.
+//! Confidential balances are covered too: only permitted accounts may deposit into and withdraw
+//! from them, and confidential transfers are checked like public ones. Since their amounts are
+//! hidden, a holding cap cannot be enforced on them and confidential transfers of an asset with
+//! one are rejected.
+//!
+//! Rules are evaluated against attributes the admin maintains per account: whether it is listed,
+//! see [`Pallet::set_listed`], and its jurisdiction, see [`Pallet::set_jurisdiction`]. Depending on
+//! the [`ListMode`] of the asset, listed accounts are the only ones allowed to take part in
+//! transfers or the only ones excluded from them. Accounts in a jurisdiction restricted with
+//! [`Pallet::restrict_jurisdiction`] may neither send nor receive the asset.
+//!
+//! Independently of the rules, [`Config::TransferHook`] is consulted on every transfer, mint and
+//! credit and may veto it.
+
+use super::*;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_system::pallet_prelude::BlockNumberFor;
+
+/// A jurisdiction, identified by its ISO 3166-1 alpha-2 country code.
+pub type Jurisdiction = [u8; 2];
+
+/// The compliance rules of an asset with the balance and block number types of `T`.
+pub(super) type ComplianceRulesOf<T, I> =
+	ComplianceRules<<T as Config<I>>::Balance, BlockNumberFor<T>>;
+
+/// How the listed accounts of an asset are treated.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, Debug, MaxEncodedLen, TypeInfo)]
+pub enum ListMode {
+	/// Whether an account is listed is irrelevant.
+	Open,
+	/// Only listed accounts may send and receive the asset.
+	AllowList,
+	/// Listed accounts may neither send nor receive the asset.
+	DenyList,
+}
+
+/// Restrictions on the transfers of an asset.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, MaxEncodedLen, TypeInfo)]
+pub struct ComplianceRules<Balance, BlockNumber> {
+	/// How listed accounts are treated.
+	pub list_mode: ListMode,
+	/// The largest balance an account may reach through transfers and minting.
+	pub max_holding: Option<Balance>,
+	/// The number of blocks an account has to wait between two outgoing transfers.
+	pub cooldown: BlockNumber,
+	/// Whether accounts without a jurisdiction are excluded from transfers.
+	pub require_jurisdiction: bool,
+}
+
+/// The compliance attributes of an account.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, Debug, MaxEncodedLen, TypeInfo)]
+pub struct ComplianceAccount<BlockNumber> {
+	/// Whether the account is on the list of the asset.
+	pub listed: bool,
+	/// The jurisdiction of the account, if known.
+	pub jurisdiction: Option<Jurisdiction>,
+	/// The block of the last outgoing transfer which was subject to a cooldown.
+	pub last_transfer: Option<BlockNumber>,
+}
+
+impl<BlockNumber> ComplianceAccount<BlockNumber> {
+	fn is_empty(&self) -> bool {
+		!self.listed && self.jurisdiction.is_none() && self.last_transfer.is_none()
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// The compliance rules of the asset `id`, if any.
+	pub fn compliance_rules(id: T::AssetId) -> Option<ComplianceRulesOf<T, I>> {
+		Compliance::<T, I>::get(id)
+	}
+
+	/// The compliance attributes of `who` for the asset `id`.
+	pub fn compliance_account(
+		id: T::AssetId,
+		who: impl sp_std::borrow::Borrow<T::AccountId>,
+	) -> ComplianceAccount<BlockNumberFor<T>> {
+		ComplianceAccounts::<T, I>::get(id, who.borrow()).unwrap_or_default()
+	}
+
+	/// Set or, if `None`, remove the compliance rules of the asset `id`, administered by `admin`.
+	pub(super) fn do_set_compliance_rules(
+		id: T::AssetId,
+		admin: T::AccountId,
+		rules: Option<ComplianceRulesOf<T, I>>,
+	) -> DispatchResult {
+		Self::ensure_compliance_admin(&id, &admin)?;
+		Compliance::<T, I>::set(&id, rules.clone());
+		Self::deposit_event(Event::ComplianceRulesSet { asset_id: id, rules });
+		Ok(())
+	}
+
+	/// Add `who` to or remove it from the list of the asset `id`, administered by `admin`.
+	pub(super) fn do_set_listed(
+		id: T::AssetId,
+		admin: T::AccountId,
+		who: T::AccountId,
+		listed: bool,
+	) -> DispatchResult {
+		Self::ensure_compliance_admin(&id, &admin)?;
+		Self::mutate_compliance_account(&id, &who, |account| account.listed = listed);
+		Self::deposit_event(Event::ListUpdated { asset_id: id, who, listed });
+		Ok(())
+	}
+
+	/// Set or clear the jurisdiction of `who` for the asset `id`, administered by `admin`.
+	pub(super) fn do_set_jurisdiction(
+		id: T::AssetId,
+		admin: T::AccountId,
+		who: T::AccountId,
+		jurisdiction: Option<Jurisdiction>,
+	) -> DispatchResult {
+		Self::ensure_compliance_admin(&id, &admin)?;
+		Self::mutate_compliance_account(&id, &who, |account| account.jurisdiction = jurisdiction);
+		Self::deposit_event(Event::JurisdictionSet { asset_id: id, who, jurisdiction });
+		Ok(())
+	}
+
+	/// Exclude accounts in `jurisdiction` from transfers of the asset `id`, administered by
+	/// `admin`, or lift that restriction.
+	pub(super) fn do_restrict_jurisdiction(
+		id: T::AssetId,
+		admin: T::AccountId,
+		jurisdiction: Jurisdiction,
+		restricted: bool,
+	) -> DispatchResult {
+		Self::ensure_compliance_admin(&id, &admin)?;
+		if restricted {
+			RestrictedJurisdictions::<T, I>::insert(&id, jurisdiction, ());
+		} else {
+			RestrictedJurisdictions::<T, I>::remove(&id, jurisdiction);
+		}
+		Self::deposit_event(Event::JurisdictionRestricted {
+			asset_id: id,
+			jurisdiction,
+			restricted,
+		});
+		Ok(())
+	}
+
+	/// Ensure that `amount` of the asset `id` may be credited to `dest`, either from `source` or,
+	/// if `None`, from outside the public balances, i.e. by minting, through the `fungibles` traits
+	/// or from a confidential balance.
+	///
+	/// Forced transfers are not subject to the compliance rules, but still to
+	/// [`Config::TransferHook`].
+	pub(super) fn ensure_compliant(
+		id: &T::AssetId,
+		source: Option<&T::AccountId>,
+		dest: &T::AccountId,
+		amount: T::Balance,
+		forced: bool,
+	) -> DispatchResult {
+		if let Some(rules) = Compliance::<T, I>::get(id).filter(|_| !forced) {
+			Self::ensure_permitted(id, &rules, dest)?;
+			if let Some(source) = source {
+				Self::ensure_may_send(id, &rules, source)?;
+			}
+			if let Some(max_holding) = rules.max_holding {
+				let balance = Account::<T, I>::get(id, dest).map_or(Zero::zero(), |a| a.balance);
+				ensure!(
+					balance.saturating_add(amount) <= max_holding,
+					Error::<T, I>::HoldingCapExceeded
+				);
+			}
+		}
+		T::TransferHook::pre_transfer(id, source, dest, amount)
+	}
+
+	/// Record that `amount` of the asset `id` was credited to `dest`, either from `source` or, if
+	/// `None`, from outside the public balances.
+	pub(super) fn note_compliant(
+		id: &T::AssetId,
+		source: Option<&T::AccountId>,
+		dest: &T::AccountId,
+		amount: T::Balance,
+		forced: bool,
+	) {
+		if let (Some(source), false) = (source, forced) {
+			Self::note_sent(id, source);
+		}
+		T::TransferHook::post_transfer(id, source, dest, amount);
+	}
+
+	/// Ensure that `source` may send the asset `id` out of its public balance without a known
+	/// recipient, as when debited through the `fungibles` traits.
+	pub(super) fn ensure_compliant_debit(
+		id: &T::AssetId,
+		source: &T::AccountId,
+		forced: bool,
+	) -> DispatchResult {
+		match Compliance::<T, I>::get(id).filter(|_| !forced) {
+			Some(rules) => Self::ensure_may_send(id, &rules, source),
+			None => Ok(()),
+		}
+	}
+
+	/// Record that `source` sent the asset `id` out of its public balance.
+	pub(super) fn note_compliant_debit(id: &T::AssetId, source: &T::AccountId, forced: bool) {
+		if !forced {
+			Self::note_sent(id, source);
+		}
+	}
+
+	/// Ensure that `who` may move the asset `id` between its public and confidential balances.
+	pub(super) fn ensure_compliant_holder(id: &T::AssetId, who: &T::AccountId) -> DispatchResult {
+		match Compliance::<T, I>::get(id) {
+			Some(rules) => Self::ensure_permitted(id, &rules, who),
+			None => Ok(()),
+		}
+	}
+
+	/// Ensure that `source` may transfer the asset `id` confidentially to `dest`.
+	pub(super) fn ensure_compliant_confidential(
+		id: &T::AssetId,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+	) -> DispatchResult {
+		if let Some(rules) = Compliance::<T, I>::get(id) {
+			ensure!(rules.max_holding.is_none(), Error::<T, I>::HoldingCapExceeded);
+			Self::ensure_permitted(id, &rules, dest)?;
+			Self::ensure_may_send(id, &rules, source)?;
+		}
+		T::TransferHook::pre_confidential_transfer(id, source, dest)
+	}
+
+	/// Record that `source` transferred the asset `id` confidentially to `dest`.
+	pub(super) fn note_compliant_confidential(
+		id: &T::AssetId,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+	) {
+		Self::note_sent(id, source);
+		T::TransferHook::post_confidential_transfer(id, source, dest);
+	}
+
+	fn ensure_may_send(
+		id: &T::AssetId,
+		rules: &ComplianceRulesOf<T, I>,
+		source: &T::AccountId,
+	) -> DispatchResult {
+		Self::ensure_permitted(id, rules, source)?;
+		if let Some(last_transfer) = Self::compliance_account(id.clone(), source).last_transfer {
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				now >= last_transfer.saturating_add(rules.cooldown),
+				Error::<T, I>::CooldownActive
+			);
+		}
+		Ok(())
+	}
+
+	fn note_sent(id: &T::AssetId, source: &T::AccountId) {
+		let cooldown = Compliance::<T, I>::get(id).map(|rules| rules.cooldown);
+		if cooldown.map_or(false, |cooldown| !cooldown.is_zero()) {
+			let now = frame_system::Pallet::<T>::block_number();
+			Self::mutate_compliance_account(id, source, |account| {
+				account.last_transfer = Some(now)
+			});
+		}
+	}
+
+	fn ensure_permitted(
+		id: &T::AssetId,
+		rules: &ComplianceRulesOf<T, I>,
+		who: &T::AccountId,
+	) -> DispatchResult {
+		let account = Self::compliance_account(id.clone(), who);
+		match rules.list_mode {
+			ListMode::Open => {},
+			ListMode::AllowList => ensure!(account.listed, Error::<T, I>::NotPermitted),
+			ListMode::DenyList => ensure!(!account.listed, Error::<T, I>::NotPermitted),
+		}
+		match account.jurisdiction {
+			Some(jurisdiction) => ensure!(
+				!RestrictedJurisdictions::<T, I>::contains_key(id, jurisdiction),
+				Error::<T, I>::JurisdictionRestricted
+			),
+			None => ensure!(!rules.require_jurisdiction, Error::<T, I>::JurisdictionRestricted),
+		}
+		Ok(())
+	}
+
+	fn ensure_compliance_admin(id: &T::AssetId, who: &T::AccountId) -> DispatchResult {
+		let details = Asset::<T, I>::get(id).ok_or(Error::<T, I>::Unknown)?;
+		ensure!(details.status == AssetStatus::Live, Error::<T, I>::AssetNotLive);
+		ensure!(who == &details.admin, Error::<T, I>::NoPermission);
+		Ok(())
+	}
+
+	fn mutate_compliance_account(
+		id: &T::AssetId,
+		who: &T::AccountId,
+		f: impl FnOnce(&mut ComplianceAccount<BlockNumberFor<T>>),
+	) {
+		ComplianceAccounts::<T, I>::mutate_exists(id, who, |maybe_account| {
+			let mut account = maybe_account.take().unwrap_or_default();
+			f(&mut account);
+			*maybe_account = Some(account).filter(|account| !account.is_empty());
+		})
+	}
+}
@@ -0,0 +1,309 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Confidential balances.
+//!
+//! The owner of an asset opts it in with [`Pallet::enable_confidential`]. Holders then register an
+//! encryption key with [`Pallet::configure_confidential_account`] and move funds from their public
+//! balance into their confidential one with [`Pallet::deposit_confidential`]. Confidential
+//! balances are encrypted with the scheme of [`sp_confidential_assets`]. Transfers between them
+//! only reveal the accounts involved, never the amount.
+//!
+//! A confidential account has an available and a pending balance. Deposits and incoming transfers
+//! are credited to the pending balance so that they do not invalidate proofs which the holder
+//! creates against its available balance at the same time. [`Pallet::apply_pending_balance`]
+//! moves the pending balance into the available one.
+//!
+//! Funds in confidential balances remain part of the supply of the asset. Their total is tracked
+//! by [`ConfidentialAssetDetails::supply`] since deposits and withdrawals are public, and it may
+//! not exceed `u64::MAX`, the largest amount the range proofs cover.
+//!
+//! Confidential accounts are not tied to the public account of their holder, which may die while
+//! funds remain in the confidential balance. Their storage is paid for by a deposit of
+//! `AssetAccountDeposit`, which is returned when the asset is destroyed.
+
+use super::*;
+use codec::{Decode, Encode, MaxEncodedLen};
+use sp_confidential_assets::{
+	Ciphertext, PubkeyValidityProof, PublicKey, TransferAmount, TransferProof, WithdrawProof,
+};
+
+/// The confidential balances of an asset.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, Debug, MaxEncodedLen, TypeInfo)]
+pub struct ConfidentialAssetDetails<Balance> {
+	/// The total amount held in confidential balances. This is part of the supply of the asset.
+	pub supply: Balance,
+	/// The number of confidential accounts.
+	pub accounts: u32,
+}
+
+/// The confidential balance of an account.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, MaxEncodedLen, TypeInfo)]
+pub struct ConfidentialAccount<DepositBalance> {
+	/// The key the balances are encrypted under.
+	pub public_key: PublicKey,
+	/// The balance which can be transferred and withdrawn.
+	pub available: Ciphertext,
+	/// Deposits and incoming transfers which were not applied yet.
+	pub pending: Ciphertext,
+	/// The deposit reserved from the holder for the storage of the account.
+	pub deposit: DepositBalance,
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// The total amount of the asset `id` held in confidential balances, if the asset supports
+	/// them.
+	pub fn confidential_supply(id: T::AssetId) -> Option<T::Balance> {
+		ConfidentialAssets::<T, I>::get(id).map(|details| details.supply)
+	}
+
+	/// The confidential balance of `who` in the asset `id`.
+	pub fn confidential_account(
+		id: T::AssetId,
+		who: impl sp_std::borrow::Borrow<T::AccountId>,
+	) -> Option<ConfidentialAccount<DepositBalanceOf<T, I>>> {
+		ConfidentialAccounts::<T, I>::get(id, who.borrow())
+	}
+
+	/// Enable confidential balances for the asset `id`, owned by `owner`.
+	pub(super) fn do_enable_confidential(id: T::AssetId, owner: T::AccountId) -> DispatchResult {
+		let details = Asset::<T, I>::get(&id).ok_or(Error::<T, I>::Unknown)?;
+		ensure!(details.status == AssetStatus::Live, Error::<T, I>::AssetNotLive);
+		ensure!(owner == details.owner, Error::<T, I>::NoPermission);
+		ensure!(!ConfidentialAssets::<T, I>::contains_key(&id), Error::<T, I>::AlreadyExists);
+
+		ConfidentialAssets::<T, I>::insert(&id, ConfidentialAssetDetails::default());
+		Self::deposit_event(Event::ConfidentialEnabled { asset_id: id });
+		Ok(())
+	}
+
+	/// Create the confidential account of `who` with the key `public_key`.
+	///
+	/// `who` needs to hold the asset publicly. `AssetAccountDeposit` is reserved from it for the
+	/// storage of its confidential account.
+	pub(super) fn do_configure_confidential_account(
+		id: T::AssetId,
+		who: T::AccountId,
+		public_key: PublicKey,
+		proof: PubkeyValidityProof,
+	) -> DispatchResult {
+		let mut details =
+			ConfidentialAssets::<T, I>::get(&id).ok_or(Error::<T, I>::NotConfidential)?;
+		ensure!(Account::<T, I>::contains_key(&id, &who), Error::<T, I>::NoAccount);
+		ensure!(
+			!ConfidentialAccounts::<T, I>::contains_key(&id, &who),
+			Error::<T, I>::AlreadyExists
+		);
+		proof
+			.verify(&public_key, &(&id, &who).encode())
+			.map_err(|_| Error::<T, I>::InvalidProof)?;
+
+		let deposit = T::AssetAccountDeposit::get();
+		T::Currency::reserve(&who, deposit)?;
+		details.accounts = details.accounts.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+		let account = ConfidentialAccount {
+			public_key,
+			available: Ciphertext::default(),
+			pending: Ciphertext::default(),
+			deposit,
+		};
+		ConfidentialAccounts::<T, I>::insert(&id, &who, account);
+		ConfidentialAssets::<T, I>::insert(&id, details);
+		Self::deposit_event(Event::ConfidentialAccountConfigured { asset_id: id, who });
+		Ok(())
+	}
+
+	/// Move `amount` from the public balance of `who` into its pending confidential balance.
+	///
+	/// The public balance of `who` is kept alive.
+	pub(super) fn do_deposit_confidential(
+		id: T::AssetId,
+		who: T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		let mut details =
+			ConfidentialAssets::<T, I>::get(&id).ok_or(Error::<T, I>::NotConfidential)?;
+		details.supply = details.supply.checked_add(&amount).ok_or(ArithmeticError::Overflow)?;
+		// The amount fits as well since it is at most the new supply.
+		Self::confidential_amount(details.supply)?;
+		let value = Self::confidential_amount(amount)?;
+
+		Self::ensure_compliant_holder(&id, &who)?;
+		let f = DebitFlags { keep_alive: true, best_effort: false };
+		Self::decrease_balance(id.clone(), &who, amount, f, |_, _| Ok(()))?;
+		Self::mutate_confidential_account(&id, &who, |account| {
+			account.pending = account.pending.add_amount(value)?;
+			Ok(())
+		})?;
+		ConfidentialAssets::<T, I>::insert(&id, details);
+
+		Self::deposit_event(Event::ConfidentialDeposited { asset_id: id, who, amount });
+		Ok(())
+	}
+
+	/// Move the pending confidential balance of `who` into its available one.
+	///
+	/// Fails if the pending balance is not `expected`, i.e. if `who` is not aware of all of it.
+	pub(super) fn do_apply_pending_balance(
+		id: T::AssetId,
+		who: T::AccountId,
+		expected: Ciphertext,
+	) -> DispatchResult {
+		let account = ConfidentialAccounts::<T, I>::get(&id, &who)
+			.ok_or(Error::<T, I>::NoConfidentialAccount)?;
+		ensure!(account.pending == expected, Error::<T, I>::PendingBalanceChanged);
+
+		Self::mutate_confidential_account(&id, &who, |account| {
+			account.available = account.available.add_ciphertext(&account.pending)?;
+			account.pending = Ciphertext::default();
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::PendingBalanceApplied { asset_id: id, who });
+		Ok(())
+	}
+
+	/// Transfer the encrypted `amount` from the available confidential balance of `source` to the
+	/// pending confidential balance of `dest`.
+	pub(super) fn do_confidential_transfer(
+		id: T::AssetId,
+		source: T::AccountId,
+		dest: T::AccountId,
+		amount: TransferAmount,
+		proof: &TransferProof,
+	) -> DispatchResult {
+		ensure!(ConfidentialAssets::<T, I>::contains_key(&id), Error::<T, I>::NotConfidential);
+		let details = Asset::<T, I>::get(&id).ok_or(Error::<T, I>::Unknown)?;
+		ensure!(details.status == AssetStatus::Live, Error::<T, I>::AssetNotLive);
+		if let Some(account) = Account::<T, I>::get(&id, &source) {
+			ensure!(!account.status.is_frozen(), TokenError::Frozen);
+		}
+		if let Some(account) = Account::<T, I>::get(&id, &dest) {
+			ensure!(!account.status.is_blocked(), TokenError::Blocked);
+		}
+		Self::ensure_compliant_confidential(&id, &source, &dest)?;
+
+		let sender = ConfidentialAccounts::<T, I>::get(&id, &source)
+			.ok_or(Error::<T, I>::NoConfidentialAccount)?;
+		let receiver = ConfidentialAccounts::<T, I>::get(&id, &dest)
+			.ok_or(Error::<T, I>::NoConfidentialAccount)?;
+		proof
+			.verify(
+				&sender.public_key,
+				&receiver.public_key,
+				&sender.available,
+				&amount,
+				&(&id, &source, &dest).encode(),
+			)
+			.map_err(|_| Error::<T, I>::InvalidProof)?;
+
+		Self::mutate_confidential_account(&id, &source, |account| {
+			account.available = account.available.sub_ciphertext(&amount.sender_ciphertext())?;
+			Ok(())
+		})?;
+		Self::mutate_confidential_account(&id, &dest, |account| {
+			account.pending = account.pending.add_ciphertext(&amount.receiver_ciphertext())?;
+			Ok(())
+		})?;
+		Self::note_compliant_confidential(&id, &source, &dest);
+
+		Self::deposit_event(Event::ConfidentialTransferred {
+			asset_id: id,
+			from: source,
+			to: dest,
+		});
+		Ok(())
+	}
+
+	/// Move `amount` from the available confidential balance of `who` into its public balance.
+	pub(super) fn do_withdraw_confidential(
+		id: T::AssetId,
+		who: T::AccountId,
+		amount: T::Balance,
+		proof: &WithdrawProof,
+	) -> DispatchResult {
+		let mut details =
+			ConfidentialAssets::<T, I>::get(&id).ok_or(Error::<T, I>::NotConfidential)?;
+		let asset = Asset::<T, I>::get(&id).ok_or(Error::<T, I>::Unknown)?;
+		ensure!(asset.status == AssetStatus::Live, Error::<T, I>::AssetNotLive);
+		if let Some(account) = Account::<T, I>::get(&id, &who) {
+			ensure!(!account.status.is_frozen(), TokenError::Frozen);
+		}
+		Self::ensure_compliant(&id, None, &who, amount, false)?;
+		// The proof shows that `who` holds at least `amount`, which is part of the supply.
+		details.supply = details.supply.checked_sub(&amount).ok_or(ArithmeticError::Underflow)?;
+		let value = Self::confidential_amount(amount)?;
+		let account = ConfidentialAccounts::<T, I>::get(&id, &who)
+			.ok_or(Error::<T, I>::NoConfidentialAccount)?;
+		proof
+			.verify(&account.public_key, &account.available, value, &(&id, &who).encode())
+			.map_err(|_| Error::<T, I>::InvalidProof)?;
+
+		Self::mutate_confidential_account(&id, &who, |account| {
+			account.available = account.available.sub_amount(value)?;
+			Ok(())
+		})?;
+		Self::increase_balance(id.clone(), &who, amount, |_| Ok(()))?;
+		ConfidentialAssets::<T, I>::insert(&id, details);
+		Self::note_compliant(&id, None, &who, amount, false);
+
+		Self::deposit_event(Event::ConfidentialWithdrawn { asset_id: id, who, amount });
+		Ok(())
+	}
+
+	/// Remove up to `max_items` confidential accounts of the asset `id` and release their
+	/// deposits.
+	///
+	/// Returns the number of removed accounts.
+	pub(super) fn destroy_confidential_accounts(id: &T::AssetId, max_items: u32) -> u32 {
+		if max_items == 0 {
+			return 0
+		}
+		let mut removed = 0;
+		for (who, account) in ConfidentialAccounts::<T, I>::drain_prefix(id) {
+			T::Currency::unreserve(&who, account.deposit);
+			removed.saturating_inc();
+			if removed >= max_items {
+				break
+			}
+		}
+		ConfidentialAssets::<T, I>::mutate(id, |maybe_details| {
+			if let Some(details) = maybe_details {
+				details.accounts.saturating_reduce(removed);
+			}
+		});
+		removed
+	}
+
+	fn confidential_amount(amount: T::Balance) -> Result<u64, DispatchError> {
+		amount.try_into().map_err(|_| ArithmeticError::Overflow.into())
+	}
+
+	fn mutate_confidential_account(
+		id: &T::AssetId,
+		who: &T::AccountId,
+		f: impl FnOnce(
+			&mut ConfidentialAccount<DepositBalanceOf<T, I>>,
+		) -> Result<(), sp_confidential_assets::Error>,
+	) -> DispatchResult {
+		ConfidentialAccounts::<T, I>::try_mutate(id, who, |maybe_account| {
+			let account = maybe_account.as_mut().ok_or(Error::<T, I>::NoConfidentialAccount)?;
+			// Stored ciphertexts are always valid, hence this can only be caused by the caller.
+			f(account).map_err(|_| Error::<T, I>::InvalidProof.into())
+		})
+	}
+}
@@ -415,6 +415,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		amount: T::Balance,
 		maybe_check_issuer: Option<T::AccountId>,
 	) -> DispatchResult {
+		Self::ensure_compliant(&id, None, beneficiary, amount, false)?;
 		Self::increase_balance(id.clone(), beneficiary, amount, |details| -> DispatchResult {
 			if let Some(check_issuer) = maybe_check_issuer {
 				ensure!(check_issuer == details.issuer, Error::<T, I>::NoPermission);
@@ -425,6 +426,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 			Ok(())
 		})?;
+		Self::note_compliant(&id, None, beneficiary, amount, false);
 
 		Self::deposit_event(Event::Issued { asset_id: id, owner: beneficiary.clone(), amount });
 
@@ -618,6 +620,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		let debit = Self::prep_debit(id.clone(), source, amount, f.into())?;
 		let (credit, maybe_burn) = Self::prep_credit(id.clone(), dest, amount, debit, f.burn_dust)?;
 
+		// Transfers forced by the admin are exempt from the compliance rules.
+		let forced = maybe_need_admin.is_some();
+		if source != dest {
+			Self::ensure_compliant(&id, Some(source), dest, credit, forced)?;
+		}
+
 		let mut source_account =
 			Account::<T, I>::get(&id, &source).ok_or(Error::<T, I>::NoAccount)?;
 		let mut source_died: Option<DeadConsequence> = None;
@@ -684,6 +692,9 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			Ok(())
 		})?;
 
+		if source != dest {
+			Self::note_compliant(&id, Some(source), dest, credit, forced);
+		}
 		Self::deposit_event(Event::Transferred {
 			asset_id: id,
 			from: source.clone(),
@@ -774,6 +785,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 					}
 					if let Remove = Self::dead_account(&who, &mut details, &v.reason, false) {
 						Account::<T, I>::remove(&id, &who);
+						ComplianceAccounts::<T, I>::remove(&id, &who);
 						dead_accounts.push(who);
 					} else {
 						// deposit may have been released, need to update `Account`
@@ -788,6 +800,25 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				Ok(())
 			})?;
 
+		// Confidential balances and compliance attributes may outlive the public balances of
+		// their holders.
+		let mut budget = max_items.saturating_sub(dead_accounts.len() as u32);
+		let mut removed_items = 0;
+		if remaining_accounts == 0 {
+			removed_items = Self::destroy_confidential_accounts(&id, budget);
+			budget.saturating_reduce(removed_items);
+			// Unlike `clear_prefix` without a cursor, draining does not revisit the entries removed
+			// by earlier calls in the same block.
+			let removed =
+				ComplianceAccounts::<T, I>::drain_prefix(&id).take(budget as usize).count() as u32;
+			budget.saturating_reduce(removed);
+			removed_items.saturating_accrue(removed);
+			let removed = RestrictedJurisdictions::<T, I>::drain_prefix(&id)
+				.take(budget as usize)
+				.count() as u32;
+			removed_items.saturating_accrue(removed);
+		}
+
 		for who in &dead_accounts {
 			T::Freezer::died(id.clone(), &who);
 		}
@@ -797,7 +828,9 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			accounts_destroyed: dead_accounts.len() as u32,
 			accounts_remaining: remaining_accounts as u32,
 		});
-		Ok(dead_accounts.len() as u32)
+		// Confidential accounts and compliance state are removed like accounts and count towards
+		// the weight.
+		Ok((dead_accounts.len() as u32).saturating_add(removed_items))
 	}
 
 	/// Destroy approvals associated with a given asset up to the max (T::RemoveItemsLimit).
@@ -845,9 +878,17 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			ensure!(details.status == AssetStatus::Destroying, Error::<T, I>::IncorrectStatus);
 			ensure!(details.accounts == 0, Error::<T, I>::InUse);
 			ensure!(details.approvals == 0, Error::<T, I>::InUse);
+			ensure!(
+				ConfidentialAssets::<T, I>::get(&id).map_or(true, |c| c.accounts == 0),
+				Error::<T, I>::InUse
+			);
+			ensure!(!ComplianceAccounts::<T, I>::contains_prefix(&id), Error::<T, I>::InUse);
+			ensure!(!RestrictedJurisdictions::<T, I>::contains_prefix(&id), Error::<T, I>::InUse);
 			ensure!(T::CallbackHandle::destroyed(&id).is_ok(), Error::<T, I>::CallbackFailed);
 
 			let metadata = Metadata::<T, I>::take(&id);
+			ConfidentialAssets::<T, I>::remove(&id);
+			Compliance::<T, I>::remove(&id);
 			T::Currency::unreserve(
 				&details.owner,
 				details.deposit.saturating_add(metadata.deposit),
@@ -20,7 +20,7 @@
 use frame_support::{
 	defensive,
 	traits::tokens::{
-		Fortitude,
+		Fortitude::{self, Force},
 		Precision::{self, BestEffort},
 		Preservation::{self, Expendable},
 		Provenance::{self, Minted},
@@ -98,18 +98,21 @@ impl<T: Config<I>, I: 'static> fungibles::Mutate<<T as SystemConfig>::AccountId>
 		Self::deposit_event(Event::Burned { asset_id, owner: target.clone(), balance });
 	}
 
-	fn done_transfer(
-		asset_id: Self::AssetId,
+	// The default would credit `dest` on a best effort basis after debiting `source`, burning the
+	// amount if the compliance rules reject the credit.
+	fn transfer(
+		asset: Self::AssetId,
 		source: &<T as SystemConfig>::AccountId,
 		dest: &<T as SystemConfig>::AccountId,
 		amount: Self::Balance,
-	) {
-		Self::deposit_event(Event::Transferred {
-			asset_id,
-			from: source.clone(),
-			to: dest.clone(),
-			amount,
-		});
+		preservation: Preservation,
+	) -> Result<Self::Balance, DispatchError> {
+		let f = TransferFlags {
+			keep_alive: preservation != Expendable,
+			best_effort: false,
+			burn_dust: false,
+		};
+		Self::do_transfer(asset, source, dest, amount, None, f)
 	}
 }
 
@@ -146,13 +149,17 @@ impl<T: Config<I>, I: 'static> fungibles::Unbalanced<T::AccountId> for Pallet<T,
 		amount: Self::Balance,
 		precision: Precision,
 		preservation: Preservation,
-		_: Fortitude,
+		fortitude: Fortitude,
 	) -> Result<Self::Balance, DispatchError> {
+		let forced = fortitude == Force;
+		Self::ensure_compliant_debit(&asset, who, forced)?;
 		let f = DebitFlags {
 			keep_alive: preservation != Expendable,
 			best_effort: precision == BestEffort,
 		};
-		Self::decrease_balance(asset, who, amount, f, |_, _| Ok(()))
+		let actual = Self::decrease_balance(asset.clone(), who, amount, f, |_, _| Ok(()))?;
+		Self::note_compliant_debit(&asset, who, forced);
+		Ok(actual)
 	}
 	fn increase_balance(
 		asset: T::AssetId,
@@ -160,7 +167,9 @@ impl<T: Config<I>, I: 'static> fungibles::Unbalanced<T::AccountId> for Pallet<T,
 		amount: Self::Balance,
 		_: Precision,
 	) -> Result<Self::Balance, DispatchError> {
-		Self::increase_balance(asset, who, amount, |_| Ok(()))?;
+		Self::ensure_compliant(&asset, None, who, amount, false)?;
+		Self::increase_balance(asset.clone(), who, amount, |_| Ok(()))?;
+		Self::note_compliant(&asset, None, who, amount, false);
 		Ok(amount)
 	}
 
@@ -66,6 +66,10 @@
 //!   from the approving account into some third-party destination account.
 //! * **Sufficiency**: The idea of a minimum-balance of an asset being sufficient to allow the
 //!   account's existence on the system without requiring any other existential-deposit.
+//! * **Confidential balance**: A balance whose amount is only known to its holder. Transfers
+//!   between confidential balances carry zero-knowledge proofs instead of amounts.
+//! * **Compliance rules**: Restrictions on who may hold and transfer an asset, based on allow or
+//!   deny lists, holding caps, transfer cooldowns and the jurisdictions of accounts.
 //!
 //! ### Goals
 //!
@@ -94,6 +98,12 @@
 //! * `refund`: Return the deposit (if any) of the caller's asset account or a consumer reference
 //!   (if any) of the caller's account.
 //! * `refund_other`: Return the deposit (if any) of a specified asset account.
+//! * `configure_confidential_account`: Register the encryption key of the caller's confidential
+//!   balance.
+//! * `deposit_confidential`: Move assets from the caller's public into its confidential balance.
+//! * `apply_pending_balance`: Make the caller's incoming confidential funds available.
+//! * `confidential_transfer`: Transfer an encrypted amount between confidential balances.
+//! * `withdraw_confidential`: Move assets from the caller's confidential into its public balance.
 //!
 //! ### Permissioned Functions
 //!
@@ -120,6 +130,16 @@
 //!   called by the asset class's Freezer or Admin.
 //! * `block`: Disallows further `transfer`s to and from an account; called by the asset class's
 //!   Freezer.
+//! * `enable_confidential`: Allows holders to keep confidential balances of an asset class; called
+//!   by the asset class's Owner.
+//! * `set_compliance_rules`: Sets or removes the compliance rules of an asset class; called by the
+//!   asset class's Admin.
+//! * `set_listed`: Adds an account to or removes it from the list of an asset class; called by the
+//!   asset class's Admin.
+//! * `set_jurisdiction`: Sets or clears the jurisdiction of an account; called by the asset class's
+//!   Admin.
+//! * `restrict_jurisdiction`: Excludes accounts in a jurisdiction from transfers of an asset class
+//!   or lifts that restriction; called by the asset class's Admin.
 //!
 //! Please refer to the [`Call`] enum and its associated variants for documentation on each
 //! function.
@@ -137,6 +157,9 @@
 //! Using `CallbackHandle` associated type, user can configure custom callback functions which are
 //! executed when new asset is created or an existing asset is destroyed.
 //!
+//! Using `TransferHook` associated type, user can veto or observe transfers and mints of assets,
+//! including confidential transfers.
+//!
 //! ## Related Modules
 //!
 //! * [`System`](../frame_system/index.html)
@@ -159,6 +182,11 @@ pub mod mock;
 mod tests;
 pub mod weights;
 
+mod compliance;
+use compliance::ComplianceRulesOf;
+pub use compliance::{ComplianceAccount, ComplianceRules, Jurisdiction, ListMode};
+mod confidential;
+pub use confidential::{ConfidentialAccount, ConfidentialAssetDetails};
 mod extra_mutator;
 pub use extra_mutator::*;
 mod functions;
@@ -186,6 +214,9 @@ use frame_support::{
 	},
 };
 use frame_system::Config as SystemConfig;
+use sp_confidential_assets::{
+	Ciphertext, PubkeyValidityProof, PublicKey, TransferAmount, TransferProof, WithdrawProof,
+};
 
 pub use pallet::*;
 pub use weights::WeightInfo;
@@ -209,6 +240,49 @@ pub trait AssetsCallback<AssetId, AccountId> {
 /// Empty implementation in case no callbacks are required.
 impl<AssetId, AccountId> AssetsCallback<AssetId, AccountId> for () {}
 
+/// Trait with hooks that are executed around transfers and mints of assets.
+pub trait TransferHook<AssetId, AccountId, Balance> {
+	/// Called before `amount` of the asset `id` is credited to `dest`, either from `source` or,
+	/// if `None`, by minting. Returning an error vetoes the operation.
+	fn pre_transfer(
+		_id: &AssetId,
+		_source: Option<&AccountId>,
+		_dest: &AccountId,
+		_amount: Balance,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	/// Called after `amount` of the asset `id` was credited to `dest`, either from `source` or,
+	/// if `None`, by minting.
+	fn post_transfer(
+		_id: &AssetId,
+		_source: Option<&AccountId>,
+		_dest: &AccountId,
+		_amount: Balance,
+	) {
+	}
+
+	/// Called before a confidential transfer of the asset `id` from `source` to `dest`, whose
+	/// amount is hidden. Returning an error vetoes it.
+	///
+	/// Withdrawals from confidential balances are reported to [`Self::pre_transfer`] as credits
+	/// without a `source`.
+	fn pre_confidential_transfer(
+		_id: &AssetId,
+		_source: &AccountId,
+		_dest: &AccountId,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	/// Called after a confidential transfer of the asset `id` from `source` to `dest`.
+	fn post_confidential_transfer(_id: &AssetId, _source: &AccountId, _dest: &AccountId) {}
+}
+
+/// Empty implementation in case no hooks are required.
+impl<AssetId, AccountId, Balance> TransferHook<AssetId, AccountId, Balance> for () {}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -261,6 +335,7 @@ pub mod pallet {
 			type StringLimit = ConstU32<50>;
 			type Extra = ();
 			type CallbackHandle = ();
+			type TransferHook = ();
 			type WeightInfo = ();
 			#[cfg(feature = "runtime-benchmarks")]
 			type BenchmarkHelper = ();
@@ -363,6 +438,9 @@ pub mod pallet {
 		/// Callback methods for asset state change (e.g. asset created or destroyed)
 		type CallbackHandle: AssetsCallback<Self::AssetId, Self::AccountId>;
 
+		/// Hooks consulted on transfers and mints, which may veto them.
+		type TransferHook: TransferHook<Self::AssetId, Self::AccountId, Self::Balance>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 
@@ -415,6 +493,45 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	#[pallet::storage]
+	/// Assets which support confidential balances, with the total amount held in them.
+	pub(super) type ConfidentialAssets<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, ConfidentialAssetDetails<T::Balance>>;
+
+	#[pallet::storage]
+	/// The confidential balances of accounts.
+	/// First key is the asset ID, second key is the holder.
+	pub(super) type ConfidentialAccounts<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		ConfidentialAccount<DepositBalanceOf<T, I>>,
+	>;
+
+	#[pallet::storage]
+	/// The compliance rules of assets.
+	pub(super) type Compliance<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, ComplianceRulesOf<T, I>>;
+
+	#[pallet::storage]
+	/// The compliance attributes of accounts.
+	/// First key is the asset ID, second key is the account.
+	pub(super) type ComplianceAccounts<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AssetId,
+		Blake2_128Concat,
+		T::AccountId,
+		ComplianceAccount<BlockNumberFor<T>>,
+	>;
+
+	#[pallet::storage]
+	/// Jurisdictions whose accounts may not take part in transfers of an asset.
+	pub(super) type RestrictedJurisdictions<T: Config<I>, I: 'static = ()> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AssetId, Twox64Concat, Jurisdiction, ()>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
@@ -571,6 +688,34 @@ pub mod pallet {
 		Touched { asset_id: T::AssetId, who: T::AccountId, depositor: T::AccountId },
 		/// Some account `who` was blocked.
 		Blocked { asset_id: T::AssetId, who: T::AccountId },
+		/// Confidential balances were enabled for an asset.
+		ConfidentialEnabled { asset_id: T::AssetId },
+		/// Some account `who` registered the key of its confidential balance.
+		ConfidentialAccountConfigured { asset_id: T::AssetId, who: T::AccountId },
+		/// Some `amount` was moved from the public into the confidential balance of `who`.
+		ConfidentialDeposited { asset_id: T::AssetId, who: T::AccountId, amount: T::Balance },
+		/// The pending confidential balance of `who` was made available.
+		PendingBalanceApplied { asset_id: T::AssetId, who: T::AccountId },
+		/// Some undisclosed amount was transferred between confidential balances.
+		ConfidentialTransferred { asset_id: T::AssetId, from: T::AccountId, to: T::AccountId },
+		/// Some `amount` was moved from the confidential into the public balance of `who`.
+		ConfidentialWithdrawn { asset_id: T::AssetId, who: T::AccountId, amount: T::Balance },
+		/// The compliance rules of an asset were set or, if `None`, removed.
+		ComplianceRulesSet { asset_id: T::AssetId, rules: Option<ComplianceRulesOf<T, I>> },
+		/// Some account `who` was added to or removed from the list of an asset.
+		ListUpdated { asset_id: T::AssetId, who: T::AccountId, listed: bool },
+		/// The jurisdiction of some account `who` was set or cleared.
+		JurisdictionSet {
+			asset_id: T::AssetId,
+			who: T::AccountId,
+			jurisdiction: Option<Jurisdiction>,
+		},
+		/// Accounts in a jurisdiction were excluded from or readmitted to transfers of an asset.
+		JurisdictionRestricted {
+			asset_id: T::AssetId,
+			jurisdiction: Jurisdiction,
+			restricted: bool,
+		},
 	}
 
 	#[pallet::error]
@@ -618,6 +763,22 @@ pub mod pallet {
 		NotFrozen,
 		/// Callback action resulted in error
 		CallbackFailed,
+		/// The asset does not support confidential balances.
+		NotConfidential,
+		/// The account has no confidential balance of the asset.
+		NoConfidentialAccount,
+		/// A zero-knowledge proof or an encrypted amount is invalid.
+		InvalidProof,
+		/// The pending confidential balance is not the expected one.
+		PendingBalanceChanged,
+		/// The list of the asset does not permit the account to take part in the transfer.
+		NotPermitted,
+		/// The jurisdiction of the account is restricted or unknown.
+		JurisdictionRestricted,
+		/// The transfer would exceed the holding cap of the asset.
+		HoldingCapExceeded,
+		/// The account made a transfer too recently.
+		CooldownActive,
 	}
 
 	#[pallet::call(weight(<T as Config<I>>::WeightInfo))]
@@ -1683,6 +1844,250 @@ pub mod pallet {
 			Self::deposit_event(Event::<T, I>::Blocked { asset_id: id, who });
 			Ok(())
 		}
+
+		/// Allow holders of an asset to keep confidential balances of it.
+		///
+		/// Origin must be Signed and the sender should be the Owner of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset.
+		///
+		/// Emits `ConfidentialEnabled`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::call_index(32)]
+		pub fn enable_confidential(
+			origin: OriginFor<T>,
+			id: T::AssetIdParameter,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let id: T::AssetId = id.into();
+			Self::do_enable_confidential(id, origin)
+		}
+
+		/// Register the key under which the confidential balance of the sender is encrypted.
+		///
+		/// Origin must be Signed and the sender must hold the asset `id`. The key cannot be changed
+		/// afterwards.
+		///
+		/// Funds of the sender will be reserved according to `T::AssetAccountDeposit`. They are
+		/// returned when the asset is destroyed.
+		///
+		/// - `id`: The identifier of the asset which must support confidential balances.
+		/// - `public_key`: The encryption key.
+		/// - `proof`: Proof that the sender knows the secret key of `public_key`, bound to the
+		///   context `(id, sender)`.
+		///
+		/// Emits `ConfidentialAccountConfigured`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::call_index(33)]
+		pub fn configure_confidential_account(
+			origin: OriginFor<T>,
+			id: T::AssetIdParameter,
+			public_key: PublicKey,
+			proof: PubkeyValidityProof,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let id: T::AssetId = id.into();
+			Self::do_configure_confidential_account(id, who, public_key, proof)
+		}
+
+		/// Move assets from the public balance of the sender into its pending confidential
+		/// balance.
+		///
+		/// Origin must be Signed and the sender must have a confidential balance of the asset
+		/// `id`. Its public balance is kept alive.
+		///
+		/// - `id`: The identifier of the asset.
+		/// - `amount`: The amount to move. This is public.
+		///
+		/// Emits `ConfidentialDeposited`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::call_index(34)]
+		pub fn deposit_confidential(
+			origin: OriginFor<T>,
+			id: T::AssetIdParameter,
+			#[pallet::compact] amount: T::Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let id: T::AssetId = id.into();
+			Self::do_deposit_confidential(id, who, amount)
+		}
+
+		/// Make the pending confidential balance of the sender available for transfers and
+		/// withdrawals.
+		///
+		/// Origin must be Signed and the sender must have a confidential balance of the asset
+		/// `id`.
+		///
+		/// - `id`: The identifier of the asset.
+		/// - `expected`: The pending balance the sender is aware of. The call fails if further
+		///   funds arrived in the meantime.
+		///
+		/// Emits `PendingBalanceApplied`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::call_index(35)]
+		pub fn apply_pending_balance(
+			origin: OriginFor<T>,
+			id: T::AssetIdParameter,
+			expected: Ciphertext,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let id: T::AssetId = id.into();
+			Self::do_apply_pending_balance(id, who, expected)
+		}
+
+		/// Transfer an encrypted amount from the available confidential balance of the sender to
+		/// the pending confidential balance of `dest`.
+		///
+		/// Origin must be Signed and both the sender and `dest` must have a confidential balance
+		/// of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset.
+		/// - `dest`: The account to be credited.
+		/// - `amount`: The amount encrypted under the keys of the sender and `dest`.
+		/// - `proof`: Proof that `amount` is well formed and that the sender's available balance
+		///   covers it, bound to the context `(id, sender, dest)`.
+		///
+		/// Emits `ConfidentialTransferred`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::call_index(36)]
+		pub fn confidential_transfer(
+			origin: OriginFor<T>,
+			id: T::AssetIdParameter,
+			dest: AccountIdLookupOf<T>,
+			amount: TransferAmount,
+			proof: Box<TransferProof>,
+		) -> DispatchResult {
+			let source = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+			let id: T::AssetId = id.into();
+			Self::do_confidential_transfer(id, source, dest, amount, &proof)
+		}
+
+		/// Move assets from the available confidential balance of the sender into its public
+		/// balance.
+		///
+		/// Origin must be Signed and the sender must have a confidential balance of the asset
+		/// `id`.
+		///
+		/// - `id`: The identifier of the asset.
+		/// - `amount`: The amount to move. This is public.
+		/// - `proof`: Proof that the sender's available balance covers `amount`, bound to the
+		///   context `(id, sender)`.
+		///
+		/// Emits `ConfidentialWithdrawn`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::call_index(37)]
+		pub fn withdraw_confidential(
+			origin: OriginFor<T>,
+			id: T::AssetIdParameter,
+			#[pallet::compact] amount: T::Balance,
+			proof: Box<WithdrawProof>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let id: T::AssetId = id.into();
+			Self::do_withdraw_confidential(id, who, amount, &proof)
+		}
+
+		/// Set or remove the compliance rules of an asset.
+		///
+		/// Origin must be Signed and the sender should be the Admin of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset.
+		/// - `rules`: The new rules, or `None` to lift all restrictions.
+		///
+		/// Emits `ComplianceRulesSet`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::call_index(38)]
+		pub fn set_compliance_rules(
+			origin: OriginFor<T>,
+			id: T::AssetIdParameter,
+			rules: Option<ComplianceRulesOf<T, I>>,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let id: T::AssetId = id.into();
+			Self::do_set_compliance_rules(id, origin, rules)
+		}
+
+		/// Add an account to or remove it from the list of an asset.
+		///
+		/// Origin must be Signed and the sender should be the Admin of the asset `id`. Whether
+		/// listed accounts are allowed or denied depends on the compliance rules of the asset.
+		///
+		/// - `id`: The identifier of the asset.
+		/// - `who`: The account to be listed or unlisted.
+		/// - `listed`: Whether `who` should be on the list.
+		///
+		/// Emits `ListUpdated`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::call_index(39)]
+		pub fn set_listed(
+			origin: OriginFor<T>,
+			id: T::AssetIdParameter,
+			who: AccountIdLookupOf<T>,
+			listed: bool,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			let id: T::AssetId = id.into();
+			Self::do_set_listed(id, origin, who, listed)
+		}
+
+		/// Set or clear the jurisdiction of an account for an asset.
+		///
+		/// Origin must be Signed and the sender should be the Admin of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset.
+		/// - `who`: The account whose jurisdiction is set.
+		/// - `jurisdiction`: The ISO 3166-1 alpha-2 code of the jurisdiction, or `None` to clear
+		///   it.
+		///
+		/// Emits `JurisdictionSet`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::call_index(40)]
+		pub fn set_jurisdiction(
+			origin: OriginFor<T>,
+			id: T::AssetIdParameter,
+			who: AccountIdLookupOf<T>,
+			jurisdiction: Option<Jurisdiction>,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			let id: T::AssetId = id.into();
+			Self::do_set_jurisdiction(id, origin, who, jurisdiction)
+		}
+
+		/// Exclude accounts in a jurisdiction from transfers of an asset, or lift that
+		/// restriction.
+		///
+		/// Origin must be Signed and the sender should be the Admin of the asset `id`.
+		///
+		/// - `id`: The identifier of the asset.
+		/// - `jurisdiction`: The ISO 3166-1 alpha-2 code of the jurisdiction.
+		/// - `restricted`: Whether accounts in `jurisdiction` should be excluded.
+		///
+		/// Emits `JurisdictionRestricted`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::call_index(41)]
+		pub fn restrict_jurisdiction(
+			origin: OriginFor<T>,
+			id: T::AssetIdParameter,
+			jurisdiction: Jurisdiction,
+			restricted: bool,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let id: T::AssetId = id.into();
+			Self::do_restrict_jurisdiction(id, origin, jurisdiction, restricted)
+		}
 	}
 
 	/// Implements [`AccountTouch`] trait.
@@ -115,6 +115,7 @@ impl Config for Test {
 	type ForceOrigin = frame_system::EnsureRoot<u64>;
 	type Freezer = TestFreezer;
 	type CallbackHandle = AssetsCallbackHandle;
+	type TransferHook = TestTransferHook;
 }
 
 use std::collections::HashMap;
@@ -126,6 +127,8 @@ pub enum Hook {
 parameter_types! {
 	static Frozen: HashMap<(u32, u64), u64> = Default::default();
 	static Hooks: Vec<Hook> = Default::default();
+	static Vetoed: Vec<u64> = Default::default();
+	static Transfers: Vec<(u32, Option<u64>, u64, u64)> = Default::default();
 }
 
 pub struct TestFreezer;
@@ -142,6 +145,36 @@ impl FrozenBalance<u32, u64, u64> for TestFreezer {
 	}
 }
 
+pub struct TestTransferHook;
+impl TransferHook<u32, u64, u64> for TestTransferHook {
+	fn pre_transfer(
+		_asset: &u32,
+		_source: Option<&u64>,
+		dest: &u64,
+		_amount: u64,
+	) -> DispatchResult {
+		ensure!(!Vetoed::get().contains(dest), DispatchError::Other("vetoed"));
+		Ok(())
+	}
+
+	fn post_transfer(asset: &u32, source: Option<&u64>, dest: &u64, amount: u64) {
+		Transfers::mutate(|v| v.push((*asset, source.copied(), *dest, amount)));
+	}
+
+	fn pre_confidential_transfer(_asset: &u32, _source: &u64, dest: &u64) -> DispatchResult {
+		ensure!(!Vetoed::get().contains(dest), DispatchError::Other("vetoed"));
+		Ok(())
+	}
+}
+
+pub(crate) fn set_vetoed(who: u64) {
+	Vetoed::mutate(|v| v.push(who));
+}
+
+pub(crate) fn take_transfers() -> Vec<(u32, Option<u64>, u64, u64)> {
+	Transfers::take()
+}
+
 pub(crate) fn set_frozen_balance(asset: u32, who: u64, amount: u64) {
 	Frozen::mutate(|v| {
 		v.insert((asset, who), amount);
@@ -185,6 +218,8 @@ pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
 	let mut ext: sp_io::TestExternalities = storage.into();
 	// Clear thread local vars for https://github.com/paritytech/substrate/issues/10479.
 	ext.execute_with(|| take_hooks());
+	ext.execute_with(|| take_transfers());
+	ext.execute_with(|| Vetoed::take());
 	ext.execute_with(|| System::set_block_number(1));
 	ext
 }
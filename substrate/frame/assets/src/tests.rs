@@ -28,6 +28,8 @@ use pallet_balances::Error as BalancesError;
 use sp_io::storage;
 use sp_runtime::{traits::ConvertInto, TokenError};
 
+mod compliance;
+mod confidential;
 mod sets;
 
 fn asset_ids() -> Vec<u32> {
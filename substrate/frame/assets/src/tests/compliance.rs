@@ -0,0 +1,358 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for compliance rules and transfer hooks.
+
+use super::*;
+use frame_support::traits::{
+	fungibles::Mutate,
+	tokens::{
+		Fortitude::{Force, Polite},
+		Precision::Exact,
+		Preservation::Expendable,
+	},
+};
+
+fn rules(list_mode: ListMode) -> ComplianceRules<u64, u64> {
+	ComplianceRules { list_mode, max_holding: None, cooldown: 0, require_jurisdiction: false }
+}
+
+/// Create the asset `0`, administered by `1`, and give `1` `100` of it.
+fn setup() {
+	assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 1));
+	assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+}
+
+fn set_rules(rules: ComplianceRules<u64, u64>) {
+	assert_ok!(Assets::set_compliance_rules(RuntimeOrigin::signed(1), 0, Some(rules)));
+}
+
+fn list(who: u64) {
+	assert_ok!(Assets::set_listed(RuntimeOrigin::signed(1), 0, who, true));
+}
+
+#[test]
+fn compliance_calls_require_admin() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_noop!(
+			Assets::set_compliance_rules(RuntimeOrigin::signed(2), 0, Some(rules(ListMode::Open))),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Assets::set_listed(RuntimeOrigin::signed(2), 0, 2, true),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Assets::set_jurisdiction(RuntimeOrigin::signed(2), 0, 2, Some(*b"CH")),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Assets::restrict_jurisdiction(RuntimeOrigin::signed(2), 0, *b"CH", true),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Assets::set_listed(RuntimeOrigin::signed(1), 1, 2, true),
+			Error::<Test>::Unknown
+		);
+
+		set_rules(rules(ListMode::AllowList));
+		System::assert_last_event(RuntimeEvent::Assets(crate::Event::ComplianceRulesSet {
+			asset_id: 0,
+			rules: Some(rules(ListMode::AllowList)),
+		}));
+		assert_eq!(Assets::compliance_rules(0), Some(rules(ListMode::AllowList)));
+
+		list(2);
+		System::assert_last_event(RuntimeEvent::Assets(crate::Event::ListUpdated {
+			asset_id: 0,
+			who: 2,
+			listed: true,
+		}));
+		assert_ok!(Assets::set_jurisdiction(RuntimeOrigin::signed(1), 0, 2, Some(*b"CH")));
+		System::assert_last_event(RuntimeEvent::Assets(crate::Event::JurisdictionSet {
+			asset_id: 0,
+			who: 2,
+			jurisdiction: Some(*b"CH"),
+		}));
+		assert_eq!(
+			Assets::compliance_account(0, 2),
+			ComplianceAccount { listed: true, jurisdiction: Some(*b"CH"), last_transfer: None }
+		);
+
+		// Accounts without attributes are not stored.
+		assert_ok!(Assets::set_listed(RuntimeOrigin::signed(1), 0, 2, false));
+		assert_ok!(Assets::set_jurisdiction(RuntimeOrigin::signed(1), 0, 2, None));
+		assert!(!ComplianceAccounts::<Test>::contains_key(0, 2));
+
+		assert_ok!(Assets::set_compliance_rules(RuntimeOrigin::signed(1), 0, None));
+		assert_eq!(Assets::compliance_rules(0), None);
+	});
+}
+
+#[test]
+fn allow_list_works() {
+	new_test_ext().execute_with(|| {
+		setup();
+		set_rules(rules(ListMode::AllowList));
+
+		// Both parties have to be listed.
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 10),
+			Error::<Test>::NotPermitted
+		);
+		list(2);
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 10),
+			Error::<Test>::NotPermitted
+		);
+		list(1);
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 10));
+		assert_eq!(Assets::balance(0, 2), 10);
+
+		// Minting and approved transfers are subject to the rules as well.
+		assert_noop!(Assets::mint(RuntimeOrigin::signed(1), 0, 3, 10), Error::<Test>::NotPermitted);
+		Balances::make_free_balance_be(&1, 10);
+		assert_ok!(Assets::approve_transfer(RuntimeOrigin::signed(1), 0, 4, 10));
+		assert_noop!(
+			Assets::transfer_approved(RuntimeOrigin::signed(4), 0, 1, 3, 10),
+			Error::<Test>::NotPermitted
+		);
+		list(3);
+		assert_ok!(Assets::transfer_approved(RuntimeOrigin::signed(4), 0, 1, 3, 10));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 3, 10));
+		assert_eq!(Assets::balance(0, 3), 20);
+	});
+}
+
+#[test]
+fn deny_list_works() {
+	new_test_ext().execute_with(|| {
+		setup();
+		set_rules(rules(ListMode::DenyList));
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 10));
+
+		list(2);
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 10),
+			Error::<Test>::NotPermitted
+		);
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(2), 0, 3, 10),
+			Error::<Test>::NotPermitted
+		);
+		assert_noop!(Assets::mint(RuntimeOrigin::signed(1), 0, 2, 10), Error::<Test>::NotPermitted);
+
+		// The list has no effect without rules.
+		assert_ok!(Assets::set_compliance_rules(RuntimeOrigin::signed(1), 0, None));
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(2), 0, 3, 10));
+	});
+}
+
+#[test]
+fn holding_cap_works() {
+	new_test_ext().execute_with(|| {
+		setup();
+		set_rules(ComplianceRules { max_holding: Some(50), ..rules(ListMode::Open) });
+
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 30));
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 21),
+			Error::<Test>::HoldingCapExceeded
+		);
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 20));
+		assert_eq!(Assets::balance(0, 2), 50);
+		assert_noop!(
+			Assets::mint(RuntimeOrigin::signed(1), 0, 2, 1),
+			Error::<Test>::HoldingCapExceeded
+		);
+		assert_noop!(
+			Assets::mint(RuntimeOrigin::signed(1), 0, 3, 51),
+			Error::<Test>::HoldingCapExceeded
+		);
+		// The cap only applies to the receiving account.
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 3, 10));
+	});
+}
+
+#[test]
+fn cooldown_works() {
+	new_test_ext().execute_with(|| {
+		setup();
+		set_rules(ComplianceRules { cooldown: 10, ..rules(ListMode::Open) });
+
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 10));
+		assert_eq!(Assets::compliance_account(0, 1).last_transfer, Some(1));
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 10),
+			Error::<Test>::CooldownActive
+		);
+		// Only the sender is affected.
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(2), 0, 3, 5));
+
+		System::set_block_number(10);
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 10),
+			Error::<Test>::CooldownActive
+		);
+		System::set_block_number(11);
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 10));
+		assert_eq!(Assets::compliance_account(0, 1).last_transfer, Some(11));
+	});
+}
+
+#[test]
+fn jurisdictions_work() {
+	new_test_ext().execute_with(|| {
+		setup();
+		set_rules(ComplianceRules { require_jurisdiction: true, ..rules(ListMode::Open) });
+
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 10),
+			Error::<Test>::JurisdictionRestricted
+		);
+		assert_ok!(Assets::set_jurisdiction(RuntimeOrigin::signed(1), 0, 1, Some(*b"CH")));
+		assert_ok!(Assets::set_jurisdiction(RuntimeOrigin::signed(1), 0, 2, Some(*b"US")));
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 10));
+
+		assert_ok!(Assets::restrict_jurisdiction(RuntimeOrigin::signed(1), 0, *b"US", true));
+		System::assert_last_event(RuntimeEvent::Assets(crate::Event::JurisdictionRestricted {
+			asset_id: 0,
+			jurisdiction: *b"US",
+			restricted: true,
+		}));
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 10),
+			Error::<Test>::JurisdictionRestricted
+		);
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(2), 0, 1, 10),
+			Error::<Test>::JurisdictionRestricted
+		);
+
+		assert_ok!(Assets::restrict_jurisdiction(RuntimeOrigin::signed(1), 0, *b"US", false));
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(2), 0, 1, 10));
+	});
+}
+
+#[test]
+fn forced_transfers_are_exempt() {
+	new_test_ext().execute_with(|| {
+		setup();
+		set_rules(ComplianceRules {
+			max_holding: Some(10),
+			cooldown: 10,
+			..rules(ListMode::AllowList)
+		});
+
+		assert_ok!(Assets::force_transfer(RuntimeOrigin::signed(1), 0, 1, 2, 20));
+		assert_eq!(Assets::balance(0, 2), 20);
+		assert_eq!(Assets::compliance_account(0, 1).last_transfer, None);
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(2), 0, 1, 10),
+			Error::<Test>::NotPermitted
+		);
+	});
+}
+
+#[test]
+fn transfer_hook_works() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 10));
+		assert_ok!(Assets::force_transfer(RuntimeOrigin::signed(1), 0, 2, 3, 5));
+		assert_eq!(
+			take_transfers(),
+			vec![(0, None, 1, 100), (0, Some(1), 2, 10), (0, Some(2), 3, 5)]
+		);
+
+		set_vetoed(3);
+		assert_noop!(
+			Assets::transfer(RuntimeOrigin::signed(1), 0, 3, 10),
+			DispatchError::Other("vetoed")
+		);
+		assert_noop!(
+			Assets::mint(RuntimeOrigin::signed(1), 0, 3, 10),
+			DispatchError::Other("vetoed")
+		);
+		// Forced transfers are subject to the hook as well.
+		assert_noop!(
+			Assets::force_transfer(RuntimeOrigin::signed(1), 0, 1, 3, 10),
+			DispatchError::Other("vetoed")
+		);
+		assert!(take_transfers().is_empty());
+	});
+}
+
+#[test]
+fn fungibles_are_subject_to_rules() {
+	new_test_ext().execute_with(|| {
+		setup();
+		set_rules(ComplianceRules { cooldown: 10, ..rules(ListMode::AllowList) });
+		list(1);
+
+		// Credits are treated like minting and debits like sending.
+		assert_noop!(<Assets as Mutate<_>>::mint_into(0, &2, 10), Error::<Test>::NotPermitted);
+		assert_noop!(
+			<Assets as Mutate<_>>::transfer(0, &1, &2, 10, Expendable),
+			Error::<Test>::NotPermitted
+		);
+		list(2);
+		assert_ok!(<Assets as Mutate<_>>::transfer(0, &1, &2, 10, Expendable));
+		assert_eq!(Assets::compliance_account(0, 1).last_transfer, Some(1));
+		assert_noop!(
+			<Assets as Mutate<_>>::burn_from(0, &1, 10, Exact, Polite),
+			Error::<Test>::CooldownActive
+		);
+		// Forced debits are exempt.
+		assert_ok!(<Assets as Mutate<_>>::burn_from(0, &1, 10, Exact, Force));
+		assert_eq!(Assets::balance(0, 1), 80);
+
+		set_vetoed(2);
+		assert_noop!(<Assets as Mutate<_>>::mint_into(0, &2, 10), DispatchError::Other("vetoed"));
+	});
+}
+
+#[test]
+fn destroy_removes_compliance_state() {
+	new_test_ext().execute_with(|| {
+		setup();
+		set_rules(ComplianceRules { cooldown: 10, ..rules(ListMode::DenyList) });
+		assert_ok!(Assets::transfer(RuntimeOrigin::signed(1), 0, 2, 10));
+		list(3);
+		assert_ok!(Assets::restrict_jurisdiction(RuntimeOrigin::signed(1), 0, *b"US", true));
+
+		assert_ok!(Assets::freeze_asset(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Assets::start_destroy(RuntimeOrigin::signed(1), 0));
+		// Public accounts go first, then the compliance state, within the limit of each call.
+		assert_eq!(Assets::do_destroy_accounts(0, 1), Ok(1));
+		assert_eq!(Assets::do_destroy_accounts(0, 1), Ok(1));
+		assert!(ComplianceAccounts::<Test>::contains_key(0, 3));
+		assert_eq!(Assets::do_destroy_accounts(0, 1), Ok(1));
+		assert!(!ComplianceAccounts::<Test>::contains_prefix(0));
+		assert!(RestrictedJurisdictions::<Test>::contains_prefix(0));
+		assert_noop!(Assets::finish_destroy(RuntimeOrigin::signed(1), 0), Error::<Test>::InUse);
+		assert_eq!(Assets::do_destroy_accounts(0, 1), Ok(1));
+		assert_eq!(Assets::do_destroy_accounts(0, 1), Ok(0));
+		assert_ok!(Assets::destroy_approvals(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Assets::finish_destroy(RuntimeOrigin::signed(1), 0));
+
+		assert_eq!(Assets::compliance_rules(0), None);
+		assert!(!ComplianceAccounts::<Test>::contains_prefix(0));
+		assert!(!RestrictedJurisdictions::<Test>::contains_prefix(0));
+	});
+}
@@ -0,0 +1,497 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for confidential balances.
+
+use super::*;
+use codec::Encode;
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use sp_confidential_assets::SecretKey;
+
+fn rng() -> ChaCha20Rng {
+	ChaCha20Rng::from_seed([0; 32])
+}
+
+fn secret(who: u64) -> SecretKey {
+	SecretKey::from_seed(&who.encode())
+}
+
+fn confidential(who: u64) -> ConfidentialAccount<u64> {
+	Assets::confidential_account(0, who).unwrap()
+}
+
+/// Create the asset `0` with confidential balances and give `1` and `2` `100` of it each.
+fn setup() {
+	assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 1));
+	assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+	assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 2, 100));
+	assert_ok!(Assets::enable_confidential(RuntimeOrigin::signed(1), 0));
+}
+
+/// Create the confidential account of `who`, funding it to pay the deposit.
+fn configure(who: u64) {
+	Balances::make_free_balance_be(&who, 100);
+	let proof = PubkeyValidityProof::new(&secret(who), &(0u32, who).encode(), &mut rng());
+	assert_ok!(Assets::configure_confidential_account(
+		RuntimeOrigin::signed(who),
+		0,
+		secret(who).public(),
+		proof
+	));
+}
+
+/// Deposit `amount` into the confidential balance of `who` and make it available.
+fn deposit(who: u64, amount: u64) {
+	assert_ok!(Assets::deposit_confidential(RuntimeOrigin::signed(who), 0, amount));
+	assert_ok!(Assets::apply_pending_balance(
+		RuntimeOrigin::signed(who),
+		0,
+		confidential(who).pending
+	));
+}
+
+#[test]
+fn enable_confidential_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Assets::enable_confidential(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::Unknown
+		);
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 1));
+		assert_noop!(
+			Assets::enable_confidential(RuntimeOrigin::signed(2), 0),
+			Error::<Test>::NoPermission
+		);
+
+		assert_ok!(Assets::enable_confidential(RuntimeOrigin::signed(1), 0));
+		System::assert_last_event(RuntimeEvent::Assets(crate::Event::ConfidentialEnabled {
+			asset_id: 0,
+		}));
+		assert_eq!(Assets::confidential_supply(0), Some(0));
+		assert_eq!(ConfidentialAssets::<Test>::get(0).unwrap().accounts, 0);
+		assert_noop!(
+			Assets::enable_confidential(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::AlreadyExists
+		);
+	});
+}
+
+#[test]
+fn configure_confidential_account_works() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, 1, true, 1));
+		let proof =
+			|who: u64| PubkeyValidityProof::new(&secret(who), &(0u32, who).encode(), &mut rng());
+
+		assert_noop!(
+			Assets::configure_confidential_account(
+				RuntimeOrigin::signed(1),
+				1,
+				secret(1).public(),
+				proof(1)
+			),
+			Error::<Test>::NotConfidential
+		);
+		// Only holders of the asset can have a confidential balance.
+		assert_noop!(
+			Assets::configure_confidential_account(
+				RuntimeOrigin::signed(3),
+				0,
+				secret(3).public(),
+				proof(3)
+			),
+			Error::<Test>::NoAccount
+		);
+		// The proof is bound to the account.
+		assert_noop!(
+			Assets::configure_confidential_account(
+				RuntimeOrigin::signed(1),
+				0,
+				secret(2).public(),
+				proof(2)
+			),
+			Error::<Test>::InvalidProof
+		);
+		// The holder pays for the storage of its confidential account.
+		assert_noop!(
+			Assets::configure_confidential_account(
+				RuntimeOrigin::signed(1),
+				0,
+				secret(1).public(),
+				proof(1)
+			),
+			BalancesError::<Test>::InsufficientBalance
+		);
+
+		configure(1);
+		System::assert_last_event(RuntimeEvent::Assets(
+			crate::Event::ConfidentialAccountConfigured { asset_id: 0, who: 1 },
+		));
+		assert_eq!(Balances::reserved_balance(&1), 10);
+		assert_eq!(confidential(1).deposit, 10);
+		assert_eq!(ConfidentialAssets::<Test>::get(0).unwrap().accounts, 1);
+		assert_eq!(confidential(1).public_key, secret(1).public());
+		assert_eq!(secret(1).decrypt(&confidential(1).available, 0), Some(0));
+		assert_noop!(
+			Assets::configure_confidential_account(
+				RuntimeOrigin::signed(1),
+				0,
+				secret(1).public(),
+				proof(1)
+			),
+			Error::<Test>::AlreadyExists
+		);
+	});
+}
+
+#[test]
+fn deposit_and_withdraw_confidential_work() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_noop!(
+			Assets::deposit_confidential(RuntimeOrigin::signed(1), 0, 60),
+			Error::<Test>::NoConfidentialAccount
+		);
+		configure(1);
+
+		assert_ok!(Assets::deposit_confidential(RuntimeOrigin::signed(1), 0, 60));
+		System::assert_last_event(RuntimeEvent::Assets(crate::Event::ConfidentialDeposited {
+			asset_id: 0,
+			who: 1,
+			amount: 60,
+		}));
+		assert_eq!(Assets::balance(0, 1), 40);
+		assert_eq!(Assets::total_supply(0), 200);
+		assert_eq!(Assets::confidential_supply(0), Some(60));
+		assert_eq!(secret(1).decrypt(&confidential(1).pending, 100), Some(60));
+		// The public balance is kept alive.
+		assert_noop!(
+			Assets::deposit_confidential(RuntimeOrigin::signed(1), 0, 40),
+			Error::<Test>::BalanceLow
+		);
+
+		assert_noop!(
+			Assets::apply_pending_balance(RuntimeOrigin::signed(1), 0, Default::default()),
+			Error::<Test>::PendingBalanceChanged
+		);
+		assert_ok!(Assets::apply_pending_balance(
+			RuntimeOrigin::signed(1),
+			0,
+			confidential(1).pending
+		));
+		assert_eq!(secret(1).decrypt(&confidential(1).available, 100), Some(60));
+		assert_eq!(secret(1).decrypt(&confidential(1).pending, 100), Some(0));
+
+		let available = confidential(1).available;
+		let context = (0u32, 1u64).encode();
+		let proof =
+			WithdrawProof::new(&secret(1), &available, 60, 60, &context, &mut rng()).unwrap();
+		assert_noop!(
+			Assets::withdraw_confidential(RuntimeOrigin::signed(1), 0, 50, Box::new(proof.clone())),
+			Error::<Test>::InvalidProof
+		);
+		assert_ok!(Assets::withdraw_confidential(RuntimeOrigin::signed(1), 0, 60, Box::new(proof)));
+		System::assert_last_event(RuntimeEvent::Assets(crate::Event::ConfidentialWithdrawn {
+			asset_id: 0,
+			who: 1,
+			amount: 60,
+		}));
+		assert_eq!(Assets::balance(0, 1), 100);
+		assert_eq!(Assets::confidential_supply(0), Some(0));
+		assert_eq!(secret(1).decrypt(&confidential(1).available, 100), Some(0));
+
+		// Nothing is left to withdraw.
+		let proof =
+			WithdrawProof::new(&secret(1), &available, 60, 60, &context, &mut rng()).unwrap();
+		assert_noop!(
+			Assets::withdraw_confidential(RuntimeOrigin::signed(1), 0, 60, Box::new(proof)),
+			Error::<Test>::InvalidProof
+		);
+	});
+}
+
+#[test]
+fn confidential_transfer_works() {
+	new_test_ext().execute_with(|| {
+		setup();
+		configure(1);
+		deposit(1, 60);
+
+		let available = confidential(1).available;
+		let context = (0u32, 1u64, 2u64).encode();
+		// The receiver needs a confidential balance.
+		let (amount, proof) = TransferProof::new(
+			&secret(1),
+			&secret(2).public(),
+			&available,
+			60,
+			25,
+			&context,
+			&mut rng(),
+		)
+		.unwrap();
+		assert_noop!(
+			Assets::confidential_transfer(
+				RuntimeOrigin::signed(1),
+				0,
+				2,
+				amount,
+				Box::new(proof.clone())
+			),
+			Error::<Test>::NoConfidentialAccount
+		);
+		configure(2);
+
+		// The proof is bound to the receiver, even if another account shares its key.
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 3, 100));
+		Balances::make_free_balance_be(&3, 100);
+		let key_proof = PubkeyValidityProof::new(&secret(2), &(0u32, 3u64).encode(), &mut rng());
+		assert_ok!(Assets::configure_confidential_account(
+			RuntimeOrigin::signed(3),
+			0,
+			secret(2).public(),
+			key_proof
+		));
+		assert_noop!(
+			Assets::confidential_transfer(
+				RuntimeOrigin::signed(1),
+				0,
+				3,
+				amount,
+				Box::new(proof.clone())
+			),
+			Error::<Test>::InvalidProof
+		);
+
+		assert_ok!(Assets::confidential_transfer(
+			RuntimeOrigin::signed(1),
+			0,
+			2,
+			amount,
+			Box::new(proof.clone())
+		));
+		System::assert_last_event(RuntimeEvent::Assets(crate::Event::ConfidentialTransferred {
+			asset_id: 0,
+			from: 1,
+			to: 2,
+		}));
+		assert_eq!(secret(1).decrypt(&confidential(1).available, 100), Some(35));
+		assert_eq!(secret(2).decrypt(&confidential(2).pending, 100), Some(25));
+		// Public balances and the supply are unaffected.
+		assert_eq!(Assets::balance(0, 1), 40);
+		assert_eq!(Assets::balance(0, 2), 100);
+		assert_eq!(Assets::confidential_supply(0), Some(60));
+
+		// The proof cannot be replayed since the available balance changed.
+		assert_noop!(
+			Assets::confidential_transfer(RuntimeOrigin::signed(1), 0, 2, amount, Box::new(proof)),
+			Error::<Test>::InvalidProof
+		);
+	});
+}
+
+#[test]
+fn confidential_transfer_respects_freezing() {
+	new_test_ext().execute_with(|| {
+		setup();
+		configure(1);
+		configure(2);
+		deposit(1, 60);
+		let context = (0u32, 1u64, 2u64).encode();
+		let (amount, proof) = TransferProof::new(
+			&secret(1),
+			&secret(2).public(),
+			&confidential(1).available,
+			60,
+			25,
+			&context,
+			&mut rng(),
+		)
+		.unwrap();
+
+		assert_ok!(Assets::freeze(RuntimeOrigin::signed(1), 0, 1));
+		assert_noop!(
+			Assets::confidential_transfer(
+				RuntimeOrigin::signed(1),
+				0,
+				2,
+				amount,
+				Box::new(proof.clone())
+			),
+			TokenError::Frozen
+		);
+		assert_ok!(Assets::thaw(RuntimeOrigin::signed(1), 0, 1));
+
+		assert_ok!(Assets::block(RuntimeOrigin::signed(1), 0, 2));
+		assert_noop!(
+			Assets::confidential_transfer(
+				RuntimeOrigin::signed(1),
+				0,
+				2,
+				amount,
+				Box::new(proof.clone())
+			),
+			TokenError::Blocked
+		);
+
+		assert_ok!(Assets::freeze_asset(RuntimeOrigin::signed(1), 0));
+		assert_noop!(
+			Assets::confidential_transfer(RuntimeOrigin::signed(1), 0, 2, amount, Box::new(proof)),
+			Error::<Test>::AssetNotLive
+		);
+	});
+}
+
+#[test]
+fn withdraw_confidential_respects_freezing() {
+	new_test_ext().execute_with(|| {
+		setup();
+		configure(1);
+		deposit(1, 60);
+		let context = (0u32, 1u64).encode();
+		let proof = WithdrawProof::new(
+			&secret(1),
+			&confidential(1).available,
+			60,
+			60,
+			&context,
+			&mut rng(),
+		)
+		.unwrap();
+
+		assert_ok!(Assets::freeze(RuntimeOrigin::signed(1), 0, 1));
+		assert_noop!(
+			Assets::withdraw_confidential(RuntimeOrigin::signed(1), 0, 60, Box::new(proof.clone())),
+			TokenError::Frozen
+		);
+		assert_ok!(Assets::thaw(RuntimeOrigin::signed(1), 0, 1));
+
+		assert_ok!(Assets::freeze_asset(RuntimeOrigin::signed(1), 0));
+		assert_noop!(
+			Assets::withdraw_confidential(RuntimeOrigin::signed(1), 0, 60, Box::new(proof.clone())),
+			Error::<Test>::AssetNotLive
+		);
+		assert_ok!(Assets::thaw_asset(RuntimeOrigin::signed(1), 0));
+
+		assert_ok!(Assets::withdraw_confidential(RuntimeOrigin::signed(1), 0, 60, Box::new(proof)));
+		assert_eq!(Assets::confidential_supply(0), Some(0));
+	});
+}
+
+#[test]
+fn confidential_balances_are_subject_to_rules() {
+	new_test_ext().execute_with(|| {
+		setup();
+		configure(1);
+		configure(2);
+		deposit(1, 60);
+		let rules = |max_holding| ComplianceRules {
+			list_mode: ListMode::AllowList,
+			max_holding,
+			cooldown: 0,
+			require_jurisdiction: false,
+		};
+		assert_ok!(Assets::set_compliance_rules(RuntimeOrigin::signed(1), 0, Some(rules(None))));
+		assert_ok!(Assets::set_listed(RuntimeOrigin::signed(1), 0, 1, true));
+
+		assert_noop!(
+			Assets::deposit_confidential(RuntimeOrigin::signed(2), 0, 10),
+			Error::<Test>::NotPermitted
+		);
+		let (amount, proof) = TransferProof::new(
+			&secret(1),
+			&secret(2).public(),
+			&confidential(1).available,
+			60,
+			25,
+			&(0u32, 1u64, 2u64).encode(),
+			&mut rng(),
+		)
+		.unwrap();
+		let transfer = || {
+			Assets::confidential_transfer(
+				RuntimeOrigin::signed(1),
+				0,
+				2,
+				amount,
+				Box::new(proof.clone()),
+			)
+		};
+		assert_noop!(transfer(), Error::<Test>::NotPermitted);
+		assert_ok!(Assets::set_listed(RuntimeOrigin::signed(1), 0, 2, true));
+
+		// A holding cap cannot be enforced on hidden amounts, but on withdrawals.
+		assert_ok!(Assets::set_compliance_rules(
+			RuntimeOrigin::signed(1),
+			0,
+			Some(rules(Some(50)))
+		));
+		assert_noop!(transfer(), Error::<Test>::HoldingCapExceeded);
+		let withdraw = WithdrawProof::new(
+			&secret(1),
+			&confidential(1).available,
+			60,
+			20,
+			&(0u32, 1u64).encode(),
+			&mut rng(),
+		)
+		.unwrap();
+		assert_noop!(
+			Assets::withdraw_confidential(RuntimeOrigin::signed(1), 0, 20, Box::new(withdraw)),
+			Error::<Test>::HoldingCapExceeded
+		);
+
+		assert_ok!(Assets::set_compliance_rules(RuntimeOrigin::signed(1), 0, Some(rules(None))));
+		set_vetoed(2);
+		assert_noop!(transfer(), DispatchError::Other("vetoed"));
+	});
+}
+
+#[test]
+fn destroy_removes_confidential_balances() {
+	new_test_ext().execute_with(|| {
+		setup();
+		configure(1);
+		configure(2);
+		deposit(1, 60);
+		// The public account of `2` dies while its confidential account remains.
+		assert_ok!(Assets::burn(RuntimeOrigin::signed(1), 0, 2, 100));
+		assert!(Assets::confidential_account(0, 2).is_some());
+
+		assert_ok!(Assets::freeze_asset(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Assets::start_destroy(RuntimeOrigin::signed(1), 0));
+		// Public accounts go first, confidential ones share the same limit.
+		assert_eq!(Assets::do_destroy_accounts(0, 1), Ok(1));
+		assert_eq!(ConfidentialAccounts::<Test>::iter_prefix(0).count(), 2);
+		assert_eq!(Assets::do_destroy_accounts(0, 1), Ok(1));
+		assert_eq!(ConfidentialAccounts::<Test>::iter_prefix(0).count(), 1);
+		assert_eq!(ConfidentialAssets::<Test>::get(0).unwrap().accounts, 1);
+		assert_noop!(Assets::finish_destroy(RuntimeOrigin::signed(1), 0), Error::<Test>::InUse);
+
+		assert_ok!(Assets::destroy_accounts(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Assets::destroy_approvals(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Assets::finish_destroy(RuntimeOrigin::signed(1), 0));
+
+		assert!(Assets::confidential_account(0, 1).is_none());
+		assert!(Assets::confidential_account(0, 2).is_none());
+		assert_eq!(Assets::confidential_supply(0), None);
+		// The deposits were returned.
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(Balances::reserved_balance(&2), 0);
+	});
+}
@@ -0,0 +1,399 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Ethereum compatible RPC interface for the contracts pallet.
+//!
+//! Implements the subset of the `eth_*` namespace that is needed by Ethereum wallets and
+//! tooling to interact with contracts. Everything is answered by the
+//! [`pallet_contracts::EthApi`] runtime API.
+
+use std::sync::Arc;
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::{
+		error::{ErrorCode, ErrorObject},
+		ErrorObjectOwned,
+	},
+};
+use pallet_contracts::eth::{EthCallResult, Receipt};
+use sc_transaction_pool_api::{TransactionPool, TransactionSource};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::keccak_256, Bytes, H160, H256, U256};
+use sp_runtime::traits::{Block as BlockT, NumberFor, UniqueSaturatedFrom, UniqueSaturatedInto};
+
+pub use pallet_contracts::EthApi as EthRuntimeApi;
+
+mod types;
+pub use types::*;
+
+#[rpc(client, server)]
+pub trait EthRpcApi {
+	#[method(name = "eth_chainId")]
+	fn chain_id(&self) -> RpcResult<U256>;
+
+	#[method(name = "eth_gasPrice")]
+	fn gas_price(&self) -> RpcResult<U256>;
+
+	#[method(name = "eth_blockNumber")]
+	fn block_number(&self) -> RpcResult<U256>;
+
+	#[method(name = "eth_getBalance")]
+	fn balance(&self, address: H160, block: Option<BlockNumberOrTag>) -> RpcResult<U256>;
+
+	#[method(name = "eth_getTransactionCount")]
+	fn transaction_count(&self, address: H160, block: Option<BlockNumberOrTag>) -> RpcResult<U256>;
+
+	#[method(name = "eth_call")]
+	fn call(&self, request: CallRequest, block: Option<BlockNumberOrTag>) -> RpcResult<Bytes>;
+
+	#[method(name = "eth_estimateGas")]
+	fn estimate_gas(
+		&self,
+		request: CallRequest,
+		block: Option<BlockNumberOrTag>,
+	) -> RpcResult<U256>;
+
+	#[method(name = "eth_sendRawTransaction")]
+	async fn send_raw_transaction(&self, payload: Bytes) -> RpcResult<H256>;
+
+	#[method(name = "eth_getTransactionReceipt")]
+	fn transaction_receipt(&self, hash: H256) -> RpcResult<Option<ReceiptInfo>>;
+
+	#[method(name = "eth_getLogs")]
+	fn logs(&self, filter: LogFilter) -> RpcResult<Vec<LogInfo>>;
+}
+
+/// Provides the Ethereum compatible RPC methods of the contracts pallet.
+pub struct Eth<C, P> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	/// Shared reference to the transaction pool.
+	pool: Arc<P>,
+	/// How many blocks are searched by receipt and log queries.
+	max_block_range: u32,
+}
+
+impl<C, P> Eth<C, P> {
+	/// Creates a new instance of the Eth Rpc helper.
+	///
+	/// `max_block_range` limits how many blocks below the best block are searched for receipts
+	/// and how many blocks a single `eth_getLogs` query may cover.
+	pub fn new(client: Arc<C>, pool: Arc<P>, max_block_range: u32) -> Self {
+		Self { client, pool, max_block_range }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The transaction was rejected or the contract failed to execute.
+	ExecutionFailed,
+	/// The contract reverted its execution.
+	Reverted,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::Reverted => 3,
+			Error::ExecutionFailed => -32000,
+		}
+	}
+}
+
+fn runtime_error(error: impl ToString) -> ErrorObjectOwned {
+	ErrorObject::owned(Error::RuntimeError.into(), "Runtime error", Some(error.to_string()))
+}
+
+fn invalid_params(message: impl Into<String>) -> ErrorObjectOwned {
+	ErrorObject::owned(ErrorCode::InvalidParams.code(), message, None::<()>)
+}
+
+/// Turns the result of a dry-run into the gas it requires or the reason why it failed.
+fn check_call_result(result: &EthCallResult) -> RpcResult<()> {
+	match &result.result {
+		Ok(retval) if retval.did_revert() => Err(ErrorObject::owned(
+			Error::Reverted.into(),
+			"execution reverted",
+			Some(Bytes(retval.data.clone())),
+		)),
+		Ok(_) => Ok(()),
+		Err(err) => Err(ErrorObject::owned(
+			Error::ExecutionFailed.into(),
+			"execution failed",
+			Some(format!("{:?}", err)),
+		)),
+	}
+}
+
+impl<C, P, Block> Eth<C, P>
+where
+	Block: BlockT,
+	Block::Hash: Into<H256>,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: EthRuntimeApi<Block>,
+	P: TransactionPool<Block = Block> + 'static,
+{
+	/// Resolves `block` to a block number. Defaults to the best block.
+	fn block_number_of(&self, block: Option<BlockNumberOrTag>) -> RpcResult<NumberFor<Block>> {
+		let info = self.client.info();
+		match block.unwrap_or_default() {
+			BlockNumberOrTag::Tag(BlockTag::Latest | BlockTag::Pending) => Ok(info.best_number),
+			BlockNumberOrTag::Tag(BlockTag::Safe | BlockTag::Finalized) =>
+				Ok(info.finalized_number),
+			BlockNumberOrTag::Tag(BlockTag::Earliest) => Ok(0u32.into()),
+			BlockNumberOrTag::Number(number) => {
+				let number = u64::try_from(number)
+					.map(NumberFor::<Block>::unique_saturated_from)
+					.map_err(|_| invalid_params("Block number out of range"))?;
+				if number > info.best_number {
+					return Err(invalid_params("Unknown block"))
+				}
+				Ok(number)
+			},
+		}
+	}
+
+	/// Resolves `block` to a block hash. Defaults to the best block.
+	fn block_hash_of(&self, block: Option<BlockNumberOrTag>) -> RpcResult<Block::Hash> {
+		let number = self.block_number_of(block)?;
+		self.client
+			.hash(number)
+			.map_err(runtime_error)?
+			.ok_or_else(|| invalid_params("Unknown block"))
+	}
+
+	/// Dry-runs the `request` on top of `block`.
+	fn dry_run(
+		&self,
+		request: CallRequest,
+		block: Option<BlockNumberOrTag>,
+	) -> RpcResult<EthCallResult> {
+		let at = self.block_hash_of(block)?;
+		let gas = request
+			.gas
+			.map(u64::try_from)
+			.transpose()
+			.map_err(|_| invalid_params("Gas limit out of range"))?;
+		self.client
+			.runtime_api()
+			.call(
+				at,
+				request.from.unwrap_or_default(),
+				request.to,
+				request.value.unwrap_or_default(),
+				gas,
+				request.data.map(|data| data.0).unwrap_or_default(),
+			)
+			.map_err(runtime_error)
+	}
+
+	/// The receipts of all Ethereum transactions included in the block with the given `number`.
+	fn receipts_at(&self, number: NumberFor<Block>) -> RpcResult<Vec<ReceiptInfo>> {
+		let Some(hash) = self.client.hash(number).map_err(runtime_error)? else {
+			return Ok(Vec::new())
+		};
+		let api = self.client.runtime_api();
+		let receipts = api.receipts(hash).map_err(runtime_error)?;
+		if receipts.is_empty() {
+			return Ok(Vec::new())
+		}
+		let gas_price = api.gas_price(hash).map_err(runtime_error)?;
+
+		let block_hash: H256 = hash.into();
+		let block_number = U256::from(UniqueSaturatedInto::<u64>::unique_saturated_into(number));
+		let mut cumulative_gas_used = U256::zero();
+		let mut log_index = U256::zero();
+		Ok(receipts
+			.into_iter()
+			.map(|receipt| {
+				let Receipt {
+					transaction_hash,
+					transaction_index,
+					from,
+					to,
+					contract_address,
+					gas_used,
+					success,
+					logs,
+				} = receipt;
+				let transaction_index = U256::from(transaction_index);
+				let logs = logs
+					.into_iter()
+					.map(|log| {
+						let log = LogInfo {
+							address: log.address,
+							topics: log.topics,
+							data: log.data.into(),
+							block_hash,
+							block_number,
+							transaction_hash,
+							transaction_index,
+							log_index,
+							removed: false,
+						};
+						log_index += U256::one();
+						log
+					})
+					.collect::<Vec<_>>();
+				cumulative_gas_used = cumulative_gas_used.saturating_add(gas_used.into());
+				ReceiptInfo {
+					transaction_hash,
+					transaction_index,
+					block_hash,
+					block_number,
+					from,
+					to,
+					contract_address,
+					gas_used: gas_used.into(),
+					cumulative_gas_used,
+					effective_gas_price: gas_price,
+					status: u8::from(success).into(),
+					logs_bloom: logs_bloom(&logs),
+					logs,
+				}
+			})
+			.collect())
+	}
+}
+
+#[async_trait]
+impl<C, P, Block> EthRpcApiServer for Eth<C, P>
+where
+	Block: BlockT,
+	Block::Hash: Into<H256>,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: EthRuntimeApi<Block>,
+	P: TransactionPool<Block = Block> + 'static,
+{
+	fn chain_id(&self) -> RpcResult<U256> {
+		let best = self.client.info().best_hash;
+		self.client.runtime_api().chain_id(best).map(Into::into).map_err(runtime_error)
+	}
+
+	fn gas_price(&self) -> RpcResult<U256> {
+		let best = self.client.info().best_hash;
+		self.client.runtime_api().gas_price(best).map_err(runtime_error)
+	}
+
+	fn block_number(&self) -> RpcResult<U256> {
+		let best = self.client.info().best_number;
+		Ok(UniqueSaturatedInto::<u64>::unique_saturated_into(best).into())
+	}
+
+	fn balance(&self, address: H160, block: Option<BlockNumberOrTag>) -> RpcResult<U256> {
+		let at = self.block_hash_of(block)?;
+		self.client.runtime_api().balance(at, address).map_err(runtime_error)
+	}
+
+	fn transaction_count(&self, address: H160, block: Option<BlockNumberOrTag>) -> RpcResult<U256> {
+		let at = self.block_hash_of(block)?;
+		self.client.runtime_api().nonce(at, address).map_err(runtime_error)
+	}
+
+	fn call(&self, request: CallRequest, block: Option<BlockNumberOrTag>) -> RpcResult<Bytes> {
+		let result = self.dry_run(request, block)?;
+		check_call_result(&result)?;
+		Ok(result.result.map(|retval| retval.data).unwrap_or_default().into())
+	}
+
+	fn estimate_gas(
+		&self,
+		request: CallRequest,
+		block: Option<BlockNumberOrTag>,
+	) -> RpcResult<U256> {
+		let result = self.dry_run(request, block)?;
+		check_call_result(&result)?;
+		Ok(result.gas_required.into())
+	}
+
+	async fn send_raw_transaction(&self, payload: Bytes) -> RpcResult<H256> {
+		let best = self.client.info().best_hash;
+		let hash = H256(keccak_256(&payload));
+		let xt = self
+			.client
+			.runtime_api()
+			.transact(best, payload.0)
+			.map_err(runtime_error)?
+			.map_err(|err| {
+				ErrorObject::owned(
+					Error::ExecutionFailed.into(),
+					"Invalid transaction",
+					Some(format!("{:?}", err)),
+				)
+			})?;
+		self.pool
+			.submit_one(best, TransactionSource::External, xt)
+			.await
+			.map_err(|err| {
+				ErrorObject::owned(
+					Error::ExecutionFailed.into(),
+					"Transaction was rejected by the pool",
+					Some(err.to_string()),
+				)
+			})?;
+		Ok(hash)
+	}
+
+	fn transaction_receipt(&self, hash: H256) -> RpcResult<Option<ReceiptInfo>> {
+		let best = self.client.info().best_number;
+		let mut number = best;
+		loop {
+			if let Some(receipt) =
+				self.receipts_at(number)?.into_iter().find(|r| r.transaction_hash == hash)
+			{
+				return Ok(Some(receipt))
+			}
+			if number == 0u32.into() || best - number >= self.max_block_range.into() {
+				return Ok(None)
+			}
+			number -= 1u32.into();
+		}
+	}
+
+	fn logs(&self, filter: LogFilter) -> RpcResult<Vec<LogInfo>> {
+		let from = self.block_number_of(filter.from_block)?;
+		let to = self.block_number_of(filter.to_block)?;
+		if from > to {
+			return Err(invalid_params("fromBlock is greater than toBlock"))
+		}
+		if to - from >= self.max_block_range.into() {
+			return Err(invalid_params(format!(
+				"Block range exceeds the maximum of {} blocks",
+				self.max_block_range
+			)))
+		}
+
+		let mut logs = Vec::new();
+		let mut number = from;
+		while number <= to {
+			logs.extend(
+				self.receipts_at(number)?
+					.into_iter()
+					.flat_map(|receipt| receipt.logs)
+					.filter(|log| filter.matches(log)),
+			);
+			number += 1u32.into();
+		}
+		Ok(logs)
+	}
+}
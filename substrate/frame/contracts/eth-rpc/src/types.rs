@@ -0,0 +1,268 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The JSON representation of the types used by the Ethereum RPC interface.
+
+use serde::{Deserialize, Serialize};
+use sp_core::{hashing::keccak_256, Bytes, H160, H256, U256};
+
+/// A named block as understood by Ethereum tooling.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockTag {
+	/// The genesis block.
+	Earliest,
+	/// The best block.
+	Latest,
+	/// Treated the same as [`BlockTag::Latest`].
+	Pending,
+	/// Treated the same as [`BlockTag::Finalized`].
+	Safe,
+	/// The last finalized block.
+	Finalized,
+}
+
+/// Selects a block either by its number or by a [`BlockTag`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BlockNumberOrTag {
+	/// The block with the given number.
+	Number(U256),
+	/// The block named by the tag.
+	Tag(BlockTag),
+}
+
+impl Default for BlockNumberOrTag {
+	fn default() -> Self {
+		Self::Tag(BlockTag::Latest)
+	}
+}
+
+/// The arguments of `eth_call` and `eth_estimateGas`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CallRequest {
+	/// The caller. Defaults to the zero address.
+	pub from: Option<H160>,
+	/// The contract to call or `None` to instantiate a new one.
+	pub to: Option<H160>,
+	/// The gas limit. Defaults to the maximum weight of a block.
+	pub gas: Option<U256>,
+	/// Ignored as the gas price is dictated by the runtime.
+	pub gas_price: Option<U256>,
+	/// The balance to transfer.
+	pub value: Option<U256>,
+	/// The input data of the call or instantiation.
+	#[serde(alias = "input")]
+	pub data: Option<Bytes>,
+}
+
+/// One or more addresses a log filter applies to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AddressFilter {
+	/// Matches logs of a single contract.
+	Single(H160),
+	/// Matches logs of any of the contracts.
+	Multiple(Vec<H160>),
+}
+
+impl AddressFilter {
+	fn matches(&self, address: &H160) -> bool {
+		match self {
+			Self::Single(expected) => expected == address,
+			Self::Multiple(expected) => expected.contains(address),
+		}
+	}
+}
+
+/// The arguments of `eth_getLogs`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LogFilter {
+	/// The first block to search. Defaults to the best block.
+	pub from_block: Option<BlockNumberOrTag>,
+	/// The last block to search. Defaults to the best block.
+	pub to_block: Option<BlockNumberOrTag>,
+	/// Only return logs emitted by these contracts.
+	pub address: Option<AddressFilter>,
+	/// Only return logs whose topics match at the given positions. `null` matches any topic.
+	pub topics: Option<Vec<Option<H256>>>,
+}
+
+impl LogFilter {
+	/// Whether the `log` matches the address and topics of this filter.
+	pub fn matches(&self, log: &LogInfo) -> bool {
+		if !self.address.as_ref().map_or(true, |filter| filter.matches(&log.address)) {
+			return false
+		}
+		self.topics.iter().flatten().enumerate().all(|(idx, expected)| match expected {
+			Some(expected) => log.topics.get(idx) == Some(expected),
+			None => true,
+		})
+	}
+}
+
+/// A log as returned by `eth_getLogs` and as part of a [`ReceiptInfo`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogInfo {
+	/// The contract that emitted the log.
+	pub address: H160,
+	/// The topics of the log.
+	pub topics: Vec<H256>,
+	/// The data of the log.
+	pub data: Bytes,
+	/// The hash of the block that includes the transaction.
+	pub block_hash: H256,
+	/// The number of the block that includes the transaction.
+	pub block_number: U256,
+	/// The hash of the transaction that emitted the log.
+	pub transaction_hash: H256,
+	/// The index of the transaction within the block.
+	pub transaction_index: U256,
+	/// The index of the log within the block.
+	pub log_index: U256,
+	/// Always `false` as logs are only returned for blocks of the canonical chain.
+	pub removed: bool,
+}
+
+/// A receipt as returned by `eth_getTransactionReceipt`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptInfo {
+	/// The hash of the transaction.
+	pub transaction_hash: H256,
+	/// The index of the transaction within the block.
+	pub transaction_index: U256,
+	/// The hash of the block that includes the transaction.
+	pub block_hash: H256,
+	/// The number of the block that includes the transaction.
+	pub block_number: U256,
+	/// The signer of the transaction.
+	pub from: H160,
+	/// The contract that was called. `None` for instantiations.
+	pub to: Option<H160>,
+	/// The contract that was created by the transaction.
+	pub contract_address: Option<H160>,
+	/// The gas used by the transaction.
+	pub gas_used: U256,
+	/// The gas used by this and all previous transactions of the block.
+	pub cumulative_gas_used: U256,
+	/// The price that was paid for each unit of gas.
+	pub effective_gas_price: U256,
+	/// `1` if the transaction succeeded and `0` otherwise.
+	pub status: U256,
+	/// The logs emitted by contracts during the transaction.
+	pub logs: Vec<LogInfo>,
+	/// The bloom filter of the logs.
+	pub logs_bloom: Bytes,
+}
+
+/// Computes the 2048 bit bloom filter of the `logs` as defined by the Ethereum yellow paper.
+pub fn logs_bloom(logs: &[LogInfo]) -> Bytes {
+	let mut bloom = [0u8; 256];
+	let items = logs.iter().flat_map(|log| {
+		core::iter::once(log.address.as_bytes()).chain(log.topics.iter().map(|t| t.as_bytes()))
+	});
+	for item in items {
+		let hash = keccak_256(item);
+		for idx in [0, 2, 4] {
+			let bit = (usize::from(hash[idx]) << 8 | usize::from(hash[idx + 1])) & 2047;
+			bloom[255 - bit / 8] |= 1 << (bit % 8);
+		}
+	}
+	bloom.to_vec().into()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn block_number_or_tag_deserializes() {
+		assert_eq!(
+			serde_json::from_str::<BlockNumberOrTag>(r#""latest""#).unwrap(),
+			BlockNumberOrTag::Tag(BlockTag::Latest),
+		);
+		assert_eq!(
+			serde_json::from_str::<BlockNumberOrTag>(r#""0x1b""#).unwrap(),
+			BlockNumberOrTag::Number(27.into()),
+		);
+		assert!(serde_json::from_str::<BlockNumberOrTag>(r#""newest""#).is_err());
+	}
+
+	#[test]
+	fn call_request_accepts_input_alias() {
+		let request: CallRequest = serde_json::from_str(
+			r#"{"to":"0x0101010101010101010101010101010101010101","input":"0x2a"}"#,
+		)
+		.unwrap();
+		assert_eq!(request.to, Some(H160::repeat_byte(1)));
+		assert_eq!(request.data, Some(vec![42u8].into()));
+		assert_eq!(request.from, None);
+	}
+
+	#[test]
+	fn log_filter_matches() {
+		let log = LogInfo {
+			address: H160::repeat_byte(1),
+			topics: vec![H256::repeat_byte(2), H256::repeat_byte(3)],
+			data: Bytes::default(),
+			block_hash: H256::zero(),
+			block_number: 0.into(),
+			transaction_hash: H256::zero(),
+			transaction_index: 0.into(),
+			log_index: 0.into(),
+			removed: false,
+		};
+		let filter = |address, topics| LogFilter { address, topics, ..Default::default() };
+
+		assert!(filter(None, None).matches(&log));
+		assert!(filter(Some(AddressFilter::Single(H160::repeat_byte(1))), None).matches(&log));
+		assert!(!filter(Some(AddressFilter::Single(H160::repeat_byte(2))), None).matches(&log));
+		assert!(filter(
+			Some(AddressFilter::Multiple(vec![H160::repeat_byte(2), H160::repeat_byte(1)])),
+			None
+		)
+		.matches(&log));
+		assert!(filter(None, Some(vec![None, Some(H256::repeat_byte(3))])).matches(&log));
+		assert!(!filter(None, Some(vec![Some(H256::repeat_byte(3))])).matches(&log));
+		assert!(!filter(None, Some(vec![None, None, Some(H256::repeat_byte(3))])).matches(&log));
+	}
+
+	#[test]
+	fn logs_bloom_contains_address_and_topics() {
+		assert_eq!(logs_bloom(&[]).0, vec![0u8; 256]);
+
+		let log = LogInfo {
+			address: H160::repeat_byte(1),
+			topics: vec![H256::repeat_byte(2)],
+			data: Bytes::default(),
+			block_hash: H256::zero(),
+			block_number: 0.into(),
+			transaction_hash: H256::zero(),
+			transaction_index: 0.into(),
+			log_index: 0.into(),
+			removed: false,
+		};
+		let bloom = logs_bloom(&[log]);
+		// Every item sets at most three bits.
+		let bits_set: u32 = bloom.0.iter().map(|byte| byte.count_ones()).sum();
+		assert!(bits_set > 0 && bits_set <= 6);
+	}
+}
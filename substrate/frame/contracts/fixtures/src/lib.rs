@@ -23,11 +23,32 @@ use std::{fs, path::PathBuf};
 pub fn compile_module<T>(
 	fixture_name: &str,
 ) -> anyhow::Result<(Vec<u8>, <T::Hashing as Hash>::Output)>
+where
+	T: frame_system::Config,
+{
+	load_fixture::<T>(fixture_name, "wasm")
+}
+
+/// Load a given PolkaVM program and returns the program blob along with it's hash.
+#[cfg(feature = "riscv")]
+pub fn compile_riscv_module<T>(
+	fixture_name: &str,
+) -> anyhow::Result<(Vec<u8>, <T::Hashing as Hash>::Output)>
+where
+	T: frame_system::Config,
+{
+	load_fixture::<T>(fixture_name, "polkavm")
+}
+
+fn load_fixture<T>(
+	fixture_name: &str,
+	extension: &str,
+) -> anyhow::Result<(Vec<u8>, <T::Hashing as Hash>::Output)>
 where
 	T: frame_system::Config,
 {
 	let out_dir: PathBuf = env!("OUT_DIR").into();
-	let fixture_path = out_dir.join(format!("{fixture_name}.wasm"));
+	let fixture_path = out_dir.join(format!("{fixture_name}.{extension}"));
 	let binary = fs::read(fixture_path)?;
 	let code_hash = T::Hashing::hash(&binary);
 	Ok((binary, code_hash))
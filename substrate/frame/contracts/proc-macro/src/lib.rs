@@ -323,6 +323,17 @@ impl HostFn {
 	fn module(&self) -> String {
 		format!("seal{}", self.version)
 	}
+
+	/// The symbol under which a PolkaVM contract imports this function.
+	///
+	/// PolkaVM has no notion of modules which is why the version is encoded into the name.
+	fn polkavm_symbol(&self) -> String {
+		if self.version == 0 {
+			self.name.clone()
+		} else {
+			format!("{}_v{}", self.name, self.version)
+		}
+	}
 }
 
 impl EnvDef {
@@ -493,9 +504,11 @@ fn expand_docs(def: &EnvDef) -> TokenStream2 {
 /// Expands environment definition.
 /// Should generate source code for:
 ///  - implementations of the host functions to be added to the wasm runtime environment (see
-///    `expand_impls()`).
+///    `expand_impls()`);
+///  - dispatch of the host functions called by PolkaVM contracts (see `expand_polkavm()`).
 fn expand_env(def: &EnvDef, docs: bool) -> TokenStream2 {
 	let impls = expand_impls(def);
+	let polkavm = expand_polkavm(def);
 	let docs = docs.then_some(expand_docs(def)).unwrap_or(TokenStream2::new());
 	let stable_api_count = def.host_funcs.iter().filter(|f| f.is_stable).count();
 
@@ -506,6 +519,7 @@ fn expand_env(def: &EnvDef, docs: bool) -> TokenStream2 {
 		pub const STABLE_API_COUNT: usize = #stable_api_count;
 
 		#impls
+		#polkavm
 		/// Documentation of the API (host functions) available to contracts.
 		///
 		/// The `Current` trait might be the most useful doc to look at. The versioned
@@ -560,6 +574,156 @@ fn expand_impls(def: &EnvDef) -> TokenStream2 {
 	}
 }
 
+/// Generates the entry points used by the PolkaVM engine:
+///   - `Env::handle_ecall` which dispatches a host function call by its symbol;
+///   - `Env::polkavm_import_allowed` which is used to validate the imports of a program.
+///
+/// Arguments are passed in registers `a0` to `a5`, one register per `u32` and two registers
+/// (low word first) per `u64`. Functions which need more than six registers receive a pointer to
+/// all their arguments packed as little endian words in `a0` instead.
+fn expand_polkavm(def: &EnvDef) -> TokenStream2 {
+	let is_u64 = |arg: &FnArg| match arg {
+		FnArg::Typed(pat) => match &*pat.ty {
+			syn::Type::Path(tp) => tp.path.is_ident("u64"),
+			_ => false,
+		},
+		_ => false,
+	};
+
+	let arms = def.host_funcs.iter().map(|f| {
+		let params = f.item.sig.inputs.iter().skip(2);
+		let (module, name, body, output) = (f.module(), &f.name, &f.item.block, &f.item.sig.output);
+		let symbol = syn::LitByteStr::new(f.polkavm_symbol().as_bytes(), Span::call_site());
+		let is_stable = f.is_stable;
+		let not_deprecated = f.not_deprecated;
+
+		let mut word = 0usize;
+		let bindings = params
+			.clone()
+			.filter_map(|arg| {
+				let FnArg::Typed(p) = arg else { return None };
+				let (pat, ty) = (&p.pat, &p.ty);
+				let binding = if is_u64(arg) {
+					let (lo, hi) = (word, word + 1);
+					word += 2;
+					quote! { let #pat: #ty = (__words__[#lo] as u64) | ((__words__[#hi] as u64) << 32); }
+				} else {
+					let idx = word;
+					word += 1;
+					quote! { let #pat = __words__[#idx] as #ty; }
+				};
+				Some(binding)
+			})
+			.collect::<Vec<_>>();
+		let num_words = word;
+		let read_words = if num_words <= 6 {
+			quote! { let __words__ = __regs__; }
+		} else {
+			quote! {
+				let mut __buf__ = [0u8; #num_words * 4];
+				crate::wasm::Memory::<E::T>::read_into_buf(&*memory, __regs__[0], &mut __buf__)?;
+				let __words__: [u32; #num_words] = ::core::array::from_fn(|i| {
+					let mut word = [0u8; 4];
+					word.copy_from_slice(&__buf__[i * 4..i * 4 + 4]);
+					u32::from_le_bytes(word)
+				});
+			}
+		};
+
+		let trace_fmt_args = params.clone().filter_map(|arg| match arg {
+			syn::FnArg::Receiver(_) => None,
+			syn::FnArg::Typed(p) => match *p.pat.clone() {
+				syn::Pat::Ident(ref pat_ident) => Some(pat_ident.ident.clone()),
+				_ => None,
+			},
+		});
+		let params_fmt_str = trace_fmt_args
+			.clone()
+			.map(|s| format!("{s}: {{:?}}"))
+			.collect::<Vec<_>>()
+			.join(", ");
+		let trace_fmt_str = format!("{}::{}({}) = {{:?}}\n", module, name, params_fmt_str);
+
+		let into_output = match f.returns {
+			HostFnReturn::Unit => quote! { |_| None },
+			HostFnReturn::U32 => quote! { |ret: u32| Some(ret as u64) },
+			HostFnReturn::U64 => quote! { |ret: u64| Some(ret) },
+			HostFnReturn::ReturnCode =>
+				quote! { |ret| Some(::core::convert::Into::<u32>::into(ret) as u64) },
+		};
+
+		quote! {
+			#symbol if ::core::cfg!(feature = "runtime-benchmarks") ||
+				((#is_stable || __allow_unstable__) && (#not_deprecated || __allow_deprecated__)) =>
+			{
+				#read_words
+				#( #bindings )*
+				let mut func = || #output {
+					let result = #body;
+					if ::log::log_enabled!(target: "runtime::contracts::strace", ::log::Level::Trace) {
+						use sp_std::fmt::Write;
+						let mut w = sp_std::Writer::default();
+						let _ = core::write!(&mut w, #trace_fmt_str, #( #trace_fmt_args, )* result);
+						let msg = core::str::from_utf8(&w.inner()).unwrap_or_default();
+						ctx.ext().append_debug_buffer(msg);
+					}
+					result
+				};
+				func().map(#into_output)
+			},
+		}
+	});
+
+	let allowed = def.host_funcs.iter().map(|f| {
+		let symbol = syn::LitByteStr::new(f.polkavm_symbol().as_bytes(), Span::call_site());
+		let is_stable = f.is_stable;
+		let not_deprecated = f.not_deprecated;
+		quote! {
+			#symbol => ::core::cfg!(feature = "runtime-benchmarks") ||
+				((#is_stable || __allow_unstable__) && (#not_deprecated || __allow_deprecated__)),
+		}
+	});
+
+	quote! {
+		impl Env {
+			/// Execute the host function a PolkaVM contract imported as `symbol`.
+			///
+			/// `__regs__` contains the argument registers `a0` to `a5`. Returns the value to be
+			/// written to the return registers or `None` if the function doesn't return a value.
+			#[allow(unused_variables, unused_mut)]
+			pub fn handle_ecall<'a, E: Ext>(
+				ctx: &mut crate::wasm::Runtime<'a, E>,
+				memory: &mut ::polkavm::RawInstance,
+				symbol: &[u8],
+				__regs__: [u32; 6],
+				allow_unstable: AllowUnstableInterface,
+				allow_deprecated: AllowDeprecatedInterface,
+			) -> Result<Option<u64>, TrapReason> {
+				let __allow_unstable__ = matches!(allow_unstable, AllowUnstableInterface::Yes);
+				let __allow_deprecated__ = matches!(allow_deprecated, AllowDeprecatedInterface::Yes);
+				match symbol {
+					#( #arms )*
+					_ => Err(Error::<E::T>::CodeRejected.into()),
+				}
+			}
+
+			/// Returns whether a PolkaVM contract is allowed to import `symbol`.
+			pub fn polkavm_import_allowed(
+				symbol: &[u8],
+				allow_unstable: AllowUnstableInterface,
+				allow_deprecated: AllowDeprecatedInterface,
+			) -> bool {
+				let __allow_unstable__ = matches!(allow_unstable, AllowUnstableInterface::Yes);
+				let __allow_deprecated__ = matches!(allow_deprecated, AllowDeprecatedInterface::Yes);
+				match symbol {
+					#( #allowed )*
+					_ => false,
+				}
+			}
+		}
+	}
+}
+
 fn expand_functions(def: &EnvDef, expand_blocks: bool, host_state: TokenStream2) -> TokenStream2 {
 	let impls = def.host_funcs.iter().map(|f| {
 		// skip the context and memory argument
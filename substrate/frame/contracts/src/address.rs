@@ -19,8 +19,13 @@
 
 use crate::{CodeHash, Config};
 use codec::{Decode, Encode};
+use sp_core::H160;
+use sp_io::hashing::keccak_256;
 use sp_runtime::traits::{Hash, TrailingZeroInput};
 
+/// The suffix which marks an account as derived from an Ethereum address.
+const ETH_SUFFIX: [u8; 12] = [0xEE; 12];
+
 /// Provides the contract address generation method.
 ///
 /// See [`DefaultAddressGenerator`] for the default implementation.
@@ -66,3 +71,46 @@ impl<T: Config> AddressGenerator<T> for DefaultAddressGenerator {
 			.expect("infinite length input; no invalid inputs for type; qed")
 	}
 }
+
+/// Maps between the accounts of the runtime and 20 byte Ethereum addresses.
+///
+/// See [`DefaultAddressMapper`] for the default implementation.
+///
+/// # Note for implementors
+///
+/// [`Self::to_account_id`] is only required to be the inverse of [`Self::to_address`] for
+/// accounts which were themselves derived from an Ethereum address. The addresses of all other
+/// accounts (e.g contracts) are resolved by the pallet which keeps a lookup table for them.
+pub trait AddressMapper<AccountId> {
+	/// The Ethereum address of `account_id`.
+	fn to_address(account_id: &AccountId) -> H160;
+
+	/// The account that is controlled by the key behind the Ethereum `address`.
+	fn to_account_id(address: &H160) -> AccountId;
+}
+
+/// Default address mapper.
+///
+/// An Ethereum address is mapped to an account by appending twelve `0xEE` bytes to it. Those
+/// accounts are mapped back by stripping the suffix. Any other account is mapped to the last
+/// 20 bytes of the keccak hash of its encoding, like Ethereum derives addresses from public keys.
+/// Formula:
+/// `address ++ [0xEE; 12]` and `keccak_256(account_id)[12..]`
+pub struct DefaultAddressMapper;
+
+impl<AccountId: Encode + Decode> AddressMapper<AccountId> for DefaultAddressMapper {
+	fn to_address(account_id: &AccountId) -> H160 {
+		let encoded = account_id.encode();
+		if encoded.len() == 32 && encoded[20..] == ETH_SUFFIX {
+			H160::from_slice(&encoded[..20])
+		} else {
+			H160::from_slice(&keccak_256(&encoded)[12..])
+		}
+	}
+
+	fn to_account_id(address: &H160) -> AccountId {
+		let entropy = (address, ETH_SUFFIX).encode();
+		Decode::decode(&mut TrailingZeroInput::new(&entropy))
+			.expect("infinite length input; no invalid inputs for type; qed")
+	}
+}
@@ -32,7 +32,8 @@ use self::{
 use crate::{
 	exec::Key,
 	migration::{
-		codegen::LATEST_MIGRATION_VERSION, v09, v10, v11, v12, v13, v14, v15, v16, MigrationStep,
+		codegen::LATEST_MIGRATION_VERSION, v09, v10, v11, v12, v13, v14, v15, v16, v17, v18,
+		MigrationStep,
 	},
 	Pallet as Contracts, *,
 };
@@ -351,6 +352,38 @@ mod benchmarks {
 		Ok(())
 	}
 
+	// This benchmarks the v17 migration step (Add code type to `CodeInfo`).
+	#[benchmark(pov_mode = Measured)]
+	fn v17_migration_step() {
+		let account = account::<T::AccountId>("account", 0, 0);
+		let hash = v17::store_old_dummy_code::<T>(account);
+		let mut m = v17::Migration::<T>::default();
+
+		#[block]
+		{
+			m.step(&mut WeightMeter::new());
+		}
+		let info = v17::CodeInfoOf::<T>::get(hash).unwrap();
+		assert_eq!(info.code_type, CodeType::Wasm);
+	}
+
+	// This benchmarks the v18 migration step (Map the Ethereum address of contracts).
+	#[benchmark(pov_mode = Measured)]
+	fn v18_migration_step() -> Result<(), BenchmarkError> {
+		let contract =
+			<Contract<T>>::with_caller(whitelisted_caller(), WasmModule::dummy(), vec![])?;
+		let address = Contracts::<T>::address_of(&contract.account_id);
+		ContractOfAddress::<T>::remove(&address);
+		let mut m = v18::Migration::<T>::default();
+
+		#[block]
+		{
+			m.step(&mut WeightMeter::new());
+		}
+		assert_eq!(ContractOfAddress::<T>::get(&address), Some(contract.account_id));
+		Ok(())
+	}
+
 	// This benchmarks the weight of executing Migration::migrate to execute a noop migration.
 	#[benchmark(pov_mode = Measured)]
 	fn migration_noop() {
@@ -550,6 +583,118 @@ mod benchmarks {
 		Ok(())
 	}
 
+	// We call a dummy contract through a signed Ethereum transaction to measure the overhead of
+	// decoding the transaction and recovering its signer.
+	// `c`: Size of the input data in bytes.
+	#[benchmark(pov_mode = Measured)]
+	fn eth_transact(c: Linear<0, { T::MaxCodeLen::get() }>) -> Result<(), BenchmarkError> {
+		let instance =
+			Contract::<T>::with_caller(whitelisted_caller(), WasmModule::dummy(), vec![])?;
+		let key_type = sp_core::crypto::KeyTypeId(*b"code");
+		let pub_key = sp_io::crypto::ecdsa_generate(key_type, None);
+		let tx = eth::Transaction {
+			tx_type: eth::TransactionType::DynamicFee,
+			chain_id: T::ChainId::get(),
+			nonce: 0.into(),
+			max_priority_fee_per_gas: 0.into(),
+			gas_price: Contracts::<T>::balance_to_u256(Contracts::<T>::eth_gas_price()),
+			gas: Contracts::<T>::weight_to_eth_gas(T::BlockWeights::get().max_block).into(),
+			to: Some(Contracts::<T>::address_of(&instance.account_id)),
+			value: 0.into(),
+			data: vec![42u8; c as usize],
+			access_list: rlp::EMPTY_LIST_RLP.to_vec(),
+		};
+		let sig = sp_io::crypto::ecdsa_sign_prehashed(key_type, &pub_key, &tx.signing_hash().0)
+			.expect("Generates signature");
+		let payload = tx.encode_signed(AsRef::<[u8; 65]>::as_ref(&sig));
+		let signer = Contracts::<T>::eth_check_transaction(&payload)
+			.map_err(|_| BenchmarkError::Stop("Invalid transaction"))?;
+		let origin = T::AddressMapper::to_account_id(&signer);
+		T::Currency::set_balance(&origin, caller_funding::<T>());
+
+		#[extrinsic_call]
+		_(RawOrigin::None, payload);
+
+		assert_eq!(frame_system::Pallet::<T>::account_nonce(&origin), 1u32.into());
+		Ok(())
+	}
+
+	// The same as `call` but the deposit is paid by the sponsorship account of the contract.
+	// This requires `Config::SponsorshipPolicy` to sponsor calls without value.
+	#[benchmark(pov_mode = Measured)]
+	fn call_sponsored() -> Result<(), BenchmarkError> {
+		let data = vec![42u8; 1024];
+		let instance =
+			Contract::<T>::with_caller(whitelisted_caller(), WasmModule::dummy(), vec![])?;
+		let sponsor = Contracts::<T>::sponsorship_account(&instance.account_id);
+		T::Currency::set_balance(&sponsor, caller_funding::<T>());
+		<SponsorshipOf<T>>::insert(
+			&instance.account_id,
+			Sponsorship {
+				max_fee: caller_funding::<T>(),
+				max_deposit: caller_funding::<T>(),
+				max_calls: 1,
+				period: Zero::zero(),
+			},
+		);
+		let origin = RawOrigin::Signed(instance.caller.clone());
+		let callee = instance.addr.clone();
+		let before = T::Currency::balance(&instance.caller);
+		let gas_limit = T::BlockWeights::get().max_block;
+		#[extrinsic_call]
+		_(origin, callee, 0u32.into(), gas_limit, None, data);
+		// the sponsor paid for everything
+		assert_eq!(T::Currency::balance(&instance.caller), before);
+		assert_eq!(
+			<SponsoredCallsOf<T>>::get(&instance.account_id, &instance.caller).map(|(_, c)| c),
+			Some(1),
+		);
+		Ok(())
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn set_sponsorship() -> Result<(), BenchmarkError> {
+		let instance =
+			Contract::<T>::with_caller(whitelisted_caller(), WasmModule::dummy(), vec![])?;
+		let sponsorship = Sponsorship {
+			max_fee: 0u32.into(),
+			max_deposit: 0u32.into(),
+			max_calls: 0,
+			period: Zero::zero(),
+		};
+		<SponsorshipOf<T>>::insert(&instance.account_id, sponsorship);
+		let sponsor = Contracts::<T>::sponsorship_account(&instance.account_id);
+		T::Currency::set_balance(&sponsor, caller_funding::<T>());
+		let origin = RawOrigin::Signed(instance.account_id.clone());
+		// removing the sponsorship is the more expensive path as it refunds the contract
+		#[extrinsic_call]
+		_(origin, None);
+		assert!(!<SponsorshipOf<T>>::contains_key(&instance.account_id));
+		assert_eq!(T::Currency::balance(&sponsor), 0u32.into());
+		Ok(())
+	}
+
+	#[benchmark(pov_mode = Measured)]
+	fn fund_sponsorship() -> Result<(), BenchmarkError> {
+		let instance =
+			Contract::<T>::with_caller(whitelisted_caller(), WasmModule::dummy(), vec![])?;
+		let sponsorship = Sponsorship {
+			max_fee: 0u32.into(),
+			max_deposit: 0u32.into(),
+			max_calls: 0,
+			period: Zero::zero(),
+		};
+		<SponsorshipOf<T>>::insert(&instance.account_id, sponsorship);
+		let amount = caller_funding::<T>() / 2u32.into();
+		let origin = RawOrigin::Signed(instance.caller.clone());
+		let contract = instance.addr.clone();
+		#[extrinsic_call]
+		_(origin, contract, amount);
+		let sponsor = Contracts::<T>::sponsorship_account(&instance.account_id);
+		assert_eq!(T::Currency::balance(&sponsor), amount);
+		Ok(())
+	}
+
 	// This constructs a contract that is maximal expensive to instrument.
 	// It creates a maximum number of metering blocks per byte.
 	// `c`: Size of the code in bytes.
@@ -2246,15 +2391,15 @@ mod benchmarks {
 					Regular(Instruction::I64Const(0)),              // proof_size weight
 					Regular(Instruction::I32Const(SENTINEL as i32)), /* deposit limit ptr: use
 					                                                 * parent's limit */
-					Regular(Instruction::I32Const(0)),                      // value_ptr
-					Regular(Instruction::I32Const(0)),                      // input_data_ptr
-					Regular(Instruction::I32Const(0)),                      // input_data_len
-					Regular(Instruction::I32Const(addr_offset as i32)),     // address_ptr
+					Regular(Instruction::I32Const(0)), // value_ptr
+					Regular(Instruction::I32Const(0)), // input_data_ptr
+					Regular(Instruction::I32Const(0)), // input_data_len
+					Regular(Instruction::I32Const(addr_offset as i32)), // address_ptr
 					Regular(Instruction::I32Const(addr_len_offset as i32)), // address_len_ptr
-					Regular(Instruction::I32Const(SENTINEL as i32)),        // output_ptr
-					Regular(Instruction::I32Const(0)),                      // output_len_ptr
-					Regular(Instruction::I32Const(0)),                      // salt_ptr
-					Regular(Instruction::I32Const(0)),                      // salt_len_ptr
+					Regular(Instruction::I32Const(SENTINEL as i32)), // output_ptr
+					Regular(Instruction::I32Const(0)), // output_len_ptr
+					Regular(Instruction::I32Const(0)), // salt_ptr
+					Regular(Instruction::I32Const(0)), // salt_len_ptr
 					Regular(Instruction::Call(0)),
 					Regular(Instruction::Drop),
 				],
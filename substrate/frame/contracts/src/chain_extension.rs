@@ -71,7 +71,7 @@
 //! on how to use a chain extension in order to provide new features to ink! contracts.
 
 use crate::{
-	wasm::{Runtime, RuntimeCosts},
+	wasm::{Memory, Runtime, RuntimeCosts},
 	Error,
 };
 use codec::{Decode, MaxEncodedLen};
@@ -258,7 +258,7 @@ impl<'a, 'b, E: Ext> Environment<'a, 'b, E, InitState> {
 	/// ever create this type. Chain extensions merely consume it.
 	pub(crate) fn new(
 		runtime: &'a mut Runtime<'b, E>,
-		memory: &'a mut [u8],
+		memory: &'a mut dyn Memory<E::T>,
 		id: u32,
 		input_ptr: u32,
 		input_len: u32,
@@ -422,7 +422,7 @@ struct Inner<'a, 'b, E: Ext> {
 	/// The runtime contains all necessary functions to interact with the running contract.
 	runtime: &'a mut Runtime<'b, E>,
 	/// Reference to the contracts memory.
-	memory: &'a mut [u8],
+	memory: &'a mut dyn Memory<E::T>,
 	/// Verbatim argument passed to `seal_call_chain_extension`.
 	id: u32,
 	/// Verbatim argument passed to `seal_call_chain_extension`.
@@ -15,11 +15,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod call_tracer;
+
 pub use crate::{
 	exec::{ExecResult, ExportedFunction},
 	primitives::ExecReturnValue,
 };
 use crate::{Config, LOG_TARGET};
+pub use call_tracer::{trace, CallKind, CallTrace, CallTraceOf, StorageWrite, TracedEvent};
 
 /// Umbrella trait for all interfaces that serves for debugging.
 pub trait Debugger<T: Config>: Tracing<T> + CallInterceptor<T> {}
@@ -0,0 +1,236 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A built-in tracer that records the call tree of contract executions.
+//!
+//! Unlike the [`Tracing`](super::Tracing) hooks which are configured per runtime, this tracer
+//! is enabled on demand by wrapping the code to trace into [`trace`]. It is meant to be used
+//! off-chain only, e.g. by the `ContractsApi::trace_extrinsic` runtime API which replays an
+//! extrinsic of a past block.
+
+use crate::{exec::ExecResult, AccountIdOf, BalanceOf, Config};
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch_context::{run_in_context, with_context},
+	weights::Weight,
+};
+use frame_system::pallet_prelude::HeaderFor;
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Header, Digest, DigestItem, DispatchError, RuntimeDebug};
+use sp_std::{mem, prelude::*};
+
+/// How a contract was entered.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum CallKind {
+	/// A regular call.
+	Call,
+	/// A delegate call. The code is executed in the context of the caller.
+	DelegateCall,
+	/// The constructor of a new contract.
+	Instantiate,
+}
+
+/// A write to the storage of a contract.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct StorageWrite {
+	/// The key as supplied by the contract.
+	pub key: Vec<u8>,
+	/// The value before the write.
+	pub old_value: Option<Vec<u8>>,
+	/// The value after the write. `None` if the item was removed.
+	pub new_value: Option<Vec<u8>>,
+}
+
+/// An event emitted by a contract.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct TracedEvent<Hash> {
+	/// The topics supplied by the contract.
+	pub topics: Vec<Hash>,
+	/// The data supplied by the contract.
+	pub data: Vec<u8>,
+}
+
+/// The trace of a single contract execution including all the calls it made.
+///
+/// Changes recorded for a call which reverted or failed (and the calls nested into it) were
+/// rolled back.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct CallTrace<AccountId, Balance, Hash> {
+	/// How the contract was entered.
+	pub kind: CallKind,
+	/// The caller. `None` if the caller is root.
+	pub from: Option<AccountId>,
+	/// The contract that was executed. For delegate calls this is the contract whose storage is
+	/// used.
+	pub to: AccountId,
+	/// The code that was executed.
+	pub code_hash: Hash,
+	/// The balance transferred to `to`.
+	pub value: Balance,
+	/// The input passed to the contract.
+	pub input: Vec<u8>,
+	/// The data returned by the contract. Empty if the execution failed.
+	pub output: Vec<u8>,
+	/// The gas consumed by the contract including all nested calls.
+	pub gas_used: Weight,
+	/// Whether the contract reverted its execution.
+	pub reverted: bool,
+	/// The error that caused the execution to fail.
+	pub error: Option<DispatchError>,
+	/// All writes to the storage of `to` in the order they were made.
+	pub storage_writes: Vec<StorageWrite>,
+	/// All events emitted by the contract in the order they were emitted.
+	pub events: Vec<TracedEvent<Hash>>,
+	/// The calls made by the contract in the order they were made.
+	pub calls: Vec<Self>,
+}
+
+/// The [`CallTrace`] of a runtime.
+pub type CallTraceOf<T> =
+	CallTrace<AccountIdOf<T>, BalanceOf<T>, <T as frame_system::Config>::Hash>;
+
+/// The state of the tracer while it is active.
+struct CallTracer<T: Config> {
+	/// The calls which are currently executing. The last one is the innermost.
+	stack: Vec<CallTraceOf<T>>,
+	/// The finished top level calls.
+	traces: Vec<CallTraceOf<T>>,
+}
+
+impl<T: Config> Default for CallTracer<T> {
+	fn default() -> Self {
+		Self { stack: Vec::new(), traces: Vec::new() }
+	}
+}
+
+/// Runs `f` with the tracer enabled.
+///
+/// Returns the traces of all contract executions started by `f` next to its result.
+pub fn trace<T: Config, R>(f: impl FnOnce() -> R) -> (R, Vec<CallTraceOf<T>>) {
+	run_in_context(|| {
+		with_context::<CallTracer<T>, _>(|tracer| tracer.set(Default::default()));
+		let result = f();
+		let traces = with_context::<CallTracer<T>, _>(|tracer| {
+			let traces = tracer.get_mut().map(|t| mem::take(&mut t.traces));
+			tracer.clear();
+			traces
+		})
+		.flatten()
+		.unwrap_or_default();
+		(result, traces)
+	})
+}
+
+/// Builds the header to initialize the replay of the block with `header`.
+///
+/// The number, the parent hash and the pre-runtime digests are kept. The latter are read by the
+/// consensus pallets while the block is initialized, e.g. the slot of BABE or Aura. Everything
+/// else is recomputed while the block is executed.
+pub fn replay_header<T: Config>(header: &HeaderFor<T>) -> HeaderFor<T> {
+	let logs = header
+		.digest()
+		.logs()
+		.iter()
+		.filter(|item| matches!(item, DigestItem::PreRuntime(..)))
+		.cloned()
+		.collect();
+	HeaderFor::<T>::new(
+		*header.number(),
+		Default::default(),
+		Default::default(),
+		*header.parent_hash(),
+		Digest { logs },
+	)
+}
+
+/// Applies the `extrinsics` of a block in order and traces the one at `index`.
+///
+/// The block needs to be initialized already. Returns `None` if there is no extrinsic at
+/// `index`.
+pub fn trace_extrinsic<T: Config, Xt>(
+	extrinsics: Vec<Xt>,
+	index: u32,
+	mut apply: impl FnMut(Xt),
+) -> Option<Vec<CallTraceOf<T>>> {
+	for (i, extrinsic) in extrinsics.into_iter().enumerate() {
+		if i == index as usize {
+			return Some(trace::<T, _>(|| apply(extrinsic)).1)
+		}
+		apply(extrinsic);
+	}
+	None
+}
+
+/// Calls `f` with the tracer if it is active.
+fn with_tracer<T: Config>(f: impl FnOnce(&mut CallTracer<T>)) {
+	with_context::<CallTracer<T>, _>(|tracer| {
+		if let Some(tracer) = tracer.get_mut() {
+			f(tracer)
+		}
+	});
+}
+
+/// Whether the tracer is active.
+///
+/// Used to avoid work which is only needed for the trace.
+pub(crate) fn is_active<T: Config>() -> bool {
+	with_context::<CallTracer<T>, _>(|tracer| tracer.get().is_some()).unwrap_or(false)
+}
+
+/// Records that a contract is about to be executed.
+///
+/// `call` is only constructed when the tracer is active.
+pub(crate) fn enter<T: Config>(call: impl FnOnce() -> CallTraceOf<T>) {
+	with_tracer::<T>(|tracer| tracer.stack.push(call()));
+}
+
+/// Records the outcome of the innermost contract execution.
+pub(crate) fn exit<T: Config>(output: &ExecResult, gas_used: Weight) {
+	with_tracer::<T>(|tracer| {
+		let Some(mut call) = tracer.stack.pop() else { return };
+		call.gas_used = gas_used;
+		match output {
+			Ok(output) => {
+				call.output = output.data.clone();
+				call.reverted = output.did_revert();
+			},
+			Err(err) => call.error = Some(err.error),
+		}
+		match tracer.stack.last_mut() {
+			Some(parent) => parent.calls.push(call),
+			None => tracer.traces.push(call),
+		}
+	});
+}
+
+/// Records a write to the storage of the contract that is currently executing.
+pub(crate) fn storage_written<T: Config>(write: StorageWrite) {
+	with_tracer::<T>(|tracer| {
+		if let Some(call) = tracer.stack.last_mut() {
+			call.storage_writes.push(write);
+		}
+	});
+}
+
+/// Records an event emitted by the contract that is currently executing.
+pub(crate) fn event_deposited<T: Config>(event: impl FnOnce() -> TracedEvent<T::Hash>) {
+	with_tracer::<T>(|tracer| {
+		if let Some(call) = tracer.stack.last_mut() {
+			call.events.push(event());
+		}
+	});
+}
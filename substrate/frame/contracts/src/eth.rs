@@ -0,0 +1,468 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Types to interact with contracts through signed Ethereum transactions.
+//!
+//! A raw transaction as submitted via `eth_sendRawTransaction` is decoded into a
+//! [`SignedTransaction`]. It is then executed by [`crate::Pallet::eth_transact`] as a contract
+//! call or instantiation on behalf of the account that signed it. The remaining types are
+//! returned by the [`crate::EthApi`] which is used by the Ethereum RPC compatibility layer.
+//!
+//! Legacy ([EIP-155](https://eips.ethereum.org/EIPS/eip-155)), access list
+//! ([EIP-2930](https://eips.ethereum.org/EIPS/eip-2930)) and dynamic fee
+//! ([EIP-1559](https://eips.ethereum.org/EIPS/eip-1559)) transactions are supported. Legacy
+//! transactions need to be replay protected by including the chain id.
+
+use crate::primitives::ExecReturnValue;
+use codec::{Decode, Encode};
+use rlp::{DecoderError, Rlp, RlpStream};
+use scale_info::TypeInfo;
+use sp_core::{H160, H256, U256};
+use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
+use sp_runtime::{DispatchError, RuntimeDebug};
+use sp_std::prelude::*;
+
+/// Half of the order of the secp256k1 curve.
+///
+/// Signatures with a larger `s` value are rejected as they are malleable (EIP-2).
+const SECP256K1_HALF_N: [u8; 32] = [
+	0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+	0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+/// The reasons why an Ethereum transaction can be rejected before it is executed.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum EthTransactError {
+	/// The payload is not a valid RLP encoded transaction.
+	Decode,
+	/// The transaction type is not supported or a legacy transaction lacks replay protection.
+	UnsupportedType,
+	/// The signature is malformed or the signer can't be recovered from it.
+	BadSignature,
+	/// The transaction was signed for another chain.
+	WrongChainId,
+	/// A numeric field doesn't fit into the type used by the runtime.
+	Overflow,
+}
+
+impl From<DecoderError> for EthTransactError {
+	fn from(_: DecoderError) -> Self {
+		Self::Decode
+	}
+}
+
+/// The envelope of an Ethereum transaction.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum TransactionType {
+	/// A legacy transaction which is replay protected as described by EIP-155.
+	Legacy,
+	/// An EIP-2930 transaction.
+	AccessList,
+	/// An EIP-1559 transaction.
+	DynamicFee,
+}
+
+impl TransactionType {
+	/// The prefix of the encoding of typed transactions.
+	fn prefix(&self) -> Option<u8> {
+		match self {
+			Self::Legacy => None,
+			Self::AccessList => Some(1),
+			Self::DynamicFee => Some(2),
+		}
+	}
+}
+
+/// The fields of an Ethereum transaction that are covered by its signature.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Transaction {
+	/// The envelope of the transaction.
+	pub tx_type: TransactionType,
+	/// The chain the transaction is valid on.
+	pub chain_id: u64,
+	/// The nonce of the signer.
+	pub nonce: U256,
+	/// The tip for the block author. Only set for [`TransactionType::DynamicFee`].
+	pub max_priority_fee_per_gas: U256,
+	/// The maximum price the signer is willing to pay for each unit of gas.
+	pub gas_price: U256,
+	/// The maximum amount of gas the transaction is allowed to consume.
+	pub gas: U256,
+	/// The contract to call or `None` to instantiate a new one.
+	pub to: Option<H160>,
+	/// The balance transferred to `to` or the new contract.
+	pub value: U256,
+	/// The input data of the call or the instantiation.
+	///
+	/// Contracts are not EVM byte code. Hence when instantiating, the data is interpreted as
+	/// the SCALE encoded [`crate::Code`] followed by the input of the constructor.
+	pub data: Vec<u8>,
+	/// The RLP encoded access list. It has no effect but is covered by the signature.
+	pub access_list: Vec<u8>,
+}
+
+impl Transaction {
+	/// The hash that needs to be signed by the sender.
+	pub fn signing_hash(&self) -> H256 {
+		let mut stream = self.stream(0);
+		if self.tx_type == TransactionType::Legacy {
+			stream.append(&self.chain_id);
+			stream.append_empty_data();
+			stream.append_empty_data();
+		}
+		H256(keccak_256(&self.prefixed(stream)))
+	}
+
+	/// The raw transaction as it is submitted via `eth_sendRawTransaction`.
+	///
+	/// The `signature` is expected as `r ++ s ++ v` where `v` is the recovery id (`0` or `1`).
+	pub fn encode_signed(&self, signature: &[u8; 65]) -> Vec<u8> {
+		let mut stream = self.stream(3);
+		match self.tx_type {
+			TransactionType::Legacy =>
+				stream.append(&(u64::from(signature[64]) + 35 + self.chain_id.saturating_mul(2))),
+			_ => stream.append(&signature[64]),
+		};
+		stream.append(&trim_leading_zeros(&signature[..32]));
+		stream.append(&trim_leading_zeros(&signature[32..64]));
+		self.prefixed(stream)
+	}
+
+	/// Starts the RLP list of the transaction with `extra` items reserved for the signature.
+	fn stream(&self, extra: usize) -> RlpStream {
+		let typed = self.tx_type != TransactionType::Legacy;
+		let fields = match self.tx_type {
+			TransactionType::Legacy => 6,
+			TransactionType::AccessList => 8,
+			TransactionType::DynamicFee => 9,
+		};
+		let legacy_chain_id = if typed || extra > 0 { 0 } else { 3 };
+		let mut stream = RlpStream::new_list(fields + extra + legacy_chain_id);
+		if typed {
+			stream.append(&self.chain_id);
+		}
+		append_u256(&mut stream, &self.nonce);
+		if self.tx_type == TransactionType::DynamicFee {
+			append_u256(&mut stream, &self.max_priority_fee_per_gas);
+		}
+		append_u256(&mut stream, &self.gas_price);
+		append_u256(&mut stream, &self.gas);
+		match self.to {
+			Some(to) => stream.append(&to.as_bytes().to_vec()),
+			None => stream.append_empty_data(),
+		};
+		append_u256(&mut stream, &self.value);
+		stream.append(&self.data);
+		if typed {
+			stream.append_raw(&self.access_list, 1);
+		}
+		stream
+	}
+
+	/// Prepends the type byte to the encoding of typed transactions.
+	fn prefixed(&self, stream: RlpStream) -> Vec<u8> {
+		let body = stream.out();
+		let mut out = Vec::with_capacity(body.len() + 1);
+		out.extend(self.tx_type.prefix());
+		out.extend_from_slice(&body);
+		out
+	}
+}
+
+/// An Ethereum transaction together with its signature.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SignedTransaction {
+	/// The signed fields.
+	pub tx: Transaction,
+	/// The signature as `r ++ s ++ v` where `v` is the recovery id.
+	pub signature: [u8; 65],
+	/// The hash of the raw transaction which identifies it to Ethereum tooling.
+	pub hash: H256,
+}
+
+impl SignedTransaction {
+	/// Decodes a raw transaction as it is submitted via `eth_sendRawTransaction`.
+	pub fn decode(payload: &[u8]) -> Result<Self, EthTransactError> {
+		let (tx_type, body) = match payload.first() {
+			Some(1) => (TransactionType::AccessList, &payload[1..]),
+			Some(2) => (TransactionType::DynamicFee, &payload[1..]),
+			Some(byte) if *byte >= 0xc0 => (TransactionType::Legacy, payload),
+			_ => return Err(EthTransactError::UnsupportedType),
+		};
+
+		let rlp = Rlp::new(body);
+		if !rlp.is_list() || rlp.as_raw().len() != body.len() {
+			return Err(EthTransactError::Decode)
+		}
+		let expected_items = match tx_type {
+			TransactionType::Legacy => 9,
+			TransactionType::AccessList => 11,
+			TransactionType::DynamicFee => 12,
+		};
+		if rlp.item_count()? != expected_items {
+			return Err(EthTransactError::Decode)
+		}
+
+		let mut items = rlp.iter();
+		let mut next = || items.next().ok_or(EthTransactError::Decode);
+		let typed = tx_type != TransactionType::Legacy;
+		let chain_id = if typed { Some(next()?.as_val::<u64>()?) } else { None };
+		let nonce = decode_u256(&next()?)?;
+		let max_priority_fee_per_gas = if tx_type == TransactionType::DynamicFee {
+			decode_u256(&next()?)?
+		} else {
+			U256::zero()
+		};
+		let gas_price = decode_u256(&next()?)?;
+		let gas = decode_u256(&next()?)?;
+		let to = match next()?.data()? {
+			[] => None,
+			to if to.len() == 20 => Some(H160::from_slice(to)),
+			_ => return Err(EthTransactError::Decode),
+		};
+		let value = decode_u256(&next()?)?;
+		let data = next()?.data()?.to_vec();
+		let access_list = if typed {
+			let access_list = next()?;
+			if !access_list.is_list() {
+				return Err(EthTransactError::Decode)
+			}
+			access_list.as_raw().to_vec()
+		} else {
+			Vec::new()
+		};
+
+		let v = next()?.as_val::<u64>()?;
+		let (chain_id, recovery_id) = match chain_id {
+			Some(chain_id) if v <= 1 => (chain_id, v as u8),
+			// EIP-155: `v = chain_id * 2 + 35 + recovery_id`
+			None if v >= 35 => ((v - 35) / 2, ((v - 35) % 2) as u8),
+			None => return Err(EthTransactError::UnsupportedType),
+			_ => return Err(EthTransactError::BadSignature),
+		};
+		let mut signature = [0u8; 65];
+		for range in [0..32, 32..64] {
+			let bytes = next()?.data()?;
+			if bytes.len() > 32 {
+				return Err(EthTransactError::BadSignature)
+			}
+			signature[range.end - bytes.len()..range.end].copy_from_slice(bytes);
+		}
+		signature[64] = recovery_id;
+
+		let tx = Transaction {
+			tx_type,
+			chain_id,
+			nonce,
+			max_priority_fee_per_gas,
+			gas_price,
+			gas,
+			to,
+			value,
+			data,
+			access_list,
+		};
+		Ok(Self { tx, signature, hash: H256(keccak_256(payload)) })
+	}
+
+	/// Recovers the address of the account that signed the transaction.
+	pub fn recover_signer(&self) -> Result<H160, EthTransactError> {
+		if self.signature[32..64] > SECP256K1_HALF_N[..] {
+			return Err(EthTransactError::BadSignature)
+		}
+		let public = secp256k1_ecdsa_recover(&self.signature, &self.tx.signing_hash().0)
+			.map_err(|_| EthTransactError::BadSignature)?;
+		Ok(H160::from_slice(&keccak_256(&public)[12..]))
+	}
+}
+
+/// Result type of [`crate::EthApi::call`].
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct EthCallResult {
+	/// The output of the contract or the reason why it failed to execute.
+	pub result: Result<ExecReturnValue, DispatchError>,
+	/// The amount of gas that needs to be supplied for the transaction to succeed.
+	///
+	/// This includes the overhead of decoding and verifying the transaction.
+	pub gas_required: u64,
+	/// The address of the new contract if the call was an instantiation.
+	pub contract_address: Option<H160>,
+}
+
+/// An event emitted by a contract during the execution of an Ethereum transaction.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct Log {
+	/// The contract that emitted the event.
+	pub address: H160,
+	/// The topics the contract supplied when emitting the event.
+	pub topics: Vec<H256>,
+	/// The data the contract supplied when emitting the event.
+	pub data: Vec<u8>,
+}
+
+/// The receipt of an Ethereum transaction that was included in a block.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct Receipt {
+	/// The hash of the raw transaction.
+	pub transaction_hash: H256,
+	/// The index of the extrinsic within its block.
+	pub transaction_index: u32,
+	/// The signer of the transaction.
+	pub from: H160,
+	/// The contract that was called. `None` for instantiations.
+	pub to: Option<H160>,
+	/// The contract that was created. `None` for calls or failed instantiations.
+	pub contract_address: Option<H160>,
+	/// The amount of gas that was charged for the transaction.
+	pub gas_used: u64,
+	/// Whether the contract executed successfully without reverting.
+	pub success: bool,
+	/// The events emitted by contracts during the transaction.
+	pub logs: Vec<Log>,
+}
+
+/// Appends `value` as a big endian integer without leading zeros.
+fn append_u256(stream: &mut RlpStream, value: &U256) {
+	let mut buf = [0u8; 32];
+	value.to_big_endian(&mut buf);
+	stream.append(&trim_leading_zeros(&buf));
+}
+
+fn trim_leading_zeros(bytes: &[u8]) -> Vec<u8> {
+	let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+	bytes[start..].to_vec()
+}
+
+/// Decodes a big endian integer without leading zeros.
+fn decode_u256(rlp: &Rlp) -> Result<U256, EthTransactError> {
+	let bytes = rlp.data()?;
+	if bytes.len() > 32 || bytes.first() == Some(&0) {
+		return Err(EthTransactError::Decode)
+	}
+	Ok(U256::from_big_endian(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::{ecdsa, Pair};
+
+	fn sign(tx: &Transaction, pair: &ecdsa::Pair) -> Vec<u8> {
+		let signature = pair.sign_prehashed(&tx.signing_hash().0);
+		tx.encode_signed(&signature.0)
+	}
+
+	fn address(pair: &ecdsa::Pair) -> H160 {
+		let public =
+			secp256k1_ecdsa_recover(&pair.sign_prehashed(&[0u8; 32]).0, &[0u8; 32]).unwrap();
+		H160::from_slice(&keccak_256(&public)[12..])
+	}
+
+	fn transaction(tx_type: TransactionType) -> Transaction {
+		Transaction {
+			tx_type,
+			chain_id: 42,
+			nonce: 7.into(),
+			max_priority_fee_per_gas: if tx_type == TransactionType::DynamicFee {
+				2.into()
+			} else {
+				U256::zero()
+			},
+			gas_price: 1_000.into(),
+			gas: 21_000.into(),
+			to: Some(H160::repeat_byte(0x11)),
+			value: U256::from(10).pow(18.into()),
+			data: vec![1, 2, 3],
+			access_list: if tx_type == TransactionType::Legacy { vec![] } else { vec![0xc0] },
+		}
+	}
+
+	#[test]
+	fn decode_and_recover_works() {
+		let pair = ecdsa::Pair::from_seed(&[7u8; 32]);
+		for tx_type in
+			[TransactionType::Legacy, TransactionType::AccessList, TransactionType::DynamicFee]
+		{
+			let tx = transaction(tx_type);
+			let payload = sign(&tx, &pair);
+			let signed = SignedTransaction::decode(&payload).unwrap();
+			assert_eq!(signed.tx, tx);
+			assert_eq!(signed.hash, H256(keccak_256(&payload)));
+			assert_eq!(signed.recover_signer(), Ok(address(&pair)));
+		}
+	}
+
+	#[test]
+	fn decode_known_legacy_transaction() {
+		// The example transaction from EIP-155.
+		let payload = array_bytes::hex2bytes_unchecked(
+			"f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+		);
+		let signed = SignedTransaction::decode(&payload).unwrap();
+		assert_eq!(signed.tx.chain_id, 1);
+		assert_eq!(signed.tx.nonce, 9.into());
+		assert_eq!(signed.tx.to, Some(H160::repeat_byte(0x35)));
+		assert_eq!(
+			signed.recover_signer(),
+			Ok(H160(array_bytes::hex2array_unchecked("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f")))
+		);
+	}
+
+	#[test]
+	fn decode_rejects_invalid_payloads() {
+		let pair = ecdsa::Pair::from_seed(&[7u8; 32]);
+		let payload = sign(&transaction(TransactionType::DynamicFee), &pair);
+
+		// Unknown transaction type.
+		let mut unknown = payload.clone();
+		unknown[0] = 3;
+		assert_eq!(SignedTransaction::decode(&unknown), Err(EthTransactError::UnsupportedType));
+
+		// Trailing bytes.
+		let mut trailing = payload.clone();
+		trailing.push(0);
+		assert_eq!(SignedTransaction::decode(&trailing), Err(EthTransactError::Decode));
+
+		// Truncated payload.
+		assert_eq!(
+			SignedTransaction::decode(&payload[..payload.len() - 1]),
+			Err(EthTransactError::Decode)
+		);
+
+		// Legacy transaction without replay protection.
+		let mut stream = RlpStream::new_list(9);
+		for _ in 0..6 {
+			stream.append_empty_data();
+		}
+		stream.append(&27u64);
+		stream.append(&vec![1u8]);
+		stream.append(&vec![1u8]);
+		assert_eq!(
+			SignedTransaction::decode(&stream.out()),
+			Err(EthTransactError::UnsupportedType)
+		);
+	}
+
+	#[test]
+	fn tampered_transaction_recovers_other_signer() {
+		let pair = ecdsa::Pair::from_seed(&[7u8; 32]);
+		let mut signed =
+			SignedTransaction::decode(&sign(&transaction(TransactionType::Legacy), &pair)).unwrap();
+		signed.tx.value = 1.into();
+		assert_ne!(signed.recover_signer(), Ok(address(&pair)));
+	}
+}
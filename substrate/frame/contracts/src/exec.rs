@@ -16,13 +16,16 @@
 // limitations under the License.
 
 use crate::{
-	debug::{CallInterceptor, CallSpan, Tracing},
+	debug::{
+		call_tracer::{self, CallKind, CallTrace, StorageWrite, TracedEvent},
+		CallInterceptor, CallSpan, Tracing,
+	},
 	gas::GasMeter,
 	primitives::{ExecReturnValue, StorageDeposit},
 	storage::{self, meter::Diff, WriteOutcome},
 	BalanceOf, CodeHash, CodeInfo, CodeInfoOf, Config, ContractInfo, ContractInfoOf,
-	DebugBufferVec, Determinism, Error, Event, Nonce, Origin, Pallet as Contracts, Schedule,
-	LOG_TARGET,
+	ContractOfAddress, DebugBufferVec, Determinism, Error, Event, Nonce, Origin,
+	Pallet as Contracts, Schedule, LOG_TARGET,
 };
 use frame_support::{
 	crypto::ecdsa::ECDSAExt,
@@ -916,6 +919,25 @@ where
 		let entry_point = frame.entry_point;
 		let delegated_code_hash =
 			if frame.delegate_caller.is_some() { Some(*executable.code_hash()) } else { None };
+		call_tracer::enter::<T>(|| CallTrace {
+			kind: match (entry_point, delegated_code_hash) {
+				(ExportedFunction::Constructor, _) => CallKind::Instantiate,
+				(ExportedFunction::Call, Some(_)) => CallKind::DelegateCall,
+				(ExportedFunction::Call, None) => CallKind::Call,
+			},
+			from: self.caller().account_id().ok().cloned(),
+			to: frame.account_id.clone(),
+			code_hash: *executable.code_hash(),
+			value: frame.value_transferred,
+			input: input_data.clone(),
+			output: Vec::new(),
+			gas_used: Weight::zero(),
+			reverted: false,
+			error: None,
+			storage_writes: Vec::new(),
+			events: Vec::new(),
+			calls: Vec::new(),
+		});
 		let do_transaction = || {
 			// We need to charge the storage deposit before the initial transfer so that
 			// it can create the account in case the initial transfer is < ed.
@@ -982,6 +1004,12 @@ where
 
 					let caller = self.caller().account_id()?.clone();
 
+					// Make the contract reachable by its Ethereum address.
+					ContractOfAddress::<T>::insert(
+						Contracts::<T>::address_of(account_id),
+						account_id.clone(),
+					);
+
 					// Deposit an instantiation event.
 					Contracts::<T>::deposit_event(
 						vec![T::Hashing::hash_of(&caller), T::Hashing::hash_of(account_id)],
@@ -1036,6 +1064,7 @@ where
 			Err(error) => (false, Err(error.into())),
 		};
 
+		call_tracer::exit::<T>(&output, self.top_frame().nested_gas.gas_consumed());
 		self.pop_frame(success);
 		output
 	}
@@ -1315,6 +1344,7 @@ where
 
 		info.queue_trie_for_deletion();
 		ContractInfoOf::<T>::remove(&frame.account_id);
+		ContractOfAddress::<T>::remove(Contracts::<T>::address_of(&frame.account_id));
 		Self::decrement_refcount(info.code_hash);
 
 		for (code_hash, deposit) in info.delegate_dependencies() {
@@ -1353,12 +1383,18 @@ where
 		take_old: bool,
 	) -> Result<WriteOutcome, DispatchError> {
 		let frame = self.top_frame_mut();
-		frame.contract_info.get(&frame.account_id).write(
-			key.into(),
-			value,
-			Some(&mut frame.nested_storage),
-			take_old,
-		)
+		let contract = frame.contract_info.get(&frame.account_id);
+		let trace = call_tracer::is_active::<T>().then(|| StorageWrite {
+			key: key.to_vec(),
+			old_value: contract.read(key),
+			new_value: value.clone(),
+		});
+		let outcome =
+			contract.write(key.into(), value, Some(&mut frame.nested_storage), take_old)?;
+		if let Some(write) = trace {
+			call_tracer::storage_written::<T>(write);
+		}
+		Ok(outcome)
 	}
 
 	fn address(&self) -> &T::AccountId {
@@ -1422,6 +1458,10 @@ where
 	}
 
 	fn deposit_event(&mut self, topics: Vec<T::Hash>, data: Vec<u8>) {
+		call_tracer::event_deposited::<T>(|| TracedEvent {
+			topics: topics.clone(),
+			data: data.clone(),
+		});
 		Contracts::<Self::T>::deposit_event(
 			topics,
 			Event::ContractEmitted { contract: self.top_frame().account_id.clone(), data },
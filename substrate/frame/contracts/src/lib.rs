@@ -95,11 +95,13 @@ mod primitives;
 pub use primitives::*;
 
 mod schedule;
+mod sponsorship;
 mod storage;
 mod wasm;
 
 pub mod chain_extension;
 pub mod debug;
+pub mod eth;
 pub mod migration;
 pub mod test_utils;
 pub mod weights;
@@ -107,6 +109,11 @@ pub mod weights;
 #[cfg(test)]
 mod tests;
 use crate::{
+	debug::CallTrace,
+	eth::{
+		EthCallResult, EthTransactError, Log, Receipt, SignedTransaction, Transaction,
+		TransactionType,
+	},
 	exec::{
 		AccountIdOf, ErrorOrigin, ExecError, Executable, Ext, Key, MomentOf, Stack as ExecStack,
 	},
@@ -122,6 +129,7 @@ use frame_support::{
 	error::BadOrigin,
 	traits::{
 		fungible::{Inspect, Mutate, MutateHold},
+		tokens::{Fortitude, Precision, Preservation},
 		ConstU32, Contains, Get, Randomness, Time,
 	},
 	weights::{Weight, WeightMeter},
@@ -130,24 +138,34 @@ use frame_support::{
 use frame_system::{
 	ensure_signed,
 	pallet_prelude::{BlockNumberFor, OriginFor},
-	EventRecord, Pallet as System,
+	EventRecord, Pallet as System, Phase,
 };
 use scale_info::TypeInfo;
 use smallvec::Array;
+use sp_core::{H160, H256, U256};
 use sp_runtime::{
-	traits::{Convert, Dispatchable, Hash, Saturating, StaticLookup, Zero},
-	DispatchError, RuntimeDebug,
+	traits::{
+		Block as BlockT, Convert, Dispatchable, Hash, Saturating, StaticLookup, TrailingZeroInput,
+		UniqueSaturatedInto, Zero,
+	},
+	transaction_validity::{
+		InvalidTransaction, TransactionLongevity, TransactionValidityError, ValidTransaction,
+	},
+	ArithmeticError, DispatchError, RuntimeDebug,
 };
 use sp_std::{fmt::Debug, prelude::*};
 
 pub use crate::{
-	address::{AddressGenerator, DefaultAddressGenerator},
+	address::{AddressGenerator, AddressMapper, DefaultAddressGenerator, DefaultAddressMapper},
 	debug::Tracing,
 	exec::Frame,
 	migration::{MigrateSequence, Migration, NoopMigration},
 	pallet::*,
 	schedule::{HostFnWeights, InstructionWeights, Limits, Schedule},
-	wasm::Determinism,
+	sponsorship::{
+		ChargeSponsor, CheckSponsoredNonce, SponsorValueFreeCalls, Sponsorship, SponsorshipPolicy,
+	},
+	wasm::{CodeType, Determinism},
 };
 pub use weights::WeightInfo;
 
@@ -170,6 +188,12 @@ type EventRecordOf<T> =
 /// needs to support it indefinitely.
 type OldWeight = u64;
 
+/// The length of the salt used when instantiating contracts from Ethereum transactions.
+const ETH_SALT_LEN: u32 = 32;
+
+/// The number of blocks an Ethereum transaction stays valid in the transaction pool.
+const ETH_TRANSACTION_LONGEVITY: TransactionLongevity = 64;
+
 /// Used as a sentinel value when reading and writing contract memory.
 ///
 /// It is usually used to signal `None` to a contract when only a primitive is allowed
@@ -245,7 +269,7 @@ pub mod pallet {
 	use sp_runtime::Perbill;
 
 	/// The in-code storage version.
-	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(16);
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(18);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -311,7 +335,9 @@ pub mod pallet {
 		type CallFilter: Contains<<Self as frame_system::Config>::RuntimeCall>;
 
 		/// Used to answer contracts' queries regarding the current weight price. This is **not**
-		/// used to calculate the actual fee and is only for informational purposes.
+		/// used to calculate the actual fee of regular extrinsics and is only for informational
+		/// purposes. However, it determines the base gas price of Ethereum transactions submitted
+		/// through [`Pallet::eth_transact`] whose fees are burned by this pallet.
 		#[pallet::no_default_bounds]
 		type WeightPrice: Convert<Weight, BalanceOf<Self>>;
 
@@ -373,6 +399,38 @@ pub mod pallet {
 		#[pallet::no_default_bounds]
 		type AddressGenerator: AddressGenerator<Self>;
 
+		/// Maps accounts to Ethereum addresses and back.
+		///
+		/// This is used to execute Ethereum transactions submitted through
+		/// [`Pallet::eth_transact`] on behalf of the account controlled by their signer.
+		///
+		/// # Note
+		///
+		/// Changing the mapping for an existing chain is not possible without a storage migration
+		/// since the Ethereum addresses of all contracts are stored.
+		#[pallet::no_default_bounds]
+		type AddressMapper: AddressMapper<Self::AccountId>;
+
+		/// The chain id that Ethereum transactions need to be signed for.
+		#[pallet::constant]
+		type ChainId: Get<u64>;
+
+		/// The weight that one unit of Ethereum gas buys.
+		///
+		/// The gas limit of an Ethereum transaction is converted to a [`Weight`] limit by
+		/// multiplying both components with this value. The consumed weight is converted back
+		/// by taking the larger of both quotients. The price of a unit of gas is determined by
+		/// applying [`Self::WeightPrice`] to this value.
+		#[pallet::constant]
+		type WeightPerEthGas: Get<Weight>;
+
+		/// Decides whether the sponsor of a contract pays for a call made via
+		/// [`Pallet::call_sponsored`].
+		///
+		/// Use `()` to disable sponsoring.
+		#[pallet::no_default_bounds]
+		type SponsorshipPolicy: SponsorshipPolicy<Self>;
+
 		/// The maximum length of a contract code in bytes.
 		///
 		/// The value should be chosen carefully taking into the account the overall memory limit
@@ -482,7 +540,7 @@ pub mod pallet {
 		use super::*;
 		use frame_support::{
 			derive_impl,
-			traits::{ConstBool, ConstU32},
+			traits::{ConstBool, ConstU32, ConstU64},
 		};
 		use frame_system::EnsureSigned;
 		use sp_core::parameter_types;
@@ -502,6 +560,7 @@ pub mod pallet {
 			pub const DefaultDepositLimit: Balance = deposit(1024, 1024 * 1024);
 			pub const CodeHashLockupDepositPercent: Perbill = Perbill::from_percent(0);
 			pub const MaxDelegateDependencies: u32 = 32;
+			pub const WeightPerEthGas: Weight = Weight::from_parts(10_000, 1);
 		}
 
 		/// A type providing default configurations for this pallet in testing environment.
@@ -541,7 +600,9 @@ pub mod pallet {
 			type RuntimeCall = ();
 
 			type AddressGenerator = DefaultAddressGenerator;
+			type AddressMapper = DefaultAddressMapper;
 			type CallFilter = ();
+			type ChainId = ConstU64<42>;
 			type ChainExtension = ();
 			type CodeHashLockupDepositPercent = CodeHashLockupDepositPercent;
 			type DefaultDepositLimit = DefaultDepositLimit;
@@ -563,6 +624,8 @@ pub mod pallet {
 			type Environment = ();
 			type ApiVersion = ();
 			type Xcm = ();
+			type WeightPerEthGas = WeightPerEthGas;
+			type SponsorshipPolicy = ();
 		}
 	}
 
@@ -653,7 +716,13 @@ pub mod pallet {
 				"Debug buffer should have minimum size of {} (current setting is {})",
 				MIN_DEBUG_BUF_SIZE,
 				T::MaxDebugBufferLen::get(),
-			)
+			);
+
+			// Both dimensions are divided by it to determine the gas consumed.
+			assert!(
+				T::WeightPerEthGas::get().all_gt(Weight::zero()),
+				"WeightPerEthGas needs to be non zero in both dimensions",
+			);
 		}
 	}
 
@@ -876,6 +945,7 @@ pub mod pallet {
 				data,
 				gas_limit: gas_limit.into(),
 				storage_deposit_limit: storage_deposit_limit.map(Into::into),
+				sponsor: None,
 				debug_message: None,
 			};
 			let dest = T::Lookup::lookup(dest)?;
@@ -958,6 +1028,7 @@ pub mod pallet {
 				data,
 				gas_limit,
 				storage_deposit_limit,
+				sponsor: None,
 				debug_message: None,
 			};
 
@@ -1003,6 +1074,7 @@ pub mod pallet {
 				data,
 				gas_limit,
 				storage_deposit_limit: storage_deposit_limit.map(Into::into),
+				sponsor: None,
 				debug_message: None,
 			};
 			let mut output = InstantiateInput::<T> { code: WasmCode::CodeHash(code_hash), salt }
@@ -1051,6 +1123,301 @@ pub mod pallet {
 				},
 			}
 		}
+
+		/// Executes a signed Ethereum transaction.
+		///
+		/// The `payload` is the raw transaction as it is submitted via `eth_sendRawTransaction`.
+		/// It is executed on behalf of the account that [`Config::AddressMapper`] maps its
+		/// signer to. A transaction with a `to` address calls the contract found at that
+		/// address. Otherwise a new contract is instantiated. See [`eth::Transaction::data`].
+		///
+		/// This is an unsigned extrinsic. Its validity is checked by verifying the signature and
+		/// nonce of the Ethereum transaction and that the signer can pay for it.
+		///
+		/// The fee for the whole gas limit is put on hold before execution. The fee for the
+		/// consumed gas is burned and the rest is released afterwards. A unit of gas costs the
+		/// base price determined by [`Config::WeightPerEthGas`] and [`Config::WeightPrice`] plus
+		/// the priority fee offered by the transaction. The storage deposit is limited by
+		/// [`Config::DefaultDepositLimit`].
+		///
+		/// # Note
+		///
+		/// Like on Ethereum a transaction whose contract fails or reverts is still included.
+		/// The nonce is incremented and the fee is charged. The outcome is reported by
+		/// [`Event::EthTransactionExecuted`].
+		#[pallet::call_index(10)]
+		#[pallet::weight(<Pallet<T>>::eth_transact_weight(payload))]
+		pub fn eth_transact(origin: OriginFor<T>, payload: Vec<u8>) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			let checked = Self::check_eth_transaction(&payload)
+				.map_err(|_| <Error<T>>::InvalidEthTransaction)?;
+			ensure!(
+				checked.nonce == System::<T>::account_nonce(&checked.origin),
+				<Error<T>>::InvalidEthTransaction
+			);
+			let fee_reason: T::RuntimeHoldReason = HoldReason::EthTransactionFee.into();
+			T::Currency::hold(&fee_reason, &checked.origin, checked.max_fee)
+				.map_err(|_| <Error<T>>::InvalidEthTransaction)?;
+			System::<T>::inc_account_nonce(&checked.origin);
+
+			let tx = &checked.signed.tx;
+			let output = Self::eth_execute(
+				checked.origin.clone(),
+				tx,
+				checked.value,
+				checked.gas_limit.saturating_sub(checked.base_weight),
+				T::DefaultDepositLimit::get(),
+			);
+
+			let weight_used = checked.base_weight.saturating_add(output.gas_consumed);
+			let gas_used = Self::weight_to_eth_gas(weight_used);
+			let fee = checked
+				.gas_price
+				.saturating_mul(gas_used.unique_saturated_into())
+				.min(checked.max_fee);
+			let burned = T::Currency::burn_held(
+				&fee_reason,
+				&checked.origin,
+				fee,
+				Precision::BestEffort,
+				Fortitude::Force,
+			)
+			.unwrap_or_default();
+			let _ = T::Currency::release(
+				&fee_reason,
+				&checked.origin,
+				checked.max_fee.saturating_sub(burned),
+				Precision::BestEffort,
+			);
+
+			let success = matches!(&output.result, Ok(retval) if !retval.did_revert());
+			Self::deposit_event(
+				vec![T::Hashing::hash_of(&checked.origin)],
+				Event::EthTransactionExecuted {
+					hash: checked.signed.hash,
+					from: checked.signer,
+					to: tx.to,
+					contract_address: output
+						.contract
+						.filter(|_| tx.to.is_none() && success)
+						.map(|contract| Self::address_of(&contract)),
+					gas_used,
+					success,
+				},
+			);
+
+			Ok(PostDispatchInfo { actual_weight: Some(weight_used), pays_fee: Pays::No })
+		}
+
+		/// Makes a call to a contract which pays for it.
+		///
+		/// This behaves like [`Self::call`] except that the storage deposit of `dest` is paid
+		/// from the [`Self::sponsorship_account`] of `dest`. This requires that `dest` set its
+		/// terms via [`Self::set_sponsorship`], that the caller didn't use up its
+		/// [`Sponsorship::max_calls`] and that [`Config::SponsorshipPolicy`] allows the call.
+		///
+		/// # Parameters
+		///
+		/// * `storage_deposit_limit`: Further restricts the storage deposit the sponsor pays.
+		///   Defaults to the `max_deposit` set by the contract.
+		///
+		/// See [`Self::call`] for all other parameters.
+		///
+		/// # Note
+		///
+		/// The transaction fee is only paid by the sponsor when the runtime wraps its payment
+		/// extension into [`ChargeSponsor`]. Otherwise the caller pays for it as usual. Storage
+		/// deposit of other contracts called along the way is paid by the caller.
+		///
+		/// Like with [`Self::eth_transact`] a call whose contract fails or reverts is still
+		/// paid for. The outcome is reported by [`Event::CallSponsored`].
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::call_sponsored().saturating_add(*gas_limit))]
+		pub fn call_sponsored(
+			origin: OriginFor<T>,
+			dest: AccountIdLookupOf<T>,
+			#[pallet::compact] value: BalanceOf<T>,
+			gas_limit: Weight,
+			storage_deposit_limit: Option<<BalanceOf<T> as codec::HasCompact>::Type>,
+			data: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			Migration::<T>::ensure_migrated()?;
+			let checked = Self::check_sponsored_call(origin, dest, value, gas_limit, &data)?;
+			<SponsoredCallsOf<T>>::insert(
+				&checked.contract,
+				&checked.caller,
+				(checked.period_start, checked.calls.saturating_add(1)),
+			);
+
+			// The fee was already withdrawn by `ChargeSponsor`.
+			let available = T::Currency::reducible_balance(
+				&checked.sponsor,
+				Preservation::Preserve,
+				Fortitude::Polite,
+			)
+			.saturating_sub(Self::min_balance());
+			let storage_deposit_limit = storage_deposit_limit
+				.map_or(checked.sponsorship.max_deposit, Into::into)
+				.min(checked.sponsorship.max_deposit)
+				.min(available);
+			let common = CommonInput {
+				origin: Origin::from_account_id(checked.caller.clone()),
+				value,
+				data,
+				gas_limit,
+				storage_deposit_limit: Some(storage_deposit_limit),
+				sponsor: Some((checked.contract.clone(), checked.sponsor.clone())),
+				debug_message: None,
+			};
+			let output = CallInput::<T> {
+				dest: checked.contract.clone(),
+				determinism: Determinism::Enforced,
+			}
+			.run_guarded(common);
+
+			let weight_used =
+				output.gas_meter.gas_consumed().saturating_add(T::WeightInfo::call_sponsored());
+
+			Self::deposit_event(
+				vec![T::Hashing::hash_of(&checked.contract), T::Hashing::hash_of(&checked.caller)],
+				Event::CallSponsored {
+					contract: checked.contract,
+					caller: checked.caller,
+					success: matches!(&output.result, Ok(retval) if !retval.did_revert()),
+				},
+			);
+
+			Ok(Some(weight_used).into())
+		}
+
+		/// Sets the terms under which the calling contract sponsors calls made to it.
+		///
+		/// This needs to be dispatched by the contract itself via `seal_call_runtime`. Passing
+		/// `None` stops the sponsoring and moves the remaining balance of the
+		/// [`Self::sponsorship_account`] back to the contract.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::set_sponsorship())]
+		pub fn set_sponsorship(
+			origin: OriginFor<T>,
+			sponsorship: Option<Sponsorship<T>>,
+		) -> DispatchResult {
+			Migration::<T>::ensure_migrated()?;
+			let contract = ensure_signed(origin)?;
+			ensure!(<ContractInfoOf<T>>::contains_key(&contract), <Error<T>>::ContractNotFound);
+
+			match sponsorship {
+				Some(sponsorship) => {
+					Self::deposit_event(
+						vec![T::Hashing::hash_of(&contract)],
+						Event::SponsorshipSet {
+							contract: contract.clone(),
+							max_fee: sponsorship.max_fee,
+							max_deposit: sponsorship.max_deposit,
+							max_calls: sponsorship.max_calls,
+							period: sponsorship.period,
+						},
+					);
+					<SponsorshipOf<T>>::insert(&contract, sponsorship);
+				},
+				None => {
+					<SponsorshipOf<T>>::take(&contract).ok_or(<Error<T>>::SponsorshipNotFound)?;
+					let sponsor = Self::sponsorship_account(&contract);
+					let refunded = T::Currency::transfer(
+						&sponsor,
+						&contract,
+						T::Currency::reducible_balance(
+							&sponsor,
+							Preservation::Expendable,
+							Fortitude::Polite,
+						),
+						Preservation::Expendable,
+					)?;
+					Self::deposit_event(
+						vec![T::Hashing::hash_of(&contract)],
+						Event::SponsorshipRemoved { contract, refunded },
+					);
+				},
+			}
+			Ok(())
+		}
+
+		/// Transfers `amount` to the [`Self::sponsorship_account`] of `contract`.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::fund_sponsorship())]
+		pub fn fund_sponsorship(
+			origin: OriginFor<T>,
+			contract: AccountIdLookupOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			Migration::<T>::ensure_migrated()?;
+			let from = ensure_signed(origin)?;
+			let contract = T::Lookup::lookup(contract)?;
+			ensure!(<SponsorshipOf<T>>::contains_key(&contract), <Error<T>>::SponsorshipNotFound);
+			T::Currency::transfer(
+				&from,
+				&Self::sponsorship_account(&contract),
+				amount,
+				Preservation::Preserve,
+			)?;
+			Self::deposit_event(
+				vec![T::Hashing::hash_of(&contract), T::Hashing::hash_of(&from)],
+				Event::SponsorshipFunded { contract, from, amount },
+			);
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T>
+	where
+		<BalanceOf<T> as HasCompact>::Type: Clone + Eq + PartialEq + Debug + TypeInfo + Encode,
+	{
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let Call::eth_transact { payload } = call else {
+				return InvalidTransaction::Call.into()
+			};
+			let checked = Self::check_eth_transaction(payload)?;
+			let current_nonce = System::<T>::account_nonce(&checked.origin);
+			if checked.nonce < current_nonce {
+				return InvalidTransaction::Stale.into()
+			}
+			// Like on Ethereum transactions are ordered by the priority fee they pay per gas.
+			let priority =
+				checked.gas_price.saturating_sub(Self::eth_gas_price()).unique_saturated_into();
+
+			// Use the same tags as `frame_system::CheckNonce` so that Ethereum transactions and
+			// regular extrinsics of the same account are ordered by the transaction pool.
+			let provides = vec![Encode::encode(&(&checked.origin, checked.nonce))];
+			let requires = if checked.nonce > current_nonce {
+				vec![Encode::encode(&(&checked.origin, checked.nonce.saturating_sub(1u32.into())))]
+			} else {
+				vec![]
+			};
+			Ok(ValidTransaction {
+				priority,
+				requires,
+				provides,
+				longevity: ETH_TRANSACTION_LONGEVITY,
+				propagate: true,
+			})
+		}
+
+		fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+			let Call::eth_transact { payload } = call else {
+				return Err(InvalidTransaction::Call.into())
+			};
+			let checked = Self::check_eth_transaction(payload)?;
+			let current_nonce = System::<T>::account_nonce(&checked.origin);
+			if checked.nonce < current_nonce {
+				return Err(InvalidTransaction::Stale.into())
+			}
+			if checked.nonce > current_nonce {
+				return Err(InvalidTransaction::Future.into())
+			}
+			Ok(())
+		}
 	}
 
 	#[pallet::event]
@@ -1138,6 +1505,60 @@ pub mod pallet {
 			to: T::AccountId,
 			amount: BalanceOf<T>,
 		},
+
+		/// An Ethereum transaction was executed by [`Pallet::eth_transact`].
+		///
+		/// # Note
+		///
+		/// Unlike [`Event::Called`] this is also emitted when the contract failed.
+		EthTransactionExecuted {
+			/// The hash of the raw transaction.
+			hash: H256,
+			/// The signer of the transaction.
+			from: H160,
+			/// The contract that was called. `None` for instantiations.
+			to: Option<H160>,
+			/// The contract that was instantiated. `None` for calls or failed instantiations.
+			contract_address: Option<H160>,
+			/// The amount of gas that was charged for the transaction.
+			gas_used: u64,
+			/// Whether the contract executed successfully without reverting.
+			success: bool,
+		},
+
+		/// A contract started sponsoring calls or changed its terms.
+		SponsorshipSet {
+			contract: T::AccountId,
+			max_fee: BalanceOf<T>,
+			max_deposit: BalanceOf<T>,
+			max_calls: u32,
+			period: BlockNumberFor<T>,
+		},
+
+		/// A contract stopped sponsoring calls.
+		SponsorshipRemoved {
+			/// The contract that stopped sponsoring.
+			contract: T::AccountId,
+			/// The balance moved from the sponsorship account back to the contract.
+			refunded: BalanceOf<T>,
+		},
+
+		/// The sponsorship account of a contract was topped up.
+		SponsorshipFunded { contract: T::AccountId, from: T::AccountId, amount: BalanceOf<T> },
+
+		/// The sponsorship account of a contract paid for a call.
+		///
+		/// # Note
+		///
+		/// Unlike [`Event::Called`] this is also emitted when the contract failed.
+		CallSponsored {
+			/// The contract that was called.
+			contract: T::AccountId,
+			/// The account that made the call.
+			caller: T::AccountId,
+			/// Whether the contract executed successfully without reverting.
+			success: bool,
+		},
 	}
 
 	#[pallet::error]
@@ -1233,6 +1654,12 @@ pub mod pallet {
 		DelegateDependencyAlreadyExists,
 		/// Can not add a delegate dependency to the code hash of the contract itself.
 		CannotAddSelfAsDelegateDependency,
+		/// The Ethereum transaction is malformed, wrongly signed or can't be paid for.
+		InvalidEthTransaction,
+		/// The contract does not sponsor this call or its sponsorship account can't pay for it.
+		NotSponsored,
+		/// The contract does not sponsor any calls.
+		SponsorshipNotFound,
 	}
 
 	/// A reason for the pallet contracts placing a hold on funds.
@@ -1242,6 +1669,8 @@ pub mod pallet {
 		CodeUploadDepositReserve,
 		/// The Pallet has reserved it for storage deposit.
 		StorageDepositReserve,
+		/// The fee for the gas limit of an Ethereum transaction during its execution.
+		EthTransactionFee,
 	}
 
 	/// A mapping from a contract's code hash to its code.
@@ -1302,6 +1731,46 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type MigrationInProgress<T: Config> =
 		StorageValue<_, migration::Cursor, OptionQuery>;
+
+	/// The account of a contract given its Ethereum address.
+	///
+	/// The address of a contract is determined by [`Config::AddressMapper`]. Since it is not
+	/// reversible for contract accounts the mapping is stored when the contract is instantiated.
+	///
+	/// TWOX-NOTE: SAFE since the address of a contract is derived from a secure hash.
+	#[pallet::storage]
+	pub(crate) type ContractOfAddress<T: Config> = StorageMap<_, Twox64Concat, H160, T::AccountId>;
+
+	/// The terms under which a contract sponsors calls made to it.
+	///
+	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub(crate) type SponsorshipOf<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, Sponsorship<T>>;
+
+	/// The calls a contract sponsored for a caller within the current period.
+	///
+	/// Holds the block in which the period started and the number of calls made since.
+	///
+	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub(crate) type SponsoredCallsOf<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		(BlockNumberFor<T>, u32),
+	>;
+
+	/// The part of the storage deposit of a contract that was paid by its sponsorship account.
+	///
+	/// Refunds are paid back to the sponsorship account first.
+	///
+	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub(crate) type SponsoredDepositOf<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
 }
 
 /// The type of origins supported by the contracts pallet.
@@ -1340,6 +1809,9 @@ struct CommonInput<'a, T: Config> {
 	data: Vec<u8>,
 	gas_limit: Weight,
 	storage_deposit_limit: Option<BalanceOf<T>>,
+	/// The contract whose storage deposit is paid by the given sponsorship account instead of
+	/// `origin`. Only supported for calls.
+	sponsor: Option<(T::AccountId, T::AccountId)>,
 	debug_message: Option<&'a mut DebugBufferVec<T>>,
 }
 
@@ -1404,6 +1876,56 @@ struct InternalOutput<T: Config, O> {
 	result: Result<O, ExecError>,
 }
 
+/// An Ethereum transaction which passed all checks except for the nonce.
+struct CheckedEthTransaction<T: Config> {
+	/// The decoded transaction.
+	signed: SignedTransaction,
+	/// The address that signed the transaction.
+	signer: H160,
+	/// The account the transaction is executed for.
+	origin: T::AccountId,
+	/// The nonce of the transaction.
+	nonce: T::Nonce,
+	/// The value of the transaction.
+	value: BalanceOf<T>,
+	/// The price paid per unit of gas, including the priority fee.
+	gas_price: BalanceOf<T>,
+	/// The fee for the whole gas limit.
+	max_fee: BalanceOf<T>,
+	/// The gas limit of the transaction converted to weight.
+	gas_limit: Weight,
+	/// The part of `gas_limit` that is not available to contracts.
+	base_weight: Weight,
+}
+
+/// Return type of [`Pallet::eth_execute`].
+struct EthExecOutput<T: Config> {
+	/// The result of the call or instantiation.
+	result: Result<ExecReturnValue, DispatchError>,
+	/// The gas consumed by contract execution.
+	gas_consumed: Weight,
+	/// The gas required for contract execution to succeed.
+	gas_required: Weight,
+	/// The contract that was called or instantiated.
+	contract: Option<T::AccountId>,
+}
+
+/// A call which passed all checks of [`Pallet::call_sponsored`].
+struct CheckedSponsoredCall<T: Config> {
+	/// The account making the call.
+	caller: T::AccountId,
+	/// The contract that is called.
+	contract: T::AccountId,
+	/// The account paying for the call.
+	sponsor: T::AccountId,
+	/// The terms set by the contract.
+	sponsorship: Sponsorship<T>,
+	/// The block in which the current period of the caller started.
+	period_start: BlockNumberFor<T>,
+	/// The calls sponsored for the caller in the current period so far.
+	calls: u32,
+}
+
 // Set up a global reference to the boolean flag used for the re-entrancy guard.
 environmental!(executing_contract: bool);
 
@@ -1457,7 +1979,7 @@ trait Invokable<T: Config>: Sized {
 					}),
 				},
 				// Enter contract call.
-				|_| self.run(common, GasMeter::new(gas_limit)),
+				|_| wasm::with_module_cache(|| self.run(common, GasMeter::new(gas_limit))),
 			)
 		})
 	}
@@ -1484,17 +2006,21 @@ impl<T: Config> Invokable<T> for CallInput<T> {
 		mut gas_meter: GasMeter<T>,
 	) -> InternalOutput<T, Self::Output> {
 		let CallInput { dest, determinism } = self;
-		let CommonInput { origin, value, data, debug_message, .. } = common;
-		let mut storage_meter =
-			match StorageMeter::new(&origin, common.storage_deposit_limit, common.value) {
-				Ok(meter) => meter,
-				Err(err) =>
-					return InternalOutput {
-						result: Err(err.into()),
-						gas_meter,
-						storage_deposit: Default::default(),
-					},
-			};
+		let CommonInput { origin, value, data, sponsor, debug_message, .. } = common;
+		let storage_meter = match sponsor {
+			Some((contract, sponsor)) =>
+				StorageMeter::new_sponsored(contract, sponsor, common.storage_deposit_limit),
+			None => StorageMeter::new(&origin, common.storage_deposit_limit, common.value),
+		};
+		let mut storage_meter = match storage_meter {
+			Ok(meter) => meter,
+			Err(err) =>
+				return InternalOutput {
+					result: Err(err.into()),
+					gas_meter,
+					storage_deposit: Default::default(),
+				},
+		};
 		let schedule = T::Schedule::get();
 		let result = ExecStack::<T, WasmBlob<T>>::run_call(
 			origin.clone(),
@@ -1626,6 +2152,7 @@ impl<T: Config> Pallet<T> {
 			data,
 			gas_limit,
 			storage_deposit_limit,
+			sponsor: None,
 			debug_message: debug_message.as_mut(),
 		};
 		let output = CallInput::<T> { dest, determinism }.run_guarded(common);
@@ -1722,6 +2249,7 @@ impl<T: Config> Pallet<T> {
 			data,
 			gas_limit,
 			storage_deposit_limit,
+			sponsor: None,
 			debug_message: debug_message.as_mut(),
 		};
 
@@ -1813,6 +2341,409 @@ impl<T: Config> Pallet<T> {
 		ContractInfo::<T>::load_code_hash(account)
 	}
 
+	/// The Ethereum address of `account`.
+	pub fn address_of(account: &T::AccountId) -> H160 {
+		T::AddressMapper::to_address(account)
+	}
+
+	/// The account behind the Ethereum `address`.
+	///
+	/// Resolves the addresses of contracts and falls back to [`Config::AddressMapper`] for all
+	/// other addresses.
+	pub fn account_of(address: &H160) -> T::AccountId {
+		ContractOfAddress::<T>::get(address)
+			.unwrap_or_else(|| T::AddressMapper::to_account_id(address))
+	}
+
+	/// The free balance of the account behind the Ethereum `address`.
+	pub fn eth_balance(address: &H160) -> U256 {
+		Self::balance_to_u256(T::Currency::balance(&Self::account_of(address)))
+	}
+
+	/// The nonce of the account behind the Ethereum `address`.
+	pub fn eth_nonce(address: &H160) -> U256 {
+		let nonce = System::<T>::account_nonce(Self::account_of(address));
+		U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(nonce))
+	}
+
+	/// The price of a unit of Ethereum gas. See [`Config::WeightPerEthGas`].
+	pub fn eth_gas_price() -> BalanceOf<T> {
+		T::WeightPrice::convert(T::WeightPerEthGas::get())
+	}
+
+	/// The account that pays for the calls sponsored by `contract`.
+	///
+	/// Formula: `hash("contract_sponsor_v1" ++ contract)`
+	pub fn sponsorship_account(contract: &T::AccountId) -> T::AccountId {
+		let entropy = (b"contract_sponsor_v1", contract).using_encoded(T::Hashing::hash);
+		Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+			.expect("infinite length input; no invalid inputs for type; qed")
+	}
+
+	/// Checks whether `dest` pays for a call of `origin` with the given parameters.
+	///
+	/// Used by [`ChargeSponsor`] to decide who pays the transaction fee of
+	/// [`Self::call_sponsored`]. Whether the sponsor can afford the fee is checked by the
+	/// wrapped payment extension.
+	fn check_sponsored_call(
+		origin: OriginFor<T>,
+		dest: AccountIdLookupOf<T>,
+		value: BalanceOf<T>,
+		gas_limit: Weight,
+		data: &[u8],
+	) -> Result<CheckedSponsoredCall<T>, DispatchError> {
+		let caller = ensure_signed(origin)?;
+		let contract = T::Lookup::lookup(dest)?;
+		let sponsorship = <SponsorshipOf<T>>::get(&contract).ok_or(<Error<T>>::NotSponsored)?;
+		let now = System::<T>::block_number();
+		let (period_start, calls) = match <SponsoredCallsOf<T>>::get(&contract, &caller) {
+			Some((start, calls))
+				if sponsorship.period.is_zero() ||
+					now < start.saturating_add(sponsorship.period) =>
+				(start, calls),
+			_ => (now, 0),
+		};
+		ensure!(calls < sponsorship.max_calls, <Error<T>>::NotSponsored);
+		ensure!(
+			T::SponsorshipPolicy::sponsors(&contract, &caller, &value, data),
+			<Error<T>>::NotSponsored
+		);
+		let max_fee =
+			T::WeightPrice::convert(T::WeightInfo::call_sponsored().saturating_add(gas_limit));
+		ensure!(max_fee <= sponsorship.max_fee, <Error<T>>::NotSponsored);
+		let sponsor = Self::sponsorship_account(&contract);
+		Ok(CheckedSponsoredCall { caller, contract, sponsor, sponsorship, period_start, calls })
+	}
+
+	/// Checks the encoding, chain id and signature of a raw Ethereum transaction.
+	///
+	/// Returns the address of the signer. This allows off-chain callers to reject a transaction
+	/// before submitting it via [`Self::eth_transact`].
+	pub fn eth_check_transaction(payload: &[u8]) -> Result<H160, EthTransactError> {
+		let signed = SignedTransaction::decode(payload)?;
+		if signed.tx.chain_id != T::ChainId::get() {
+			return Err(EthTransactError::WrongChainId)
+		}
+		signed.recover_signer()
+	}
+
+	/// Dry-run an Ethereum transaction without requiring a signature.
+	///
+	/// This is used to implement `eth_call` and `eth_estimateGas`. If no `gas` limit is
+	/// supplied the execution may consume the weight of a whole block.
+	pub fn eth_call(
+		from: H160,
+		to: Option<H160>,
+		value: U256,
+		gas: Option<u64>,
+		data: Vec<u8>,
+	) -> EthCallResult {
+		let origin = T::AddressMapper::to_account_id(&from);
+		let balance = match Self::u256_to_balance(value) {
+			Ok(balance) => balance,
+			Err(err) =>
+				return EthCallResult {
+					result: Err(err.into()),
+					gas_required: 0,
+					contract_address: None,
+				},
+		};
+		let nonce = System::<T>::account_nonce(&origin);
+		let tx = Transaction {
+			tx_type: TransactionType::DynamicFee,
+			chain_id: T::ChainId::get(),
+			nonce: U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(nonce)),
+			max_priority_fee_per_gas: U256::zero(),
+			gas_price: Self::balance_to_u256(Self::eth_gas_price()),
+			gas: gas.unwrap_or(u64::MAX).into(),
+			to,
+			value,
+			data,
+			access_list: rlp::EMPTY_LIST_RLP.to_vec(),
+		};
+
+		let payload_len = tx.encode_signed(&[1u8; 65]).len() as u32;
+		let base_weight = Self::eth_base_weight(&tx, payload_len);
+		let gas_limit = gas
+			.map(Self::eth_gas_to_weight)
+			.unwrap_or_else(|| T::BlockWeights::get().max_block)
+			.saturating_sub(base_weight);
+		let output =
+			Self::eth_execute(origin, &tx, balance, gas_limit, T::DefaultDepositLimit::get());
+		EthCallResult {
+			result: output.result,
+			gas_required: Self::weight_to_eth_gas(base_weight.saturating_add(output.gas_required)),
+			contract_address: output
+				.contract
+				.filter(|_| to.is_none())
+				.map(|contract| Self::address_of(&contract)),
+		}
+	}
+
+	/// The receipts of all Ethereum transactions executed in the current block.
+	///
+	/// # Note
+	///
+	/// This reads all the events of the block. It should only be called off-chain.
+	pub fn eth_receipts() -> Vec<Receipt>
+	where
+		<T as Config>::RuntimeEvent: TryInto<Event<T>>,
+	{
+		let mut receipts = Vec::new();
+		// Logs are emitted before the receipt of the extrinsic they belong to.
+		let mut logs: (u32, Vec<Log>) = (0, Vec::new());
+		for record in System::<T>::read_events_no_consensus() {
+			let EventRecord { phase, event, topics } = *record;
+			let Phase::ApplyExtrinsic(index) = phase else { continue };
+			let Ok(event) = TryInto::<Event<T>>::try_into(<T as Config>::RuntimeEvent::from(event))
+			else {
+				continue
+			};
+			match event {
+				Event::ContractEmitted { contract, data } => {
+					if logs.0 != index {
+						logs = (index, Vec::new());
+					}
+					logs.1.push(Log {
+						address: Self::address_of(&contract),
+						topics: topics
+							.iter()
+							.filter_map(|topic| <[u8; 32]>::try_from(topic.as_ref()).ok())
+							.map(H256)
+							.collect(),
+						data,
+					});
+				},
+				Event::EthTransactionExecuted {
+					hash,
+					from,
+					to,
+					contract_address,
+					gas_used,
+					success,
+				} => receipts.push(Receipt {
+					transaction_hash: hash,
+					transaction_index: index,
+					from,
+					to,
+					contract_address,
+					gas_used,
+					success,
+					logs: if logs.0 == index { sp_std::mem::take(&mut logs.1) } else { Vec::new() },
+				}),
+				_ => (),
+			}
+		}
+		receipts
+	}
+
+	/// The weight of [`Self::eth_transact`].
+	///
+	/// This is the gas limit of the transaction or its base weight if that is larger.
+	fn eth_transact_weight(payload: &[u8]) -> Weight {
+		match SignedTransaction::decode(payload) {
+			Ok(signed) => {
+				let base_weight = Self::eth_base_weight(&signed.tx, payload.len() as u32);
+				let gas = u64::try_from(signed.tx.gas).unwrap_or(u64::MAX);
+				Self::eth_gas_to_weight(gas).max(base_weight)
+			},
+			Err(_) => T::WeightInfo::eth_transact(payload.len() as u32),
+		}
+	}
+
+	/// The weight charged for an Ethereum transaction on top of the execution of contracts.
+	///
+	/// This covers decoding and verifying the transaction as well as uploading the code when
+	/// instantiating.
+	fn eth_base_weight(tx: &Transaction, payload_len: u32) -> Weight {
+		let base = T::WeightInfo::eth_transact(payload_len);
+		if tx.to.is_some() {
+			return base
+		}
+		let instantiate = match Self::decode_eth_instantiate(&tx.data) {
+			Ok((Code::Upload(code), data)) => T::WeightInfo::instantiate_with_code(
+				code.len() as u32,
+				data.len() as u32,
+				ETH_SALT_LEN,
+			),
+			Ok((Code::Existing(_), data)) =>
+				T::WeightInfo::instantiate(data.len() as u32, ETH_SALT_LEN),
+			Err(_) => Weight::zero(),
+		};
+		base.saturating_add(instantiate)
+	}
+
+	/// Checks everything about an Ethereum transaction except for its nonce.
+	fn check_eth_transaction(
+		payload: &[u8],
+	) -> Result<CheckedEthTransaction<T>, TransactionValidityError> {
+		let signed = SignedTransaction::decode(payload).map_err(|_| InvalidTransaction::Call)?;
+		let tx = &signed.tx;
+		if tx.chain_id != T::ChainId::get() {
+			return Err(InvalidTransaction::BadProof.into())
+		}
+		let signer = signed.recover_signer().map_err(|_| InvalidTransaction::BadProof)?;
+		let origin = T::AddressMapper::to_account_id(&signer);
+		let nonce = u128::try_from(tx.nonce)
+			.ok()
+			.and_then(|nonce| T::Nonce::try_from(nonce).ok())
+			.ok_or(InvalidTransaction::Future)?;
+		if tx.to.is_none() && Self::decode_eth_instantiate(&tx.data).is_err() {
+			return Err(InvalidTransaction::Call.into())
+		}
+
+		// Like the intrinsic gas on Ethereum, the gas limit needs to cover the base weight.
+		let gas = u64::try_from(tx.gas).map_err(|_| InvalidTransaction::ExhaustsResources)?;
+		let gas_limit = Self::eth_gas_to_weight(gas);
+		let base_weight = Self::eth_base_weight(tx, payload.len() as u32);
+		if gas_limit.any_lt(base_weight) {
+			return Err(InvalidTransaction::ExhaustsResources.into())
+		}
+
+		// The signer needs to be able to pay for the whole gas limit, the value and the storage
+		// deposit limit.
+		let gas_price = Self::eth_effective_gas_price(tx)?;
+		let value = Self::u256_to_balance(tx.value).map_err(|_| InvalidTransaction::Payment)?;
+		let max_fee = gas_price.saturating_mul(gas.unique_saturated_into());
+		let spendable =
+			T::Currency::reducible_balance(&origin, Preservation::Preserve, Fortitude::Polite);
+		if spendable < max_fee.saturating_add(value).saturating_add(T::DefaultDepositLimit::get()) {
+			return Err(InvalidTransaction::Payment.into())
+		}
+
+		Ok(CheckedEthTransaction {
+			signed,
+			signer,
+			origin,
+			nonce,
+			value,
+			gas_price,
+			max_fee,
+			gas_limit,
+			base_weight,
+		})
+	}
+
+	/// The price per unit of gas an Ethereum transaction pays.
+	///
+	/// This is the base price plus the priority fee, which is capped by the `gas_price` of the
+	/// transaction. Fails if `gas_price` doesn't cover the base price.
+	fn eth_effective_gas_price(tx: &Transaction) -> Result<BalanceOf<T>, TransactionValidityError> {
+		let base_price = Self::balance_to_u256(Self::eth_gas_price());
+		if tx.gas_price < base_price {
+			return Err(InvalidTransaction::Payment.into())
+		}
+		let priority_fee = match tx.tx_type {
+			TransactionType::Legacy | TransactionType::AccessList => tx.gas_price - base_price,
+			TransactionType::DynamicFee =>
+				(tx.gas_price - base_price).min(tx.max_priority_fee_per_gas),
+		};
+		Self::u256_to_balance(base_price.saturating_add(priority_fee))
+			.map_err(|_| InvalidTransaction::Payment.into())
+	}
+
+	/// Executes the call or instantiation described by an Ethereum transaction.
+	fn eth_execute(
+		origin: T::AccountId,
+		tx: &Transaction,
+		value: BalanceOf<T>,
+		gas_limit: Weight,
+		storage_deposit_limit: BalanceOf<T>,
+	) -> EthExecOutput<T> {
+		let Some(to) = tx.to else {
+			let (code, data) = match Self::decode_eth_instantiate(&tx.data) {
+				Ok(decoded) => decoded,
+				Err(err) =>
+					return EthExecOutput {
+						result: Err(err),
+						gas_consumed: Weight::zero(),
+						gas_required: Weight::zero(),
+						contract: None,
+					},
+			};
+			let output = Self::bare_instantiate(
+				origin,
+				value,
+				gas_limit,
+				Some(storage_deposit_limit),
+				code,
+				data,
+				Self::eth_salt(tx),
+				DebugInfo::Skip,
+				CollectEvents::Skip,
+			);
+			let (result, contract) = match output.result {
+				Ok(retval) => (Ok(retval.result), Some(retval.account_id)),
+				Err(err) => (Err(err), None),
+			};
+			return EthExecOutput {
+				result,
+				gas_consumed: output.gas_consumed,
+				gas_required: output.gas_required,
+				contract,
+			}
+		};
+
+		let dest = Self::account_of(&to);
+		let output = Self::bare_call(
+			origin,
+			dest.clone(),
+			value,
+			gas_limit,
+			Some(storage_deposit_limit),
+			tx.data.clone(),
+			DebugInfo::Skip,
+			CollectEvents::Skip,
+			Determinism::Enforced,
+		);
+		EthExecOutput {
+			result: output.result,
+			gas_consumed: output.gas_consumed,
+			gas_required: output.gas_required,
+			contract: Some(dest),
+		}
+	}
+
+	/// Splits the data of an instantiating Ethereum transaction into code and input.
+	fn decode_eth_instantiate(data: &[u8]) -> Result<(Code<CodeHash<T>>, Vec<u8>), DispatchError> {
+		let mut input = data;
+		let code = Code::decode(&mut input).map_err(|_| <Error<T>>::InvalidEthTransaction)?;
+		Ok((code, input.to_vec()))
+	}
+
+	/// The salt used when instantiating contracts from Ethereum transactions.
+	///
+	/// Like `CREATE` on Ethereum this makes the address of the new contract depend on the
+	/// nonce of the signer.
+	fn eth_salt(tx: &Transaction) -> Vec<u8> {
+		let mut salt = [0u8; ETH_SALT_LEN as usize];
+		tx.nonce.to_big_endian(&mut salt);
+		salt.to_vec()
+	}
+
+	fn eth_gas_to_weight(gas: u64) -> Weight {
+		T::WeightPerEthGas::get().saturating_mul(gas)
+	}
+
+	fn weight_to_eth_gas(weight: Weight) -> u64 {
+		let per_gas = T::WeightPerEthGas::get();
+		weight
+			.ref_time()
+			.div_ceil(per_gas.ref_time())
+			.max(weight.proof_size().div_ceil(per_gas.proof_size()))
+	}
+
+	fn u256_to_balance(value: U256) -> Result<BalanceOf<T>, ArithmeticError> {
+		u128::try_from(value)
+			.ok()
+			.and_then(|value| value.try_into().ok())
+			.ok_or(ArithmeticError::Overflow)
+	}
+
+	fn balance_to_u256(value: BalanceOf<T>) -> U256 {
+		U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(value))
+	}
+
 	/// Store code for benchmarks which does not validate the code.
 	#[cfg(feature = "runtime-benchmarks")]
 	fn store_code_raw(
@@ -1847,8 +2778,8 @@ impl<T: Config> Pallet<T> {
 }
 
 sp_api::decl_runtime_apis! {
-	/// The API used to dry-run contract interactions.
-	#[api_version(2)]
+	/// The API used to dry-run and trace contract interactions.
+	#[api_version(3)]
 	pub trait ContractsApi<AccountId, Balance, BlockNumber, Hash, EventRecord> where
 		AccountId: Codec,
 		Balance: Codec,
@@ -1900,5 +2831,58 @@ sp_api::decl_runtime_apis! {
 			address: AccountId,
 			key: Vec<u8>,
 		) -> GetStorageResult;
+
+		/// Replay an extrinsic of a block and trace all contract executions it started.
+		///
+		/// Needs to be called on top of the parent of the block with `header` and `extrinsics`. The
+		/// block is initialized with the pre-runtime digests of `header`, see
+		/// [`crate::debug::call_tracer::replay_header`]. The `extrinsics` preceding the one at
+		/// `index` are applied first so that the traced one sees the same state as when the block
+		/// was imported. Returns `None` if there is no extrinsic at `index`.
+		///
+		/// See [`crate::debug::call_tracer`].
+		#[api_version(3)]
+		fn trace_extrinsic(
+			header: <Block as BlockT>::Header,
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			index: u32,
+		) -> Option<Vec<CallTrace<AccountId, Balance, Hash>>>;
+	}
+	/// The API used by the Ethereum RPC compatibility layer.
+	pub trait EthApi {
+		/// The chain id that Ethereum transactions need to be signed for.
+		fn chain_id() -> u64;
+
+		/// The price of a unit of Ethereum gas.
+		///
+		/// See [`crate::Pallet::eth_gas_price`].
+		fn gas_price() -> U256;
+
+		/// The free balance of an Ethereum address.
+		fn balance(address: H160) -> U256;
+
+		/// The nonce of an Ethereum address.
+		fn nonce(address: H160) -> U256;
+
+		/// Dry-run a call or instantiation. `to` is `None` for the latter.
+		///
+		/// See [`crate::Pallet::eth_call`].
+		fn call(
+			from: H160,
+			to: Option<H160>,
+			value: U256,
+			gas: Option<u64>,
+			data: Vec<u8>,
+		) -> EthCallResult;
+
+		/// Wrap a raw Ethereum transaction into an extrinsic that can be submitted to the pool.
+		///
+		/// Fails if the transaction is malformed or not signed for this chain.
+		fn transact(payload: Vec<u8>) -> Result<<Block as BlockT>::Extrinsic, EthTransactError>;
+
+		/// The receipts of all Ethereum transactions included in the block.
+		///
+		/// See [`crate::Pallet::eth_receipts`].
+		fn receipts() -> Vec<Receipt>;
 	}
 }
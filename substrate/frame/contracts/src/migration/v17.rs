@@ -0,0 +1,145 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Add the `code_type` field to `CodeInfo`. All code uploaded so far is Wasm.
+
+use crate::{
+	migration::{IsFinished, MigrationStep},
+	weights::WeightInfo,
+	AccountIdOf, BalanceOf, CodeHash, CodeType, Config, Determinism, Pallet, Weight, LOG_TARGET,
+};
+use codec::{Decode, Encode};
+use frame_support::{pallet_prelude::*, storage_alias, weights::WeightMeter, DefaultNoBound};
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+use sp_std::prelude::*;
+
+mod v16 {
+	use super::*;
+
+	#[derive(Encode, Decode, scale_info::TypeInfo, MaxEncodedLen)]
+	#[codec(mel_bound())]
+	#[scale_info(skip_type_params(T))]
+	pub struct CodeInfo<T: Config> {
+		pub owner: AccountIdOf<T>,
+		#[codec(compact)]
+		pub deposit: BalanceOf<T>,
+		#[codec(compact)]
+		pub refcount: u64,
+		pub determinism: Determinism,
+		pub code_len: u32,
+	}
+
+	#[storage_alias]
+	pub type CodeInfoOf<T: Config> = StorageMap<Pallet<T>, Identity, CodeHash<T>, CodeInfo<T>>;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub fn store_old_dummy_code<T: Config>(account: T::AccountId) -> CodeHash<T> {
+	use sp_runtime::traits::Hash;
+
+	let len = T::MaxCodeLen::get();
+	let code = vec![42u8; len as usize];
+	let hash = T::Hashing::hash(&code);
+
+	let info = v16::CodeInfo {
+		owner: account,
+		deposit: 10_000u32.into(),
+		refcount: u64::MAX,
+		determinism: Determinism::Enforced,
+		code_len: len,
+	};
+	v16::CodeInfoOf::<T>::insert(hash, info);
+	hash
+}
+
+#[derive(Encode, Decode, scale_info::TypeInfo, MaxEncodedLen)]
+#[codec(mel_bound())]
+#[scale_info(skip_type_params(T))]
+pub struct CodeInfo<T: Config> {
+	owner: AccountIdOf<T>,
+	#[codec(compact)]
+	deposit: BalanceOf<T>,
+	#[codec(compact)]
+	refcount: u64,
+	determinism: Determinism,
+	code_len: u32,
+	pub code_type: CodeType,
+}
+
+#[storage_alias]
+pub type CodeInfoOf<T: Config> = StorageMap<Pallet<T>, Identity, CodeHash<T>, CodeInfo<T>>;
+
+#[derive(Encode, Decode, MaxEncodedLen, DefaultNoBound)]
+pub struct Migration<T: Config> {
+	last_code_hash: Option<CodeHash<T>>,
+}
+
+impl<T: Config> MigrationStep for Migration<T> {
+	const VERSION: u16 = 17;
+
+	fn max_step_weight() -> Weight {
+		T::WeightInfo::v17_migration_step()
+	}
+
+	fn step(&mut self, meter: &mut WeightMeter) -> IsFinished {
+		let mut iter = if let Some(last_hash) = self.last_code_hash.take() {
+			v16::CodeInfoOf::<T>::iter_from(v16::CodeInfoOf::<T>::hashed_key_for(last_hash))
+		} else {
+			v16::CodeInfoOf::<T>::iter()
+		};
+
+		if let Some((hash, old)) = iter.next() {
+			log::debug!(target: LOG_TARGET, "Migrating CodeInfo for code_hash {:?}", hash);
+			let info = CodeInfo::<T> {
+				owner: old.owner,
+				deposit: old.deposit,
+				refcount: old.refcount,
+				determinism: old.determinism,
+				code_len: old.code_len,
+				code_type: CodeType::Wasm,
+			};
+			CodeInfoOf::<T>::insert(hash, info);
+			self.last_code_hash = Some(hash);
+			meter.consume(T::WeightInfo::v17_migration_step());
+			IsFinished::No
+		} else {
+			log::debug!(target: LOG_TARGET, "No more CodeInfo to migrate");
+			meter.consume(T::WeightInfo::v17_migration_step());
+			IsFinished::Yes
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade_step() -> Result<Vec<u8>, TryRuntimeError> {
+		let count = v16::CodeInfoOf::<T>::iter_keys().count() as u32;
+		Ok(count.encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade_step(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+		let count = <u32 as Decode>::decode(&mut &state[..])
+			.expect("pre_upgrade_step provides a valid state; qed");
+		let mut migrated = 0u32;
+		for (_, info) in CodeInfoOf::<T>::iter() {
+			ensure!(info.code_type == CodeType::Wasm, "Code type must be Wasm");
+			migrated += 1;
+		}
+		ensure!(migrated == count, "Number of migrated CodeInfo entries mismatch");
+		Ok(())
+	}
+}
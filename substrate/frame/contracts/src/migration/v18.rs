@@ -0,0 +1,84 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Store the Ethereum address of every existing contract in `ContractOfAddress`.
+
+use crate::{
+	migration::{IsFinished, MigrationStep},
+	weights::WeightInfo,
+	Config, ContractInfoOf, ContractOfAddress, Pallet, Weight, LOG_TARGET,
+};
+use codec::{Decode, Encode};
+use frame_support::{pallet_prelude::*, weights::WeightMeter, DefaultNoBound};
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+use sp_std::prelude::*;
+
+#[derive(Encode, Decode, MaxEncodedLen, DefaultNoBound)]
+pub struct Migration<T: Config> {
+	last_account: Option<T::AccountId>,
+}
+
+impl<T: Config> MigrationStep for Migration<T> {
+	const VERSION: u16 = 18;
+
+	fn max_step_weight() -> Weight {
+		T::WeightInfo::v18_migration_step()
+	}
+
+	fn step(&mut self, meter: &mut WeightMeter) -> IsFinished {
+		let mut iter = if let Some(last_account) = self.last_account.take() {
+			ContractInfoOf::<T>::iter_keys_from(ContractInfoOf::<T>::hashed_key_for(last_account))
+		} else {
+			ContractInfoOf::<T>::iter_keys()
+		};
+
+		if let Some(key) = iter.next() {
+			log::debug!(target: LOG_TARGET, "Mapping the address of contract {:?}", key);
+			ContractOfAddress::<T>::insert(Pallet::<T>::address_of(&key), key.clone());
+			self.last_account = Some(key);
+			meter.consume(T::WeightInfo::v18_migration_step());
+			IsFinished::No
+		} else {
+			log::debug!(target: LOG_TARGET, "No more contracts to map");
+			meter.consume(T::WeightInfo::v18_migration_step());
+			IsFinished::Yes
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade_step() -> Result<Vec<u8>, TryRuntimeError> {
+		let count = ContractInfoOf::<T>::iter_keys().count() as u32;
+		Ok(count.encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade_step(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+		let count = <u32 as Decode>::decode(&mut &state[..])
+			.expect("pre_upgrade_step provides a valid state; qed");
+		for account in ContractInfoOf::<T>::iter_keys() {
+			ensure!(
+				ContractOfAddress::<T>::get(Pallet::<T>::address_of(&account)) == Some(account),
+				"Contract address is not mapped"
+			);
+		}
+		ensure!(
+			ContractOfAddress::<T>::iter_keys().count() as u32 == count,
+			"Number of mapped contracts mismatch"
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,331 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sponsoring of contract calls.
+//!
+//! A contract opts in to sponsoring by dispatching [`Pallet::set_sponsorship`] from its own
+//! account (via `seal_call_runtime`). From then on its callers can use
+//! [`Pallet::call_sponsored`] which charges the storage deposit of the contract to its
+//! sponsorship account instead of the caller. When the runtime wraps its payment extension into
+//! [`ChargeSponsor`] the sponsorship account also pays the transaction fee. Anyone can top up
+//! this account with [`Pallet::fund_sponsorship`].
+//!
+//! Callers don't need to hold any balance when the runtime also replaces
+//! `frame_system::CheckNonce` with [`CheckSponsoredNonce`]. To keep a single caller from
+//! draining the sponsorship account every caller is limited to [`Sponsorship::max_calls`] per
+//! [`Sponsorship::period`].
+//!
+//! Storage deposit refunds of the contract flow back to the sponsorship account as far as it
+//! paid for the deposit, no matter who triggered the refund.
+//!
+//! [`Pallet::set_sponsorship`]: crate::Pallet::set_sponsorship
+//! [`Pallet::call_sponsored`]: crate::Pallet::call_sponsored
+//! [`Pallet::fund_sponsorship`]: crate::Pallet::fund_sponsorship
+
+use crate::{AccountIdOf, BalanceOf, Call, Config, Pallet};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::{DispatchInfo, DispatchResult, RawOrigin},
+	storage::{transactional::with_transaction_unchecked, TransactionOutcome},
+	traits::IsSubType,
+	CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use frame_system::{pallet_prelude::BlockNumberFor, CheckNonce};
+use scale_info::{StaticTypeInfo, TypeInfo};
+use sp_runtime::{
+	traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension, Zero},
+	transaction_validity::{TransactionValidity, TransactionValidityError},
+};
+use sp_std::{fmt, marker::PhantomData};
+
+/// The terms under which a contract sponsors calls made to it.
+#[derive(
+	Encode,
+	Decode,
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+pub struct Sponsorship<T: Config> {
+	/// The maximum fee for the weight of a single call as priced by [`Config::WeightPrice`].
+	///
+	/// [`Config::WeightPrice`]: crate::Config::WeightPrice
+	pub max_fee: BalanceOf<T>,
+	/// The maximum storage deposit paid for a single call.
+	pub max_deposit: BalanceOf<T>,
+	/// The maximum number of calls sponsored for a single caller within one `period`.
+	pub max_calls: u32,
+	/// The number of blocks after which every caller is granted `max_calls` anew.
+	///
+	/// The period of a caller starts with its first sponsored call. Zero means that the calls
+	/// are never granted anew.
+	pub period: BlockNumberFor<T>,
+}
+
+/// Decides whether the sponsor of a contract pays for a call.
+///
+/// This is consulted in addition to the [`Sponsorship`] limits the contract itself has set.
+/// See [`SponsorValueFreeCalls`] for an implementation.
+pub trait SponsorshipPolicy<T: Config> {
+	/// Whether the sponsor of `contract` pays for a call by `caller`.
+	///
+	/// This is called while validating the transaction and hence needs to be cheap.
+	fn sponsors(
+		contract: &AccountIdOf<T>,
+		caller: &AccountIdOf<T>,
+		value: &BalanceOf<T>,
+		data: &[u8],
+	) -> bool;
+}
+
+/// Sponsoring is disabled.
+impl<T: Config> SponsorshipPolicy<T> for () {
+	fn sponsors(
+		_contract: &AccountIdOf<T>,
+		_caller: &AccountIdOf<T>,
+		_value: &BalanceOf<T>,
+		_data: &[u8],
+	) -> bool {
+		false
+	}
+}
+
+/// Sponsors all calls which do not transfer any balance.
+///
+/// A caller which transfers balance can as well pay for its own call.
+pub struct SponsorValueFreeCalls;
+
+impl<T: Config> SponsorshipPolicy<T> for SponsorValueFreeCalls {
+	fn sponsors(
+		_contract: &AccountIdOf<T>,
+		_caller: &AccountIdOf<T>,
+		value: &BalanceOf<T>,
+		_data: &[u8],
+	) -> bool {
+		value.is_zero()
+	}
+}
+
+/// The sponsorship account paying for `call` made by `who`, if any.
+fn sponsor_of<T: Config>(
+	who: &AccountIdOf<T>,
+	call: &<T as frame_system::Config>::RuntimeCall,
+) -> Option<AccountIdOf<T>>
+where
+	<T as frame_system::Config>::RuntimeCall: IsSubType<Call<T>>,
+{
+	let Some(Call::call_sponsored { dest, value, gas_limit, data, .. }) = call.is_sub_type() else {
+		return None
+	};
+	let origin = RawOrigin::Signed(who.clone()).into();
+	Pallet::<T>::check_sponsored_call(origin, dest.clone(), *value, *gas_limit, data)
+		.ok()
+		.map(|checked| checked.sponsor)
+}
+
+/// Makes the sponsorship account pay the transaction fee of [`Pallet::call_sponsored`].
+///
+/// This wraps the payment extension `S` of the runtime, e.g.
+/// `pallet_transaction_payment::ChargeTransactionPayment`, and lets it charge the weight,
+/// length and tip of sponsored calls from the sponsorship account instead of the caller. The fee
+/// is withdrawn before dispatch so that a sponsor which can't afford it invalidates the
+/// transaction instead of executing it for free. All other calls are passed through unchanged.
+///
+/// Like `pallet_skip_feeless_payment::SkipCheckIfFeeless` this extension is invisible from the
+/// outside as it encodes and identifies as `S`.
+///
+/// [`Pallet::call_sponsored`]: crate::Pallet::call_sponsored
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct ChargeSponsor<T, S>(pub S, PhantomData<T>);
+
+impl<T, S: StaticTypeInfo> TypeInfo for ChargeSponsor<T, S> {
+	type Identity = S;
+	fn type_info() -> scale_info::Type {
+		S::type_info()
+	}
+}
+
+impl<T, S: fmt::Debug> fmt::Debug for ChargeSponsor<T, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ChargeSponsor<{:?}>", self.0)
+	}
+}
+
+impl<T, S> From<S> for ChargeSponsor<T, S> {
+	fn from(s: S) -> Self {
+		Self(s, PhantomData)
+	}
+}
+
+impl<T: Config, S> ChargeSponsor<T, S>
+where
+	<T as frame_system::Config>::RuntimeCall: IsSubType<Call<T>>,
+{
+	/// The account paying the fee of `call` made by `who`.
+	fn payer(
+		who: &AccountIdOf<T>,
+		call: &<T as frame_system::Config>::RuntimeCall,
+	) -> AccountIdOf<T> {
+		sponsor_of::<T>(who, call).unwrap_or_else(|| who.clone())
+	}
+}
+
+impl<T, S> SignedExtension for ChargeSponsor<T, S>
+where
+	T: Config + Send + Sync,
+	S: SignedExtension<AccountId = AccountIdOf<T>, Call = <T as frame_system::Config>::RuntimeCall>,
+	<T as frame_system::Config>::RuntimeCall: IsSubType<Call<T>>,
+{
+	type AccountId = S::AccountId;
+	type Call = S::Call;
+	type AdditionalSigned = S::AdditionalSigned;
+	type Pre = S::Pre;
+	const IDENTIFIER: &'static str = S::IDENTIFIER;
+
+	fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+		self.0.additional_signed()
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		self.0.validate(&Self::payer(who, call), call, info, len)
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		let payer = Self::payer(who, call);
+		self.0.pre_dispatch(&payer, call, info, len)
+	}
+
+	fn post_dispatch(
+		pre: Option<Self::Pre>,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		S::post_dispatch(pre, info, post_info, len, result)
+	}
+}
+
+/// Lets accounts without any balance make sponsored calls.
+///
+/// `frame_system::CheckNonce` rejects transactions of accounts which are neither provided for
+/// nor sufficient as nobody paid for the storage of their nonce. This wraps it and makes the
+/// signer of a [`Pallet::call_sponsored`] which passes all checks sufficient before its nonce is
+/// checked. The reference is never removed so that the nonce, and with it the replay protection,
+/// outlives the call. All other transactions are passed through unchanged.
+///
+/// Use it in place of `CheckNonce` together with [`ChargeSponsor`] which makes the sponsor pay
+/// for the transaction. This extension encodes and identifies as `CheckNonce`.
+///
+/// [`Pallet::call_sponsored`]: crate::Pallet::call_sponsored
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct CheckSponsoredNonce<T: Config>(pub CheckNonce<T>);
+
+impl<T: Config> TypeInfo for CheckSponsoredNonce<T> {
+	type Identity = CheckNonce<T>;
+	fn type_info() -> scale_info::Type {
+		CheckNonce::<T>::type_info()
+	}
+}
+
+impl<T: Config> fmt::Debug for CheckSponsoredNonce<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "CheckSponsoredNonce<{:?}>", self.0)
+	}
+}
+
+impl<T: Config> From<CheckNonce<T>> for CheckSponsoredNonce<T> {
+	fn from(check: CheckNonce<T>) -> Self {
+		Self(check)
+	}
+}
+
+impl<T: Config> CheckSponsoredNonce<T>
+where
+	<T as frame_system::Config>::RuntimeCall: IsSubType<Call<T>>,
+{
+	/// Whether `who` needs to be made sufficient in order to make `call`.
+	fn provides_account(
+		who: &AccountIdOf<T>,
+		call: &<T as frame_system::Config>::RuntimeCall,
+	) -> bool {
+		!frame_system::Pallet::<T>::account_exists(who) && sponsor_of::<T>(who, call).is_some()
+	}
+}
+
+impl<T> SignedExtension for CheckSponsoredNonce<T>
+where
+	T: Config + Send + Sync,
+	<T as frame_system::Config>::RuntimeCall:
+		IsSubType<Call<T>> + Dispatchable<Info = DispatchInfo>,
+{
+	type AccountId = AccountIdOf<T>;
+	type Call = <T as frame_system::Config>::RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = ();
+	const IDENTIFIER: &'static str = CheckNonce::<T>::IDENTIFIER;
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		self.0.additional_signed()
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		if !Self::provides_account(who, call) {
+			return self.0.validate(who, call, info, len)
+		}
+		// Validation must not change any state.
+		with_transaction_unchecked(|| {
+			frame_system::Pallet::<T>::inc_sufficients(who);
+			TransactionOutcome::Rollback(self.0.validate(who, call, info, len))
+		})
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<(), TransactionValidityError> {
+		if Self::provides_account(who, call) {
+			frame_system::Pallet::<T>::inc_sufficients(who);
+		}
+		self.0.pre_dispatch(who, call, info, len)
+	}
+}
@@ -19,7 +19,7 @@
 
 use crate::{
 	storage::ContractInfo, AccountIdOf, BalanceOf, CodeInfo, Config, Error, Event, HoldReason,
-	Inspect, Origin, Pallet, StorageDeposit as Deposit, System, LOG_TARGET,
+	Inspect, Origin, Pallet, SponsoredDepositOf, StorageDeposit as Deposit, System, LOG_TARGET,
 };
 
 use frame_support::{
@@ -86,6 +86,14 @@ pub trait Ext<T: Config> {
 		amount: &DepositOf<T>,
 		state: &ContractState<T>,
 	) -> Result<(), DispatchError>;
+	/// Takes the share of a refund of `amount` from `contract` that its sponsor is owed.
+	///
+	/// Returns the sponsorship account of `contract` and the part of `amount` that it paid for
+	/// when sponsoring calls. This part is no longer accounted to the sponsor afterwards.
+	fn take_sponsored(
+		contract: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> (T::AccountId, BalanceOf<T>);
 }
 
 /// This [`Ext`] is used for actual on-chain execution when balance needs to be charged.
@@ -130,6 +138,9 @@ pub struct RawMeter<T: Config, E, S: State + Default + Debug> {
 	charges: Vec<Charge<T>>,
 	/// We store the nested state to determine if it has a special limit for sub-call.
 	nested: S,
+	/// The contract whose deposit is paid by the given sponsorship account instead of the
+	/// origin. Only set for root meters.
+	sponsor: Option<(T::AccountId, T::AccountId)>,
 	/// Type parameter only used in impls.
 	_phantom: PhantomData<E>,
 }
@@ -379,6 +390,20 @@ where
 		}
 	}
 
+	/// Create new storage meter which charges the deposit of `contract` from `sponsor` instead
+	/// of the origin.
+	///
+	/// The deposit of all other contracts is still charged from the origin. This tries to
+	/// [`Ext::check_limit`] on `sponsor` and fails if this is not possible.
+	pub fn new_sponsored(
+		contract: T::AccountId,
+		sponsor: T::AccountId,
+		limit: Option<BalanceOf<T>>,
+	) -> Result<Self, DispatchError> {
+		let limit = E::check_limit(&sponsor, limit, Zero::zero())?;
+		Ok(Self { limit, sponsor: Some((contract, sponsor)), ..Default::default() })
+	}
+
 	/// The total amount of deposit that should change hands as result of the execution
 	/// that this meter was passed into. This will also perform all the charges accumulated
 	/// in the whole contract stack.
@@ -391,11 +416,28 @@ where
 			Origin::Root => return Ok(Deposit::Charge(Zero::zero())),
 			Origin::Signed(o) => o,
 		};
-		for charge in self.charges.iter().filter(|c| matches!(c.amount, Deposit::Refund(_))) {
-			E::charge(origin, &charge.contract, &charge.amount, &charge.state)?;
+		// Refunds go back to the sponsor as far as it paid for the deposit of the contract.
+		for charge in self.charges.iter() {
+			let Deposit::Refund(amount) = charge.amount else { continue };
+			let (sponsor, sponsored) = E::take_sponsored(&charge.contract, amount);
+			let rest = amount.saturating_sub(sponsored);
+			// A termination is carried out by the last transfer only.
+			if !sponsored.is_zero() && rest.is_zero() {
+				E::charge(&sponsor, &charge.contract, &charge.amount, &charge.state)?;
+				continue
+			}
+			if !sponsored.is_zero() {
+				let refund = Deposit::Refund(sponsored);
+				E::charge(&sponsor, &charge.contract, &refund, &ContractState::Alive)?;
+			}
+			E::charge(origin, &charge.contract, &Deposit::Refund(rest), &charge.state)?;
 		}
 		for charge in self.charges.iter().filter(|c| matches!(c.amount, Deposit::Charge(_))) {
-			E::charge(origin, &charge.contract, &charge.amount, &charge.state)?;
+			let payer = match &self.sponsor {
+				Some((contract, sponsor)) if *contract == charge.contract => sponsor,
+				_ => origin,
+			};
+			E::charge(payer, &charge.contract, &charge.amount, &charge.state)?;
 		}
 		Ok(self.total_deposit)
 	}
@@ -559,6 +601,12 @@ impl<T: Config> Ext<T> for ReservingExt {
 						amount: *amount,
 					},
 				);
+
+				if *origin == Pallet::<T>::sponsorship_account(contract) {
+					SponsoredDepositOf::<T>::mutate(contract, |deposit| {
+						deposit.saturating_accrue(*amount)
+					});
+				}
 			},
 			Deposit::Refund(amount) => {
 				let transferred = T::Currency::transfer_on_hold(
@@ -604,6 +652,18 @@ impl<T: Config> Ext<T> for ReservingExt {
 		}
 		Ok(())
 	}
+
+	fn take_sponsored(
+		contract: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> (T::AccountId, BalanceOf<T>) {
+		let sponsored = SponsoredDepositOf::<T>::mutate_exists(contract, |deposit| {
+			let taken = deposit.unwrap_or_default().min(amount);
+			*deposit = deposit.map(|d| d.saturating_sub(taken)).filter(|d| !d.is_zero());
+			taken
+		});
+		(Pallet::<T>::sponsorship_account(contract), sponsored)
+	}
 }
 
 mod private {
@@ -617,7 +677,7 @@ mod tests {
 	use super::*;
 	use crate::{
 		exec::AccountIdOf,
-		tests::{Test, ALICE, BOB, CHARLIE},
+		tests::{Test, ALICE, BOB, CHARLIE, DJANGO},
 	};
 	use frame_support::parameter_types;
 	use pretty_assertions::assert_eq;
@@ -626,6 +686,8 @@ mod tests {
 
 	parameter_types! {
 		static TestExtTestValue: TestExt = Default::default();
+		/// The contract, its sponsor and the deposit the sponsor paid for it.
+		static TestSponsoredDeposit: Option<(AccountIdOf<Test>, AccountIdOf<Test>, BalanceOf<Test>)> = None;
 	}
 
 	#[derive(Debug, PartialEq, Eq, Clone)]
@@ -686,10 +748,25 @@ mod tests {
 			});
 			Ok(())
 		}
+
+		fn take_sponsored(
+			contract: &AccountIdOf<Test>,
+			amount: BalanceOf<Test>,
+		) -> (AccountIdOf<Test>, BalanceOf<Test>) {
+			TestSponsoredDeposit::mutate(|sponsored| match sponsored {
+				Some((sponsored, sponsor, deposit)) if sponsored == contract => {
+					let taken = amount.min(*deposit);
+					*deposit -= taken;
+					(sponsor.clone(), taken)
+				},
+				_ => (contract.clone(), 0),
+			})
+		}
 	}
 
 	fn clear_ext() {
-		TestExtTestValue::mutate(|ext| ext.clear())
+		TestExtTestValue::mutate(|ext| ext.clear());
+		TestSponsoredDeposit::take();
 	}
 
 	struct ChargingTestCase {
@@ -734,6 +811,103 @@ mod tests {
 		)
 	}
 
+	#[test]
+	fn sponsored_meter_charges_sponsor() {
+		clear_ext();
+
+		let mut meter = TestMeter::new_sponsored(CHARLIE, BOB, Some(1_000)).unwrap();
+
+		let mut nested0 = meter.nested(BalanceOf::<Test>::zero());
+		nested0.charge(&Diff { bytes_added: 10, ..Default::default() });
+		meter.absorb(nested0, &CHARLIE, None);
+		let mut nested1 = meter.nested(BalanceOf::<Test>::zero());
+		nested1.charge(&Diff { bytes_added: 5, ..Default::default() });
+		meter.absorb(nested1, &DJANGO, None);
+
+		// Only the deposit of the sponsored contract is paid by the sponsor.
+		assert_eq!(
+			meter.try_into_deposit(&Origin::from_account_id(ALICE)).unwrap(),
+			Deposit::Charge(15)
+		);
+		assert_eq!(
+			TestExtTestValue::get(),
+			TestExt {
+				limit_checks: vec![LimitCheck { origin: BOB, limit: 1_000, min_leftover: 0 }],
+				charges: vec![
+					Charge {
+						origin: BOB,
+						contract: CHARLIE,
+						amount: Deposit::Charge(10),
+						state: ContractState::Alive,
+					},
+					Charge {
+						origin: ALICE,
+						contract: DJANGO,
+						amount: Deposit::Charge(5),
+						state: ContractState::Alive,
+					},
+				],
+			}
+		)
+	}
+
+	#[test]
+	fn refunds_go_to_sponsor_first() {
+		let info =
+			|| new_info(StorageInfo { bytes: 100, bytes_deposit: 100, ..Default::default() });
+
+		// Only part of the refund was paid by the sponsor.
+		clear_ext();
+		TestSponsoredDeposit::set(Some((CHARLIE, BOB, 15)));
+		let mut meter = TestMeter::new(&Origin::from_account_id(ALICE), Some(1_000), 0).unwrap();
+		let mut nested0 = meter.nested(BalanceOf::<Test>::zero());
+		nested0.charge(&Diff { bytes_removed: 20, ..Default::default() });
+		meter.absorb(nested0, &CHARLIE, Some(&mut info()));
+		assert_eq!(
+			meter.try_into_deposit(&Origin::from_account_id(ALICE)).unwrap(),
+			Deposit::Refund(20)
+		);
+		assert_eq!(
+			TestExtTestValue::get().charges,
+			vec![
+				Charge {
+					origin: BOB,
+					contract: CHARLIE,
+					amount: Deposit::Refund(15),
+					state: ContractState::Alive,
+				},
+				Charge {
+					origin: ALICE,
+					contract: CHARLIE,
+					amount: Deposit::Refund(5),
+					state: ContractState::Alive,
+				},
+			]
+		);
+		assert_eq!(TestSponsoredDeposit::get(), Some((CHARLIE, BOB, 0)));
+
+		// The whole deposit was paid by the sponsor which also receives the termination.
+		clear_ext();
+		TestSponsoredDeposit::set(Some((CHARLIE, BOB, 1_000)));
+		let mut meter = TestMeter::new(&Origin::from_account_id(ALICE), Some(1_000), 0).unwrap();
+		let mut nested0 = meter.nested(BalanceOf::<Test>::zero());
+		nested0.terminate(&info(), DJANGO);
+		meter.absorb(nested0, &CHARLIE, None);
+		assert_eq!(
+			meter.try_into_deposit(&Origin::from_account_id(ALICE)).unwrap(),
+			Deposit::Refund(100)
+		);
+		assert_eq!(
+			TestExtTestValue::get().charges,
+			vec![Charge {
+				origin: BOB,
+				contract: CHARLIE,
+				amount: Deposit::Refund(100),
+				state: ContractState::Terminated { beneficiary: DJANGO },
+			}]
+		);
+	}
+
 	#[test]
 	fn empty_charge_works() {
 		clear_ext();
@@ -28,23 +28,27 @@ use crate::{
 		ChainExtension, Environment, Ext, InitState, RegisteredChainExtension,
 		Result as ExtensionResult, RetVal, ReturnFlags, SysConfig,
 	},
+	debug::{self, CallKind, CallTrace, StorageWrite, TracedEvent},
+	eth,
 	exec::{Frame, Key},
 	migration::codegen::LATEST_MIGRATION_VERSION,
 	primitives::CodeUploadReturnValue,
 	storage::DeletionQueueManager,
 	tests::test_utils::{get_contract, get_contract_checked},
-	wasm::{Determinism, LoadingMode, ReturnErrorCode as RuntimeReturnCode},
+	wasm::{CodeType, Determinism, LoadingMode, ReturnErrorCode as RuntimeReturnCode},
 	weights::WeightInfo,
-	Array, BalanceOf, Code, CodeHash, CodeInfoOf, CollectEvents, Config, ContractInfo,
-	ContractInfoOf, DebugInfo, DefaultAddressGenerator, DeletionQueueCounter, Error, HoldReason,
-	MigrationInProgress, Origin, Pallet, PristineCode, Schedule,
+	AddressMapper, Array, BalanceOf, ChargeSponsor, CheckSponsoredNonce, Code, CodeHash,
+	CodeInfoOf, CollectEvents, Config, ContractInfo, ContractInfoOf, ContractOfAddress, DebugInfo,
+	DefaultAddressGenerator, DefaultAddressMapper, DeletionQueueCounter, Error, HoldReason,
+	MigrationInProgress, Origin, Pallet, PristineCode, Schedule, SponsorValueFreeCalls,
+	SponsoredCallsOf, SponsoredDepositOf, Sponsorship, SponsorshipOf,
 };
 use assert_matches::assert_matches;
 use codec::{Decode, Encode};
 use frame_support::{
 	assert_err, assert_err_ignore_postinfo, assert_err_with_weight, assert_noop, assert_ok,
 	derive_impl,
-	dispatch::{DispatchErrorWithPostInfo, PostDispatchInfo},
+	dispatch::{DispatchErrorWithPostInfo, GetDispatchInfo, Pays, PostDispatchInfo},
 	pallet_prelude::EnsureOrigin,
 	parameter_types,
 	storage::child,
@@ -56,14 +60,13 @@ use frame_support::{
 	weights::{constants::WEIGHT_REF_TIME_PER_SECOND, Weight, WeightMeter},
 };
 use frame_system::{EventRecord, Phase};
-use pallet_contracts_fixtures::compile_module;
 use pretty_assertions::{assert_eq, assert_ne};
-use sp_core::ByteArray;
+use sp_core::{ecdsa, ByteArray, Pair, H160};
 use sp_io::hashing::blake2_256;
 use sp_keystore::{testing::MemoryKeystore, KeystoreExt};
 use sp_runtime::{
 	testing::H256,
-	traits::{BlakeTwo256, Convert, Hash, IdentityLookup},
+	traits::{BlakeTwo256, Convert, Dispatchable, Hash, IdentityLookup},
 	AccountId32, BuildStorage, DispatchError, Perbill, TokenError,
 };
 
@@ -508,6 +511,7 @@ impl Config for Test {
 	type CodeHashLockupDepositPercent = CodeHashLockupDepositPercent;
 	type MaxDelegateDependencies = MaxDelegateDependencies;
 	type Debug = TestDebug;
+	type SponsorshipPolicy = SponsorValueFreeCalls;
 }
 
 pub const ALICE: AccountId32 = AccountId32::new([1u8; 32]);
@@ -578,6 +582,19 @@ impl ExtBuilder {
 	}
 }
 
+/// Load the fixture `fixture_name` compiled for the engine the current test runs on.
+///
+/// Tests run on Wasm unless they are rerun by `riscv::on_polkavm`.
+fn compile_module<T: frame_system::Config>(
+	fixture_name: &str,
+) -> Result<(Vec<u8>, <T::Hashing as Hash>::Output), impl std::fmt::Debug> {
+	#[cfg(feature = "riscv")]
+	if riscv::ON_POLKAVM.with(|on| on.get()) {
+		return pallet_contracts_fixtures::compile_riscv_module::<T>(fixture_name)
+	}
+	pallet_contracts_fixtures::compile_module::<T>(fixture_name)
+}
+
 fn initialize_block(number: u64) {
 	System::reset_events();
 	System::initialize(&number, &[0u8; 32].into(), &Default::default());
@@ -2517,6 +2534,33 @@ fn upload_code_works() {
 	});
 }
 
+#[test]
+fn upload_code_records_code_type() {
+	let (wasm, code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+
+		assert_ok!(Contracts::bare_upload_code(ALICE, wasm, None, Determinism::Enforced));
+		assert_eq!(CodeInfoOf::<Test>::get(code_hash).unwrap().code_type(), CodeType::Wasm);
+	});
+}
+
+#[test]
+fn upload_invalid_polkavm_code_fails() {
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+
+		// Has the PolkaVM magic but isn't a valid program blob.
+		let mut code = b"PVM\0".to_vec();
+		code.extend_from_slice(&[1, 2, 3, 4]);
+		assert_err!(
+			Contracts::bare_upload_code(ALICE, code, None, Determinism::Enforced),
+			<Error<Test>>::CodeRejected,
+		);
+	});
+}
+
 #[test]
 fn upload_code_limit_too_low() {
 	let (wasm, _code_hash) = compile_module::<Test>("dummy").unwrap();
@@ -4249,3 +4293,1059 @@ fn gas_consumed_is_linear_for_nested_calls() {
 		assert_eq!(gas_max, gas_0 + gas_per_recursion * max_call_depth as u64);
 	});
 }
+
+/// Signs an Ethereum transaction for the test chain with `pair`.
+fn eth_transaction(
+	pair: &ecdsa::Pair,
+	nonce: u64,
+	to: Option<H160>,
+	value: u64,
+	data: Vec<u8>,
+) -> Vec<u8> {
+	eth_transaction_with_tip(pair, nonce, to, value, data, 0)
+}
+
+/// Signs an Ethereum transaction which offers `tip` per gas on top of the base price.
+fn eth_transaction_with_tip(
+	pair: &ecdsa::Pair,
+	nonce: u64,
+	to: Option<H160>,
+	value: u64,
+	data: Vec<u8>,
+	tip: u64,
+) -> Vec<u8> {
+	let tx = eth::Transaction {
+		tx_type: eth::TransactionType::DynamicFee,
+		chain_id: <Test as Config>::ChainId::get(),
+		nonce: nonce.into(),
+		max_priority_fee_per_gas: tip.into(),
+		gas_price: (Contracts::eth_gas_price() + tip).into(),
+		gas: ETH_GAS_LIMIT.into(),
+		to,
+		value: value.into(),
+		data,
+		access_list: rlp::EMPTY_LIST_RLP.to_vec(),
+	};
+	tx.encode_signed(&pair.sign_prehashed(&tx.signing_hash().0).0)
+}
+
+const ETH_GAS_LIMIT: u64 = 10_000_000;
+
+/// Creates a key pair and funds the account it maps to.
+fn eth_signer() -> (ecdsa::Pair, H160, AccountId32) {
+	let pair = ecdsa::Pair::from_seed(&[7u8; 32]);
+	let address =
+		Contracts::eth_check_transaction(&eth_transaction(&pair, 0, Some(H160::zero()), 0, vec![]))
+			.unwrap();
+	let account = <Test as Config>::AddressMapper::to_account_id(&address);
+	let _ = <Test as Config>::Currency::set_balance(&account, 1_000_000_000_000_000);
+	(pair, address, account)
+}
+
+fn last_eth_transaction() -> crate::Event<Test> {
+	System::events()
+		.into_iter()
+		.rev()
+		.find_map(|record| match record.event {
+			RuntimeEvent::Contracts(event @ crate::Event::EthTransactionExecuted { .. }) =>
+				Some(event),
+			_ => None,
+		})
+		.expect("An Ethereum transaction was executed")
+}
+
+#[test]
+fn default_address_mapper_roundtrips() {
+	let address = H160::repeat_byte(0x42);
+	let account: AccountId32 = DefaultAddressMapper::to_account_id(&address);
+	assert_eq!(<DefaultAddressMapper as AddressMapper<AccountId32>>::to_address(&account), address);
+
+	// Native accounts map to a hash of the account id.
+	let address = <DefaultAddressMapper as AddressMapper<AccountId32>>::to_address(&ALICE);
+	assert_eq!(address.as_bytes(), &sp_io::hashing::keccak_256(ALICE.as_ref())[12..]);
+}
+
+#[test]
+fn eth_transact_instantiate_and_call_works() {
+	let (wasm, _code_hash) = compile_module::<Test>("event_size").unwrap();
+
+	ExtBuilder::default().existential_deposit(1).build().execute_with(|| {
+		let (pair, address, account) = eth_signer();
+		let balance = <Test as Config>::Currency::balance(&account);
+		System::note_finished_initialize();
+
+		// Instantiate the contract.
+		let payload =
+			eth_transaction(&pair, 0, None, 1_000, Code::<CodeHash<Test>>::Upload(wasm).encode());
+		assert_ok!(Contracts::eth_transact(RuntimeOrigin::none(), payload.clone()));
+		let crate::Event::EthTransactionExecuted {
+			hash, from, to, contract_address, success, ..
+		} = last_eth_transaction()
+		else {
+			unreachable!()
+		};
+		assert_eq!(hash, H256(sp_io::hashing::keccak_256(&payload)));
+		assert_eq!(from, address);
+		assert_eq!(to, None);
+		assert!(success);
+		let contract_address = contract_address.unwrap();
+		let contract = ContractOfAddress::<Test>::get(contract_address).unwrap();
+		assert_eq!(Contracts::address_of(&contract), contract_address);
+		assert_eq!(Contracts::account_of(&contract_address), contract);
+		assert_eq!(System::account_nonce(&account), 1);
+		System::note_applied_extrinsic(&Ok(().into()), Default::default());
+
+		// Call the contract which emits an event.
+		let payload = eth_transaction(&pair, 1, Some(contract_address), 0, 4u32.encode());
+		assert_ok!(Contracts::eth_transact(RuntimeOrigin::none(), payload.clone()));
+		let crate::Event::EthTransactionExecuted { gas_used, success, .. } = last_eth_transaction()
+		else {
+			unreachable!()
+		};
+		assert!(success);
+		assert!(gas_used > 0);
+		assert_eq!(System::account_nonce(&account), 2);
+		System::note_applied_extrinsic(&Ok(().into()), Default::default());
+
+		// The value and the fees were deducted.
+		assert!(<Test as Config>::Currency::balance(&account) < balance - 1_000);
+
+		let receipts = Contracts::eth_receipts();
+		assert_eq!(receipts.len(), 2);
+		assert_eq!(receipts[0].transaction_index, 0);
+		assert_eq!(receipts[0].contract_address, Some(contract_address));
+		assert!(receipts[0].logs.is_empty());
+		assert_eq!(receipts[1].transaction_hash, H256(sp_io::hashing::keccak_256(&payload)));
+		assert_eq!(receipts[1].transaction_index, 1);
+		assert_eq!(receipts[1].to, Some(contract_address));
+		assert_eq!(
+			receipts[1].logs,
+			vec![eth::Log { address: contract_address, topics: vec![], data: vec![0u8; 4] }]
+		);
+	});
+}
+
+#[test]
+fn eth_transact_validate_unsigned_works() {
+	use sp_runtime::{
+		traits::ValidateUnsigned,
+		transaction_validity::{InvalidTransaction, TransactionSource},
+	};
+	let (wasm, _code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(1).build().execute_with(|| {
+		let (pair, _address, account) = eth_signer();
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let addr = builder::bare_instantiate(Code::Upload(wasm)).build_and_unwrap_account_id();
+		let dest = Some(Contracts::address_of(&addr));
+		let validate = |payload| {
+			<Contracts as ValidateUnsigned>::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::eth_transact { payload },
+			)
+		};
+
+		let valid = validate(eth_transaction(&pair, 0, dest, 0, vec![])).unwrap();
+		assert_eq!(valid.provides, vec![(&account, 0u64).encode()]);
+		assert!(valid.requires.is_empty());
+		assert_eq!(valid.priority, 0);
+		assert_eq!(valid.longevity, crate::ETH_TRANSACTION_LONGEVITY);
+
+		// Transactions are prioritized by their tip per gas.
+		let valid = validate(eth_transaction_with_tip(&pair, 0, dest, 0, vec![], 3)).unwrap();
+		assert_eq!(valid.priority, 3);
+
+		let valid = validate(eth_transaction(&pair, 2, dest, 0, vec![])).unwrap();
+		assert_eq!(valid.requires, vec![(&account, 1u64).encode()]);
+		assert_err!(
+			<Contracts as ValidateUnsigned>::pre_dispatch(&crate::Call::eth_transact {
+				payload: eth_transaction(&pair, 2, dest, 0, vec![])
+			}),
+			InvalidTransaction::Future
+		);
+
+		// Only valid for its nonce.
+		System::inc_account_nonce(&account);
+		assert_err!(
+			validate(eth_transaction(&pair, 0, dest, 0, vec![])),
+			InvalidTransaction::Stale
+		);
+
+		// The signer needs to be able to pay for value, the gas limit and the deposit limit.
+		let balance = <Test as Config>::Currency::balance(&account);
+		assert_err!(
+			validate(eth_transaction(&pair, 1, dest, balance, vec![])),
+			InvalidTransaction::Payment
+		);
+		let max_fee = Contracts::eth_gas_price() * ETH_GAS_LIMIT;
+		let _ = <Test as Config>::Currency::set_balance(
+			&account,
+			max_fee + DefaultDepositLimit::get() + 1,
+		);
+		assert_ok!(validate(eth_transaction(&pair, 1, dest, 0, vec![])));
+		assert_err!(
+			validate(eth_transaction(&pair, 1, dest, 1, vec![])),
+			InvalidTransaction::Payment
+		);
+
+		// Malformed payload.
+		assert_err!(validate(vec![1, 2, 3]), InvalidTransaction::Call);
+
+		// Creation transactions need to contain a valid `Code`.
+		assert_err!(
+			validate(eth_transaction(&pair, 1, None, 0, vec![42])),
+			InvalidTransaction::Call
+		);
+	});
+}
+
+#[test]
+fn eth_transact_charges_failed_transactions() {
+	let (wasm, _code_hash) = compile_module::<Test>("ok_trap_revert").unwrap();
+
+	ExtBuilder::default().existential_deposit(1).build().execute_with(|| {
+		let (pair, _address, account) = eth_signer();
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let addr = builder::bare_instantiate(Code::Upload(wasm)).build_and_unwrap_account_id();
+		let dest = Some(Contracts::address_of(&addr));
+		let balance = <Test as Config>::Currency::balance(&account);
+
+		// The contract traps.
+		let payload = eth_transaction(&pair, 0, dest, 0, vec![2]);
+		assert_ok!(Contracts::eth_transact(RuntimeOrigin::none(), payload.clone()));
+		let crate::Event::EthTransactionExecuted { gas_used, success, .. } = last_eth_transaction()
+		else {
+			unreachable!()
+		};
+		assert!(!success);
+		assert_eq!(System::account_nonce(&account), 1);
+		assert_eq!(
+			<Test as Config>::Currency::balance(&account),
+			balance - gas_used * Contracts::eth_gas_price()
+		);
+		assert_eq!(
+			test_utils::get_balance_on_hold(&HoldReason::EthTransactionFee.into(), &account),
+			0
+		);
+
+		// The same transaction can't be executed again.
+		assert_err_ignore_postinfo!(
+			Contracts::eth_transact(RuntimeOrigin::none(), payload),
+			<Error<Test>>::InvalidEthTransaction
+		);
+
+		// The contract reverts.
+		let payload = eth_transaction(&pair, 1, dest, 0, vec![1]);
+		assert_ok!(Contracts::eth_transact(RuntimeOrigin::none(), payload));
+		let crate::Event::EthTransactionExecuted { success, .. } = last_eth_transaction() else {
+			unreachable!()
+		};
+		assert!(!success);
+		assert_eq!(System::account_nonce(&account), 2);
+	});
+}
+
+#[test]
+fn eth_transact_releases_unused_fee() {
+	let (wasm, _code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(1).build().execute_with(|| {
+		let (pair, _address, account) = eth_signer();
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let addr = builder::bare_instantiate(Code::Upload(wasm)).build_and_unwrap_account_id();
+		let dest = Some(Contracts::address_of(&addr));
+		let balance = <Test as Config>::Currency::balance(&account);
+		let issuance = <Test as Config>::Currency::total_issuance();
+
+		let tip = 2;
+		let payload = eth_transaction_with_tip(&pair, 0, dest, 0, vec![], tip);
+		assert_ok!(Contracts::eth_transact(RuntimeOrigin::none(), payload));
+		let crate::Event::EthTransactionExecuted { gas_used, success, .. } = last_eth_transaction()
+		else {
+			unreachable!()
+		};
+		assert!(success);
+		assert!(gas_used < ETH_GAS_LIMIT);
+
+		// Only the consumed gas is paid for, including the tip. The rest of the held max fee is
+		// released.
+		let fee = gas_used * (Contracts::eth_gas_price() + tip);
+		assert_eq!(<Test as Config>::Currency::balance(&account), balance - fee);
+		assert_eq!(
+			test_utils::get_balance_on_hold(&HoldReason::EthTransactionFee.into(), &account),
+			0
+		);
+		// The fee is burned.
+		assert_eq!(<Test as Config>::Currency::total_issuance(), issuance - fee);
+	});
+}
+
+#[test]
+fn eth_transact_pre_dispatch_checks_max_fee_and_value() {
+	use sp_runtime::{traits::ValidateUnsigned, transaction_validity::InvalidTransaction};
+	let (wasm, _code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(1).build().execute_with(|| {
+		let (pair, _address, account) = eth_signer();
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let addr = builder::bare_instantiate(Code::Upload(wasm)).build_and_unwrap_account_id();
+		let dest = Some(Contracts::address_of(&addr));
+		let pre_dispatch = |payload| {
+			<Contracts as ValidateUnsigned>::pre_dispatch(&crate::Call::eth_transact { payload })
+		};
+
+		// Just enough to pay for the max fee, a value of 10 and the deposit limit.
+		let max_fee = Contracts::eth_gas_price() * ETH_GAS_LIMIT;
+		let _ = <Test as Config>::Currency::set_balance(
+			&account,
+			max_fee + 10 + DefaultDepositLimit::get() + 1,
+		);
+		assert_ok!(pre_dispatch(eth_transaction(&pair, 0, dest, 10, vec![])));
+		assert_err!(
+			pre_dispatch(eth_transaction(&pair, 0, dest, 11, vec![])),
+			InvalidTransaction::Payment
+		);
+		// A tip raises the max fee.
+		assert_err!(
+			pre_dispatch(eth_transaction_with_tip(&pair, 0, dest, 10, vec![], 1)),
+			InvalidTransaction::Payment
+		);
+		assert_eq!(System::account_nonce(&account), 0);
+	});
+}
+
+#[test]
+fn eth_transact_priority_follows_tip() {
+	use sp_runtime::{traits::ValidateUnsigned, transaction_validity::TransactionSource};
+
+	ExtBuilder::default().existential_deposit(1).build().execute_with(|| {
+		let (pair, _address, _account) = eth_signer();
+		let other = ecdsa::Pair::from_seed(&[8u8; 32]);
+		let other_address = Contracts::eth_check_transaction(&eth_transaction(
+			&other,
+			0,
+			Some(H160::zero()),
+			0,
+			vec![],
+		))
+		.unwrap();
+		let _ = <Test as Config>::Currency::set_balance(
+			&<Test as Config>::AddressMapper::to_account_id(&other_address),
+			1_000_000_000_000_000,
+		);
+		let priority = |payload| {
+			<Contracts as ValidateUnsigned>::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::eth_transact { payload },
+			)
+			.unwrap()
+			.priority
+		};
+
+		let low = priority(eth_transaction_with_tip(&pair, 0, Some(H160::zero()), 0, vec![], 1));
+		let high = priority(eth_transaction_with_tip(&other, 0, Some(H160::zero()), 0, vec![], 5));
+		assert_eq!(low, 1);
+		assert_eq!(high, 5);
+		assert!(high > low);
+	});
+}
+
+#[test]
+fn eth_transact_rejects_signed_origin() {
+	ExtBuilder::default().existential_deposit(1).build().execute_with(|| {
+		let (pair, _address, _account) = eth_signer();
+		assert_noop!(
+			Contracts::eth_transact(
+				RuntimeOrigin::signed(ALICE),
+				eth_transaction(&pair, 0, Some(H160::zero()), 0, vec![])
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn terminate_removes_address_mapping() {
+	let (wasm, _code_hash) = compile_module::<Test>("self_destruct").unwrap();
+
+	ExtBuilder::default().existential_deposit(1).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let addr = builder::bare_instantiate(Code::Upload(wasm))
+			.value(100_000)
+			.build_and_unwrap_account_id();
+		let address = Contracts::address_of(&addr);
+		assert_eq!(ContractOfAddress::<Test>::get(address), Some(addr.clone()));
+
+		assert_ok!(builder::call(addr.clone()).build());
+		assert_eq!(ContractOfAddress::<Test>::get(address), None);
+	});
+}
+
+#[test]
+fn eth_call_works() {
+	let (wasm, _code_hash) = compile_module::<Test>("ok_trap_revert").unwrap();
+
+	ExtBuilder::default().existential_deposit(1).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let addr = builder::bare_instantiate(Code::Upload(wasm)).build_and_unwrap_account_id();
+		let to = Some(Contracts::address_of(&addr));
+
+		// The caller doesn't need to have any balance nor sign anything.
+		let from = H160::repeat_byte(1);
+		let result = Contracts::eth_call(from, to, 0.into(), None, vec![0]);
+		assert!(!result.result.unwrap().did_revert());
+		assert!(result.gas_required > 0);
+		assert_eq!(result.contract_address, None);
+
+		let result = Contracts::eth_call(from, to, 0.into(), None, vec![1]);
+		assert!(result.result.unwrap().did_revert());
+
+		// The returned gas is sufficient.
+		let gas_required = Contracts::eth_call(from, to, 0.into(), None, vec![0]).gas_required;
+		let result = Contracts::eth_call(from, to, 0.into(), Some(gas_required), vec![0]);
+		assert_ok!(result.result);
+		let result = Contracts::eth_call(from, to, 0.into(), Some(gas_required / 2), vec![0]);
+		assert_err!(result.result, <Error<Test>>::OutOfGas);
+
+		// Nothing is persisted.
+		let from_account = <Test as Config>::AddressMapper::to_account_id(&from);
+		assert_eq!(System::account_nonce(&from_account), 0);
+	});
+}
+
+#[test]
+fn call_tracer_records_call_tree() {
+	let (caller_code, _) = compile_module::<Test>("call").unwrap();
+	let (callee_code, callee_hash) = compile_module::<Test>("store_call").unwrap();
+
+	ExtBuilder::default().existential_deposit(1).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let caller =
+			builder::bare_instantiate(Code::Upload(caller_code)).build_and_unwrap_account_id();
+		let callee =
+			builder::bare_instantiate(Code::Upload(callee_code)).build_and_unwrap_account_id();
+
+		// Nothing is recorded unless tracing is enabled.
+		assert!(!debug::call_tracer::is_active::<Test>());
+
+		let input = (4u32, &callee).encode();
+		let (result, traces) = debug::trace::<Test, _>(|| {
+			assert!(debug::call_tracer::is_active::<Test>());
+			builder::call(caller.clone()).data(input.clone()).build()
+		});
+		assert_ok!(result);
+		assert!(!debug::call_tracer::is_active::<Test>());
+
+		assert_eq!(traces.len(), 1);
+		let trace = &traces[0];
+		assert_eq!(trace.kind, CallKind::Call);
+		assert_eq!(trace.from, Some(ALICE));
+		assert_eq!(trace.to, caller);
+		assert_eq!(trace.input, input);
+		assert!(!trace.reverted);
+		assert_eq!(trace.error, None);
+		assert!(trace.storage_writes.is_empty());
+
+		assert_eq!(trace.calls.len(), 1);
+		let nested = &trace.calls[0];
+		let mut key = [0u8; 32];
+		key[0] = 1;
+		assert_eq!(
+			nested,
+			&CallTrace {
+				kind: CallKind::Call,
+				from: Some(caller.clone()),
+				to: callee.clone(),
+				code_hash: callee_hash,
+				value: 0,
+				input: 4u32.encode(),
+				output: vec![],
+				gas_used: nested.gas_used,
+				reverted: false,
+				error: None,
+				storage_writes: vec![StorageWrite {
+					key: key.to_vec(),
+					old_value: None,
+					new_value: Some(vec![0u8; 4]),
+				}],
+				events: vec![],
+				calls: vec![],
+			}
+		);
+		assert!(nested.gas_used.ref_time() > 0);
+		assert!(trace.gas_used.ref_time() > nested.gas_used.ref_time());
+	});
+}
+
+#[test]
+fn call_tracer_records_events_and_failures() {
+	let (event_code, _) = compile_module::<Test>("event_and_return_on_deploy").unwrap();
+	let (revert_code, _) = compile_module::<Test>("ok_trap_revert").unwrap();
+
+	ExtBuilder::default().existential_deposit(1).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+
+		let (result, traces) = debug::trace::<Test, _>(|| {
+			builder::bare_instantiate(Code::Upload(event_code)).value(100).build()
+		});
+		let addr = result.result.unwrap().account_id;
+		assert_eq!(traces.len(), 1);
+		assert_eq!(traces[0].kind, CallKind::Instantiate);
+		assert_eq!(traces[0].to, addr);
+		assert_eq!(traces[0].value, 100);
+		assert_eq!(traces[0].output, vec![1, 2, 3, 4]);
+		assert_eq!(traces[0].events, vec![TracedEvent { topics: vec![], data: vec![1, 2, 3, 4] }]);
+
+		let addr =
+			builder::bare_instantiate(Code::Upload(revert_code)).build_and_unwrap_account_id();
+		let (_, traces) =
+			debug::trace::<Test, _>(|| builder::bare_call(addr.clone()).data(vec![1]).build());
+		assert!(traces[0].reverted);
+		assert_eq!(traces[0].error, None);
+
+		let (_, traces) =
+			debug::trace::<Test, _>(|| builder::bare_call(addr.clone()).data(vec![2]).build());
+		assert!(!traces[0].reverted);
+		assert_eq!(traces[0].error, Some(<Error<Test>>::ContractTrapped.into()));
+	});
+}
+
+#[test]
+fn call_tracer_replays_preceding_extrinsics() {
+	let (wasm, _) = compile_module::<Test>("store_call").unwrap();
+
+	ExtBuilder::default().existential_deposit(1).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let addr = builder::bare_instantiate(Code::Upload(wasm)).build_and_unwrap_account_id();
+
+		let store = |len: u32| {
+			let addr = addr.clone();
+			move || assert_ok!(builder::call(addr).data(len.encode()).build())
+		};
+		let extrinsics = vec![store(4), store(8)];
+		let traces =
+			debug::call_tracer::trace_extrinsic::<Test, _>(extrinsics, 1, |xt| xt()).unwrap();
+
+		// The first extrinsic was applied but only the second one was traced.
+		assert_eq!(traces.len(), 1);
+		assert_eq!(traces[0].storage_writes[0].old_value, Some(vec![0u8; 4]));
+		assert_eq!(traces[0].storage_writes[0].new_value, Some(vec![0u8; 8]));
+
+		assert_eq!(
+			debug::call_tracer::trace_extrinsic::<Test, _>(vec![store(4)], 1, |xt| xt()),
+			None
+		);
+	});
+}
+
+#[test]
+fn call_tracer_keeps_pre_runtime_digests_of_replayed_header() {
+	use sp_runtime::{traits::Header as _, Digest, DigestItem};
+
+	let pre_runtime = DigestItem::PreRuntime(*b"test", vec![1]);
+	let header = frame_system::pallet_prelude::HeaderFor::<Test>::new(
+		7,
+		H256::repeat_byte(1),
+		H256::repeat_byte(2),
+		H256::repeat_byte(3),
+		Digest {
+			logs: vec![
+				pre_runtime.clone(),
+				DigestItem::Consensus(*b"test", vec![2]),
+				DigestItem::Seal(*b"test", vec![3]),
+			],
+		},
+	);
+	let replayed = debug::call_tracer::replay_header::<Test>(&header);
+	assert_eq!(*replayed.number(), 7);
+	assert_eq!(*replayed.parent_hash(), H256::repeat_byte(3));
+	assert_eq!(*replayed.state_root(), H256::zero());
+	assert_eq!(*replayed.extrinsics_root(), H256::zero());
+	assert_eq!(replayed.digest().logs(), &[pre_runtime]);
+}
+
+#[test]
+fn contracts_can_opt_in_to_sponsoring() {
+	let (wasm, _code_hash) = compile_module::<Test>("call_runtime").unwrap();
+
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let addr = builder::bare_instantiate(Code::Upload(wasm)).build_and_unwrap_account_id();
+		let sponsorship =
+			Sponsorship::<Test> { max_fee: 1_000, max_deposit: 500, max_calls: 1, period: 0 };
+
+		// Only contracts can sponsor calls.
+		assert_noop!(
+			Contracts::set_sponsorship(RuntimeOrigin::signed(ALICE), Some(sponsorship.clone())),
+			<Error<Test>>::ContractNotFound,
+		);
+		assert_noop!(
+			Contracts::fund_sponsorship(RuntimeOrigin::signed(ALICE), addr.clone(), 100),
+			<Error<Test>>::SponsorshipNotFound,
+		);
+
+		// The contract opts in by calling into the runtime.
+		let call = RuntimeCall::Contracts(crate::Call::set_sponsorship {
+			sponsorship: Some(sponsorship.clone()),
+		});
+		let result = builder::bare_call(addr.clone()).data(call.encode()).build_and_unwrap_result();
+		assert_eq!(u32::decode(&mut result.data.as_ref()).unwrap(), 0);
+		assert_eq!(SponsorshipOf::<Test>::get(&addr), Some(sponsorship));
+
+		initialize_block(2);
+		let sponsor = Contracts::sponsorship_account(&addr);
+		assert_ok!(Contracts::fund_sponsorship(RuntimeOrigin::signed(ALICE), addr.clone(), 100));
+		assert_eq!(<Test as Config>::Currency::total_balance(&sponsor), 100);
+
+		// Stopping the sponsorship returns the remaining balance to the contract.
+		let before = <Test as Config>::Currency::total_balance(&addr);
+		assert_ok!(Contracts::set_sponsorship(RuntimeOrigin::signed(addr.clone()), None));
+		assert_eq!(SponsorshipOf::<Test>::get(&addr), None);
+		assert_eq!(<Test as Config>::Currency::total_balance(&sponsor), 0);
+		assert_eq!(<Test as Config>::Currency::total_balance(&addr), before + 100);
+		assert_noop!(
+			Contracts::set_sponsorship(RuntimeOrigin::signed(addr.clone()), None),
+			<Error<Test>>::SponsorshipNotFound,
+		);
+
+		let events = System::events().into_iter().map(|e| e.event).collect::<Vec<_>>();
+		assert!(events.contains(&RuntimeEvent::Contracts(crate::Event::SponsorshipFunded {
+			contract: addr.clone(),
+			from: ALICE,
+			amount: 100,
+		})));
+		assert!(events.contains(&RuntimeEvent::Contracts(crate::Event::SponsorshipRemoved {
+			contract: addr,
+			refunded: 100,
+		})));
+	});
+}
+
+/// A payment extension which only reports the account it would charge.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, scale_info::TypeInfo)]
+struct ReportPayer;
+
+impl sp_runtime::traits::SignedExtension for ReportPayer {
+	type AccountId = AccountId32;
+	type Call = RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = AccountId32;
+	const IDENTIFIER: &'static str = "ReportPayer";
+
+	fn additional_signed(
+		&self,
+	) -> Result<(), sp_runtime::transaction_validity::TransactionValidityError> {
+		Ok(())
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &AccountId32,
+		_call: &RuntimeCall,
+		_info: &sp_runtime::traits::DispatchInfoOf<RuntimeCall>,
+		_len: usize,
+	) -> Result<AccountId32, sp_runtime::transaction_validity::TransactionValidityError> {
+		Ok(who.clone())
+	}
+}
+
+#[test]
+fn call_sponsored_charges_sponsor() {
+	let (wasm, _code_hash) = compile_module::<Test>("store_call").unwrap();
+
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let _ = <Test as Config>::Currency::set_balance(&CHARLIE, 50);
+		let addr = builder::bare_instantiate(Code::Upload(wasm)).build_and_unwrap_account_id();
+		let sponsor = Contracts::sponsorship_account(&addr);
+		let max_fee = GAS_LIMIT.ref_time() * 2;
+		assert_ok!(Contracts::set_sponsorship(
+			RuntimeOrigin::signed(addr.clone()),
+			Some(Sponsorship { max_fee, max_deposit: 1_000, max_calls: 1, period: 0 }),
+		));
+		let _ = <Test as Config>::Currency::set_balance(&sponsor, max_fee * 2);
+
+		let call = |value, gas_limit| {
+			RuntimeCall::Contracts(crate::Call::call_sponsored {
+				dest: addr.clone(),
+				value,
+				gas_limit,
+				storage_deposit_limit: None,
+				data: 100u32.encode(),
+			})
+		};
+
+		// Calls which are not sponsored need to be paid for by the caller.
+		let payer = |call: RuntimeCall| {
+			use sp_runtime::traits::SignedExtension;
+			let info = call.get_dispatch_info();
+			ChargeSponsor::<Test, _>::from(ReportPayer).pre_dispatch(&CHARLIE, &call, &info, 0)
+		};
+		let origin = RuntimeOrigin::signed(CHARLIE);
+		assert_eq!(payer(call(1, GAS_LIMIT)), Ok(CHARLIE));
+		assert_eq!(payer(call(0, GAS_LIMIT * 3)), Ok(CHARLIE));
+		assert_err_ignore_postinfo!(
+			call(1, GAS_LIMIT).dispatch(origin.clone()),
+			<Error<Test>>::NotSponsored,
+		);
+		assert_eq!(payer(call(0, GAS_LIMIT)), Ok(sponsor.clone()));
+
+		initialize_block(2);
+		let sponsor_before = <Test as Config>::Currency::total_balance(&sponsor);
+		let deposit_before = get_contract(&addr).total_deposit();
+		let info = call(0, GAS_LIMIT).dispatch(origin).unwrap();
+		assert_eq!(info.pays_fee, Pays::Yes);
+
+		// The sponsor paid for the deposit while the caller paid nothing.
+		let deposit = get_contract(&addr).total_deposit() - deposit_before;
+		assert!(deposit > 0);
+		assert_eq!(<Test as Config>::Currency::total_balance(&CHARLIE), 50);
+		assert_eq!(<Test as Config>::Currency::total_balance(&sponsor), sponsor_before - deposit);
+		assert_eq!(SponsoredDepositOf::<Test>::get(&addr), deposit);
+		assert!(System::events().into_iter().any(|e| e.event ==
+			RuntimeEvent::Contracts(crate::Event::StorageDepositTransferredAndHeld {
+				from: sponsor.clone(),
+				to: addr.clone(),
+				amount: deposit,
+			})));
+		assert_eq!(
+			System::events().last().unwrap().event,
+			RuntimeEvent::Contracts(crate::Event::CallSponsored {
+				contract: addr.clone(),
+				caller: CHARLIE,
+				success: true,
+			}),
+		);
+	});
+}
+
+#[test]
+fn sponsored_deposit_is_refunded_to_sponsor() {
+	let (wasm, _code_hash) = compile_module::<Test>("store_call").unwrap();
+
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let _ = <Test as Config>::Currency::set_balance(&CHARLIE, 1_000_000);
+		let addr = builder::bare_instantiate(Code::Upload(wasm)).build_and_unwrap_account_id();
+		let sponsor = Contracts::sponsorship_account(&addr);
+		assert_ok!(Contracts::set_sponsorship(
+			RuntimeOrigin::signed(addr.clone()),
+			Some(Sponsorship {
+				max_fee: GAS_LIMIT.ref_time() * 2,
+				max_deposit: 1_000,
+				max_calls: 1,
+				period: 0,
+			}),
+		));
+		let _ = <Test as Config>::Currency::set_balance(&sponsor, 1_000_000);
+
+		// The sponsor pays for the storage of a sponsored call.
+		assert_ok!(Contracts::call_sponsored(
+			RuntimeOrigin::signed(CHARLIE),
+			addr.clone(),
+			0,
+			GAS_LIMIT,
+			None,
+			100u32.encode(),
+		));
+		let sponsored = SponsoredDepositOf::<Test>::get(&addr);
+		assert!(sponsored > 0);
+
+		// Freeing it in an ordinary call refunds the sponsor instead of the caller.
+		let sponsor_before = <Test as Config>::Currency::total_balance(&sponsor);
+		let caller_before = <Test as Config>::Currency::total_balance(&CHARLIE);
+		assert_ok!(builder::call(addr.clone())
+			.origin(RuntimeOrigin::signed(CHARLIE))
+			.data(0u32.encode())
+			.build());
+		let refunded = 100 * DepositPerByte::get();
+		assert_eq!(SponsoredDepositOf::<Test>::get(&addr), sponsored - refunded);
+		assert_eq!(<Test as Config>::Currency::total_balance(&sponsor), sponsor_before + refunded);
+		assert_eq!(<Test as Config>::Currency::total_balance(&CHARLIE), caller_before);
+	});
+}
+
+#[test]
+fn call_sponsored_respects_deposit_limit() {
+	let (wasm, _code_hash) = compile_module::<Test>("store_call").unwrap();
+
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let _ = <Test as Config>::Currency::set_balance(&CHARLIE, 50);
+		let addr = builder::bare_instantiate(Code::Upload(wasm)).build_and_unwrap_account_id();
+		let sponsor = Contracts::sponsorship_account(&addr);
+		let max_fee = GAS_LIMIT.ref_time() * 2;
+		assert_ok!(Contracts::set_sponsorship(
+			RuntimeOrigin::signed(addr.clone()),
+			Some(Sponsorship { max_fee, max_deposit: 10, max_calls: 1, period: 0 }),
+		));
+		let _ = <Test as Config>::Currency::set_balance(&sponsor, max_fee * 2);
+
+		initialize_block(2);
+		let deposit_before = get_contract(&addr).total_deposit();
+		assert_ok!(Contracts::call_sponsored(
+			RuntimeOrigin::signed(CHARLIE),
+			addr.clone(),
+			0,
+			GAS_LIMIT,
+			None,
+			100u32.encode(),
+		));
+
+		// The call failed as it needs more deposit than sponsored.
+		assert_eq!(get_contract(&addr).total_deposit(), deposit_before);
+		assert_eq!(<Test as Config>::Currency::total_balance(&sponsor), max_fee * 2);
+		assert_matches!(
+			System::events().last().unwrap().event,
+			RuntimeEvent::Contracts(crate::Event::CallSponsored { success: false, .. })
+		);
+	});
+}
+
+#[test]
+fn sponsored_calls_are_limited_per_caller() {
+	let (wasm, _code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let addr = builder::bare_instantiate(Code::Upload(wasm)).build_and_unwrap_account_id();
+		let sponsor = Contracts::sponsorship_account(&addr);
+		assert_ok!(Contracts::set_sponsorship(
+			RuntimeOrigin::signed(addr.clone()),
+			Some(Sponsorship {
+				max_fee: GAS_LIMIT.ref_time() * 2,
+				max_deposit: 1_000,
+				max_calls: 2,
+				period: 10,
+			}),
+		));
+		let _ = <Test as Config>::Currency::set_balance(&sponsor, 1_000_000);
+
+		let call_sponsored = |who: AccountId32| {
+			Contracts::call_sponsored(
+				RuntimeOrigin::signed(who),
+				addr.clone(),
+				0,
+				GAS_LIMIT,
+				None,
+				vec![],
+			)
+		};
+		let payer = |who: AccountId32| {
+			use sp_runtime::traits::SignedExtension;
+			let call = RuntimeCall::Contracts(crate::Call::call_sponsored {
+				dest: addr.clone(),
+				value: 0,
+				gas_limit: GAS_LIMIT,
+				storage_deposit_limit: None,
+				data: vec![],
+			});
+			let info = call.get_dispatch_info();
+			ChargeSponsor::<Test, _>::from(ReportPayer).pre_dispatch(&who, &call, &info, 0)
+		};
+
+		// The period starts with the first call.
+		initialize_block(2);
+		assert_ok!(call_sponsored(CHARLIE));
+		initialize_block(5);
+		assert_ok!(call_sponsored(CHARLIE));
+		assert_eq!(SponsoredCallsOf::<Test>::get(&addr, &CHARLIE), Some((2, 2)));
+
+		// The allowance is used up until the period ends.
+		assert_err_ignore_postinfo!(call_sponsored(CHARLIE), <Error<Test>>::NotSponsored);
+		assert_eq!(payer(CHARLIE), Ok(CHARLIE));
+		initialize_block(11);
+		assert_err_ignore_postinfo!(call_sponsored(CHARLIE), <Error<Test>>::NotSponsored);
+
+		// Other callers have their own allowance.
+		assert_eq!(payer(DJANGO), Ok(sponsor.clone()));
+		assert_ok!(call_sponsored(DJANGO));
+
+		initialize_block(12);
+		assert_eq!(payer(CHARLIE), Ok(sponsor));
+		assert_ok!(call_sponsored(CHARLIE));
+		assert_eq!(SponsoredCallsOf::<Test>::get(&addr, &CHARLIE), Some((12, 1)));
+	});
+}
+
+#[test]
+fn unfunded_caller_can_make_sponsored_call() {
+	use sp_runtime::{
+		traits::SignedExtension,
+		transaction_validity::{InvalidTransaction, TransactionValidityError},
+	};
+
+	let (wasm, _code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let addr = builder::bare_instantiate(Code::Upload(wasm)).build_and_unwrap_account_id();
+		let sponsor = Contracts::sponsorship_account(&addr);
+		assert_ok!(Contracts::set_sponsorship(
+			RuntimeOrigin::signed(addr.clone()),
+			Some(Sponsorship {
+				max_fee: GAS_LIMIT.ref_time() * 2,
+				max_deposit: 1_000,
+				max_calls: 1,
+				period: 0,
+			}),
+		));
+		let _ = <Test as Config>::Currency::set_balance(&sponsor, 1_000_000);
+
+		let call = RuntimeCall::Contracts(crate::Call::call_sponsored {
+			dest: addr.clone(),
+			value: 0,
+			gas_limit: GAS_LIMIT,
+			storage_deposit_limit: None,
+			data: vec![],
+		});
+		let info = call.get_dispatch_info();
+		let extra = |nonce| {
+			(
+				CheckSponsoredNonce::<Test>::from(frame_system::CheckNonce::from(nonce)),
+				ChargeSponsor::<Test, _>::from(ReportPayer),
+			)
+		};
+		assert!(!System::account_exists(&DJANGO));
+
+		// Validation does not create the account.
+		assert_ok!(extra(0).validate(&DJANGO, &call, &info, 0));
+		assert!(!System::account_exists(&DJANGO));
+
+		// The sponsored call makes the caller sufficient so that its nonce is kept.
+		initialize_block(2);
+		let (_, payer) = extra(0).pre_dispatch(&DJANGO, &call, &info, 0).unwrap();
+		assert_eq!(payer, sponsor);
+		assert_ok!(call.clone().dispatch(RuntimeOrigin::signed(DJANGO)));
+		assert_eq!(System::sufficients(&DJANGO), 1);
+		assert_eq!(System::account_nonce(&DJANGO), 1);
+		assert_eq!(<Test as Config>::Currency::total_balance(&DJANGO), 0);
+		assert_eq!(
+			extra(0).pre_dispatch(&DJANGO, &call, &info, 0).unwrap_err(),
+			TransactionValidityError::Invalid(InvalidTransaction::Stale),
+		);
+
+		// Once the allowance is used up the caller needs to pay by itself.
+		assert_eq!(
+			extra(1).pre_dispatch(&DJANGO, &call, &info, 0).map(|(_, payer)| payer),
+			Ok(DJANGO),
+		);
+
+		// Calls which are not sponsored still need a funded caller.
+		let transfer = RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
+			dest: ALICE,
+			value: 1,
+		});
+		let info = transfer.get_dispatch_info();
+		assert_eq!(
+			extra(0).validate(&BOB, &transfer, &info, 0).unwrap_err(),
+			TransactionValidityError::Invalid(InvalidTransaction::Payment),
+		);
+	});
+}
+
+/// The same contracts compiled to RISC-V and executed by PolkaVM.
+#[cfg(feature = "riscv")]
+mod riscv {
+	use super::*;
+	use std::cell::Cell;
+
+	thread_local! {
+		/// Whether [`compile_module`] loads the fixtures compiled for PolkaVM.
+		pub(super) static ON_POLKAVM: Cell<bool> = Cell::new(false);
+	}
+
+	/// Run the given tests again with all their fixtures compiled to RISC-V.
+	///
+	/// Tests depending on Wasm specifics, like determinism, instrumentation, code sizes or exact
+	/// gas values, are not listed.
+	macro_rules! on_polkavm {
+		($($test:ident),* $(,)?) => {
+			$(
+				#[test]
+				fn $test() {
+					ON_POLKAVM.with(|on| on.set(true));
+					super::$test();
+				}
+			)*
+		};
+	}
+
+	on_polkavm!(
+		instantiate_unique_trie_id,
+		deposit_event_max_value_limit,
+		run_out_of_fuel_engine,
+		run_out_of_fuel_host,
+		storage_work,
+		storage_max_value_limit,
+		deploy_and_call_other_contract,
+		delegate_call,
+		cannot_self_destruct_through_draining,
+		cannot_self_destruct_while_live,
+		self_destruct_works,
+		destroy_contract_and_transfer_funds,
+		cannot_self_destruct_in_constructor,
+		crypto_hashes,
+		transfer_return_code,
+		call_return_code,
+		instantiate_return_code,
+		chain_extension_works,
+		chain_extension_temp_storage_works,
+		lazy_removal_works,
+		refcounter,
+		debug_message_works,
+		debug_message_invalid_utf8,
+		ecdsa_recover,
+		sr25519_verify,
+		storage_deposit_callee_works,
+		contract_reverted,
+		set_code_hash,
+		reentrance_count_works_with_call,
+		reentrance_count_works_with_delegated_call,
+		account_reentrance_count_works,
+		balance_api_returns_free_balance,
+	);
+
+	#[test]
+	fn upload_code_records_code_type() {
+		ON_POLKAVM.with(|on| on.set(true));
+		let (code, code_hash) = compile_module::<Test>("dummy").unwrap();
+
+		ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+			let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+
+			assert_ok!(Contracts::bare_upload_code(ALICE, code, None, Determinism::Enforced));
+			assert_eq!(CodeInfoOf::<Test>::get(code_hash).unwrap().code_type(), CodeType::PolkaVm);
+		});
+	}
+
+	#[test]
+	fn wasm_contract_calls_polkavm_contract() {
+		let (caller_code, _caller_hash) = compile_module::<Test>("call_return_code").unwrap();
+		let (callee_code, _callee_hash) =
+			pallet_contracts_fixtures::compile_riscv_module::<Test>("ok_trap_revert").unwrap();
+		ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+			let min_balance = Contracts::min_balance();
+			let _ = <Test as Config>::Currency::set_balance(&ALICE, 1000 * min_balance);
+
+			let caller = builder::bare_instantiate(Code::Upload(caller_code))
+				.value(min_balance * 100)
+				.build_and_unwrap_account_id();
+			let callee = builder::bare_instantiate(Code::Upload(callee_code))
+				.value(min_balance * 100)
+				.build_and_unwrap_account_id();
+
+			// The callee reverts on `1` which is reported back to the Wasm caller.
+			let result = builder::bare_call(caller)
+				.data(
+					AsRef::<[u8]>::as_ref(&callee)
+						.iter()
+						.chain(&1u32.to_le_bytes())
+						.cloned()
+						.collect(),
+				)
+				.build_and_unwrap_result();
+			assert_return_code!(result, RuntimeReturnCode::CalleeReverted);
+		});
+	}
+}
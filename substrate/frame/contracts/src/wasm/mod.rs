@@ -16,9 +16,10 @@
 // limitations under the License.
 
 //! This module provides a means for executing contracts
-//! represented in wasm.
+//! represented in wasm or as PolkaVM program blobs.
 
 mod prepare;
+mod riscv;
 mod runtime;
 
 #[cfg(doc)]
@@ -31,10 +32,13 @@ pub use {
 	tests::MockExt,
 };
 
+pub(crate) use crate::wasm::riscv::with_module_cache;
+
 pub use crate::wasm::{
 	prepare::{LoadedModule, LoadingMode},
 	runtime::{
-		AllowDeprecatedInterface, AllowUnstableInterface, Environment, Runtime, RuntimeCosts,
+		AllowDeprecatedInterface, AllowUnstableInterface, Environment, Memory, Runtime,
+		RuntimeCosts,
 	},
 };
 
@@ -54,7 +58,7 @@ use frame_support::{
 use sp_core::Get;
 use sp_runtime::{DispatchError, RuntimeDebug};
 use sp_std::prelude::*;
-use wasmi::{InstancePre, Linker, Memory, MemoryType, StackLimits, Store};
+use wasmi::{InstancePre, Linker, MemoryType, StackLimits, Store};
 
 const BYTES_PER_PAGE: usize = 64 * 1024;
 
@@ -99,6 +103,8 @@ pub struct CodeInfo<T: Config> {
 	determinism: Determinism,
 	/// length of the code in bytes.
 	code_len: u32,
+	/// The engine this code is executed with.
+	code_type: CodeType,
 }
 
 /// Defines the required determinism level of a wasm blob when either running or uploading code.
@@ -122,6 +128,33 @@ pub enum Determinism {
 	Relaxed,
 }
 
+/// The format of an uploaded contract code blob.
+///
+/// It determines which engine is used to execute the code.
+#[derive(
+	Clone, Copy, Encode, Decode, scale_info::TypeInfo, MaxEncodedLen, Debug, PartialEq, Eq,
+)]
+pub enum CodeType {
+	/// A Wasm module executed by `wasmi`.
+	Wasm,
+	/// A RISC-V program blob executed by PolkaVM.
+	PolkaVm,
+}
+
+impl CodeType {
+	/// Detect the code type from the magic bytes at the start of `code`.
+	///
+	/// Everything that isn't a PolkaVM blob is treated as Wasm and left for the Wasm validation
+	/// to reject if it is invalid.
+	pub fn detect(code: &[u8]) -> Self {
+		if code.starts_with(riscv::BLOB_MAGIC) {
+			Self::PolkaVm
+		} else {
+			Self::Wasm
+		}
+	}
+}
+
 impl ExportedFunction {
 	/// The wasm export name for the function.
 	fn identifier(&self) -> &str {
@@ -151,18 +184,21 @@ impl<T: Config> Token<T> for CodeLoadToken {
 
 impl<T: Config> WasmBlob<T> {
 	/// Create the module by checking the `code`.
+	///
+	/// The [`CodeType`] is detected from the `code` itself.
 	pub fn from_code(
 		code: Vec<u8>,
 		schedule: &Schedule<T>,
 		owner: AccountIdOf<T>,
 		determinism: Determinism,
 	) -> Result<Self, (DispatchError, &'static str)> {
-		prepare::prepare::<runtime::Env, T>(
-			code.try_into().map_err(|_| (<Error<T>>::CodeTooLarge.into(), ""))?,
-			schedule,
-			owner,
-			determinism,
-		)
+		let code_type = CodeType::detect(&code);
+		let code = code.try_into().map_err(|_| (<Error<T>>::CodeTooLarge.into(), ""))?;
+		match code_type {
+			CodeType::Wasm =>
+				prepare::prepare::<runtime::Env, T>(code, schedule, owner, determinism),
+			CodeType::PolkaVm => riscv::prepare(code, schedule, owner),
+		}
 	}
 
 	/// Remove the code from storage and refund the deposit to its owner.
@@ -205,7 +241,7 @@ impl<T: Config> WasmBlob<T> {
 		host_state: H,
 		schedule: &Schedule<T>,
 		allow_deprecated: AllowDeprecatedInterface,
-	) -> Result<(Store<H>, Memory, InstancePre), &'static str>
+	) -> Result<(Store<H>, wasmi::Memory, InstancePre), &'static str>
 	where
 		E: Environment<H>,
 	{
@@ -230,7 +266,7 @@ impl<T: Config> WasmBlob<T> {
 		let qed = "We checked the limits versus our Schedule,
 					 which specifies the max amount of memory pages
 					 well below u16::MAX; qed";
-		let memory = Memory::new(
+		let memory = wasmi::Memory::new(
 			&mut store,
 			MemoryType::new(memory_limits.0, Some(memory_limits.1)).expect(qed),
 		)
@@ -311,6 +347,7 @@ impl<T: Config> CodeInfo<T> {
 			refcount: 0,
 			code_len: 0,
 			determinism: Determinism::Enforced,
+			code_type: CodeType::Wasm,
 		}
 	}
 
@@ -319,6 +356,11 @@ impl<T: Config> CodeInfo<T> {
 		self.determinism
 	}
 
+	/// Returns the engine this code is executed with.
+	pub fn code_type(&self) -> CodeType {
+		self.code_type
+	}
+
 	/// Returns reference count of the module.
 	pub fn refcount(&self) -> u64 {
 		self.refcount
@@ -463,6 +505,9 @@ impl<T: Config> Executable<T> for WasmBlob<T> {
 		input_data: Vec<u8>,
 	) -> ExecResult {
 		use InstanceOrExecReturn::*;
+		if let CodeType::PolkaVm = self.code_info.code_type {
+			return riscv::execute(self, ext, function, input_data)
+		}
 		match Self::prepare_execute(self, Runtime::new(ext, input_data), function)? {
 			Instance((func, mut store)) => {
 				let result = func.call(&mut store, &[], &mut []);
@@ -3498,7 +3543,7 @@ mod tests {
 		let data = vec![1u8, 2, 3];
 		let memory = data.encode();
 		let decoded: BoundedVec<u8, ConstU32<128>> =
-			runtime.read_sandbox_memory_as(&memory, 0u32).unwrap();
+			runtime.read_sandbox_memory_as(memory.as_slice(), 0u32).unwrap();
 		assert_eq!(decoded.into_inner(), data);
 	}
 
@@ -23,7 +23,7 @@ use crate::{
 	chain_extension::ChainExtension,
 	storage::meter::Diff,
 	wasm::{
-		runtime::AllowDeprecatedInterface, CodeInfo, Determinism, Environment, WasmBlob,
+		runtime::AllowDeprecatedInterface, CodeInfo, CodeType, Determinism, Environment, WasmBlob,
 		BYTES_PER_PAGE,
 	},
 	AccountIdOf, CodeVec, Config, Error, Schedule, LOG_TARGET,
@@ -326,7 +326,8 @@ where
 	let deposit = Diff { bytes_added, items_added: 2, ..Default::default() }
 		.update_contract::<T>(None)
 		.charge_or_zero();
-	let code_info = CodeInfo { owner, deposit, determinism, refcount: 0, code_len };
+	let code_info =
+		CodeInfo { owner, deposit, determinism, refcount: 0, code_len, code_type: CodeType::Wasm };
 	let code_hash = T::Hashing::hash(&code);
 
 	Ok(WasmBlob { code, code_info, code_hash })
@@ -359,6 +360,7 @@ pub mod benchmarking {
 			refcount: 0,
 			code_len: code.len() as u32,
 			determinism,
+			code_type: CodeType::Wasm,
 		};
 		let code_hash = T::Hashing::hash(&code);
 
@@ -383,8 +385,9 @@ mod tests {
 		}
 	}
 
-	/// Using unreachable statements triggers unreachable warnings in the generated code
-	#[allow(unreachable_code)]
+	/// Using unreachable statements triggers unreachable warnings in the generated code.
+	/// The PolkaVM entry points are never used by these tests.
+	#[allow(unreachable_code, dead_code)]
 	mod env {
 		use super::*;
 		use crate::wasm::runtime::{AllowDeprecatedInterface, AllowUnstableInterface, TrapReason};
@@ -0,0 +1,330 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module takes care of checking and executing contracts which are compiled to RISC-V and
+//! uploaded as PolkaVM program blobs.
+//!
+//! The host functions are the same as for Wasm contracts. They are dispatched by their symbol
+//! name through [`Env::handle_ecall`] whenever the program executes an `ecalli` instruction.
+
+use crate::{
+	exec::{ExecResult, ExportedFunction, Ext},
+	primitives::ExecReturnValue,
+	storage::meter::Diff,
+	wasm::{
+		runtime::{AllowDeprecatedInterface, AllowUnstableInterface, Env, Memory},
+		CodeInfo, CodeType, Determinism, Runtime, WasmBlob,
+	},
+	AccountIdOf, CodeVec, Config, Error, Schedule, LOG_TARGET,
+};
+use codec::{Encode, MaxEncodedLen};
+use frame_support::traits::Get;
+use pallet_contracts_uapi::ReturnFlags;
+use polkavm::{
+	BackendKind, Config as PolkaVmConfig, Engine, GasMeteringKind, InterruptKind, Module,
+	ModuleConfig, RawInstance, Reg,
+};
+use sp_runtime::{traits::Hash, DispatchError};
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+
+/// Every PolkaVM program blob starts with these bytes.
+pub const BLOB_MAGIC: &[u8] = b"PVM\0";
+
+/// The registers used to pass arguments to host functions.
+const ARG_REGS: [Reg; 6] = [Reg::A0, Reg::A1, Reg::A2, Reg::A3, Reg::A4, Reg::A5];
+
+/// The granularity in which PolkaVM memory is accessible.
+const PAGE_SIZE: u32 = 4096;
+
+// The modules loaded during the current call stack, keyed by their encoded code hash.
+environmental::environmental!(module_cache: BTreeMap<Vec<u8>, Module>);
+
+/// The memory of a PolkaVM contract.
+///
+/// PolkaVM memory is not contiguous but made up of regions of accessible pages.
+impl<T: Config> Memory<T> for RawInstance {
+	fn read_into_buf(&self, ptr: u32, buf: &mut [u8]) -> Result<(), DispatchError> {
+		self.read_memory_into(ptr, buf)
+			.map(|_| ())
+			.map_err(|_| Error::<T>::OutOfBounds.into())
+	}
+
+	fn read_up_to(&self, ptr: u32, max_len: u32) -> Result<Vec<u8>, DispatchError> {
+		let end = ptr.saturating_add(max_len);
+		let mut buf = Vec::new();
+		let mut chunk_start = ptr;
+		// Read page by page until the first inaccessible page.
+		while chunk_start < end {
+			let chunk_end =
+				(chunk_start / PAGE_SIZE).saturating_add(1).saturating_mul(PAGE_SIZE).min(end);
+			let mut chunk = vec![0u8; (chunk_end - chunk_start) as usize];
+			if self.read_memory_into(chunk_start, &mut chunk[..]).is_err() {
+				break
+			}
+			buf.extend_from_slice(&chunk);
+			chunk_start = chunk_end;
+		}
+		if buf.is_empty() && max_len > 0 {
+			return Err(Error::<T>::OutOfBounds.into())
+		}
+		Ok(buf)
+	}
+
+	fn write(&mut self, ptr: u32, buf: &[u8]) -> Result<(), DispatchError> {
+		self.write_memory(ptr, buf).map_err(|_| Error::<T>::OutOfBounds.into())
+	}
+}
+
+fn allow_unstable<T: Config>() -> AllowUnstableInterface {
+	if T::UnsafeUnstableInterface::get() {
+		AllowUnstableInterface::Yes
+	} else {
+		AllowUnstableInterface::No
+	}
+}
+
+/// Load the program blob into an interpreter with synchronous gas metering.
+fn load_module(code: &[u8]) -> Result<Module, &'static str> {
+	let mut config = PolkaVmConfig::new();
+	config.set_backend(Some(BackendKind::Interpreter));
+	let engine = Engine::new(&config).map_err(|err| {
+		log::debug!(target: LOG_TARGET, "failed to create polkavm engine: {err}");
+		"Can't create the PolkaVM engine"
+	})?;
+
+	let mut module_config = ModuleConfig::new();
+	module_config.set_gas_metering(Some(GasMeteringKind::Sync));
+	Module::new(&engine, &module_config, code.to_vec().into()).map_err(|err| {
+		log::debug!(target: LOG_TARGET, "failed to load polkavm program: {err}");
+		"Can't decode the PolkaVM program blob"
+	})
+}
+
+/// Run `f` with a cache of the PolkaVM modules executed by it.
+///
+/// This way a program blob is only loaded once per call stack, no matter how often the contract
+/// is called.
+pub fn with_module_cache<R>(f: impl FnOnce() -> R) -> R {
+	module_cache::using_once(&mut BTreeMap::new(), f)
+}
+
+/// Get the module of `code_hash` from the cache, loading it from `code` if it isn't cached yet.
+fn cached_module(code_hash: Vec<u8>, code: &[u8]) -> Result<Module, &'static str> {
+	if let Some(module) = module_cache::with(|cache| cache.get(&code_hash).cloned()).flatten() {
+		return Ok(module)
+	}
+	let module = load_module(code)?;
+	module_cache::with(|cache| cache.insert(code_hash, module.clone()));
+	Ok(module)
+}
+
+/// Check that every imported host function exists and may be used.
+fn check_imports<T: Config>(
+	module: &Module,
+	allow_deprecated: AllowDeprecatedInterface,
+) -> Result<(), &'static str> {
+	let imports = module.imports();
+	for idx in 0..imports.len() {
+		let allowed = imports.get(idx).map_or(false, |symbol| {
+			Env::polkavm_import_allowed(symbol.as_bytes(), allow_unstable::<T>(), allow_deprecated)
+		});
+		if !allowed {
+			return Err("PolkaVM program imports an unknown host function")
+		}
+	}
+	Ok(())
+}
+
+/// Validates the given program blob:
+///
+/// - It exports the `call` and `deploy` entry points.
+/// - All imported host functions are defined and neither deprecated nor unstable (unless unstable
+///   interfaces are enabled).
+/// - Its memory fits into the limits permitted by the `schedule`.
+fn validate<T: Config>(code: &[u8], schedule: &Schedule<T>) -> Result<(), &'static str> {
+	let module = load_module(code)?;
+
+	for function in [ExportedFunction::Call, ExportedFunction::Constructor] {
+		if !module
+			.exports()
+			.any(|export| export.symbol().as_bytes() == function.identifier().as_bytes())
+		{
+			return Err("PolkaVM program doesn't export the required entry points")
+		}
+	}
+
+	check_imports::<T>(&module, AllowDeprecatedInterface::No)?;
+
+	let memory_map = module.memory_map();
+	let memory_size = memory_map
+		.ro_data_size()
+		.saturating_add(memory_map.rw_data_size())
+		.saturating_add(memory_map.stack_size());
+	if memory_size > schedule.limits.max_memory_size() {
+		return Err("PolkaVM program requires more memory than allowed")
+	}
+
+	Ok(())
+}
+
+/// Validates the program blob and constructs the contract `code_info` by calculating the storage
+/// deposit.
+///
+/// PolkaVM execution is always deterministic which is why the code is stored as
+/// [`Determinism::Enforced`].
+pub fn prepare<T: Config>(
+	code: CodeVec<T>,
+	schedule: &Schedule<T>,
+	owner: AccountIdOf<T>,
+) -> Result<WasmBlob<T>, (DispatchError, &'static str)> {
+	validate::<T>(code.as_ref(), schedule).map_err(|msg| {
+		log::debug!(target: LOG_TARGET, "New code rejected on validation: {}", msg);
+		(Error::<T>::CodeRejected.into(), msg)
+	})?;
+
+	// Calculate deposit for storing contract code and `code_info` in two different storage items.
+	let code_len = code.len() as u32;
+	let bytes_added = code_len.saturating_add(<CodeInfo<T>>::max_encoded_len() as u32);
+	let deposit = Diff { bytes_added, items_added: 2, ..Default::default() }
+		.update_contract::<T>(None)
+		.charge_or_zero();
+	let code_info = CodeInfo {
+		owner,
+		deposit,
+		determinism: Determinism::Enforced,
+		refcount: 0,
+		code_len,
+		code_type: CodeType::PolkaVm,
+	};
+	let code_hash = T::Hashing::hash(&code);
+
+	Ok(WasmBlob { code, code_info, code_hash })
+}
+
+/// Executes `function` of the program blob.
+///
+/// Gas is metered by PolkaVM and synced with the gas meter of `ext` whenever a host function is
+/// entered or left, the same way as it is done for wasmi.
+pub fn execute<T: Config, E: Ext<T = T>>(
+	blob: WasmBlob<T>,
+	ext: &mut E,
+	function: &ExportedFunction,
+	input_data: Vec<u8>,
+) -> ExecResult {
+	let allow_deprecated = match function {
+		ExportedFunction::Call => AllowDeprecatedInterface::Yes,
+		ExportedFunction::Constructor => AllowDeprecatedInterface::No,
+	};
+	let module = cached_module(blob.code_hash.encode(), blob.code.as_slice())
+		.and_then(|module| check_imports::<T>(&module, allow_deprecated).map(|_| module))
+		.map_err(|msg| {
+			log::debug!(target: LOG_TARGET, "failed to instantiate polkavm program: {}", msg);
+			Error::<T>::CodeRejected
+		})?;
+	let entry_point = module
+		.exports()
+		.find(|export| export.symbol().as_bytes() == function.identifier().as_bytes())
+		.ok_or_else(|| {
+			log::error!(target: LOG_TARGET, "failed to find entry point");
+			Error::<T>::CodeRejected
+		})?
+		.program_counter();
+	let mut instance = module.instantiate().map_err(|err| {
+		log::debug!(target: LOG_TARGET, "failed to instantiate polkavm program: {err}");
+		Error::<T>::CodeRejected
+	})?;
+
+	let mut runtime = Runtime::new(ext, input_data);
+
+	// Set gas limit for the execution.
+	// We normalize it by the base instruction weight, the same way as we do for wasmi.
+	let gas_limit = runtime
+		.ext()
+		.gas_meter_mut()
+		.gas_left()
+		.ref_time()
+		.checked_div(T::Schedule::get().instruction_weights.base as u64)
+		.ok_or(Error::<T>::InvalidSchedule)?
+		.min(i64::MAX as u64);
+	instance.set_gas(gas_limit as i64);
+	let gas_consumed =
+		|instance: &RawInstance| gas_limit.saturating_sub(instance.gas().max(0) as u64);
+
+	// The entry point should already see the correct refcount in case it will be ever inspected.
+	if let &ExportedFunction::Constructor = function {
+		E::increment_refcount(blob.code_hash)?;
+	}
+
+	let imports = module.imports();
+	instance.prepare_call_untyped(entry_point, &[]);
+	let result = loop {
+		let idx = match instance.run() {
+			Ok(InterruptKind::Ecalli(idx)) => idx,
+			Ok(InterruptKind::Step) => continue,
+			// Contract returned from the entry point -> no data was returned.
+			Ok(InterruptKind::Finished) =>
+				break Ok(ExecReturnValue { flags: ReturnFlags::empty(), data: Vec::new() }),
+			Ok(InterruptKind::NotEnoughGas) => break Err(Error::<T>::OutOfGas.into()),
+			Ok(InterruptKind::Trap) | Ok(InterruptKind::Segfault(_)) =>
+				break Err(Error::<T>::ContractTrapped.into()),
+			Err(err) => {
+				log::debug!(target: LOG_TARGET, "polkavm execution failed: {err}");
+				break Err(Error::<T>::ContractTrapped.into())
+			},
+		};
+		let Some(symbol) = imports.get(idx) else { break Err(Error::<T>::CodeRejected.into()) };
+
+		// Write gas from PolkaVM into pallet-contracts before entering the host function.
+		let gas_left_before =
+			match runtime.ext().gas_meter_mut().sync_from_executor(gas_consumed(&instance)) {
+				Ok(gas_left) => gas_left,
+				Err(err) => break Err(err.into()),
+			};
+
+		let regs = ARG_REGS.map(|reg| instance.reg(reg));
+		let result = Env::handle_ecall(
+			&mut runtime,
+			&mut instance,
+			symbol.as_bytes(),
+			regs,
+			allow_unstable::<T>(),
+			allow_deprecated,
+		);
+
+		// Write gas from pallet-contracts into PolkaVM after leaving the host function.
+		let gas_synced = match runtime.ext().gas_meter_mut().sync_to_executor(gas_left_before) {
+			Ok(consumed) => u64::from(consumed),
+			Err(err) => break Err(err.into()),
+		};
+		match instance.gas().checked_sub(gas_synced.min(i64::MAX as u64) as i64) {
+			Some(gas) if gas >= 0 => instance.set_gas(gas),
+			_ => break Err(Error::<T>::OutOfGas.into()),
+		}
+
+		match result {
+			Ok(Some(output)) => {
+				instance.set_reg(Reg::A0, output as u32);
+				instance.set_reg(Reg::A1, (output >> 32) as u32);
+			},
+			Ok(None) => (),
+			Err(reason) => break Runtime::<E>::trap_to_execution_result(&reason),
+		}
+	};
+
+	runtime.ext().gas_meter_mut().sync_from_executor(gas_consumed(&instance))?;
+	result
+}
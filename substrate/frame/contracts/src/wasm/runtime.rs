@@ -40,7 +40,7 @@ use sp_runtime::{
 	DispatchError, RuntimeDebug,
 };
 use sp_std::{fmt, prelude::*};
-use wasmi::{core::HostError, errors::LinkerError, Linker, Memory, Store};
+use wasmi::{core::HostError, errors::LinkerError, Linker, Store};
 use xcm::VersionedXcm;
 
 type CallOf<T> = <T as frame_system::Config>::RuntimeCall;
@@ -49,6 +49,7 @@ type CallOf<T> = <T as frame_system::Config>::RuntimeCall;
 const MAX_DECODE_NESTING: u32 = 256;
 
 /// Passed to [`Environment`] to determine whether it should expose deprecated interfaces.
+#[derive(Clone, Copy)]
 pub enum AllowDeprecatedInterface {
 	/// No deprecated interfaces are exposed.
 	No,
@@ -57,6 +58,7 @@ pub enum AllowDeprecatedInterface {
 }
 
 /// Passed to [`Environment`] to determine whether it should expose unstable interfaces.
+#[derive(Clone, Copy)]
 pub enum AllowUnstableInterface {
 	/// No unstable interfaces are exposed.
 	No,
@@ -77,6 +79,66 @@ pub trait Environment<HostState> {
 	) -> Result<(), LinkerError>;
 }
 
+/// The memory of a contract.
+///
+/// Host functions only access the contract memory through this trait. This allows to share them
+/// between the execution engines.
+pub trait Memory<T: Config> {
+	/// Read `buf.len()` bytes at `ptr` into `buf`.
+	///
+	/// Returns `Err` if the designated area is not within the bounds of the memory.
+	fn read_into_buf(&self, ptr: u32, buf: &mut [u8]) -> Result<(), DispatchError>;
+
+	/// Read up to `max_len` bytes at `ptr`.
+	///
+	/// Less bytes are returned if the memory ends before. Returns `Err` if `ptr` is not within the
+	/// bounds of the memory.
+	fn read_up_to(&self, ptr: u32, max_len: u32) -> Result<Vec<u8>, DispatchError>;
+
+	/// Write `buf` at `ptr`.
+	///
+	/// Returns `Err` if the designated area is not within the bounds of the memory.
+	fn write(&mut self, ptr: u32, buf: &[u8]) -> Result<(), DispatchError>;
+}
+
+/// The linear memory of a wasm contract.
+impl<T: Config> Memory<T> for [u8] {
+	fn read_into_buf(&self, ptr: u32, buf: &mut [u8]) -> Result<(), DispatchError> {
+		let ptr = ptr as usize;
+		let bound_checked =
+			self.get(ptr..ptr + buf.len()).ok_or_else(|| Error::<T>::OutOfBounds)?;
+		buf.copy_from_slice(bound_checked);
+		Ok(())
+	}
+
+	fn read_up_to(&self, ptr: u32, max_len: u32) -> Result<Vec<u8>, DispatchError> {
+		let bound_checked = self.get(ptr as usize..).ok_or_else(|| Error::<T>::OutOfBounds)?;
+		Ok(bound_checked[..bound_checked.len().min(max_len as usize)].to_vec())
+	}
+
+	fn write(&mut self, ptr: u32, buf: &[u8]) -> Result<(), DispatchError> {
+		let ptr = ptr as usize;
+		let bound_checked =
+			self.get_mut(ptr..ptr + buf.len()).ok_or_else(|| Error::<T>::OutOfBounds)?;
+		bound_checked.copy_from_slice(buf);
+		Ok(())
+	}
+}
+
+impl<T: Config, M: ?Sized + Memory<T>> Memory<T> for &mut M {
+	fn read_into_buf(&self, ptr: u32, buf: &mut [u8]) -> Result<(), DispatchError> {
+		(**self).read_into_buf(ptr, buf)
+	}
+
+	fn read_up_to(&self, ptr: u32, max_len: u32) -> Result<Vec<u8>, DispatchError> {
+		(**self).read_up_to(ptr, max_len)
+	}
+
+	fn write(&mut self, ptr: u32, buf: &[u8]) -> Result<(), DispatchError> {
+		(**self).write(ptr, buf)
+	}
+}
+
 /// Type of a storage key.
 enum KeyType {
 	/// Legacy fix sized key `[u8;32]`.
@@ -405,7 +467,7 @@ fn ensure_executable<T: Config>(message: &VersionedXcm<CallOf<T>>) -> DispatchRe
 pub struct Runtime<'a, E: Ext + 'a> {
 	ext: &'a mut E,
 	input_data: Option<Vec<u8>>,
-	memory: Option<Memory>,
+	memory: Option<wasmi::Memory>,
 	chain_extension: Option<Box<<E::T as Config>::ChainExtension>>,
 }
 
@@ -419,18 +481,17 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 		}
 	}
 
-	pub fn memory(&self) -> Option<Memory> {
+	pub fn memory(&self) -> Option<wasmi::Memory> {
 		self.memory
 	}
 
-	pub fn set_memory(&mut self, memory: Memory) {
+	pub fn set_memory(&mut self, memory: wasmi::Memory) {
 		self.memory = Some(memory);
 	}
 
 	/// Converts the sandbox result and the runtime state into the execution outcome.
 	pub fn to_execution_result(self, sandbox_result: Result<(), wasmi::Error>) -> ExecResult {
 		use wasmi::core::TrapCode::OutOfFuel;
-		use TrapReason::*;
 
 		match sandbox_result {
 			// Contract returned from main function -> no data was returned.
@@ -447,19 +508,7 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 				}
 				// If we encoded a reason then it is some abort generated by a host function.
 				if let Some(reason) = &trap.downcast_ref::<TrapReason>() {
-					match &reason {
-						Return(ReturnData { flags, data }) => {
-							let flags = ReturnFlags::from_bits(*flags)
-								.ok_or(Error::<E::T>::InvalidCallFlags)?;
-							return Ok(ExecReturnValue { flags, data: data.to_vec() })
-						},
-						Termination =>
-							return Ok(ExecReturnValue {
-								flags: ReturnFlags::empty(),
-								data: Vec::new(),
-							}),
-						SupervisorError(error) => return Err((*error).into()),
-					}
+					return Self::trap_to_execution_result(reason)
 				}
 				// Otherwise the trap came from the contract itself.
 				Err(Error::<E::T>::ContractTrapped.into())
@@ -474,6 +523,22 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 		}
 	}
 
+	/// Converts the reason a host function aborted the execution into an [`ExecResult`].
+	///
+	/// This is shared by all engines as the host functions are the same for all of them.
+	pub fn trap_to_execution_result(reason: &TrapReason) -> ExecResult {
+		match reason {
+			TrapReason::Return(ReturnData { flags, data }) => {
+				let flags =
+					ReturnFlags::from_bits(*flags).ok_or(Error::<E::T>::InvalidCallFlags)?;
+				Ok(ExecReturnValue { flags, data: data.to_vec() })
+			},
+			TrapReason::Termination =>
+				Ok(ExecReturnValue { flags: ReturnFlags::empty(), data: Vec::new() }),
+			TrapReason::SupervisorError(error) => Err((*error).into()),
+		}
+	}
+
 	/// Get a mutable reference to the inner `Ext`.
 	///
 	/// This is mainly for the chain extension to have access to the environment the
@@ -526,9 +591,9 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 	/// Returns `Err` if one of the following conditions occurs:
 	///
 	/// - requested buffer is not within the bounds of the sandbox memory.
-	pub fn read_sandbox_memory(
+	pub fn read_sandbox_memory<M: ?Sized + Memory<E::T>>(
 		&self,
-		memory: &[u8],
+		memory: &M,
 		ptr: u32,
 		len: u32,
 	) -> Result<Vec<u8>, DispatchError> {
@@ -543,17 +608,13 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 	/// Returns `Err` if one of the following conditions occurs:
 	///
 	/// - requested buffer is not within the bounds of the sandbox memory.
-	pub fn read_sandbox_memory_into_buf(
+	pub fn read_sandbox_memory_into_buf<M: ?Sized + Memory<E::T>>(
 		&self,
-		memory: &[u8],
+		memory: &M,
 		ptr: u32,
 		buf: &mut [u8],
 	) -> Result<(), DispatchError> {
-		let ptr = ptr as usize;
-		let bound_checked =
-			memory.get(ptr..ptr + buf.len()).ok_or_else(|| Error::<E::T>::OutOfBounds)?;
-		buf.copy_from_slice(bound_checked);
-		Ok(())
+		memory.read_into_buf(ptr, buf)
 	}
 
 	/// Reads and decodes a type with a size fixed at compile time from contract memory.
@@ -562,15 +623,13 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 	///
 	/// The weight of reading a fixed value is included in the overall weight of any
 	/// contract callable function.
-	pub fn read_sandbox_memory_as<D: Decode + MaxEncodedLen>(
+	pub fn read_sandbox_memory_as<D: Decode + MaxEncodedLen, M: ?Sized + Memory<E::T>>(
 		&self,
-		memory: &[u8],
+		memory: &M,
 		ptr: u32,
 	) -> Result<D, DispatchError> {
-		let ptr = ptr as usize;
-		let mut bound_checked = memory.get(ptr..).ok_or_else(|| Error::<E::T>::OutOfBounds)?;
-
-		let decoded = D::decode_with_depth_limit(MAX_DECODE_NESTING, &mut bound_checked)
+		let buf = memory.read_up_to(ptr, D::max_encoded_len() as u32)?;
+		let decoded = D::decode_with_depth_limit(MAX_DECODE_NESTING, &mut buf.as_slice())
 			.map_err(|_| DispatchError::from(Error::<E::T>::DecodingFailed))?;
 		Ok(decoded)
 	}
@@ -586,17 +645,14 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 	///
 	/// There must be an extra benchmark for determining the influence of `len` with
 	/// regard to the overall weight.
-	pub fn read_sandbox_memory_as_unbounded<D: Decode>(
+	pub fn read_sandbox_memory_as_unbounded<D: Decode, M: ?Sized + Memory<E::T>>(
 		&self,
-		memory: &[u8],
+		memory: &M,
 		ptr: u32,
 		len: u32,
 	) -> Result<D, DispatchError> {
-		let ptr = ptr as usize;
-		let mut bound_checked =
-			memory.get(ptr..ptr + len as usize).ok_or_else(|| Error::<E::T>::OutOfBounds)?;
-
-		let decoded = D::decode_all_with_depth_limit(MAX_DECODE_NESTING, &mut bound_checked)
+		let buf = self.read_sandbox_memory(memory, ptr, len)?;
+		let decoded = D::decode_all_with_depth_limit(MAX_DECODE_NESTING, &mut buf.as_slice())
 			.map_err(|_| DispatchError::from(Error::<E::T>::DecodingFailed))?;
 
 		Ok(decoded)
@@ -621,9 +677,9 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 	///
 	/// In addition to the error conditions of `write_sandbox_memory` this functions returns
 	/// `Err` if the size of the buffer located at `out_ptr` is too small to fit `buf`.
-	pub fn write_sandbox_output(
+	pub fn write_sandbox_output<M: ?Sized + Memory<E::T>>(
 		&mut self,
-		memory: &mut [u8],
+		memory: &mut M,
 		out_ptr: u32,
 		out_len_ptr: u32,
 		buf: &[u8],
@@ -654,17 +710,13 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 	/// Returns `Err` if one of the following conditions occurs:
 	///
 	/// - designated area is not within the bounds of the sandbox memory.
-	fn write_sandbox_memory(
+	fn write_sandbox_memory<M: ?Sized + Memory<E::T>>(
 		&self,
-		memory: &mut [u8],
+		memory: &mut M,
 		ptr: u32,
 		buf: &[u8],
 	) -> Result<(), DispatchError> {
-		let ptr = ptr as usize;
-		let bound_checked =
-			memory.get_mut(ptr..ptr + buf.len()).ok_or_else(|| Error::<E::T>::OutOfBounds)?;
-		bound_checked.copy_from_slice(buf);
-		Ok(())
+		memory.write(ptr, buf)
 	}
 
 	/// Computes the given hash function on the supplied input.
@@ -679,9 +731,9 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 	/// # Note
 	///
 	/// The `input` and `output` buffers may overlap.
-	fn compute_hash_on_intermediate_buffer<F, R>(
+	fn compute_hash_on_intermediate_buffer<F, R, M: ?Sized + Memory<E::T>>(
 		&self,
-		memory: &mut [u8],
+		memory: &mut M,
 		hash_fn: F,
 		input_ptr: u32,
 		input_len: u32,
@@ -730,9 +782,9 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 			(err, _) => Self::err_into_return_code(err),
 		}
 	}
-	fn decode_key(
+	fn decode_key<M: ?Sized + Memory<E::T>>(
 		&self,
-		memory: &[u8],
+		memory: &M,
 		key_type: KeyType,
 		key_ptr: u32,
 	) -> Result<crate::exec::Key<E::T>, TrapReason> {
@@ -754,9 +806,9 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 		res.map_err(|_| Error::<E::T>::DecodingFailed.into())
 	}
 
-	fn set_storage(
+	fn set_storage<M: ?Sized + Memory<E::T>>(
 		&mut self,
-		memory: &[u8],
+		memory: &M,
 		key_type: KeyType,
 		key_ptr: u32,
 		value_ptr: u32,
@@ -779,9 +831,9 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 		Ok(write_outcome.old_len_with_sentinel())
 	}
 
-	fn clear_storage(
+	fn clear_storage<M: ?Sized + Memory<E::T>>(
 		&mut self,
-		memory: &[u8],
+		memory: &M,
 		key_type: KeyType,
 		key_ptr: u32,
 	) -> Result<u32, TrapReason> {
@@ -793,9 +845,9 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 		Ok(outcome.old_len_with_sentinel())
 	}
 
-	fn get_storage(
+	fn get_storage<M: ?Sized + Memory<E::T>>(
 		&mut self,
-		memory: &mut [u8],
+		memory: &mut M,
 		key_type: KeyType,
 		key_ptr: u32,
 		out_ptr: u32,
@@ -822,9 +874,9 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 		}
 	}
 
-	fn contains_storage(
+	fn contains_storage<M: ?Sized + Memory<E::T>>(
 		&mut self,
-		memory: &[u8],
+		memory: &M,
 		key_type: KeyType,
 		key_ptr: u32,
 	) -> Result<u32, TrapReason> {
@@ -836,9 +888,9 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 		Ok(outcome.unwrap_or(SENTINEL))
 	}
 
-	fn call(
+	fn call<M: ?Sized + Memory<E::T>>(
 		&mut self,
-		memory: &mut [u8],
+		memory: &mut M,
 		flags: CallFlags,
 		call_type: CallType,
 		input_data_ptr: u32,
@@ -914,9 +966,9 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 		Ok(Runtime::<E>::exec_into_return_code(call_outcome)?)
 	}
 
-	fn instantiate(
+	fn instantiate<M: ?Sized + Memory<E::T>>(
 		&mut self,
-		memory: &mut [u8],
+		memory: &mut M,
 		code_hash_ptr: u32,
 		weight: Weight,
 		deposit_ptr: u32,
@@ -969,7 +1021,11 @@ impl<'a, E: Ext + 'a> Runtime<'a, E> {
 		Ok(Runtime::<E>::exec_into_return_code(instantiate_outcome.map(|(_, retval)| retval))?)
 	}
 
-	fn terminate(&mut self, memory: &[u8], beneficiary_ptr: u32) -> Result<(), TrapReason> {
+	fn terminate<M: ?Sized + Memory<E::T>>(
+		&mut self,
+		memory: &M,
+		beneficiary_ptr: u32,
+	) -> Result<(), TrapReason> {
 		self.charge_gas(RuntimeCosts::Terminate)?;
 		let beneficiary: <<E as Ext>::T as frame_system::Config>::AccountId =
 			self.read_sandbox_memory_as(memory, beneficiary_ptr)?;
@@ -2037,8 +2093,17 @@ pub mod env {
 			"Constructor initializes with `Some`. This is the only place where it is set to `None`.\
 			It is always reset to `Some` afterwards. qed"
 		);
-		let env =
-			Environment::new(ctx, memory, id, input_ptr, input_len, output_ptr, output_len_ptr);
+		// The chain extension can't be generic over the memory of the engine.
+		let mut memory = memory;
+		let env = Environment::new(
+			ctx,
+			&mut memory,
+			id,
+			input_ptr,
+			input_len,
+			output_ptr,
+			output_len_ptr,
+		);
 		let ret = match chain_extension.call(env)? {
 			RetVal::Converging(val) => Ok(val),
 			RetVal::Diverging { flags, data } =>
@@ -1,4 +1,3 @@
-#![allow(unused_variables, unused_mut)]
 // Copyright (C) Parity Technologies (UK) Ltd.
 //
 // Licensed under the Apache License, Version 2.0 (the "License");
@@ -12,10 +11,211 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
-// TODO: bring up to date with wasm32.rs
 
-use super::{CallFlags, HostFn, HostFnImpl, Result};
-use crate::ReturnFlags;
+//! Host functions for contracts compiled to RISC-V and executed by PolkaVM.
+//!
+//! PolkaVM has no notion of import modules. The version of a host function is therefore encoded
+//! into its symbol: `foo` is version 0 and `foo_v1` is version 1.
+//!
+//! Every argument is passed as `u32` words in the registers `a0` to `a5`, where an `u64` takes two
+//! words (low word first). Host functions which take more than six words receive a pointer to all
+//! their words packed into an array instead.
+
+use super::{
+	extract_from_slice, ptr_len_or_sentinel, ptr_or_sentinel, CallFlags, HostFn, HostFnImpl, Result,
+};
+use crate::{ReturnCode, ReturnFlags};
+
+mod sys {
+	#[polkavm_derive::polkavm_import]
+	extern "C" {
+		pub fn account_reentrance_count(account_ptr: *const u8) -> u32;
+
+		pub fn lock_delegate_dependency(code_hash_ptr: *const u8);
+
+		pub fn address(output_ptr: *mut u8, output_len_ptr: *mut u32);
+
+		pub fn balance(output_ptr: *mut u8, output_len_ptr: *mut u32);
+
+		pub fn block_number(output_ptr: *mut u8, output_len_ptr: *mut u32);
+
+		pub fn call(packed_args: *const u32) -> u32;
+
+		pub fn call_v1(packed_args: *const u32) -> u32;
+
+		pub fn call_v2(packed_args: *const u32) -> u32;
+
+		pub fn call_chain_extension(
+			func_id: u32,
+			input_ptr: *const u8,
+			input_len: u32,
+			output_ptr: *mut u8,
+			output_len_ptr: *mut u32,
+		) -> u32;
+
+		pub fn call_runtime(call_ptr: *const u8, call_len: u32) -> u32;
+
+		pub fn caller(output_ptr: *mut u8, output_len_ptr: *mut u32);
+
+		pub fn caller_is_origin() -> u32;
+
+		pub fn caller_is_root() -> u32;
+
+		pub fn clear_storage(key_ptr: *const u8);
+
+		pub fn clear_storage_v1(key_ptr: *const u8, key_len: u32) -> u32;
+
+		pub fn code_hash(
+			account_id_ptr: *const u8,
+			output_ptr: *mut u8,
+			output_len_ptr: *mut u32,
+		) -> u32;
+
+		pub fn contains_storage(key_ptr: *const u8) -> u32;
+
+		pub fn contains_storage_v1(key_ptr: *const u8, key_len: u32) -> u32;
+
+		pub fn debug_message(str_ptr: *const u8, str_len: u32) -> u32;
+
+		pub fn delegate_call(
+			flags: u32,
+			code_hash_ptr: *const u8,
+			input_data_ptr: *const u8,
+			input_data_len: u32,
+			output_ptr: *mut u8,
+			output_len_ptr: *mut u32,
+		) -> u32;
+
+		pub fn deposit_event(
+			topics_ptr: *const u8,
+			topics_len: u32,
+			data_ptr: *const u8,
+			data_len: u32,
+		);
+
+		pub fn ecdsa_recover(
+			signature_ptr: *const u8,
+			message_hash_ptr: *const u8,
+			output_ptr: *mut u8,
+		) -> u32;
+
+		pub fn ecdsa_to_eth_address(public_key_ptr: *const u8, output_ptr: *mut u8) -> u32;
+
+		pub fn gas_left(output_ptr: *mut u8, output_len_ptr: *mut u32);
+
+		pub fn gas_left_v1(output_ptr: *mut u8, output_len_ptr: *mut u32);
+
+		pub fn get_storage(key_ptr: *const u8, out_ptr: *mut u8, out_len_ptr: *mut u32) -> u32;
+
+		pub fn get_storage_v1(
+			key_ptr: *const u8,
+			key_len: u32,
+			out_ptr: *mut u8,
+			out_len_ptr: *mut u32,
+		) -> u32;
+
+		pub fn hash_blake2_128(input_ptr: *const u8, input_len: u32, output_ptr: *mut u8);
+
+		pub fn hash_blake2_256(input_ptr: *const u8, input_len: u32, output_ptr: *mut u8);
+
+		pub fn hash_keccak_256(input_ptr: *const u8, input_len: u32, output_ptr: *mut u8);
+
+		pub fn hash_sha2_256(input_ptr: *const u8, input_len: u32, output_ptr: *mut u8);
+
+		pub fn input(buf_ptr: *mut u8, buf_len_ptr: *mut u32);
+
+		pub fn instantiate_v1(packed_args: *const u32) -> u32;
+
+		pub fn instantiate_v2(packed_args: *const u32) -> u32;
+
+		pub fn instantiation_nonce() -> u64;
+
+		pub fn is_contract(account_id_ptr: *const u8) -> u32;
+
+		pub fn minimum_balance(output_ptr: *mut u8, output_len_ptr: *mut u32);
+
+		pub fn now(output_ptr: *mut u8, output_len_ptr: *mut u32);
+
+		pub fn own_code_hash(output_ptr: *mut u8, output_len_ptr: *mut u32);
+
+		pub fn reentrance_count() -> u32;
+
+		pub fn unlock_delegate_dependency(code_hash_ptr: *const u8);
+
+		pub fn seal_return(flags: u32, data_ptr: *const u8, data_len: u32);
+
+		pub fn set_code_hash(code_hash_ptr: *const u8) -> u32;
+
+		pub fn set_storage(key_ptr: *const u8, value_ptr: *const u8, value_len: u32);
+
+		pub fn set_storage_v1(key_ptr: *const u8, value_ptr: *const u8, value_len: u32) -> u32;
+
+		pub fn set_storage_v2(
+			key_ptr: *const u8,
+			key_len: u32,
+			value_ptr: *const u8,
+			value_len: u32,
+		) -> u32;
+
+		pub fn sr25519_verify(
+			signature_ptr: *const u8,
+			public_key_ptr: *const u8,
+			message_len: u32,
+			message_ptr: *const u8,
+		) -> u32;
+
+		pub fn take_storage(
+			key_ptr: *const u8,
+			key_len: u32,
+			out_ptr: *mut u8,
+			out_len_ptr: *mut u32,
+		) -> u32;
+
+		pub fn terminate(beneficiary_ptr: *const u8);
+
+		pub fn terminate_v1(beneficiary_ptr: *const u8);
+
+		pub fn transfer(
+			account_id_ptr: *const u8,
+			account_id_len: u32,
+			transferred_value_ptr: *const u8,
+			transferred_value_len: u32,
+		) -> u32;
+
+		pub fn value_transferred(output_ptr: *mut u8, output_len_ptr: *mut u32);
+
+		pub fn weight_to_fee(
+			gas_lo: u32,
+			gas_hi: u32,
+			output_ptr: *mut u8,
+			output_len_ptr: *mut u32,
+		);
+
+		pub fn weight_to_fee_v1(
+			ref_time_limit_lo: u32,
+			ref_time_limit_hi: u32,
+			proof_size_limit_lo: u32,
+			proof_size_limit_hi: u32,
+			output_ptr: *mut u8,
+			output_len_ptr: *mut u32,
+		);
+
+		pub fn xcm_execute(msg_ptr: *const u8, msg_len: u32) -> u32;
+
+		pub fn xcm_send(
+			dest_ptr: *const u8,
+			msg_ptr: *const u8,
+			msg_len: u32,
+			output_ptr: *mut u8,
+		) -> u32;
+	}
+}
+
+/// Split an `u64` into its low and high word.
+#[inline(always)]
+fn words(value: u64) -> (u32, u32) {
+	(value as u32, (value >> 32) as u32)
+}
 
 /// A macro to implement all Host functions with a signature of `fn(&mut &mut [u8])`.
 ///
@@ -33,14 +233,18 @@ use crate::ReturnFlags;
 //     unsafe { sys::gas_left(...); }
 // }
 // fn gas_left_v1(output: &mut &mut [u8]) {
-//     unsafe { sys::v1::gas_left(...); }
+//     unsafe { sys::gas_left_v1(...); }
 // }
 // ```
 macro_rules! impl_wrapper_for {
-	(@impl_fn $( $mod:ident )::*, $suffix_sep: literal, $suffix:tt, $name:ident) => {
+	(@impl_fn $suffix_sep: literal, $suffix:tt, $name:ident) => {
 		paste::paste! {
 			fn [<$name $suffix_sep $suffix>](output: &mut &mut [u8]) {
-				todo!()
+				let mut output_len = output.len() as u32;
+				unsafe {
+					sys::[<$name $suffix_sep $suffix>](output.as_mut_ptr(), &mut output_len);
+				}
+				extract_from_slice(output, output_len as usize)
 			}
 		}
 	};
@@ -48,12 +252,12 @@ macro_rules! impl_wrapper_for {
 	() => {};
 
 	(($mod:ident) => [$( $name:ident),*], $($tail:tt)*) => {
-		$(impl_wrapper_for!(@impl_fn sys::$mod, "_", $mod, $name);)*
+		$(impl_wrapper_for!(@impl_fn "_", $mod, $name);)*
 		impl_wrapper_for!($($tail)*);
 	};
 
 	(() =>	[$( $name:ident),*], $($tail:tt)*) => {
-		$(impl_wrapper_for!(@impl_fn sys, "", "", $name);)*
+		$(impl_wrapper_for!(@impl_fn "", "", $name);)*
 		impl_wrapper_for!($($tail)*);
 	};
 }
@@ -63,22 +267,20 @@ macro_rules! impl_hash_fn {
 	( $name:ident, $bytes_result:literal ) => {
 		paste::item! {
 			fn [<hash_ $name>](input: &[u8], output: &mut [u8; $bytes_result]) {
-				todo!()
+				unsafe {
+					sys::[<hash_ $name>](
+						input.as_ptr(),
+						input.len() as u32,
+						output.as_mut_ptr(),
+					)
+				}
 			}
 		}
 	};
 }
 
-/// A macro to implement the get_storage functions.
-macro_rules! impl_get_storage {
-	($fn_name:ident, $sys_get_storage:path) => {
-		fn $fn_name(key: &[u8], output: &mut &mut [u8]) -> Result {
-			todo!()
-		}
-	};
-}
-
 impl HostFn for HostFnImpl {
+	#[inline(always)]
 	fn instantiate_v1(
 		code_hash: &[u8],
 		gas: u64,
@@ -88,7 +290,32 @@ impl HostFn for HostFnImpl {
 		mut output: Option<&mut &mut [u8]>,
 		salt: &[u8],
 	) -> Result {
-		todo!()
+		let (address_ptr, mut address_len) = ptr_len_or_sentinel(&mut address);
+		let (output_ptr, mut output_len) = ptr_len_or_sentinel(&mut output);
+		let (gas_lo, gas_hi) = words(gas);
+		let args = [
+			code_hash.as_ptr() as u32,
+			gas_lo,
+			gas_hi,
+			value.as_ptr() as u32,
+			input.as_ptr() as u32,
+			input.len() as u32,
+			address_ptr as u32,
+			&mut address_len as *mut u32 as u32,
+			output_ptr as u32,
+			&mut output_len as *mut u32 as u32,
+			salt.as_ptr() as u32,
+			salt.len() as u32,
+		];
+		let ret_code = ReturnCode(unsafe { sys::instantiate_v1(args.as_ptr()) });
+
+		if let Some(ref mut address) = address {
+			extract_from_slice(address, address_len as usize);
+		}
+		if let Some(ref mut output) = output {
+			extract_from_slice(output, output_len as usize);
+		}
+		ret_code.into()
 	}
 
 	fn instantiate_v2(
@@ -102,9 +329,42 @@ impl HostFn for HostFnImpl {
 		mut output: Option<&mut &mut [u8]>,
 		salt: &[u8],
 	) -> Result {
-		todo!()
+		let (address_ptr, mut address_len) = ptr_len_or_sentinel(&mut address);
+		let (output_ptr, mut output_len) = ptr_len_or_sentinel(&mut output);
+		let deposit_ptr = ptr_or_sentinel(&deposit);
+		let (ref_time_limit_lo, ref_time_limit_hi) = words(ref_time_limit);
+		let (proof_size_limit_lo, proof_size_limit_hi) = words(proof_size_limit);
+		let args = [
+			code_hash.as_ptr() as u32,
+			ref_time_limit_lo,
+			ref_time_limit_hi,
+			proof_size_limit_lo,
+			proof_size_limit_hi,
+			deposit_ptr as u32,
+			value.as_ptr() as u32,
+			input.as_ptr() as u32,
+			input.len() as u32,
+			address_ptr as u32,
+			&mut address_len as *mut u32 as u32,
+			output_ptr as u32,
+			&mut output_len as *mut u32 as u32,
+			salt.as_ptr() as u32,
+			salt.len() as u32,
+		];
+		let ret_code = ReturnCode(unsafe { sys::instantiate_v2(args.as_ptr()) });
+
+		if let Some(ref mut address) = address {
+			extract_from_slice(address, address_len as usize);
+		}
+
+		if let Some(ref mut output) = output {
+			extract_from_slice(output, output_len as usize);
+		}
+
+		ret_code.into()
 	}
 
+	#[inline(always)]
 	fn call(
 		callee: &[u8],
 		gas: u64,
@@ -112,9 +372,30 @@ impl HostFn for HostFnImpl {
 		input_data: &[u8],
 		mut output: Option<&mut &mut [u8]>,
 	) -> Result {
-		todo!()
+		let (output_ptr, mut output_len) = ptr_len_or_sentinel(&mut output);
+		let (gas_lo, gas_hi) = words(gas);
+		let args = [
+			callee.as_ptr() as u32,
+			callee.len() as u32,
+			gas_lo,
+			gas_hi,
+			value.as_ptr() as u32,
+			value.len() as u32,
+			input_data.as_ptr() as u32,
+			input_data.len() as u32,
+			output_ptr as u32,
+			&mut output_len as *mut u32 as u32,
+		];
+		let ret_code = ReturnCode(unsafe { sys::call(args.as_ptr()) });
+
+		if let Some(ref mut output) = output {
+			extract_from_slice(output, output_len as usize);
+		}
+
+		ret_code.into()
 	}
 
+	#[inline(always)]
 	fn call_v1(
 		flags: CallFlags,
 		callee: &[u8],
@@ -123,7 +404,26 @@ impl HostFn for HostFnImpl {
 		input_data: &[u8],
 		mut output: Option<&mut &mut [u8]>,
 	) -> Result {
-		todo!()
+		let (output_ptr, mut output_len) = ptr_len_or_sentinel(&mut output);
+		let (gas_lo, gas_hi) = words(gas);
+		let args = [
+			flags.bits(),
+			callee.as_ptr() as u32,
+			gas_lo,
+			gas_hi,
+			value.as_ptr() as u32,
+			input_data.as_ptr() as u32,
+			input_data.len() as u32,
+			output_ptr as u32,
+			&mut output_len as *mut u32 as u32,
+		];
+		let ret_code = ReturnCode(unsafe { sys::call_v1(args.as_ptr()) });
+
+		if let Some(ref mut output) = output {
+			extract_from_slice(output, output_len as usize);
+		}
+
+		ret_code.into()
 	}
 
 	fn call_v2(
@@ -136,91 +436,212 @@ impl HostFn for HostFnImpl {
 		input_data: &[u8],
 		mut output: Option<&mut &mut [u8]>,
 	) -> Result {
-		todo!()
+		let (output_ptr, mut output_len) = ptr_len_or_sentinel(&mut output);
+		let deposit_ptr = ptr_or_sentinel(&deposit);
+		let (ref_time_limit_lo, ref_time_limit_hi) = words(ref_time_limit);
+		let (proof_size_limit_lo, proof_size_limit_hi) = words(proof_size_limit);
+		let args = [
+			flags.bits(),
+			callee.as_ptr() as u32,
+			ref_time_limit_lo,
+			ref_time_limit_hi,
+			proof_size_limit_lo,
+			proof_size_limit_hi,
+			deposit_ptr as u32,
+			value.as_ptr() as u32,
+			input_data.as_ptr() as u32,
+			input_data.len() as u32,
+			output_ptr as u32,
+			&mut output_len as *mut u32 as u32,
+		];
+		let ret_code = ReturnCode(unsafe { sys::call_v2(args.as_ptr()) });
+
+		if let Some(ref mut output) = output {
+			extract_from_slice(output, output_len as usize);
+		}
+
+		ret_code.into()
 	}
 
 	fn caller_is_root() -> u32 {
-		todo!()
+		ReturnCode(unsafe { sys::caller_is_root() }).into_u32()
 	}
 
+	#[inline(always)]
 	fn delegate_call(
 		flags: CallFlags,
 		code_hash: &[u8],
 		input: &[u8],
 		mut output: Option<&mut &mut [u8]>,
 	) -> Result {
-		todo!()
+		let (output_ptr, mut output_len) = ptr_len_or_sentinel(&mut output);
+		let ret_code = ReturnCode(unsafe {
+			sys::delegate_call(
+				flags.bits(),
+				code_hash.as_ptr(),
+				input.as_ptr(),
+				input.len() as u32,
+				output_ptr,
+				&mut output_len,
+			)
+		});
+
+		if let Some(ref mut output) = output {
+			extract_from_slice(output, output_len as usize);
+		}
+
+		ret_code.into()
 	}
 
 	fn transfer(account_id: &[u8], value: &[u8]) -> Result {
-		todo!()
+		let ret_code = ReturnCode(unsafe {
+			sys::transfer(
+				account_id.as_ptr(),
+				account_id.len() as u32,
+				value.as_ptr(),
+				value.len() as u32,
+			)
+		});
+		ret_code.into()
 	}
 
 	fn deposit_event(topics: &[u8], data: &[u8]) {
-		todo!()
+		unsafe {
+			sys::deposit_event(
+				topics.as_ptr(),
+				topics.len() as u32,
+				data.as_ptr(),
+				data.len() as u32,
+			)
+		}
 	}
 
 	fn set_storage(key: &[u8], value: &[u8]) {
-		todo!()
+		unsafe { sys::set_storage(key.as_ptr(), value.as_ptr(), value.len() as u32) };
 	}
 
 	fn set_storage_v1(key: &[u8], encoded_value: &[u8]) -> Option<u32> {
-		todo!()
+		let ret_code = ReturnCode(unsafe {
+			sys::set_storage_v1(key.as_ptr(), encoded_value.as_ptr(), encoded_value.len() as u32)
+		});
+		ret_code.into()
 	}
 
 	fn set_storage_v2(key: &[u8], encoded_value: &[u8]) -> Option<u32> {
-		todo!()
+		let ret_code = ReturnCode(unsafe {
+			sys::set_storage_v2(
+				key.as_ptr(),
+				key.len() as u32,
+				encoded_value.as_ptr(),
+				encoded_value.len() as u32,
+			)
+		});
+		ret_code.into()
 	}
 
 	fn clear_storage(key: &[u8]) {
-		todo!()
+		unsafe { sys::clear_storage(key.as_ptr()) };
 	}
 
 	fn clear_storage_v1(key: &[u8]) -> Option<u32> {
-		todo!()
+		let ret_code = ReturnCode(unsafe { sys::clear_storage_v1(key.as_ptr(), key.len() as u32) });
+		ret_code.into()
+	}
+
+	#[inline(always)]
+	fn get_storage(key: &[u8], output: &mut &mut [u8]) -> Result {
+		let mut output_len = output.len() as u32;
+		let ret_code = ReturnCode(unsafe {
+			sys::get_storage(key.as_ptr(), output.as_mut_ptr(), &mut output_len)
+		});
+		extract_from_slice(output, output_len as usize);
+		ret_code.into()
+	}
+
+	#[inline(always)]
+	fn get_storage_v1(key: &[u8], output: &mut &mut [u8]) -> Result {
+		let mut output_len = output.len() as u32;
+		let ret_code = ReturnCode(unsafe {
+			sys::get_storage_v1(
+				key.as_ptr(),
+				key.len() as u32,
+				output.as_mut_ptr(),
+				&mut output_len,
+			)
+		});
+		extract_from_slice(output, output_len as usize);
+		ret_code.into()
+	}
+
+	#[inline(always)]
+	fn take_storage(key: &[u8], output: &mut &mut [u8]) -> Result {
+		let mut output_len = output.len() as u32;
+		let ret_code = ReturnCode(unsafe {
+			sys::take_storage(key.as_ptr(), key.len() as u32, output.as_mut_ptr(), &mut output_len)
+		});
+		extract_from_slice(output, output_len as usize);
+		ret_code.into()
 	}
 
-	impl_get_storage!(get_storage, sys::get_storage);
-	impl_get_storage!(get_storage_v1, sys::v1::get_storage);
-
-	fn take_storage(key: &[u8], output: &mut &mut [u8]) -> Result {
-		todo!()
+	fn debug_message(str: &[u8]) -> Result {
+		let ret_code = ReturnCode(unsafe { sys::debug_message(str.as_ptr(), str.len() as u32) });
+		ret_code.into()
 	}
 
 	fn contains_storage(key: &[u8]) -> Option<u32> {
-		todo!()
+		let ret_code = ReturnCode(unsafe { sys::contains_storage(key.as_ptr()) });
+		ret_code.into()
 	}
 
 	fn contains_storage_v1(key: &[u8]) -> Option<u32> {
-		todo!()
+		let ret_code =
+			ReturnCode(unsafe { sys::contains_storage_v1(key.as_ptr(), key.len() as u32) });
+		ret_code.into()
 	}
 
 	fn terminate(beneficiary: &[u8]) -> ! {
-		todo!()
+		unsafe { sys::terminate(beneficiary.as_ptr()) };
+		unreachable!("terminate does not return to the contract")
 	}
 
 	fn terminate_v1(beneficiary: &[u8]) -> ! {
-		todo!()
-	}
-
-	fn call_chain_extension(func_id: u32, input: &[u8], output: Option<&mut &mut [u8]>) -> u32 {
-		todo!()
+		unsafe { sys::terminate_v1(beneficiary.as_ptr()) };
+		unreachable!("terminate does not return to the contract")
+	}
+
+	fn call_chain_extension(func_id: u32, input: &[u8], mut output: Option<&mut &mut [u8]>) -> u32 {
+		let (output_ptr, mut output_len) = ptr_len_or_sentinel(&mut output);
+		let ret_code = ReturnCode(unsafe {
+			sys::call_chain_extension(
+				func_id,
+				input.as_ptr(),
+				input.len() as u32,
+				output_ptr,
+				&mut output_len,
+			)
+		});
+
+		if let Some(ref mut output) = output {
+			extract_from_slice(output, output_len as usize);
+		}
+		ret_code.into_u32()
 	}
 
+	#[inline(always)]
 	fn input(output: &mut &mut [u8]) {
-		todo!()
+		let mut output_len = output.len() as u32;
+		unsafe { sys::input(output.as_mut_ptr(), &mut output_len) };
+		extract_from_slice(output, output_len as usize);
 	}
 
 	fn return_value(flags: ReturnFlags, return_value: &[u8]) -> ! {
-		todo!()
+		unsafe { sys::seal_return(flags.bits(), return_value.as_ptr(), return_value.len() as u32) };
+		unreachable!("seal_return does not return to the contract")
 	}
 
 	fn call_runtime(call: &[u8]) -> Result {
-		todo!()
-	}
-
-	fn debug_message(str: &[u8]) -> Result {
-		todo!()
+		let ret_code = ReturnCode(unsafe { sys::call_runtime(call.as_ptr(), call.len() as u32) });
+		ret_code.into()
 	}
 
 	impl_wrapper_for! {
@@ -228,12 +649,29 @@ impl HostFn for HostFnImpl {
 		(v1) => [gas_left],
 	}
 
+	#[inline(always)]
 	fn weight_to_fee(gas: u64, output: &mut &mut [u8]) {
-		todo!()
+		let mut output_len = output.len() as u32;
+		let (gas_lo, gas_hi) = words(gas);
+		unsafe { sys::weight_to_fee(gas_lo, gas_hi, output.as_mut_ptr(), &mut output_len) };
+		extract_from_slice(output, output_len as usize);
 	}
 
 	fn weight_to_fee_v1(ref_time_limit: u64, proof_size_limit: u64, output: &mut &mut [u8]) {
-		todo!()
+		let mut output_len = output.len() as u32;
+		let (ref_time_limit_lo, ref_time_limit_hi) = words(ref_time_limit);
+		let (proof_size_limit_lo, proof_size_limit_hi) = words(proof_size_limit);
+		unsafe {
+			sys::weight_to_fee_v1(
+				ref_time_limit_lo,
+				ref_time_limit_hi,
+				proof_size_limit_lo,
+				proof_size_limit_hi,
+				output.as_mut_ptr(),
+				&mut output_len,
+			)
+		};
+		extract_from_slice(output, output_len as usize);
 	}
 
 	impl_hash_fn!(sha2_256, 32);
@@ -246,62 +684,87 @@ impl HostFn for HostFnImpl {
 		message_hash: &[u8; 32],
 		output: &mut [u8; 33],
 	) -> Result {
-		todo!()
+		let ret_code = ReturnCode(unsafe {
+			sys::ecdsa_recover(signature.as_ptr(), message_hash.as_ptr(), output.as_mut_ptr())
+		});
+		ret_code.into()
 	}
 
 	fn ecdsa_to_eth_address(pubkey: &[u8; 33], output: &mut [u8; 20]) -> Result {
-		todo!()
+		let ret_code =
+			ReturnCode(unsafe { sys::ecdsa_to_eth_address(pubkey.as_ptr(), output.as_mut_ptr()) });
+		ret_code.into()
 	}
 
 	fn sr25519_verify(signature: &[u8; 64], message: &[u8], pub_key: &[u8; 32]) -> Result {
-		todo!()
+		let ret_code = ReturnCode(unsafe {
+			sys::sr25519_verify(
+				signature.as_ptr(),
+				pub_key.as_ptr(),
+				message.len() as u32,
+				message.as_ptr(),
+			)
+		});
+		ret_code.into()
 	}
 
 	fn is_contract(account_id: &[u8]) -> bool {
-		todo!()
+		let ret_val = ReturnCode(unsafe { sys::is_contract(account_id.as_ptr()) });
+		ret_val.into_bool()
 	}
 
 	fn caller_is_origin() -> bool {
-		todo!()
+		let ret_val = ReturnCode(unsafe { sys::caller_is_origin() });
+		ret_val.into_bool()
 	}
 
 	fn set_code_hash(code_hash: &[u8]) -> Result {
-		todo!()
+		let ret_val = ReturnCode(unsafe { sys::set_code_hash(code_hash.as_ptr()) });
+		ret_val.into()
 	}
 
 	fn code_hash(account_id: &[u8], output: &mut [u8]) -> Result {
-		todo!()
+		let mut output_len = output.len() as u32;
+		let ret_val = ReturnCode(unsafe {
+			sys::code_hash(account_id.as_ptr(), output.as_mut_ptr(), &mut output_len)
+		});
+		ret_val.into()
 	}
 
 	fn own_code_hash(output: &mut [u8]) {
-		todo!()
+		let mut output_len = output.len() as u32;
+		unsafe { sys::own_code_hash(output.as_mut_ptr(), &mut output_len) }
 	}
 
 	fn account_reentrance_count(account: &[u8]) -> u32 {
-		todo!()
+		unsafe { sys::account_reentrance_count(account.as_ptr()) }
 	}
 
 	fn lock_delegate_dependency(code_hash: &[u8]) {
-		todo!()
+		unsafe { sys::lock_delegate_dependency(code_hash.as_ptr()) }
 	}
 
 	fn unlock_delegate_dependency(code_hash: &[u8]) {
-		todo!()
+		unsafe { sys::unlock_delegate_dependency(code_hash.as_ptr()) }
 	}
 
 	fn instantiation_nonce() -> u64 {
-		todo!()
+		unsafe { sys::instantiation_nonce() }
 	}
 
 	fn reentrance_count() -> u32 {
-		todo!()
+		unsafe { sys::reentrance_count() }
 	}
 
 	fn xcm_execute(msg: &[u8]) -> Result {
-		todo!()
+		let ret_code = ReturnCode(unsafe { sys::xcm_execute(msg.as_ptr(), msg.len() as _) });
+		ret_code.into()
 	}
 
 	fn xcm_send(dest: &[u8], msg: &[u8], output: &mut [u8; 32]) -> Result {
-		todo!()
+		let ret_code = ReturnCode(unsafe {
+			sys::xcm_send(dest.as_ptr(), msg.as_ptr(), msg.len() as _, output.as_mut_ptr())
+		});
+		ret_code.into()
 	}
 }
@@ -123,6 +123,40 @@ benchmarks_instance_pallet! {
 		);
 	}
 
+	vote_override {
+		let caller = funded_account::<T, I>("caller", 0);
+		whitelist_account!(caller);
+		let voter = funded_account::<T, I>("voter", 0);
+		let voter_lookup = T::Lookup::unlookup(voter.clone());
+		let account_vote = account_vote::<T, I>(100u32.into());
+
+		let (class, all_polls) = fill_voting::<T, I>();
+		let polls = &all_polls[&class];
+		let r = polls.len() - 1;
+		// The delegate votes on all the polls, so that every override withdraws the delegation.
+		for i in polls.iter() {
+			ConvictionVoting::<T, I>::vote(RawOrigin::Signed(voter.clone()).into(), *i, account_vote)?;
+		}
+		ConvictionVoting::<T, I>::delegate(
+			RawOrigin::Signed(caller.clone()).into(),
+			class.clone(),
+			voter_lookup,
+			Conviction::Locked1x,
+			1000u32.into(),
+		)?;
+		// We need to create existing overrides
+		for i in polls.iter().skip(1) {
+			ConvictionVoting::<T, I>::vote(RawOrigin::Signed(caller.clone()).into(), *i, account_vote)?;
+		}
+		assert_eq!(OverridesFor::<T, I>::get(&caller, &class).len(), r, "Overrides were not recorded.");
+
+		let index = polls[0];
+	}: vote(RawOrigin::Signed(caller.clone()), index, account_vote)
+	verify {
+		assert_eq!(OverridesFor::<T, I>::get(&caller, &class).len(), r + 1);
+		assert_matches!(VotingFor::<T, I>::get(&caller, &class), Voting::Delegating(_));
+	}
+
 	remove_vote {
 		let caller = funded_account::<T, I>("caller", 0);
 		whitelist_account!(caller);
@@ -180,6 +214,38 @@ benchmarks_instance_pallet! {
 		);
 	}
 
+	remove_override {
+		let caller = funded_account::<T, I>("caller", 0);
+		whitelist_account!(caller);
+		let voter = funded_account::<T, I>("voter", 0);
+		let voter_lookup = T::Lookup::unlookup(voter.clone());
+		let account_vote = account_vote::<T, I>(100u32.into());
+
+		let (class, all_polls) = fill_voting::<T, I>();
+		let polls = &all_polls[&class];
+		let r = polls.len();
+		for i in polls.iter() {
+			ConvictionVoting::<T, I>::vote(RawOrigin::Signed(voter.clone()).into(), *i, account_vote)?;
+		}
+		ConvictionVoting::<T, I>::delegate(
+			RawOrigin::Signed(caller.clone()).into(),
+			class.clone(),
+			voter_lookup,
+			Conviction::Locked1x,
+			1000u32.into(),
+		)?;
+		// We need to create existing overrides
+		for i in polls.iter() {
+			ConvictionVoting::<T, I>::vote(RawOrigin::Signed(caller.clone()).into(), *i, account_vote)?;
+		}
+		assert_eq!(OverridesFor::<T, I>::get(&caller, &class).len(), r, "Overrides were not recorded.");
+
+		let index = polls[0];
+	}: remove_vote(RawOrigin::Signed(caller.clone()), Some(class.clone()), index)
+	verify {
+		assert_eq!(OverridesFor::<T, I>::get(&caller, &class).len(), r - 1);
+	}
+
 	delegate {
 		let r in 0 .. T::MaxVotes::get().min(T::Polls::max_ongoing().1);
 
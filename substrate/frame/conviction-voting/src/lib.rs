@@ -41,6 +41,7 @@ use sp_runtime::{
 	ArithmeticError, DispatchError, Perbill,
 };
 use sp_std::prelude::*;
+use vote::PriorLock;
 
 mod conviction;
 mod types;
@@ -91,6 +92,7 @@ pub mod pallet {
 			DispatchResultWithPostInfo, IsType, StorageDoubleMap, StorageMap, ValueQuery,
 		},
 		traits::ClassCountOf,
+		weights::Weight,
 		Twox64Concat,
 	};
 	use frame_system::pallet_prelude::*;
@@ -163,6 +165,33 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// The votes which delegating accounts have cast in place of their delegate in a particular
+	/// voting class. For each of these polls, the delegated voting power of the account is not
+	/// counted towards the vote of its delegate.
+	#[pallet::storage]
+	pub type OverridesFor<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		ClassOf<T, I>,
+		BoundedVec<(PollIndexOf<T, I>, AccountVote<BalanceOf<T, I>>), T::MaxVotes>,
+		ValueQuery,
+	>;
+
+	/// The delegations which are withheld from the vote of a delegate on a particular poll,
+	/// because the accounts delegating to it have cast their own vote on that poll.
+	#[pallet::storage]
+	pub type RetractedDelegationsFor<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		PollIndexOf<T, I>,
+		Delegations<BalanceOf<T, I>>,
+		ValueQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -199,6 +228,8 @@ pub mod pallet {
 		ClassNeeded,
 		/// The class ID supplied is invalid.
 		BadClass,
+		/// No class was supplied.
+		NoClasses,
 	}
 
 	#[pallet::call]
@@ -206,6 +237,11 @@ pub mod pallet {
 		/// Vote in a poll. If `vote.is_aye()`, the vote is to enact the proposal;
 		/// otherwise it is a vote to keep the status quo.
 		///
+		/// If the signing account is delegating in the class of the poll, the vote overrides its
+		/// delegation for this poll only: the delegated voting power is withdrawn from the vote of
+		/// the delegate on the poll for as long as the override is in place. The override is
+		/// removed through `remove_vote`, and becomes a direct vote when the account undelegates.
+		///
 		/// The dispatch origin of this call must be _Signed_.
 		///
 		/// - `poll_index`: The index of the poll to vote for.
@@ -213,7 +249,11 @@ pub mod pallet {
 		///
 		/// Weight: `O(R)` where R is the number of polls the voter has voted on.
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::vote_new().max(T::WeightInfo::vote_existing()))]
+		#[pallet::weight(
+			T::WeightInfo::vote_new()
+				.max(T::WeightInfo::vote_existing())
+				.max(T::WeightInfo::vote_override())
+		)]
 		pub fn vote(
 			origin: OriginFor<T>,
 			#[pallet::compact] poll_index: PollIndexOf<T, I>,
@@ -235,8 +275,8 @@ pub mod pallet {
 		///     `remove_vote`).
 		///
 		/// - `to`: The account whose voting the `target` account's voting power will follow.
-		/// - `class`: The class of polls to delegate. To delegate multiple classes, multiple calls
-		///   to this function are required.
+		/// - `class`: The class of polls to delegate. To delegate multiple classes at once, use
+		///   `delegate_multiple`.
 		/// - `conviction`: The conviction that will be attached to the delegated votes. When the
 		///   account is undelegated, the funds will be locked for the corresponding period.
 		/// - `balance`: The amount of the account's balance to be used in delegating. This must not
@@ -274,14 +314,17 @@ pub mod pallet {
 		///
 		/// - `class`: The class of polls to remove the delegation from.
 		///
+		/// The votes with which the account overrode its delegation become its direct votes.
+		///
 		/// Emits `Undelegated`.
 		///
-		/// Weight: `O(R)` where R is the number of polls the voter delegating to has
-		///   voted on. Weight is initially charged as if maximum votes, but is refunded later.
+		/// Weight: `O(R + O)` where R is the number of polls the voter delegating to has
+		///   voted on and O the number of overrides of the account. Weight is initially charged as
+		///   if maximum votes, but is refunded later.
 		// NOTE: weight must cover an incorrect voting of origin with max votes, this is ensure
 		// because a valid delegation cover decoding a direct voting with max votes.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::undelegate(T::MaxVotes::get().into()))]
+		#[pallet::weight(T::WeightInfo::undelegate(T::MaxVotes::get().saturating_mul(2)))]
 		pub fn undelegate(
 			origin: OriginFor<T>,
 			class: ClassOf<T, I>,
@@ -333,6 +376,9 @@ pub mod pallet {
 		/// *overlocking* (where the two locks are combined into a single lock that is the maximum
 		/// of both the amount locked and the time is it locked for).
 		///
+		/// If the account is delegating, the vote is an override of its delegation and removing it
+		/// returns the delegated voting power to the vote of the delegate on the poll.
+		///
 		/// The dispatch origin of this call must be _Signed_, and the signer must have a vote
 		/// registered for poll `index`.
 		///
@@ -343,7 +389,7 @@ pub mod pallet {
 		/// Weight: `O(R + log R)` where R is the number of polls that `target` has voted on.
 		///   Weight is calculated for the maximum number of vote.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::remove_vote())]
+		#[pallet::weight(T::WeightInfo::remove_vote().max(T::WeightInfo::remove_override()))]
 		pub fn remove_vote(
 			origin: OriginFor<T>,
 			class: Option<ClassOf<T, I>>,
@@ -370,7 +416,7 @@ pub mod pallet {
 		/// Weight: `O(R + log R)` where R is the number of polls that `target` has voted on.
 		///   Weight is calculated for the maximum number of vote.
 		#[pallet::call_index(5)]
-		#[pallet::weight(T::WeightInfo::remove_other_vote())]
+		#[pallet::weight(T::WeightInfo::remove_other_vote().max(T::WeightInfo::remove_override()))]
 		pub fn remove_other_vote(
 			origin: OriginFor<T>,
 			target: AccountIdLookupOf<T>,
@@ -383,6 +429,50 @@ pub mod pallet {
 			Self::try_remove_vote(&target, index, Some(class), scope)?;
 			Ok(())
 		}
+
+		/// Delegate the voting power (with some given conviction) of the sending account for
+		/// several classes of polls at once.
+		///
+		/// This is equivalent to calling `delegate` once for each of the `classes`, and fails as
+		/// a whole if any of the delegations fails.
+		///
+		/// The dispatch origin of this call must be _Signed_, and the signing account must have
+		/// no voting activity in any of the `classes`.
+		///
+		/// - `classes`: The classes of polls to delegate.
+		/// - `to`: The account whose voting the `target` account's voting power will follow.
+		/// - `conviction`: The conviction that will be attached to the delegated votes.
+		/// - `balance`: The amount of the account's balance to be used in delegating in each of the
+		///   classes.
+		///
+		/// Emits `Delegated` for each of the classes.
+		///
+		/// Weight: `O(C * R)` where C is the number of classes and R is the number of polls the
+		///   voter delegating to has voted on. Weight is initially charged as if maximum votes,
+		///   but is refunded later.
+		#[pallet::call_index(6)]
+		#[pallet::weight(
+			T::WeightInfo::delegate(T::MaxVotes::get()).saturating_mul(classes.len() as u64)
+		)]
+		pub fn delegate_multiple(
+			origin: OriginFor<T>,
+			classes: BoundedVec<ClassOf<T, I>, ClassCountOf<T::Polls, TallyOf<T, I>>>,
+			to: AccountIdLookupOf<T>,
+			conviction: Conviction,
+			balance: BalanceOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+			ensure!(!classes.is_empty(), Error::<T, I>::NoClasses);
+			let mut weight = Weight::zero();
+			for class in classes {
+				let votes =
+					Self::try_delegate(who.clone(), class, to.clone(), conviction, balance)?;
+				weight.saturating_accrue(T::WeightInfo::delegate(votes));
+			}
+
+			Ok(Some(weight).into())
+		}
 	}
 }
 
@@ -400,29 +490,65 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		T::Polls::try_access_poll(poll_index, |poll_status| {
 			let (tally, class) = poll_status.ensure_ongoing().ok_or(Error::<T, I>::NotOngoing)?;
 			VotingFor::<T, I>::try_mutate(who, &class, |voting| {
-				if let Voting::Casting(Casting { ref mut votes, delegations, .. }) = voting {
-					match votes.binary_search_by_key(&poll_index, |i| i.0) {
-						Ok(i) => {
-							// Shouldn't be possible to fail, but we handle it gracefully.
-							tally.remove(votes[i].1).ok_or(ArithmeticError::Underflow)?;
-							if let Some(approve) = votes[i].1.as_standard() {
-								tally.reduce(approve, *delegations);
-							}
-							votes[i].1 = vote;
-						},
-						Err(i) => {
-							votes
-								.try_insert(i, (poll_index, vote))
-								.map_err(|_| Error::<T, I>::MaxVotesReached)?;
-						},
-					}
-					// Shouldn't be possible to fail, but we handle it gracefully.
-					tally.add(vote).ok_or(ArithmeticError::Overflow)?;
-					if let Some(approve) = vote.as_standard() {
-						tally.increase(approve, *delegations);
-					}
-				} else {
-					return Err(Error::<T, I>::AlreadyDelegating.into())
+				match voting {
+					Voting::Casting(Casting { ref mut votes, delegations, .. }) => {
+						// Only the delegations which were not withdrawn for this poll are counted.
+						let delegations = &delegations
+							.saturating_sub(RetractedDelegationsFor::<T, I>::get(who, poll_index));
+						match votes.binary_search_by_key(&poll_index, |i| i.0) {
+							Ok(i) => {
+								// Shouldn't be possible to fail, but we handle it gracefully.
+								tally.remove(votes[i].1).ok_or(ArithmeticError::Underflow)?;
+								if let Some(approve) = votes[i].1.as_standard() {
+									tally.reduce(approve, *delegations);
+								}
+								votes[i].1 = vote;
+							},
+							Err(i) => {
+								votes
+									.try_insert(i, (poll_index, vote))
+									.map_err(|_| Error::<T, I>::MaxVotesReached)?;
+							},
+						}
+						// Shouldn't be possible to fail, but we handle it gracefully.
+						tally.add(vote).ok_or(ArithmeticError::Overflow)?;
+						if let Some(approve) = vote.as_standard() {
+							tally.increase(approve, *delegations);
+						}
+					},
+					Voting::Delegating(Delegating { balance, target, conviction, .. }) => {
+						OverridesFor::<T, I>::try_mutate(
+							who,
+							&class,
+							|overrides| -> DispatchResult {
+								match overrides.binary_search_by_key(&poll_index, |i| i.0) {
+									Ok(i) => {
+										// Shouldn't be possible to fail, but we handle it
+										// gracefully.
+										tally
+											.remove(overrides[i].1)
+											.ok_or(ArithmeticError::Underflow)?;
+										overrides[i].1 = vote;
+									},
+									Err(i) => {
+										overrides
+											.try_insert(i, (poll_index, vote))
+											.map_err(|_| Error::<T, I>::MaxVotesReached)?;
+										Self::retract_delegation(
+											target,
+											&class,
+											poll_index,
+											tally,
+											conviction.votes(*balance),
+										);
+									},
+								}
+								// Shouldn't be possible to fail, but we handle it gracefully.
+								tally.add(vote).ok_or(ArithmeticError::Overflow)?;
+								Ok(())
+							},
+						)?;
+					},
 				}
 				// Extend the lock to `balance` (rather than setting it) since we don't know what
 				// other votes are in place.
@@ -447,8 +573,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		let class = class_hint
 			.or_else(|| Some(T::Polls::as_ongoing(poll_index)?.1))
 			.ok_or(Error::<T, I>::ClassNeeded)?;
-		VotingFor::<T, I>::try_mutate(who, class, |voting| {
-			if let Voting::Casting(Casting { ref mut votes, delegations, ref mut prior }) = voting {
+		VotingFor::<T, I>::try_mutate(who, &class, |voting| match voting {
+			Voting::Casting(Casting { ref mut votes, delegations, ref mut prior }) => {
 				let i = votes
 					.binary_search_by_key(&poll_index, |i| i.0)
 					.map_err(|_| Error::<T, I>::NotVoter)?;
@@ -460,34 +586,133 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						// Shouldn't be possible to fail, but we handle it gracefully.
 						tally.remove(v.1).ok_or(ArithmeticError::Underflow)?;
 						if let Some(approve) = v.1.as_standard() {
-							tally.reduce(approve, *delegations);
+							tally.reduce(
+								approve,
+								delegations.saturating_sub(RetractedDelegationsFor::<T, I>::get(
+									who, poll_index,
+								)),
+							);
 						}
 						Ok(())
 					},
+					PollStatus::Completed(end, approved) =>
+						Self::note_completed_vote(v.1, end, approved, scope, prior),
+					PollStatus::None => Ok(()), // Poll was cancelled.
+				})
+			},
+			Voting::Delegating(Delegating {
+				balance, target, conviction, ref mut prior, ..
+			}) => OverridesFor::<T, I>::try_mutate(who, &class, |overrides| {
+				let i = overrides
+					.binary_search_by_key(&poll_index, |i| i.0)
+					.map_err(|_| Error::<T, I>::NotVoter)?;
+				let v = overrides.remove(i);
+				let amount = conviction.votes(*balance);
+
+				T::Polls::try_access_poll(poll_index, |poll_status| match poll_status {
+					PollStatus::Ongoing(tally, _) => {
+						ensure!(matches!(scope, UnvoteScope::Any), Error::<T, I>::NoPermission);
+						// Shouldn't be possible to fail, but we handle it gracefully.
+						tally.remove(v.1).ok_or(ArithmeticError::Underflow)?;
+						Self::restore_delegation(target, &class, poll_index, tally, amount);
+						Ok(())
+					},
 					PollStatus::Completed(end, approved) => {
-						if let Some((lock_periods, balance)) = v.1.locked_if(approved) {
-							let unlock_at = end.saturating_add(
-								T::VoteLockingPeriod::get().saturating_mul(lock_periods.into()),
-							);
-							let now = frame_system::Pallet::<T>::block_number();
-							if now < unlock_at {
-								ensure!(
-									matches!(scope, UnvoteScope::Any),
-									Error::<T, I>::NoPermissionYet
-								);
-								prior.accumulate(unlock_at, balance)
-							}
-						}
+						Self::note_completed_vote(v.1, end, approved, scope, prior)?;
+						Self::release_retracted(target, poll_index, amount);
+						Ok(())
+					},
+					PollStatus::None => {
+						// Poll was cancelled.
+						Self::release_retracted(target, poll_index, amount);
 						Ok(())
 					},
-					PollStatus::None => Ok(()), // Poll was cancelled.
 				})
-			} else {
-				Ok(())
-			}
+			}),
 		})
 	}
 
+	/// Account for the removal of `vote` from a poll which ended at `end` with the result
+	/// `approved`, by adding any lock it still requires to `prior`.
+	fn note_completed_vote(
+		vote: AccountVote<BalanceOf<T, I>>,
+		end: BlockNumberFor<T>,
+		approved: bool,
+		scope: UnvoteScope,
+		prior: &mut PriorLock<BlockNumberFor<T>, BalanceOf<T, I>>,
+	) -> DispatchResult {
+		if let Some((lock_periods, balance)) = vote.locked_if(approved) {
+			let unlock_at =
+				end.saturating_add(T::VoteLockingPeriod::get().saturating_mul(lock_periods.into()));
+			let now = frame_system::Pallet::<T>::block_number();
+			if now < unlock_at {
+				ensure!(matches!(scope, UnvoteScope::Any), Error::<T, I>::NoPermissionYet);
+				prior.accumulate(unlock_at, balance)
+			}
+		}
+		Ok(())
+	}
+
+	/// Withdraw the delegations `amount` from the vote of `target` on the ongoing poll
+	/// `poll_index`, whose tally is `tally`.
+	fn retract_delegation(
+		target: &T::AccountId,
+		class: &ClassOf<T, I>,
+		poll_index: PollIndexOf<T, I>,
+		tally: &mut TallyOf<T, I>,
+		amount: Delegations<BalanceOf<T, I>>,
+	) {
+		RetractedDelegationsFor::<T, I>::mutate(target, poll_index, |retracted| {
+			*retracted = retracted.saturating_add(amount)
+		});
+		if let Some(approve) = Self::standard_vote_of(target, class, poll_index) {
+			tally.reduce(approve, amount);
+		}
+	}
+
+	/// Return the delegations `amount` previously withdrawn by `retract_delegation` to the vote
+	/// of `target` on the ongoing poll `poll_index`, whose tally is `tally`.
+	fn restore_delegation(
+		target: &T::AccountId,
+		class: &ClassOf<T, I>,
+		poll_index: PollIndexOf<T, I>,
+		tally: &mut TallyOf<T, I>,
+		amount: Delegations<BalanceOf<T, I>>,
+	) {
+		Self::release_retracted(target, poll_index, amount);
+		if let Some(approve) = Self::standard_vote_of(target, class, poll_index) {
+			tally.increase(approve, amount);
+		}
+	}
+
+	/// Forget the delegations `amount` withdrawn from the vote of `target` on `poll_index`,
+	/// without touching the tally of the poll.
+	fn release_retracted(
+		target: &T::AccountId,
+		poll_index: PollIndexOf<T, I>,
+		amount: Delegations<BalanceOf<T, I>>,
+	) {
+		RetractedDelegationsFor::<T, I>::mutate_exists(target, poll_index, |maybe_retracted| {
+			let retracted = maybe_retracted.unwrap_or_default().saturating_sub(amount);
+			*maybe_retracted = (retracted != Default::default()).then_some(retracted);
+		});
+	}
+
+	/// The direction of the standard vote of `who` on `poll_index`, if any.
+	fn standard_vote_of(
+		who: &T::AccountId,
+		class: &ClassOf<T, I>,
+		poll_index: PollIndexOf<T, I>,
+	) -> Option<bool> {
+		match VotingFor::<T, I>::get(who, class) {
+			Voting::Casting(Casting { votes, .. }) => votes
+				.binary_search_by_key(&poll_index, |i| i.0)
+				.ok()
+				.and_then(|i| votes[i].1.as_standard()),
+			Voting::Delegating(_) => None,
+		}
+	}
+
 	/// Return the number of votes for `who`.
 	fn increase_upstream_delegation(
 		who: &T::AccountId,
@@ -517,11 +742,18 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	}
 
 	/// Return the number of votes for `who`.
+	///
+	/// `amount` has already been withdrawn from the votes of `who` on the polls `overridden`,
+	/// which must be sorted.
 	fn reduce_upstream_delegation(
 		who: &T::AccountId,
 		class: &ClassOf<T, I>,
 		amount: Delegations<BalanceOf<T, I>>,
+		overridden: &[PollIndexOf<T, I>],
 	) -> u32 {
+		for &poll_index in overridden {
+			Self::release_retracted(who, poll_index, amount);
+		}
 		VotingFor::<T, I>::mutate(who, class, |voting| match voting {
 			Voting::Delegating(Delegating { delegations, .. }) => {
 				// We don't support second level delegating, so we don't need to do anything more.
@@ -531,6 +763,9 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			Voting::Casting(Casting { votes, delegations, .. }) => {
 				*delegations = delegations.saturating_sub(amount);
 				for &(poll_index, account_vote) in votes.iter() {
+					if overridden.binary_search(&poll_index).is_ok() {
+						continue
+					}
 					if let AccountVote::Standard { vote, .. } = account_vote {
 						T::Polls::access_poll(poll_index, |poll_status| {
 							if let PollStatus::Ongoing(tally, _) = poll_status {
@@ -604,11 +839,15 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						delegations,
 						mut prior,
 					}) => {
+						let overrides = OverridesFor::<T, I>::take(&who, &class);
+						let overridden =
+							overrides.iter().map(|(poll_index, _)| *poll_index).collect::<Vec<_>>();
 						// remove any delegation votes to our current target.
 						let votes = Self::reduce_upstream_delegation(
 							&target,
 							&class,
 							conviction.votes(balance),
+							&overridden,
 						);
 						let now = frame_system::Pallet::<T>::block_number();
 						let lock_periods = conviction.lock_periods().into();
@@ -618,9 +857,23 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 							),
 							balance,
 						);
-						voting.set_common(delegations, prior);
+						// The overrides become direct votes, which also carry the delegations to
+						// the account.
+						for &(poll_index, account_vote) in overrides.iter() {
+							if let Some(approve) = account_vote.as_standard() {
+								let delegations = delegations.saturating_sub(
+									RetractedDelegationsFor::<T, I>::get(&who, poll_index),
+								);
+								T::Polls::access_poll(poll_index, |poll_status| {
+									if let PollStatus::Ongoing(tally, _) = poll_status {
+										tally.increase(approve, delegations);
+									}
+								});
+							}
+						}
+						*voting = Voting::Casting(Casting { votes: overrides, delegations, prior });
 
-						Ok(votes)
+						Ok(votes.saturating_add(overridden.len() as u32))
 					},
 					Voting::Casting(_) => Err(Error::<T, I>::NotDelegating.into()),
 				}
@@ -658,7 +911,14 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		let class_lock_needed = VotingFor::<T, I>::mutate(who, class, |voting| {
 			voting.rejig(frame_system::Pallet::<T>::block_number());
 			voting.locked_balance()
-		});
+		})
+		.max(
+			OverridesFor::<T, I>::get(who, class)
+				.iter()
+				.map(|(_, vote)| vote.balance())
+				.max()
+				.unwrap_or_else(Zero::zero),
+		);
 		let lock_needed = ClassLocksFor::<T, I>::mutate(who, |locks| {
 			locks.retain(|x| &x.0 != class);
 			if !class_lock_needed.is_zero() {
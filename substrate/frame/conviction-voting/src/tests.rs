@@ -19,6 +19,8 @@
 
 use std::collections::BTreeMap;
 
+use assert_matches::assert_matches;
+
 use frame_support::{
 	assert_noop, assert_ok, derive_impl, parameter_types,
 	traits::{ConstU32, ConstU64, Contains, Polling, VoteTally},
@@ -516,6 +518,185 @@ fn classwise_delegation_works() {
 	});
 }
 
+#[test]
+fn delegator_can_override_delegate_vote() {
+	new_test_ext().execute_with(|| {
+		Polls::set(
+			vec![(0, Ongoing(Tally::new(0), 0)), (1, Ongoing(Tally::new(0), 0))]
+				.into_iter()
+				.collect(),
+		);
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(1), 0, 2, Conviction::Locked1x, 5));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 0, aye(20, 1)));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 1, aye(20, 1)));
+		assert_eq!(tally(0), Tally::from_parts(25, 0, 25));
+		assert_eq!(tally(1), Tally::from_parts(25, 0, 25));
+
+		// 1 overrides the vote of 2 on poll 0 only.
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 0, nay(10, 1)));
+		assert_eq!(tally(0), Tally::from_parts(20, 10, 20));
+		assert_eq!(tally(1), Tally::from_parts(25, 0, 25));
+		assert_eq!(OverridesFor::<Test>::get(1, 0).into_inner(), vec![(0, nay(10, 1))]);
+		assert_matches!(VotingFor::<Test>::get(1, 0), Voting::Delegating(_));
+
+		// Changing the override does not withdraw the delegation again.
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 0, aye(10, 1)));
+		assert_eq!(tally(0), Tally::from_parts(30, 0, 30));
+
+		// Removing the override gives the delegation back to 2.
+		assert_ok!(Voting::remove_vote(RuntimeOrigin::signed(1), None, 0));
+		assert_eq!(tally(0), Tally::from_parts(25, 0, 25));
+		assert!(OverridesFor::<Test>::get(1, 0).is_empty());
+		assert!(!RetractedDelegationsFor::<Test>::contains_key(2, 0));
+		assert_noop!(
+			Voting::remove_vote(RuntimeOrigin::signed(1), None, 0),
+			Error::<Test>::NotVoter
+		);
+	});
+}
+
+#[test]
+fn override_is_respected_by_later_delegate_votes() {
+	new_test_ext().execute_with(|| {
+		Polls::set(
+			vec![(0, Ongoing(Tally::new(0), 0)), (1, Ongoing(Tally::new(0), 0))]
+				.into_iter()
+				.collect(),
+		);
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(1), 0, 2, Conviction::Locked1x, 5));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 0, nay(10, 1)));
+		assert_eq!(tally(0), Tally::from_parts(0, 10, 0));
+
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 0, aye(20, 1)));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 1, aye(20, 1)));
+		assert_eq!(tally(0), Tally::from_parts(20, 10, 20));
+		assert_eq!(tally(1), Tally::from_parts(25, 0, 25));
+
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 0, nay(20, 1)));
+		assert_eq!(tally(0), Tally::from_parts(0, 30, 0));
+		assert_ok!(Voting::remove_vote(RuntimeOrigin::signed(2), None, 0));
+		assert_eq!(tally(0), Tally::from_parts(0, 10, 0));
+
+		assert_ok!(Voting::remove_vote(RuntimeOrigin::signed(1), None, 0));
+		assert_eq!(tally(0), Tally::from_parts(0, 0, 0));
+		assert!(!RetractedDelegationsFor::<Test>::contains_key(2, 0));
+	});
+}
+
+#[test]
+fn undelegating_turns_overrides_into_votes() {
+	new_test_ext().execute_with(|| {
+		Polls::set(
+			vec![(0, Ongoing(Tally::new(0), 0)), (1, Ongoing(Tally::new(0), 0))]
+				.into_iter()
+				.collect(),
+		);
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(1), 0, 2, Conviction::Locked1x, 5));
+		// Delegations to a delegating account are not counted.
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(3), 0, 1, Conviction::Locked1x, 10));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 0, aye(20, 1)));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 1, aye(20, 1)));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 0, nay(10, 1)));
+		assert_eq!(tally(0), Tally::from_parts(20, 10, 20));
+		assert_eq!(tally(1), Tally::from_parts(25, 0, 25));
+
+		assert_ok!(Voting::undelegate(RuntimeOrigin::signed(1), 0));
+		assert_eq!(tally(0), Tally::from_parts(20, 20, 20));
+		assert_eq!(tally(1), Tally::from_parts(20, 0, 20));
+		assert!(OverridesFor::<Test>::get(1, 0).is_empty());
+		assert!(!RetractedDelegationsFor::<Test>::contains_key(2, 0));
+		assert_matches!(
+			VotingFor::<Test>::get(1, 0),
+			Voting::Casting(Casting { votes, .. }) if votes.into_inner() == vec![(0, nay(10, 1))]
+		);
+
+		assert_ok!(Voting::remove_vote(RuntimeOrigin::signed(1), None, 0));
+		assert_eq!(tally(0), Tally::from_parts(20, 0, 20));
+	});
+}
+
+#[test]
+fn override_balance_gets_locked() {
+	new_test_ext().execute_with(|| {
+		Polls::set(vec![(0, Ongoing(Tally::new(0), 0))].into_iter().collect());
+		assert_ok!(Voting::delegate(RuntimeOrigin::signed(1), 0, 2, Conviction::Locked1x, 5));
+		assert_eq!(Balances::usable_balance(1), 5);
+
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 0, aye(10, 1)));
+		assert_eq!(Balances::usable_balance(1), 0);
+		// The override is still in place, so nothing can be unlocked.
+		assert_ok!(Voting::unlock(RuntimeOrigin::signed(1), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 0);
+
+		Polls::set(vec![(0, Completed(1, true))].into_iter().collect());
+		assert_noop!(
+			Voting::remove_other_vote(RuntimeOrigin::signed(2), 1, 0, 0),
+			Error::<Test>::NoPermissionYet
+		);
+		assert_ok!(Voting::remove_vote(RuntimeOrigin::signed(1), Some(0), 0));
+		assert_ok!(Voting::unlock(RuntimeOrigin::signed(1), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 0);
+
+		run_to(4);
+		assert_ok!(Voting::unlock(RuntimeOrigin::signed(1), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 5);
+	});
+}
+
+#[test]
+fn delegate_multiple_works() {
+	new_test_ext().execute_with(|| {
+		Polls::set(
+			vec![
+				(0, Ongoing(Tally::new(0), 0)),
+				(1, Ongoing(Tally::new(0), 1)),
+				(2, Ongoing(Tally::new(0), 2)),
+			]
+			.into_iter()
+			.collect(),
+		);
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 0, aye(10, 0)));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 1, aye(10, 0)));
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(2), 2, aye(10, 0)));
+
+		assert_noop!(
+			Voting::delegate_multiple(
+				RuntimeOrigin::signed(1),
+				vec![].try_into().unwrap(),
+				2,
+				Conviction::Locked1x,
+				5
+			),
+			Error::<Test>::NoClasses
+		);
+		assert_noop!(
+			Voting::delegate_multiple(
+				RuntimeOrigin::signed(1),
+				vec![2, 2].try_into().unwrap(),
+				2,
+				Conviction::Locked1x,
+				5
+			),
+			Error::<Test>::AlreadyDelegating
+		);
+
+		assert_ok!(Voting::delegate_multiple(
+			RuntimeOrigin::signed(1),
+			vec![0, 1].try_into().unwrap(),
+			2,
+			Conviction::Locked1x,
+			5
+		));
+		assert_eq!(tally(0), Tally::from_parts(6, 0, 15));
+		assert_eq!(tally(1), Tally::from_parts(6, 0, 15));
+		assert_eq!(tally(2), Tally::from_parts(1, 0, 10));
+		assert_matches!(VotingFor::<Test>::get(1, 0), Voting::Delegating(_));
+		assert_matches!(VotingFor::<Test>::get(1, 1), Voting::Delegating(_));
+		assert_matches!(VotingFor::<Test>::get(1, 2), Voting::Casting(_));
+		assert_eq!(Balances::usable_balance(1), 5);
+	});
+}
+
 #[test]
 fn redelegation_after_vote_ending_should_keep_lock() {
 	new_test_ext().execute_with(|| {
@@ -771,9 +952,11 @@ fn errors_with_vote_work() {
 
 		assert_ok!(Voting::delegate(RuntimeOrigin::signed(1), 0, 2, Conviction::None, 10));
 		assert_noop!(
-			Voting::vote(RuntimeOrigin::signed(1), 3, aye(10, 0)),
-			Error::<Test>::AlreadyDelegating
+			Voting::vote(RuntimeOrigin::signed(1), 3, aye(11, 0)),
+			Error::<Test>::InsufficientFunds
 		);
+		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), 3, aye(10, 0)));
+		assert_ok!(Voting::remove_vote(RuntimeOrigin::signed(1), Some(0), 3));
 
 		assert_ok!(Voting::undelegate(RuntimeOrigin::signed(1), 0));
 		Polls::set(
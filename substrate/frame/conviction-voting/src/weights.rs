@@ -53,8 +53,10 @@ use core::marker::PhantomData;
 pub trait WeightInfo {
 	fn vote_new() -> Weight;
 	fn vote_existing() -> Weight;
+	fn vote_override() -> Weight;
 	fn remove_vote() -> Weight;
 	fn remove_other_vote() -> Weight;
+	fn remove_override() -> Weight;
 	fn delegate(r: u32, ) -> Weight;
 	fn undelegate(r: u32, ) -> Weight;
 	fn unlock() -> Weight;
@@ -109,6 +111,11 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(7_u64))
 			.saturating_add(T::DbWeight::get().writes(7_u64))
 	}
+	/// Not benchmarked yet. Overriding a delegation votes on the poll and withdraws the delegated
+	/// votes from its tally, which is bounded by the weights of replacing and removing a vote.
+	fn vote_override() -> Weight {
+		Self::vote_existing().saturating_add(Self::remove_vote())
+	}
 	/// Storage: `ConvictionVoting::VotingFor` (r:1 w:1)
 	/// Proof: `ConvictionVoting::VotingFor` (`max_values`: None, `max_size`: Some(27241), added: 29716, mode: `MaxEncodedLen`)
 	/// Storage: `Referenda::ReferendumInfoFor` (r:1 w:1)
@@ -139,6 +146,11 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Not benchmarked yet. Removing an override removes the vote from the tally of the poll and
+	/// adds the delegated votes back, which is bounded by the weight of removing a vote twice.
+	fn remove_override() -> Weight {
+		Self::remove_vote().saturating_mul(2)
+	}
 	/// Storage: `ConvictionVoting::VotingFor` (r:2 w:2)
 	/// Proof: `ConvictionVoting::VotingFor` (`max_values`: None, `max_size`: Some(27241), added: 29716, mode: `MaxEncodedLen`)
 	/// Storage: `Referenda::ReferendumInfoFor` (r:1 w:1)
@@ -258,6 +270,11 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(7_u64))
 			.saturating_add(RocksDbWeight::get().writes(7_u64))
 	}
+	/// Not benchmarked yet. Overriding a delegation votes on the poll and withdraws the delegated
+	/// votes from its tally, which is bounded by the weights of replacing and removing a vote.
+	fn vote_override() -> Weight {
+		Self::vote_existing().saturating_add(Self::remove_vote())
+	}
 	/// Storage: `ConvictionVoting::VotingFor` (r:1 w:1)
 	/// Proof: `ConvictionVoting::VotingFor` (`max_values`: None, `max_size`: Some(27241), added: 29716, mode: `MaxEncodedLen`)
 	/// Storage: `Referenda::ReferendumInfoFor` (r:1 w:1)
@@ -288,6 +305,11 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Not benchmarked yet. Removing an override removes the vote from the tally of the poll and
+	/// adds the delegated votes back, which is bounded by the weight of removing a vote twice.
+	fn remove_override() -> Weight {
+		Self::remove_vote().saturating_mul(2)
+	}
 	/// Storage: `ConvictionVoting::VotingFor` (r:2 w:2)
 	/// Proof: `ConvictionVoting::VotingFor` (`max_values`: None, `max_size`: Some(27241), added: 29716, mode: `MaxEncodedLen`)
 	/// Storage: `Referenda::ReferendumInfoFor` (r:1 w:1)
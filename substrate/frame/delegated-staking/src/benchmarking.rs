@@ -0,0 +1,182 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the delegated staking pallet.
+//!
+//! The pallet has no dispatchable calls, so these measure the operations it exposes to other
+//! pallets through [`sp_staking::DelegationInterface`] and [`sp_staking::DelegationMigrator`].
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as DelegatedStaking;
+
+use frame_benchmarking::v2::*;
+
+const SEED: u32 = 0;
+
+/// An amount comfortably above the minimum bond of [`Config::CoreStaking`].
+fn delegation_amount<T: Config>() -> BalanceOf<T> {
+	T::CoreStaking::minimum_nominator_bond().max(T::Currency::minimum_balance()) * 10u32.into()
+}
+
+/// Give `who` twice the delegation amount.
+fn fund<T: Config>(who: &T::AccountId) {
+	let _ = T::Currency::set_balance(who, delegation_amount::<T>() * 2u32.into());
+}
+
+/// Register an agent with a single delegator that has delegated [`delegation_amount`].
+fn setup_agent<T: Config>() -> Result<(T::AccountId, T::AccountId), BenchmarkError> {
+	let agent: T::AccountId = account("agent", 0, SEED);
+	let reward: T::AccountId = account("reward", 0, SEED);
+	let delegator: T::AccountId = account("delegator", 0, SEED);
+
+	DelegatedStaking::<T>::register_agent(&agent, &reward)?;
+	fund::<T>(&delegator);
+	DelegatedStaking::<T>::delegate_to_agent(&delegator, &agent, delegation_amount::<T>())?;
+
+	Ok((agent, delegator))
+}
+
+/// Make `who` a direct staker of [`Config::CoreStaking`] and migrate it to an agent.
+fn setup_migrated_agent<T: Config>() -> Result<T::AccountId, BenchmarkError> {
+	let agent: T::AccountId = account("agent", 0, SEED);
+	let reward: T::AccountId = account("reward", 0, SEED);
+
+	fund::<T>(&agent);
+	T::CoreStaking::bond(&agent, delegation_amount::<T>(), &reward)?;
+	DelegatedStaking::<T>::migrate_to_agent(&agent, &reward)?;
+
+	Ok(agent)
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn register_agent() -> Result<(), BenchmarkError> {
+		let agent: T::AccountId = account("agent", 0, SEED);
+		let reward: T::AccountId = account("reward", 0, SEED);
+
+		#[block]
+		{
+			DelegatedStaking::<T>::register_agent(&agent, &reward)?;
+		}
+
+		assert!(DelegatedStaking::<T>::is_agent(&agent));
+		Ok(())
+	}
+
+	// Worst case: an existing delegator delegates more to an already bonded agent.
+	#[benchmark]
+	fn delegate_to_agent() -> Result<(), BenchmarkError> {
+		let (agent, delegator) = setup_agent::<T>()?;
+		let amount = delegation_amount::<T>() / 2u32.into();
+
+		#[block]
+		{
+			DelegatedStaking::<T>::delegate_to_agent(&delegator, &agent, amount)?;
+		}
+
+		assert_eq!(
+			DelegatedStaking::<T>::held_balance_of(&delegator),
+			delegation_amount::<T>() + amount
+		);
+		Ok(())
+	}
+
+	// Worst case: the released funds have to be withdrawn from `CoreStaking` first.
+	#[benchmark]
+	fn release_delegation() -> Result<(), BenchmarkError> {
+		let (agent, delegator) = setup_agent::<T>()?;
+		let amount = delegation_amount::<T>();
+
+		T::CoreStaking::unbond(&agent, amount)?;
+		T::CoreStaking::set_current_era(
+			T::CoreStaking::current_era() + T::CoreStaking::bonding_duration(),
+		);
+
+		#[block]
+		{
+			DelegatedStaking::<T>::release_delegation(&agent, &delegator, amount, 0)?;
+		}
+
+		assert!(!DelegatedStaking::<T>::is_delegator(&delegator));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn migrate_to_agent() -> Result<(), BenchmarkError> {
+		let agent: T::AccountId = account("agent", 0, SEED);
+		let reward: T::AccountId = account("reward", 0, SEED);
+
+		fund::<T>(&agent);
+		T::CoreStaking::bond(&agent, delegation_amount::<T>(), &reward)?;
+
+		#[block]
+		{
+			DelegatedStaking::<T>::migrate_to_agent(&agent, &reward)?;
+		}
+
+		assert!(DelegatedStaking::<T>::is_agent(&agent));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn claim_delegation() -> Result<(), BenchmarkError> {
+		let agent = setup_migrated_agent::<T>()?;
+		let delegator: T::AccountId = account("delegator", 0, SEED);
+		let amount = delegation_amount::<T>() / 2u32.into();
+
+		#[block]
+		{
+			DelegatedStaking::<T>::claim_delegation(&agent, &delegator, amount)?;
+		}
+
+		assert_eq!(DelegatedStaking::<T>::held_balance_of(&delegator), amount);
+		Ok(())
+	}
+
+	// Worst case: a reporter is rewarded for applying the slash.
+	#[benchmark]
+	fn apply_slash() -> Result<(), BenchmarkError> {
+		let (agent, delegator) = setup_agent::<T>()?;
+		let reporter: T::AccountId = account("reporter", 0, SEED);
+		let amount = delegation_amount::<T>() / 2u32.into();
+
+		Agents::<T>::mutate(&agent, |maybe_ledger| {
+			if let Some(ledger) = maybe_ledger {
+				ledger.pending_slash = amount;
+			}
+		});
+
+		#[block]
+		{
+			DelegatedStaking::<T>::do_slash(&agent, &delegator, amount, Some(reporter))?;
+		}
+
+		assert_eq!(DelegatedStaking::<T>::held_balance_of(&delegator), amount);
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(
+		DelegatedStaking,
+		crate::mock::ExtBuilder::default().build(),
+		crate::mock::Runtime
+	);
+}
@@ -0,0 +1,159 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementations of public traits, namely [`DelegationInterface`] and [`OnStakingUpdate`].
+
+use super::*;
+use frame_support::storage::with_storage_layer;
+use sp_staking::{DelegationInterface, DelegationMigrator, EraIndex, OnStakingUpdate};
+use sp_std::collections::btree_map::BTreeMap;
+
+impl<T: Config> DelegationInterface for Pallet<T> {
+	type Balance = BalanceOf<T>;
+	type AccountId = T::AccountId;
+
+	/// Effective balance of the `Agent` account.
+	fn agent_balance(who: &Self::AccountId) -> Option<Self::Balance> {
+		Agent::<T>::get(who).map(|agent| agent.ledger.effective_balance()).ok()
+	}
+
+	fn delegator_balance(delegator: &Self::AccountId) -> Option<Self::Balance> {
+		Delegation::<T>::get(delegator).map(|delegation| delegation.amount)
+	}
+
+	/// Delegate funds to an `Agent`.
+	fn delegate(
+		who: &Self::AccountId,
+		agent: &Self::AccountId,
+		reward_account: &Self::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		// register and delegate atomically, so that a failed delegation leaves no agent behind.
+		with_storage_layer(|| {
+			Pallet::<T>::register_agent(agent, reward_account)?;
+
+			// Delegate the funds from who to the `Agent` account.
+			Pallet::<T>::delegate_to_agent(who, agent, amount)
+		})
+	}
+
+	/// Add more delegation to the `Agent` account.
+	fn delegate_extra(
+		who: &Self::AccountId,
+		agent: &Self::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		Pallet::<T>::delegate_to_agent(who, agent, amount)
+	}
+
+	/// Withdraw delegation of `delegator` to `Agent`.
+	///
+	/// If there are funds in `Agent` account that can be withdrawn, then those funds would be
+	/// unlocked/released in the delegator's account.
+	fn withdraw_delegation(
+		delegator: &Self::AccountId,
+		agent: &Self::AccountId,
+		amount: Self::Balance,
+		num_slashing_spans: u32,
+	) -> DispatchResult {
+		Pallet::<T>::release_delegation(agent, delegator, amount, num_slashing_spans)
+	}
+
+	fn pending_slash(agent: &Self::AccountId) -> Option<Self::Balance> {
+		Agent::<T>::get(agent).map(|agent| agent.ledger.pending_slash).ok()
+	}
+
+	fn delegator_slash(
+		agent: &Self::AccountId,
+		delegator: &Self::AccountId,
+		value: Self::Balance,
+		maybe_reporter: Option<Self::AccountId>,
+	) -> DispatchResult {
+		Pallet::<T>::do_slash(agent, delegator, value, maybe_reporter)
+	}
+}
+
+impl<T: Config> DelegationMigrator for Pallet<T> {
+	type Balance = BalanceOf<T>;
+	type AccountId = T::AccountId;
+
+	fn migrate_nominator_to_agent(
+		agent: &Self::AccountId,
+		reward_account: &Self::AccountId,
+	) -> DispatchResult {
+		Pallet::<T>::migrate_to_agent(agent, reward_account)
+	}
+
+	fn migrate_delegation(
+		agent: &Self::AccountId,
+		delegator: &Self::AccountId,
+		value: Self::Balance,
+	) -> DispatchResult {
+		Pallet::<T>::claim_delegation(agent, delegator, value)
+	}
+
+	/// Only used for testing.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn force_kill_agent(agent: &Self::AccountId) {
+		if <Agents<T>>::take(agent).is_none() {
+			return
+		}
+		let _ = frame_system::Pallet::<T>::dec_providers(agent).defensive();
+
+		<Delegators<T>>::iter()
+			.filter(|(_, delegation)| &delegation.agent == agent)
+			.collect::<Vec<_>>()
+			.into_iter()
+			.for_each(|(delegator, _)| {
+				let _ = T::Currency::release_all(
+					&HoldReason::StakingDelegation.into(),
+					&delegator,
+					Precision::BestEffort,
+				);
+				if <Delegators<T>>::take(&delegator).is_some() {
+					let _ = frame_system::Pallet::<T>::dec_providers(&delegator).defensive();
+				}
+			});
+
+		T::CoreStaking::migrate_to_direct_staker(agent);
+	}
+}
+
+impl<T: Config> OnStakingUpdate<T::AccountId, BalanceOf<T>> for Pallet<T> {
+	fn on_slash(
+		who: &T::AccountId,
+		_slashed_active: BalanceOf<T>,
+		_slashed_unlocking: &BTreeMap<EraIndex, BalanceOf<T>>,
+		slashed_total: BalanceOf<T>,
+	) {
+		<Agents<T>>::mutate(who, |maybe_register| match maybe_register {
+			// if existing agent, register the slashed amount as pending slash.
+			Some(register) => register.pending_slash.saturating_accrue(slashed_total),
+			None => {
+				// nothing to do
+			},
+		});
+	}
+
+	fn on_withdraw(stash: &T::AccountId, amount: BalanceOf<T>) {
+		// if there is a withdraw to the agent, then add it to the unclaimed withdrawals.
+		let _ = Agent::<T>::get(stash)
+			// can't do anything if there is an overflow error. Just raise a defensive error.
+			.and_then(|agent| agent.add_unclaimed_withdraw(amount).defensive())
+			.map(|agent| agent.save());
+	}
+}
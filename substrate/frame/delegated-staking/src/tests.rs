@@ -0,0 +1,632 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for pallet-delegated-staking.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok, traits::fungible::InspectHold};
+use frame_system::RawOrigin;
+use pallet_staking::Error as StakingError;
+use sp_staking::{DelegationInterface, DelegationMigrator, StakerStatus};
+
+#[test]
+fn create_an_agent_with_first_delegator() {
+	ExtBuilder::default().build_and_execute(|| {
+		let agent: AccountId = 200;
+		let reward_account: AccountId = 201;
+		let delegator: AccountId = 202;
+
+		// set intention to accept delegation.
+		fund(&agent, 1000);
+		assert_ok!(DelegatedStaking::register_agent(&agent, &reward_account));
+
+		// delegate to agent
+		fund(&delegator, 1000);
+		assert_ok!(DelegatedStaking::delegate_to_agent(&delegator, &agent, 100));
+
+		// verify
+		assert!(DelegatedStaking::is_agent(&agent));
+		assert_eq!(DelegatedStaking::stakeable_balance(&agent), 100);
+		assert_eq!(
+			Balances::balance_on_hold(&HoldReason::StakingDelegation.into(), &delegator),
+			100
+		);
+		assert_eq!(DelegatedStaking::held_balance_of(&delegator), 100);
+		// the agent bonds the delegated funds without holding any of them.
+		assert!(eq_stake(agent, 100, 100));
+		assert_eq!(Balances::free_balance(agent), 1000);
+		assert_eq!(
+			events_since_last_call(),
+			vec![Event::Delegated { agent, delegator, amount: 100 }]
+		);
+	});
+}
+
+#[test]
+fn cannot_become_agent() {
+	ExtBuilder::default().build_and_execute(|| {
+		// cannot set reward account same as agent account
+		assert_noop!(
+			DelegatedStaking::register_agent(&100, &100),
+			Error::<T>::InvalidRewardDestination
+		);
+
+		// an existing validator cannot become agent
+		assert_noop!(
+			DelegatedStaking::register_agent(&GENESIS_VALIDATOR, &100),
+			Error::<T>::AlreadyStaking
+		);
+
+		// an existing direct staker to `CoreStaking` cannot become an agent.
+		assert_noop!(
+			DelegatedStaking::register_agent(&GENESIS_NOMINATOR_ONE, &100),
+			Error::<T>::AlreadyStaking
+		);
+		assert_noop!(
+			DelegatedStaking::register_agent(&GENESIS_NOMINATOR_TWO, &100),
+			Error::<T>::AlreadyStaking
+		);
+
+		// an existing agent cannot register again.
+		assert_ok!(DelegatedStaking::register_agent(&200, &201));
+		assert_noop!(DelegatedStaking::register_agent(&200, &201), Error::<T>::NotAllowed);
+	});
+}
+
+#[test]
+fn delegation_restrictions() {
+	ExtBuilder::default().build_and_execute(|| {
+		let agent_one: AccountId = 200;
+		let agent_two: AccountId = 300;
+		let delegator: AccountId = 202;
+
+		assert_ok!(DelegatedStaking::register_agent(&agent_one, &201));
+		assert_ok!(DelegatedStaking::register_agent(&agent_two, &301));
+
+		fund(&delegator, 200);
+		assert_ok!(DelegatedStaking::delegate_to_agent(&delegator, &agent_one, 100));
+
+		// a delegator cannot delegate to more than one agent.
+		assert_noop!(
+			DelegatedStaking::delegate_to_agent(&delegator, &agent_two, 50),
+			Error::<T>::InvalidDelegation
+		);
+
+		// an agent cannot delegate.
+		fund(&agent_one, 200);
+		assert_noop!(
+			DelegatedStaking::delegate_to_agent(&agent_one, &agent_two, 50),
+			Error::<T>::InvalidDelegation
+		);
+
+		// a direct staker cannot delegate.
+		assert_noop!(
+			DelegatedStaking::delegate_to_agent(&GENESIS_NOMINATOR_ONE, &agent_one, 50),
+			Error::<T>::AlreadyStaking
+		);
+
+		// cannot delegate to an account that is not an agent.
+		fund(&203, 200);
+		assert_noop!(DelegatedStaking::delegate_to_agent(&203, &204, 50), Error::<T>::NotAgent);
+
+		// cannot delegate more than the free balance.
+		assert!(DelegatedStaking::delegate_to_agent(&delegator, &agent_one, 150).is_err());
+
+		// a delegator can add more delegation to the same agent.
+		assert_ok!(DelegatedStaking::delegate_to_agent(&delegator, &agent_one, 50));
+		assert_eq!(DelegatedStaking::held_balance_of(&delegator), 150);
+		assert_eq!(
+			<DelegatedStaking as DelegationInterface>::delegator_balance(&delegator),
+			Some(150)
+		);
+		assert!(eq_stake(agent_one, 150, 150));
+	});
+}
+
+#[test]
+fn agent_restrictions() {
+	// Similar to creating a nomination pool
+	ExtBuilder::default().build_and_execute(|| {
+		let agent_one = 200;
+		let delegator_one = 210;
+		fund(&delegator_one, 200);
+		assert_ok!(<DelegatedStaking as DelegationInterface>::delegate(
+			&delegator_one,
+			&agent_one,
+			&(agent_one + 1),
+			100
+		));
+
+		let agent_two = 300;
+		let delegator_two = 310;
+		fund(&delegator_two, 200);
+		assert_ok!(<DelegatedStaking as DelegationInterface>::delegate(
+			&delegator_two,
+			&agent_two,
+			&(agent_two + 1),
+			100
+		));
+
+		// agent one tries to delegate to agent 2
+		assert_noop!(
+			<DelegatedStaking as DelegationInterface>::delegate_extra(&agent_one, &agent_two, 10),
+			Error::<T>::InvalidDelegation
+		);
+
+		// agent one tries to delegate to a new agent
+		assert_noop!(
+			<DelegatedStaking as DelegationInterface>::delegate(&agent_one, &400, &(400 + 1), 10),
+			Error::<T>::InvalidDelegation
+		);
+
+		// delegator one tries to delegate to agent 2 as well (it already delegates to agent 1)
+		assert_noop!(
+			<DelegatedStaking as DelegationInterface>::delegate_extra(
+				&delegator_one,
+				&agent_two,
+				10
+			),
+			Error::<T>::InvalidDelegation
+		);
+
+		// cannot delegate to non agents.
+		let non_agent = 201;
+		// give it some funds
+		fund(&non_agent, 200);
+		assert_noop!(
+			<DelegatedStaking as DelegationInterface>::delegate_extra(
+				&(non_agent + 1),
+				&non_agent,
+				10
+			),
+			Error::<T>::NotAgent
+		);
+
+		// cannot delegate to a delegator
+		assert_noop!(
+			<DelegatedStaking as DelegationInterface>::delegate_extra(
+				&(non_agent + 1),
+				&delegator_one,
+				10
+			),
+			Error::<T>::NotAgent
+		);
+
+		// delegator cannot become an agent
+		assert_noop!(
+			DelegatedStaking::register_agent(&delegator_one, &(delegator_one + 1)),
+			Error::<T>::NotAllowed
+		);
+	});
+}
+
+#[test]
+fn apply_pending_slash() {
+	ExtBuilder::default().build_and_execute(|| {
+		start_era(1);
+		let agent: AccountId = 200;
+		let reward_acc: AccountId = 201;
+		let delegators: Vec<AccountId> = (301..=350).collect();
+		let reporter: AccountId = 400;
+
+		let total_staked = setup_delegation_stake(agent, reward_acc, delegators.clone(), 10, 10);
+
+		start_era(4);
+		// slash half of the stake
+		pallet_staking::slashing::do_slash::<T>(
+			&agent,
+			total_staked / 2,
+			&mut Default::default(),
+			&mut Default::default(),
+			3,
+		);
+
+		// the slash is posted as pending against the agent.
+		assert_eq!(get_agent(&agent).ledger.pending_slash, total_staked / 2);
+		assert_eq!(
+			<DelegatedStaking as DelegationInterface>::pending_slash(&agent),
+			Some(total_staked / 2)
+		);
+		// the agent balance accounts for the pending slash.
+		assert_eq!(agent_balance(&agent), total_staked / 2);
+
+		fund(&reporter, 100);
+		for delegator in &delegators {
+			let delegation = <Delegators<T>>::get(delegator).unwrap();
+			let slash = delegation.amount / 2;
+			let reporter_balance = Balances::free_balance(reporter);
+
+			assert_ok!(<DelegatedStaking as DelegationInterface>::delegator_slash(
+				&agent,
+				delegator,
+				slash,
+				Some(reporter)
+			));
+
+			// the delegator is slashed.
+			assert_eq!(DelegatedStaking::held_balance_of(delegator), delegation.amount - slash);
+			// the reporter gets a fraction of the slash.
+			assert_eq!(
+				Balances::free_balance(reporter),
+				reporter_balance + SlashRewardFraction::get() * slash
+			);
+		}
+
+		// all slashes are applied.
+		assert_eq!(get_agent(&agent).ledger.pending_slash, 0);
+		assert!(eq_stake(agent, total_staked / 2, total_staked / 2));
+
+		// cannot slash once all pending slashes are applied.
+		assert_noop!(
+			<DelegatedStaking as DelegationInterface>::delegator_slash(
+				&agent,
+				&delegators[0],
+				1,
+				None
+			),
+			Error::<T>::NothingToSlash
+		);
+	});
+}
+
+#[test]
+fn withdraw_delegation() {
+	ExtBuilder::default().build_and_execute(|| {
+		let agent: AccountId = 200;
+		let reward_acc: AccountId = 201;
+
+		start_era(1);
+		assert_ok!(DelegatedStaking::register_agent(&agent, &reward_acc));
+		for (delegator, amount) in [(300, 100), (301, 200), (302, 300)] {
+			fund(&delegator, 1000);
+			assert_ok!(DelegatedStaking::delegate_to_agent(&delegator, &agent, amount));
+		}
+		assert!(eq_stake(agent, 600, 600));
+
+		// unbond in era 1 and 2.
+		assert_ok!(<Staking as StakingInterface>::unbond(&agent, 50));
+		assert!(eq_stake(agent, 600, 550));
+		start_era(2);
+		assert_ok!(<Staking as StakingInterface>::unbond(&agent, 100));
+		assert!(eq_stake(agent, 600, 450));
+
+		// nothing to withdraw yet.
+		assert_noop!(
+			DelegatedStaking::release_delegation(&agent, &300, 50, 0),
+			Error::<T>::NotEnoughFunds
+		);
+
+		// the first unbonding chunk is unlocked in era 4.
+		start_era(4);
+		assert_ok!(DelegatedStaking::release_delegation(&agent, &300, 50, 0));
+		assert!(eq_stake(agent, 550, 450));
+		assert_eq!(DelegatedStaking::held_balance_of(&300), 50);
+		assert_eq!(Balances::free_balance(300), 950);
+
+		// cannot release more than what is unlocked.
+		assert_noop!(
+			DelegatedStaking::release_delegation(&agent, &301, 200, 0),
+			Error::<T>::NotEnoughFunds
+		);
+
+		start_era(5);
+		assert_ok!(DelegatedStaking::release_delegation(&agent, &301, 100, 0));
+		assert!(eq_stake(agent, 450, 450));
+		assert_eq!(DelegatedStaking::held_balance_of(&301), 100);
+
+		// cannot release more than delegated.
+		assert_noop!(
+			DelegatedStaking::release_delegation(&agent, &300, 100, 0),
+			Error::<T>::NotEnoughFunds
+		);
+
+		// cannot release from an agent the delegator has not delegated to.
+		assert_ok!(DelegatedStaking::register_agent(&400, &401));
+		assert_noop!(DelegatedStaking::release_delegation(&400, &300, 10, 0), Error::<T>::NotAgent);
+
+		assert_eq!(
+			events_since_last_call().into_iter().rev().take(2).collect::<Vec<_>>(),
+			vec![
+				Event::Released { agent, delegator: 301, amount: 100 },
+				Event::Released { agent, delegator: 300, amount: 50 },
+			]
+		);
+	});
+}
+
+#[test]
+fn agent_is_removed_once_all_funds_are_released() {
+	ExtBuilder::default().build_and_execute(|| {
+		let agent: AccountId = 200;
+		let total = setup_delegation_stake(agent, 201, vec![300, 301], 100, 100);
+		assert_eq!(total, 300);
+
+		start_era(1);
+		assert_ok!(<Staking as StakingInterface>::unbond(&agent, total));
+
+		start_era(4);
+		// withdrawing kills the stash in staking but the agent waits for the remaining delegator.
+		assert_ok!(DelegatedStaking::release_delegation(&agent, &300, 100, 0));
+		assert!(Staking::status(&agent).is_err());
+		assert_eq!(get_agent(&agent).ledger.unclaimed_withdrawals, 200);
+
+		assert_ok!(DelegatedStaking::release_delegation(&agent, &301, 200, 0));
+		assert!(!DelegatedStaking::is_agent(&agent));
+		assert!(!DelegatedStaking::is_delegator(&300) && !DelegatedStaking::is_delegator(&301));
+		assert!(!frame_system::Pallet::<T>::account_exists(&agent));
+		assert_eq!(Balances::free_balance(300), 101);
+		assert_eq!(Balances::free_balance(301), 201);
+	});
+}
+
+#[test]
+fn migrate_nominator_to_agent() {
+	ExtBuilder::default().build_and_execute(|| {
+		let agent: AccountId = GENESIS_NOMINATOR_TWO;
+		let reward_acc: AccountId = 201;
+		let staked = 200;
+		let free = Balances::free_balance(agent);
+		assert_eq!(Staking::status(&agent), Ok(StakerStatus::Nominator(vec![GENESIS_VALIDATOR])));
+
+		// reward account cannot be the agent itself.
+		assert_noop!(
+			<DelegatedStaking as DelegationMigrator>::migrate_nominator_to_agent(&agent, &agent),
+			Error::<T>::InvalidRewardDestination
+		);
+		// only a direct staker can migrate.
+		assert_noop!(
+			<DelegatedStaking as DelegationMigrator>::migrate_nominator_to_agent(&300, &301),
+			Error::<T>::NotAllowed
+		);
+
+		assert_ok!(<DelegatedStaking as DelegationMigrator>::migrate_nominator_to_agent(
+			&agent,
+			&reward_acc
+		));
+
+		// the stake is untouched but all funds have moved to the proxy delegator.
+		let proxy_delegator = DelegatedStaking::generate_proxy_delegator(&agent);
+		assert_eq!(Staking::status(&agent), Ok(StakerStatus::Nominator(vec![GENESIS_VALIDATOR])));
+		assert!(eq_stake(agent, staked, staked));
+		assert_eq!(Balances::total_balance(&agent), 0);
+		assert_eq!(DelegatedStaking::held_balance_of(&proxy_delegator), free);
+		assert_eq!(agent_balance(&agent), free);
+		// funds that were not staked can be released right away.
+		assert_eq!(get_agent(&agent).ledger.unclaimed_withdrawals, free - staked);
+
+		// the agent cannot be migrated again.
+		assert_noop!(
+			<DelegatedStaking as DelegationMigrator>::migrate_nominator_to_agent(
+				&agent,
+				&reward_acc
+			),
+			Error::<T>::NotAllowed
+		);
+
+		// delegators can now claim their share from the proxy delegator.
+		let delegator: AccountId = 300;
+		assert_ok!(<DelegatedStaking as DelegationMigrator>::migrate_delegation(
+			&agent, &delegator, 1000
+		));
+		assert_eq!(DelegatedStaking::held_balance_of(&delegator), 1000);
+		assert_eq!(DelegatedStaking::held_balance_of(&proxy_delegator), free - 1000);
+		assert_eq!(
+			events_since_last_call().last(),
+			Some(&Event::MigratedDelegation { agent, delegator, amount: 1000 })
+		);
+
+		// a delegator can only claim once.
+		assert_noop!(
+			<DelegatedStaking as DelegationMigrator>::migrate_delegation(&agent, &delegator, 100),
+			Error::<T>::NotAllowed
+		);
+		// cannot claim more than what is left in the proxy delegator.
+		assert_noop!(
+			<DelegatedStaking as DelegationMigrator>::migrate_delegation(&agent, &301, free),
+			Error::<T>::NotEnoughFunds
+		);
+
+		// claimed funds that are not staked can be released.
+		assert_ok!(DelegatedStaking::release_delegation(&agent, &delegator, 500, 0));
+		assert_eq!(Balances::free_balance(delegator), 500);
+		assert_eq!(DelegatedStaking::held_balance_of(&delegator), 500);
+
+		// the rest of the funds are claimed by another delegator.
+		assert_ok!(<DelegatedStaking as DelegationMigrator>::migrate_delegation(
+			&agent,
+			&301,
+			free - 1000
+		));
+		assert!(!DelegatedStaking::is_delegator(&proxy_delegator));
+		assert_eq!(agent_balance(&agent), free - 500);
+		assert!(eq_stake(agent, staked, staked));
+	});
+}
+
+#[test]
+fn virtual_staker_cannot_compound_rewards() {
+	ExtBuilder::default().build_and_execute(|| {
+		let agent: AccountId = 200;
+		setup_delegation_stake(agent, 201, vec![300], 100, 0);
+
+		// the reward account of a virtual staker cannot be set to itself.
+		assert_noop!(
+			<Staking as StakingInterface>::update_payee(&agent, &agent),
+			StakingError::<T>::RewardDestinationRestricted
+		);
+	});
+}
+
+mod pool_integration {
+	use super::*;
+	use pallet_nomination_pools::{BondedPools, PoolState};
+
+	#[test]
+	fn create_pool_and_join_with_delegation() {
+		ExtBuilder::default().build_and_execute(|| {
+			let creator: AccountId = 100;
+			fund(&creator, 500);
+			assert_ok!(Pools::create(
+				RawOrigin::Signed(creator).into(),
+				200,
+				creator,
+				creator,
+				creator
+			));
+
+			let pool_id = 1;
+			let pool_account = Pools::create_bonded_account(pool_id);
+			assert_eq!(BondedPools::<T>::get(pool_id).unwrap().state, PoolState::Open);
+
+			// the pool account is an agent and holds no funds itself.
+			assert!(DelegatedStaking::is_agent(&pool_account));
+			assert_eq!(Balances::total_balance(&pool_account), 0);
+			assert_eq!(DelegatedStaking::held_balance_of(&creator), 200);
+			assert!(eq_stake(pool_account, 200, 200));
+
+			// members join the pool and keep their funds in their own accounts.
+			for delegator in 300..310 {
+				fund(&delegator, 500);
+				assert_ok!(Pools::join(RawOrigin::Signed(delegator).into(), 100, pool_id));
+				assert_eq!(DelegatedStaking::held_balance_of(&delegator), 100);
+				assert_eq!(Balances::total_balance(&delegator), 500);
+			}
+			assert!(eq_stake(pool_account, 1200, 1200));
+			assert_eq!(agent_balance(&pool_account), 1200);
+
+			// a member leaves the pool.
+			start_era(1);
+			assert_ok!(Pools::unbond(RawOrigin::Signed(300).into(), 300, 100));
+			assert!(eq_stake(pool_account, 1200, 1100));
+
+			start_era(4);
+			assert_ok!(Pools::withdraw_unbonded(RawOrigin::Signed(300).into(), 300, 0));
+			assert_eq!(DelegatedStaking::held_balance_of(&300), 0);
+			assert_eq!(Balances::free_balance(300), 500);
+			assert!(!DelegatedStaking::is_delegator(&300));
+			assert!(eq_stake(pool_account, 1100, 1100));
+		});
+	}
+
+	#[test]
+	fn pool_slash_is_applied_lazily_to_members() {
+		ExtBuilder::default().build_and_execute(|| {
+			let creator: AccountId = 100;
+			let reporter: AccountId = 10;
+			fund(&creator, 500);
+			assert_ok!(Pools::create(
+				RawOrigin::Signed(creator).into(),
+				200,
+				creator,
+				creator,
+				creator
+			));
+
+			let pool_id = 1;
+			let pool_account = Pools::create_bonded_account(pool_id);
+			for delegator in 300..309 {
+				fund(&delegator, 500);
+				assert_ok!(Pools::join(RawOrigin::Signed(delegator).into(), 100, pool_id));
+			}
+			assert!(eq_stake(pool_account, 1100, 1100));
+
+			// slash half of the pool stake.
+			start_era(2);
+			pallet_staking::slashing::do_slash::<T>(
+				&pool_account,
+				550,
+				&mut Default::default(),
+				&mut Default::default(),
+				1,
+			);
+
+			// the slash is pending on the pool and its members.
+			assert_eq!(Pools::api_pool_pending_slash(pool_id), 550);
+			assert_eq!(agent_balance(&pool_account), 550);
+			assert_eq!(Pools::api_member_pending_slash(300), 50);
+			assert_eq!(DelegatedStaking::held_balance_of(&300), 100);
+
+			// anyone can apply the pending slash of a member and get rewarded for it.
+			assert_ok!(Pools::apply_slash(RawOrigin::Signed(reporter).into(), 300));
+			assert_eq!(DelegatedStaking::held_balance_of(&300), 50);
+			assert_eq!(Balances::free_balance(reporter), SlashRewardFraction::get() * 50);
+			assert_eq!(Pools::api_member_pending_slash(300), 0);
+			assert_eq!(Pools::api_pool_pending_slash(pool_id), 500);
+
+			// nothing more to slash for the member.
+			assert_noop!(
+				Pools::apply_slash(RawOrigin::Signed(reporter).into(), 300),
+				pallet_nomination_pools::Error::<T>::NothingToSlash
+			);
+		});
+	}
+
+	#[test]
+	fn pool_members_vote_with_pooled_funds() {
+		use frame_support::traits::Polling;
+		use pallet_conviction_voting::{AccountVote, Conviction, Tally, Vote};
+
+		ExtBuilder::default().build_and_execute(|| {
+			let creator: AccountId = 100;
+			let member: AccountId = 300;
+			fund(&creator, 500);
+			assert_ok!(Pools::create(
+				RawOrigin::Signed(creator).into(),
+				200,
+				creator,
+				creator,
+				creator
+			));
+
+			let pool_id = 1;
+			fund(&member, 500);
+			assert_ok!(Pools::join(RawOrigin::Signed(member).into(), 100, pool_id));
+			assert_eq!(DelegatedStaking::held_balance_of(&member), 100);
+			assert_eq!(Balances::usable_balance(member), 400);
+
+			// the pooled funds stay in the member account and count towards its vote.
+			let vote = AccountVote::Standard {
+				vote: Vote { aye: true, conviction: Conviction::None },
+				balance: 500,
+			};
+			assert_ok!(ConvictionVoting::vote(RawOrigin::Signed(member).into(), 0, vote));
+			assert_eq!(
+				<TestPolls as Polling<_>>::as_ongoing(0).unwrap().0,
+				Tally::from_parts(50, 0, 500)
+			);
+
+			// the vote lock overlaps the pool hold.
+			assert_eq!(Balances::usable_balance(member), 0);
+			assert_eq!(DelegatedStaking::held_balance_of(&member), 100);
+
+			// leaving the pool releases the hold but the funds remain locked by the vote.
+			start_era(1);
+			assert_ok!(Pools::unbond(RawOrigin::Signed(member).into(), member, 100));
+			start_era(4);
+			assert_ok!(Pools::withdraw_unbonded(RawOrigin::Signed(member).into(), member, 0));
+			assert_eq!(DelegatedStaking::held_balance_of(&member), 0);
+			assert_eq!(Balances::free_balance(member), 500);
+			assert_eq!(Balances::usable_balance(member), 0);
+
+			// removing the vote unlocks the funds.
+			assert_ok!(ConvictionVoting::remove_vote(RawOrigin::Signed(member).into(), None, 0));
+			assert_ok!(ConvictionVoting::unlock(RawOrigin::Signed(member).into(), 0, member));
+			assert_eq!(Balances::usable_balance(member), 500);
+		});
+	}
+}
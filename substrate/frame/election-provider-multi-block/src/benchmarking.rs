@@ -0,0 +1,402 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Multi-block election pallet benchmarking.
+
+use super::*;
+use crate::Pallet as MultiBlock;
+use frame_benchmarking::{account, v2::*, BenchmarkError};
+use frame_support::{
+	assert_ok,
+	traits::{
+		fungible::{Inspect, Mutate},
+		EnsureOrigin, Hooks,
+	},
+};
+use frame_system::RawOrigin;
+use sp_npos_elections::Support;
+
+const SEED: u32 = 999;
+
+/// Fill the data provider with enough targets and voters to fill every page of the snapshot.
+fn set_up_data_provider<T: Config>() {
+	T::DataProvider::clear();
+
+	let targets = (0..T::TargetSnapshotPerBlock::get())
+		.map(|i| account::<T::AccountId>("Target", i, SEED))
+		.collect::<Vec<_>>();
+	targets.iter().for_each(|target| T::DataProvider::add_target(target.clone()));
+
+	let votes_per_voter = (<SolutionOf<T::MinerConfig> as NposSolution>::LIMIT as u32)
+		.min(<T::DataProvider as ElectionDataProvider>::MaxVotesPerVoter::get())
+		.min(targets.len() as u32) as usize;
+	let voters = T::VoterSnapshotPerBlock::get().saturating_mul(T::Pages::get());
+	(0..voters).for_each(|i| {
+		let voter = account::<T::AccountId>("Voter", i, SEED);
+		let weight = T::Currency::minimum_balance().saturated_into::<u64>() * 1000;
+		let votes = targets
+			.iter()
+			.cycle()
+			.skip(i as usize)
+			.take(votes_per_voter)
+			.cloned()
+			.collect::<Vec<_>>();
+		T::DataProvider::add_voter(voter, weight, votes.try_into().unwrap());
+	});
+}
+
+/// Create the target snapshot and all pages of the voter snapshot.
+fn create_snapshot<T: Config>() {
+	set_up_data_provider::<T>();
+	assert_ok!(MultiBlock::<T>::create_targets_snapshot());
+	for page in (MultiBlock::<T>::lsp()..=MultiBlock::<T>::msp()).rev() {
+		assert_ok!(MultiBlock::<T>::create_voters_snapshot_paged(page));
+	}
+}
+
+/// An account with enough funds to register and submit a full signed solution.
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who = account::<T::AccountId>(name, index, SEED);
+	let deposit = T::SignedDepositBase::get()
+		.saturating_add(T::SignedDepositPerPage::get().saturating_mul(T::Pages::get().into()));
+	let _ = T::Currency::set_balance(
+		&who,
+		T::Currency::minimum_balance().saturating_add(deposit.saturating_mul(10u32.into())),
+	);
+	who
+}
+
+/// Register `who` with the score of `paged_solution`, and submit its first `pages` pages.
+fn submit_signed<T: Config>(who: &T::AccountId, paged_solution: &PagedSolutionOf<T>, pages: u32) {
+	assert_ok!(MultiBlock::<T>::register(
+		RawOrigin::Signed(who.clone()).into(),
+		paged_solution.score
+	));
+	for (page, solution) in paged_solution.solution_pages.iter().enumerate().take(pages as usize) {
+		assert_ok!(MultiBlock::<T>::submit_page(
+			RawOrigin::Signed(who.clone()).into(),
+			page as PageIndex,
+			Some(Box::new(solution.clone()))
+		));
+	}
+}
+
+/// Create the snapshot, and submit a full signed solution that is about to be verified.
+fn set_up_signed_validation<T: Config>() {
+	create_snapshot::<T>();
+	MultiBlock::<T>::phase_transition(Phase::Signed);
+	let paged_solution = MultiBlock::<T>::mine_solution().unwrap();
+	submit_signed::<T>(&funded_account::<T>("submitter", 0), &paged_solution, T::Pages::get());
+
+	let now = frame_system::Pallet::<T>::block_number();
+	MultiBlock::<T>::phase_transition(Phase::SignedValidation(now));
+}
+
+/// Create the snapshot, queue an unsigned solution and move to the export phase.
+fn set_up_export<T: Config>() {
+	create_snapshot::<T>();
+	let paged_solution = MultiBlock::<T>::mine_solution().unwrap();
+	assert_ok!(MultiBlock::<T>::verify_synchronous(paged_solution, ElectionCompute::Unsigned));
+	assert_ok!(MultiBlock::<T>::prepare_export());
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn on_initialize_nothing() {
+		assert!(MultiBlock::<T>::current_phase().is_off());
+
+		#[block]
+		{
+			MultiBlock::<T>::on_initialize(1u32.into());
+		}
+
+		assert!(MultiBlock::<T>::current_phase().is_off());
+	}
+
+	#[benchmark]
+	fn on_initialize_into_snapshot_msp() {
+		set_up_data_provider::<T>();
+		let msp = MultiBlock::<T>::msp();
+
+		#[block]
+		{
+			assert_ok!(MultiBlock::<T>::create_targets_snapshot());
+			assert_ok!(MultiBlock::<T>::create_voters_snapshot_paged(msp));
+			MultiBlock::<T>::phase_transition(Phase::Snapshot(msp));
+		}
+
+		assert!(MultiBlock::<T>::target_snapshot().is_some());
+		assert!(MultiBlock::<T>::paged_voter_snapshot(msp).is_some());
+	}
+
+	#[benchmark]
+	fn on_initialize_into_snapshot_rest() {
+		set_up_data_provider::<T>();
+		let msp = MultiBlock::<T>::msp();
+		assert_ok!(MultiBlock::<T>::create_targets_snapshot());
+		assert_ok!(MultiBlock::<T>::create_voters_snapshot_paged(msp));
+		MultiBlock::<T>::phase_transition(Phase::Snapshot(msp));
+		let page = msp.saturating_sub(1);
+
+		#[block]
+		{
+			assert_ok!(MultiBlock::<T>::create_voters_snapshot_paged(page));
+			MultiBlock::<T>::phase_transition(Phase::Snapshot(page));
+		}
+
+		assert!(MultiBlock::<T>::paged_voter_snapshot(page).is_some());
+	}
+
+	#[benchmark]
+	fn on_initialize_into_signed() {
+		create_snapshot::<T>();
+		MultiBlock::<T>::phase_transition(Phase::Snapshot(MultiBlock::<T>::lsp()));
+
+		#[block]
+		{
+			MultiBlock::<T>::phase_transition(Phase::Signed);
+		}
+
+		assert!(MultiBlock::<T>::current_phase().is_signed());
+	}
+
+	#[benchmark]
+	fn on_initialize_into_signed_validation() {
+		create_snapshot::<T>();
+		MultiBlock::<T>::phase_transition(Phase::Signed);
+		let now = frame_system::Pallet::<T>::block_number();
+
+		#[block]
+		{
+			MultiBlock::<T>::phase_transition(Phase::SignedValidation(now));
+		}
+
+		assert!(MultiBlock::<T>::current_phase().is_signed_validation());
+	}
+
+	#[benchmark]
+	fn on_initialize_into_unsigned() {
+		set_up_signed_validation::<T>();
+		// everything but the last page is verified, which leaves the most data to clear.
+		for _ in 1..T::Pages::get() {
+			MultiBlock::<T>::do_verify_signed_page();
+		}
+		let now = frame_system::Pallet::<T>::block_number();
+
+		#[block]
+		{
+			MultiBlock::<T>::abort_verification();
+			MultiBlock::<T>::phase_transition(Phase::Unsigned(now));
+		}
+
+		assert!(MultiBlock::<T>::current_phase().is_unsigned());
+		assert_eq!(VerificationStatus::<T>::get(), Status::Nothing);
+	}
+
+	#[benchmark]
+	fn verify_page() {
+		set_up_signed_validation::<T>();
+		let msp = MultiBlock::<T>::msp();
+
+		#[block]
+		{
+			MultiBlock::<T>::do_verify_signed_page();
+		}
+
+		// with a single page, the solution is already finalized.
+		assert!(
+			VerifyingSupports::<T>::contains_key(msp) ||
+				MultiBlock::<T>::queued_solution().is_some()
+		);
+	}
+
+	#[benchmark]
+	fn finalize_verification() {
+		set_up_signed_validation::<T>();
+		for _ in 1..T::Pages::get() {
+			MultiBlock::<T>::do_verify_signed_page();
+		}
+
+		#[block]
+		{
+			MultiBlock::<T>::do_verify_signed_page();
+		}
+
+		assert!(MultiBlock::<T>::queued_solution().is_some());
+	}
+
+	#[benchmark]
+	fn register() {
+		MultiBlock::<T>::phase_transition(Phase::Signed);
+		let round = MultiBlock::<T>::round();
+
+		// the queue is full, thus the weakest submission is ejected.
+		for i in 0..T::SignedMaxSubmissions::get() {
+			let who = funded_account::<T>("submitter", i);
+			let claimed_score =
+				ElectionScore { minimal_stake: 10_000_000u128 + i as u128, ..Default::default() };
+			assert_ok!(MultiBlock::<T>::register(RawOrigin::Signed(who).into(), claimed_score));
+		}
+		let caller = funded_account::<T>("caller", 0);
+		let claimed_score =
+			ElectionScore { minimal_stake: 10_000_000u128 + 1, ..Default::default() };
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), claimed_score);
+
+		assert!(SubmissionMetadataStorage::<T>::contains_key(round, caller));
+	}
+
+	#[benchmark]
+	fn submit_page() {
+		create_snapshot::<T>();
+		MultiBlock::<T>::phase_transition(Phase::Signed);
+		let round = MultiBlock::<T>::round();
+		let paged_solution = MultiBlock::<T>::mine_solution().unwrap();
+		let caller = funded_account::<T>("caller", 0);
+		submit_signed::<T>(&caller, &paged_solution, 0);
+
+		let msp = MultiBlock::<T>::msp();
+		let solution = paged_solution.solution_pages[msp as usize].clone();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), msp, Some(Box::new(solution)));
+
+		assert!(SubmissionStorage::<T>::contains_key((round, caller, msp)));
+	}
+
+	#[benchmark]
+	fn bail() {
+		create_snapshot::<T>();
+		MultiBlock::<T>::phase_transition(Phase::Signed);
+		let round = MultiBlock::<T>::round();
+		let paged_solution = MultiBlock::<T>::mine_solution().unwrap();
+		let caller = funded_account::<T>("caller", 0);
+		submit_signed::<T>(&caller, &paged_solution, T::Pages::get());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert!(!SubmissionMetadataStorage::<T>::contains_key(round, caller));
+	}
+
+	#[benchmark]
+	fn clear_old_round_data(p: Linear<1, { T::Pages::get() }>) {
+		create_snapshot::<T>();
+		MultiBlock::<T>::phase_transition(Phase::Signed);
+		let round = MultiBlock::<T>::round();
+		let paged_solution = MultiBlock::<T>::mine_solution().unwrap();
+		let caller = funded_account::<T>("caller", 0);
+		submit_signed::<T>(&caller, &paged_solution, p);
+		Round::<T>::mutate(|r| *r += 1);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), round);
+
+		assert!(!SubmissionMetadataStorage::<T>::contains_key(round, caller));
+	}
+
+	#[benchmark]
+	fn submit_unsigned() {
+		create_snapshot::<T>();
+		let paged_solution = MultiBlock::<T>::mine_solution().unwrap();
+		let now = frame_system::Pallet::<T>::block_number();
+		MultiBlock::<T>::phase_transition(Phase::Unsigned(now));
+		assert!(MultiBlock::<T>::queued_solution().is_none());
+
+		#[extrinsic_call]
+		_(RawOrigin::None, Box::new(paged_solution));
+
+		assert!(MultiBlock::<T>::queued_solution().is_some());
+	}
+
+	#[benchmark]
+	fn set_minimum_untrusted_score() -> Result<(), BenchmarkError> {
+		let origin =
+			T::ManagerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Some(ElectionScore::default()));
+
+		assert!(MultiBlock::<T>::minimum_untrusted_score().is_some());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_emergency_solution() -> Result<(), BenchmarkError> {
+		let origin =
+			T::ManagerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		MultiBlock::<T>::phase_transition(Phase::Emergency);
+		let supports: Supports<T::AccountId> = (0..T::MaxWinners::get())
+			.map(|i| {
+				let voter = account::<T::AccountId>("Voter", i, SEED);
+				(
+					account::<T::AccountId>("Target", i, SEED),
+					Support { total: 1_000, voters: vec![(voter, 1_000)] },
+				)
+			})
+			.collect();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, supports);
+
+		assert!(matches!(
+			MultiBlock::<T>::queued_solution(),
+			Some(SolutionInfo { compute: ElectionCompute::Emergency, .. })
+		));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn export_non_terminal() {
+		set_up_export::<T>();
+		let msp = MultiBlock::<T>::msp();
+
+		#[block]
+		{
+			assert_ok!(MultiBlock::<T>::elect(msp));
+		}
+
+		assert!(!QueuedSolutionSupports::<T>::contains_key(msp));
+	}
+
+	#[benchmark]
+	fn export_terminal() {
+		set_up_export::<T>();
+		let round = MultiBlock::<T>::round();
+		let lsp = MultiBlock::<T>::lsp();
+		for page in (lsp + 1..=MultiBlock::<T>::msp()).rev() {
+			assert_ok!(MultiBlock::<T>::elect(page));
+		}
+
+		#[block]
+		{
+			assert_ok!(MultiBlock::<T>::elect(lsp));
+		}
+
+		assert_eq!(MultiBlock::<T>::round(), round + 1);
+	}
+
+	impl_benchmark_test_suite!(
+		MultiBlock,
+		crate::mock::ExtBuilder::default().build_offchainify().0,
+		crate::mock::Runtime,
+	);
+}
@@ -0,0 +1,1391 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Multi-block, paged election provider pallet.
+//!
+//! An election provider that spreads every expensive step of an election over multiple blocks, so
+//! that the number of voters that can take part in an election is no longer bound by what fits in
+//! a single block.
+//!
+//! All the data of this pallet is split into [`Config::Pages`] pages:
+//!
+//! - The voter snapshot is fetched from the [`Config::DataProvider`] one page per block, starting
+//!   at the most significant page ([`ElectionProviderBase::msp`]) and ending at the least
+//!   significant one ([`ElectionProviderBase::lsp`]). The target snapshot is a single page and is
+//!   fetched alongside the first voter page.
+//! - A solution ([`PagedRawSolution`]) is made of one solution page per voter snapshot page. Each
+//!   solution page indexes voters of its own snapshot page and targets of the shared target
+//!   snapshot.
+//! - The result is exported one page at a time through [`ElectionProvider::elect`], and the
+//!   consumer is expected to request pages from the most significant to the least significant one.
+//!
+//! ## Phases
+//!
+//! The timeline of an election is as follows:
+//!
+//! ```ignore
+//!                                                                         elect(msp) .. elect(lsp)
+//! +---------+------------+---------+--------------------+----------+--------+
+//! |   Off   | Snapshot   | Signed  | SignedValidation   | Unsigned | Export |      Off
+//! +---------+------------+---------+--------------------+----------+--------+
+//!           ^ Pages blocks before the signed phase.
+//! ```
+//!
+//! Each phase boundary is computed backwards from
+//! [`ElectionDataProvider::next_election_prediction`], see [`Phase`].
+//!
+//! ### Signed Phase
+//!
+//! Signed submissions are done in two steps. First, a submitter [`Pallet::register`]s the score
+//! that they claim for their solution, which places a [`Config::SignedDepositBase`] on hold. The
+//! best [`Config::SignedMaxSubmissions`] claims are kept sorted; registering a better claim into a
+//! full queue ejects the weakest one, which is refunded in full. Then, the submitter uploads their
+//! solution one page at a time via [`Pallet::submit_page`], each page holding an additional
+//! [`Config::SignedDepositPerPage`]. A submitter can [`Pallet::bail`] during the signed phase, in
+//! which case only [`Config::BailoutGraceRatio`] of their deposit is returned.
+//!
+//! ### Signed Validation Phase
+//!
+//! The leader of the queue, namely the best claimed score, is verified one page per block, from
+//! the most significant to the least significant page. Once the last page is verified, the winner
+//! count and the claimed score are checked against the accumulated supports. If all checks pass,
+//! the solution is queued and the submitter is rewarded with [`Config::SignedRewardBase`] on top
+//! of their deposit. If any check fails, the deposit is slashed and the next leader is verified,
+//! if there is enough time left.
+//!
+//! Submissions that were never verified can be reclaimed with [`Pallet::clear_old_round_data`]
+//! once their round is over.
+//!
+//! ### Unsigned Phase
+//!
+//! Validators' offchain workers mine a full paged solution from the snapshot at the beginning of
+//! the unsigned phase and submit it via [`Pallet::submit_unsigned`]. Unsigned solutions are
+//! verified in full, all pages at once, and are queued if better than the currently queued one.
+//!
+//! ### Export and Fallback
+//!
+//! The first call to [`ElectionProvider::elect`] moves the pallet to [`Phase::Export`]. If no
+//! solution is queued at that point, [`Config::Fallback`] is used, and its result is exported in
+//! the least significant page while all other pages are empty. If the fallback fails too, the
+//! pallet enters [`Phase::Emergency`] and waits for [`Config::ManagerOrigin`] to provide a result
+//! via [`Pallet::set_emergency_solution`]. Exporting the least significant page concludes the
+//! election and starts a new round.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_election_provider_support::{
+	bounds::{CountBound, DataProviderBounds},
+	BoundedSupportsOf, ElectionDataProvider, ElectionProvider, ElectionProviderBase,
+	InstantElectionProvider, NposSolution, NposSolver, PageIndex,
+};
+use frame_support::{
+	dispatch::DispatchClass,
+	ensure,
+	traits::{fungible, Get},
+	weights::Weight,
+};
+use frame_system::{ensure_none, offchain::SendTransactionTypes, pallet_prelude::BlockNumberFor};
+use scale_info::TypeInfo;
+use sp_arithmetic::{
+	traits::{CheckedAdd, Saturating, Zero},
+	UpperOf,
+};
+use sp_npos_elections::{ElectionScore, EvaluateSupport, ExtendedBalance, Supports};
+use sp_runtime::{
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
+		TransactionValidityError, ValidTransaction,
+	},
+	DispatchError, ModuleError, PerThing, Perbill, RuntimeDebug, SaturatedConversion,
+};
+use sp_std::prelude::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[macro_use]
+pub mod helpers;
+
+const LOG_TARGET: &str = "runtime::multiblock-election";
+
+pub mod signed;
+pub mod unsigned;
+pub mod verifier;
+pub mod weights;
+
+pub use signed::SubmissionMetadata;
+pub use unsigned::{Miner, MinerConfig, MinerError};
+pub use verifier::{PartialBackings, Status};
+pub use weights::WeightInfo;
+
+/// The solution type used by this crate.
+pub type SolutionOf<T> = <T as MinerConfig>::Solution;
+
+/// The voter index. Derived from [`SolutionOf`].
+pub type SolutionVoterIndexOf<T> = <SolutionOf<T> as NposSolution>::VoterIndex;
+/// The target index. Derived from [`SolutionOf`].
+pub type SolutionTargetIndexOf<T> = <SolutionOf<T> as NposSolution>::TargetIndex;
+/// The accuracy of the election, when submitted from offchain. Derived from [`SolutionOf`].
+pub type SolutionAccuracyOf<T> = <SolutionOf<T> as NposSolution>::Accuracy;
+/// The paged solution type of a runtime.
+pub type PagedSolutionOf<T> = PagedRawSolution<SolutionOf<<T as Config>::MinerConfig>>;
+/// The fallback election type.
+pub type FallbackErrorOf<T> = <<T as crate::Config>::Fallback as ElectionProviderBase>::Error;
+/// A voter's fundamental data: their ID, their stake, and the list of candidates for whom they
+/// voted.
+pub type VoterOf<T> = frame_election_provider_support::VoterOf<<T as Config>::DataProvider>;
+/// The balance type of the deposits and rewards of this pallet.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as fungible::Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Current phase of the pallet.
+///
+/// The phases are entered in the order in which they are declared, and each phase boundary is
+/// computed backwards from the next election block:
+///
+/// - [`Phase::Snapshot`] starts `Pages` blocks before the signed phase.
+/// - [`Phase::Signed`] lasts for [`Config::SignedPhase`] blocks.
+/// - [`Phase::SignedValidation`] lasts for [`Config::SignedValidationPhase`] blocks.
+/// - [`Phase::Unsigned`] lasts for [`Config::UnsignedPhase`] blocks, until the first page is
+///   requested.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum Phase<Bn> {
+	/// Nothing is happening, and nothing will happen.
+	Off,
+	/// The snapshot is being created. The inner value is the last page that was fetched.
+	Snapshot(PageIndex),
+	/// Signed solutions are being registered and submitted.
+	Signed,
+	/// Signed solutions are being verified, one page per block. The inner value is the block at
+	/// which this phase started.
+	SignedValidation(Bn),
+	/// Unsigned solutions can be submitted. The inner value is the block at which this phase
+	/// started.
+	Unsigned(Bn),
+	/// The election result is being exported, no more solutions are accepted.
+	Export,
+	/// The election has failed, and we are waiting for an emergency solution.
+	Emergency,
+}
+
+impl<Bn> Default for Phase<Bn> {
+	fn default() -> Self {
+		Phase::Off
+	}
+}
+
+impl<Bn: PartialEq + Eq> Phase<Bn> {
+	/// Whether the phase is off or not.
+	pub fn is_off(&self) -> bool {
+		matches!(self, Phase::Off)
+	}
+
+	/// Whether the snapshot is being created.
+	pub fn is_snapshot(&self) -> bool {
+		matches!(self, Phase::Snapshot(_))
+	}
+
+	/// Whether the phase is signed or not.
+	pub fn is_signed(&self) -> bool {
+		matches!(self, Phase::Signed)
+	}
+
+	/// Whether the phase is signed validation or not.
+	pub fn is_signed_validation(&self) -> bool {
+		matches!(self, Phase::SignedValidation(_))
+	}
+
+	/// Whether the phase is unsigned or not.
+	pub fn is_unsigned(&self) -> bool {
+		matches!(self, Phase::Unsigned(_))
+	}
+
+	/// Whether the phase is unsigned and opened at the given block.
+	pub fn is_unsigned_open_at(&self, at: Bn) -> bool {
+		matches!(self, Phase::Unsigned(real) if *real == at)
+	}
+
+	/// Whether the result is being exported or not.
+	pub fn is_export(&self) -> bool {
+		matches!(self, Phase::Export)
+	}
+
+	/// Whether the phase is emergency or not.
+	pub fn is_emergency(&self) -> bool {
+		matches!(self, Phase::Emergency)
+	}
+}
+
+/// The type of `Computation` that provided the queued election result.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, MaxEncodedLen, Debug, TypeInfo)]
+pub enum ElectionCompute {
+	/// Election was computed with a signed submission.
+	Signed,
+	/// Election was computed with an unsigned submission.
+	Unsigned,
+	/// Election was computed using the fallback.
+	Fallback,
+	/// Election was computed with emergency status.
+	Emergency,
+}
+
+/// A paged raw solution, as submitted by miners.
+///
+/// `solution_pages` must contain exactly `Pages` entries. The solution at index `i` is the one of
+/// page `i`, thus the first item corresponds to the least significant page.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, Default)]
+pub struct PagedRawSolution<S> {
+	/// The solution pages.
+	pub solution_pages: Vec<S>,
+	/// The claimed score of the whole solution.
+	pub score: ElectionScore,
+	/// The round at which this solution should be submitted.
+	pub round: u32,
+}
+
+/// Information about the queued solution.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct SolutionInfo {
+	/// How the solution was computed.
+	pub compute: ElectionCompute,
+	/// The score of the solution.
+	pub score: ElectionScore,
+}
+
+/// Internal errors of the pallet.
+///
+/// Note that this is different from [`pallet::Error`].
+#[derive(frame_support::DebugNoBound)]
+pub enum ElectionError<T: Config> {
+	/// An error happened in the data provider.
+	DataProvider(&'static str),
+	/// An error nested in the fallback.
+	Fallback(FallbackErrorOf<T>),
+	/// The requested page does not exist.
+	InvalidPage,
+}
+
+// NOTE: we have to do this manually because of the additional where clause needed on
+// `FallbackErrorOf<T>`.
+#[cfg(test)]
+impl<T: Config> PartialEq for ElectionError<T>
+where
+	FallbackErrorOf<T>: PartialEq,
+{
+	fn eq(&self, other: &Self) -> bool {
+		use ElectionError::*;
+		match (self, other) {
+			(DataProvider(x), DataProvider(y)) if x == y => true,
+			(Fallback(x), Fallback(y)) if x == y => true,
+			(InvalidPage, InvalidPage) => true,
+			_ => false,
+		}
+	}
+}
+
+/// Errors that can happen in the feasibility check of a solution.
+#[derive(Debug, Eq, PartialEq)]
+pub enum FeasibilityError {
+	/// Wrong number of winners presented.
+	WrongWinnerCount,
+	/// The snapshot is not available.
+	///
+	/// Kinda defensive: The pallet should technically never attempt to do a feasibility check
+	/// when no snapshot is present.
+	SnapshotUnavailable,
+	/// Internal error from the election crate.
+	NposElection(sp_npos_elections::Error),
+	/// A vote is invalid.
+	InvalidVote,
+	/// A voter is invalid.
+	InvalidVoter,
+	/// The given score was invalid.
+	InvalidScore,
+	/// The provided round is incorrect.
+	InvalidRound,
+	/// The solution does not have the expected number of pages.
+	WrongPageCount,
+	/// Comparison against `MinimumUntrustedScore` failed.
+	UntrustedScoreTooLow,
+	/// The solution is not better than the queued one.
+	ScoreNotImproved,
+	/// Data Provider returned too many desired targets
+	TooManyDesiredTargets,
+	/// Conversion into bounded types failed.
+	///
+	/// Should never happen under correct configurations.
+	BoundedConversionFailed,
+}
+
+impl From<sp_npos_elections::Error> for FeasibilityError {
+	fn from(e: sp_npos_elections::Error) -> Self {
+		FeasibilityError::NposElection(e)
+	}
+}
+
+pub use pallet::*;
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>
+			+ TryInto<Event<Self>>;
+
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+
+		/// The currency used for deposits and rewards of signed submissions.
+		type Currency: fungible::Mutate<Self::AccountId>
+			+ fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+		/// The number of pages of the snapshot, the solutions and the exported result.
+		#[pallet::constant]
+		type Pages: Get<PageIndex>;
+
+		/// Duration of the signed phase.
+		#[pallet::constant]
+		type SignedPhase: Get<BlockNumberFor<Self>>;
+
+		/// Duration of the signed validation phase.
+		///
+		/// Verifying a signed solution takes one block per page, thus this must be at least
+		/// [`Config::Pages`].
+		#[pallet::constant]
+		type SignedValidationPhase: Get<BlockNumberFor<Self>>;
+
+		/// Duration of the unsigned phase.
+		#[pallet::constant]
+		type UnsignedPhase: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of voters fetched in each page of the snapshot.
+		#[pallet::constant]
+		type VoterSnapshotPerBlock: Get<u32>;
+
+		/// The maximum number of electable targets to put in the snapshot.
+		#[pallet::constant]
+		type TargetSnapshotPerBlock: Get<u32>;
+
+		/// The maximum number of winners that can be elected by this election provider.
+		///
+		/// Note: This must always be greater or equal to `T::DataProvider::desired_targets()`.
+		#[pallet::constant]
+		type MaxWinners: Get<u32>;
+
+		/// Maximum number of signed submissions that can be queued in each round.
+		#[pallet::constant]
+		type SignedMaxSubmissions: Get<u32>;
+
+		/// Base deposit held when registering a signed submission.
+		#[pallet::constant]
+		type SignedDepositBase: Get<BalanceOf<Self>>;
+
+		/// Deposit held for each page of a signed submission.
+		#[pallet::constant]
+		type SignedDepositPerPage: Get<BalanceOf<Self>>;
+
+		/// Base reward minted for a signed solution that is verified and queued.
+		#[pallet::constant]
+		type SignedRewardBase: Get<BalanceOf<Self>>;
+
+		/// The ratio of the deposit that is returned to a submitter that bails out.
+		#[pallet::constant]
+		type BailoutGraceRatio: Get<Perbill>;
+
+		/// The priority of the unsigned transaction submitted in the unsigned-phase.
+		#[pallet::constant]
+		type MinerTxPriority: Get<TransactionPriority>;
+
+		/// Configurations of the embedded miner.
+		///
+		/// Any external software implementing this can use the [`unsigned::Miner`] type provided,
+		/// which can mine new solutions and check their feasibility.
+		type MinerConfig: crate::unsigned::MinerConfig<
+			AccountId = Self::AccountId,
+			MaxVotesPerVoter = <Self::DataProvider as ElectionDataProvider>::MaxVotesPerVoter,
+			MaxWinners = Self::MaxWinners,
+		>;
+
+		/// The solver used by the offchain worker to mine unsigned solutions.
+		type Solver: NposSolver<AccountId = Self::AccountId>;
+
+		/// Something that will provide the election data.
+		type DataProvider: ElectionDataProvider<
+			AccountId = Self::AccountId,
+			BlockNumber = BlockNumberFor<Self>,
+		>;
+
+		/// Configuration for the fallback, used if no solution is queued when the election result
+		/// is requested.
+		type Fallback: InstantElectionProvider<
+			AccountId = Self::AccountId,
+			BlockNumber = BlockNumberFor<Self>,
+			DataProvider = Self::DataProvider,
+			MaxWinners = Self::MaxWinners,
+		>;
+
+		/// The origin that can set the minimum untrusted score and the emergency solution.
+		type ManagerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The weight of the pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// A reason for placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Funds held as the deposit of a signed submission.
+		#[codec(index = 0)]
+		SignedSubmission,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let next_election = T::DataProvider::next_election_prediction(now).max(now);
+			let remaining = next_election - now;
+
+			let unsigned_deadline = T::UnsignedPhase::get();
+			let signed_validation_deadline =
+				unsigned_deadline.saturating_add(T::SignedValidationPhase::get());
+			let signed_deadline = signed_validation_deadline.saturating_add(T::SignedPhase::get());
+			let snapshot_deadline = signed_deadline.saturating_add(T::Pages::get().into());
+
+			let current_phase = Self::current_phase();
+			log!(trace, "current phase {:?}, next election {:?}", current_phase, next_election);
+
+			match current_phase {
+				Phase::Off if remaining <= snapshot_deadline => {
+					let msp = Self::msp();
+					match Self::create_targets_snapshot()
+						.and_then(|_| Self::create_voters_snapshot_paged(msp))
+					{
+						Ok(_) => {
+							Self::phase_transition(Phase::Snapshot(msp));
+							T::WeightInfo::on_initialize_into_snapshot_msp()
+						},
+						Err(why) => {
+							// not much we can do about this at this point; we will retry in the
+							// next block.
+							log!(warn, "failed to create the snapshot due to {:?}", why);
+							T::WeightInfo::on_initialize_nothing()
+						},
+					}
+				},
+				Phase::Snapshot(page) if page > Self::lsp() => {
+					let next_page = page.saturating_sub(1);
+					match Self::create_voters_snapshot_paged(next_page) {
+						Ok(_) => {
+							Self::phase_transition(Phase::Snapshot(next_page));
+							T::WeightInfo::on_initialize_into_snapshot_rest()
+						},
+						Err(why) => {
+							log!(
+								warn,
+								"failed to create snapshot page {} due to {:?}",
+								next_page,
+								why
+							);
+							T::WeightInfo::on_initialize_nothing()
+						},
+					}
+				},
+				Phase::Snapshot(_) if remaining <= signed_deadline => {
+					Self::phase_transition(Phase::Signed);
+					T::WeightInfo::on_initialize_into_signed()
+				},
+				Phase::Signed if remaining <= signed_validation_deadline => {
+					Self::phase_transition(Phase::SignedValidation(now));
+					T::WeightInfo::on_initialize_into_signed_validation()
+						.saturating_add(Self::do_verify_signed_page())
+				},
+				Phase::SignedValidation(_) if remaining <= unsigned_deadline => {
+					// whatever is being verified at this point ran out of time.
+					Self::abort_verification();
+					Self::phase_transition(Phase::Unsigned(now));
+					T::WeightInfo::on_initialize_into_unsigned()
+				},
+				Phase::SignedValidation(_) => Self::do_verify_signed_page(),
+				_ => T::WeightInfo::on_initialize_nothing(),
+			}
+		}
+
+		fn offchain_worker(now: BlockNumberFor<T>) {
+			if Self::current_phase().is_unsigned_open_at(now) {
+				match Self::mine_check_and_submit() {
+					Ok(_) => log!(info, "successfully submitted a paged solution."),
+					Err(why) => log!(debug, "offchain worker failed due to {:?}", why),
+				}
+			}
+		}
+
+		fn integrity_test() {
+			use sp_std::mem::size_of;
+			// The index type of both voters and targets need to be smaller than that of usize (very
+			// unlikely to be the case, but anyhow)..
+			assert!(size_of::<SolutionVoterIndexOf<T::MinerConfig>>() <= size_of::<usize>());
+			assert!(size_of::<SolutionTargetIndexOf<T::MinerConfig>>() <= size_of::<usize>());
+
+			assert!(T::Pages::get() > 0, "there must be at least one page");
+
+			// a signed solution must be verifiable within the signed validation phase.
+			let pages: BlockNumberFor<T> = T::Pages::get().into();
+			assert!(
+				T::SignedPhase::get().is_zero() || T::SignedValidationPhase::get() >= pages,
+				"signed validation phase is too short to verify a single solution"
+			);
+
+			// every page of the solution must be able to address a full page of the snapshot.
+			let max_vote: usize = <SolutionOf<T::MinerConfig> as NposSolution>::LIMIT;
+			let maximum_chain_accuracy: Vec<UpperOf<SolutionAccuracyOf<T::MinerConfig>>> = (0..
+				max_vote)
+				.map(|_| {
+					<UpperOf<SolutionAccuracyOf<T::MinerConfig>>>::from(
+						<SolutionAccuracyOf<T::MinerConfig>>::one().deconstruct(),
+					)
+				})
+				.collect();
+			let _: UpperOf<SolutionAccuracyOf<T::MinerConfig>> = maximum_chain_accuracy
+				.iter()
+				.fold(Zero::zero(), |acc, x| acc.checked_add(x).unwrap());
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			Self::do_try_state()
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register a signed solution with the given claimed score.
+		///
+		/// [`Config::SignedDepositBase`] is held from the origin. If the queue of signed
+		/// submissions is full, the weakest one is ejected and refunded. The solution pages are
+		/// then submitted via [`Pallet::submit_page`].
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::register())]
+		pub fn register(origin: OriginFor<T>, claimed_score: ElectionScore) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::current_phase().is_signed(), Error::<T>::PhaseNotSigned);
+			Self::do_register(who, claimed_score)
+		}
+
+		/// Submit, or remove, a single page of a registered signed solution.
+		///
+		/// Each stored page holds an additional [`Config::SignedDepositPerPage`], which is
+		/// released again if the page is removed by passing `None`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::submit_page())]
+		pub fn submit_page(
+			origin: OriginFor<T>,
+			page: PageIndex,
+			maybe_solution: Option<Box<SolutionOf<T::MinerConfig>>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::current_phase().is_signed(), Error::<T>::PhaseNotSigned);
+			ensure!(page < T::Pages::get(), Error::<T>::BadPageIndex);
+			Self::do_submit_page(who, page, maybe_solution.map(|s| *s))
+		}
+
+		/// Retract a registered signed solution.
+		///
+		/// Only [`Config::BailoutGraceRatio`] of the deposit is returned, the rest is slashed.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::bail())]
+		pub fn bail(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::current_phase().is_signed(), Error::<T>::PhaseNotSigned);
+			Self::do_bail(who)
+		}
+
+		/// Submit a paged solution for the unsigned phase.
+		///
+		/// The dispatch origin fo this call must be __none__.
+		///
+		/// This submission is checked on the fly, all pages at once. Moreover, this unsigned
+		/// solution is only validated when submitted to the pool from the **local** node.
+		/// Effectively, this means that only active validators can submit this transaction when
+		/// authoring a block (similar to an inherent).
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::submit_unsigned())]
+		pub fn submit_unsigned(
+			origin: OriginFor<T>,
+			paged_solution: Box<PagedSolutionOf<T>>,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			Self::unsigned_pre_dispatch_checks(&paged_solution)?;
+
+			let score = Self::verify_synchronous(*paged_solution, ElectionCompute::Unsigned)
+				.map_err(|why| {
+					log!(warn, "unsigned solution failed the feasibility check: {:?}", why);
+					Error::<T>::UnsignedSolutionInvalid
+				})?;
+			Self::deposit_event(Event::SolutionStored {
+				compute: ElectionCompute::Unsigned,
+				origin: None,
+				score,
+			});
+			Ok(())
+		}
+
+		/// Set a new value for `MinimumUntrustedScore`.
+		///
+		/// Dispatch origin must be aligned with `T::ManagerOrigin`.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::set_minimum_untrusted_score())]
+		pub fn set_minimum_untrusted_score(
+			origin: OriginFor<T>,
+			maybe_next_score: Option<ElectionScore>,
+		) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			<MinimumUntrustedScore<T>>::set(maybe_next_score);
+			Ok(())
+		}
+
+		/// Set a solution in the queue, to be handed out to the client of this pallet in the next
+		/// call to `ElectionProvider::elect`.
+		///
+		/// The whole result is exported in the least significant page.
+		///
+		/// This can only be set by `T::ManagerOrigin`, and only when the phase is `Emergency`.
+		///
+		/// The solution is not checked for any feasibility and is assumed to be trustworthy, as
+		/// any feasibility check itself can in principle cause the election process to fail (due
+		/// to memory/weight constrains).
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::set_emergency_solution())]
+		pub fn set_emergency_solution(
+			origin: OriginFor<T>,
+			supports: Supports<T::AccountId>,
+		) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			ensure!(Self::current_phase().is_emergency(), Error::<T>::CallNotAllowed);
+
+			let score = supports.evaluate();
+			let supports: BoundedSupportsOf<Self> =
+				supports.try_into().map_err(|_| Error::<T>::TooManyWinners)?;
+
+			let _ = <QueuedSolutionSupports<T>>::clear(u32::MAX, None);
+			<QueuedSolutionSupports<T>>::insert(Self::lsp(), supports);
+			<QueuedSolutionInfo<T>>::put(SolutionInfo {
+				compute: ElectionCompute::Emergency,
+				score,
+			});
+			Self::deposit_event(Event::SolutionStored {
+				compute: ElectionCompute::Emergency,
+				origin: None,
+				score,
+			});
+			Ok(())
+		}
+
+		/// Clear the data of a signed submission of a past round that was never verified, and
+		/// release its deposit.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::clear_old_round_data(T::Pages::get()))]
+		pub fn clear_old_round_data(origin: OriginFor<T>, round: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(round < Self::round(), Error::<T>::RoundNotOver);
+			Self::do_clear_old_round_data(who, round)
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// There was a phase transition in a given round.
+		PhaseTransitioned {
+			from: Phase<BlockNumberFor<T>>,
+			to: Phase<BlockNumberFor<T>>,
+			round: u32,
+		},
+		/// A solution was verified and queued.
+		///
+		/// If the solution is signed, this means that it hasn't yet been processed. If the
+		/// solution is unsigned, this means that it has also been processed.
+		SolutionStored {
+			compute: ElectionCompute,
+			origin: Option<T::AccountId>,
+			score: ElectionScore,
+		},
+		/// A signed solution was registered with the given claimed score.
+		Registered { round: u32, who: T::AccountId, claimed_score: ElectionScore },
+		/// A page of a signed solution was stored or removed.
+		PageStored { round: u32, who: T::AccountId, page: PageIndex },
+		/// A signed submission was ejected from the queue by a better one, and refunded.
+		Ejected { round: u32, who: T::AccountId },
+		/// A signed submitter bailed out of the election.
+		Bailed { round: u32, who: T::AccountId },
+		/// A signed solution failed verification at the given page.
+		VerificationFailed { who: T::AccountId, page: PageIndex },
+		/// An account has been rewarded for their signed submission being finalized.
+		Rewarded { account: T::AccountId, value: BalanceOf<T> },
+		/// An account has been slashed for submitting an invalid signed submission.
+		Slashed { account: T::AccountId, value: BalanceOf<T> },
+		/// The election has been finalized, with the given computation and score.
+		ElectionFinalized { compute: ElectionCompute, score: ElectionScore },
+		/// An election failed.
+		///
+		/// Not much can be said about which computes failed in the process.
+		ElectionFailed,
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The call is only allowed during the signed phase.
+		PhaseNotSigned,
+		/// Submission was too early.
+		PreDispatchEarlySubmission,
+		/// Submission was prepared for a different round.
+		PreDispatchWrongRound,
+		/// Submission does not have the expected number of pages.
+		PreDispatchWrongPageCount,
+		/// Submission was too weak, score-wise.
+		PreDispatchWeakSubmission,
+		/// The unsigned solution failed the feasibility check.
+		UnsignedSolutionInvalid,
+		/// The claimed score is not better than the minimum untrusted score.
+		WeakSubmission,
+		/// The origin has already registered a solution in this round.
+		Duplicate,
+		/// The queue of signed submissions is full, and the claimed score is not better than
+		/// any of the queued ones.
+		QueueFull,
+		/// The origin has not registered a solution in the given round.
+		NotRegistered,
+		/// The page index is out of bounds.
+		BadPageIndex,
+		/// The call is not allowed at this point.
+		CallNotAllowed,
+		/// The submitted result has too many winners.
+		TooManyWinners,
+		/// The given round is not over yet.
+		RoundNotOver,
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+		fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			if let Call::submit_unsigned { paged_solution, .. } = call {
+				// Discard solution not coming from the local OCW.
+				match source {
+					TransactionSource::Local | TransactionSource::InBlock => { /* allowed */ },
+					_ => return InvalidTransaction::Call.into(),
+				}
+
+				let _ = Self::unsigned_pre_dispatch_checks(paged_solution)
+					.map_err(|err| {
+						log!(debug, "unsigned transaction validation failed due to {:?}", err);
+						err
+					})
+					.map_err(dispatch_error_to_invalid)?;
+
+				ValidTransaction::with_tag_prefix("OffchainPagedElection")
+					// The higher the score.minimal_stake, the better a solution is.
+					.priority(
+						T::MinerTxPriority::get()
+							.saturating_add(paged_solution.score.minimal_stake.saturated_into()),
+					)
+					// Used to deduplicate unsigned solutions: each validator should produce one
+					// solution per round at most, and solutions are not propagate.
+					.and_provides(paged_solution.round)
+					// Transaction should stay in the pool for the duration of the unsigned phase.
+					.longevity(T::UnsignedPhase::get().saturated_into::<u64>())
+					// We don't propagate this. This can never be validated at a remote node.
+					.propagate(false)
+					.build()
+			} else {
+				InvalidTransaction::Call.into()
+			}
+		}
+
+		fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+			if let Call::submit_unsigned { paged_solution, .. } = call {
+				Self::unsigned_pre_dispatch_checks(paged_solution)
+					.map_err(dispatch_error_to_invalid)
+					.map_err(Into::into)
+			} else {
+				Err(InvalidTransaction::Call.into())
+			}
+		}
+	}
+
+	/// Internal counter for the number of rounds.
+	///
+	/// This is useful for de-duplication of transactions submitted to the pool, and general
+	/// diagnostics of the pallet.
+	///
+	/// This is merely incremented once per every time that an upstream `elect` is called with the
+	/// least significant page.
+	#[pallet::storage]
+	#[pallet::getter(fn round)]
+	pub type Round<T: Config> = StorageValue<_, u32, ValueQuery, DefaultForRound>;
+
+	/// Current phase.
+	#[pallet::storage]
+	#[pallet::getter(fn current_phase)]
+	pub type CurrentPhase<T: Config> = StorageValue<_, Phase<BlockNumberFor<T>>, ValueQuery>;
+
+	/// The pages of the voter snapshot, keyed by page index.
+	///
+	/// Each page is created in a separate block, from the most significant to the least
+	/// significant page. Always cleared at the end of the round.
+	#[pallet::storage]
+	pub type PagedVoterSnapshot<T: Config> =
+		StorageMap<_, Twox64Concat, PageIndex, Vec<VoterOf<T>>>;
+
+	/// The target snapshot, shared by all pages.
+	///
+	/// Always created alongside the most significant voter page and cleared at the end of the
+	/// round.
+	#[pallet::storage]
+	pub type TargetSnapshot<T: Config> = StorageValue<_, Vec<T::AccountId>>;
+
+	/// Desired number of targets to elect for this round.
+	///
+	/// Only exists when the snapshot exists.
+	#[pallet::storage]
+	#[pallet::getter(fn desired_targets)]
+	pub type DesiredTargets<T> = StorageValue<_, u32>;
+
+	/// The minimum score that each 'untrusted' solution must attain in order to be considered
+	/// feasible.
+	///
+	/// Can be set via `set_minimum_untrusted_score`.
+	#[pallet::storage]
+	#[pallet::getter(fn minimum_untrusted_score)]
+	pub type MinimumUntrustedScore<T: Config> = StorageValue<_, ElectionScore>;
+
+	/// The claimed scores of the signed submissions of each round, sorted from the weakest to the
+	/// strongest.
+	///
+	/// The last item is the leader, namely the next submission to be verified.
+	#[pallet::storage]
+	pub type SortedScores<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		BoundedVec<(T::AccountId, ElectionScore), T::SignedMaxSubmissions>,
+		ValueQuery,
+	>;
+
+	/// The metadata of each signed submission, keyed by round and submitter.
+	#[pallet::storage]
+	pub type SubmissionMetadataStorage<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		u32,
+		Twox64Concat,
+		T::AccountId,
+		SubmissionMetadata<BalanceOf<T>>,
+	>;
+
+	/// The solution pages of each signed submission, keyed by round, submitter and page.
+	#[pallet::storage]
+	pub type SubmissionStorage<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, u32>,
+			NMapKey<Twox64Concat, T::AccountId>,
+			NMapKey<Twox64Concat, PageIndex>,
+		),
+		SolutionOf<T::MinerConfig>,
+	>;
+
+	/// The status of the verification of the leading signed submission.
+	#[pallet::storage]
+	pub type VerificationStatus<T: Config> = StorageValue<_, Status, ValueQuery>;
+
+	/// The supports of the pages of the signed submission being verified, keyed by page.
+	#[pallet::storage]
+	pub type VerifyingSupports<T: Config> =
+		StorageMap<_, Twox64Concat, PageIndex, BoundedSupportsOf<Pallet<T>>>;
+
+	/// The backings accumulated so far by each winner of the signed submission being verified.
+	#[pallet::storage]
+	pub type VerifyingBackings<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, PartialBackings, ValueQuery>;
+
+	/// The supports of the pages of the queued solution, keyed by page.
+	///
+	/// Pages are removed as they are exported.
+	#[pallet::storage]
+	pub type QueuedSolutionSupports<T: Config> =
+		StorageMap<_, Twox64Concat, PageIndex, BoundedSupportsOf<Pallet<T>>>;
+
+	/// Information about the queued solution, if any.
+	#[pallet::storage]
+	#[pallet::getter(fn queued_solution)]
+	pub type QueuedSolutionInfo<T: Config> = StorageValue<_, SolutionInfo>;
+
+	#[pallet::type_value]
+	pub fn DefaultForRound() -> u32 {
+		1
+	}
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+}
+
+impl<T: Config> Pallet<T> {
+	/// Move to the given phase, depositing an event.
+	pub(crate) fn phase_transition(to: Phase<BlockNumberFor<T>>) {
+		log!(info, "Starting phase {:?}, round {}.", to, Self::round());
+		Self::deposit_event(Event::PhaseTransitioned {
+			from: <CurrentPhase<T>>::get(),
+			to,
+			round: Self::round(),
+		});
+		<CurrentPhase<T>>::put(to);
+	}
+
+	/// The number of voters in each page of the snapshot and the number of targets, for
+	/// diagnostics.
+	pub fn snapshot_metadata() -> (Vec<(PageIndex, u32)>, u32) {
+		let voters = <PagedVoterSnapshot<T>>::iter()
+			.map(|(page, voters)| (page, voters.len() as u32))
+			.collect::<Vec<_>>();
+		let targets = <TargetSnapshot<T>>::decode_len().unwrap_or_default() as u32;
+		(voters, targets)
+	}
+
+	/// Get the given page of the voter snapshot, if it exists.
+	pub fn paged_voter_snapshot(page: PageIndex) -> Option<Vec<VoterOf<T>>> {
+		<PagedVoterSnapshot<T>>::get(page)
+	}
+
+	/// Get the target snapshot, if it exists.
+	pub fn target_snapshot() -> Option<Vec<T::AccountId>> {
+		<TargetSnapshot<T>>::get()
+	}
+
+	/// Fetch the targets and the desired targets from the data provider, and store them.
+	///
+	/// Returns the number of targets that were stored.
+	pub(crate) fn create_targets_snapshot() -> Result<u32, ElectionError<T>> {
+		let bounds = DataProviderBounds {
+			count: Some(CountBound(T::TargetSnapshotPerBlock::get())),
+			size: None,
+		};
+		// targets always fit in a single page, which is fetched alongside the most significant
+		// voter page.
+		let targets = T::DataProvider::electable_targets(bounds, Self::msp())
+			.map_err(ElectionError::DataProvider)?;
+
+		let mut desired_targets = <Pallet<T> as ElectionProviderBase>::desired_targets_checked()
+			.map_err(ElectionError::DataProvider)?;
+
+		// If `desired_targets` > `targets.len()`, cap `desired_targets` to that level and emit a
+		// warning
+		let max_desired_targets: u32 = targets.len() as u32;
+		if desired_targets > max_desired_targets {
+			log!(
+				warn,
+				"desired_targets: {} > targets.len(): {}, capping desired_targets",
+				desired_targets,
+				max_desired_targets
+			);
+			desired_targets = max_desired_targets;
+		}
+
+		<TargetSnapshot<T>>::put(targets);
+		<DesiredTargets<T>>::put(desired_targets);
+		Ok(max_desired_targets)
+	}
+
+	/// Fetch the given page of voters from the data provider, and store it.
+	///
+	/// Returns the number of voters that were stored.
+	pub(crate) fn create_voters_snapshot_paged(page: PageIndex) -> Result<u32, ElectionError<T>> {
+		let bounds = DataProviderBounds {
+			count: Some(CountBound(T::VoterSnapshotPerBlock::get())),
+			size: None,
+		};
+		let voters =
+			T::DataProvider::electing_voters(bounds, page).map_err(ElectionError::DataProvider)?;
+		let count = voters.len() as u32;
+		log!(debug, "created voter snapshot page {} with {} voters", page, count);
+		<PagedVoterSnapshot<T>>::insert(page, voters);
+		Ok(count)
+	}
+
+	/// Kill everything created for the current round, and start the next one.
+	///
+	/// The signed submissions of the round are left in place, to be reclaimed by their
+	/// submitters via [`Pallet::clear_old_round_data`].
+	pub(crate) fn rotate_round() {
+		let round = Self::round();
+		<Round<T>>::mutate(|r| *r += 1);
+		Self::phase_transition(Phase::Off);
+
+		let _ = <PagedVoterSnapshot<T>>::clear(u32::MAX, None);
+		<TargetSnapshot<T>>::kill();
+		<DesiredTargets<T>>::kill();
+
+		let _ = <QueuedSolutionSupports<T>>::clear(u32::MAX, None);
+		<QueuedSolutionInfo<T>>::kill();
+
+		Self::abort_verification();
+		<SortedScores<T>>::remove(round);
+	}
+
+	/// Make sure that a result is queued, and move to the export phase.
+	///
+	/// If no solution is queued, the fallback is used. If that fails too, the pallet enters the
+	/// emergency phase.
+	fn prepare_export() -> Result<(), ElectionError<T>> {
+		if !<QueuedSolutionInfo<T>>::exists() {
+			// default data provider bounds are unbounded. calling `instant_elect` with
+			// unbounded data provider bounds means that the on-chain `T:Bounds` configs will
+			// *not* be overwritten.
+			match T::Fallback::instant_elect(
+				DataProviderBounds::default(),
+				DataProviderBounds::default(),
+			) {
+				Ok(supports) => {
+					let _ = <QueuedSolutionSupports<T>>::clear(u32::MAX, None);
+					<QueuedSolutionSupports<T>>::insert(Self::lsp(), supports);
+					<QueuedSolutionInfo<T>>::put(SolutionInfo {
+						compute: ElectionCompute::Fallback,
+						score: Default::default(),
+					});
+				},
+				Err(why) => {
+					log!(error, "fallback election failed due to {:?}", why);
+					Self::phase_transition(Phase::Emergency);
+					Self::deposit_event(Event::ElectionFailed);
+					return Err(ElectionError::Fallback(why))
+				},
+			}
+		}
+
+		// nothing that is still being verified can make it at this point.
+		Self::abort_verification();
+		Self::phase_transition(Phase::Export);
+		Ok(())
+	}
+
+	/// Register some amount of weight directly with the system pallet.
+	///
+	/// This is always mandatory weight.
+	fn register_weight(weight: Weight) {
+		<frame_system::Pallet<T>>::register_extra_weight_unchecked(
+			weight,
+			DispatchClass::Mandatory,
+		);
+	}
+}
+
+#[cfg(feature = "try-runtime")]
+impl<T: Config> Pallet<T> {
+	fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+		Self::try_state_snapshot()?;
+		Self::try_state_signed_submissions()?;
+		Self::try_state_queued_solution()
+	}
+
+	// [`PagedVoterSnapshot`] and [`TargetSnapshot`] state checks. Invariants:
+	// - the target snapshot and the desired targets exist together.
+	// - voter pages only exist if the target snapshot exists.
+	fn try_state_snapshot() -> Result<(), sp_runtime::TryRuntimeError> {
+		if <TargetSnapshot<T>>::exists() != <DesiredTargets<T>>::exists() {
+			return Err("If the target snapshot exists, the desired targets must too.".into())
+		}
+		if <PagedVoterSnapshot<T>>::iter_keys().next().is_some() && !<TargetSnapshot<T>>::exists() {
+			return Err("Voter snapshot pages exist without a target snapshot.".into())
+		}
+		Ok(())
+	}
+
+	// [`SortedScores`] and [`SubmissionMetadataStorage`] state checks. Invariants:
+	// - the scores of the current round are sorted.
+	// - each of them has metadata.
+	fn try_state_signed_submissions() -> Result<(), sp_runtime::TryRuntimeError> {
+		let round = Self::round();
+		let sorted = <SortedScores<T>>::get(round);
+		if sorted.windows(2).any(|w| w[0].1 > w[1].1) {
+			return Err("Signed submission scores are not sorted.".into())
+		}
+		if sorted
+			.iter()
+			.any(|(who, _)| !<SubmissionMetadataStorage<T>>::contains_key(round, who))
+		{
+			return Err("A sorted signed submission has no metadata.".into())
+		}
+		Ok(())
+	}
+
+	// [`QueuedSolutionSupports`] state checks. Invariants:
+	// - queued pages only exist if a queued solution exists.
+	fn try_state_queued_solution() -> Result<(), sp_runtime::TryRuntimeError> {
+		if <QueuedSolutionSupports<T>>::iter_keys().next().is_some() &&
+			!<QueuedSolutionInfo<T>>::exists()
+		{
+			return Err("Queued pages exist without a queued solution.".into())
+		}
+		Ok(())
+	}
+}
+
+impl<T: Config> ElectionProviderBase for Pallet<T> {
+	type AccountId = T::AccountId;
+	type BlockNumber = BlockNumberFor<T>;
+	type Error = ElectionError<T>;
+	type MaxWinners = T::MaxWinners;
+	type DataProvider = T::DataProvider;
+	type Pages = T::Pages;
+}
+
+impl<T: Config> ElectionProvider for Pallet<T> {
+	fn ongoing() -> bool {
+		!Self::current_phase().is_off()
+	}
+
+	fn ready() -> bool {
+		let phase = Self::current_phase();
+		!phase.is_off() && !phase.is_snapshot()
+	}
+
+	fn elect(page: PageIndex) -> Result<BoundedSupportsOf<Self>, Self::Error> {
+		ensure!(page < T::Pages::get(), ElectionError::InvalidPage);
+
+		if !Self::current_phase().is_export() {
+			Self::prepare_export()?;
+		}
+
+		let supports = <QueuedSolutionSupports<T>>::take(page).unwrap_or_default();
+		log!(debug, "exporting page {} with {} winners", page, supports.len());
+
+		if page == Self::lsp() {
+			if let Some(SolutionInfo { compute, score }) = <QueuedSolutionInfo<T>>::get() {
+				log!(info, "Finalized election round with compute {:?}.", compute);
+				Self::deposit_event(Event::ElectionFinalized { compute, score });
+			}
+			Self::rotate_round();
+			Self::register_weight(T::WeightInfo::export_terminal());
+		} else {
+			Self::register_weight(T::WeightInfo::export_non_terminal());
+		}
+
+		Ok(supports)
+	}
+}
+
+/// convert a DispatchError to a custom InvalidTransaction with the inner code being the error
+/// number.
+pub fn dispatch_error_to_invalid(error: DispatchError) -> InvalidTransaction {
+	let error_number = match error {
+		DispatchError::Module(ModuleError { error, .. }) => error[0],
+		_ => 0,
+	};
+	InvalidTransaction::Custom(error_number)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::*;
+	use frame_support::{assert_noop, assert_ok};
+	use sp_npos_elections::Support;
+
+	fn transition(from: Phase<BlockNumber>, to: Phase<BlockNumber>) -> Event<Runtime> {
+		Event::PhaseTransitioned { from, to, round: 1 }
+	}
+
+	#[test]
+	fn phase_rotation_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			// 0 ---------- 14 15 16 17 ---------- 22 ---------- 25 ---------- 30
+			//              snapshot   signed      sig-valid     unsigned      elect
+			assert_eq!(MultiBlock::current_phase(), Phase::Off);
+			assert_eq!(MultiBlock::round(), 1);
+
+			roll_to(13);
+			assert_eq!(MultiBlock::current_phase(), Phase::Off);
+			assert!(MultiBlock::target_snapshot().is_none());
+			assert!(!MultiBlock::ready());
+
+			// the target snapshot is created alongside the most significant voter page.
+			roll_to(14);
+			assert_eq!(MultiBlock::current_phase(), Phase::Snapshot(2));
+			assert_eq!(MultiBlock::target_snapshot(), Some(Targets::get()));
+			assert_eq!(MultiBlock::desired_targets(), Some(2));
+			assert_eq!(MultiBlock::paged_voter_snapshot(2), Some(Voters::get()[..4].to_vec()));
+			assert!(MultiBlock::paged_voter_snapshot(1).is_none());
+			assert!(!MultiBlock::ready());
+
+			// and the other pages follow, one per block.
+			roll_to(16);
+			assert_eq!(MultiBlock::current_phase(), Phase::Snapshot(0));
+			assert_eq!(MultiBlock::paged_voter_snapshot(1), Some(Voters::get()[4..8].to_vec()));
+			assert_eq!(MultiBlock::paged_voter_snapshot(0), Some(Voters::get()[8..].to_vec()));
+
+			roll_to(17);
+			assert_eq!(MultiBlock::current_phase(), Phase::Signed);
+			assert!(MultiBlock::ready());
+
+			roll_to(22);
+			assert_eq!(MultiBlock::current_phase(), Phase::SignedValidation(22));
+
+			roll_to(25);
+			assert_eq!(MultiBlock::current_phase(), Phase::Unsigned(25));
+
+			// nothing happens until the result is requested.
+			roll_to(30);
+			assert_eq!(MultiBlock::current_phase(), Phase::Unsigned(25));
+			assert_eq!(
+				multi_block_events(),
+				vec![
+					transition(Phase::Off, Phase::Snapshot(2)),
+					transition(Phase::Snapshot(2), Phase::Snapshot(1)),
+					transition(Phase::Snapshot(1), Phase::Snapshot(0)),
+					transition(Phase::Snapshot(0), Phase::Signed),
+					transition(Phase::Signed, Phase::SignedValidation(22)),
+					transition(Phase::SignedValidation(22), Phase::Unsigned(25)),
+				]
+			);
+		})
+	}
+
+	#[test]
+	fn full_round_exports_all_pages() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to_signed();
+			let solution = mine_full_solution();
+			submit_signed(99, &solution);
+			roll_to_unsigned();
+			assert_eq!(
+				MultiBlock::queued_solution(),
+				Some(SolutionInfo { compute: ElectionCompute::Signed, score: solution.score })
+			);
+			let queued = (0..Pages::get())
+				.map(|page| QueuedSolutionSupports::<Runtime>::get(page).unwrap_or_default())
+				.collect::<Vec<_>>();
+
+			assert_eq!(MultiBlock::elect(Pages::get()), Err(ElectionError::InvalidPage));
+
+			// the first page moves to the export phase.
+			assert_eq!(MultiBlock::elect(2), Ok(queued[2].clone()));
+			assert_eq!(MultiBlock::current_phase(), Phase::Export);
+			assert_eq!(MultiBlock::elect(1), Ok(queued[1].clone()));
+			assert_eq!(MultiBlock::round(), 1);
+
+			// the least significant page concludes the round.
+			assert_eq!(MultiBlock::elect(0), Ok(queued[0].clone()));
+			assert_eq!(MultiBlock::round(), 2);
+			assert_eq!(MultiBlock::current_phase(), Phase::Off);
+			assert!(multi_block_events().contains(&Event::ElectionFinalized {
+				compute: ElectionCompute::Signed,
+				score: solution.score
+			}));
+
+			// all pages together elect the desired targets.
+			let winners = queued
+				.iter()
+				.flat_map(|supports| supports.iter().map(|(who, _)| *who))
+				.collect::<sp_std::collections::btree_set::BTreeSet<_>>();
+			assert_eq!(winners.len(), 2);
+
+			// and everything of the round is cleared.
+			assert!(MultiBlock::queued_solution().is_none());
+			assert!(MultiBlock::target_snapshot().is_none());
+			assert!(MultiBlock::desired_targets().is_none());
+			assert_eq!(MultiBlock::snapshot_metadata(), (vec![], 0));
+			assert_eq!(QueuedSolutionSupports::<Runtime>::iter().count(), 0);
+		})
+	}
+
+	#[test]
+	fn fallback_is_used_if_no_solution_is_queued() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to_unsigned();
+			assert!(MultiBlock::queued_solution().is_none());
+
+			// the fallback result is exported in the least significant page.
+			assert!(MultiBlock::elect(2).unwrap().is_empty());
+			assert_eq!(
+				MultiBlock::queued_solution(),
+				Some(SolutionInfo { compute: ElectionCompute::Fallback, score: Default::default() })
+			);
+			assert!(MultiBlock::elect(1).unwrap().is_empty());
+			assert_eq!(MultiBlock::elect(0).unwrap().len(), 2);
+
+			assert_eq!(MultiBlock::round(), 2);
+			assert_eq!(MultiBlock::current_phase(), Phase::Off);
+			assert!(multi_block_events().contains(&Event::ElectionFinalized {
+				compute: ElectionCompute::Fallback,
+				score: Default::default()
+			}));
+		})
+	}
+
+	#[test]
+	fn emergency_phase_awaits_emergency_solution() {
+		ExtBuilder::default().build_and_execute(|| {
+			OnChainFallback::set(false);
+			roll_to_unsigned();
+
+			let supports = vec![
+				(10, Support { total: 20, voters: vec![(1, 10), (10, 10)] }),
+				(20, Support { total: 30, voters: vec![(6, 10), (20, 20)] }),
+			];
+			assert_noop!(
+				MultiBlock::set_emergency_solution(RuntimeOrigin::root(), supports.clone()),
+				Error::<Runtime>::CallNotAllowed
+			);
+
+			// neither a solution nor the fallback is available.
+			assert_eq!(MultiBlock::elect(2), Err(ElectionError::Fallback("NoFallback.")));
+			assert_eq!(MultiBlock::current_phase(), Phase::Emergency);
+			assert!(multi_block_events().contains(&Event::ElectionFailed));
+			assert_eq!(MultiBlock::elect(2), Err(ElectionError::Fallback("NoFallback.")));
+			assert_eq!(MultiBlock::round(), 1);
+
+			assert_noop!(
+				MultiBlock::set_emergency_solution(RuntimeOrigin::signed(99), supports.clone()),
+				DispatchError::BadOrigin
+			);
+			assert_ok!(MultiBlock::set_emergency_solution(RuntimeOrigin::root(), supports.clone()));
+			let score = supports.evaluate();
+			assert_eq!(
+				MultiBlock::queued_solution(),
+				Some(SolutionInfo { compute: ElectionCompute::Emergency, score })
+			);
+
+			// the emergency solution is exported in the least significant page.
+			assert!(MultiBlock::elect(2).unwrap().is_empty());
+			assert_eq!(MultiBlock::current_phase(), Phase::Export);
+			assert!(MultiBlock::elect(1).unwrap().is_empty());
+			assert_eq!(MultiBlock::elect(0).unwrap().into_inner(), supports);
+
+			assert_eq!(MultiBlock::round(), 2);
+			assert_eq!(MultiBlock::current_phase(), Phase::Off);
+			assert!(multi_block_events()
+				.contains(&Event::ElectionFinalized { compute: ElectionCompute::Emergency, score }));
+		})
+	}
+}
@@ -0,0 +1,452 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{self as multi_block, unsigned::MinerConfig};
+use frame_election_provider_support::{
+	bounds::{DataProviderBounds, ElectionBounds, ElectionBoundsBuilder},
+	data_provider, onchain, ElectionDataProvider, NposSolution, PageIndex, SequentialPhragmen,
+};
+pub use frame_support::derive_impl;
+use frame_support::{
+	assert_ok, parameter_types,
+	traits::{ConstU32, Hooks},
+	weights::{constants, Weight},
+};
+use parking_lot::RwLock;
+use sp_core::{
+	offchain::{
+		testing::{PoolState, TestOffchainExt, TestTransactionPoolExt},
+		OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+	},
+	H256,
+};
+use sp_npos_elections::BalancingConfig;
+use sp_runtime::{
+	bounded_vec,
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage, PerU16,
+};
+use std::sync::Arc;
+
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic =
+	sp_runtime::generic::UncheckedExtrinsic<AccountId, RuntimeCall, (), ()>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		Balances: pallet_balances,
+		MultiBlock: multi_block,
+	}
+);
+
+pub(crate) type Balance = u64;
+pub(crate) type AccountId = u64;
+pub(crate) type BlockNumber = u64;
+pub(crate) type VoterIndex = u32;
+pub(crate) type TargetIndex = u16;
+
+frame_election_provider_support::generate_solution_type!(
+	pub struct TestNposSolution::<
+		VoterIndex = VoterIndex,
+		TargetIndex = TargetIndex,
+		Accuracy = PerU16,
+		MaxVoters = ConstU32::<2_000>
+	>(16)
+);
+
+/// All events of this pallet.
+pub(crate) fn multi_block_events() -> Vec<super::Event<Runtime>> {
+	System::read_events_for_pallet::<super::Event<Runtime>>()
+}
+
+/// To from `now` to block `n`.
+pub fn roll_to(n: BlockNumber) {
+	let now = System::block_number();
+	for i in now + 1..=n {
+		System::set_block_number(i);
+		MultiBlock::on_initialize(i);
+	}
+}
+
+pub fn roll_to_signed() {
+	while !MultiBlock::current_phase().is_signed() {
+		roll_to(System::block_number() + 1);
+	}
+}
+
+pub fn roll_to_signed_validation() {
+	while !MultiBlock::current_phase().is_signed_validation() {
+		roll_to(System::block_number() + 1);
+	}
+}
+
+pub fn roll_to_unsigned() {
+	while !MultiBlock::current_phase().is_unsigned() {
+		roll_to(System::block_number() + 1);
+	}
+}
+
+/// Roll to the unsigned phase and export all pages, until round `n` starts.
+pub fn roll_to_round(n: u32) {
+	assert!(MultiBlock::round() <= n);
+
+	while MultiBlock::round() != n {
+		roll_to_unsigned();
+		for page in (MultiBlock::lsp()..=MultiBlock::msp()).rev() {
+			assert_ok!(MultiBlock::elect(page));
+		}
+	}
+}
+
+/// Mine a full paged solution from the current snapshot.
+pub fn mine_full_solution() -> PagedSolutionOf<Runtime> {
+	MultiBlock::mine_solution().unwrap()
+}
+
+/// Register `who` with the score of `paged_solution`, and submit all of its pages.
+pub fn submit_signed(who: AccountId, paged_solution: &PagedSolutionOf<Runtime>) {
+	assert_ok!(MultiBlock::register(RuntimeOrigin::signed(who), paged_solution.score));
+	for (page, solution) in paged_solution.solution_pages.iter().enumerate() {
+		assert_ok!(MultiBlock::submit_page(
+			RuntimeOrigin::signed(who),
+			page as PageIndex,
+			Some(Box::new(solution.clone()))
+		));
+	}
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {
+	type SS58Prefix = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type RuntimeOrigin = RuntimeOrigin;
+	type Nonce = u64;
+	type RuntimeCall = RuntimeCall;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ();
+	type DbWeight = ();
+	type BlockLength = ();
+	type BlockWeights = BlockWeights;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+const NORMAL_DISPATCH_RATIO: Perbill = Perbill::from_percent(75);
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+	pub BlockWeights: frame_system::limits::BlockWeights = frame_system::limits::BlockWeights
+		::with_sensible_defaults(
+			Weight::from_parts(2u64 * constants::WEIGHT_REF_TIME_PER_SECOND, u64::MAX),
+			NORMAL_DISPATCH_RATIO,
+		);
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = ();
+}
+
+parameter_types! {
+	pub static Targets: Vec<AccountId> = vec![10, 20, 30, 40];
+	// Split into pages of `VoterSnapshotPerBlock` voters, the first chunk being the most
+	// significant page.
+	pub static Voters: Vec<VoterOf<Runtime>> = vec![
+		(1, 10, bounded_vec![10, 20]),
+		(2, 10, bounded_vec![30, 40]),
+		(3, 10, bounded_vec![40]),
+		(4, 10, bounded_vec![10, 20, 30, 40]),
+		(5, 20, bounded_vec![10]),
+		(6, 20, bounded_vec![20, 30]),
+		(7, 30, bounded_vec![40]),
+		(8, 5, bounded_vec![10, 30]),
+		// self votes.
+		(10, 10, bounded_vec![10]),
+		(20, 20, bounded_vec![20]),
+		(30, 30, bounded_vec![30]),
+		(40, 40, bounded_vec![40]),
+	];
+
+	pub static DesiredTargets: u32 = 2;
+	pub static Pages: PageIndex = 3;
+	pub static VoterSnapshotPerBlock: u32 = 4;
+	pub static TargetSnapshotPerBlock: u32 = 100;
+	pub static SignedPhase: BlockNumber = 5;
+	pub static SignedValidationPhase: BlockNumber = 3;
+	pub static UnsignedPhase: BlockNumber = 5;
+	pub static SignedMaxSubmissions: u32 = 5;
+	pub static SignedDepositBase: Balance = 5;
+	pub static SignedDepositPerPage: Balance = 1;
+	pub static SignedRewardBase: Balance = 7;
+	pub static BailoutGraceRatio: Perbill = Perbill::from_percent(50);
+	pub static MinerTxPriority: u64 = 100;
+
+	#[derive(Debug)]
+	pub static MaxWinners: u32 = 200;
+	pub static OnChainElectionsBounds: ElectionBounds = ElectionBoundsBuilder::default().build();
+	pub static EpochLength: u64 = 30;
+	pub static OnChainFallback: bool = true;
+}
+
+pub struct OnChainSeqPhragmen;
+impl onchain::Config for OnChainSeqPhragmen {
+	type System = Runtime;
+	type Solver = SequentialPhragmen<AccountId, SolutionAccuracyOf<Runtime>, Balancing>;
+	type DataProvider = StakingMock;
+	type WeightInfo = ();
+	type MaxWinners = MaxWinners;
+	type Bounds = OnChainElectionsBounds;
+}
+
+pub struct MockFallback;
+impl ElectionProviderBase for MockFallback {
+	type BlockNumber = BlockNumber;
+	type AccountId = AccountId;
+	type Error = &'static str;
+	type DataProvider = StakingMock;
+	type MaxWinners = MaxWinners;
+	type Pages = ConstU32<1>;
+}
+
+impl InstantElectionProvider for MockFallback {
+	fn instant_elect(
+		voters_bounds: DataProviderBounds,
+		targets_bounds: DataProviderBounds,
+	) -> Result<BoundedSupportsOf<Self>, Self::Error> {
+		if OnChainFallback::get() {
+			onchain::OnChainExecution::<OnChainSeqPhragmen>::instant_elect(
+				voters_bounds,
+				targets_bounds,
+			)
+			.map_err(|_| "onchain::OnChainExecution failed.")
+		} else {
+			Err("NoFallback.")
+		}
+	}
+}
+
+parameter_types! {
+	pub static Balancing: Option<BalancingConfig> = Some( BalancingConfig { iterations: 0, tolerance: 0 } );
+}
+
+impl MinerConfig for Runtime {
+	type AccountId = AccountId;
+	type Solution = TestNposSolution;
+	type MaxVotesPerVoter = <StakingMock as ElectionDataProvider>::MaxVotesPerVoter;
+	type MaxWinners = MaxWinners;
+}
+
+impl crate::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type Currency = Balances;
+	type Pages = Pages;
+	type SignedPhase = SignedPhase;
+	type SignedValidationPhase = SignedValidationPhase;
+	type UnsignedPhase = UnsignedPhase;
+	type VoterSnapshotPerBlock = VoterSnapshotPerBlock;
+	type TargetSnapshotPerBlock = TargetSnapshotPerBlock;
+	type MaxWinners = MaxWinners;
+	type SignedMaxSubmissions = SignedMaxSubmissions;
+	type SignedDepositBase = SignedDepositBase;
+	type SignedDepositPerPage = SignedDepositPerPage;
+	type SignedRewardBase = SignedRewardBase;
+	type BailoutGraceRatio = BailoutGraceRatio;
+	type MinerTxPriority = MinerTxPriority;
+	type MinerConfig = Self;
+	type Solver = SequentialPhragmen<AccountId, SolutionAccuracyOf<Runtime>, Balancing>;
+	type DataProvider = StakingMock;
+	type Fallback = MockFallback;
+	type ManagerOrigin = frame_system::EnsureRoot<AccountId>;
+	type WeightInfo = ();
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Runtime
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+pub type Extrinsic = sp_runtime::testing::TestXt<RuntimeCall, ()>;
+
+parameter_types! {
+	pub MaxNominations: u32 = <TestNposSolution as NposSolution>::LIMIT as u32;
+}
+
+pub struct StakingMock;
+impl ElectionDataProvider for StakingMock {
+	type BlockNumber = BlockNumber;
+	type AccountId = AccountId;
+	type MaxVotesPerVoter = MaxNominations;
+
+	fn electable_targets(
+		bounds: DataProviderBounds,
+		_page: PageIndex,
+	) -> data_provider::Result<Vec<AccountId>> {
+		let targets = Targets::get();
+
+		if bounds.count.map_or(false, |max_len| targets.len() > max_len.0 as usize) {
+			return Err("Targets too big")
+		}
+
+		Ok(targets)
+	}
+
+	fn electing_voters(
+		bounds: DataProviderBounds,
+		page: PageIndex,
+	) -> data_provider::Result<Vec<VoterOf<Runtime>>> {
+		let voters = Voters::get();
+
+		// unbounded requests, such as the ones of the fallback, get all voters at once.
+		let Some(page_size) = bounds.count.map(|c| c.0 as usize) else { return Ok(voters) };
+		let msp = Pages::get().saturating_sub(1);
+		let start = (msp.saturating_sub(page) as usize).saturating_mul(page_size);
+
+		Ok(voters.into_iter().skip(start).take(page_size).collect())
+	}
+
+	fn desired_targets() -> data_provider::Result<u32> {
+		Ok(DesiredTargets::get())
+	}
+
+	fn next_election_prediction(now: u64) -> u64 {
+		now + EpochLength::get() - now % EpochLength::get()
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn put_snapshot(
+		voters: Vec<VoterOf<Runtime>>,
+		targets: Vec<AccountId>,
+		_target_stake: Option<frame_election_provider_support::VoteWeight>,
+	) {
+		Targets::set(targets);
+		Voters::set(voters);
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn clear() {
+		Targets::set(vec![]);
+		Voters::set(vec![]);
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add_voter(
+		voter: AccountId,
+		weight: frame_election_provider_support::VoteWeight,
+		targets: frame_support::BoundedVec<AccountId, Self::MaxVotesPerVoter>,
+	) {
+		let mut current = Voters::get();
+		current.push((voter, weight, targets));
+		Voters::set(current);
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add_target(target: AccountId) {
+		let mut current = Targets::get();
+		current.push(target);
+		Targets::set(current);
+	}
+}
+
+#[derive(Default)]
+pub struct ExtBuilder {}
+
+impl ExtBuilder {
+	pub fn signed_validation_phase(self, validation: BlockNumber) -> Self {
+		<SignedValidationPhase>::set(validation);
+		self
+	}
+	pub fn signed_max_submission(self, count: u32) -> Self {
+		<SignedMaxSubmissions>::set(count);
+		self
+	}
+	pub fn build(self) -> sp_io::TestExternalities {
+		sp_tracing::try_init_simple();
+		let mut storage =
+			frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+		let _ = pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![
+				// bunch of account for submitting stuff only.
+				(99, 100),
+				(100, 100),
+				(101, 100),
+				(102, 100),
+				(103, 100),
+				(999, 100),
+			],
+		}
+		.assimilate_storage(&mut storage);
+
+		let mut ext = sp_io::TestExternalities::from(storage);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	pub fn build_offchainify(self) -> (sp_io::TestExternalities, Arc<RwLock<PoolState>>) {
+		let mut ext = self.build();
+		let (offchain, _offchain_state) = TestOffchainExt::new();
+		let (pool, pool_state) = TestTransactionPoolExt::new();
+
+		ext.register_extension(OffchainDbExt::new(offchain.clone()));
+		ext.register_extension(OffchainWorkerExt::new(offchain));
+		ext.register_extension(TransactionPoolExt::new(pool));
+
+		(ext, pool_state)
+	}
+
+	pub fn build_and_execute(self, test: impl FnOnce() -> ()) {
+		sp_tracing::try_init_simple();
+
+		let mut ext = self.build();
+		ext.execute_with(test);
+
+		#[cfg(feature = "try-runtime")]
+		ext.execute_with(|| {
+			frame_support::assert_ok!(
+				<MultiBlock as frame_support::traits::Hooks<u64>>::try_state(System::block_number())
+			);
+		});
+	}
+}
@@ -0,0 +1,389 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The signed phase implementation.
+
+use crate::{
+	Config, Error, Event, HoldReason, Pallet, SolutionOf, SortedScores, SubmissionMetadataStorage,
+	SubmissionStorage,
+};
+use codec::{Decode, Encode};
+use frame_election_provider_support::PageIndex;
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	traits::{
+		fungible::{Mutate, MutateHold},
+		tokens::{Fortitude, Precision},
+		Defensive, Get,
+	},
+};
+use scale_info::TypeInfo;
+use sp_arithmetic::traits::Saturating;
+use sp_npos_elections::ElectionScore;
+use sp_runtime::{Perbill, RuntimeDebug};
+use sp_std::prelude::*;
+
+/// The metadata of a registered signed submission.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SubmissionMetadata<Balance> {
+	/// The total deposit held for this submission, including the per-page deposits.
+	pub deposit: Balance,
+	/// The score claimed by the submitter.
+	pub claimed_score: ElectionScore,
+	/// Whether each page of the solution has been submitted, indexed by page.
+	pub pages: Vec<bool>,
+}
+
+impl<T: Config> Pallet<T> {
+	/// The leader of the signed submissions of the given round, namely the one with the best
+	/// claimed score.
+	pub fn signed_leader(round: u32) -> Option<(T::AccountId, ElectionScore)> {
+		<SortedScores<T>>::get(round).last().cloned()
+	}
+
+	/// Register `who` with `claimed_score` in the current round.
+	pub(crate) fn do_register(who: T::AccountId, claimed_score: ElectionScore) -> DispatchResult {
+		let round = Self::round();
+		ensure!(!<SubmissionMetadataStorage<T>>::contains_key(round, &who), Error::<T>::Duplicate);
+		ensure!(
+			Self::minimum_untrusted_score().map_or(true, |min_score| {
+				claimed_score.strict_threshold_better(min_score, Perbill::zero())
+			}),
+			Error::<T>::WeakSubmission
+		);
+
+		let mut sorted = <SortedScores<T>>::get(round);
+		let maybe_ejected = if sorted.is_full() {
+			// the weakest submission is the first one.
+			ensure!(
+				sorted.first().map_or(true, |(_, weakest)| claimed_score > *weakest),
+				Error::<T>::QueueFull
+			);
+			Some(sorted.remove(0).0)
+		} else {
+			None
+		};
+
+		// in case of equal scores, the earlier submission stays closer to the leader.
+		let position = sorted.partition_point(|(_, score)| *score < claimed_score);
+		sorted
+			.try_insert(position, (who.clone(), claimed_score))
+			.defensive_proof("a spot was made for the new submission; qed")
+			.map_err(|_| Error::<T>::QueueFull)?;
+
+		let deposit = T::SignedDepositBase::get();
+		T::Currency::hold(&HoldReason::SignedSubmission.into(), &who, deposit)?;
+
+		if let Some(ejected) = maybe_ejected {
+			if let Some(metadata) = Self::take_submission(round, &ejected) {
+				Self::release_deposit(&ejected, metadata.deposit);
+			}
+			Self::deposit_event(Event::Ejected { round, who: ejected });
+		}
+
+		<SortedScores<T>>::insert(round, sorted);
+		<SubmissionMetadataStorage<T>>::insert(
+			round,
+			&who,
+			SubmissionMetadata {
+				deposit,
+				claimed_score,
+				pages: vec![false; T::Pages::get() as usize],
+			},
+		);
+		Self::deposit_event(Event::Registered { round, who, claimed_score });
+		Ok(())
+	}
+
+	/// Store, or remove, a page of the submission of `who` in the current round.
+	pub(crate) fn do_submit_page(
+		who: T::AccountId,
+		page: PageIndex,
+		maybe_solution: Option<SolutionOf<T::MinerConfig>>,
+	) -> DispatchResult {
+		let round = Self::round();
+		let mut metadata =
+			<SubmissionMetadataStorage<T>>::get(round, &who).ok_or(Error::<T>::NotRegistered)?;
+		let had_page = *metadata.pages.get(page as usize).ok_or(Error::<T>::BadPageIndex)?;
+		let per_page = T::SignedDepositPerPage::get();
+
+		match (had_page, maybe_solution.is_some()) {
+			(false, true) => {
+				T::Currency::hold(&HoldReason::SignedSubmission.into(), &who, per_page)?;
+				metadata.deposit = metadata.deposit.saturating_add(per_page);
+			},
+			(true, false) => {
+				Self::release_deposit(&who, per_page);
+				metadata.deposit = metadata.deposit.saturating_sub(per_page);
+			},
+			_ => {},
+		}
+		metadata.pages[page as usize] = maybe_solution.is_some();
+
+		match maybe_solution {
+			Some(solution) => <SubmissionStorage<T>>::insert((round, &who, page), solution),
+			None => <SubmissionStorage<T>>::remove((round, &who, page)),
+		}
+		<SubmissionMetadataStorage<T>>::insert(round, &who, metadata);
+
+		Self::deposit_event(Event::PageStored { round, who, page });
+		Ok(())
+	}
+
+	/// Retract the submission of `who` in the current round, returning only
+	/// [`Config::BailoutGraceRatio`] of their deposit.
+	pub(crate) fn do_bail(who: T::AccountId) -> DispatchResult {
+		let round = Self::round();
+		let metadata = Self::take_submission(round, &who).ok_or(Error::<T>::NotRegistered)?;
+		<SortedScores<T>>::mutate(round, |sorted| sorted.retain(|(x, _)| x != &who));
+
+		let refund = T::BailoutGraceRatio::get() * metadata.deposit;
+		Self::release_deposit(&who, refund);
+		Self::burn_deposit(&who, metadata.deposit.saturating_sub(refund));
+
+		Self::deposit_event(Event::Bailed { round, who });
+		Ok(())
+	}
+
+	/// Release the deposit of a submission of a past round that was never verified.
+	pub(crate) fn do_clear_old_round_data(who: T::AccountId, round: u32) -> DispatchResult {
+		let metadata = Self::take_submission(round, &who).ok_or(Error::<T>::NotRegistered)?;
+		Self::release_deposit(&who, metadata.deposit);
+		Ok(())
+	}
+
+	/// Reward `who`, whose submission was just verified and queued.
+	pub(crate) fn reward_signed(round: u32, who: &T::AccountId) {
+		<SortedScores<T>>::mutate(round, |sorted| sorted.retain(|(x, _)| x != who));
+		if let Some(metadata) = Self::take_submission(round, who) {
+			Self::release_deposit(who, metadata.deposit);
+		}
+
+		let reward = T::SignedRewardBase::get();
+		let _ = T::Currency::mint_into(who, reward).defensive();
+		Self::deposit_event(Event::Rewarded { account: who.clone(), value: reward });
+	}
+
+	/// Slash `who`, whose submission just failed verification.
+	pub(crate) fn slash_signed(round: u32, who: &T::AccountId) {
+		<SortedScores<T>>::mutate(round, |sorted| sorted.retain(|(x, _)| x != who));
+		if let Some(metadata) = Self::take_submission(round, who) {
+			Self::burn_deposit(who, metadata.deposit);
+			Self::deposit_event(Event::Slashed { account: who.clone(), value: metadata.deposit });
+		}
+	}
+
+	/// Remove the metadata and all the pages of the submission of `who` in `round`.
+	fn take_submission(
+		round: u32,
+		who: &T::AccountId,
+	) -> Option<SubmissionMetadata<crate::BalanceOf<T>>> {
+		let metadata = <SubmissionMetadataStorage<T>>::take(round, who)?;
+		for page in 0..T::Pages::get() {
+			<SubmissionStorage<T>>::remove((round, who, page));
+		}
+		Some(metadata)
+	}
+
+	fn release_deposit(who: &T::AccountId, amount: crate::BalanceOf<T>) {
+		let _ = T::Currency::release(
+			&HoldReason::SignedSubmission.into(),
+			who,
+			amount,
+			Precision::BestEffort,
+		)
+		.defensive();
+	}
+
+	fn burn_deposit(who: &T::AccountId, amount: crate::BalanceOf<T>) {
+		let _ = T::Currency::burn_held(
+			&HoldReason::SignedSubmission.into(),
+			who,
+			amount,
+			Precision::BestEffort,
+			Fortitude::Force,
+		)
+		.defensive();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{mock::*, Phase};
+	use frame_support::{assert_noop, assert_ok, traits::fungible::InspectHold};
+
+	fn score(minimal_stake: u128) -> ElectionScore {
+		ElectionScore { minimal_stake, ..Default::default() }
+	}
+
+	fn held(who: AccountId) -> Balance {
+		Balances::balance_on_hold(&HoldReason::SignedSubmission.into(), &who)
+	}
+
+	#[test]
+	fn cannot_register_outside_of_signed_phase() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_eq!(MultiBlock::current_phase(), Phase::Off);
+			assert_noop!(
+				MultiBlock::register(RuntimeOrigin::signed(99), score(10)),
+				Error::<Runtime>::PhaseNotSigned
+			);
+
+			roll_to_signed();
+			assert_ok!(MultiBlock::register(RuntimeOrigin::signed(99), score(10)));
+			assert_noop!(
+				MultiBlock::register(RuntimeOrigin::signed(99), score(20)),
+				Error::<Runtime>::Duplicate
+			);
+		})
+	}
+
+	#[test]
+	fn register_keeps_scores_sorted_and_ejects_weakest() {
+		ExtBuilder::default().signed_max_submission(2).build_and_execute(|| {
+			roll_to_signed();
+			let round = MultiBlock::round();
+
+			assert_ok!(MultiBlock::register(RuntimeOrigin::signed(99), score(10)));
+			assert_ok!(MultiBlock::register(RuntimeOrigin::signed(100), score(5)));
+			assert_eq!(
+				SortedScores::<Runtime>::get(round).into_inner(),
+				vec![(100, score(5)), (99, score(10))]
+			);
+			assert_eq!(held(100), SignedDepositBase::get());
+
+			// not better than the weakest.
+			assert_noop!(
+				MultiBlock::register(RuntimeOrigin::signed(101), score(5)),
+				Error::<Runtime>::QueueFull
+			);
+
+			// better than the weakest, which is ejected and refunded.
+			assert_ok!(MultiBlock::register(RuntimeOrigin::signed(101), score(7)));
+			assert_eq!(
+				SortedScores::<Runtime>::get(round).into_inner(),
+				vec![(101, score(7)), (99, score(10))]
+			);
+			assert_eq!(held(100), 0);
+			assert!(!SubmissionMetadataStorage::<Runtime>::contains_key(round, 100));
+			assert_eq!(MultiBlock::signed_leader(round), Some((99, score(10))));
+			assert!(multi_block_events().contains(&crate::Event::Ejected { round, who: 100 }));
+		})
+	}
+
+	#[test]
+	fn submit_page_holds_and_releases_per_page_deposit() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to_signed();
+			let round = MultiBlock::round();
+			assert_noop!(
+				MultiBlock::submit_page(RuntimeOrigin::signed(99), 0, Some(Default::default())),
+				Error::<Runtime>::NotRegistered
+			);
+
+			assert_ok!(MultiBlock::register(RuntimeOrigin::signed(99), score(10)));
+			assert_noop!(
+				MultiBlock::submit_page(RuntimeOrigin::signed(99), Pages::get(), None),
+				Error::<Runtime>::BadPageIndex
+			);
+
+			assert_ok!(MultiBlock::submit_page(
+				RuntimeOrigin::signed(99),
+				1,
+				Some(Default::default())
+			));
+			assert_eq!(held(99), SignedDepositBase::get() + SignedDepositPerPage::get());
+			assert!(SubmissionStorage::<Runtime>::contains_key((round, 99, 1)));
+
+			// re-submitting the same page does not hold again.
+			assert_ok!(MultiBlock::submit_page(
+				RuntimeOrigin::signed(99),
+				1,
+				Some(Default::default())
+			));
+			assert_eq!(held(99), SignedDepositBase::get() + SignedDepositPerPage::get());
+
+			// removing it releases the page deposit.
+			assert_ok!(MultiBlock::submit_page(RuntimeOrigin::signed(99), 1, None));
+			assert_eq!(held(99), SignedDepositBase::get());
+			assert!(!SubmissionStorage::<Runtime>::contains_key((round, 99, 1)));
+		})
+	}
+
+	#[test]
+	fn bail_returns_grace_ratio_of_deposit() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to_signed();
+			let round = MultiBlock::round();
+			let free_before = Balances::free_balance(99);
+
+			assert_ok!(MultiBlock::register(RuntimeOrigin::signed(99), score(10)));
+			assert_ok!(MultiBlock::submit_page(
+				RuntimeOrigin::signed(99),
+				0,
+				Some(Default::default())
+			));
+			let deposit = SignedDepositBase::get() + SignedDepositPerPage::get();
+
+			assert_ok!(MultiBlock::bail(RuntimeOrigin::signed(99)));
+			assert_eq!(held(99), 0);
+			assert_eq!(
+				Balances::free_balance(99),
+				free_before - deposit + BailoutGraceRatio::get() * deposit
+			);
+			assert!(SortedScores::<Runtime>::get(round).is_empty());
+			assert!(!SubmissionStorage::<Runtime>::contains_key((round, 99, 0)));
+			assert_noop!(
+				MultiBlock::bail(RuntimeOrigin::signed(99)),
+				Error::<Runtime>::NotRegistered
+			);
+		})
+	}
+
+	#[test]
+	fn unverified_submissions_can_be_cleared_after_the_round() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to_signed();
+			let round = MultiBlock::round();
+			let solution = mine_full_solution();
+
+			// 100 is the leader with a correct solution, 99 claims a weaker score.
+			submit_signed(100, &solution);
+			assert_ok!(MultiBlock::register(RuntimeOrigin::signed(99), score(1)));
+			assert_noop!(
+				MultiBlock::clear_old_round_data(RuntimeOrigin::signed(99), round),
+				Error::<Runtime>::RoundNotOver
+			);
+
+			// 100 is verified and rewarded, while 99 is never verified since it cannot improve
+			// the queued solution.
+			roll_to_round(round + 1);
+			assert_eq!(held(100), 0);
+			assert_eq!(held(99), SignedDepositBase::get());
+			assert_noop!(
+				MultiBlock::clear_old_round_data(RuntimeOrigin::signed(100), round),
+				Error::<Runtime>::NotRegistered
+			);
+
+			assert_ok!(MultiBlock::clear_old_round_data(RuntimeOrigin::signed(99), round));
+			assert_eq!(held(99), 0);
+			assert!(!SubmissionMetadataStorage::<Runtime>::contains_key(round, 99));
+		})
+	}
+}
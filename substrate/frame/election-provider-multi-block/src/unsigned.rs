@@ -0,0 +1,460 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The unsigned phase, and its miner.
+
+use crate::{
+	helpers, Call, Config, Error, FeasibilityError, PagedRawSolution, PagedSolutionOf,
+	PagedVoterSnapshot, Pallet, SolutionAccuracyOf, SolutionOf,
+};
+use frame_election_provider_support::{NposSolution, NposSolver};
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get};
+use frame_system::offchain::SubmitTransaction;
+use scale_info::TypeInfo;
+use sp_npos_elections::{
+	assignment_ratio_to_staked_normalized, assignment_staked_to_ratio_normalized, ElectionResult,
+	ElectionScore, ExtendedBalance, Support, Supports,
+};
+use sp_runtime::DispatchError;
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+
+/// Same as [`crate::VoterOf`], but parameterized by the `MinerConfig`.
+pub type MinerVoterOf<T> = frame_election_provider_support::Voter<
+	<T as MinerConfig>::AccountId,
+	<T as MinerConfig>::MaxVotesPerVoter,
+>;
+
+/// The relative distribution of a voter's stake among the winning targets.
+pub type AssignmentOf<T> =
+	sp_npos_elections::Assignment<<T as MinerConfig>::AccountId, SolutionAccuracyOf<T>>;
+
+/// The [`IndexAssignment`][frame_election_provider_support::IndexAssignment] type specialized for a
+/// particular miner configuration `T`.
+pub type IndexAssignmentOf<T> = frame_election_provider_support::IndexAssignmentOf<SolutionOf<T>>;
+
+/// Error type for operations related to the OCW npos solution miner.
+#[derive(frame_support::DebugNoBound, frame_support::PartialEqNoBound)]
+pub enum MinerError {
+	/// An internal error in the NPoS elections crate.
+	NposElections(sp_npos_elections::Error),
+	/// Snapshot data was unavailable unexpectedly.
+	SnapshotUnAvailable,
+	/// Submitting a transaction to the pool failed.
+	PoolSubmissionFailed,
+	/// The pre-dispatch checks failed for the mined solution.
+	PreDispatchChecksFailed(DispatchError),
+	/// The solution generated from the miner is not feasible.
+	Feasibility(FeasibilityError),
+	/// An error from the solver.
+	Solver,
+}
+
+impl From<sp_npos_elections::Error> for MinerError {
+	fn from(e: sp_npos_elections::Error) -> Self {
+		MinerError::NposElections(e)
+	}
+}
+
+impl From<FeasibilityError> for MinerError {
+	fn from(e: FeasibilityError) -> Self {
+		MinerError::Feasibility(e)
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Mine a new paged npos solution from the snapshot of the current round.
+	pub fn mine_solution() -> Result<PagedSolutionOf<T>, MinerError> {
+		let voter_pages = (0..T::Pages::get())
+			.map(|page| <PagedVoterSnapshot<T>>::get(page).ok_or(MinerError::SnapshotUnAvailable))
+			.collect::<Result<Vec<_>, _>>()?;
+		let targets = Self::target_snapshot().ok_or(MinerError::SnapshotUnAvailable)?;
+		let desired_targets = Self::desired_targets().ok_or(MinerError::SnapshotUnAvailable)?;
+
+		Miner::<T::MinerConfig>::mine_paged_solution_with_snapshot::<T::Solver>(
+			&voter_pages,
+			&targets,
+			desired_targets,
+			Self::round(),
+		)
+	}
+
+	/// Mine a new solution, check it, and submit it to the transaction pool as an unsigned
+	/// transaction.
+	pub fn mine_check_and_submit() -> Result<(), MinerError> {
+		let paged_solution = Self::mine_solution()?;
+		Self::unsigned_pre_dispatch_checks(&paged_solution)
+			.map_err(MinerError::PreDispatchChecksFailed)?;
+
+		let call = Call::submit_unsigned { paged_solution: Box::new(paged_solution) };
+		SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
+			.map_err(|_| MinerError::PoolSubmissionFailed)
+	}
+
+	/// Checks if an unsigned solution can be submitted.
+	///
+	/// These check both for snapshot independent checks, and some checks that are specific to the
+	/// unsigned phase.
+	///
+	/// NOTE: Ideally, these tests should move more and more outside of this and more to the miner's
+	/// code, so that we do less and less storage reads here.
+	pub fn unsigned_pre_dispatch_checks(paged_solution: &PagedSolutionOf<T>) -> DispatchResult {
+		// ensure solution is timely. Don't panic yet. This is a cheap check.
+		ensure!(Self::current_phase().is_unsigned(), Error::<T>::PreDispatchEarlySubmission);
+
+		// ensure round is current
+		ensure!(Self::round() == paged_solution.round, Error::<T>::PreDispatchWrongRound);
+
+		// ensure correct number of pages.
+		ensure!(
+			paged_solution.solution_pages.len() as u32 == T::Pages::get(),
+			Error::<T>::PreDispatchWrongPageCount,
+		);
+
+		// ensure score is being improved.
+		ensure!(
+			Self::ensure_score_improves(paged_solution.score).is_ok(),
+			Error::<T>::PreDispatchWeakSubmission,
+		);
+
+		Ok(())
+	}
+}
+
+/// Configurations for a miner that comes with this pallet.
+pub trait MinerConfig {
+	/// The account id type.
+	type AccountId: Ord + Clone + codec::Codec + sp_std::fmt::Debug;
+	/// The solution that the miner is mining, one per page.
+	type Solution: codec::Codec
+		+ Default
+		+ PartialEq
+		+ Eq
+		+ Clone
+		+ sp_std::fmt::Debug
+		+ Ord
+		+ NposSolution
+		+ TypeInfo;
+	/// Maximum number of votes per voter in the snapshots.
+	type MaxVotesPerVoter;
+	/// The maximum number of winners that can be elected.
+	type MaxWinners: Get<u32>;
+}
+
+/// A base miner, suitable to be used for both signed and unsigned submissions.
+pub struct Miner<T: MinerConfig>(sp_std::marker::PhantomData<T>);
+impl<T: MinerConfig> Miner<T> {
+	/// Mine a paged solution from the given snapshot.
+	///
+	/// The election is computed over all voters of all pages at once. The resulting assignments
+	/// are then split according to the snapshot page of each voter.
+	pub fn mine_paged_solution_with_snapshot<S>(
+		voter_pages: &[Vec<MinerVoterOf<T>>],
+		targets: &[T::AccountId],
+		desired_targets: u32,
+		round: u32,
+	) -> Result<PagedRawSolution<SolutionOf<T>>, MinerError>
+	where
+		S: NposSolver<AccountId = T::AccountId>,
+	{
+		let all_voters = voter_pages.iter().flatten().cloned().collect::<Vec<_>>();
+		let ElectionResult { assignments, winners: _ } =
+			S::solve(desired_targets as usize, targets.to_vec(), all_voters.clone()).map_err(
+				|e| {
+					log_no_system!(error, "solver error: {:?}", e);
+					MinerError::Solver
+				},
+			)?;
+
+		// Reduce (requires round-trip to staked form)
+		let assignments: Vec<AssignmentOf<T>> = {
+			let cache = helpers::generate_voter_cache::<T>(&all_voters);
+			let stake_of = helpers::stake_of_fn::<T>(&all_voters, &cache);
+			let mut staked = assignment_ratio_to_staked_normalized(assignments, &stake_of)?;
+			sp_npos_elections::reduce(&mut staked);
+			assignment_staked_to_ratio_normalized(staked)?
+		};
+
+		// split the assignments into their pages.
+		let target_index = helpers::target_index_fn::<T>(targets);
+		let mut remaining = assignments;
+		let mut solution_pages = Vec::with_capacity(voter_pages.len());
+		for page_voters in voter_pages {
+			let cache = helpers::generate_voter_cache::<T>(page_voters);
+			let voter_index = helpers::voter_index_fn::<T>(&cache);
+			let (in_page, rest): (Vec<_>, Vec<_>) = remaining
+				.into_iter()
+				.partition(|assignment| cache.contains_key(&assignment.who));
+			remaining = rest;
+
+			let index_assignments = in_page
+				.iter()
+				.map(|assignment| {
+					IndexAssignmentOf::<T>::new(assignment, &voter_index, &target_index)
+				})
+				.collect::<Result<Vec<_>, _>>()?;
+			solution_pages.push(SolutionOf::<T>::try_from(&index_assignments[..])?);
+		}
+		debug_assert!(remaining.is_empty(), "all voters belong to a snapshot page");
+
+		// compute the score exactly as it will be computed on-chain.
+		let page_supports = solution_pages
+			.iter()
+			.zip(voter_pages)
+			.map(|(solution, page_voters)| {
+				Self::feasibility_check_page(
+					solution.clone(),
+					page_voters,
+					targets,
+					desired_targets,
+				)
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+		let (score, _) = Self::score_of(page_supports.iter().map(|supports| supports.as_slice()));
+
+		Ok(PagedRawSolution { solution_pages, score, round })
+	}
+
+	/// Check a single solution page against the voters of the same page, and return its supports.
+	///
+	/// Only the checks that can be done on a single page are performed here, namely the validity
+	/// of all votes. The winner count and the score can only be checked once all pages are known.
+	pub fn feasibility_check_page(
+		solution: SolutionOf<T>,
+		page_voters: &[MinerVoterOf<T>],
+		targets: &[T::AccountId],
+		desired_targets: u32,
+	) -> Result<Supports<T::AccountId>, FeasibilityError> {
+		// Fail early if targets requested by data provider exceed maximum winners supported.
+		ensure!(desired_targets <= T::MaxWinners::get(), FeasibilityError::TooManyDesiredTargets);
+		// Winners are not directly encoded in the solution.
+		ensure!(
+			solution.unique_targets().len() as u32 <= desired_targets,
+			FeasibilityError::WrongWinnerCount
+		);
+
+		// ----- Start building. First, we need some closures.
+		let cache = helpers::generate_voter_cache::<T>(page_voters);
+		let voter_at = helpers::voter_at_fn::<T>(page_voters);
+		let target_at = helpers::target_at_fn::<T>(targets);
+		let voter_index = helpers::voter_index_fn_usize::<T>(&cache);
+
+		// Then convert solution -> assignment. This will fail if any of the indices are gibberish,
+		// namely any of the voters or targets.
+		let assignments = solution
+			.into_assignment(voter_at, target_at)
+			.map_err::<FeasibilityError, _>(Into::into)?;
+
+		// Ensure that assignments is correct.
+		let _ = assignments.iter().try_for_each(|assignment| {
+			// Defensive-only: must exist in the snapshot page.
+			let snapshot_index =
+				voter_index(&assignment.who).ok_or(FeasibilityError::InvalidVoter)?;
+			// Defensive-only: index comes from the snapshot, must exist.
+			let (_voter, _stake, targets) =
+				page_voters.get(snapshot_index).ok_or(FeasibilityError::InvalidVoter)?;
+
+			// Check that all of the targets are valid based on the snapshot.
+			if assignment.distribution.iter().any(|(d, _)| !targets.contains(d)) {
+				return Err(FeasibilityError::InvalidVote)
+			}
+			Ok(())
+		})?;
+
+		// ----- Start building support. First, we need one more closure.
+		let stake_of = helpers::stake_of_fn::<T>(page_voters, &cache);
+
+		// This might fail if the normalization fails. Very unlikely. See `integrity_test`.
+		let staked_assignments = assignment_ratio_to_staked_normalized(assignments, stake_of)
+			.map_err::<FeasibilityError, _>(Into::into)?;
+		Ok(sp_npos_elections::to_supports(&staked_assignments))
+	}
+
+	/// Merge the supports of all pages of a solution, and return the score and the number of
+	/// winners of the whole solution.
+	pub fn score_of<'a>(
+		page_supports: impl IntoIterator<Item = &'a [(T::AccountId, Support<T::AccountId>)]>,
+	) -> (ElectionScore, u32)
+	where
+		T::AccountId: 'a,
+	{
+		let mut backings: BTreeMap<T::AccountId, ExtendedBalance> = BTreeMap::new();
+		for supports in page_supports {
+			for (winner, support) in supports {
+				let total = backings.entry(winner.clone()).or_default();
+				*total = total.saturating_add(support.total);
+			}
+		}
+		let winners = backings.len() as u32;
+		(Self::score_from_backings(backings.into_values()), winners)
+	}
+
+	/// Compute the score of a solution given the total backing of each of its winners.
+	///
+	/// Same as [`sp_npos_elections::EvaluateSupport::evaluate`], without the need of having the
+	/// full supports at hand.
+	pub fn score_from_backings(
+		backings: impl IntoIterator<Item = ExtendedBalance>,
+	) -> ElectionScore {
+		let mut minimal_stake = ExtendedBalance::max_value();
+		let mut sum_stake: ExtendedBalance = 0;
+		let mut sum_stake_squared: ExtendedBalance = 0;
+
+		for total in backings {
+			sum_stake = sum_stake.saturating_add(total);
+			sum_stake_squared = sum_stake_squared.saturating_add(total.saturating_mul(total));
+			minimal_stake = minimal_stake.min(total);
+		}
+
+		ElectionScore { minimal_stake, sum_stake, sum_stake_squared }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{mock::*, ElectionCompute, Phase, SolutionInfo};
+	use frame_election_provider_support::{ElectionProvider, PageIndex};
+	use frame_support::{
+		assert_noop, assert_ok,
+		traits::{Hooks, UnfilteredDispatchable},
+	};
+	use sp_npos_elections::EvaluateSupport;
+
+	fn voter_pages_of(
+		voter_pages: &[Vec<MinerVoterOf<Runtime>>],
+	) -> BTreeMap<AccountId, PageIndex> {
+		voter_pages
+			.iter()
+			.enumerate()
+			.flat_map(|(page, voters)| {
+				voters.iter().map(move |(who, _, _)| (*who, page as PageIndex))
+			})
+			.collect()
+	}
+
+	#[test]
+	fn mined_solution_matches_single_page_election() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to_unsigned();
+			let solution = MultiBlock::mine_solution().unwrap();
+			assert_eq!(solution.solution_pages.len() as u32, Pages::get());
+			assert_eq!(solution.round, MultiBlock::round());
+
+			// the same election, computed over a single page, yields the same score.
+			let voters = (0..Pages::get())
+				.flat_map(|page| MultiBlock::paged_voter_snapshot(page).unwrap())
+				.collect::<Vec<_>>();
+			let targets = MultiBlock::target_snapshot().unwrap();
+			let ElectionResult { assignments, .. } = <Runtime as crate::Config>::Solver::solve(
+				MultiBlock::desired_targets().unwrap() as usize,
+				targets,
+				voters.clone(),
+			)
+			.unwrap();
+			let stake_of = |who: &AccountId| {
+				voters.iter().find(|(x, _, _)| x == who).map(|(_, s, _)| *s).unwrap_or_default()
+			};
+			let staked = assignment_ratio_to_staked_normalized(assignments, stake_of).unwrap();
+			let single_page_score = sp_npos_elections::to_supports(&staked).evaluate();
+			// the per-winner backings can differ slightly due to the accuracy of the solution, but
+			// the total stake is the same.
+			assert_eq!(solution.score.sum_stake, single_page_score.sum_stake);
+		})
+	}
+
+	#[test]
+	fn each_solution_page_only_refers_to_its_own_voters() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to_unsigned();
+			let solution = MultiBlock::mine_solution().unwrap();
+			let voter_pages = (0..Pages::get())
+				.map(|page| MultiBlock::paged_voter_snapshot(page).unwrap())
+				.collect::<Vec<_>>();
+			let pages_of = voter_pages_of(&voter_pages);
+			let targets = MultiBlock::target_snapshot().unwrap();
+
+			for (page, solution_page) in solution.solution_pages.iter().enumerate() {
+				let voter_at = helpers::voter_at_fn::<Runtime>(&voter_pages[page]);
+				let target_at = helpers::target_at_fn::<Runtime>(&targets);
+				let assignments =
+					solution_page.clone().into_assignment(voter_at, target_at).unwrap();
+				assert!(assignments
+					.iter()
+					.all(|a| pages_of.get(&a.who) == Some(&(page as PageIndex))));
+			}
+		})
+	}
+
+	#[test]
+	fn unsigned_pre_dispatch_checks() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to_signed();
+			let mut solution = mine_full_solution();
+			assert_noop!(
+				MultiBlock::unsigned_pre_dispatch_checks(&solution),
+				Error::<Runtime>::PreDispatchEarlySubmission
+			);
+
+			roll_to_unsigned();
+			solution.round += 1;
+			assert_noop!(
+				MultiBlock::unsigned_pre_dispatch_checks(&solution),
+				Error::<Runtime>::PreDispatchWrongRound
+			);
+			solution.round -= 1;
+
+			<crate::MinimumUntrustedScore<Runtime>>::put(ElectionScore {
+				minimal_stake: solution.score.minimal_stake + 1,
+				..Default::default()
+			});
+			assert_noop!(
+				MultiBlock::unsigned_pre_dispatch_checks(&solution),
+				Error::<Runtime>::PreDispatchWeakSubmission
+			);
+			<crate::MinimumUntrustedScore<Runtime>>::kill();
+			assert!(MultiBlock::unsigned_pre_dispatch_checks(&solution).is_ok());
+		})
+	}
+
+	#[test]
+	fn ocw_submits_solution_when_unsigned_phase_opens() {
+		let (mut ext, pool) = ExtBuilder::default().build_offchainify();
+		ext.execute_with(|| {
+			roll_to_unsigned();
+			let now = System::block_number();
+			assert_eq!(MultiBlock::current_phase(), Phase::Unsigned(now));
+
+			// not in the first block of the phase.
+			MultiBlock::offchain_worker(now + 1);
+			assert!(pool.read().transactions.is_empty());
+
+			MultiBlock::offchain_worker(now);
+			let encoded = pool.read().transactions[0].clone();
+			let extrinsic: Extrinsic = codec::Decode::decode(&mut &*encoded).unwrap();
+			let call = extrinsic.call;
+			assert!(matches!(call, RuntimeCall::MultiBlock(Call::submit_unsigned { .. })));
+
+			// dispatching it queues an unsigned solution.
+			assert_ok!(call.dispatch_bypass_filter(RuntimeOrigin::none()));
+			assert!(matches!(
+				MultiBlock::queued_solution(),
+				Some(SolutionInfo { compute: ElectionCompute::Unsigned, .. })
+			));
+			assert!(MultiBlock::elect(MultiBlock::msp()).is_ok());
+		})
+	}
+}
@@ -0,0 +1,419 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The verification of paged solutions.
+//!
+//! Signed solutions are verified incrementally, one page per block, into the `Verifying*`
+//! storage items. Unsigned solutions are verified synchronously, all pages at once. Either way,
+//! a solution that passes all the checks is moved to the `QueuedSolution*` storage items, from
+//! where it is exported.
+
+use crate::{
+	Config, ElectionCompute, Event, FeasibilityError, Miner, PagedRawSolution, PagedSolutionOf,
+	Pallet, QueuedSolutionInfo, QueuedSolutionSupports, SolutionInfo, SolutionOf,
+	SubmissionStorage, VerificationStatus, VerifyingBackings, VerifyingSupports,
+};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_election_provider_support::{BoundedSupportsOf, ElectionProviderBase, PageIndex};
+use frame_support::{ensure, traits::Get, weights::Weight};
+use scale_info::TypeInfo;
+use sp_arithmetic::traits::Saturating;
+use sp_npos_elections::{ElectionScore, ExtendedBalance, Supports};
+use sp_runtime::{Perbill, RuntimeDebug};
+use sp_std::prelude::*;
+
+/// The status of the incremental verification of signed solutions.
+#[derive(
+	Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, RuntimeDebug, Default,
+)]
+pub enum Status {
+	/// Nothing is being verified.
+	#[default]
+	Nothing,
+	/// The leading signed solution is being verified, and the given page is the next one to be
+	/// verified.
+	Ongoing(PageIndex),
+}
+
+/// The backing of a single winner, accumulated over the pages verified so far.
+#[derive(
+	Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, RuntimeDebug, Default,
+)]
+pub struct PartialBackings {
+	/// The total backing stake of the winner.
+	pub total: ExtendedBalance,
+	/// The number of backers of the winner.
+	pub backers: u32,
+}
+
+impl<T: Config> Pallet<T> {
+	/// Verify the next page of the leading signed solution, if any.
+	///
+	/// Once the least significant page is verified, the solution as a whole is checked, and
+	/// queued if all checks pass. The submitter is slashed as soon as any check fails.
+	pub(crate) fn do_verify_signed_page() -> Weight {
+		let round = Self::round();
+		let page = match <VerificationStatus<T>>::get() {
+			Status::Ongoing(page) => page,
+			Status::Nothing => match Self::signed_leader(round) {
+				// there is no point in verifying a claim that cannot be queued. All the remaining
+				// claims are weaker than this one.
+				Some((_, claimed_score)) if Self::ensure_score_improves(claimed_score).is_ok() =>
+					Self::msp(),
+				_ => return T::WeightInfo::on_initialize_nothing(),
+			},
+		};
+
+		let Some((leader, claimed_score)) = Self::signed_leader(round) else {
+			// defensive: the leader can only be removed through verification.
+			log!(warn, "signed leader disappeared during its verification.");
+			Self::abort_verification();
+			return T::WeightInfo::on_initialize_nothing()
+		};
+
+		let outcome = Self::verify_signed_page(round, &leader, page).and_then(|_| {
+			if page == Self::lsp() {
+				Self::finalize_signed_verification(claimed_score).map(Some)
+			} else {
+				Ok(None)
+			}
+		});
+
+		match outcome {
+			Ok(Some(score)) => {
+				log!(info, "signed solution of {:?} verified and queued.", leader);
+				Self::reward_signed(round, &leader);
+				Self::deposit_event(Event::SolutionStored {
+					compute: ElectionCompute::Signed,
+					origin: Some(leader),
+					score,
+				});
+				T::WeightInfo::verify_page().saturating_add(T::WeightInfo::finalize_verification())
+			},
+			Ok(None) => {
+				<VerificationStatus<T>>::put(Status::Ongoing(page.saturating_sub(1)));
+				T::WeightInfo::verify_page()
+			},
+			Err(why) => {
+				log!(warn, "signed solution of {:?} failed at page {}: {:?}", leader, page, why);
+				Self::abort_verification();
+				Self::slash_signed(round, &leader);
+				Self::deposit_event(Event::VerificationFailed { who: leader, page });
+				T::WeightInfo::verify_page().saturating_add(T::WeightInfo::finalize_verification())
+			},
+		}
+	}
+
+	/// Verify a single page of the signed solution of `who`, and accumulate its supports.
+	///
+	/// A page that was not submitted is considered empty.
+	fn verify_signed_page(
+		round: u32,
+		who: &T::AccountId,
+		page: PageIndex,
+	) -> Result<(), FeasibilityError> {
+		let supports = match <SubmissionStorage<T>>::get((round, who, page)) {
+			Some(solution) => Self::feasibility_check_page(solution, page)?,
+			None => Default::default(),
+		};
+		let supports: BoundedSupportsOf<Self> =
+			supports.try_into().map_err(|_| FeasibilityError::BoundedConversionFailed)?;
+
+		for (winner, support) in supports.iter() {
+			<VerifyingBackings<T>>::mutate(winner, |backings| {
+				backings.total = backings.total.saturating_add(support.total);
+				backings.backers.saturating_accrue(support.voters.len() as u32);
+			});
+		}
+		<VerifyingSupports<T>>::insert(page, supports);
+		Ok(())
+	}
+
+	/// Check the accumulated backings of the signed solution being verified, and queue it.
+	fn finalize_signed_verification(
+		claimed_score: ElectionScore,
+	) -> Result<ElectionScore, FeasibilityError> {
+		let desired_targets =
+			Self::desired_targets().ok_or(FeasibilityError::SnapshotUnavailable)?;
+		let backings = <VerifyingBackings<T>>::drain()
+			.map(|(_, backings)| backings.total)
+			.collect::<Vec<_>>();
+
+		ensure!(backings.len() as u32 == desired_targets, FeasibilityError::WrongWinnerCount);
+		let score = Miner::<T::MinerConfig>::score_from_backings(backings);
+		ensure!(score == claimed_score, FeasibilityError::InvalidScore);
+		Self::ensure_score_improves(score)?;
+
+		let _ = <QueuedSolutionSupports<T>>::clear(u32::MAX, None);
+		for (page, supports) in <VerifyingSupports<T>>::drain() {
+			<QueuedSolutionSupports<T>>::insert(page, supports);
+		}
+		<QueuedSolutionInfo<T>>::put(SolutionInfo { compute: ElectionCompute::Signed, score });
+		<VerificationStatus<T>>::kill();
+		Ok(score)
+	}
+
+	/// Verify all pages of `paged_solution` at once, and queue it if all checks pass.
+	pub(crate) fn verify_synchronous(
+		paged_solution: PagedSolutionOf<T>,
+		compute: ElectionCompute,
+	) -> Result<ElectionScore, FeasibilityError> {
+		let PagedRawSolution { solution_pages, score: claimed_score, round } = paged_solution;
+		ensure!(round == Self::round(), FeasibilityError::InvalidRound);
+		ensure!(solution_pages.len() as u32 == T::Pages::get(), FeasibilityError::WrongPageCount);
+		let desired_targets =
+			Self::desired_targets().ok_or(FeasibilityError::SnapshotUnavailable)?;
+
+		let pages = solution_pages
+			.into_iter()
+			.enumerate()
+			.map(|(page, solution)| {
+				Self::feasibility_check_page(solution, page as PageIndex).and_then(|supports| {
+					BoundedSupportsOf::<Self>::try_from(supports)
+						.map_err(|_| FeasibilityError::BoundedConversionFailed)
+				})
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let (score, winners) =
+			Miner::<T::MinerConfig>::score_of(pages.iter().map(|supports| supports.as_slice()));
+		ensure!(winners == desired_targets, FeasibilityError::WrongWinnerCount);
+		ensure!(score == claimed_score, FeasibilityError::InvalidScore);
+		Self::ensure_score_improves(score)?;
+
+		let _ = <QueuedSolutionSupports<T>>::clear(u32::MAX, None);
+		for (page, supports) in pages.into_iter().enumerate() {
+			<QueuedSolutionSupports<T>>::insert(page as PageIndex, supports);
+		}
+		<QueuedSolutionInfo<T>>::put(SolutionInfo { compute, score });
+		Ok(score)
+	}
+
+	/// Check a single solution page against the snapshot of the same page.
+	pub fn feasibility_check_page(
+		solution: SolutionOf<T::MinerConfig>,
+		page: PageIndex,
+	) -> Result<Supports<T::AccountId>, FeasibilityError> {
+		let voters =
+			Self::paged_voter_snapshot(page).ok_or(FeasibilityError::SnapshotUnavailable)?;
+		let targets = Self::target_snapshot().ok_or(FeasibilityError::SnapshotUnavailable)?;
+		let desired_targets =
+			Self::desired_targets().ok_or(FeasibilityError::SnapshotUnavailable)?;
+
+		Miner::<T::MinerConfig>::feasibility_check_page(
+			solution,
+			&voters,
+			&targets,
+			desired_targets,
+		)
+	}
+
+	/// Ensure that `score` can replace the queued solution, if any, and that it passes the
+	/// minimum untrusted score.
+	pub(crate) fn ensure_score_improves(score: ElectionScore) -> Result<(), FeasibilityError> {
+		ensure!(
+			Self::minimum_untrusted_score().map_or(true, |min_score| {
+				score.strict_threshold_better(min_score, Perbill::zero())
+			}),
+			FeasibilityError::UntrustedScoreTooLow
+		);
+		ensure!(
+			Self::queued_solution().map_or(true, |queued| score > queued.score),
+			FeasibilityError::ScoreNotImproved
+		);
+		Ok(())
+	}
+
+	/// Stop verifying the current signed solution, if any, and clear everything that was
+	/// accumulated so far.
+	///
+	/// The submitter is neither rewarded nor slashed.
+	pub(crate) fn abort_verification() {
+		<VerificationStatus<T>>::kill();
+		let _ = <VerifyingSupports<T>>::clear(u32::MAX, None);
+		let _ = <VerifyingBackings<T>>::clear(u32::MAX, None);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{mock::*, signed::SubmissionMetadata, Phase, SubmissionMetadataStorage};
+	use frame_election_provider_support::ElectionProvider;
+	use frame_support::{assert_noop, assert_ok};
+
+	#[test]
+	fn signed_solution_is_verified_page_by_page() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to_signed();
+			let round = MultiBlock::round();
+			let solution = mine_full_solution();
+			submit_signed(99, &solution);
+
+			roll_to_signed_validation();
+			// the most significant page is verified in the first block.
+			assert_eq!(
+				VerificationStatus::<Runtime>::get(),
+				Status::Ongoing(MultiBlock::msp() - 1)
+			);
+			assert!(VerifyingSupports::<Runtime>::contains_key(MultiBlock::msp()));
+			assert!(MultiBlock::queued_solution().is_none());
+
+			roll_to(System::block_number() + Pages::get() as u64 - 1);
+			assert_eq!(VerificationStatus::<Runtime>::get(), Status::Nothing);
+			assert_eq!(
+				MultiBlock::queued_solution(),
+				Some(SolutionInfo { compute: ElectionCompute::Signed, score: solution.score })
+			);
+			assert_eq!(VerifyingSupports::<Runtime>::iter().count(), 0);
+			assert_eq!(VerifyingBackings::<Runtime>::iter().count(), 0);
+			assert!(SubmissionMetadataStorage::<Runtime>::get(round, 99).is_none());
+			assert!(multi_block_events()
+				.contains(&crate::Event::Rewarded { account: 99, value: SignedRewardBase::get() }));
+
+			// the exported pages are the ones of the solution.
+			let exported = (0..Pages::get())
+				.rev()
+				.map(|page| MultiBlock::elect(page).unwrap().into_inner())
+				.collect::<Vec<_>>();
+			assert!(exported.iter().any(|supports| !supports.is_empty()));
+			assert_eq!(MultiBlock::round(), round + 1);
+		})
+	}
+
+	#[test]
+	fn invalid_score_claim_is_slashed() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to_signed();
+			let round = MultiBlock::round();
+			let mut solution = mine_full_solution();
+			solution.score.minimal_stake += 1;
+			submit_signed(99, &solution);
+			let deposit = SubmissionMetadataStorage::<Runtime>::get(round, 99)
+				.map(|SubmissionMetadata { deposit, .. }| deposit)
+				.unwrap();
+
+			roll_to_signed_validation();
+			roll_to(System::block_number() + Pages::get() as u64 - 1);
+
+			assert!(MultiBlock::queued_solution().is_none());
+			assert_eq!(VerificationStatus::<Runtime>::get(), Status::Nothing);
+			assert!(SubmissionMetadataStorage::<Runtime>::get(round, 99).is_none());
+			assert!(multi_block_events()
+				.contains(&crate::Event::Slashed { account: 99, value: deposit }));
+			assert!(multi_block_events()
+				.contains(&crate::Event::VerificationFailed { who: 99, page: MultiBlock::lsp() }));
+		})
+	}
+
+	#[test]
+	fn next_leader_is_verified_after_a_failure() {
+		ExtBuilder::default().signed_validation_phase(8).build_and_execute(|| {
+			roll_to_signed();
+			let good = mine_full_solution();
+			let mut bad = good.clone();
+			bad.score.minimal_stake += 1;
+
+			submit_signed(99, &good);
+			// the bad claim is better, thus verified first.
+			submit_signed(100, &bad);
+
+			roll_to_signed_validation();
+			roll_to(System::block_number() + 2 * Pages::get() as u64);
+
+			assert_eq!(
+				MultiBlock::queued_solution(),
+				Some(SolutionInfo { compute: ElectionCompute::Signed, score: good.score })
+			);
+			assert!(multi_block_events()
+				.contains(&crate::Event::VerificationFailed { who: 100, page: MultiBlock::lsp() }));
+		})
+	}
+
+	#[test]
+	fn verification_is_aborted_when_unsigned_phase_starts() {
+		ExtBuilder::default()
+			.signed_validation_phase(Pages::get() as u64)
+			.build_and_execute(|| {
+				roll_to_signed();
+				let round = MultiBlock::round();
+				let good = mine_full_solution();
+				let mut bad = good.clone();
+				bad.score.minimal_stake += 1;
+				bad.solution_pages[MultiBlock::msp() as usize] =
+					TestNposSolution { votes1: vec![(999, 0)], ..Default::default() };
+
+				submit_signed(99, &good);
+				submit_signed(100, &bad);
+
+				// the bad solution fails in the first block, leaving too little time for the good
+				// one.
+				roll_to_signed_validation();
+				assert!(multi_block_events().contains(&crate::Event::VerificationFailed {
+					who: 100,
+					page: MultiBlock::msp()
+				}));
+				roll_to_unsigned();
+
+				assert!(MultiBlock::queued_solution().is_none());
+				assert_eq!(VerificationStatus::<Runtime>::get(), Status::Nothing);
+				assert_eq!(VerifyingSupports::<Runtime>::iter().count(), 0);
+				assert_eq!(VerifyingBackings::<Runtime>::iter().count(), 0);
+				// not slashed, the submission is still there.
+				assert!(SubmissionMetadataStorage::<Runtime>::get(round, 99).is_some());
+			})
+	}
+
+	#[test]
+	fn unsigned_solution_is_verified_synchronously() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to_unsigned();
+			let solution = mine_full_solution();
+
+			// wrong page count.
+			let mut bad = solution.clone();
+			bad.solution_pages.pop();
+			assert_noop!(
+				MultiBlock::submit_unsigned(RuntimeOrigin::none(), Box::new(bad)),
+				crate::Error::<Runtime>::PreDispatchWrongPageCount
+			);
+
+			// wrong score.
+			let mut bad = solution.clone();
+			bad.score.sum_stake += 1;
+			assert_noop!(
+				MultiBlock::submit_unsigned(RuntimeOrigin::none(), Box::new(bad)),
+				crate::Error::<Runtime>::UnsignedSolutionInvalid
+			);
+
+			assert_ok!(MultiBlock::submit_unsigned(
+				RuntimeOrigin::none(),
+				Box::new(solution.clone())
+			));
+			assert_eq!(
+				MultiBlock::queued_solution(),
+				Some(SolutionInfo { compute: ElectionCompute::Unsigned, score: solution.score })
+			);
+
+			// the same solution cannot be submitted twice.
+			assert_noop!(
+				MultiBlock::submit_unsigned(RuntimeOrigin::none(), Box::new(solution)),
+				crate::Error::<Runtime>::PreDispatchWeakSubmission
+			);
+		})
+	}
+}
@@ -0,0 +1,676 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Placeholder weights for `pallet_election_provider_multi_block`.
+//!
+//! THESE WEIGHTS WERE NOT GENERATED BY THE BENCHMARK CLI. The values are estimates derived from
+//! the storage accesses of each call and comparable calls of other pallets.
+//!
+//! They must be replaced by the output of the `pallet_election_provider_multi_block` benchmarks,
+//! e.g. generated with `frame-omni-bencher`, before the pallet is used on a production chain.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_election_provider_multi_block`.
+pub trait WeightInfo {
+	fn on_initialize_nothing() -> Weight;
+	fn on_initialize_into_snapshot_msp() -> Weight;
+	fn on_initialize_into_snapshot_rest() -> Weight;
+	fn on_initialize_into_signed() -> Weight;
+	fn on_initialize_into_signed_validation() -> Weight;
+	fn on_initialize_into_unsigned() -> Weight;
+	fn verify_page() -> Weight;
+	fn finalize_verification() -> Weight;
+	fn export_non_terminal() -> Weight;
+	fn export_terminal() -> Weight;
+	fn register() -> Weight;
+	fn submit_page() -> Weight;
+	fn bail() -> Weight;
+	fn clear_old_round_data(p: u32, ) -> Weight;
+	fn submit_unsigned() -> Weight;
+	fn set_minimum_untrusted_score() -> Weight;
+	fn set_emergency_solution() -> Weight;
+}
+
+/// Weights for `pallet_election_provider_multi_block` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:0)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::CurrentEra` (r:1 w:0)
+	/// Proof: `Staking::CurrentEra` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::ForceEra` (r:1 w:0)
+	/// Proof: `Staking::ForceEra` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn on_initialize_nothing() -> Weight {
+		Weight::from_parts(10_403_000, 1633)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:1)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::VoterSnapshotStatus` (r:1 w:1)
+	/// Proof: `Staking::VoterSnapshotStatus` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `VoterList::ListBags` (r:200 w:0)
+	/// Proof: `VoterList::ListBags` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `VoterList::ListNodes` (r:1000 w:0)
+	/// Proof: `VoterList::ListNodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::Bonded` (r:1000 w:0)
+	/// Proof: `Staking::Bonded` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::Ledger` (r:1000 w:0)
+	/// Proof: `Staking::Ledger` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::Nominators` (r:1000 w:0)
+	/// Proof: `Staking::Nominators` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::Validators` (r:1000 w:0)
+	/// Proof: `Staking::Validators` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::ValidatorCount` (r:1 w:0)
+	/// Proof: `Staking::ValidatorCount` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::TargetSnapshot` (r:0 w:1)
+	/// Proof: `MultiBlock::TargetSnapshot` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::DesiredTargets` (r:0 w:1)
+	/// Proof: `MultiBlock::DesiredTargets` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::PagedVoterSnapshot` (r:0 w:1)
+	/// Proof: `MultiBlock::PagedVoterSnapshot` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn on_initialize_into_snapshot_msp() -> Weight {
+		Weight::from_parts(18_421_370_000, 2525675)
+			.saturating_add(T::DbWeight::get().reads(5206_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:1)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::VoterSnapshotStatus` (r:1 w:1)
+	/// Proof: `Staking::VoterSnapshotStatus` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `VoterList::ListBags` (r:200 w:0)
+	/// Proof: `VoterList::ListBags` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `VoterList::ListNodes` (r:1000 w:0)
+	/// Proof: `VoterList::ListNodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::Bonded` (r:1000 w:0)
+	/// Proof: `Staking::Bonded` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::Ledger` (r:1000 w:0)
+	/// Proof: `Staking::Ledger` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::Nominators` (r:1000 w:0)
+	/// Proof: `Staking::Nominators` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::PagedVoterSnapshot` (r:0 w:1)
+	/// Proof: `MultiBlock::PagedVoterSnapshot` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn on_initialize_into_snapshot_rest() -> Weight {
+		Weight::from_parts(13_012_881_000, 2501432)
+			.saturating_add(T::DbWeight::get().reads(4205_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:1)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::CurrentEra` (r:1 w:0)
+	/// Proof: `Staking::CurrentEra` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::ForceEra` (r:1 w:0)
+	/// Proof: `Staking::ForceEra` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn on_initialize_into_signed() -> Weight {
+		Weight::from_parts(14_862_000, 1633)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:1)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerificationStatus` (r:1 w:0)
+	/// Proof: `MultiBlock::VerificationStatus` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SortedScores` (r:1 w:0)
+	/// Proof: `MultiBlock::SortedScores` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::CurrentEra` (r:1 w:0)
+	/// Proof: `Staking::CurrentEra` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::ForceEra` (r:1 w:0)
+	/// Proof: `Staking::ForceEra` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn on_initialize_into_signed_validation() -> Weight {
+		Weight::from_parts(18_439_000, 3711)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:1)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerificationStatus` (r:0 w:1)
+	/// Proof: `MultiBlock::VerificationStatus` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerifyingSupports` (r:0 w:32)
+	/// Proof: `MultiBlock::VerifyingSupports` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerifyingBackings` (r:0 w:1000)
+	/// Proof: `MultiBlock::VerifyingBackings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::CurrentEra` (r:1 w:0)
+	/// Proof: `Staking::CurrentEra` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::ForceEra` (r:1 w:0)
+	/// Proof: `Staking::ForceEra` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn on_initialize_into_unsigned() -> Weight {
+		Weight::from_parts(1_182_441_000, 1633)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1034_u64))
+	}
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerificationStatus` (r:1 w:1)
+	/// Proof: `MultiBlock::VerificationStatus` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SortedScores` (r:1 w:0)
+	/// Proof: `MultiBlock::SortedScores` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionStorage` (r:1 w:0)
+	/// Proof: `MultiBlock::SubmissionStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::PagedVoterSnapshot` (r:1 w:0)
+	/// Proof: `MultiBlock::PagedVoterSnapshot` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::TargetSnapshot` (r:1 w:0)
+	/// Proof: `MultiBlock::TargetSnapshot` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::DesiredTargets` (r:1 w:0)
+	/// Proof: `MultiBlock::DesiredTargets` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerifyingBackings` (r:1000 w:1000)
+	/// Proof: `MultiBlock::VerifyingBackings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerifyingSupports` (r:0 w:1)
+	/// Proof: `MultiBlock::VerifyingSupports` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn verify_page() -> Weight {
+		Weight::from_parts(1_679_907_000, 2481009)
+			.saturating_add(T::DbWeight::get().reads(1007_u64))
+			.saturating_add(T::DbWeight::get().writes(1002_u64))
+	}
+	/// Storage: `MultiBlock::DesiredTargets` (r:1 w:0)
+	/// Proof: `MultiBlock::DesiredTargets` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerifyingBackings` (r:1000 w:1000)
+	/// Proof: `MultiBlock::VerifyingBackings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::MinimumUntrustedScore` (r:1 w:0)
+	/// Proof: `MultiBlock::MinimumUntrustedScore` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionInfo` (r:1 w:1)
+	/// Proof: `MultiBlock::QueuedSolutionInfo` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionSupports` (r:32 w:64)
+	/// Proof: `MultiBlock::QueuedSolutionSupports` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerifyingSupports` (r:32 w:32)
+	/// Proof: `MultiBlock::VerifyingSupports` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SortedScores` (r:1 w:1)
+	/// Proof: `MultiBlock::SortedScores` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionMetadataStorage` (r:1 w:1)
+	/// Proof: `MultiBlock::SubmissionMetadataStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionStorage` (r:0 w:32)
+	/// Proof: `MultiBlock::SubmissionStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn finalize_verification() -> Weight {
+		Weight::from_parts(1_302_118_000, 2484553)
+			.saturating_add(T::DbWeight::get().reads(1069_u64))
+			.saturating_add(T::DbWeight::get().writes(1164_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:1)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionInfo` (r:1 w:0)
+	/// Proof: `MultiBlock::QueuedSolutionInfo` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionSupports` (r:1 w:1)
+	/// Proof: `MultiBlock::QueuedSolutionSupports` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerificationStatus` (r:0 w:1)
+	/// Proof: `MultiBlock::VerificationStatus` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn export_non_terminal() -> Weight {
+		Weight::from_parts(152_906_000, 116438)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:1)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:1)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionInfo` (r:1 w:1)
+	/// Proof: `MultiBlock::QueuedSolutionInfo` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionSupports` (r:32 w:32)
+	/// Proof: `MultiBlock::QueuedSolutionSupports` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::PagedVoterSnapshot` (r:0 w:32)
+	/// Proof: `MultiBlock::PagedVoterSnapshot` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::TargetSnapshot` (r:0 w:1)
+	/// Proof: `MultiBlock::TargetSnapshot` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::DesiredTargets` (r:0 w:1)
+	/// Proof: `MultiBlock::DesiredTargets` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SortedScores` (r:0 w:1)
+	/// Proof: `MultiBlock::SortedScores` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerificationStatus` (r:0 w:1)
+	/// Proof: `MultiBlock::VerificationStatus` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn export_terminal() -> Weight {
+		Weight::from_parts(309_874_000, 116438)
+			.saturating_add(T::DbWeight::get().reads(35_u64))
+			.saturating_add(T::DbWeight::get().writes(71_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:0)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionMetadataStorage` (r:2 w:2)
+	/// Proof: `MultiBlock::SubmissionMetadataStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::MinimumUntrustedScore` (r:1 w:0)
+	/// Proof: `MultiBlock::MinimumUntrustedScore` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SortedScores` (r:1 w:1)
+	/// Proof: `MultiBlock::SortedScores` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionStorage` (r:0 w:32)
+	/// Proof: `MultiBlock::SubmissionStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Balances::Holds` (r:2 w:2)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn register() -> Weight {
+		Weight::from_parts(106_170_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(37_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:0)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionMetadataStorage` (r:1 w:1)
+	/// Proof: `MultiBlock::SubmissionMetadataStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionStorage` (r:0 w:1)
+	/// Proof: `MultiBlock::SubmissionStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn submit_page() -> Weight {
+		Weight::from_parts(70_925_000, 4764)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:0)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionMetadataStorage` (r:1 w:1)
+	/// Proof: `MultiBlock::SubmissionMetadataStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionStorage` (r:0 w:32)
+	/// Proof: `MultiBlock::SubmissionStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SortedScores` (r:1 w:1)
+	/// Proof: `MultiBlock::SortedScores` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn bail() -> Weight {
+		Weight::from_parts(97_305_000, 5102)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(36_u64))
+	}
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionMetadataStorage` (r:1 w:1)
+	/// Proof: `MultiBlock::SubmissionMetadataStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionStorage` (r:0 w:32)
+	/// Proof: `MultiBlock::SubmissionStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `p` is `[1, 32]`.
+	fn clear_old_round_data(p: u32, ) -> Weight {
+		Weight::from_parts(54_210_318, 4764)
+			// Standard Error: 9_241
+			.saturating_add(Weight::from_parts(1_224_507, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(p.into())))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:0)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionInfo` (r:1 w:1)
+	/// Proof: `MultiBlock::QueuedSolutionInfo` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::MinimumUntrustedScore` (r:1 w:0)
+	/// Proof: `MultiBlock::MinimumUntrustedScore` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::DesiredTargets` (r:1 w:0)
+	/// Proof: `MultiBlock::DesiredTargets` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::PagedVoterSnapshot` (r:32 w:0)
+	/// Proof: `MultiBlock::PagedVoterSnapshot` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::TargetSnapshot` (r:1 w:0)
+	/// Proof: `MultiBlock::TargetSnapshot` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionSupports` (r:0 w:64)
+	/// Proof: `MultiBlock::QueuedSolutionSupports` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn submit_unsigned() -> Weight {
+		Weight::from_parts(53_017_440_000, 79389624)
+			.saturating_add(T::DbWeight::get().reads(37_u64))
+			.saturating_add(T::DbWeight::get().writes(65_u64))
+	}
+	/// Storage: `MultiBlock::MinimumUntrustedScore` (r:0 w:1)
+	/// Proof: `MultiBlock::MinimumUntrustedScore` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn set_minimum_untrusted_score() -> Weight {
+		Weight::from_parts(4_002_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:0)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionSupports` (r:0 w:33)
+	/// Proof: `MultiBlock::QueuedSolutionSupports` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionInfo` (r:0 w:1)
+	/// Proof: `MultiBlock::QueuedSolutionInfo` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn set_emergency_solution() -> Weight {
+		Weight::from_parts(417_600_000, 1633)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(34_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:0)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::CurrentEra` (r:1 w:0)
+	/// Proof: `Staking::CurrentEra` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::ForceEra` (r:1 w:0)
+	/// Proof: `Staking::ForceEra` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn on_initialize_nothing() -> Weight {
+		Weight::from_parts(10_403_000, 1633)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:1)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::VoterSnapshotStatus` (r:1 w:1)
+	/// Proof: `Staking::VoterSnapshotStatus` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `VoterList::ListBags` (r:200 w:0)
+	/// Proof: `VoterList::ListBags` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `VoterList::ListNodes` (r:1000 w:0)
+	/// Proof: `VoterList::ListNodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::Bonded` (r:1000 w:0)
+	/// Proof: `Staking::Bonded` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::Ledger` (r:1000 w:0)
+	/// Proof: `Staking::Ledger` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::Nominators` (r:1000 w:0)
+	/// Proof: `Staking::Nominators` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::Validators` (r:1000 w:0)
+	/// Proof: `Staking::Validators` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::ValidatorCount` (r:1 w:0)
+	/// Proof: `Staking::ValidatorCount` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::TargetSnapshot` (r:0 w:1)
+	/// Proof: `MultiBlock::TargetSnapshot` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::DesiredTargets` (r:0 w:1)
+	/// Proof: `MultiBlock::DesiredTargets` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::PagedVoterSnapshot` (r:0 w:1)
+	/// Proof: `MultiBlock::PagedVoterSnapshot` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn on_initialize_into_snapshot_msp() -> Weight {
+		Weight::from_parts(18_421_370_000, 2525675)
+			.saturating_add(RocksDbWeight::get().reads(5206_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:1)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::VoterSnapshotStatus` (r:1 w:1)
+	/// Proof: `Staking::VoterSnapshotStatus` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `VoterList::ListBags` (r:200 w:0)
+	/// Proof: `VoterList::ListBags` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `VoterList::ListNodes` (r:1000 w:0)
+	/// Proof: `VoterList::ListNodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::Bonded` (r:1000 w:0)
+	/// Proof: `Staking::Bonded` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::Ledger` (r:1000 w:0)
+	/// Proof: `Staking::Ledger` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::Nominators` (r:1000 w:0)
+	/// Proof: `Staking::Nominators` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::PagedVoterSnapshot` (r:0 w:1)
+	/// Proof: `MultiBlock::PagedVoterSnapshot` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn on_initialize_into_snapshot_rest() -> Weight {
+		Weight::from_parts(13_012_881_000, 2501432)
+			.saturating_add(RocksDbWeight::get().reads(4205_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:1)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::CurrentEra` (r:1 w:0)
+	/// Proof: `Staking::CurrentEra` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::ForceEra` (r:1 w:0)
+	/// Proof: `Staking::ForceEra` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn on_initialize_into_signed() -> Weight {
+		Weight::from_parts(14_862_000, 1633)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:1)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerificationStatus` (r:1 w:0)
+	/// Proof: `MultiBlock::VerificationStatus` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SortedScores` (r:1 w:0)
+	/// Proof: `MultiBlock::SortedScores` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::CurrentEra` (r:1 w:0)
+	/// Proof: `Staking::CurrentEra` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::ForceEra` (r:1 w:0)
+	/// Proof: `Staking::ForceEra` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn on_initialize_into_signed_validation() -> Weight {
+		Weight::from_parts(18_439_000, 3711)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:1)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerificationStatus` (r:0 w:1)
+	/// Proof: `MultiBlock::VerificationStatus` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerifyingSupports` (r:0 w:32)
+	/// Proof: `MultiBlock::VerifyingSupports` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerifyingBackings` (r:0 w:1000)
+	/// Proof: `MultiBlock::VerifyingBackings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::CurrentEra` (r:1 w:0)
+	/// Proof: `Staking::CurrentEra` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Staking::ForceEra` (r:1 w:0)
+	/// Proof: `Staking::ForceEra` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn on_initialize_into_unsigned() -> Weight {
+		Weight::from_parts(1_182_441_000, 1633)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1034_u64))
+	}
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerificationStatus` (r:1 w:1)
+	/// Proof: `MultiBlock::VerificationStatus` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SortedScores` (r:1 w:0)
+	/// Proof: `MultiBlock::SortedScores` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionStorage` (r:1 w:0)
+	/// Proof: `MultiBlock::SubmissionStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::PagedVoterSnapshot` (r:1 w:0)
+	/// Proof: `MultiBlock::PagedVoterSnapshot` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::TargetSnapshot` (r:1 w:0)
+	/// Proof: `MultiBlock::TargetSnapshot` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::DesiredTargets` (r:1 w:0)
+	/// Proof: `MultiBlock::DesiredTargets` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerifyingBackings` (r:1000 w:1000)
+	/// Proof: `MultiBlock::VerifyingBackings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerifyingSupports` (r:0 w:1)
+	/// Proof: `MultiBlock::VerifyingSupports` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn verify_page() -> Weight {
+		Weight::from_parts(1_679_907_000, 2481009)
+			.saturating_add(RocksDbWeight::get().reads(1007_u64))
+			.saturating_add(RocksDbWeight::get().writes(1002_u64))
+	}
+	/// Storage: `MultiBlock::DesiredTargets` (r:1 w:0)
+	/// Proof: `MultiBlock::DesiredTargets` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerifyingBackings` (r:1000 w:1000)
+	/// Proof: `MultiBlock::VerifyingBackings` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::MinimumUntrustedScore` (r:1 w:0)
+	/// Proof: `MultiBlock::MinimumUntrustedScore` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionInfo` (r:1 w:1)
+	/// Proof: `MultiBlock::QueuedSolutionInfo` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionSupports` (r:32 w:64)
+	/// Proof: `MultiBlock::QueuedSolutionSupports` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerifyingSupports` (r:32 w:32)
+	/// Proof: `MultiBlock::VerifyingSupports` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SortedScores` (r:1 w:1)
+	/// Proof: `MultiBlock::SortedScores` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionMetadataStorage` (r:1 w:1)
+	/// Proof: `MultiBlock::SubmissionMetadataStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionStorage` (r:0 w:32)
+	/// Proof: `MultiBlock::SubmissionStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn finalize_verification() -> Weight {
+		Weight::from_parts(1_302_118_000, 2484553)
+			.saturating_add(RocksDbWeight::get().reads(1069_u64))
+			.saturating_add(RocksDbWeight::get().writes(1164_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:1)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionInfo` (r:1 w:0)
+	/// Proof: `MultiBlock::QueuedSolutionInfo` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionSupports` (r:1 w:1)
+	/// Proof: `MultiBlock::QueuedSolutionSupports` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerificationStatus` (r:0 w:1)
+	/// Proof: `MultiBlock::VerificationStatus` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn export_non_terminal() -> Weight {
+		Weight::from_parts(152_906_000, 116438)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:1)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:1)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionInfo` (r:1 w:1)
+	/// Proof: `MultiBlock::QueuedSolutionInfo` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionSupports` (r:32 w:32)
+	/// Proof: `MultiBlock::QueuedSolutionSupports` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::PagedVoterSnapshot` (r:0 w:32)
+	/// Proof: `MultiBlock::PagedVoterSnapshot` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::TargetSnapshot` (r:0 w:1)
+	/// Proof: `MultiBlock::TargetSnapshot` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::DesiredTargets` (r:0 w:1)
+	/// Proof: `MultiBlock::DesiredTargets` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SortedScores` (r:0 w:1)
+	/// Proof: `MultiBlock::SortedScores` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::VerificationStatus` (r:0 w:1)
+	/// Proof: `MultiBlock::VerificationStatus` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn export_terminal() -> Weight {
+		Weight::from_parts(309_874_000, 116438)
+			.saturating_add(RocksDbWeight::get().reads(35_u64))
+			.saturating_add(RocksDbWeight::get().writes(71_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:0)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionMetadataStorage` (r:2 w:2)
+	/// Proof: `MultiBlock::SubmissionMetadataStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::MinimumUntrustedScore` (r:1 w:0)
+	/// Proof: `MultiBlock::MinimumUntrustedScore` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SortedScores` (r:1 w:1)
+	/// Proof: `MultiBlock::SortedScores` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionStorage` (r:0 w:32)
+	/// Proof: `MultiBlock::SubmissionStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Balances::Holds` (r:2 w:2)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn register() -> Weight {
+		Weight::from_parts(106_170_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(37_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:0)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionMetadataStorage` (r:1 w:1)
+	/// Proof: `MultiBlock::SubmissionMetadataStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionStorage` (r:0 w:1)
+	/// Proof: `MultiBlock::SubmissionStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn submit_page() -> Weight {
+		Weight::from_parts(70_925_000, 4764)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:0)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionMetadataStorage` (r:1 w:1)
+	/// Proof: `MultiBlock::SubmissionMetadataStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionStorage` (r:0 w:32)
+	/// Proof: `MultiBlock::SubmissionStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SortedScores` (r:1 w:1)
+	/// Proof: `MultiBlock::SortedScores` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn bail() -> Weight {
+		Weight::from_parts(97_305_000, 5102)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(36_u64))
+	}
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionMetadataStorage` (r:1 w:1)
+	/// Proof: `MultiBlock::SubmissionMetadataStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::SubmissionStorage` (r:0 w:32)
+	/// Proof: `MultiBlock::SubmissionStorage` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `p` is `[1, 32]`.
+	fn clear_old_round_data(p: u32, ) -> Weight {
+		Weight::from_parts(54_210_318, 4764)
+			// Standard Error: 9_241
+			.saturating_add(Weight::from_parts(1_224_507, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(p.into())))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:0)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::Round` (r:1 w:0)
+	/// Proof: `MultiBlock::Round` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionInfo` (r:1 w:1)
+	/// Proof: `MultiBlock::QueuedSolutionInfo` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::MinimumUntrustedScore` (r:1 w:0)
+	/// Proof: `MultiBlock::MinimumUntrustedScore` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::DesiredTargets` (r:1 w:0)
+	/// Proof: `MultiBlock::DesiredTargets` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::PagedVoterSnapshot` (r:32 w:0)
+	/// Proof: `MultiBlock::PagedVoterSnapshot` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::TargetSnapshot` (r:1 w:0)
+	/// Proof: `MultiBlock::TargetSnapshot` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionSupports` (r:0 w:64)
+	/// Proof: `MultiBlock::QueuedSolutionSupports` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn submit_unsigned() -> Weight {
+		Weight::from_parts(53_017_440_000, 79389624)
+			.saturating_add(RocksDbWeight::get().reads(37_u64))
+			.saturating_add(RocksDbWeight::get().writes(65_u64))
+	}
+	/// Storage: `MultiBlock::MinimumUntrustedScore` (r:0 w:1)
+	/// Proof: `MultiBlock::MinimumUntrustedScore` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn set_minimum_untrusted_score() -> Weight {
+		Weight::from_parts(4_002_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `MultiBlock::CurrentPhase` (r:1 w:0)
+	/// Proof: `MultiBlock::CurrentPhase` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionSupports` (r:0 w:33)
+	/// Proof: `MultiBlock::QueuedSolutionSupports` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `MultiBlock::QueuedSolutionInfo` (r:0 w:1)
+	/// Proof: `MultiBlock::QueuedSolutionInfo` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn set_emergency_solution() -> Weight {
+		Weight::from_parts(417_600_000, 1633)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(34_u64))
+	}
+}
@@ -272,8 +272,8 @@ frame_benchmarking::benchmarks! {
 		// we don't directly need the data-provider to be populated, but it is just easy to use it.
 		set_up_data_provider::<T>(v, t);
 		// default bounds are unbounded.
-		let targets = T::DataProvider::electable_targets(DataProviderBounds::default())?;
-		let voters = T::DataProvider::electing_voters(DataProviderBounds::default())?;
+		let targets = T::DataProvider::electable_targets(DataProviderBounds::default(), 0)?;
+		let voters = T::DataProvider::electing_voters(DataProviderBounds::default(), 0)?;
 		let desired_targets = T::DataProvider::desired_targets()?;
 		assert!(<MultiPhase<T>>::snapshot().is_none());
 	}: {
@@ -311,7 +311,7 @@ frame_benchmarking::benchmarks! {
 		assert!(<Snapshot<T>>::get().is_some());
 		assert!(<SnapshotMetadata<T>>::get().is_some());
 	}: {
-		assert_ok!(<MultiPhase<T> as ElectionProvider>::elect());
+		assert_ok!(<MultiPhase<T> as ElectionProvider>::elect(0));
 	} verify {
 		assert!(<MultiPhase<T>>::queued_solution().is_none());
 		assert!(<DesiredTargets<T>>::get().is_none());
@@ -56,8 +56,18 @@
 //!
 //! To accommodate both type of elections in one trait, the traits lean toward **stateful
 //! election**, as it is more general than the stateless. This is why [`ElectionProvider::elect`]
-//! has no parameters. All value and type parameter must be provided by the [`ElectionDataProvider`]
-//! trait, even if the election happens immediately.
+//! only receives the page to return. All value and type parameter must be provided by the
+//! [`ElectionDataProvider`] trait, even if the election happens immediately.
+//!
+//! ## Multi-page Elections
+//!
+//! Both the data provider and the election provider can work in pages. An election provider
+//! declares the number of pages it works with in [`ElectionProviderBase::Pages`]. The data provider
+//! is then queried for `Pages` pages of voters, starting from the most significant page
+//! ([`ElectionProviderBase::msp`]) down to the least significant one
+//! ([`ElectionProviderBase::lsp`]), and the election result is exported in the same number of
+//! pages via [`ElectionProvider::elect`]. A single page election provider has `Pages = 1`, in which
+//! case `msp` and `lsp` are both `0`.
 //!
 //! ## Election Data
 //!
@@ -109,12 +119,14 @@
 //!         fn desired_targets() -> data_provider::Result<u32> {
 //!             Ok(1)
 //!         }
-//!         fn electing_voters(bounds: DataProviderBounds)
+//!         fn electing_voters(bounds: DataProviderBounds, _page: PageIndex)
 //!           -> data_provider::Result<Vec<VoterOf<Self>>>
 //!         {
 //!             Ok(Default::default())
 //!         }
-//!         fn electable_targets(bounds: DataProviderBounds) -> data_provider::Result<Vec<AccountId>> {
+//!         fn electable_targets(bounds: DataProviderBounds, _page: PageIndex)
+//!           -> data_provider::Result<Vec<AccountId>>
+//!         {
 //!             Ok(vec![10, 20, 30])
 //!         }
 //!         fn next_election_prediction(now: BlockNumber) -> BlockNumber {
@@ -139,13 +151,13 @@
 //!         type Error = &'static str;
 //!         type DataProvider = T::DataProvider;
 //!         type MaxWinners = ConstU32<{ u32::MAX }>;
-//!
+//!         type Pages = ConstU32<1>;
 //!     }
 //!
 //!     impl<T: Config> ElectionProvider for GenericElectionProvider<T> {
 //!         fn ongoing() -> bool { false }
-//!         fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error> {
-//!             Self::DataProvider::electable_targets(DataProviderBounds::default())
+//!         fn elect(page: PageIndex) -> Result<BoundedSupportsOf<Self>, Self::Error> {
+//!             Self::DataProvider::electable_targets(DataProviderBounds::default(), page)
 //!                 .map_err(|_| "failed to elect")
 //!                 .map(|t| bounded_vec![(t[0], Support::default())])
 //!         }
@@ -273,6 +285,9 @@ pub type IndexAssignmentOf<C> = IndexAssignment<
 	<C as NposSolution>::Accuracy,
 >;
 
+/// The index of a page of an election, be it a page of the snapshot or of the solution.
+pub type PageIndex = u32;
+
 /// Types that are used by the data provider trait.
 pub mod data_provider {
 	/// Alias for the result type of the election data provider.
@@ -295,16 +310,28 @@ pub trait ElectionDataProvider {
 	///
 	/// This should be implemented as a self-weighing function. The implementor should register its
 	/// appropriate weight at the end of execution with the system pallet directly.
-	fn electable_targets(bounds: DataProviderBounds)
-		-> data_provider::Result<Vec<Self::AccountId>>;
-
-	/// All the voters that participate in the election, thus "electing".
+	///
+	/// Targets are expected to fit in a single page; an implementation is free to ignore `page`.
+	fn electable_targets(
+		bounds: DataProviderBounds,
+		page: PageIndex,
+	) -> data_provider::Result<Vec<Self::AccountId>>;
+
+	/// All the voters that participate in the election associated with page `page`, thus
+	/// "electing".
 	///
 	/// Note that if a notion of self-vote exists, it should be represented here.
 	///
+	/// Pages are requested from the most significant to the least significant one, and the
+	/// implementor should return the next chunk of voters on every call, up to `bounds`. Once the
+	/// least significant page (`0`) has been returned, the next call starts a new snapshot.
+	///
 	/// This should be implemented as a self-weighing function. The implementor should register its
 	/// appropriate weight at the end of execution with the system pallet directly.
-	fn electing_voters(bounds: DataProviderBounds) -> data_provider::Result<Vec<VoterOf<Self>>>;
+	fn electing_voters(
+		bounds: DataProviderBounds,
+		page: PageIndex,
+	) -> data_provider::Result<Vec<VoterOf<Self>>>;
 
 	/// The number of targets to elect.
 	///
@@ -387,6 +414,23 @@ pub trait ElectionProviderBase {
 		BlockNumber = Self::BlockNumber,
 	>;
 
+	/// The number of pages that this election provider works with.
+	///
+	/// Must be at least one.
+	type Pages: Get<PageIndex>;
+
+	/// The most significant page, i.e. the first one that is fetched from the data provider and
+	/// exported by the election provider.
+	fn msp() -> PageIndex {
+		Self::Pages::get().saturating_sub(1)
+	}
+
+	/// The least significant page, i.e. the last one that is fetched from the data provider and
+	/// exported by the election provider.
+	fn lsp() -> PageIndex {
+		Zero::zero()
+	}
+
 	/// checked call to `Self::DataProvider::desired_targets()` ensuring the value never exceeds
 	/// [`Self::MaxWinners`].
 	fn desired_targets_checked() -> data_provider::Result<u32> {
@@ -405,16 +449,31 @@ pub trait ElectionProviderBase {
 /// It must always use [`ElectionProviderBase::DataProvider`] to fetch the data it needs.
 ///
 /// This election provider that could function asynchronously. This implies that this election might
-/// needs data ahead of time (ergo, receives no arguments to `elect`), and might be `ongoing` at
-/// times.
+/// needs data ahead of time (ergo, receives no arguments to `elect` other than the page), and might
+/// be `ongoing` at times.
 pub trait ElectionProvider: ElectionProviderBase {
 	/// Indicate if this election provider is currently ongoing an asynchronous election or not.
 	fn ongoing() -> bool;
 
-	/// Performs the election. This should be implemented as a self-weighing function. The
-	/// implementor should register its appropriate weight at the end of execution with the
-	/// system pallet directly.
-	fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error>;
+	/// Indicate if the input of the ongoing election has been fully gathered from the data
+	/// provider, such that its result can be requested with [`Self::elect`].
+	///
+	/// A consumer of a multi-page election provider should only start requesting pages once this
+	/// is the case. Election providers that gather their input when the result is requested are
+	/// always ready.
+	fn ready() -> bool {
+		true
+	}
+
+	/// Performs the election and returns the supports of page `page`. This should be implemented
+	/// as a self-weighing function. The implementor should register its appropriate weight at the
+	/// end of execution with the system pallet directly.
+	///
+	/// A multi-page election provider expects its pages to be requested from
+	/// [`ElectionProviderBase::msp`] down to [`ElectionProviderBase::lsp`], each page being
+	/// requested exactly once per election. Requesting the least significant page concludes the
+	/// election.
+	fn elect(page: PageIndex) -> Result<BoundedSupportsOf<Self>, Self::Error>;
 }
 
 /// A (almost) marker trait that signifies an election provider as working synchronously. i.e. being
@@ -444,6 +503,7 @@ where
 	type Error = &'static str;
 	type MaxWinners = MaxWinners;
 	type DataProvider = DataProvider;
+	type Pages = frame_support::traits::ConstU32<1>;
 }
 
 impl<AccountId, BlockNumber, DataProvider, MaxWinners> ElectionProvider
@@ -456,7 +516,7 @@ where
 		false
 	}
 
-	fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error> {
+	fn elect(_: PageIndex) -> Result<BoundedSupportsOf<Self>, Self::Error> {
 		Err("`NoElection` cannot do anything.")
 	}
 }
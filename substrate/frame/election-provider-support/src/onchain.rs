@@ -22,12 +22,16 @@
 use crate::{
 	bounds::{DataProviderBounds, ElectionBounds, ElectionBoundsBuilder},
 	BoundedSupportsOf, Debug, ElectionDataProvider, ElectionProvider, ElectionProviderBase,
-	InstantElectionProvider, NposSolver, WeightInfo,
+	InstantElectionProvider, NposSolver, PageIndex, WeightInfo,
+};
+use frame_support::{
+	dispatch::DispatchClass,
+	traits::{ConstU32, Get},
 };
-use frame_support::{dispatch::DispatchClass, traits::Get};
 use sp_npos_elections::{
 	assignment_ratio_to_staked_normalized, to_supports, BoundedSupports, ElectionResult, VoteWeight,
 };
+use sp_runtime::traits::Zero;
 use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData, prelude::*};
 
 /// Errors of the on-chain election.
@@ -99,8 +103,12 @@ pub type OnChainBoundedSupportsOf<E> = BoundedSupports<
 fn elect_with_input_bounds<T: Config>(
 	bounds: ElectionBounds,
 ) -> Result<OnChainBoundedSupportsOf<T>, Error> {
-	let (voters, targets) = T::DataProvider::electing_voters(bounds.voters)
-		.and_then(|voters| Ok((voters, T::DataProvider::electable_targets(bounds.targets)?)))
+	// the on-chain election is single paged, and thus only ever asks for the least significant
+	// page of the snapshot.
+	let (voters, targets) = T::DataProvider::electing_voters(bounds.voters, Zero::zero())
+		.and_then(|voters| {
+			Ok((voters, T::DataProvider::electable_targets(bounds.targets, Zero::zero())?))
+		})
 		.map_err(Error::DataProvider)?;
 
 	let desired_targets = T::DataProvider::desired_targets().map_err(Error::DataProvider)?;
@@ -151,6 +159,7 @@ impl<T: Config> ElectionProviderBase for OnChainExecution<T> {
 	type Error = Error;
 	type MaxWinners = T::MaxWinners;
 	type DataProvider = T::DataProvider;
+	type Pages = ConstU32<1>;
 }
 
 impl<T: Config> InstantElectionProvider for OnChainExecution<T> {
@@ -172,7 +181,8 @@ impl<T: Config> ElectionProvider for OnChainExecution<T> {
 		false
 	}
 
-	fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error> {
+	fn elect(_page: PageIndex) -> Result<BoundedSupportsOf<Self>, Self::Error> {
+		// single paged, thus the page index is irrelevant.
 		let election_bounds = ElectionBoundsBuilder::from(T::Bounds::get()).build();
 		elect_with_input_bounds::<T>(election_bounds)
 	}
@@ -265,7 +275,10 @@ mod tests {
 			type AccountId = AccountId;
 			type BlockNumber = BlockNumber;
 			type MaxVotesPerVoter = ConstU32<2>;
-			fn electing_voters(_: DataProviderBounds) -> data_provider::Result<Vec<VoterOf<Self>>> {
+			fn electing_voters(
+				_: DataProviderBounds,
+				_: PageIndex,
+			) -> data_provider::Result<Vec<VoterOf<Self>>> {
 				Ok(vec![
 					(1, 10, bounded_vec![10, 20]),
 					(2, 20, bounded_vec![30, 20]),
@@ -273,7 +286,10 @@ mod tests {
 				])
 			}
 
-			fn electable_targets(_: DataProviderBounds) -> data_provider::Result<Vec<AccountId>> {
+			fn electable_targets(
+				_: DataProviderBounds,
+				_: PageIndex,
+			) -> data_provider::Result<Vec<AccountId>> {
 				Ok(vec![10, 20, 30])
 			}
 
@@ -291,7 +307,7 @@ mod tests {
 	fn onchain_seq_phragmen_works() {
 		sp_io::TestExternalities::new_empty().execute_with(|| {
 			assert_eq!(
-				<OnChainExecution::<PhragmenParams> as ElectionProvider>::elect().unwrap(),
+				<OnChainExecution::<PhragmenParams> as ElectionProvider>::elect(0).unwrap(),
 				vec![
 					(10, Support { total: 25, voters: vec![(1, 10), (3, 15)] }),
 					(30, Support { total: 35, voters: vec![(2, 20), (3, 15)] })
@@ -308,7 +324,7 @@ mod tests {
 			MaxWinners::set(9);
 
 			assert_noop!(
-				<OnChainExecution::<PhragmenParams> as ElectionProvider>::elect(),
+				<OnChainExecution::<PhragmenParams> as ElectionProvider>::elect(0),
 				Error::TooManyWinners,
 			);
 		})
@@ -318,7 +334,7 @@ mod tests {
 	fn onchain_phragmms_works() {
 		sp_io::TestExternalities::new_empty().execute_with(|| {
 			assert_eq!(
-				<OnChainExecution::<PhragMMSParams> as ElectionProvider>::elect().unwrap(),
+				<OnChainExecution::<PhragMMSParams> as ElectionProvider>::elect(0).unwrap(),
 				vec![
 					(10, Support { total: 25, voters: vec![(1, 10), (3, 15)] }),
 					(30, Support { total: 35, voters: vec![(2, 20), (3, 15)] })
@@ -155,7 +155,7 @@ use codec::{Codec, Encode};
 use frame_support::{
 	defensive_assert,
 	dispatch::{DispatchClass, DispatchInfo, GetDispatchInfo, PostDispatchInfo},
-	migrations::MultiStepMigrator,
+	migrations::{MultiStepMigrator, UpgradeDryRunReport},
 	pallet_prelude::InvalidTransaction,
 	traits::{
 		BeforeAllRuntimeMigrations, EnsureInherentsAreFirst, ExecuteBlock, OffchainWorker,
@@ -182,9 +182,11 @@ use ::{
 		traits::{TryDecodeEntireStorage, TryDecodeEntireStorageError, TryState},
 		StorageNoopGuard,
 	},
-	frame_try_runtime::{TryStateSelect, UpgradeCheckSelect},
+	frame_try_runtime::{
+		storage_proof, MbmBlockReport, MbmSimulationReport, TryStateSelect, UpgradeCheckSelect,
+	},
 	log,
-	sp_runtime::TryRuntimeError,
+	sp_runtime::{traits::SaturatedConversion, TryRuntimeError},
 };
 
 #[allow(dead_code)]
@@ -340,7 +342,13 @@ where
 			}
 
 			<frame_system::Pallet<System>>::note_extrinsic(encoded);
-			let r = Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len)?;
+			#[cfg(feature = "storage-metering")]
+			let (r, _) = frame_support::storage::metering::with_metering(|| {
+				Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len)
+			});
+			#[cfg(not(feature = "storage-metering"))]
+			let r = Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len);
+			let r = r?;
 
 			if r.is_err() && dispatch_info.class == DispatchClass::Mandatory {
 				return Err(InvalidTransaction::BadMandatory.into())
@@ -424,6 +432,19 @@ where
 		Ok(frame_system::Pallet::<System>::block_weight().total())
 	}
 
+	/// Dry-run the upgrade to this runtime and run the `try_state` hooks of all pallets afterwards.
+	///
+	/// See [`Self::dry_run_runtime_upgrade`].
+	pub fn try_dry_run_runtime_upgrade(
+	) -> UpgradeDryRunReport<frame_system::EventRecord<System::RuntimeEvent, System::Hash>> {
+		let mut report = Self::dry_run_runtime_upgrade();
+		report.try_state = Some(AllPalletsWithSystem::try_state(
+			frame_system::Pallet::<System>::block_number(),
+			TryStateSelect::All,
+		));
+		report
+	}
+
 	/// Execute all Migrations of this runtime.
 	///
 	/// The `checks` param determines whether to execute `pre/post_upgrade` and `try_state` hooks.
@@ -465,6 +486,119 @@ where
 		Ok(before_all_weight.saturating_add(try_on_runtime_upgrade_weight))
 	}
 
+	/// Execute all Migrations of this runtime and then simulate blocks until all Multi-Block
+	/// Migrations completed.
+	///
+	/// No more than `max_blocks` empty blocks are built after the upgrade. The returned report
+	/// contains the weight of each of these blocks and whether the migrations finished in time.
+	/// If a storage proof is recorded, the size of the proof of each block and migration step is
+	/// reported as well, see [`frame_try_runtime::storage_proof`].
+	///
+	/// Returns an error if a migration failed and left the MBMs stuck, which includes errors of
+	/// the `pre/post_upgrade` hooks of stepped migrations.
+	pub fn try_runtime_upgrade_mbms(
+		checks: UpgradeCheckSelect,
+		max_blocks: u32,
+	) -> Result<MbmSimulationReport, TryRuntimeError> {
+		Self::try_runtime_upgrade(checks)?;
+
+		let max_block_weight =
+			<System::BlockWeights as frame_support::traits::Get<_>>::get().max_block;
+		// The hash of the current block is not known to the runtime. Its parent hash is good
+		// enough for the simulated blocks.
+		let mut parent_hash = frame_system::Pallet::<System>::parent_hash();
+		let mut blocks = Vec::new();
+
+		while <System as frame_system::Config>::MultiBlockMigrator::ongoing() {
+			if blocks.len() as u32 >= max_blocks {
+				log::warn!(
+					target: LOG_TARGET,
+					"try-runtime: MBMs are still ongoing after {} blocks",
+					max_blocks,
+				);
+				break
+			}
+
+			let number = frame_system::Pallet::<System>::block_number() + One::one();
+			let header = frame_system::pallet_prelude::HeaderFor::<System>::new(
+				number,
+				Default::default(),
+				Default::default(),
+				parent_hash,
+				Default::default(),
+			);
+
+			let block_proof_before = storage_proof::recorded_size();
+			if Self::initialize_block(&header) != ExtrinsicInclusionMode::OnlyInherents {
+				return Err("MBMs are ongoing but extrinsics are allowed".into())
+			}
+
+			// The MBMs are stepped right after the inherents.
+			let weight_before = frame_system::Pallet::<System>::block_weight().total();
+			let migration_proof_before = storage_proof::recorded_size();
+			Self::inherents_applied();
+			let migration_weight = frame_system::Pallet::<System>::block_weight()
+				.total()
+				.saturating_sub(weight_before);
+			let migration_proof_size = Self::recorded_proof_size_since(migration_proof_before);
+
+			<frame_system::Pallet<System>>::note_finished_extrinsics();
+			<System as frame_system::Config>::PostTransactions::post_transactions();
+			Self::on_idle_hook(number);
+			Self::on_finalize_hook(number);
+
+			let block = MbmBlockReport {
+				number: number.saturated_into(),
+				migration_weight,
+				block_weight: frame_system::Pallet::<System>::block_weight().total(),
+				migration_proof_size,
+				block_proof_size: Self::recorded_proof_size_since(block_proof_before),
+			};
+			if block.exceeds(max_block_weight) {
+				log::error!(
+					target: LOG_TARGET,
+					"try-runtime: Block #{:?} is overweight: {} (recorded proof size: {:?}) > {}",
+					number,
+					block.block_weight,
+					block.block_proof_size,
+					max_block_weight,
+				);
+			}
+			blocks.push(block);
+
+			parent_hash = frame_system::Pallet::<System>::finalize().hash();
+
+			if <System as frame_system::Config>::MultiBlockMigrator::is_stuck() {
+				log::error!(
+					target: LOG_TARGET,
+					"try-runtime: MBMs are stuck after a failed migration in block #{:?}",
+					number,
+				);
+				return Err("A multi-block migration failed".into())
+			}
+
+			if checks.try_state() {
+				let _guard = StorageNoopGuard::default();
+				AllPalletsWithSystem::try_state(number, TryStateSelect::All)?;
+			}
+		}
+
+		let completed = !<System as frame_system::Config>::MultiBlockMigrator::ongoing();
+		log::info!(
+			target: LOG_TARGET,
+			"try-runtime: MBMs {} after {} blocks",
+			if completed { "completed" } else { "did not complete" },
+			blocks.len(),
+		);
+
+		Ok(MbmSimulationReport { blocks, max_block_weight, completed })
+	}
+
+	/// The size of the storage proof that was recorded since `before`.
+	fn recorded_proof_size_since(before: Option<u64>) -> Option<u64> {
+		Some(storage_proof::recorded_size()?.saturating_sub(before?))
+	}
+
 	/// Logs the result of trying to decode the entire state.
 	fn log_decode_result(
 		res: Result<usize, Vec<TryDecodeEntireStorageError>>,
@@ -525,6 +659,8 @@ where
 {
 	/// Execute all `OnRuntimeUpgrade` of this runtime, and return the aggregate weight.
 	pub fn execute_on_runtime_upgrade() -> Weight {
+		#[cfg(feature = "proof-size-tracing")]
+		sp_tracing::enter_span!(sp_tracing::Level::TRACE, "on_runtime_upgrade");
 		let before_all_weight =
 			<AllPalletsWithSystem as BeforeAllRuntimeMigrations>::before_all_runtime_migrations();
 
@@ -539,6 +675,44 @@ where
 		before_all_weight.saturating_add(runtime_upgrade_weight)
 	}
 
+	/// Dry-run the upgrade to this runtime on top of the current state.
+	///
+	/// Runs the runtime upgrade and the hooks of the first block after it, as if the block was
+	/// initialized with this runtime: `on_runtime_upgrade`, `on_initialize` and either the first
+	/// step of the multi-block migrations or `on_poll`. `on_idle` and `on_finalize` are not run,
+	/// as they only consume the remaining weight or depend on the inherents of the block.
+	///
+	/// The upgrade is run even if this runtime is already the on-chain runtime. The block is
+	/// initialized with a default parent hash and digest. See `try_dry_run_runtime_upgrade` for a
+	/// version that also runs the `try_state` hooks.
+	///
+	/// This changes the state, it should only be called from a runtime API like
+	/// `frame_system_rpc_runtime_api::UpgradeDryRunApi` whose changes are discarded.
+	pub fn dry_run_runtime_upgrade(
+	) -> UpgradeDryRunReport<frame_system::EventRecord<System::RuntimeEvent, System::Hash>> {
+		// Force the upgrade to run.
+		frame_system::LastRuntimeUpgrade::<System>::kill();
+
+		let number = <frame_system::Pallet<System>>::block_number().saturating_add(One::one());
+		let upgrade_weight =
+			Self::initialize_block_impl(&number, &Default::default(), &Default::default());
+		let multi_block_migrations =
+			<System as frame_system::Config>::MultiBlockMigrator::ongoing();
+		Self::inherents_applied();
+
+		UpgradeDryRunReport {
+			upgrade_weight,
+			block_weight: <frame_system::Pallet<System>>::block_weight().total(),
+			max_block_weight: <System::BlockWeights as frame_support::traits::Get<_>>::get()
+				.max_block,
+			multi_block_migrations,
+			events: <frame_system::Pallet<System>>::read_events_no_consensus()
+				.map(|e| *e)
+				.collect(),
+			try_state: None,
+		}
+	}
+
 	/// Start the execution of a particular block.
 	pub fn initialize_block(
 		header: &frame_system::pallet_prelude::HeaderFor<System>,
@@ -569,19 +743,22 @@ where
 		digest
 	}
 
+	/// Returns the weight consumed by the runtime upgrade, zero if there was none.
 	fn initialize_block_impl(
 		block_number: &BlockNumberFor<System>,
 		parent_hash: &System::Hash,
 		digest: &Digest,
-	) {
+	) -> Weight {
 		// Reset events before apply runtime upgrade hook.
 		// This is required to preserve events from runtime upgrade hook.
 		// This means the format of all the event related storages must always be compatible.
 		<frame_system::Pallet<System>>::reset_events();
 
 		let mut weight = Weight::zero();
+		let mut upgrade_weight = Weight::zero();
 		if Self::runtime_upgraded() {
-			weight = weight.saturating_add(Self::execute_on_runtime_upgrade());
+			upgrade_weight = Self::execute_on_runtime_upgrade();
+			weight = weight.saturating_add(upgrade_weight);
 
 			frame_system::LastRuntimeUpgrade::<System>::put(
 				frame_system::LastRuntimeUpgradeInfo::from(
@@ -603,6 +780,8 @@ where
 
 		frame_system::Pallet::<System>::note_finished_initialize();
 		<System as frame_system::Config>::PreInherents::pre_inherents();
+
+		upgrade_weight
 	}
 
 	/// Returns if the runtime has been upgraded, based on [`frame_system::LastRuntimeUpgrade`].
@@ -670,10 +849,14 @@ where
 	///
 	/// It advances the Multi-Block-Migrations or runs the `on_poll` hook.
 	pub fn inherents_applied() {
+		#[cfg(feature = "proof-size-tracing")]
+		sp_tracing::enter_span!(sp_tracing::Level::TRACE, "inherents_applied");
 		<frame_system::Pallet<System>>::note_inherents_applied();
 		<System as frame_system::Config>::PostInherents::post_inherents();
 
 		if <System as frame_system::Config>::MultiBlockMigrator::ongoing() {
+			#[cfg(feature = "proof-size-tracing")]
+			sp_tracing::enter_span!(sp_tracing::Level::TRACE, "multi_block_migrations");
 			let used_weight = <System as frame_system::Config>::MultiBlockMigrator::step();
 			<frame_system::Pallet<System>>::register_extra_weight_unchecked(
 				used_weight,
@@ -796,7 +979,15 @@ where
 
 		// AUDIT: Under no circumstances may this function panic from here onwards.
 
-		let r = Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len)?;
+		// With the `storage-metering` feature storage accesses are metered, this allows signed
+		// extensions like `frame_system::ReclaimRefTime` to refund unused `ref_time`.
+		#[cfg(feature = "storage-metering")]
+		let (r, _) = frame_support::storage::metering::with_metering(|| {
+			Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len)
+		});
+		#[cfg(not(feature = "storage-metering"))]
+		let r = Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len);
+		let r = r?;
 
 		// Mandatory(inherents) are not allowed to fail.
 		//
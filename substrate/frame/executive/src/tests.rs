@@ -449,6 +449,23 @@ impl MockedSystemCallbacks {
 
 parameter_types! {
 	pub static MbmActive: bool = false;
+	/// The number of steps after which the MBMs finish. Zero means that they never finish.
+	pub static MbmStepsLeft: u32 = 0;
+	/// Whether the MBMs get stuck instead of completing after their last step.
+	pub static MbmFailsAtEnd: bool = false;
+	pub static MbmStuck: bool = false;
+	/// The size of the storage proof that each step of the MBMs records.
+	pub static MbmStepProofSize: u64 = 0;
+	/// The size of the storage proof that was recorded so far.
+	pub static RecordedProofSize: u64 = 0;
+}
+
+/// Reports [`RecordedProofSize`] as the size of the recorded storage proof.
+pub struct MockedProofRecorder;
+impl sp_trie::ProofSizeProvider for MockedProofRecorder {
+	fn estimate_encoded_size(&self) -> usize {
+		RecordedProofSize::get() as usize
+	}
 }
 
 pub struct MockedModeGetter;
@@ -458,8 +475,24 @@ impl MultiStepMigrator for MockedModeGetter {
 	}
 
 	fn step() -> Weight {
+		if MbmActive::get() && !MbmStuck::get() && MbmStepsLeft::get() > 0 {
+			MbmStepsLeft::mutate(|s| *s -= 1);
+			RecordedProofSize::mutate(|s| *s += MbmStepProofSize::get());
+			if MbmStepsLeft::get() == 0 {
+				if MbmFailsAtEnd::get() {
+					MbmStuck::set(true);
+				} else {
+					MbmActive::set(false);
+				}
+			}
+		}
 		Weight::zero()
 	}
+
+	#[cfg(feature = "try-runtime")]
+	fn is_stuck() -> bool {
+		MbmStuck::get()
+	}
 }
 
 fn extra(nonce: u64, fee: Balance) -> SignedExtra {
@@ -868,6 +901,34 @@ fn event_from_runtime_upgrade_is_included() {
 	});
 }
 
+#[test]
+fn dry_run_runtime_upgrade_reports_upgrade() {
+	new_test_ext(1).execute_with(|| {
+		// The dry run runs the upgrade even if the version was already upgraded to.
+		RuntimeVersionTestValues::mutate(|v| {
+			*v = sp_version::RuntimeVersion { spec_version: 1, ..Default::default() }
+		});
+		LastRuntimeUpgrade::<Runtime>::put(LastRuntimeUpgradeInfo::from(
+			RuntimeVersionTestValues::get(),
+		));
+		System::set_block_number(1);
+
+		let report = Executive::dry_run_runtime_upgrade();
+
+		// 100 from the custom upgrade and 200 from the modules.
+		assert!(report.upgrade_weight.all_gte(Weight::from_parts(300, 0)));
+		assert!(report.block_weight.all_gte(report.upgrade_weight));
+		assert!(report.fits_in_block());
+		assert!(!report.multi_block_migrations);
+		assert!(report
+			.events
+			.iter()
+			.any(|r| r.event == frame_system::Event::<Runtime>::CodeUpdated.into()));
+		assert_eq!(report.try_state, None);
+		assert_eq!(sp_io::storage::get(CUSTOM_ON_RUNTIME_KEY).unwrap(), true.encode());
+	});
+}
+
 /// Regression test that ensures that the custom on runtime upgrade is called when executive is
 /// used through the `ExecuteBlock` trait.
 #[test]
@@ -1143,6 +1204,87 @@ fn try_execute_block_works() {
 	});
 }
 
+#[test]
+#[cfg(feature = "try-runtime")]
+fn try_runtime_upgrade_mbms_works() {
+	new_test_ext(1).execute_with(|| {
+		MbmActive::set(true);
+		MbmStepsLeft::set(3);
+
+		let report =
+			Executive::try_runtime_upgrade_mbms(frame_try_runtime::UpgradeCheckSelect::None, 10)
+				.unwrap();
+		assert!(report.completed);
+		assert_eq!(report.blocks.len(), 3);
+		assert!(!MbmActive::get());
+		// No storage proof is recorded.
+		assert!(report.blocks.iter().all(|b| b.block_proof_size.is_none()));
+		assert_eq!(report.max_migration_proof_size(), None);
+	});
+}
+
+#[test]
+#[cfg(feature = "try-runtime")]
+fn try_runtime_upgrade_mbms_reports_proof_size() {
+	let mut ext = new_test_ext(1);
+	ext.register_extension(sp_trie::proof_size_extension::ProofSizeExt::new(MockedProofRecorder));
+	ext.execute_with(|| {
+		MbmActive::set(true);
+		MbmStepsLeft::set(3);
+		MbmStepProofSize::set(1024);
+
+		let report =
+			Executive::try_runtime_upgrade_mbms(frame_try_runtime::UpgradeCheckSelect::None, 10)
+				.unwrap();
+		assert!(report.completed);
+		assert_eq!(report.blocks.len(), 3);
+		for block in &report.blocks {
+			assert_eq!(block.migration_proof_size, Some(1024));
+			assert_eq!(block.block_proof_size, Some(1024));
+		}
+		assert_eq!(report.max_migration_proof_size(), Some(1024));
+		assert_eq!(report.overweight_blocks().count(), 0);
+
+		// The recorded proof size is checked against the proof size limit of the block.
+		let block = &report.blocks[0];
+		assert!(!block.exceeds(Weight::from_parts(u64::MAX, 1024)));
+		assert!(block.exceeds(Weight::from_parts(u64::MAX, 1023)));
+	});
+}
+
+#[test]
+#[cfg(feature = "try-runtime")]
+fn try_runtime_upgrade_mbms_reports_unfinished_mbms() {
+	new_test_ext(1).execute_with(|| {
+		MbmActive::set(true);
+		MbmStepsLeft::set(5);
+
+		let report =
+			Executive::try_runtime_upgrade_mbms(frame_try_runtime::UpgradeCheckSelect::None, 2)
+				.unwrap();
+		assert!(!report.completed);
+		assert_eq!(report.blocks.len(), 2);
+	});
+}
+
+#[test]
+#[cfg(feature = "try-runtime")]
+fn try_runtime_upgrade_mbms_errors_on_failed_migration() {
+	new_test_ext(1).execute_with(|| {
+		MbmActive::set(true);
+		MbmStepsLeft::set(2);
+		MbmFailsAtEnd::set(true);
+
+		assert_eq!(
+			Executive::try_runtime_upgrade_mbms(frame_try_runtime::UpgradeCheckSelect::None, 10)
+				.unwrap_err(),
+			"A multi-block migration failed".into()
+		);
+		// It did not spin until `max_blocks`.
+		assert_eq!(MbmStepsLeft::get(), 0);
+	});
+}
+
 /// Same as `extrinsic_while_exts_forbidden_errors` but using the try-runtime function.
 #[test]
 #[cfg(feature = "try-runtime")]
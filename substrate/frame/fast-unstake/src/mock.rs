@@ -105,13 +105,16 @@ impl frame_election_provider_support::ElectionProviderBase for MockElection {
 	type MaxWinners = MaxWinners;
 	type DataProvider = Staking;
 	type Error = ();
+	type Pages = ConstU32<1>;
 }
 
 impl frame_election_provider_support::ElectionProvider for MockElection {
 	fn ongoing() -> bool {
 		Ongoing::get()
 	}
-	fn elect() -> Result<frame_election_provider_support::BoundedSupportsOf<Self>, Self::Error> {
+	fn elect(
+		_: frame_election_provider_support::PageIndex,
+	) -> Result<frame_election_provider_support::BoundedSupportsOf<Self>, Self::Error> {
 		Err(())
 	}
 }
@@ -125,8 +128,10 @@ impl pallet_staking::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Slash = ();
 	type Reward = ();
+	type RewardConverter = ();
 	type SessionsPerEra = ();
 	type SlashDeferDuration = ();
+	type CommissionChangeDelay = ();
 	type AdminOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type BondingDuration = BondingDuration;
 	type SessionInterface = ();
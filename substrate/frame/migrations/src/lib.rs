@@ -247,6 +247,13 @@ impl<Id> HistoricCleanupSelector<Id> {
 	}
 }
 
+/// Storage key under which the `pre_upgrade` state of the active migration is kept.
+///
+/// Only used with the `try-runtime` feature, since the state needs to survive until the migration
+/// completes, which is likely some blocks later.
+#[cfg(feature = "try-runtime")]
+const PRE_UPGRADE_STATE_KEY: &[u8] = b":pallet_migrations:try_runtime:pre_upgrade_state:";
+
 /// Convenience alias for [`MigrationCursor`].
 pub type CursorOf<T> = MigrationCursor<RawCursorOf<T>, BlockNumberFor<T>>;
 
@@ -697,6 +704,27 @@ impl<T: Config> Pallet<T> {
 			return Some(ControlFlow::Continue(cursor))
 		}
 
+		// Run the `pre_upgrade` hook before the first step of a migration.
+		#[cfg(feature = "try-runtime")]
+		if cursor.inner_cursor.is_none() {
+			match T::Migrations::nth_pre_upgrade(cursor.index) {
+				Some(Ok(state)) =>
+					frame_support::storage::unhashed::put_raw(PRE_UPGRADE_STATE_KEY, &state),
+				Some(Err(err)) => {
+					log::error!("MBM #{} pre_upgrade failed: {:?}", cursor.index, err);
+					let took = System::<T>::block_number().saturating_sub(cursor.started_at);
+					Self::deposit_event(Event::MigrationFailed { index: cursor.index, took });
+					Self::upgrade_failed(Some(cursor.index));
+					return None
+				},
+				None => {
+					defensive!("integrity_test ensures that the tuple is valid; qed");
+					Self::upgrade_failed(Some(cursor.index));
+					return None
+				},
+			}
+		}
+
 		let max_steps = T::Migrations::nth_max_steps(cursor.index);
 		let next_cursor = T::Migrations::nth_transactional_step(
 			cursor.index,
@@ -731,6 +759,21 @@ impl<T: Config> Pallet<T> {
 				}
 			},
 			Ok(None) => {
+				// Check the outcome of the migration against its `pre_upgrade` state.
+				#[cfg(feature = "try-runtime")]
+				{
+					let state = frame_support::storage::unhashed::get_raw(PRE_UPGRADE_STATE_KEY)
+						.unwrap_or_default();
+					frame_support::storage::unhashed::kill(PRE_UPGRADE_STATE_KEY);
+
+					if let Some(Err(err)) = T::Migrations::nth_post_upgrade(cursor.index, state) {
+						log::error!("MBM #{} post_upgrade failed: {:?}", cursor.index, err);
+						Self::deposit_event(Event::MigrationFailed { index: cursor.index, took });
+						Self::upgrade_failed(Some(cursor.index));
+						return None
+					}
+				}
+
 				// A migration is done when it returns cursor `None`.
 				Self::deposit_event(Event::MigrationCompleted { index: cursor.index, took });
 				Historic::<T>::insert(&bounded_id, ());
@@ -760,6 +803,10 @@ impl<T: Config> Pallet<T> {
 		use FailedMigrationHandling::*;
 		Self::deposit_event(Event::UpgradeFailed);
 
+		// The `pre_upgrade` state of the failed migration will never be checked.
+		#[cfg(feature = "try-runtime")]
+		frame_support::storage::unhashed::kill(PRE_UPGRADE_STATE_KEY);
+
 		match T::FailedMigrationHandler::failed(migration) {
 			KeepStuck => Cursor::<T>::set(Some(MigrationCursor::Stuck)),
 			ForceUnstuck => Cursor::<T>::kill(),
@@ -785,4 +832,9 @@ impl<T: Config> MultiStepMigrator for Pallet<T> {
 	fn step() -> Weight {
 		Self::progress_mbms(System::<T>::block_number())
 	}
+
+	#[cfg(feature = "try-runtime")]
+	fn is_stuck() -> bool {
+		matches!(Cursor::<T>::get(), Some(MigrationCursor::Stuck))
+	}
 }
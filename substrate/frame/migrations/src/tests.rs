@@ -333,3 +333,49 @@ fn migration_timeout_errors() {
 		assert_eq!(upgrades_started_completed_failed(), (0, 0, 1));
 	});
 }
+
+#[test]
+#[cfg(feature = "try-runtime")]
+fn pre_upgrade_state_is_kept_until_completion() {
+	use frame_support::storage::unhashed;
+
+	test_closure(|| {
+		MockedMigrations::set(vec![(SucceedAfter, 2)]);
+
+		System::set_block_number(1);
+		Migrations::on_runtime_upgrade();
+		run_to_block(2);
+
+		// The state of the `pre_upgrade` hook is stored while the migration is ongoing.
+		assert!(unhashed::exists(crate::PRE_UPGRADE_STATE_KEY));
+
+		run_to_block(5);
+
+		// And cleared once it completed.
+		assert!(!unhashed::exists(crate::PRE_UPGRADE_STATE_KEY));
+		assert_eq!(historic(), vec![mocked_id(SucceedAfter, 2)]);
+		assert_eq!(upgrades_started_completed_failed(), (1, 1, 0));
+	});
+}
+
+#[test]
+#[cfg(feature = "try-runtime")]
+fn pre_upgrade_state_is_cleared_on_failure() {
+	use frame_support::{migrations::MultiStepMigrator, storage::unhashed};
+
+	test_closure(|| {
+		MockedMigrations::set(vec![(FailAfter, 2)]);
+
+		System::set_block_number(1);
+		Migrations::on_runtime_upgrade();
+		run_to_block(2);
+		assert!(unhashed::exists(crate::PRE_UPGRADE_STATE_KEY));
+		assert!(!Migrations::is_stuck());
+
+		run_to_block(5);
+
+		assert!(!unhashed::exists(crate::PRE_UPGRADE_STATE_KEY));
+		assert!(Migrations::is_stuck());
+		assert_eq!(upgrades_started_completed_failed(), (1, 0, 1));
+	});
+}
@@ -17,28 +17,31 @@
 
 //! Benchmarks for the nomination pools coupled with the staking and bags list pallets.
 
-use frame_benchmarking::v1::{account, whitelist_account};
+use frame_benchmarking::v1::{account, whitelist_account, BenchmarkError};
 use frame_election_provider_support::SortedListProvider;
 use frame_support::{
 	assert_ok, ensure,
 	traits::{
 		fungible::{Inspect, Mutate, Unbalanced},
-		Get,
+		tokens::Preservation,
+		Get, Imbalance,
 	},
 };
 use frame_system::RawOrigin as RuntimeOrigin;
 use pallet_nomination_pools::{
-	BalanceOf, BondExtra, BondedPoolInner, BondedPools, ClaimPermission, ClaimPermissions,
-	Commission, CommissionChangeRate, CommissionClaimPermission, ConfigOp, GlobalMaxCommission,
-	MaxPoolMembers, MaxPoolMembersPerPool, MaxPools, Metadata, MinCreateBond, MinJoinBond,
-	Pallet as Pools, PoolMembers, PoolRoles, PoolState, RewardPools, SubPoolsStorage,
+	adapter::{StakeStrategy, StakeStrategyType},
+	AutoCompoundMembers, AutoCompoundPools, BalanceOf, BondExtra, BondedPoolInner, BondedPools,
+	ClaimPermission, ClaimPermissions, Commission, CommissionChangeRate, CommissionClaimPermission,
+	ConfigOp, GlobalMaxCommission, MaxPoolMembers, MaxPoolMembersPerPool, MaxPools, Metadata,
+	MinCreateBond, MinJoinBond, Pallet as Pools, PoolId, PoolMembers, PoolRoles, PoolState,
+	RewardDestination, RewardDestinations, RewardPools, SubPoolsStorage,
 };
 use pallet_staking::MaxNominationsOf;
 use sp_runtime::{
 	traits::{Bounded, StaticLookup, Zero},
-	Perbill,
+	Perbill, SaturatedConversion,
 };
-use sp_staking::{EraIndex, StakingInterface};
+use sp_staking::{EraIndex, StakingInterface, StakingUnchecked};
 use sp_std::{vec, vec::Vec};
 // `frame_benchmarking::benchmarks!` macro needs this
 use pallet_nomination_pools::Call;
@@ -107,6 +110,38 @@ fn create_pool_account<T: pallet_nomination_pools::Config>(
 	(pool_creator, pool_account)
 }
 
+/// Turn the pool `pool_id` back into a pool that holds the funds of its members in its own account,
+/// as if it had never been migrated to delegated stake.
+///
+/// Noop if the runtime does not use [`StakeStrategyType::Delegate`].
+fn migrate_to_transfer_stake<T: Config>(pool_id: PoolId) {
+	if T::StakeAdapter::strategy_type() == StakeStrategyType::Transfer {
+		// nothing to migrate.
+		return
+	}
+
+	let pool_account = Pools::<T>::create_bonded_account(pool_id);
+	// drop the agent and its delegators, releasing the funds held by each member.
+	T::StakeAdapter::remove_as_agent(&pool_account);
+
+	// move the funds of all members to the pool account.
+	PoolMembers::<T>::iter()
+		.filter(|(_, member)| member.pool_id == pool_id)
+		.for_each(|(member_account, member)| {
+			CurrencyOf::<T>::transfer(
+				&member_account,
+				&pool_account,
+				member.total_balance(),
+				Preservation::Expendable,
+			)
+			.expect("member should have enough balance to transfer");
+		});
+
+	// the pool account needs ED to exist as a direct staker.
+	let _ = CurrencyOf::<T>::mint_into(&pool_account, CurrencyOf::<T>::minimum_balance());
+	pallet_staking::Pallet::<T>::migrate_to_direct_staker(&pool_account);
+}
+
 fn vote_to_balance<T: pallet_nomination_pools::Config>(
 	vote: u64,
 ) -> Result<BalanceOf<T>, &'static str> {
@@ -151,7 +186,7 @@ impl<T: Config> ListScenario<T> {
 		let (pool_creator1, pool_origin1) =
 			create_pool_account::<T>(USER_SEED + 1, origin_weight, Some(Perbill::from_percent(50)));
 
-		T::Staking::nominate(
+		T::StakeAdapter::nominate(
 			&pool_origin1,
 			// NOTE: these don't really need to be validators.
 			vec![account("random_validator", 0, USER_SEED)],
@@ -160,7 +195,7 @@ impl<T: Config> ListScenario<T> {
 		let (_, pool_origin2) =
 			create_pool_account::<T>(USER_SEED + 2, origin_weight, Some(Perbill::from_percent(50)));
 
-		T::Staking::nominate(
+		T::StakeAdapter::nominate(
 			&pool_origin2,
 			vec![account("random_validator", 0, USER_SEED)].clone(),
 		)?;
@@ -178,7 +213,7 @@ impl<T: Config> ListScenario<T> {
 		let (_, pool_dest1) =
 			create_pool_account::<T>(USER_SEED + 3, dest_weight, Some(Perbill::from_percent(50)));
 
-		T::Staking::nominate(&pool_dest1, vec![account("random_validator", 0, USER_SEED)])?;
+		T::StakeAdapter::nominate(&pool_dest1, vec![account("random_validator", 0, USER_SEED)])?;
 
 		let weight_of = pallet_staking::Pallet::<T>::weight_of_fn();
 		assert_eq!(vote_to_balance::<T>(weight_of(&pool_origin1)).unwrap(), origin_weight);
@@ -204,11 +239,12 @@ impl<T: Config> ListScenario<T> {
 		self.origin1_member = Some(joiner.clone());
 		CurrencyOf::<T>::set_balance(&joiner, amount * 2u32.into());
 
-		let original_bonded = T::Staking::active_stake(&self.origin1).unwrap();
+		let original_bonded = T::StakeAdapter::active_stake(&self.origin1);
 
 		// Unbond `amount` from the underlying pool account so when the member joins
 		// we will maintain `current_bonded`.
-		T::Staking::unbond(&self.origin1, amount).expect("the pool was created in `Self::new`.");
+		T::StakeAdapter::unbond(&self.origin1, amount)
+			.expect("the pool was created in `Self::new`.");
 
 		// Account pool points for the unbonded balance.
 		BondedPools::<T>::mutate(&1, |maybe_pool| {
@@ -237,7 +273,7 @@ frame_benchmarking::benchmarks! {
 		// setup the worst case list scenario.
 		let scenario = ListScenario::<T>::new(origin_weight, true)?;
 		assert_eq!(
-			T::Staking::active_stake(&scenario.origin1).unwrap(),
+			T::StakeAdapter::active_stake(&scenario.origin1),
 			origin_weight
 		);
 
@@ -252,7 +288,7 @@ frame_benchmarking::benchmarks! {
 	verify {
 		assert_eq!(CurrencyOf::<T>::balance(&joiner), joiner_free - max_additional);
 		assert_eq!(
-			T::Staking::active_stake(&scenario.origin1).unwrap(),
+			T::StakeAdapter::active_stake(&scenario.origin1),
 			scenario.dest_weight
 		);
 	}
@@ -267,7 +303,7 @@ frame_benchmarking::benchmarks! {
 	}: bond_extra(RuntimeOrigin::Signed(scenario.creator1.clone()), BondExtra::FreeBalance(extra))
 	verify {
 		assert!(
-			T::Staking::active_stake(&scenario.origin1).unwrap() >=
+			T::StakeAdapter::active_stake(&scenario.origin1) >=
 			scenario.dest_weight
 		);
 	}
@@ -291,7 +327,7 @@ frame_benchmarking::benchmarks! {
 	verify {
 		 // commission of 50% deducted here.
 		assert!(
-			T::Staking::active_stake(&scenario.origin1).unwrap() >=
+			T::StakeAdapter::active_stake(&scenario.origin1) >=
 			scenario.dest_weight / 2u32.into()
 		);
 	}
@@ -345,7 +381,7 @@ frame_benchmarking::benchmarks! {
 		whitelist_account!(member_id);
 	}: _(RuntimeOrigin::Signed(member_id.clone()), member_id_lookup, all_points)
 	verify {
-		let bonded_after = T::Staking::active_stake(&scenario.origin1).unwrap();
+		let bonded_after = T::StakeAdapter::active_stake(&scenario.origin1);
 		// We at least went down to the destination bag
 		assert!(bonded_after <= scenario.dest_weight);
 		let member = PoolMembers::<T>::get(
@@ -354,7 +390,7 @@ frame_benchmarking::benchmarks! {
 		.unwrap();
 		assert_eq!(
 			member.unbonding_eras.keys().cloned().collect::<Vec<_>>(),
-			vec![0 + T::Staking::bonding_duration()]
+			vec![0 + T::StakeAdapter::bonding_duration()]
 		);
 		assert_eq!(
 			member.unbonding_eras.values().cloned().collect::<Vec<_>>(),
@@ -376,7 +412,7 @@ frame_benchmarking::benchmarks! {
 
 		// Sanity check join worked
 		assert_eq!(
-			T::Staking::active_stake(&pool_account).unwrap(),
+			T::StakeAdapter::active_stake(&pool_account),
 			min_create_bond + min_join_bond
 		);
 		assert_eq!(CurrencyOf::<T>::balance(&joiner), min_join_bond);
@@ -386,7 +422,7 @@ frame_benchmarking::benchmarks! {
 
 		// Sanity check that unbond worked
 		assert_eq!(
-			T::Staking::active_stake(&pool_account).unwrap(),
+			T::StakeAdapter::active_stake(&pool_account),
 			min_create_bond
 		);
 		assert_eq!(pallet_staking::Ledger::<T>::get(&pool_account).unwrap().unlocking.len(), 1);
@@ -419,7 +455,7 @@ frame_benchmarking::benchmarks! {
 
 		// Sanity check join worked
 		assert_eq!(
-			T::Staking::active_stake(&pool_account).unwrap(),
+			T::StakeAdapter::active_stake(&pool_account),
 			min_create_bond + min_join_bond
 		);
 		assert_eq!(CurrencyOf::<T>::balance(&joiner), min_join_bond);
@@ -430,7 +466,7 @@ frame_benchmarking::benchmarks! {
 
 		// Sanity check that unbond worked
 		assert_eq!(
-			T::Staking::active_stake(&pool_account).unwrap(),
+			T::StakeAdapter::active_stake(&pool_account),
 			min_create_bond
 		);
 		assert_eq!(pallet_staking::Ledger::<T>::get(&pool_account).unwrap().unlocking.len(), 1);
@@ -476,7 +512,7 @@ frame_benchmarking::benchmarks! {
 
 		// Sanity check that unbond worked
 		assert_eq!(
-			T::Staking::active_stake(&pool_account).unwrap(),
+			T::StakeAdapter::active_stake(&pool_account),
 			Zero::zero()
 		);
 		assert_eq!(
@@ -556,8 +592,8 @@ frame_benchmarking::benchmarks! {
 			}
 		);
 		assert_eq!(
-			T::Staking::active_stake(&Pools::<T>::create_bonded_account(1)),
-			Ok(min_create_bond)
+			T::StakeAdapter::active_stake(&Pools::<T>::create_bonded_account(1)),
+			min_create_bond
 		);
 	}
 
@@ -596,8 +632,8 @@ frame_benchmarking::benchmarks! {
 			}
 		);
 		assert_eq!(
-			T::Staking::active_stake(&Pools::<T>::create_bonded_account(1)),
-			Ok(min_create_bond)
+			T::StakeAdapter::active_stake(&Pools::<T>::create_bonded_account(1)),
+			min_create_bond
 		);
 	}
 
@@ -681,13 +717,13 @@ frame_benchmarking::benchmarks! {
 			.map(|i| account("stash", USER_SEED, i))
 			.collect();
 
-		assert_ok!(T::Staking::nominate(&pool_account, validators));
-		assert!(T::Staking::nominations(&Pools::<T>::create_bonded_account(1)).is_some());
+		assert_ok!(T::StakeAdapter::nominate(&pool_account, validators));
+		assert!(T::StakeAdapter::CoreStaking::nominations(&Pools::<T>::create_bonded_account(1)).is_some());
 
 		whitelist_account!(depositor);
 	}:_(RuntimeOrigin::Signed(depositor.clone()), 1)
 	verify {
-		assert!(T::Staking::nominations(&Pools::<T>::create_bonded_account(1)).is_none());
+		assert!(T::StakeAdapter::CoreStaking::nominations(&Pools::<T>::create_bonded_account(1)).is_none());
 	}
 
 	set_commission {
@@ -786,12 +822,13 @@ frame_benchmarking::benchmarks! {
 
 		// Sanity check join worked
 		assert_eq!(
-			T::Staking::active_stake(&pool_account).unwrap(),
+			T::StakeAdapter::active_stake(&pool_account),
 			min_create_bond + min_join_bond
 		);
-	}:_(RuntimeOrigin::Signed(joiner.clone()), ClaimPermission::Permissioned)
+	}:_(RuntimeOrigin::Signed(joiner.clone()), ClaimPermission::PermissionlessAll)
 	verify {
-		assert_eq!(ClaimPermissions::<T>::get(joiner), ClaimPermission::Permissioned);
+		assert_eq!(ClaimPermissions::<T>::get(&joiner), ClaimPermission::PermissionlessAll);
+		assert!(AutoCompoundMembers::<T>::contains_key(1, joiner));
 	}
 
 	claim_commission {
@@ -838,6 +875,130 @@ frame_benchmarking::benchmarks! {
 		assert!(&Pools::<T>::check_ed_imbalance().is_ok());
 	}
 
+	apply_slash {
+		// slashes are only deferred with delegated stake.
+		if T::StakeAdapter::strategy_type() != StakeStrategyType::Delegate {
+			return Err(BenchmarkError::Skip)
+		}
+
+		// We want to fill member's unbonding pools. So let's bond with big enough amount.
+		let deposit_amount = Pools::<T>::depositor_min_bond() *
+			T::MaxUnbonding::get().into() *
+			4u32.into();
+		let (depositor, pool_account) = create_pool_account::<T>(0, deposit_amount, None);
+		let depositor_lookup = T::Lookup::unlookup(depositor.clone());
+
+		// slash the pool by half.
+		let slash_amount: u128 = deposit_amount.saturated_into::<u128>() / 2;
+		pallet_staking::slashing::do_slash::<T>(
+			&pool_account,
+			slash_amount.saturated_into(),
+			&mut Zero::zero(),
+			&mut pallet_staking::NegativeImbalanceOf::<T>::zero(),
+			EraIndex::zero(),
+		);
+
+		// the slash is applied to the pool, but not yet to the member.
+		assert_eq!(
+			PoolMembers::<T>::get(&depositor).unwrap().total_balance(),
+			deposit_amount / 2u32.into()
+		);
+		assert_eq!(
+			T::StakeAdapter::member_delegation_balance(&depositor),
+			Some(deposit_amount)
+		);
+
+		// Fill member's sub pools for the worst case.
+		for i in 1..(T::MaxUnbonding::get() + 1) {
+			pallet_staking::CurrentEra::<T>::put(i);
+			assert_ok!(Pools::<T>::unbond(
+				RuntimeOrigin::Signed(depositor.clone()).into(),
+				depositor_lookup.clone(),
+				Pools::<T>::depositor_min_bond()
+			));
+		}
+
+		pallet_staking::CurrentEra::<T>::put(T::MaxUnbonding::get() + 2);
+
+		let slash_reporter = create_funded_user_with_balance::<T>(
+			"slasher",
+			0,
+			CurrencyOf::<T>::minimum_balance()
+		);
+		whitelist_account!(depositor);
+	}:_(RuntimeOrigin::Signed(slash_reporter), depositor_lookup)
+	verify {
+		assert_eq!(
+			T::StakeAdapter::member_delegation_balance(&depositor),
+			Some(deposit_amount / 2u32.into())
+		);
+	}
+
+	migrate_delegation {
+		// members can only migrate with delegated stake.
+		if T::StakeAdapter::strategy_type() != StakeStrategyType::Delegate {
+			return Err(BenchmarkError::Skip)
+		}
+
+		let deposit_amount = Pools::<T>::depositor_min_bond() * 2u32.into();
+		let (depositor, _) = create_pool_account::<T>(0, deposit_amount, None);
+		let depositor_lookup = T::Lookup::unlookup(depositor.clone());
+
+		// migrate the pool back and forth, leaving the depositor with funds in the pool account.
+		migrate_to_transfer_stake::<T>(1);
+		assert_ok!(Pools::<T>::migrate_pool_to_delegate_stake(
+			RuntimeOrigin::Signed(depositor.clone()).into(),
+			1u32.into(),
+		));
+		assert!(Pools::<T>::api_member_needs_delegate_migration(depositor.clone()));
+
+		whitelist_account!(depositor);
+	}:_(RuntimeOrigin::Signed(depositor.clone()), depositor_lookup)
+	verify {
+		assert!(!Pools::<T>::api_member_needs_delegate_migration(depositor.clone()));
+		assert_eq!(
+			T::StakeAdapter::member_delegation_balance(&depositor),
+			Some(deposit_amount)
+		);
+	}
+
+	pool_migrate {
+		// pools can only migrate with delegated stake.
+		if T::StakeAdapter::strategy_type() != StakeStrategyType::Delegate {
+			return Err(BenchmarkError::Skip)
+		}
+
+		let deposit_amount = Pools::<T>::depositor_min_bond() * 2u32.into();
+		let (depositor, _) = create_pool_account::<T>(0, deposit_amount, None);
+
+		migrate_to_transfer_stake::<T>(1);
+		assert!(Pools::<T>::api_pool_needs_delegate_migration(1));
+
+		whitelist_account!(depositor);
+	}: migrate_pool_to_delegate_stake(RuntimeOrigin::Signed(depositor.clone()), 1u32.into())
+	verify {
+		assert!(!Pools::<T>::api_pool_needs_delegate_migration(1));
+	}
+
+	set_auto_compound {
+		let (depositor, _) = create_pool_account::<T>(0, Pools::<T>::depositor_min_bond(), None);
+		whitelist_account!(depositor);
+	}:_(RuntimeOrigin::Signed(depositor), 1, true)
+	verify {
+		assert!(AutoCompoundPools::<T>::contains_key(1));
+	}
+
+	set_reward_destination {
+		// Worst case: the rewards are sent to another pool.
+		let min_create_bond = Pools::<T>::depositor_min_bond();
+		let (depositor, _) = create_pool_account::<T>(0, min_create_bond, None);
+		let _ = create_pool_account::<T>(1, min_create_bond, None);
+		whitelist_account!(depositor);
+	}:_(RuntimeOrigin::Signed(depositor.clone()), RewardDestination::Pool(2))
+	verify {
+		assert_eq!(RewardDestinations::<T>::get(&depositor), RewardDestination::Pool(2));
+	}
+
 	impl_benchmark_test_suite!(
 		Pallet,
 		crate::mock::new_test_ext(),
@@ -70,6 +70,15 @@
 //! other members to permissionlessly bond or withdraw their rewards by calling
 //! [`Call::bond_extra_other`] or [`Call::claim_payout_other`] respectively.
 //!
+//! By default, rewards are paid out to the member's own account. A member can set a different
+//! [`RewardDestination`] with [`Call::set_reward_destination`], to have their payouts sent to
+//! another account, or into the reward account of another pool.
+//!
+//! The root of a pool can enable auto-compounding with [`Call::set_auto_compound`]. The pending
+//! rewards of all members of such a pool that allow permissionless compounding via their
+//! `ClaimPermission` are then periodically bonded back into the pool in `on_idle`, at most once
+//! every [`Config::AutoCompoundPeriod`] blocks.
+//!
 //! For design docs see the [reward pool](#reward-pool) section.
 //!
 //! ### Leave
@@ -343,9 +352,10 @@
 //!
 //! ### Limitations
 //!
-//! * PoolMembers cannot vote with their staked funds because they are transferred into the pools
-//!   account. In the future this can be overcome by allowing the members to vote with their bonded
-//!   funds via vote splitting.
+//! * PoolMembers cannot vote with their staked funds if the runtime uses the
+//!   [`adapter::TransferStake`] strategy, because their funds are transferred into the pools
+//!   account. With [`adapter::DelegateStake`], funds are only delegated to the pool and stay held
+//!   in the member's account, so they can still be used to vote in governance.
 //! * PoolMembers cannot quickly transfer to another pool if they do no like nominations, instead
 //!   they must wait for the unbonding duration.
 
@@ -355,12 +365,13 @@ use codec::Codec;
 use frame_support::{
 	defensive, defensive_assert, ensure,
 	pallet_prelude::{MaxEncodedLen, *},
-	storage::bounded_btree_map::BoundedBTreeMap,
+	storage::{bounded_btree_map::BoundedBTreeMap, with_storage_layer},
 	traits::{
 		fungible::{Inspect, InspectFreeze, Mutate, MutateFreeze},
 		tokens::{Fortitude, Preservation},
 		Defensive, DefensiveOption, DefensiveResult, DefensiveSaturating, Get,
 	},
+	weights::WeightMeter,
 	DefaultNoBound, PalletError,
 };
 use frame_system::pallet_prelude::BlockNumberFor;
@@ -397,12 +408,15 @@ pub mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod adapter;
 pub mod migration;
 pub mod weights;
 
 pub use pallet::*;
 pub use weights::WeightInfo;
 
+use adapter::StakeStrategyType;
+
 /// The balance type used by the currency system.
 pub type BalanceOf<T> =
 	<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
@@ -425,7 +439,8 @@ pub enum ConfigOp<T: Codec + Debug> {
 }
 
 /// The type of bonding that can happen to a pool.
-enum BondType {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BondType {
 	/// Someone is bonding into the pool upon creation.
 	Create,
 	/// Someone is adding more funds later to this pool.
@@ -481,6 +496,26 @@ impl ClaimPermission {
 	}
 }
 
+/// Where the rewards of a pool member are paid out to.
+#[derive(Encode, Decode, MaxEncodedLen, Clone, Debug, PartialEq, Eq, TypeInfo)]
+pub enum RewardDestination<AccountId> {
+	/// Pay out rewards to the pool member's own account.
+	Member,
+	/// Pay out rewards to another account.
+	Account(AccountId),
+	/// Pay out rewards into the reward account of another pool, to be shared among its members.
+	///
+	/// If the pool no longer exists at the time of the payout, the rewards are paid out to the
+	/// pool member's own account instead.
+	Pool(PoolId),
+}
+
+impl<AccountId> Default for RewardDestination<AccountId> {
+	fn default() -> Self {
+		Self::Member
+	}
+}
+
 /// A member in a pool.
 #[derive(
 	Encode, Decode, MaxEncodedLen, TypeInfo, Debug, CloneNoBound, frame_support::PartialEqNoBound,
@@ -543,8 +578,7 @@ impl<T: Config> PoolMember<T> {
 
 	/// Total balance of the member, both active and unbonding.
 	/// Doesn't mutate state.
-	#[cfg(any(feature = "try-runtime", feature = "fuzzing", test, debug_assertions))]
-	fn total_balance(&self) -> BalanceOf<T> {
+	pub fn total_balance(&self) -> BalanceOf<T> {
 		let pool = BondedPool::<T>::get(self.pool_id).unwrap();
 		let active_balance = pool.points_to_balance(self.active_points());
 
@@ -989,8 +1023,7 @@ impl<T: Config> BondedPool<T> {
 	///
 	/// This is often used for bonding and issuing new funds into the pool.
 	fn balance_to_point(&self, new_funds: BalanceOf<T>) -> BalanceOf<T> {
-		let bonded_balance =
-			T::Staking::active_stake(&self.bonded_account()).unwrap_or(Zero::zero());
+		let bonded_balance = T::StakeAdapter::active_stake(&self.bonded_account());
 		Pallet::<T>::balance_to_point(bonded_balance, self.points, new_funds)
 	}
 
@@ -998,8 +1031,7 @@ impl<T: Config> BondedPool<T> {
 	///
 	/// This is often used for unbonding.
 	fn points_to_balance(&self, points: BalanceOf<T>) -> BalanceOf<T> {
-		let bonded_balance =
-			T::Staking::active_stake(&self.bonded_account()).unwrap_or(Zero::zero());
+		let bonded_balance = T::StakeAdapter::active_stake(&self.bonded_account());
 		Pallet::<T>::point_to_balance(bonded_balance, self.points, points)
 	}
 
@@ -1048,14 +1080,7 @@ impl<T: Config> BondedPool<T> {
 
 	/// The pools balance that is transferable provided it is expendable by staking pallet.
 	fn transferable_balance(&self) -> BalanceOf<T> {
-		let account = self.bonded_account();
-		// Note on why we can't use `Currency::reducible_balance`: Since pooled account has a
-		// provider (staking pallet), the account can not be set expendable by
-		// `pallet-nomination-pool`. This means reducible balance always returns balance preserving
-		// ED in the account. What we want though is transferable balance given the account can be
-		// dusted.
-		T::Currency::balance(&account)
-			.saturating_sub(T::Staking::active_stake(&account).unwrap_or_default())
+		T::StakeAdapter::transferable_balance(&self.bonded_account())
 	}
 
 	fn is_root(&self, who: &T::AccountId) -> bool {
@@ -1091,6 +1116,10 @@ impl<T: Config> BondedPool<T> {
 		self.is_root(who)
 	}
 
+	fn can_set_auto_compound(&self, who: &T::AccountId) -> bool {
+		self.is_root(who)
+	}
+
 	fn can_claim_commission(&self, who: &T::AccountId) -> bool {
 		if let Some(permission) = self.commission.claim_permission.as_ref() {
 			match permission {
@@ -1121,8 +1150,7 @@ impl<T: Config> BondedPool<T> {
 	fn ok_to_be_open(&self) -> Result<(), DispatchError> {
 		ensure!(!self.is_destroying(), Error::<T>::CanNotChangeState);
 
-		let bonded_balance =
-			T::Staking::active_stake(&self.bonded_account()).unwrap_or(Zero::zero());
+		let bonded_balance = T::StakeAdapter::active_stake(&self.bonded_account());
 		ensure!(!bonded_balance.is_zero(), Error::<T>::OverflowRisk);
 
 		let points_to_balance_ratio_floor = self
@@ -1241,8 +1269,8 @@ impl<T: Config> BondedPool<T> {
 	/// Bond exactly `amount` from `who`'s funds into this pool. Increases the [`TotalValueLocked`]
 	/// by `amount`.
 	///
-	/// If the bond is [`BondType::Create`], [`Staking::bond`] is called, and `who` is allowed to be
-	/// killed. Otherwise, [`Staking::bond_extra`] is called and `who` cannot be killed.
+	/// The funds are pledged to the pool through [`Config::StakeAdapter`]. If the bond is
+	/// [`BondType::Create`], `who` is allowed to be killed. Otherwise, `who` cannot be killed.
 	///
 	/// Returns `Ok(points_issues)`, `Err` otherwise.
 	fn try_bond_funds(
@@ -1251,28 +1279,20 @@ impl<T: Config> BondedPool<T> {
 		amount: BalanceOf<T>,
 		ty: BondType,
 	) -> Result<BalanceOf<T>, DispatchError> {
-		// Cache the value
-		let bonded_account = self.bonded_account();
-		T::Currency::transfer(
-			who,
-			&bonded_account,
-			amount,
-			match ty {
-				BondType::Create => Preservation::Expendable,
-				BondType::Later => Preservation::Preserve,
-			},
-		)?;
 		// We must calculate the points issued *before* we bond who's funds, else points:balance
 		// ratio will be wrong.
 		let points_issued = self.issue(amount);
 
-		match ty {
-			BondType::Create => T::Staking::bond(&bonded_account, amount, &self.reward_account())?,
-			// The pool should always be created in such a way its in a state to bond extra, but if
-			// the active balance is slashed below the minimum bonded or the account cannot be
-			// found, we exit early.
-			BondType::Later => T::Staking::bond_extra(&bonded_account, amount)?,
-		}
+		// The pool should always be created in such a way its in a state to bond extra, but if
+		// the active balance is slashed below the minimum bonded or the account cannot be found,
+		// we exit early.
+		T::StakeAdapter::pledge_bond(
+			who,
+			&self.bonded_account(),
+			&self.reward_account(),
+			amount,
+			ty,
+		)?;
 		TotalValueLocked::<T>::mutate(|tvl| {
 			tvl.saturating_accrue(amount);
 		});
@@ -1563,7 +1583,7 @@ impl<T: Config> Get<u32> for TotalUnbondingPools<T> {
 		// NOTE: this may be dangerous in the scenario bonding_duration gets decreased because
 		// we would no longer be able to decode `BoundedBTreeMap::<EraIndex, UnbondPool<T>,
 		// TotalUnbondingPools<T>>`, which uses `TotalUnbondingPools` as the bound
-		T::Staking::bonding_duration() + T::PostUnbondingPoolsWindow::get()
+		T::StakeAdapter::bonding_duration() + T::PostUnbondingPoolsWindow::get()
 	}
 }
 
@@ -1575,7 +1595,7 @@ pub mod pallet {
 	use sp_runtime::Perbill;
 
 	/// The in-code storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(8);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(9);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -1639,8 +1659,15 @@ pub mod pallet {
 		/// Infallible method for converting `U256` to `Currency::Balance`.
 		type U256ToBalance: Convert<U256, BalanceOf<Self>>;
 
-		/// The interface for nominating.
-		type Staking: StakingInterface<Balance = BalanceOf<Self>, AccountId = Self::AccountId>;
+		/// The adapter used to bond the funds of pool members into the staking system.
+		///
+		/// Use [`adapter::TransferStake`] to transfer member funds into the pool account, or
+		/// [`adapter::DelegateStake`] to only delegate them, so that they stay in the member's
+		/// account.
+		type StakeAdapter: adapter::StakeStrategy<
+			Balance = BalanceOf<Self>,
+			AccountId = Self::AccountId,
+		>;
 
 		/// The amount of eras a `SubPools::with_era` pool can exist before it gets merged into the
 		/// `SubPools::no_era` pool. In other words, this is the amount of eras a member will be
@@ -1654,6 +1681,15 @@ pub mod pallet {
 
 		/// The origin that can manage pool configurations.
 		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The minimum number of blocks between the start of two consecutive auto-compounding
+		/// sweeps over the pool members.
+		///
+		/// A sweep compounds the pending rewards of all members of auto-compounding pools who
+		/// allow permissionless compounding, and is spread over as many blocks as needed in
+		/// `on_idle`.
+		#[pallet::constant]
+		type AutoCompoundPeriod: Get<BlockNumberFor<Self>>;
 	}
 
 	/// The sum of funds across all pools.
@@ -1744,6 +1780,36 @@ pub mod pallet {
 	pub type ClaimPermissions<T: Config> =
 		StorageMap<_, Twox64Concat, T::AccountId, ClaimPermission, ValueQuery>;
 
+	/// Map from a pool member account to the destination of their rewards.
+	#[pallet::storage]
+	pub type RewardDestinations<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, RewardDestination<T::AccountId>, ValueQuery>;
+
+	/// Pools that have opted in to auto-compounding the rewards of their members.
+	///
+	/// Only members that allow permissionless compounding via their [`ClaimPermission`] are
+	/// compounded.
+	#[pallet::storage]
+	pub type AutoCompoundPools<T: Config> = CountedStorageMap<_, Twox64Concat, PoolId, ()>;
+
+	/// Pool members that allow permissionless compounding via their [`ClaimPermission`], indexed
+	/// by their pool.
+	///
+	/// Auto-compounding sweeps only iterate over the members of [`AutoCompoundPools`] in here.
+	#[pallet::storage]
+	pub type AutoCompoundMembers<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Twox64Concat, T::AccountId, ()>;
+
+	/// The pool processed by the ongoing auto-compounding sweep, together with the last of its
+	/// members that was processed, if any.
+	#[pallet::storage]
+	pub type AutoCompoundCursor<T: Config> =
+		StorageValue<_, (PoolId, Option<T::AccountId>), OptionQuery>;
+
+	/// The block number from which the next auto-compounding sweep can start.
+	#[pallet::storage]
+	pub type NextAutoCompoundSweep<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub min_join_bond: BalanceOf<T>,
@@ -1867,6 +1933,10 @@ pub mod pallet {
 		MinBalanceDeficitAdjusted { pool_id: PoolId, amount: BalanceOf<T> },
 		/// Claimed excess frozen ED of af the reward pool.
 		MinBalanceExcessAdjusted { pool_id: PoolId, amount: BalanceOf<T> },
+		/// Auto-compounding of member rewards has been enabled or disabled for a pool.
+		AutoCompoundUpdated { pool_id: PoolId, enabled: bool },
+		/// A member has set the destination of their rewards.
+		RewardDestinationSet { member: T::AccountId, destination: RewardDestination<T::AccountId> },
 	}
 
 	#[pallet::error]
@@ -1944,6 +2014,16 @@ pub mod pallet {
 		BondExtraRestricted,
 		/// No imbalance in the ED deposit for the pool.
 		NothingToAdjust,
+		/// No slash pending that can be applied to the member.
+		NothingToSlash,
+		/// The pool or member delegation has already migrated to delegate stake.
+		AlreadyMigrated,
+		/// The pool or member delegation has not migrated yet to delegate stake.
+		NotMigrated,
+		/// This call is not allowed in the current state of the pallet.
+		NotSupported,
+		/// The reward destination is not valid for this member.
+		InvalidRewardDestination,
 	}
 
 	#[derive(Encode, Decode, PartialEq, TypeInfo, PalletError, RuntimeDebug)]
@@ -2000,6 +2080,9 @@ pub mod pallet {
 			// If a member already exists that means they already belong to a pool
 			ensure!(!PoolMembers::<T>::contains_key(&who), Error::<T>::AccountBelongsToOtherPool);
 
+			// ensure pool is not in an un-migrated state.
+			ensure!(!Self::api_pool_needs_delegate_migration(pool_id), Error::<T>::NotMigrated);
+
 			let mut bonded_pool = BondedPool::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
 			bonded_pool.ok_to_join()?;
 
@@ -2115,6 +2198,12 @@ pub mod pallet {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			let member_account = T::Lookup::lookup(member_account)?;
+			// ensure member is not in an un-migrated state.
+			ensure!(
+				!Self::api_member_needs_delegate_migration(member_account.clone()),
+				Error::<T>::NotMigrated
+			);
+
 			let (mut member, mut bonded_pool, mut reward_pool) =
 				Self::get_member_with_pools(&member_account)?;
 
@@ -2135,12 +2224,12 @@ pub mod pallet {
 				&mut reward_pool,
 			)?;
 
-			let current_era = T::Staking::current_era();
-			let unbond_era = T::Staking::bonding_duration().saturating_add(current_era);
+			let current_era = T::StakeAdapter::current_era();
+			let unbond_era = T::StakeAdapter::bonding_duration().saturating_add(current_era);
 
 			// Unbond in the actual underlying nominator.
 			let unbonding_balance = bonded_pool.dissolve(unbonding_points);
-			T::Staking::unbond(&bonded_pool.bonded_account(), unbonding_balance)?;
+			T::StakeAdapter::unbond(&bonded_pool.bonded_account(), unbonding_balance)?;
 
 			// Note that we lazily create the unbonding pools here if they don't already exist
 			let mut sub_pools = SubPoolsStorage::<T>::get(member.pool_id)
@@ -2203,7 +2292,7 @@ pub mod pallet {
 			// For now we only allow a pool to withdraw unbonded if its not destroying. If the pool
 			// is destroying then `withdraw_unbonded` can be used.
 			ensure!(pool.state != PoolState::Destroying, Error::<T>::NotDestroying);
-			T::Staking::withdraw_unbonded(pool.bonded_account(), num_slashing_spans)?;
+			T::StakeAdapter::withdraw_unbonded(&pool.bonded_account(), num_slashing_spans)?;
 
 			Ok(())
 		}
@@ -2240,7 +2329,17 @@ pub mod pallet {
 			let member_account = T::Lookup::lookup(member_account)?;
 			let mut member =
 				PoolMembers::<T>::get(&member_account).ok_or(Error::<T>::PoolMemberNotFound)?;
-			let current_era = T::Staking::current_era();
+			let current_era = T::StakeAdapter::current_era();
+
+			// ensure pool and member are not in an un-migrated state.
+			ensure!(
+				!Self::api_pool_needs_delegate_migration(member.pool_id),
+				Error::<T>::NotMigrated
+			);
+			ensure!(
+				!Self::api_member_needs_delegate_migration(member_account.clone()),
+				Error::<T>::NotMigrated
+			);
 
 			let bonded_pool = BondedPool::<T>::get(member.pool_id)
 				.defensive_ok_or::<Error<T>>(DefensiveError::PoolNotFound.into())?;
@@ -2249,14 +2348,28 @@ pub mod pallet {
 
 			bonded_pool.ok_to_withdraw_unbonded_with(&caller, &member_account)?;
 
+			// Any slash that is still pending for the member must be applied before their funds
+			// are released, otherwise the slashed part would be left behind in their delegation.
+			let pending_slash = Self::member_pending_slash(&member_account, &member);
+			if !pending_slash.is_zero() {
+				T::StakeAdapter::member_slash(
+					&member_account,
+					&bonded_pool.bonded_account(),
+					pending_slash,
+					None,
+				)?;
+			}
+
 			// NOTE: must do this after we have done the `ok_to_withdraw_unbonded_other_with` check.
 			let withdrawn_points = member.withdraw_unlocked(current_era);
 			ensure!(!withdrawn_points.is_empty(), Error::<T>::CannotWithdrawAny);
 
 			// Before calculating the `balance_to_unbond`, we call withdraw unbonded to ensure the
 			// `transferrable_balance` is correct.
-			let stash_killed =
-				T::Staking::withdraw_unbonded(bonded_pool.bonded_account(), num_slashing_spans)?;
+			let stash_killed = T::StakeAdapter::withdraw_unbonded(
+				&bonded_pool.bonded_account(),
+				num_slashing_spans,
+			)?;
 
 			// defensive-only: the depositor puts enough funds into the stash so that it will only
 			// be destroyed when they are leaving.
@@ -2291,11 +2404,11 @@ pub mod pallet {
 				// order to ensure members can leave the pool and it can be destroyed.
 				.min(bonded_pool.transferable_balance());
 
-			T::Currency::transfer(
-				&bonded_pool.bonded_account(),
+			T::StakeAdapter::member_withdraw(
 				&member_account,
+				&bonded_pool.bonded_account(),
 				balance_to_unbond,
-				Preservation::Expendable,
+				num_slashing_spans,
 			)
 			.defensive()?;
 
@@ -2309,6 +2422,8 @@ pub mod pallet {
 			let post_info_weight = if member.total_points().is_zero() {
 				// remove any `ClaimPermission` associated with the member.
 				ClaimPermissions::<T>::remove(&member_account);
+				AutoCompoundMembers::<T>::remove(member.pool_id, &member_account);
+				RewardDestinations::<T>::remove(&member_account);
 
 				// member being reaped.
 				PoolMembers::<T>::remove(&member_account);
@@ -2427,7 +2542,7 @@ pub mod pallet {
 				Error::<T>::MinimumBondNotMet
 			);
 
-			T::Staking::nominate(&bonded_pool.bonded_account(), validators)
+			T::StakeAdapter::nominate(&bonded_pool.bonded_account(), validators)
 		}
 
 		/// Set a new state for the pool.
@@ -2615,12 +2730,12 @@ pub mod pallet {
 				.active_points();
 
 			if bonded_pool.points_to_balance(depositor_points) >=
-				T::Staking::minimum_nominator_bond()
+				T::StakeAdapter::minimum_nominator_bond()
 			{
 				ensure!(bonded_pool.can_nominate(&who), Error::<T>::NotNominator);
 			}
 
-			T::Staking::chill(&bonded_pool.bonded_account())
+			T::StakeAdapter::chill(&bonded_pool.bonded_account())
 		}
 
 		/// `origin` bonds funds from `extra` for some pool member `member` into their respective
@@ -2654,14 +2769,24 @@ pub mod pallet {
 		/// * `origin` - Member of a pool.
 		/// * `permission` - The permission to be applied.
 		#[pallet::call_index(15)]
-		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		// `AutoCompoundMembers` is written on top of the benchmarked storage accesses.
+		#[pallet::weight(
+			T::WeightInfo::set_claim_permission().saturating_add(T::DbWeight::get().writes(1))
+		)]
 		pub fn set_claim_permission(
 			origin: OriginFor<T>,
 			permission: ClaimPermission,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			ensure!(PoolMembers::<T>::contains_key(&who), Error::<T>::PoolMemberNotFound);
+			let member = PoolMembers::<T>::get(&who).ok_or(Error::<T>::PoolMemberNotFound)?;
+
+			// keep track of the members that can be auto-compounded.
+			if permission.can_bond_extra() {
+				AutoCompoundMembers::<T>::insert(member.pool_id, &who, ());
+			} else {
+				AutoCompoundMembers::<T>::remove(member.pool_id, &who);
+			}
 
 			ClaimPermissions::<T>::mutate(who, |source| {
 				*source = permission;
@@ -2817,10 +2942,196 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Apply a pending slash on a member.
+		///
+		/// Fails unless [`crate::pallet::Config::StakeAdapter`] is of strategy type:
+		/// [`adapter::StakeStrategyType::Delegate`].
+		///
+		/// This call can be dispatched permissionlessly (i.e. by any account). If the member has
+		/// slash to be applied, caller may be rewarded with the part of the slash.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::apply_slash())]
+		pub fn apply_slash(
+			origin: OriginFor<T>,
+			member_account: AccountIdLookupOf<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure!(
+				T::StakeAdapter::strategy_type() == StakeStrategyType::Delegate,
+				Error::<T>::NotSupported
+			);
+
+			let who = ensure_signed(origin)?;
+			let member_account = T::Lookup::lookup(member_account)?;
+			Self::do_apply_slash(&member_account, Some(who))?;
+
+			// If successful, refund the fees.
+			Ok(Pays::No.into())
+		}
+
+		/// Migrates delegated funds from the pool account to the `member_account`.
+		///
+		/// Fails unless [`crate::pallet::Config::StakeAdapter`] is of strategy type:
+		/// [`adapter::StakeStrategyType::Delegate`].
+		///
+		/// This is a permission-less call and refunds any fee if claim is successful.
+		///
+		/// If the pool has migrated to delegation based staking, the staked tokens of pool members
+		/// can be moved and held in their own account. See [`adapter::DelegateStake`]
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::migrate_delegation())]
+		pub fn migrate_delegation(
+			origin: OriginFor<T>,
+			member_account: AccountIdLookupOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let _caller = ensure_signed(origin)?;
+
+			ensure!(
+				T::StakeAdapter::strategy_type() == StakeStrategyType::Delegate,
+				Error::<T>::NotSupported
+			);
+
+			let member_account = T::Lookup::lookup(member_account)?;
+			let member =
+				PoolMembers::<T>::get(&member_account).ok_or(Error::<T>::PoolMemberNotFound)?;
+
+			// ensure pool is migrated.
+			ensure!(
+				!Self::api_pool_needs_delegate_migration(member.pool_id),
+				Error::<T>::NotMigrated
+			);
+			// ensure member has not migrated yet.
+			ensure!(
+				T::StakeAdapter::member_delegation_balance(&member_account).is_none(),
+				Error::<T>::AlreadyMigrated
+			);
+
+			let pool_contribution = member.total_balance();
+			// the member must have some contribution to be migrated.
+			ensure!(!pool_contribution.is_zero(), Error::<T>::MinimumBondNotMet);
+
+			T::StakeAdapter::migrate_delegation(
+				&Pallet::<T>::create_bonded_account(member.pool_id),
+				&member_account,
+				pool_contribution,
+			)?;
+
+			// if successful, we refund the fee.
+			Ok(Pays::No.into())
+		}
+
+		/// Migrate pool from [`adapter::StakeStrategyType::Transfer`] to
+		/// [`adapter::StakeStrategyType::Delegate`].
+		///
+		/// Fails unless [`crate::pallet::Config::StakeAdapter`] is of strategy type:
+		/// [`adapter::StakeStrategyType::Delegate`].
+		///
+		/// This call can be dispatched permissionlessly, and refunds any fee if successful.
+		///
+		/// If the pool has already migrated to delegation based staking, this call will fail.
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::pool_migrate())]
+		pub fn migrate_pool_to_delegate_stake(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+		) -> DispatchResultWithPostInfo {
+			let _caller = ensure_signed(origin)?;
+
+			// gate this call to be called only if `DelegateStake` strategy is used.
+			ensure!(
+				T::StakeAdapter::strategy_type() == StakeStrategyType::Delegate,
+				Error::<T>::NotSupported
+			);
+			// ensure pool exists.
+			let bonded_pool = BondedPool::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			ensure!(
+				T::StakeAdapter::pool_strategy(&bonded_pool.bonded_account()) ==
+					StakeStrategyType::Transfer,
+				Error::<T>::AlreadyMigrated
+			);
+
+			T::StakeAdapter::migrate_nominator_to_agent(
+				&bonded_pool.bonded_account(),
+				&bonded_pool.reward_account(),
+			)?;
+
+			// if successful, we refund the fee.
+			Ok(Pays::No.into())
+		}
+
+		/// Enable or disable auto-compounding of member rewards for a pool.
+		///
+		/// When enabled, the pending rewards of every member of the pool whose
+		/// [`ClaimPermission`] allows permissionless compounding are periodically bonded back into
+		/// the pool in `on_idle`, at most once every [`Config::AutoCompoundPeriod`] blocks.
+		///
+		/// The dispatch origin of this call must be signed by the root role of the pool.
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::set_auto_compound())]
+		pub fn set_auto_compound(
+			origin: OriginFor<T>,
+			pool_id: PoolId,
+			enabled: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let bonded_pool = BondedPool::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			ensure!(bonded_pool.can_set_auto_compound(&who), Error::<T>::DoesNotHavePermission);
+
+			if enabled {
+				AutoCompoundPools::<T>::insert(pool_id, ());
+			} else {
+				AutoCompoundPools::<T>::remove(pool_id);
+			}
+
+			Self::deposit_event(Event::<T>::AutoCompoundUpdated { pool_id, enabled });
+			Ok(())
+		}
+
+		/// Set the destination of the rewards of the calling pool member.
+		///
+		/// The destination is used whenever rewards are paid out to the member, i.e. when calling
+		/// [`Call::claim_payout`], [`Call::claim_payout_other`] or [`Call::unbond`], or when
+		/// bonding extra from the free balance. Rewards that are bonded back into the pool, either
+		/// through [`BondExtra::Rewards`] or auto-compounding, are not affected.
+		///
+		/// # Arguments
+		///
+		/// * `origin` - Member of a pool.
+		/// * `destination` - The reward destination. A [`RewardDestination::Pool`] must be an
+		///   existing pool other than the member's own pool.
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::set_reward_destination())]
+		pub fn set_reward_destination(
+			origin: OriginFor<T>,
+			destination: RewardDestination<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let member = PoolMembers::<T>::get(&who).ok_or(Error::<T>::PoolMemberNotFound)?;
+
+			match destination {
+				RewardDestination::Member => RewardDestinations::<T>::remove(&who),
+				RewardDestination::Pool(pool_id) => {
+					ensure!(
+						pool_id != member.pool_id && BondedPools::<T>::contains_key(pool_id),
+						Error::<T>::InvalidRewardDestination
+					);
+					RewardDestinations::<T>::insert(&who, &destination);
+				},
+				RewardDestination::Account(_) =>
+					RewardDestinations::<T>::insert(&who, &destination),
+			}
+
+			Self::deposit_event(Event::<T>::RewardDestinationSet { member: who, destination });
+			Ok(())
+		}
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::do_auto_compound(now, remaining_weight)
+		}
+
 		#[cfg(feature = "try-runtime")]
 		fn try_state(_n: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
 			Self::do_try_state(u8::MAX)
@@ -2832,7 +3143,7 @@ pub mod pallet {
 				"Minimum points to balance ratio must be greater than 0"
 			);
 			assert!(
-				T::Staking::bonding_duration() < TotalUnbondingPools::<T>::get(),
+				T::StakeAdapter::bonding_duration() < TotalUnbondingPools::<T>::get(),
 				"There must be more unbonding pools then the bonding duration /
 				so a slash can be applied to relevant unbonding pools. (We assume /
 				the bonding duration > slash deffer duration.",
@@ -2850,7 +3161,7 @@ impl<T: Config> Pallet<T> {
 	/// It is essentially `max { MinNominatorBond, MinCreateBond, MinJoinBond }`, where the former
 	/// is coming from the staking pallet and the latter two are configured in this pallet.
 	pub fn depositor_min_bond() -> BalanceOf<T> {
-		T::Staking::minimum_nominator_bond()
+		T::StakeAdapter::minimum_nominator_bond()
 			.max(MinCreateBond::<T>::get())
 			.max(MinJoinBond::<T>::get())
 			.max(T::Currency::minimum_balance())
@@ -2886,7 +3197,7 @@ impl<T: Config> Pallet<T> {
 			"bonded account of dissolving pool should have no consumers"
 		);
 		defensive_assert!(
-			T::Staking::total_stake(&bonded_account).unwrap_or_default() == Zero::zero(),
+			T::StakeAdapter::total_stake(&bonded_account) == Zero::zero(),
 			"dissolving pool should not have any stake in the staking pallet"
 		);
 
@@ -2919,6 +3230,7 @@ impl<T: Config> Pallet<T> {
 		Self::deposit_event(Event::<T>::Destroyed { pool_id: bonded_pool.id });
 		// Remove bonded pool metadata.
 		Metadata::<T>::remove(bonded_pool.id);
+		AutoCompoundPools::<T>::remove(bonded_pool.id);
 
 		bonded_pool.remove();
 	}
@@ -3016,7 +3328,20 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
-	/// If the member has some rewards, transfer a payout from the reward pool to the member.
+	/// The account that the rewards of `member_account` should be paid out to, based on their
+	/// [`RewardDestination`].
+	fn reward_payee(member_account: &T::AccountId) -> T::AccountId {
+		match RewardDestinations::<T>::get(member_account) {
+			RewardDestination::Account(payee) => payee,
+			RewardDestination::Pool(pool_id) if BondedPools::<T>::contains_key(pool_id) =>
+				Self::create_reward_account(pool_id),
+			// the destination pool has been dissolved.
+			RewardDestination::Pool(_) | RewardDestination::Member => member_account.clone(),
+		}
+	}
+
+	/// If the member has some rewards, transfer a payout from the reward pool to the member's
+	/// reward destination.
 	// Emits events and potentially modifies pool state if any arithmetic saturates, but does
 	// not persist any of the mutable inputs to storage.
 	fn do_reward_payout(
@@ -3024,6 +3349,21 @@ impl<T: Config> Pallet<T> {
 		member: &mut PoolMember<T>,
 		bonded_pool: &mut BondedPool<T>,
 		reward_pool: &mut RewardPool<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let payee = Self::reward_payee(member_account);
+		Self::do_reward_payout_to(member_account, &payee, member, bonded_pool, reward_pool)
+	}
+
+	/// If the member has some rewards, transfer a payout from the reward pool to `payee`.
+	///
+	/// If `payee` is not the member itself and cannot receive the payout, e.g. because it would
+	/// stay below the existential deposit, the payout is transferred to the member instead.
+	fn do_reward_payout_to(
+		member_account: &T::AccountId,
+		payee: &T::AccountId,
+		member: &mut PoolMember<T>,
+		bonded_pool: &mut BondedPool<T>,
+		reward_pool: &mut RewardPool<T>,
 	) -> Result<BalanceOf<T>, DispatchError> {
 		debug_assert_eq!(member.pool_id, bonded_pool.id);
 		debug_assert_eq!(&mut PoolMembers::<T>::get(member_account).unwrap(), member);
@@ -3048,14 +3388,25 @@ impl<T: Config> Pallet<T> {
 		member.last_recorded_reward_counter = current_reward_counter;
 		reward_pool.register_claimed_reward(pending_rewards);
 
-		T::Currency::transfer(
-			&bonded_pool.reward_account(),
-			member_account,
-			pending_rewards,
-			// defensive: the depositor has put existential deposit into the pool and it stays
-			// untouched, reward account shall not die.
-			Preservation::Preserve,
-		)?;
+		let reward_account = bonded_pool.reward_account();
+		// defensive: the depositor has put existential deposit into the pool and it stays
+		// untouched, reward account shall not die.
+		if payee == member_account ||
+			T::Currency::transfer(
+				&reward_account,
+				payee,
+				pending_rewards,
+				Preservation::Preserve,
+			)
+			.is_err()
+		{
+			T::Currency::transfer(
+				&reward_account,
+				member_account,
+				pending_rewards,
+				Preservation::Preserve,
+			)?;
+		}
 
 		Self::deposit_event(Event::<T>::PaidOut {
 			member: member_account.clone(),
@@ -3157,6 +3508,13 @@ impl<T: Config> Pallet<T> {
 		let (mut member, mut bonded_pool, mut reward_pool) =
 			Self::get_member_with_pools(&member_account)?;
 
+		// ensure pool and member are not in an un-migrated state.
+		ensure!(!Self::api_pool_needs_delegate_migration(member.pool_id), Error::<T>::NotMigrated);
+		ensure!(
+			!Self::api_member_needs_delegate_migration(member_account.clone()),
+			Error::<T>::NotMigrated
+		);
+
 		// payout related stuff: we must claim the payouts, and updated recorded payout data
 		// before updating the bonded pool points, similar to that of `join` transaction.
 		reward_pool.update_records(
@@ -3164,8 +3522,15 @@ impl<T: Config> Pallet<T> {
 			bonded_pool.points,
 			bonded_pool.commission.current(),
 		)?;
-		let claimed = Self::do_reward_payout(
+		// rewards that are bonded must be paid out to the member, regardless of their reward
+		// destination.
+		let payee = match extra {
+			BondExtra::FreeBalance(_) => Self::reward_payee(&member_account),
+			BondExtra::Rewards => member_account.clone(),
+		};
+		let claimed = Self::do_reward_payout_to(
 			&member_account,
+			&payee,
 			&mut member,
 			&mut bonded_pool,
 			&mut reward_pool,
@@ -3261,6 +3626,99 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Continue the ongoing auto-compounding sweep over the pools in [`AutoCompoundPools`], or
+	/// start a new one if it is due, spending at most `limit` weight. Returns the consumed weight.
+	///
+	/// Only the members of each pool in [`AutoCompoundMembers`] are visited, and their pending
+	/// rewards are bonded back into the pool. Failures to compound a single member are ignored,
+	/// and the member is retried in the next sweep.
+	pub(crate) fn do_auto_compound(now: BlockNumberFor<T>, limit: Weight) -> Weight {
+		let mut meter = WeightMeter::with_limit(limit);
+		// the cursor, the next sweep, and the auto-compounding pools counter.
+		let base_weight = T::DbWeight::get().reads_writes(3, 2);
+		// finding the next auto-compounding pool and checking that it is still enabled.
+		let pool_weight = T::DbWeight::get().reads(2);
+		// the next indexed member and their pending rewards.
+		let check_weight = T::DbWeight::get().reads(5);
+		let compound_weight = T::WeightInfo::bond_extra_other();
+		let member_weight = check_weight.saturating_add(compound_weight);
+
+		if !meter.can_consume(base_weight.saturating_add(pool_weight).saturating_add(member_weight))
+		{
+			return Weight::zero()
+		}
+		meter.consume(base_weight);
+
+		if AutoCompoundPools::<T>::count().is_zero() {
+			return meter.consumed()
+		}
+
+		let (mut pool_id, mut last) = match AutoCompoundCursor::<T>::get() {
+			Some(cursor) => cursor,
+			None if now >= NextAutoCompoundSweep::<T>::get() => {
+				NextAutoCompoundSweep::<T>::put(now.saturating_add(T::AutoCompoundPeriod::get()));
+				meter.consume(pool_weight);
+				match AutoCompoundPools::<T>::iter_keys().next() {
+					Some(pool_id) => (pool_id, None),
+					None => return meter.consumed(),
+				}
+			},
+			None => return meter.consumed(),
+		};
+
+		loop {
+			// the pool may have stopped auto-compounding during the sweep.
+			if AutoCompoundPools::<T>::contains_key(pool_id) {
+				let mut members = match last {
+					Some(ref who) => AutoCompoundMembers::<T>::iter_key_prefix_from(
+						pool_id,
+						AutoCompoundMembers::<T>::hashed_key_for(pool_id, who),
+					),
+					None => AutoCompoundMembers::<T>::iter_key_prefix(pool_id),
+				};
+
+				loop {
+					if !meter.can_consume(member_weight) {
+						AutoCompoundCursor::<T>::put((pool_id, last));
+						return meter.consumed()
+					}
+					let Some(who) = members.next() else { break };
+					meter.consume(check_weight);
+
+					if Self::api_pending_rewards(who.clone()).map_or(false, |r| !r.is_zero()) {
+						meter.consume(compound_weight);
+						let _ = with_storage_layer(|| {
+							Self::do_bond_extra(who.clone(), who.clone(), BondExtra::Rewards)
+						});
+					}
+					last = Some(who);
+				}
+			}
+
+			// move on to the next pool, if there is enough weight left to make progress in it.
+			if !meter.can_consume(pool_weight.saturating_add(member_weight)) {
+				AutoCompoundCursor::<T>::put((pool_id, last));
+				return meter.consumed()
+			}
+			meter.consume(pool_weight);
+
+			let next =
+				AutoCompoundPools::<T>::iter_from(AutoCompoundPools::<T>::hashed_key_for(pool_id))
+					.next();
+			match next {
+				Some((next_pool_id, _)) => {
+					pool_id = next_pool_id;
+					last = None;
+				},
+				None => {
+					// the sweep is complete.
+					AutoCompoundCursor::<T>::kill();
+					return meter.consumed()
+				},
+			}
+		}
+	}
+
 	fn do_adjust_pool_deposit(who: T::AccountId, pool: PoolId) -> DispatchResult {
 		let bonded_pool = BondedPool::<T>::get(pool).ok_or(Error::<T>::PoolNotFound)?;
 		let reward_acc = &bonded_pool.reward_account();
@@ -3296,6 +3754,47 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Slash `member_account` by the slash that is still pending for them in their pool.
+	///
+	/// Errors if there is nothing to slash.
+	fn do_apply_slash(
+		member_account: &T::AccountId,
+		reporter: Option<T::AccountId>,
+	) -> DispatchResult {
+		let member = PoolMembers::<T>::get(member_account).ok_or(Error::<T>::PoolMemberNotFound)?;
+
+		let pending_slash = Self::member_pending_slash(member_account, &member);
+		ensure!(!pending_slash.is_zero(), Error::<T>::NothingToSlash);
+
+		T::StakeAdapter::member_slash(
+			member_account,
+			&Self::create_bonded_account(member.pool_id),
+			pending_slash,
+			reporter,
+		)
+	}
+
+	/// The slash that has been applied to the pool of `member` but not yet to the funds the
+	/// member delegated to it.
+	///
+	/// Always zero for pools that do not defer slashes, such as pools using
+	/// [`adapter::TransferStake`].
+	fn member_pending_slash(member_account: &T::AccountId, member: &PoolMember<T>) -> BalanceOf<T> {
+		// if the pool doesn't have any pending slash, it implies the member also does not have any
+		// pending slash.
+		if !T::StakeAdapter::has_pending_slash(&Self::create_bonded_account(member.pool_id)) {
+			return Zero::zero()
+		}
+
+		// this is their actual held balance that may or may not have been slashed.
+		let actual_balance =
+			T::StakeAdapter::member_delegation_balance(member_account).unwrap_or_default();
+		// this is their balance in the pool, which already accounts for any slash.
+		let expected_balance = member.total_balance();
+
+		actual_balance.saturating_sub(expected_balance)
+	}
+
 	/// Apply freeze on reward account to restrict it from going below ED.
 	pub(crate) fn freeze_pool_deposit(reward_acc: &T::AccountId) -> DispatchResult {
 		T::Currency::set_freeze(
@@ -3367,6 +3866,30 @@ impl<T: Config> Pallet<T> {
 			Metadata::<T>::iter_keys().all(|k| bonded_pools.contains(&k)),
 			"`Metadata` keys must be a subset of the above superset."
 		);
+		ensure!(
+			AutoCompoundPools::<T>::iter_keys().all(|k| bonded_pools.contains(&k)),
+			"`AutoCompoundPools` keys must be a subset of the above superset."
+		);
+		ensure!(
+			RewardDestinations::<T>::iter_keys().all(|k| PoolMembers::<T>::contains_key(k)),
+			"`RewardDestinations` keys must all be pool members."
+		);
+		ensure!(
+			AutoCompoundMembers::<T>::iter_keys().all(|(pool_id, who)| {
+				PoolMembers::<T>::get(&who).map_or(false, |m| m.pool_id == pool_id) &&
+					ClaimPermissions::<T>::get(&who).can_bond_extra()
+			}),
+			"`AutoCompoundMembers` must only index members of their pool that allow compounding."
+		);
+		ensure!(
+			ClaimPermissions::<T>::iter().all(|(who, permission)| {
+				!permission.can_bond_extra() ||
+					PoolMembers::<T>::get(&who).map_or(false, |m| {
+						AutoCompoundMembers::<T>::contains_key(m.pool_id, &who)
+					})
+			}),
+			"Members that allow compounding must be in `AutoCompoundMembers`."
+		);
 
 		ensure!(
 			MaxPools::<T>::get().map_or(true, |max| bonded_pools.len() <= (max as usize)),
@@ -3459,8 +3982,7 @@ impl<T: Config> Pallet<T> {
 				pool is being destroyed and the depositor is the last member",
 			);
 
-			expected_tvl +=
-				T::Staking::total_stake(&bonded_pool.bonded_account()).unwrap_or_default();
+			expected_tvl += T::StakeAdapter::total_stake(&bonded_pool.bonded_account());
 
 			Ok(())
 		})?;
@@ -3489,8 +4011,8 @@ impl<T: Config> Pallet<T> {
 			let subs = SubPoolsStorage::<T>::get(pool_id).unwrap_or_default();
 
 			let sum_unbonding_balance = subs.sum_unbonding_balance();
-			let bonded_balance = T::Staking::active_stake(&pool_account).unwrap_or_default();
-			let total_balance = T::Currency::total_balance(&pool_account);
+			let bonded_balance = T::StakeAdapter::active_stake(&pool_account);
+			let total_balance = T::StakeAdapter::total_balance(&pool_account);
 
 			assert!(
 				total_balance >= bonded_balance + sum_unbonding_balance,
@@ -3593,13 +4115,75 @@ impl<T: Config> Pallet<T> {
 	/// If the pool ID does not exist, returns 0 ratio balance to points. Used by runtime API.
 	pub fn api_balance_to_points(pool_id: PoolId, new_funds: BalanceOf<T>) -> BalanceOf<T> {
 		if let Some(pool) = BondedPool::<T>::get(pool_id) {
-			let bonded_balance =
-				T::Staking::active_stake(&pool.bonded_account()).unwrap_or(Zero::zero());
+			let bonded_balance = T::StakeAdapter::active_stake(&pool.bonded_account());
 			Pallet::<T>::balance_to_point(bonded_balance, pool.points, new_funds)
 		} else {
 			Zero::zero()
 		}
 	}
+
+	/// Returns the unapplied slash of the pool.
+	///
+	/// Pending slash is only applicable with [`adapter::DelegateStake`] strategy. Used by runtime
+	/// API.
+	pub fn api_pool_pending_slash(pool_id: PoolId) -> BalanceOf<T> {
+		T::StakeAdapter::pending_slash(&Self::create_bonded_account(pool_id))
+	}
+
+	/// Returns the unapplied slash of a member.
+	///
+	/// Pending slash is only applicable with [`adapter::DelegateStake`] strategy. Used by runtime
+	/// API.
+	pub fn api_member_pending_slash(who: T::AccountId) -> BalanceOf<T> {
+		PoolMembers::<T>::get(&who)
+			.map(|member| Self::member_pending_slash(&who, &member))
+			.unwrap_or_default()
+	}
+
+	/// Checks whether pool needs to be migrated to [`adapter::StakeStrategyType::Delegate`].
+	///
+	/// Only applicable when the [`Config::StakeAdapter`] is of type
+	/// [`adapter::StakeStrategyType::Delegate`]. Used by runtime API.
+	pub fn api_pool_needs_delegate_migration(pool_id: PoolId) -> bool {
+		// if the `Delegate` strategy is not used in the pallet, no pool needs migration.
+		if T::StakeAdapter::strategy_type() != StakeStrategyType::Delegate {
+			return false
+		}
+
+		// if the pool does not exist, there is nothing to migrate.
+		if !BondedPools::<T>::contains_key(pool_id) {
+			return false
+		}
+
+		// true if the pool is still not using the `Delegate` strategy.
+		T::StakeAdapter::pool_strategy(&Self::create_bonded_account(pool_id)) !=
+			StakeStrategyType::Delegate
+	}
+
+	/// Checks whether the delegation of a pool member needs to be migrated.
+	///
+	/// Only applicable when the [`Config::StakeAdapter`] is of type
+	/// [`adapter::StakeStrategyType::Delegate`], and the pool of the member has already been
+	/// migrated. Used by runtime API.
+	pub fn api_member_needs_delegate_migration(who: T::AccountId) -> bool {
+		// if the `Delegate` strategy is not used in the pallet, no member needs migration.
+		if T::StakeAdapter::strategy_type() != StakeStrategyType::Delegate {
+			return false
+		}
+
+		PoolMembers::<T>::get(&who)
+			.map(|member| {
+				// the pool needs to be migrated before its members.
+				if Self::api_pool_needs_delegate_migration(member.pool_id) {
+					return false
+				}
+
+				// a member with funds in the pool but no delegation still needs to migrate.
+				T::StakeAdapter::member_delegation_balance(&who).is_none() &&
+					!member.total_balance().is_zero()
+			})
+			.unwrap_or_default()
+	}
 }
 
 impl<T: Config> sp_staking::OnStakingUpdate<T::AccountId, BalanceOf<T>> for Pallet<T> {
@@ -27,6 +27,15 @@ use sp_runtime::TryRuntimeError;
 pub mod versioned {
 	use super::*;
 
+	/// v9: Indexes the pool members that allow permissionless compounding.
+	pub type V8ToV9<T> = frame_support::migrations::VersionedMigration<
+		8,
+		9,
+		v9::VersionUncheckedMigrateV8ToV9<T>,
+		crate::pallet::Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+
 	/// v8: Adds commission claim permissions to `BondedPools`.
 	pub type V7ToV8<T> = frame_support::migrations::VersionedMigration<
 		7,
@@ -109,6 +118,60 @@ pub mod unversioned {
 	}
 }
 
+/// This migration indexes all the pool members whose [`ClaimPermission`] allows permissionless
+/// compounding in [`AutoCompoundMembers`].
+///
+/// WARNING: This migration works under the assumption that the [`PoolMembers`] cannot be inflated
+/// arbitrarily. Otherwise this migration could fail due to too high weight.
+pub mod v9 {
+	use super::*;
+
+	pub struct VersionUncheckedMigrateV8ToV9<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateV8ToV9<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			Ok(Vec::new())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let mut reads = 0u64;
+			let mut indexed = 0u64;
+			for (who, permission) in ClaimPermissions::<T>::iter() {
+				reads.saturating_inc();
+				if !permission.can_bond_extra() {
+					continue
+				}
+
+				reads.saturating_inc();
+				if let Some(member) = PoolMembers::<T>::get(&who) {
+					AutoCompoundMembers::<T>::insert(member.pool_id, &who, ());
+					indexed.saturating_inc();
+				}
+			}
+			log!(info, "indexed {} auto-compounding pool members", indexed);
+
+			// the cursor used to point into `PoolMembers`, restart any ongoing sweep instead.
+			AutoCompoundCursor::<T>::kill();
+
+			T::DbWeight::get().reads_writes(reads, indexed.saturating_add(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_: Vec<u8>) -> Result<(), TryRuntimeError> {
+			ensure!(
+				ClaimPermissions::<T>::iter().all(|(who, permission)| {
+					!permission.can_bond_extra() ||
+						PoolMembers::<T>::get(&who).map_or(true, |m| {
+							AutoCompoundMembers::<T>::contains_key(m.pool_id, &who)
+						})
+				}),
+				"members that allow compounding have not been indexed."
+			);
+			Ok(())
+		}
+	}
+}
+
 pub mod v8 {
 	use super::{v7::V7BondedPoolInner, *};
 
@@ -1024,8 +1087,9 @@ mod helpers {
 	pub(crate) fn calculate_tvl_by_total_stake<T: Config>() -> BalanceOf<T> {
 		BondedPools::<T>::iter()
 			.map(|(id, inner)| {
-				T::Staking::total_stake(&BondedPool { id, inner: inner.clone() }.bonded_account())
-					.unwrap_or_default()
+				T::StakeAdapter::total_stake(
+					&BondedPool { id, inner: inner.clone() }.bonded_account(),
+				)
 			})
 			.reduce(|acc, total_balance| acc + total_balance)
 			.unwrap_or_default()
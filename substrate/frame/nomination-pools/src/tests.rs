@@ -7387,3 +7387,400 @@ mod chill {
 		})
 	}
 }
+
+mod delegate_stake {
+	use super::*;
+	use crate::adapter::StakeStrategy;
+
+	#[test]
+	fn delegation_calls_are_not_supported_with_transfer_stake() {
+		ExtBuilder::default().add_members(vec![(20, 20)]).build_and_execute(|| {
+			assert_eq!(
+				<Runtime as Config>::StakeAdapter::strategy_type(),
+				StakeStrategyType::Transfer
+			);
+
+			assert_noop!(
+				Pools::apply_slash(RuntimeOrigin::signed(10), 20),
+				Error::<Runtime>::NotSupported
+			);
+			assert_noop!(
+				Pools::migrate_delegation(RuntimeOrigin::signed(10), 20),
+				Error::<Runtime>::NotSupported
+			);
+			assert_noop!(
+				Pools::migrate_pool_to_delegate_stake(RuntimeOrigin::signed(10), 1),
+				Error::<Runtime>::NotSupported
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_stake_never_needs_migration_or_slashing() {
+		ExtBuilder::default().add_members(vec![(20, 20)]).build_and_execute(|| {
+			// slash the pool, which is applied to the pool account right away.
+			StakingMock::slash_by(1, 15);
+			assert_eq!(PoolMembers::<Runtime>::get(20).unwrap().total_balance(), 10);
+
+			// nothing is left pending for the pool or its members.
+			assert_eq!(Pools::api_pool_pending_slash(1), 0);
+			assert_eq!(Pools::api_member_pending_slash(10), 0);
+			assert_eq!(Pools::api_member_pending_slash(20), 0);
+
+			// and neither the pool nor its members need to migrate.
+			assert!(!Pools::api_pool_needs_delegate_migration(1));
+			assert!(!Pools::api_member_needs_delegate_migration(10));
+			assert!(!Pools::api_member_needs_delegate_migration(20));
+		});
+	}
+}
+
+mod reward_destination {
+	use super::*;
+
+	#[test]
+	fn set_reward_destination_works() {
+		ExtBuilder::default().add_members(vec![(20, 20)]).build_and_execute(|| {
+			// only pool members can set a reward destination.
+			assert_noop!(
+				Pools::set_reward_destination(
+					RuntimeOrigin::signed(30),
+					RewardDestination::Account(31)
+				),
+				Error::<Runtime>::PoolMemberNotFound
+			);
+
+			// the destination pool must exist, and must not be the member's own pool.
+			assert_noop!(
+				Pools::set_reward_destination(
+					RuntimeOrigin::signed(20),
+					RewardDestination::Pool(1)
+				),
+				Error::<Runtime>::InvalidRewardDestination
+			);
+			assert_noop!(
+				Pools::set_reward_destination(
+					RuntimeOrigin::signed(20),
+					RewardDestination::Pool(2)
+				),
+				Error::<Runtime>::InvalidRewardDestination
+			);
+
+			// when
+			assert_ok!(Pools::set_reward_destination(
+				RuntimeOrigin::signed(20),
+				RewardDestination::Account(31)
+			));
+
+			// then
+			assert_eq!(RewardDestinations::<Runtime>::get(20), RewardDestination::Account(31));
+
+			// when
+			assert_ok!(Pools::set_reward_destination(
+				RuntimeOrigin::signed(20),
+				RewardDestination::Member
+			));
+
+			// then the default destination is not stored.
+			assert!(!RewardDestinations::<Runtime>::contains_key(20));
+			assert_eq!(
+				pool_events_since_last_call(),
+				vec![
+					Event::Created { depositor: 10, pool_id: 1 },
+					Event::Bonded { member: 10, pool_id: 1, bonded: 10, joined: true },
+					Event::Bonded { member: 20, pool_id: 1, bonded: 20, joined: true },
+					Event::RewardDestinationSet {
+						member: 20,
+						destination: RewardDestination::Account(31)
+					},
+					Event::RewardDestinationSet {
+						member: 20,
+						destination: RewardDestination::Member
+					},
+				]
+			);
+
+			// when 20 leaves the pool, their destination is cleaned up.
+			assert_ok!(Pools::set_reward_destination(
+				RuntimeOrigin::signed(20),
+				RewardDestination::Account(31)
+			));
+			assert_ok!(fully_unbond_permissioned(20));
+			CurrentEra::set(3);
+			assert_ok!(Pools::withdraw_unbonded(RuntimeOrigin::signed(20), 20, 0));
+			assert!(!RewardDestinations::<Runtime>::contains_key(20));
+		})
+	}
+
+	#[test]
+	fn payouts_are_sent_to_another_account() {
+		ExtBuilder::default().add_members(vec![(20, 20)]).build_and_execute(|| {
+			assert_ok!(Pools::set_reward_destination(
+				RuntimeOrigin::signed(20),
+				RewardDestination::Account(31)
+			));
+
+			// 10 has 10 points and 20 has 20 points.
+			deposit_rewards(30);
+
+			// when
+			assert_ok!(Pools::claim_payout(RuntimeOrigin::signed(20)));
+
+			// then
+			assert_eq!(Currency::free_balance(&20), 20);
+			assert_eq!(Currency::free_balance(&31), 20);
+			assert_eq!(RewardPools::<Runtime>::get(1).unwrap().total_rewards_claimed, 20);
+
+			// rewards that are bonded are not affected by the destination.
+			deposit_rewards(30);
+			assert_ok!(Pools::bond_extra(RuntimeOrigin::signed(20), BondExtra::Rewards));
+			assert_eq!(PoolMembers::<Runtime>::get(20).unwrap().points, 40);
+			assert_eq!(Currency::free_balance(&31), 20);
+			assert_eq!(RewardPools::<Runtime>::get(1).unwrap().total_rewards_claimed, 40);
+		})
+	}
+
+	#[test]
+	fn payouts_fall_back_to_member_if_destination_cannot_receive() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_ok!(Pools::set_reward_destination(
+				RuntimeOrigin::signed(10),
+				RewardDestination::Account(31)
+			));
+
+			// a payout below the existential deposit cannot create account 31.
+			deposit_rewards(3);
+			assert_eq!(Currency::free_balance(&10), 35);
+
+			// when
+			assert_ok!(Pools::claim_payout(RuntimeOrigin::signed(10)));
+
+			// then
+			assert_eq!(Currency::free_balance(&10), 38);
+			assert_eq!(Currency::free_balance(&31), 0);
+		})
+	}
+
+	#[test]
+	fn payouts_are_sent_to_another_pool() {
+		ExtBuilder::default().add_members(vec![(20, 20)]).build_and_execute(|| {
+			// create pool 2, with 30 as its only member.
+			Currency::set_balance(&30, 100);
+			assert_ok!(Pools::create(RuntimeOrigin::signed(30), 10, 30, 30, 30));
+			assert_eq!(Currency::free_balance(&30), 85);
+
+			assert_ok!(Pools::set_reward_destination(
+				RuntimeOrigin::signed(20),
+				RewardDestination::Pool(2)
+			));
+
+			// when
+			deposit_rewards(30);
+			assert_ok!(Pools::claim_payout(RuntimeOrigin::signed(20)));
+
+			// then the rewards of 20 become rewards of pool 2.
+			assert_eq!(Currency::free_balance(&20), 20);
+			assert_eq!(
+				Currency::free_balance(&Pools::create_reward_account(2)),
+				Currency::minimum_balance() + 20
+			);
+			assert_eq!(Pools::api_pending_rewards(30), Some(20));
+
+			assert_ok!(Pools::claim_payout(RuntimeOrigin::signed(30)));
+			assert_eq!(Currency::free_balance(&30), 105);
+		})
+	}
+}
+
+mod auto_compound {
+	use super::*;
+
+	#[test]
+	fn set_auto_compound_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			// only the root can set auto-compounding.
+			assert_noop!(
+				Pools::set_auto_compound(RuntimeOrigin::signed(10), 1, true),
+				Error::<Runtime>::DoesNotHavePermission
+			);
+			assert_noop!(
+				Pools::set_auto_compound(RuntimeOrigin::signed(900), 2, true),
+				Error::<Runtime>::PoolNotFound
+			);
+
+			// when
+			assert_ok!(Pools::set_auto_compound(RuntimeOrigin::signed(900), 1, true));
+
+			// then
+			assert!(AutoCompoundPools::<Runtime>::contains_key(1));
+
+			// when
+			assert_ok!(Pools::set_auto_compound(RuntimeOrigin::signed(900), 1, false));
+
+			// then
+			assert!(!AutoCompoundPools::<Runtime>::contains_key(1));
+			assert_eq!(
+				pool_events_since_last_call(),
+				vec![
+					Event::Created { depositor: 10, pool_id: 1 },
+					Event::Bonded { member: 10, pool_id: 1, bonded: 10, joined: true },
+					Event::AutoCompoundUpdated { pool_id: 1, enabled: true },
+					Event::AutoCompoundUpdated { pool_id: 1, enabled: false },
+				]
+			);
+		})
+	}
+
+	#[test]
+	fn auto_compound_compounds_opted_in_members_periodically() {
+		ExtBuilder::default()
+			.add_members(vec![(20, 20), (21, 20)])
+			.build_and_execute(|| {
+				// 20 opts in to permissionless compounding, 10 and 21 do not.
+				assert_ok!(Pools::set_claim_permission(
+					RuntimeOrigin::signed(20),
+					ClaimPermission::PermissionlessCompound
+				));
+
+				// nothing happens while the pool has not enabled auto-compounding.
+				deposit_rewards(50);
+				Pools::on_idle(System::block_number(), Weight::MAX);
+				assert_eq!(PoolMembers::<Runtime>::get(20).unwrap().points, 20);
+
+				// when
+				assert_ok!(Pools::set_auto_compound(RuntimeOrigin::signed(900), 1, true));
+				Pools::on_idle(System::block_number(), Weight::MAX);
+
+				// then only 20 has compounded their rewards.
+				assert_eq!(PoolMembers::<Runtime>::get(20).unwrap().points, 40);
+				assert_eq!(PoolMembers::<Runtime>::get(21).unwrap().points, 20);
+				assert_eq!(PoolMembers::<Runtime>::get(10).unwrap().points, 10);
+				assert_eq!(Pools::api_pending_rewards(20), Some(0));
+				assert_eq!(Pools::api_pending_rewards(21), Some(20));
+				assert_eq!(Pools::api_pending_rewards(10), Some(10));
+				assert_eq!(RewardPools::<Runtime>::get(1).unwrap().total_rewards_claimed, 20);
+
+				// the sweep is complete, and the next one is scheduled.
+				assert_eq!(AutoCompoundCursor::<Runtime>::get(), None);
+				assert_eq!(NextAutoCompoundSweep::<Runtime>::get(), 1 + AutoCompoundPeriod::get());
+
+				// the next sweep does not start before its due.
+				deposit_rewards(70);
+				run_blocks(AutoCompoundPeriod::get() - 1);
+				Pools::on_idle(System::block_number(), Weight::MAX);
+				assert_eq!(PoolMembers::<Runtime>::get(20).unwrap().points, 40);
+
+				// when
+				run_blocks(1);
+				Pools::on_idle(System::block_number(), Weight::MAX);
+
+				// then
+				assert_eq!(PoolMembers::<Runtime>::get(20).unwrap().points, 80);
+				assert_eq!(Pools::api_pending_rewards(21), Some(40));
+				assert_eq!(Pools::api_pending_rewards(10), Some(20));
+			})
+	}
+
+	#[test]
+	fn auto_compound_respects_weight_limit() {
+		ExtBuilder::default()
+			.add_members(vec![(20, 20), (21, 20)])
+			.build_and_execute(|| {
+				for member in [10, 20, 21] {
+					assert_ok!(Pools::set_claim_permission(
+						RuntimeOrigin::signed(member),
+						ClaimPermission::PermissionlessAll
+					));
+				}
+				assert_ok!(Pools::set_auto_compound(RuntimeOrigin::signed(900), 1, true));
+				deposit_rewards(50);
+
+				let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+				let limit = db_weight
+					.reads_writes(3, 2)
+					.saturating_add(db_weight.reads(2))
+					.saturating_add(db_weight.reads(5))
+					.saturating_add(<() as WeightInfo>::bond_extra_other());
+				let compounded = || {
+					[10, 20, 21]
+						.into_iter()
+						.filter(|m| Pools::api_pending_rewards(*m) == Some(0))
+						.count()
+				};
+
+				// not enough weight to process a single member.
+				assert_eq!(
+					Pools::on_idle(
+						System::block_number(),
+						limit.saturating_sub(Weight::from_parts(1, 0))
+					),
+					Weight::zero()
+				);
+				assert_eq!(compounded(), 0);
+
+				// each call processes a single member.
+				for processed in 1..=3 {
+					assert!(Pools::on_idle(System::block_number(), limit).all_lte(limit));
+					assert_eq!(compounded(), processed);
+					assert!(AutoCompoundCursor::<Runtime>::get().is_some());
+				}
+
+				// the sweep completes in the next call.
+				Pools::on_idle(System::block_number(), limit);
+				assert_eq!(AutoCompoundCursor::<Runtime>::get(), None);
+				assert_eq!(PoolMembers::<Runtime>::get(10).unwrap().points, 20);
+				assert_eq!(PoolMembers::<Runtime>::get(20).unwrap().points, 40);
+				assert_eq!(PoolMembers::<Runtime>::get(21).unwrap().points, 40);
+			})
+	}
+
+	#[test]
+	fn auto_compound_only_visits_opted_in_members() {
+		ExtBuilder::default()
+			.add_members(vec![(20, 20), (21, 20)])
+			.build_and_execute(|| {
+				// opting in and out of permissionless compounding updates the index of the pool.
+				assert_ok!(Pools::set_claim_permission(
+					RuntimeOrigin::signed(20),
+					ClaimPermission::PermissionlessCompound
+				));
+				assert_ok!(Pools::set_claim_permission(
+					RuntimeOrigin::signed(21),
+					ClaimPermission::PermissionlessAll
+				));
+				assert_ok!(Pools::set_claim_permission(
+					RuntimeOrigin::signed(21),
+					ClaimPermission::PermissionlessWithdraw
+				));
+				assert_eq!(
+					AutoCompoundMembers::<Runtime>::iter_key_prefix(1).collect::<Vec<_>>(),
+					vec![20]
+				);
+
+				assert_ok!(Pools::set_auto_compound(RuntimeOrigin::signed(900), 1, true));
+				deposit_rewards(50);
+
+				// the sweep only visits the single indexed member.
+				let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+				let pool_weight = db_weight.reads(2);
+				let member_weight =
+					db_weight.reads(5).saturating_add(<() as WeightInfo>::bond_extra_other());
+				assert_eq!(
+					Pools::on_idle(System::block_number(), Weight::MAX),
+					db_weight
+						.reads_writes(3, 2)
+						.saturating_add(pool_weight.saturating_mul(2))
+						.saturating_add(member_weight)
+				);
+				assert_eq!(PoolMembers::<Runtime>::get(20).unwrap().points, 40);
+				assert_eq!(PoolMembers::<Runtime>::get(21).unwrap().points, 20);
+				assert_eq!(AutoCompoundCursor::<Runtime>::get(), None);
+
+				// leaving the pool removes the member from the index.
+				assert_ok!(fully_unbond_permissioned(20));
+				CurrentEra::set(3);
+				assert_ok!(Pools::withdraw_unbonded(RuntimeOrigin::signed(20), 20, 0));
+				assert!(!AutoCompoundMembers::<Runtime>::contains_key(1, 20));
+			})
+	}
+}
@@ -105,9 +105,9 @@ fn fill_queue<T: Config<I>, I: 'static>(
 	others
 }
 
-fn info<T: Config<I>, I: 'static>(index: ReferendumIndex) -> &'static TrackInfoOf<T, I> {
+fn info<T: Config<I>, I: 'static>(index: ReferendumIndex) -> StoredTrackInfoOf<T, I> {
 	let status = Referenda::<T, I>::ensure_ongoing(index).unwrap();
-	T::Tracks::info(status.track).expect("Id value returned from T::Tracks")
+	Referenda::<T, I>::track(status.track).expect("Id value returned from T::Tracks")
 }
 
 fn make_passing_after<T: Config<I>, I: 'static>(index: ReferendumIndex, period_portion: Perbill) {
@@ -656,6 +656,48 @@ benchmarks_instance_pallet! {
 		assert_last_event::<T, I>(Event::MetadataCleared { index, hash }.into());
 	}
 
+	add_track {
+		let update_origin =
+			T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let (track, static_info) = T::Tracks::tracks()[0].clone();
+		assert_ok!(Referenda::<T, I>::remove_track(update_origin.clone(), track));
+		let info = static_info.to_stored();
+		let proposal_origin = Box::new(RawOrigin::Root.into());
+	}: _<T::RuntimeOrigin>(update_origin, track, info.clone(), Some(proposal_origin))
+	verify {
+		assert_eq!(TrackInfoFor::<T, I>::get(track), Some(info));
+		assert_last_event::<T, I>(Event::TrackAdded { track }.into());
+	}
+
+	update_track {
+		let update_origin =
+			T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let origin = T::SubmitOrigin::try_successful_origin(&RawOrigin::Root.into())
+			.expect("SubmitOrigin has no successful origin required for the benchmark");
+		let index = create_referendum::<T, I>(origin.clone());
+		// No spaces free in the queue, and all of it moves to deciding.
+		fill_queue::<T, I>(origin, index, 0, 90);
+		let track = Referenda::<T, I>::ensure_ongoing(index).unwrap().track;
+		let mut info = info::<T, I>(index);
+		info.max_deciding = info.max_deciding.saturating_add(T::MaxQueued::get());
+		let deciding_count = DecidingCount::<T, I>::get(&track);
+	}: _<T::RuntimeOrigin>(update_origin, track, info.clone())
+	verify {
+		assert_eq!(TrackInfoFor::<T, I>::get(track), Some(info));
+		assert_eq!(DecidingCount::<T, I>::get(&track), deciding_count + T::MaxQueued::get());
+		assert_last_event::<T, I>(Event::TrackUpdated { track }.into());
+	}
+
+	remove_track {
+		let update_origin =
+			T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let track = T::Tracks::tracks()[0].0;
+	}: _<T::RuntimeOrigin>(update_origin, track)
+	verify {
+		assert!(!TrackInfoFor::<T, I>::contains_key(track));
+		assert_last_event::<T, I>(Event::TrackRemoved { track }.into());
+	}
+
 	impl_benchmark_test_suite!(
 		Referenda,
 		crate::mock::ExtBuilder::default().build(),
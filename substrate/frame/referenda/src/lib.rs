@@ -97,8 +97,9 @@ pub use self::{
 	types::{
 		BalanceOf, BoundedCallOf, CallOf, Curve, DecidingStatus, DecidingStatusOf, Deposit,
 		InsertSorted, NegativeImbalanceOf, PalletsOriginOf, ReferendumIndex, ReferendumInfo,
-		ReferendumInfoOf, ReferendumStatus, ReferendumStatusOf, ScheduleAddressOf, TallyOf,
-		TrackIdOf, TrackInfo, TrackInfoOf, TracksInfo, VotesOf,
+		ReferendumInfoOf, ReferendumStatus, ReferendumStatusOf, ScheduleAddressOf,
+		StoredTrackInfoOf, TallyOf, TrackIdOf, TrackInfo, TrackInfoOf, TrackName, TracksInfo,
+		VotesOf, MAX_TRACK_NAME_LEN,
 	},
 	weights::WeightInfo,
 };
@@ -144,7 +145,7 @@ pub mod pallet {
 	use frame_system::pallet_prelude::*;
 
 	/// The in-code storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -187,6 +188,8 @@ pub mod pallet {
 		type CancelOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 		/// Origin from which any vote may be killed.
 		type KillOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Origin from which tracks may be added, updated or removed.
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 		/// Handler for the unbalanced reduction when slashing a preimage deposit.
 		type Slash: OnUnbalanced<NegativeImbalanceOf<Self, I>>;
 		/// The counting type for votes. Usually just balance.
@@ -220,6 +223,10 @@ pub mod pallet {
 		#[pallet::constant]
 		type AlarmInterval: Get<BlockNumberFor<Self>>;
 
+		/// Maximum number of tracks which may be kept in storage.
+		#[pallet::constant]
+		type MaxTracks: Get<u32>;
+
 		// The other stuff.
 		/// Information concerning the different referendum tracks.
 		#[pallet::constant]
@@ -265,6 +272,11 @@ pub mod pallet {
 	pub type DecidingCount<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Twox64Concat, TrackIdOf<T, I>, u32, ValueQuery>;
 
+	/// The number of ongoing referenda of a track, whether preparing, queued or being decided.
+	#[pallet::storage]
+	pub type OngoingCount<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, TrackIdOf<T, I>, u32, ValueQuery>;
+
 	/// The metadata is a general information concerning the referendum.
 	/// The `Hash` refers to the preimage of the `Preimages` provider which can be a JSON
 	/// dump or IPFS hash of a JSON file.
@@ -275,6 +287,21 @@ pub mod pallet {
 	pub type MetadataOf<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Blake2_128Concat, ReferendumIndex, T::Hash>;
 
+	/// The tracks kept in storage.
+	///
+	/// While this is empty, the static tracks of `Config::Tracks` are used. The static tracks are
+	/// copied here by the first call adding, updating or removing a track, or by
+	/// [`migration::tracks::MigrateTracksToStorage`], after which only the tracks kept here are
+	/// used.
+	#[pallet::storage]
+	pub type TrackInfoFor<T: Config<I>, I: 'static = ()> =
+		CountedStorageMap<_, Twox64Concat, TrackIdOf<T, I>, StoredTrackInfoOf<T, I>>;
+
+	/// The track of the proposals of an origin, taking precedence over `TracksInfo::track_for`.
+	#[pallet::storage]
+	pub type TrackForOrigin<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, PalletsOriginOf<T>, TrackIdOf<T, I>>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -394,6 +421,21 @@ pub mod pallet {
 			/// Preimage hash.
 			hash: T::Hash,
 		},
+		/// A track has been added.
+		TrackAdded {
+			/// The identifier of the track.
+			track: TrackIdOf<T, I>,
+		},
+		/// The parameters of a track have been updated.
+		TrackUpdated {
+			/// The identifier of the track.
+			track: TrackIdOf<T, I>,
+		},
+		/// A track has been removed.
+		TrackRemoved {
+			/// The identifier of the track.
+			track: TrackIdOf<T, I>,
+		},
 	}
 
 	#[pallet::error]
@@ -426,6 +468,18 @@ pub mod pallet {
 		PreimageNotExist,
 		/// The preimage is stored with a different length than the one provided.
 		PreimageStoredWithDifferentLength,
+		/// A track with the given identifier already exists.
+		TrackExists,
+		/// The maximum number of tracks has been reached.
+		TooManyTracks,
+		/// The parameters of the track are invalid.
+		BadTrackInfo,
+		/// The proposal origin is already assigned to a track.
+		OriginInUse,
+		/// The track has ongoing referenda.
+		TrackInUse,
+		/// The last track cannot be removed.
+		LastTrack,
 	}
 
 	#[pallet::hooks]
@@ -439,6 +493,10 @@ pub mod pallet {
 		#[cfg(any(feature = "std", test))]
 		fn integrity_test() {
 			T::Tracks::check_integrity().expect("Static tracks configuration is valid.");
+			assert!(
+				T::Tracks::tracks().len() <= T::MaxTracks::get() as usize,
+				"Static tracks must fit into `MaxTracks`."
+			);
 		}
 	}
 
@@ -474,8 +532,12 @@ pub mod pallet {
 				}
 			}
 
-			let track =
-				T::Tracks::track_for(&proposal_origin).map_err(|_| Error::<T, I>::NoTrack)?;
+			let track = match TrackForOrigin::<T, I>::get(&proposal_origin) {
+				Some(track) => track,
+				None =>
+					T::Tracks::track_for(&proposal_origin).map_err(|_| Error::<T, I>::NoTrack)?,
+			};
+			ensure!(Self::track(track).is_some(), Error::<T, I>::NoTrack);
 			let submission_deposit = Self::take_deposit(who, T::SubmissionDeposit::get())?;
 			let index = ReferendumCount::<T, I>::mutate(|x| {
 				let r = *x;
@@ -499,6 +561,7 @@ pub mod pallet {
 				alarm: Self::set_alarm(nudge_call, now.saturating_add(T::UndecidingTimeout::get())),
 			};
 			ReferendumInfoFor::<T, I>::insert(index, ReferendumInfo::Ongoing(status));
+			OngoingCount::<T, I>::mutate(track, |x| x.saturating_inc());
 
 			Self::deposit_event(Event::<T, I>::Submitted { index, track, proposal });
 			Ok(())
@@ -579,6 +642,7 @@ pub mod pallet {
 				let _ = T::Scheduler::cancel(last_alarm);
 			}
 			Self::note_one_fewer_deciding(status.track);
+			OngoingCount::<T, I>::mutate(status.track, |x| x.saturating_dec());
 			Self::deposit_event(Event::<T, I>::Cancelled { index, tally: status.tally });
 			let info = ReferendumInfo::Cancelled(
 				frame_system::Pallet::<T>::block_number(),
@@ -604,6 +668,7 @@ pub mod pallet {
 				let _ = T::Scheduler::cancel(last_alarm);
 			}
 			Self::note_one_fewer_deciding(status.track);
+			OngoingCount::<T, I>::mutate(status.track, |x| x.saturating_dec());
 			Self::deposit_event(Event::<T, I>::Killed { index, tally: status.tally });
 			Self::slash_deposit(Some(status.submission_deposit.clone()));
 			Self::slash_deposit(status.decision_deposit.clone());
@@ -651,13 +716,13 @@ pub mod pallet {
 			track: TrackIdOf<T, I>,
 		) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
-			let track_info = T::Tracks::info(track).ok_or(Error::<T, I>::BadTrack)?;
+			let track_info = Self::track(track).ok_or(Error::<T, I>::BadTrack)?;
 			let mut track_queue = TrackQueue::<T, I>::get(track);
 			let branch =
 				if let Some((index, mut status)) = Self::next_for_deciding(&mut track_queue) {
 					let now = frame_system::Pallet::<T>::block_number();
 					let (maybe_alarm, branch) =
-						Self::begin_deciding(&mut status, index, now, track_info);
+						Self::begin_deciding(&mut status, index, now, &track_info);
 					if let Some(set_alarm) = maybe_alarm {
 						Self::ensure_alarm_at(&mut status, index, set_alarm);
 					}
@@ -735,6 +800,109 @@ pub mod pallet {
 				Ok(())
 			}
 		}
+
+		/// Add a track.
+		///
+		/// - `origin`: must be `UpdateOrigin`.
+		/// - `track`: The identifier of the new track.
+		/// - `info`: The parameters of the new track.
+		/// - `proposal_origin`: The origin whose proposals are submitted on the new track, if any.
+		///   Otherwise, the track is used by the origins which `Config::Tracks` maps to `track`.
+		///
+		/// The static tracks of `Config::Tracks` are copied to storage first, if not yet done.
+		///
+		/// Emits `TrackAdded`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::add_track())]
+		pub fn add_track(
+			origin: OriginFor<T>,
+			track: TrackIdOf<T, I>,
+			info: StoredTrackInfoOf<T, I>,
+			proposal_origin: Option<Box<PalletsOriginOf<T>>>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::ensure_valid_track(&info)?;
+			Self::ensure_tracks_stored();
+			ensure!(!TrackInfoFor::<T, I>::contains_key(track), Error::<T, I>::TrackExists);
+			ensure!(
+				TrackInfoFor::<T, I>::count() < T::MaxTracks::get(),
+				Error::<T, I>::TooManyTracks
+			);
+			if let Some(proposal_origin) = proposal_origin {
+				ensure!(
+					!TrackForOrigin::<T, I>::contains_key(&*proposal_origin),
+					Error::<T, I>::OriginInUse
+				);
+				TrackForOrigin::<T, I>::insert(*proposal_origin, track);
+			}
+			TrackInfoFor::<T, I>::insert(track, info);
+			Self::deposit_event(Event::<T, I>::TrackAdded { track });
+			Ok(())
+		}
+
+		/// Update the parameters of a track.
+		///
+		/// - `origin`: must be `UpdateOrigin`.
+		/// - `track`: The identifier of the track to be updated.
+		/// - `info`: The new parameters of the track.
+		///
+		/// The new parameters apply to the referenda of the track which are already ongoing. If
+		/// `max_deciding` is raised, the referenda queued on the track begin being decided.
+		///
+		/// The static tracks of `Config::Tracks` are copied to storage first, if not yet done.
+		///
+		/// Emits `TrackUpdated`.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::update_track())]
+		pub fn update_track(
+			origin: OriginFor<T>,
+			track: TrackIdOf<T, I>,
+			info: StoredTrackInfoOf<T, I>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::ensure_valid_track(&info)?;
+			Self::ensure_tracks_stored();
+			let old_info = TrackInfoFor::<T, I>::get(track).ok_or(Error::<T, I>::BadTrack)?;
+			// Each new slot for deciding is handed over to the queue as if a referendum had just
+			// left the deciding phase.
+			let queued = TrackQueue::<T, I>::decode_len(track).unwrap_or(0) as u32;
+			for _ in 0..info.max_deciding.saturating_sub(old_info.max_deciding).min(queued) {
+				DecidingCount::<T, I>::mutate(track, |x| x.saturating_inc());
+				Self::note_one_fewer_deciding(track);
+			}
+			TrackInfoFor::<T, I>::insert(track, info);
+			Self::deposit_event(Event::<T, I>::TrackUpdated { track });
+			Ok(())
+		}
+
+		/// Remove a track.
+		///
+		/// - `origin`: must be `UpdateOrigin`.
+		/// - `track`: The identifier of the track to be removed. It must not have any ongoing
+		///   referendum, including those still preparing, and must not be the last track.
+		///
+		/// The static tracks of `Config::Tracks` are copied to storage first, if not yet done.
+		///
+		/// Emits `TrackRemoved`.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::remove_track())]
+		pub fn remove_track(origin: OriginFor<T>, track: TrackIdOf<T, I>) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::ensure_tracks_stored();
+			ensure!(TrackInfoFor::<T, I>::contains_key(track), Error::<T, I>::BadTrack);
+			ensure!(TrackInfoFor::<T, I>::count() > 1, Error::<T, I>::LastTrack);
+			ensure!(OngoingCount::<T, I>::get(track) == 0, Error::<T, I>::TrackInUse);
+			TrackInfoFor::<T, I>::remove(track);
+			OngoingCount::<T, I>::remove(track);
+			let origins: Vec<_> = TrackForOrigin::<T, I>::iter()
+				.filter_map(|(origin, t)| (t == track).then_some(origin))
+				.collect();
+			for origin in origins {
+				TrackForOrigin::<T, I>::remove(origin);
+			}
+			Self::deposit_event(Event::<T, I>::TrackRemoved { track });
+			Ok(())
+		}
 	}
 }
 
@@ -745,7 +913,7 @@ impl<T: Config<I>, I: 'static> Polling<T::Tally> for Pallet<T, I> {
 	type Class = TrackIdOf<T, I>;
 
 	fn classes() -> Vec<Self::Class> {
-		T::Tracks::tracks().iter().map(|x| x.0).collect()
+		Self::track_ids()
 	}
 
 	fn access_poll<R>(
@@ -817,6 +985,7 @@ impl<T: Config<I>, I: 'static> Polling<T::Tally> for Pallet<T, I> {
 		};
 		Self::ensure_alarm_at(&mut status, index, sp_runtime::traits::Bounded::max_value());
 		ReferendumInfoFor::<T, I>::insert(index, ReferendumInfo::Ongoing(status));
+		OngoingCount::<T, I>::mutate(class, |x| x.saturating_inc());
 		Ok(index)
 	}
 
@@ -825,6 +994,7 @@ impl<T: Config<I>, I: 'static> Polling<T::Tally> for Pallet<T, I> {
 		let mut status = Self::ensure_ongoing(index).map_err(|_| ())?;
 		Self::ensure_no_alarm(&mut status);
 		Self::note_one_fewer_deciding(status.track);
+		OngoingCount::<T, I>::mutate(status.track, |x| x.saturating_dec());
 		let now = frame_system::Pallet::<T>::block_number();
 		let info = if approved {
 			ReferendumInfo::Approved(now, Some(status.submission_deposit), status.decision_deposit)
@@ -837,8 +1007,9 @@ impl<T: Config<I>, I: 'static> Polling<T::Tally> for Pallet<T, I> {
 
 	#[cfg(feature = "runtime-benchmarks")]
 	fn max_ongoing() -> (Self::Class, u32) {
-		let r = T::Tracks::tracks()
-			.iter()
+		let r = Self::track_ids()
+			.into_iter()
+			.filter_map(|id| Some((id, Self::track(id)?)))
 			.max_by_key(|(_, info)| info.max_deciding)
 			.expect("Always one class");
 		(r.0, r.1.max_deciding)
@@ -885,7 +1056,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	// Enqueue a proposal from a referendum which has presumably passed.
 	fn schedule_enactment(
 		index: ReferendumIndex,
-		track: &TrackInfoOf<T, I>,
+		track: &StoredTrackInfoOf<T, I>,
 		desired: DispatchTime<BlockNumberFor<T>>,
 		origin: PalletsOriginOf<T>,
 		call: BoundedCallOf<T, I>,
@@ -943,7 +1114,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		status: &mut ReferendumStatusOf<T, I>,
 		index: ReferendumIndex,
 		now: BlockNumberFor<T>,
-		track: &TrackInfoOf<T, I>,
+		track: &StoredTrackInfoOf<T, I>,
 	) -> (Option<BlockNumberFor<T>>, BeginDecidingBranch) {
 		let is_passing = Self::is_passing(
 			&status.tally,
@@ -981,7 +1152,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// If `None`, then it is queued and should be nudged automatically as the queue gets drained.
 	fn ready_for_deciding(
 		now: BlockNumberFor<T>,
-		track: &TrackInfoOf<T, I>,
+		track: &StoredTrackInfoOf<T, I>,
 		index: ReferendumIndex,
 		status: &mut ReferendumStatusOf<T, I>,
 	) -> (Option<BlockNumberFor<T>>, ServiceBranch) {
@@ -1128,7 +1299,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						let prepare_end = status.submitted.saturating_add(track.prepare_period);
 						if now >= prepare_end {
 							let (maybe_alarm, branch) =
-								Self::ready_for_deciding(now, track, index, &mut status);
+								Self::ready_for_deciding(now, &track, index, &mut status);
 							if let Some(set_alarm) = maybe_alarm {
 								alarm = alarm.min(set_alarm);
 							}
@@ -1147,6 +1318,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				if status.deciding.is_none() && now >= timeout && !status.in_queue {
 					// Too long without being decided - end it.
 					Self::ensure_no_alarm(&mut status);
+					OngoingCount::<T, I>::mutate(status.track, |x| x.saturating_dec());
 					Self::deposit_event(Event::<T, I>::TimedOut { index, tally: status.tally });
 					return (
 						ReferendumInfo::TimedOut(
@@ -1174,8 +1346,9 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 							// Passed!
 							Self::ensure_no_alarm(&mut status);
 							Self::note_one_fewer_deciding(status.track);
+							OngoingCount::<T, I>::mutate(status.track, |x| x.saturating_dec());
 							let (desired, call) = (status.enactment, status.proposal);
-							Self::schedule_enactment(index, track, desired, status.origin, call);
+							Self::schedule_enactment(index, &track, desired, status.origin, call);
 							Self::deposit_event(Event::<T, I>::Confirmed {
 								index,
 								tally: status.tally,
@@ -1204,6 +1377,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						// Failed!
 						Self::ensure_no_alarm(&mut status);
 						Self::note_one_fewer_deciding(status.track);
+						OngoingCount::<T, I>::mutate(status.track, |x| x.saturating_dec());
 						Self::deposit_event(Event::<T, I>::Rejected { index, tally: status.tally });
 						return (
 							ReferendumInfo::Rejected(
@@ -1225,7 +1399,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						ServiceBranch::ContinueNotConfirming
 					}
 				};
-				alarm = Self::decision_time(deciding, &status.tally, status.track, track);
+				alarm = Self::decision_time(deciding, &status.tally, status.track, &track);
 			},
 		}
 
@@ -1243,7 +1417,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		deciding: &DecidingStatusOf<T>,
 		tally: &T::Tally,
 		track_id: TrackIdOf<T, I>,
-		track: &TrackInfoOf<T, I>,
+		track: &StoredTrackInfoOf<T, I>,
 	) -> BlockNumberFor<T> {
 		deciding.confirming.unwrap_or_else(|| {
 			// Set alarm to the point where the current voting would make it pass.
@@ -1292,10 +1466,52 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	}
 
 	/// Get the track info value for the track `id`.
-	fn track(id: TrackIdOf<T, I>) -> Option<&'static TrackInfoOf<T, I>> {
+	///
+	/// The static tracks of `Config::Tracks` are only used while no track is kept in storage.
+	fn track(id: TrackIdOf<T, I>) -> Option<StoredTrackInfoOf<T, I>> {
+		match TrackInfoFor::<T, I>::get(id) {
+			Some(info) => Some(info),
+			None if TrackInfoFor::<T, I>::count() == 0 =>
+				T::Tracks::info(id).map(TrackInfo::to_stored),
+			None => None,
+		}
+	}
+
+	/// Get the identifiers of all the tracks, sorted.
+	fn track_ids() -> Vec<TrackIdOf<T, I>> {
+		if TrackInfoFor::<T, I>::count() == 0 {
+			return T::Tracks::tracks().iter().map(|x| x.0).collect()
+		}
+		let mut ids: Vec<_> = TrackInfoFor::<T, I>::iter_keys().collect();
+		ids.sort();
+		ids
+	}
+
+	/// Copy the static tracks of `Config::Tracks` to storage, unless some tracks are already kept
+	/// there.
+	///
+	/// Returns the number of tracks copied.
+	fn ensure_tracks_stored() -> u32 {
+		if TrackInfoFor::<T, I>::count() > 0 {
+			return 0
+		}
 		let tracks = T::Tracks::tracks();
-		let index = tracks.binary_search_by_key(&id, |x| x.0).unwrap_or_else(|x| x);
-		Some(&tracks[index].1)
+		for (id, info) in tracks {
+			TrackInfoFor::<T, I>::insert(id, info.to_stored());
+		}
+		tracks.len() as u32
+	}
+
+	/// Ensure that the parameters of a track are sensible, and its curves well-formed.
+	fn ensure_valid_track(info: &StoredTrackInfoOf<T, I>) -> DispatchResult {
+		ensure!(
+			info.max_deciding > 0 &&
+				!info.decision_period.is_zero() &&
+				info.min_approval.is_valid() &&
+				info.min_support.is_valid(),
+			Error::<T, I>::BadTrackInfo
+		);
+		Ok(())
 	}
 
 	/// Determine whether the given `tally` would result in a referendum passing at `elapsed` blocks
@@ -1360,8 +1576,10 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// * The deciding stage has to begin before confirmation period.
 	/// * If alarm is set the nudge call has to be at most [`UndecidingTimeout`] blocks away
 	///  from the submission block.
+	/// * The number of ongoing referenda of each track must match [`OngoingCount`].
 	#[cfg(any(feature = "try-runtime", test))]
 	fn try_state_referenda_info() -> Result<(), sp_runtime::TryRuntimeError> {
+		let mut ongoing = sp_std::collections::btree_map::BTreeMap::<TrackIdOf<T, I>, u32>::new();
 		ReferendumInfoFor::<T, I>::iter().try_for_each(|(_, referendum)| {
 			match referendum {
 				ReferendumInfo::Ongoing(status) => {
@@ -1369,6 +1587,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						Self::track(status.track).is_some(),
 						"No track info for the track of the referendum."
 					);
+					*ongoing.entry(status.track).or_default() += 1;
 
 					if let Some(deciding) = status.deciding {
 						ensure!(
@@ -1380,18 +1599,45 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				},
 				_ => {},
 			}
-			Ok(())
-		})
+			Ok::<(), sp_runtime::TryRuntimeError>(())
+		})?;
+		OngoingCount::<T, I>::iter().try_for_each(|(track, count)| {
+			ensure!(
+				ongoing.get(&track).copied().unwrap_or(0) == count,
+				"`OngoingCount` must match the number of ongoing referenda of the track."
+			);
+			Ok::<(), sp_runtime::TryRuntimeError>(())
+		})?;
+		ensure!(
+			ongoing
+				.into_iter()
+				.all(|(track, count)| OngoingCount::<T, I>::get(track) == count),
+			"Ongoing referenda must be counted in `OngoingCount`."
+		);
+		Ok(())
 	}
 
 	/// Looking at tracks:
 	///
 	/// * The referendum indices stored in [`TrackQueue`] must exist as keys in the
 	///  [`ReferendumInfoFor`] storage map.
+	/// * The number of tracks in [`TrackInfoFor`] must not exceed [`Config::MaxTracks`].
+	/// * The tracks in [`TrackForOrigin`] must exist in [`TrackInfoFor`].
 	#[cfg(any(feature = "try-runtime", test))]
 	fn try_state_tracks() -> Result<(), sp_runtime::TryRuntimeError> {
-		T::Tracks::tracks().iter().try_for_each(|track| {
-			TrackQueue::<T, I>::get(track.0).iter().try_for_each(
+		ensure!(
+			TrackInfoFor::<T, I>::count() <= T::MaxTracks::get(),
+			"Number of tracks in `TrackInfoFor` exceeds `MaxTracks`"
+		);
+		TrackForOrigin::<T, I>::iter_values().try_for_each(|track| -> DispatchResult {
+			ensure!(
+				TrackInfoFor::<T, I>::contains_key(track),
+				"Tracks in `TrackForOrigin` must also be stored in `TrackInfoFor`"
+			);
+			Ok(())
+		})?;
+		Self::track_ids().into_iter().try_for_each(|track| {
+			TrackQueue::<T, I>::get(track).iter().try_for_each(
 				|(referendum_index, _)| -> Result<(), sp_runtime::TryRuntimeError> {
 					ensure!(
 					ReferendumInfoFor::<T, I>::contains_key(referendum_index),
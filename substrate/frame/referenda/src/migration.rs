@@ -161,11 +161,130 @@ pub mod v1 {
 	}
 }
 
+pub mod v2 {
+	use super::*;
+	use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+
+	/// The log target.
+	const TARGET: &'static str = "runtime::referenda::migration::v2";
+
+	/// Counts the ongoing referenda of every track into [`OngoingCount`], if the on-chain storage
+	/// version is 1.
+	///
+	/// The migration reads all [`ReferendumCount`] entries of [`ReferendumInfoFor`] within a single
+	/// block, so it must only be used on chains where these fit into a block. The `try-runtime`
+	/// checks of the migration ensure that they do.
+	pub type MigrateV1ToV2<T, I = ()> = VersionedMigration<
+		1,
+		2,
+		VersionUncheckedMigrateV1ToV2<T, I>,
+		crate::pallet::Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+
+	/// Implements the migration of [`MigrateV1ToV2`] without checking the storage version.
+	pub struct VersionUncheckedMigrateV1ToV2<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> VersionUncheckedMigrateV1ToV2<T, I> {
+		/// The worst case weight of migrating `referenda` referenda, all of them being ongoing.
+		///
+		/// The proof size is dominated by the referenda, since there are far fewer tracks.
+		#[cfg(feature = "try-runtime")]
+		fn max_weight(referenda: u32) -> Weight {
+			let proof_size = (ReferendumInfoOf::<T, I>::max_encoded_len() as u64)
+				.saturating_mul(referenda.into());
+			T::DbWeight::get()
+				.reads_writes(2, 1)
+				.saturating_mul(referenda.into())
+				.saturating_add(Weight::from_parts(0, proof_size))
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateV1ToV2<T, I> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let referenda = ReferendumCount::<T, I>::get();
+			log::info!(target: TARGET, "counting the ongoing referendums of {} referendums.", referenda);
+			ensure!(
+				Self::max_weight(referenda)
+					.all_lte(<T as frame_system::Config>::BlockWeights::get().max_block),
+				"too many referendums to migrate within a single block."
+			);
+			Ok(Vec::new())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight = Weight::zero();
+			let mut ongoing = 0u32;
+			ReferendumInfoFor::<T, I>::iter().for_each(|(_, info)| {
+				weight.saturating_accrue(T::DbWeight::get().reads(1));
+				if let ReferendumInfo::Ongoing(status) = info {
+					weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+					OngoingCount::<T, I>::mutate(status.track, |x| x.saturating_inc());
+					ongoing.saturating_inc();
+				}
+			});
+			log::info!(target: TARGET, "counted {} ongoing referendums.", ongoing);
+			weight
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let ongoing = ReferendumInfoFor::<T, I>::iter_values()
+				.filter(|info| matches!(info, ReferendumInfo::Ongoing(_)))
+				.count() as u32;
+			ensure!(
+				OngoingCount::<T, I>::iter_values().sum::<u32>() == ongoing,
+				"must count all ongoing referendums."
+			);
+			Ok(())
+		}
+	}
+}
+
+/// Migration of the static tracks to storage.
+pub mod tracks {
+	use super::*;
+
+	/// The log target.
+	const TARGET: &'static str = "runtime::referenda::migration::tracks";
+
+	/// Copies the static tracks of [`Config::Tracks`] to storage, from where they may be updated
+	/// by [`Config::UpdateOrigin`].
+	///
+	/// Does nothing if some tracks are already kept in storage. Since the static tracks are also
+	/// copied by the first call adding, updating or removing a track, this migration is optional.
+	pub struct MigrateTracksToStorage<T, I = ()>(PhantomData<(T, I)>);
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateTracksToStorage<T, I> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			Ok(Pallet::<T, I>::track_ids().encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let copied = Pallet::<T, I>::ensure_tracks_stored();
+			log::info!(target: TARGET, "copied {} static tracks to storage.", copied);
+			// Each copied track also updates the counter of `TrackInfoFor`.
+			T::DbWeight::get().reads_writes(1, (copied as u64).saturating_mul(2))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let pre_track_ids: Vec<TrackIdOf<T, I>> = Decode::decode(&mut &state[..])
+				.expect("failed to decode the state from pre-upgrade.");
+			ensure!(TrackInfoFor::<T, I>::count() > 0, "tracks must be kept in storage.");
+			ensure!(Pallet::<T, I>::track_ids() == pre_track_ids, "must migrate all tracks.");
+			Ok(())
+		}
+	}
+}
+
 #[cfg(test)]
 pub mod test {
 	use super::*;
 	use crate::mock::{Test as T, *};
 	use core::str::FromStr;
+	use frame_support::assert_ok;
 
 	// create referendum status v0.
 	fn create_status_v0() -> v0::ReferendumStatusOf<T, ()> {
@@ -213,6 +332,7 @@ pub mod test {
 			v0::ReferendumInfoFor::<T, ()>::insert(5, approved_v0);
 			// run migration from v0 to v1.
 			v1::MigrateV0ToV1::<T, ()>::on_runtime_upgrade();
+			assert_eq!(Pallet::<T, ()>::on_chain_storage_version(), 1);
 			// fetch and assert migrated into v1 the ongoing referendum.
 			let ongoing_v1 = ReferendumInfoFor::<T, ()>::get(2).unwrap();
 			// referendum status schema is the same for v0 and v1.
@@ -227,6 +347,34 @@ pub mod test {
 					Some(Deposit { who: 2, amount: 20 })
 				)
 			);
+
+			// run migration from v1 to v2, counting the ongoing referendum.
+			v2::MigrateV1ToV2::<T, ()>::on_runtime_upgrade();
+			assert_eq!(Pallet::<T, ()>::on_chain_storage_version(), 2);
+			assert_eq!(OngoingCount::<T, ()>::get(status_v0.track), 1);
+			assert_eq!(OngoingCount::<T, ()>::iter_values().sum::<u32>(), 1);
+
+			// the migration does not count the referenda again once the storage version is 2.
+			v2::MigrateV1ToV2::<T, ()>::on_runtime_upgrade();
+			assert_eq!(OngoingCount::<T, ()>::iter_values().sum::<u32>(), 1);
+		});
+	}
+
+	#[test]
+	fn migrate_tracks_to_storage_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_eq!(TrackInfoFor::<T, ()>::count(), 0);
+			tracks::MigrateTracksToStorage::<T, ()>::on_runtime_upgrade();
+			assert_eq!(TrackInfoFor::<T, ()>::count(), 2);
+			for (id, info) in <T as Config<()>>::Tracks::tracks() {
+				assert_eq!(TrackInfoFor::<T, ()>::get(id), Some(info.to_stored()));
+			}
+
+			// Tracks already kept in storage are not overwritten.
+			assert_ok!(Referenda::remove_track(RuntimeOrigin::root(), 1));
+			tracks::MigrateTracksToStorage::<T, ()>::on_runtime_upgrade();
+			assert_eq!(TrackInfoFor::<T, ()>::count(), 1);
+			assert!(!TrackInfoFor::<T, ()>::contains_key(1));
 		});
 	}
 }
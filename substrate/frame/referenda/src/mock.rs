@@ -194,6 +194,7 @@ impl Config for Test {
 	type SubmitOrigin = frame_system::EnsureSigned<u64>;
 	type CancelOrigin = EnsureSignedBy<Four, u64>;
 	type KillOrigin = EnsureRoot<u64>;
+	type UpdateOrigin = EnsureRoot<u64>;
 	type Slash = ();
 	type Votes = u32;
 	type Tally = Tally;
@@ -201,6 +202,7 @@ impl Config for Test {
 	type MaxQueued = ConstU32<3>;
 	type UndecidingTimeout = ConstU64<20>;
 	type AlarmInterval = AlarmInterval;
+	type MaxTracks = ConstU32<3>;
 	type Tracks = TestTracksInfo;
 	type Preimages = Preimage;
 }
@@ -682,3 +682,246 @@ fn detects_incorrect_len() {
 		);
 	});
 }
+
+fn static_track(id: u8) -> StoredTrackInfoOf<Test> {
+	TestTracksInfo::info(id).unwrap().to_stored()
+}
+
+#[test]
+fn static_tracks_are_used_until_stored() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_eq!(TrackInfoFor::<Test>::count(), 0);
+		assert_eq!(<Referenda as Polling<Tally>>::classes(), vec![0, 1]);
+		assert_eq!(Referenda::track(1), Some(static_track(1)));
+
+		let mut info = static_track(1);
+		info.confirm_period = 5;
+		assert_ok!(Referenda::update_track(RuntimeOrigin::root(), 1, info.clone()));
+		System::assert_last_event(RuntimeEvent::Referenda(crate::Event::TrackUpdated { track: 1 }));
+
+		// The static tracks have been copied to storage.
+		assert_eq!(TrackInfoFor::<Test>::count(), 2);
+		assert_eq!(TrackInfoFor::<Test>::get(0), Some(static_track(0)));
+		assert_eq!(Referenda::track(1), Some(info));
+		assert_eq!(<Referenda as Polling<Tally>>::classes(), vec![0, 1]);
+	});
+}
+
+#[test]
+fn add_track_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		let proposal_origin: OriginCaller = RawOrigin::Signed(5).into();
+		let submit = || {
+			Referenda::submit(
+				RuntimeOrigin::signed(1),
+				Box::new(proposal_origin.clone()),
+				set_balance_proposal_bounded(1),
+				DispatchTime::At(10),
+			)
+		};
+		assert_noop!(submit(), Error::<Test>::NoTrack);
+
+		let mut info = static_track(1);
+		info.name = b"signed".to_vec().try_into().unwrap();
+		assert_noop!(
+			Referenda::add_track(
+				RuntimeOrigin::signed(1),
+				2,
+				info.clone(),
+				Some(Box::new(proposal_origin.clone()))
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			Referenda::add_track(RuntimeOrigin::root(), 1, info.clone(), None),
+			Error::<Test>::TrackExists
+		);
+		assert_ok!(Referenda::add_track(
+			RuntimeOrigin::root(),
+			2,
+			info.clone(),
+			Some(Box::new(proposal_origin.clone()))
+		));
+		System::assert_last_event(RuntimeEvent::Referenda(crate::Event::TrackAdded { track: 2 }));
+		assert_eq!(TrackInfoFor::<Test>::get(2), Some(info.clone()));
+		assert_eq!(TrackForOrigin::<Test>::get(&proposal_origin), Some(2));
+		assert_eq!(<Referenda as Polling<Tally>>::classes(), vec![0, 1, 2]);
+
+		// Proposals of the origin are submitted on the new track.
+		assert_ok!(submit());
+		assert_eq!(Referenda::ensure_ongoing(0).unwrap().track, 2);
+
+		// The origin cannot be assigned to another track.
+		assert_ok!(Referenda::remove_track(RuntimeOrigin::root(), 1));
+		assert_noop!(
+			Referenda::add_track(
+				RuntimeOrigin::root(),
+				1,
+				info.clone(),
+				Some(Box::new(proposal_origin))
+			),
+			Error::<Test>::OriginInUse
+		);
+		assert_ok!(Referenda::add_track(RuntimeOrigin::root(), 1, info.clone(), None));
+
+		// `MaxTracks` is 3.
+		assert_noop!(
+			Referenda::add_track(RuntimeOrigin::root(), 3, info, None),
+			Error::<Test>::TooManyTracks
+		);
+	});
+}
+
+#[test]
+fn tracks_with_malformed_curves_are_rejected() {
+	ExtBuilder::default().build_and_execute(|| {
+		let mut info = static_track(1);
+		info.min_approval = Curve::LinearDecreasing {
+			length: Perbill::from_percent(100),
+			floor: Perbill::from_percent(100),
+			ceil: Perbill::from_percent(50),
+		};
+		assert_noop!(
+			Referenda::update_track(RuntimeOrigin::root(), 1, info.clone()),
+			Error::<Test>::BadTrackInfo
+		);
+		assert_noop!(
+			Referenda::add_track(RuntimeOrigin::root(), 2, info, None),
+			Error::<Test>::BadTrackInfo
+		);
+
+		let mut info = static_track(1);
+		info.min_support = Curve::SteppedDecreasing {
+			begin: Perbill::from_percent(80),
+			end: Perbill::from_percent(30),
+			step: Perbill::from_percent(10),
+			period: Perbill::zero(),
+		};
+		assert_noop!(
+			Referenda::update_track(RuntimeOrigin::root(), 1, info),
+			Error::<Test>::BadTrackInfo
+		);
+
+		let mut info = static_track(1);
+		info.max_deciding = 0;
+		assert_noop!(
+			Referenda::update_track(RuntimeOrigin::root(), 1, info),
+			Error::<Test>::BadTrackInfo
+		);
+
+		let mut info = static_track(1);
+		info.decision_period = 0;
+		assert_noop!(
+			Referenda::update_track(RuntimeOrigin::root(), 1, info),
+			Error::<Test>::BadTrackInfo
+		);
+	});
+}
+
+#[test]
+fn update_track_applies_to_ongoing_referenda() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_noop!(
+			Referenda::update_track(RuntimeOrigin::root(), 2, static_track(0)),
+			Error::<Test>::BadTrack
+		);
+
+		assert_ok!(Referenda::submit(
+			RuntimeOrigin::signed(1),
+			Box::new(RawOrigin::Root.into()),
+			set_balance_proposal_bounded(1),
+			DispatchTime::At(10),
+		));
+		// Shorten the prepare period of the root track from 4 to 2 blocks.
+		let mut info = static_track(0);
+		info.prepare_period = 2;
+		assert_ok!(Referenda::update_track(RuntimeOrigin::root(), 0, info));
+		assert_ok!(Referenda::place_decision_deposit(RuntimeOrigin::signed(2), 0));
+		run_to(3);
+		assert_eq!(deciding_since(0), 3);
+		assert_eq!(DecidingCount::<Test>::get(0), 1);
+	});
+}
+
+#[test]
+fn raising_max_deciding_begins_deciding_queued_referenda() {
+	ExtBuilder::default().build_and_execute(|| {
+		for i in 0..3 {
+			assert_ok!(Referenda::submit(
+				RuntimeOrigin::signed(1),
+				Box::new(RawOrigin::Root.into()),
+				set_balance_proposal_bounded(1),
+				DispatchTime::At(10),
+			));
+			assert_ok!(Referenda::place_decision_deposit(RuntimeOrigin::signed(2), i));
+		}
+		run_to(5);
+		assert_eq!(DecidingCount::<Test>::get(0), 1);
+		assert_eq!(TrackQueue::<Test>::get(0).len(), 2);
+
+		let mut info = static_track(0);
+		info.max_deciding = 2;
+		assert_ok!(Referenda::update_track(RuntimeOrigin::root(), 0, info));
+		run_to(6);
+		assert_eq!(DecidingCount::<Test>::get(0), 2);
+		assert_eq!(TrackQueue::<Test>::get(0).len(), 1);
+		let deciding = (0..3).filter(|&i| Referenda::ensure_ongoing(i).unwrap().deciding.is_some());
+		assert_eq!(deciding.count(), 2);
+	});
+}
+
+#[test]
+fn remove_track_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_noop!(Referenda::remove_track(RuntimeOrigin::signed(1), 1), BadOrigin);
+		assert_noop!(Referenda::remove_track(RuntimeOrigin::root(), 2), Error::<Test>::BadTrack);
+
+		// The root track has a referendum being decided.
+		let r = Failing.create();
+		assert_eq!(Referenda::ensure_ongoing(r).unwrap().track, 0);
+		assert_noop!(Referenda::remove_track(RuntimeOrigin::root(), 0), Error::<Test>::TrackInUse);
+
+		let signed_origin: OriginCaller = RawOrigin::Signed(5).into();
+		assert_ok!(Referenda::add_track(
+			RuntimeOrigin::root(),
+			2,
+			static_track(1),
+			Some(Box::new(signed_origin.clone()))
+		));
+		assert_ok!(Referenda::remove_track(RuntimeOrigin::root(), 2));
+		System::assert_last_event(RuntimeEvent::Referenda(crate::Event::TrackRemoved { track: 2 }));
+		assert_eq!(TrackForOrigin::<Test>::get(&signed_origin), None);
+
+		// A referendum still preparing also keeps its track in use.
+		assert_ok!(Referenda::submit(
+			RuntimeOrigin::signed(1),
+			Box::new(RawOrigin::None.into()),
+			set_balance_proposal_bounded(1),
+			DispatchTime::At(10),
+		));
+		let preparing = ReferendumCount::<Test>::get() - 1;
+		let status = Referenda::ensure_ongoing(preparing).unwrap();
+		assert_eq!((status.track, status.decision_deposit), (1, None));
+		assert_eq!(OngoingCount::<Test>::get(1), 1);
+		assert_noop!(Referenda::remove_track(RuntimeOrigin::root(), 1), Error::<Test>::TrackInUse);
+		assert_ok!(Referenda::cancel(RuntimeOrigin::signed(4), preparing));
+		assert_eq!(OngoingCount::<Test>::get(1), 0);
+
+		assert_ok!(Referenda::remove_track(RuntimeOrigin::root(), 1));
+		assert_eq!(<Referenda as Polling<Tally>>::classes(), vec![0]);
+		assert_noop!(
+			Referenda::submit(
+				RuntimeOrigin::signed(1),
+				Box::new(RawOrigin::None.into()),
+				set_balance_proposal_bounded(1),
+				DispatchTime::At(10),
+			),
+			Error::<Test>::NoTrack
+		);
+
+		assert_ok!(Referenda::cancel(RuntimeOrigin::signed(4), r));
+		assert_eq!(OngoingCount::<Test>::get(0), 0);
+		run_to(System::block_number() + 1);
+		assert_noop!(Referenda::remove_track(RuntimeOrigin::root(), 0), Error::<Test>::LastTrack);
+	});
+}
@@ -21,7 +21,7 @@ use super::*;
 use codec::{Decode, Encode, EncodeLike, MaxEncodedLen};
 use core::fmt::Debug;
 use frame_support::{
-	traits::{schedule::v3::Anon, Bounded},
+	traits::{schedule::v3::Anon, Bounded, ConstU32},
 	Parameter,
 };
 use scale_info::TypeInfo;
@@ -62,6 +62,7 @@ pub type ReferendumStatusOf<T, I> = ReferendumStatus<
 >;
 pub type DecidingStatusOf<T> = DecidingStatus<BlockNumberFor<T>>;
 pub type TrackInfoOf<T, I = ()> = TrackInfo<BalanceOf<T, I>, BlockNumberFor<T>>;
+pub type StoredTrackInfoOf<T, I = ()> = TrackInfo<BalanceOf<T, I>, BlockNumberFor<T>, TrackName>;
 pub type TrackIdOf<T, I> =
 	<<T as Config<I>>::Tracks as TracksInfo<BalanceOf<T, I>, BlockNumberFor<T>>>::Id;
 pub type ScheduleAddressOf<T, I> = <<T as Config<I>>::Scheduler as Anon<
@@ -73,6 +74,12 @@ pub type ScheduleAddressOf<T, I> = <<T as Config<I>>::Scheduler as Anon<
 /// A referendum index.
 pub type ReferendumIndex = u32;
 
+/// The maximum length of the name of a track kept in storage.
+pub const MAX_TRACK_NAME_LEN: u32 = 32;
+
+/// The name of a track kept in storage.
+pub type TrackName = BoundedVec<u8, ConstU32<MAX_TRACK_NAME_LEN>>;
+
 pub trait InsertSorted<T> {
 	/// Inserts an item into a sorted series.
 	///
@@ -111,10 +118,14 @@ pub struct Deposit<AccountId, Balance> {
 	pub amount: Balance,
 }
 
-#[derive(Clone, Encode, TypeInfo)]
-pub struct TrackInfo<Balance, Moment> {
+/// The parameters of a track.
+///
+/// Tracks defined in code by [`TracksInfo`] are named by a `&'static str`, while tracks kept in
+/// the storage of the pallet are named by a [`TrackName`].
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct TrackInfo<Balance, Moment, Name = &'static str> {
 	/// Name of this track.
-	pub name: &'static str,
+	pub name: Name,
 	/// A limit for the number of referenda on this track that can be being decided at once.
 	/// For Root origin this should generally be just one.
 	pub max_deciding: u32,
@@ -136,6 +147,25 @@ pub struct TrackInfo<Balance, Moment> {
 	pub min_support: Curve,
 }
 
+impl<Balance: Clone, Moment: Clone> TrackInfo<Balance, Moment> {
+	/// Convert into the form of the track kept in storage.
+	///
+	/// The name is truncated to [`MAX_TRACK_NAME_LEN`] bytes.
+	pub fn to_stored(&self) -> TrackInfo<Balance, Moment, TrackName> {
+		TrackInfo {
+			name: TrackName::truncate_from(self.name.as_bytes().to_vec()),
+			max_deciding: self.max_deciding,
+			decision_deposit: self.decision_deposit.clone(),
+			prepare_period: self.prepare_period.clone(),
+			decision_period: self.decision_period.clone(),
+			confirm_period: self.confirm_period.clone(),
+			min_enactment_period: self.min_enactment_period.clone(),
+			min_approval: self.min_approval.clone(),
+			min_support: self.min_support.clone(),
+		}
+	}
+}
+
 /// Information on the voting tracks.
 pub trait TracksInfo<Balance, Moment> {
 	/// The identifier for a track.
@@ -258,7 +288,8 @@ impl<
 		Tally: Eq + PartialEq + Debug + Encode + Decode + TypeInfo + Clone,
 		AccountId: Eq + PartialEq + Debug + Encode + Decode + TypeInfo + Clone,
 		ScheduleAddress: Eq + PartialEq + Debug + Encode + Decode + TypeInfo + Clone,
-	> ReferendumInfo<TrackId, RuntimeOrigin, Moment, Call, Balance, Tally, AccountId, ScheduleAddress>
+	>
+	ReferendumInfo<TrackId, RuntimeOrigin, Moment, Call, Balance, Tally, AccountId, ScheduleAddress>
 {
 	/// Take the Decision Deposit from `self`, if there is one. Returns an `Err` if `self` is not
 	/// in a valid state for the Decision Deposit to be refunded.
@@ -511,6 +542,18 @@ impl Curve {
 	pub fn passing(&self, x: Perbill, y: Perbill) -> bool {
 		y >= self.threshold(x)
 	}
+
+	/// Return `true` iff the curve is well-formed, i.e. it is non-increasing and none of its
+	/// evaluations divide by zero.
+	pub fn is_valid(&self) -> bool {
+		match self {
+			Self::LinearDecreasing { length, floor, ceil } => !length.is_zero() && floor <= ceil,
+			Self::SteppedDecreasing { begin, end, step, period } =>
+				end <= begin && !step.is_zero() && !period.is_zero(),
+			Self::Reciprocal { factor, x_offset, .. } =>
+				factor.into_inner() > 0 && x_offset.into_inner() > 0,
+		}
+	}
 }
 
 #[cfg(feature = "std")]
@@ -757,4 +800,33 @@ mod tests {
 			Err("The tracks that were returned by `tracks` were not sorted by `Id`")
 		);
 	}
+
+	#[test]
+	fn curve_validity_works() {
+		fn pc(x: u32) -> Perbill {
+			Perbill::from_percent(x)
+		}
+
+		assert!(Curve::LinearDecreasing { length: pc(50), floor: pc(10), ceil: pc(90) }.is_valid());
+		assert!(!Curve::LinearDecreasing { length: pc(0), floor: pc(10), ceil: pc(90) }.is_valid());
+		assert!(!Curve::LinearDecreasing { length: pc(50), floor: pc(90), ceil: pc(10) }.is_valid());
+
+		let stepped =
+			|begin, end, step, period| Curve::SteppedDecreasing { begin, end, step, period };
+		assert!(stepped(pc(80), pc(30), pc(10), pc(15)).is_valid());
+		assert!(!stepped(pc(30), pc(80), pc(10), pc(15)).is_valid());
+		assert!(!stepped(pc(80), pc(30), pc(0), pc(15)).is_valid());
+		assert!(!stepped(pc(80), pc(30), pc(10), pc(0)).is_valid());
+
+		assert!(Curve::make_reciprocal(4, 28, percent(80), percent(50), percent(100)).is_valid());
+		let reciprocal = |factor, x_offset| Curve::Reciprocal {
+			factor: FixedI64::from_float(factor),
+			x_offset: FixedI64::from_float(x_offset),
+			y_offset: FixedI64::from_float(0.1),
+		};
+		assert!(reciprocal(0.03, 0.04).is_valid());
+		assert!(!reciprocal(0.0, 0.04).is_valid());
+		assert!(!reciprocal(-0.03, 0.04).is_valid());
+		assert!(!reciprocal(0.03, 0.0).is_valid());
+	}
 }
@@ -81,6 +81,9 @@ pub trait WeightInfo {
 	fn nudge_referendum_rejected() -> Weight;
 	fn set_some_metadata() -> Weight;
 	fn clear_metadata() -> Weight;
+	fn add_track() -> Weight;
+	fn update_track() -> Weight;
+	fn remove_track() -> Weight;
 }
 
 /// Weights for `pallet_referenda` using the Substrate node and recommended hardware.
@@ -518,6 +521,48 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Referenda::CounterForTrackInfoFor` (r:1 w:1)
+	/// Proof: `Referenda::CounterForTrackInfoFor` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Referenda::TrackInfoFor` (r:1 w:2)
+	/// Proof: `Referenda::TrackInfoFor` (`max_values`: None, `max_size`: Some(129), added: 2604, mode: `MaxEncodedLen`)
+	/// Storage: `Referenda::TrackForOrigin` (r:1 w:1)
+	/// Proof: `Referenda::TrackForOrigin` (`max_values`: None, `max_size`: Some(47), added: 2522, mode: `MaxEncodedLen`)
+	fn add_track() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(22_107_000, 3594)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Referenda::CounterForTrackInfoFor` (r:1 w:0)
+	/// Proof: `Referenda::CounterForTrackInfoFor` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Referenda::TrackInfoFor` (r:1 w:1)
+	/// Proof: `Referenda::TrackInfoFor` (`max_values`: None, `max_size`: Some(129), added: 2604, mode: `MaxEncodedLen`)
+	/// Storage: `Referenda::TrackQueue` (r:1 w:0)
+	/// Proof: `Referenda::TrackQueue` (`max_values`: None, `max_size`: Some(2012), added: 4487, mode: `MaxEncodedLen`)
+	/// Storage: `Referenda::DecidingCount` (r:1 w:1)
+	/// Proof: `Referenda::DecidingCount` (`max_values`: None, `max_size`: Some(14), added: 2489, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::Agenda` (r:1 w:1)
+	/// Proof: `Scheduler::Agenda` (`max_values`: None, `max_size`: Some(107022), added: 109497, mode: `MaxEncodedLen`)
+	fn update_track() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(37_056_000, 110487)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Referenda::CounterForTrackInfoFor` (r:1 w:1)
+	/// Proof: `Referenda::CounterForTrackInfoFor` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Referenda::TrackInfoFor` (r:1 w:1)
+	/// Proof: `Referenda::TrackInfoFor` (`max_values`: None, `max_size`: Some(129), added: 2604, mode: `MaxEncodedLen`)
+	/// Storage: `Referenda::OngoingCount` (r:1 w:1)
+	/// Proof: `Referenda::OngoingCount` (`max_values`: None, `max_size`: Some(14), added: 2489, mode: `MaxEncodedLen`)
+	/// Storage: `Referenda::TrackForOrigin` (r:2 w:1)
+	/// Proof: `Referenda::TrackForOrigin` (`max_values`: None, `max_size`: Some(47), added: 2522, mode: `MaxEncodedLen`)
+	fn remove_track() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(27_311_000, 6010)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -954,4 +999,46 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Referenda::CounterForTrackInfoFor` (r:1 w:1)
+	/// Proof: `Referenda::CounterForTrackInfoFor` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Referenda::TrackInfoFor` (r:1 w:2)
+	/// Proof: `Referenda::TrackInfoFor` (`max_values`: None, `max_size`: Some(129), added: 2604, mode: `MaxEncodedLen`)
+	/// Storage: `Referenda::TrackForOrigin` (r:1 w:1)
+	/// Proof: `Referenda::TrackForOrigin` (`max_values`: None, `max_size`: Some(47), added: 2522, mode: `MaxEncodedLen`)
+	fn add_track() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(22_107_000, 3594)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Referenda::CounterForTrackInfoFor` (r:1 w:0)
+	/// Proof: `Referenda::CounterForTrackInfoFor` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Referenda::TrackInfoFor` (r:1 w:1)
+	/// Proof: `Referenda::TrackInfoFor` (`max_values`: None, `max_size`: Some(129), added: 2604, mode: `MaxEncodedLen`)
+	/// Storage: `Referenda::TrackQueue` (r:1 w:0)
+	/// Proof: `Referenda::TrackQueue` (`max_values`: None, `max_size`: Some(2012), added: 4487, mode: `MaxEncodedLen`)
+	/// Storage: `Referenda::DecidingCount` (r:1 w:1)
+	/// Proof: `Referenda::DecidingCount` (`max_values`: None, `max_size`: Some(14), added: 2489, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::Agenda` (r:1 w:1)
+	/// Proof: `Scheduler::Agenda` (`max_values`: None, `max_size`: Some(107022), added: 109497, mode: `MaxEncodedLen`)
+	fn update_track() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(37_056_000, 110487)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Referenda::CounterForTrackInfoFor` (r:1 w:1)
+	/// Proof: `Referenda::CounterForTrackInfoFor` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Referenda::TrackInfoFor` (r:1 w:1)
+	/// Proof: `Referenda::TrackInfoFor` (`max_values`: None, `max_size`: Some(129), added: 2604, mode: `MaxEncodedLen`)
+	/// Storage: `Referenda::OngoingCount` (r:1 w:1)
+	/// Proof: `Referenda::OngoingCount` (`max_values`: None, `max_size`: Some(14), added: 2489, mode: `MaxEncodedLen`)
+	/// Storage: `Referenda::TrackForOrigin` (r:2 w:1)
+	/// Proof: `Referenda::TrackForOrigin` (`max_values`: None, `max_size`: Some(47), added: 2522, mode: `MaxEncodedLen`)
+	fn remove_track() -> Weight {
+		// Not benchmarked yet. Estimated from the storage accesses of the call.
+		Weight::from_parts(27_311_000, 6010)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
 }
@@ -0,0 +1,278 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Benchmarks for Smart Accounts Pallet
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::{Pallet as SmartAccounts, *};
+use crate::credential::sign_with_passkey;
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+const SEED: u32 = 0;
+
+/// Create a funded smart account owned by a passkey.
+fn create_account<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+	let (owner, _) = sign_with_passkey(1, &[0; 32], T::PasskeyRelyingParty::get());
+	SmartAccounts::<T>::create(RawOrigin::Signed(caller.clone()).into(), Credential::Key(owner), 0)
+		.expect("caller is funded; qed");
+	let account = SmartAccounts::<T>::account_id(&caller, 0);
+	T::Currency::make_free_balance_be(&account, BalanceOf::<T>::max_value() / 2u32.into());
+	account
+}
+
+/// Add `s` session keys to `account`.
+fn add_session_keys<T: Config>(account: &T::AccountId, s: u32) {
+	for i in 0..s {
+		let (key, _) = sign_with_passkey(i as u8 + 2, &[0; 32], T::PasskeyRelyingParty::get());
+		SmartAccounts::<T>::add_session_key(
+			RawOrigin::Signed(account.clone()).into(),
+			key,
+			BlockNumberFor::<T>::max_value(),
+			Zero::zero(),
+		)
+		.expect("account exists and has space for the key; qed");
+	}
+}
+
+/// The accounts used as `g` guardians.
+fn guardians<T: Config>(g: u32) -> Vec<T::AccountId> {
+	(0..g).map(|i| account("guardian", i, SEED)).collect()
+}
+
+/// Set `g` guardians with a threshold of `g` for `who`.
+fn setup_guardians<T: Config>(who: &T::AccountId, g: u32) -> Vec<T::AccountId> {
+	let guardians = guardians::<T>(g);
+	SmartAccounts::<T>::set_guardians(
+		RawOrigin::Signed(who.clone()).into(),
+		BoundedVec::truncate_from(guardians.clone()),
+		g,
+		Zero::zero(),
+	)
+	.expect("account exists and the threshold is valid; qed");
+	guardians
+}
+
+#[benchmarks(
+	where
+		<T as frame_system::Config>::RuntimeCall:
+			Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+)]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn create() {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		let (owner, _) = sign_with_passkey(1, &[0; 32], T::PasskeyRelyingParty::get());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), Credential::Key(owner), 0);
+
+		assert!(Accounts::<T>::contains_key(SmartAccounts::<T>::account_id(&caller, 0)));
+	}
+
+	// Passkeys are the most expensive built-in scheme covered by this weight.
+	#[benchmark]
+	fn execute() {
+		let account = create_account::<T>();
+		let nonce = frame_system::Pallet::<T>::account_nonce(&account);
+		let call: <T as Config>::RuntimeCall =
+			frame_system::Call::<T>::remark { remark: vec![] }.into();
+		let message = SmartAccounts::<T>::signing_payload(&account, &nonce, &call);
+		let (_, proof) = sign_with_passkey(1, &message, T::PasskeyRelyingParty::get());
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::None,
+			account.clone(),
+			nonce,
+			Box::new(call),
+			Signer::Owner,
+			BoundedVec::truncate_from(proof),
+		);
+
+		assert_eq!(frame_system::Pallet::<T>::account_nonce(&account), nonce + 1u32.into());
+	}
+
+	#[benchmark]
+	fn set_owner() {
+		let account = create_account::<T>();
+		let (owner, _) = sign_with_passkey(2, &[0; 32], T::PasskeyRelyingParty::get());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(account.clone()), Credential::Key(owner.clone()));
+
+		assert_eq!(Accounts::<T>::get(&account).unwrap().owner, Credential::Key(owner));
+	}
+
+	#[benchmark]
+	fn add_session_key() {
+		let account = create_account::<T>();
+		let (key, _) = sign_with_passkey(2, &[0; 32], T::PasskeyRelyingParty::get());
+		let id = SmartAccounts::<T>::session_key_id(&key);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(account.clone()),
+			key,
+			BlockNumberFor::<T>::max_value(),
+			BalanceOf::<T>::max_value(),
+		);
+
+		assert!(SessionKeys::<T>::contains_key(&account, id));
+	}
+
+	#[benchmark]
+	fn remove_session_key() {
+		let account = create_account::<T>();
+		add_session_keys::<T>(&account, 1);
+		let (key, _) = sign_with_passkey(2, &[0; 32], T::PasskeyRelyingParty::get());
+		let id = SmartAccounts::<T>::session_key_id(&key);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(account.clone()), id);
+
+		assert!(!SessionKeys::<T>::contains_key(&account, id));
+	}
+
+	#[benchmark]
+	fn set_guardians(g: Linear<0, { T::MaxGuardians::get() }>) {
+		let account = create_account::<T>();
+		let guardians = guardians::<T>(g);
+		let (owner, _) = sign_with_passkey(2, &[0; 32], T::PasskeyRelyingParty::get());
+		Recoveries::<T>::insert(
+			&account,
+			Recovery {
+				new_owner: Credential::Key(owner),
+				approvals: Default::default(),
+				started: Zero::zero(),
+			},
+		);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(account.clone()),
+			BoundedVec::truncate_from(guardians),
+			g,
+			Zero::zero(),
+		);
+
+		assert!(!Recoveries::<T>::contains_key(&account));
+	}
+
+	#[benchmark]
+	fn initiate_recovery(g: Linear<1, { T::MaxGuardians::get() }>) {
+		let account = create_account::<T>();
+		let guardians = setup_guardians::<T>(&account, g);
+		let (owner, _) = sign_with_passkey(2, &[0; 32], T::PasskeyRelyingParty::get());
+
+		// The last guardian is the most expensive to look up.
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(guardians[g as usize - 1].clone()),
+			account.clone(),
+			Credential::Key(owner),
+		);
+
+		assert!(Recoveries::<T>::contains_key(&account));
+	}
+
+	#[benchmark]
+	fn approve_recovery(g: Linear<2, { T::MaxGuardians::get() }>) -> Result<(), BenchmarkError> {
+		let account = create_account::<T>();
+		let guardians = setup_guardians::<T>(&account, g);
+		let (owner, _) = sign_with_passkey(2, &[0; 32], T::PasskeyRelyingParty::get());
+		SmartAccounts::<T>::initiate_recovery(
+			RawOrigin::Signed(guardians[0].clone()).into(),
+			account.clone(),
+			Credential::Key(owner),
+		)?;
+		for guardian in &guardians[1..g as usize - 1] {
+			SmartAccounts::<T>::approve_recovery(
+				RawOrigin::Signed(guardian.clone()).into(),
+				account.clone(),
+			)?;
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(guardians[g as usize - 1].clone()), account.clone());
+
+		assert_eq!(Recoveries::<T>::get(&account).unwrap().approvals.len(), g as usize);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn finish_recovery(s: Linear<0, { T::MaxSessionKeys::get() }>) {
+		let account = create_account::<T>();
+		add_session_keys::<T>(&account, s);
+		let guardians = setup_guardians::<T>(&account, T::MaxGuardians::get());
+		let (owner, _) = sign_with_passkey(u8::MAX, &[0; 32], T::PasskeyRelyingParty::get());
+		Recoveries::<T>::insert(
+			&account,
+			Recovery {
+				new_owner: Credential::Key(owner.clone()),
+				approvals: BoundedVec::truncate_from(guardians),
+				started: Zero::zero(),
+			},
+		);
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), account.clone());
+
+		assert_eq!(Accounts::<T>::get(&account).unwrap().owner, Credential::Key(owner));
+		assert_eq!(SessionKeys::<T>::iter_prefix(&account).count(), 0);
+	}
+
+	#[benchmark]
+	fn cancel_recovery() {
+		let account = create_account::<T>();
+		let (owner, _) = sign_with_passkey(2, &[0; 32], T::PasskeyRelyingParty::get());
+		Recoveries::<T>::insert(
+			&account,
+			Recovery {
+				new_owner: Credential::Key(owner),
+				approvals: Default::default(),
+				started: Zero::zero(),
+			},
+		);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(account.clone()));
+
+		assert!(!Recoveries::<T>::contains_key(&account));
+	}
+
+	#[benchmark]
+	fn kill(s: Linear<0, { T::MaxSessionKeys::get() }>) {
+		let account = create_account::<T>();
+		add_session_keys::<T>(&account, s);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(account.clone()));
+
+		assert!(!Accounts::<T>::contains_key(&account));
+	}
+
+	impl_benchmark_test_suite!(SmartAccounts, crate::tests::new_test_ext(), crate::tests::Test);
+}
@@ -0,0 +1,237 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Credentials which authorize the transactions of a smart account.
+//!
+//! A [`KeyCredential`] is a public key of one of the built-in signature schemes. Proofs are
+//! verified by the pallet itself with a cost bounded by the benchmarked weight. A
+//! [`Credential::Custom`] delegates the decision to the [`Verifier`] configured by the runtime.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::weights::Weight;
+use scale_info::TypeInfo;
+use sp_core::{ecdsa, ed25519, sr25519};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// A public key of one of the built-in signature schemes.
+///
+/// Every scheme signs the 32 byte message returned by [`crate::Pallet::signing_payload`].
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum KeyCredential {
+	/// A sr25519 public key.
+	Sr25519(sr25519::Public),
+	/// An ed25519 public key.
+	Ed25519(ed25519::Public),
+	/// A compressed secp256k1 public key. The message is hashed with blake2-256 before signing.
+	Ecdsa(ecdsa::Public),
+	/// A compressed secp256r1 (P-256) public key.
+	///
+	/// Proofs are 64 byte signatures over the SHA-256 hash of the message.
+	Secp256r1([u8; 33]),
+	/// The compressed secp256r1 public key of a passkey.
+	///
+	/// Proofs are SCALE encoded [`WebAuthnAssertion`]s whose challenge is the message.
+	Passkey([u8; 33]),
+}
+
+/// The party which decides whether a transaction of a smart account is valid.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Credential<AccountId> {
+	/// A key of a built-in signature scheme.
+	Key(KeyCredential),
+	/// The verifier at this account. See [`Verifier`].
+	Custom(AccountId),
+}
+
+/// A WebAuthn assertion as produced by `navigator.credentials.get`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct WebAuthnAssertion {
+	/// The authenticator data. Must start with the SHA-256 hash of the relying party id and
+	/// have the user present flag set.
+	pub authenticator_data: Vec<u8>,
+	/// The client data JSON. Its challenge must be the base64url encoded message and its origin
+	/// the relying party.
+	pub client_data_json: Vec<u8>,
+	/// The DER encoded signature over `authenticator_data ++ sha256(client_data_json)`.
+	pub signature: Vec<u8>,
+}
+
+/// Verifies authorizations of smart accounts owned by a [`Credential::Custom`].
+///
+/// This allows signature schemes and authorization rules which are not built into the pallet,
+/// e.g. a multisig implemented by a contract.
+pub trait Verifier<AccountId> {
+	/// Whether `verifier` can decide about the transactions of a smart account.
+	///
+	/// Only verifiers passing this check can become the owner of a smart account.
+	fn is_verifier(verifier: &AccountId) -> bool;
+
+	/// The maximum weight [`Self::verify`] consumes.
+	///
+	/// It is charged on top of the weight of [`crate::Pallet::execute`] and needs to be derived
+	/// from benchmarks of the implementation.
+	fn weight() -> Weight;
+
+	/// Whether `verifier` accepts `proof` as authorization of `message` for `account`.
+	///
+	/// This is called while validating and dispatching the transaction. Any state changes are
+	/// discarded.
+	fn verify(verifier: &AccountId, account: &AccountId, message: &[u8; 32], proof: &[u8]) -> bool;
+}
+
+/// Custom verifiers are not supported.
+impl<AccountId> Verifier<AccountId> for () {
+	fn is_verifier(_verifier: &AccountId) -> bool {
+		false
+	}
+
+	fn weight() -> Weight {
+		Weight::zero()
+	}
+
+	fn verify(
+		_verifier: &AccountId,
+		_account: &AccountId,
+		_message: &[u8; 32],
+		_proof: &[u8],
+	) -> bool {
+		false
+	}
+}
+
+impl KeyCredential {
+	/// Whether `proof` is a valid signature of `message` by this key.
+	///
+	/// Passkey assertions need to be made for `relying_party`, the domain of the relying party.
+	pub fn verify(&self, message: &[u8; 32], proof: &[u8], relying_party: &str) -> bool {
+		match self {
+			Self::Sr25519(public) => sr25519::Signature::try_from(proof)
+				.map_or(false, |sig| sp_io::crypto::sr25519_verify(&sig, message, public)),
+			Self::Ed25519(public) => ed25519::Signature::try_from(proof)
+				.map_or(false, |sig| sp_io::crypto::ed25519_verify(&sig, message, public)),
+			Self::Ecdsa(public) => ecdsa::Signature::try_from(proof)
+				.map_or(false, |sig| sp_io::crypto::ecdsa_verify(&sig, message, public)),
+			Self::Secp256r1(public) => verify_p256(public, message, proof, false),
+			Self::Passkey(public) => WebAuthnAssertion::decode(&mut &proof[..])
+				.map_or(false, |assertion| assertion.verify(public, message, relying_party)),
+		}
+	}
+}
+
+impl WebAuthnAssertion {
+	/// Whether this assertion was made by `public` for `relying_party` over the challenge
+	/// `message`.
+	fn verify(&self, public: &[u8; 33], message: &[u8; 32], relying_party: &str) -> bool {
+		// Otherwise an assertion obtained by any website could authorize transactions.
+		let rp_id_hash = sp_io::hashing::sha2_256(relying_party.as_bytes());
+		if self.authenticator_data.get(..32) != Some(&rp_id_hash[..]) {
+			return false
+		}
+		// The user present flag is the lowest bit of the flags following the rp id hash.
+		if self.authenticator_data.get(32).map_or(true, |flags| flags & 0x01 == 0) {
+			return false
+		}
+		if !contains(&self.client_data_json, br#""type":"webauthn.get""#) {
+			return false
+		}
+		if !contains(&self.client_data_json, &origin(relying_party)) {
+			return false
+		}
+		let mut challenge = br#""challenge":""#.to_vec();
+		challenge.extend(base64url(message));
+		challenge.push(b'"');
+		if !contains(&self.client_data_json, &challenge) {
+			return false
+		}
+
+		let mut signed = self.authenticator_data.clone();
+		signed.extend(sp_io::hashing::sha2_256(&self.client_data_json));
+		verify_p256(public, &signed, &self.signature, true)
+	}
+}
+
+fn verify_p256(public: &[u8; 33], message: &[u8], signature: &[u8], der: bool) -> bool {
+	use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+
+	let Ok(key) = VerifyingKey::from_sec1_bytes(public) else { return false };
+	let signature =
+		if der { Signature::from_der(signature) } else { Signature::from_slice(signature) };
+	signature.map_or(false, |sig| key.verify(message, &sig).is_ok())
+}
+
+/// The `origin` member of the client data of assertions made on `relying_party`.
+fn origin(relying_party: &str) -> Vec<u8> {
+	let mut origin = br#""origin":"https://"#.to_vec();
+	origin.extend(relying_party.as_bytes());
+	origin.push(b'"');
+	origin
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+	haystack.windows(needle.len()).any(|window| window == needle)
+}
+
+/// Encodes `data` as base64url without padding as required for WebAuthn challenges.
+pub(crate) fn base64url(data: &[u8]) -> Vec<u8> {
+	const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+	let mut out = Vec::with_capacity((data.len() * 4 + 2) / 3);
+	for chunk in data.chunks(3) {
+		let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+		let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+		for i in 0..=chunk.len() {
+			out.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize]);
+		}
+	}
+	out
+}
+
+/// Sign `message` with the passkey derived from `seed` on `relying_party`.
+///
+/// Returns the credential of the passkey and the proof expected by [`KeyCredential::verify`].
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+pub(crate) fn sign_with_passkey(
+	seed: u8,
+	message: &[u8; 32],
+	relying_party: &str,
+) -> (KeyCredential, Vec<u8>) {
+	use p256::ecdsa::{signature::Signer, Signature, SigningKey};
+
+	let key = SigningKey::from_slice(&[seed.max(1); 32]).expect("non-zero scalar is a valid key");
+	let public = key.verifying_key().to_encoded_point(true);
+	let public: [u8; 33] = public.as_bytes().try_into().expect("compressed points have 33 bytes");
+
+	// The rp id hash followed by the user present flag and a zero signature counter.
+	let mut authenticator_data = sp_io::hashing::sha2_256(relying_party.as_bytes()).to_vec();
+	authenticator_data.extend([0x01, 0, 0, 0, 0]);
+	let mut client_data_json = br#"{"type":"webauthn.get","challenge":""#.to_vec();
+	client_data_json.extend(base64url(message));
+	client_data_json.extend(b"\",");
+	client_data_json.extend(origin(relying_party));
+	client_data_json.push(b'}');
+
+	let mut signed = authenticator_data.clone();
+	signed.extend(sp_io::hashing::sha2_256(&client_data_json));
+	let signature: Signature = key.sign(&signed);
+	let assertion = WebAuthnAssertion {
+		authenticator_data,
+		client_data_json,
+		signature: signature.to_der().as_bytes().to_vec(),
+	};
+	(KeyCredential::Passkey(public), assertion.encode())
+}
@@ -0,0 +1,953 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Smart Accounts Pallet
+//! A pallet for accounts whose transactions are authorized by programmable credentials.
+//!
+//! - [`Config`]
+//! - [`Call`]
+//!
+//! ## Overview
+//!
+//! Like a pure proxy, a smart account is a keyless account derived from its creator. Unlike a
+//! proxy, it is not controlled by other accounts but by a [`Credential`]: either a public key of
+//! one of the built-in signature schemes, including passkeys, or a custom verifier such as a
+//! contract which decides about every transaction (see [`Verifier`]).
+//!
+//! Transactions of a smart account are submitted as unsigned [`Call::execute`] extrinsics. Their
+//! validity is decided during transaction validation by verifying the proof against the
+//! credential. They share the nonce of `frame_system` with regular extrinsics of the account and
+//! the transaction fee is withdrawn from the smart account through
+//! `pallet_transaction_payment`.
+//!
+//! In addition to its owner credential, a smart account can authorize session keys with an
+//! expiry and a spending limit, and nominate guardians which can replace a lost owner credential
+//! after a delay.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! * `create` - Create a new smart account owned by a credential.
+//! * `execute` - Dispatch a call from a smart account authorized by its owner or a session key.
+//! * `set_owner` - Replace the owner credential of the calling smart account.
+//! * `add_session_key` - Authorize a session key for the calling smart account.
+//! * `remove_session_key` - Revoke a session key of the calling smart account.
+//! * `set_guardians` - Set the guardians able to recover the calling smart account.
+//! * `initiate_recovery` - Propose a new owner credential for a smart account as a guardian.
+//! * `approve_recovery` - Approve an ongoing recovery as a guardian.
+//! * `finish_recovery` - Replace the owner credential once a recovery is approved and delayed.
+//! * `cancel_recovery` - Cancel an ongoing recovery of the calling smart account.
+//! * `kill` - Remove the calling smart account and release the deposit.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+mod credential;
+mod tests;
+pub mod weights;
+
+pub use credential::{Credential, KeyCredential, Verifier, WebAuthnAssertion};
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::{extract_actual_weight, DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo},
+	ensure,
+	storage::{with_transaction, TransactionOutcome},
+	traits::{Contains, Currency, Get, IsSubType, IsType, OriginTrait, ReservableCurrency},
+	weights::Weight,
+	BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_transaction_payment::OnChargeTransaction;
+use scale_info::TypeInfo;
+use sp_io::hashing::blake2_256;
+use sp_runtime::{
+	traits::{Dispatchable, Hash, SaturatedConversion, Saturating, TrailingZeroInput, Zero},
+	transaction_validity::{
+		InvalidTransaction, TransactionLongevity, TransactionPriority, TransactionSource,
+		TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+	DispatchError, DispatchResult, RuntimeDebug,
+};
+use sp_std::prelude::*;
+pub use weights::WeightInfo;
+
+pub use pallet::*;
+
+type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+type OnChargeOf<T> = <T as pallet_transaction_payment::Config>::OnChargeTransaction;
+
+type FeeBalanceOf<T> = <OnChargeOf<T> as OnChargeTransaction<T>>::Balance;
+
+/// The context prefixed to every message signed on behalf of a smart account.
+const SIGNING_CONTEXT: &[u8] = b"smart_account";
+
+/// Who authorized the execution of a call on behalf of a smart account.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Signer<Hash> {
+	/// The owner credential of the account.
+	Owner,
+	/// The session key with this id. See [`Pallet::session_key_id`].
+	Session(Hash),
+}
+
+/// The state of a smart account.
+#[derive(
+	Encode,
+	Decode,
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+pub struct SmartAccount<T: Config> {
+	/// The credential authorizing transactions of the account.
+	pub owner: Credential<T::AccountId>,
+	/// The account which created the smart account and placed the deposit.
+	pub depositor: T::AccountId,
+	/// The amount held from `depositor`.
+	pub deposit: BalanceOf<T>,
+	/// The number of session keys the account has authorized.
+	pub session_keys: u32,
+	/// The accounts which can recover this account.
+	pub guardians: BoundedVec<T::AccountId, T::MaxGuardians>,
+	/// The number of guardians needed to recover this account.
+	pub threshold: u32,
+	/// The number of blocks between initiating and finishing a recovery.
+	pub recovery_delay: BlockNumberFor<T>,
+}
+
+/// A key which can authorize transactions of a smart account for a limited time and amount.
+#[derive(
+	Encode,
+	Decode,
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+pub struct SessionKey<T: Config> {
+	/// The public key of the session.
+	pub key: KeyCredential,
+	/// The first block at which the key is no longer valid.
+	pub expires_at: BlockNumberFor<T>,
+	/// The maximum amount the calls authorized by this key can spend in total.
+	pub spend_limit: BalanceOf<T>,
+	/// The amount already spent by calls authorized by this key.
+	pub spent: BalanceOf<T>,
+}
+
+/// An ongoing recovery of a smart account.
+#[derive(
+	Encode,
+	Decode,
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+pub struct Recovery<T: Config> {
+	/// The credential replacing the owner once the recovery is finished.
+	pub new_owner: Credential<T::AccountId>,
+	/// The guardians which approved the recovery.
+	pub approvals: BoundedVec<T::AccountId, T::MaxGuardians>,
+	/// The block at which the recovery was initiated.
+	pub started: BlockNumberFor<T>,
+}
+
+/// The outcome of checking the authorization of [`Call::execute`].
+struct CheckedExecution<T: Config> {
+	/// The session key used, if any.
+	session: Option<(T::Hash, BlockNumberFor<T>)>,
+	/// Whether the proof was verified by [`Config::Verifier`].
+	verified_by_custom: bool,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_transaction_payment::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The overarching call type.
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<frame_system::Call<Self>>
+			+ IsSubType<Call<Self>>
+			+ IsType<<Self as frame_system::Config>::RuntimeCall>;
+
+		/// The currency mechanism.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Verifies authorizations of accounts owned by a [`Credential::Custom`].
+		///
+		/// Use `()` to only allow the built-in signature schemes.
+		type Verifier: Verifier<Self::AccountId>;
+
+		/// The calls session keys may dispatch.
+		///
+		/// Calls of this pallet are never allowed as they would let a session key escalate its
+		/// privileges. The same applies to calls which delegate control over the account, such
+		/// as adding a proxy, which should be excluded by this filter.
+		///
+		/// The spending limit of a session key only meters the total balance of `Currency`. Calls
+		/// moving other assets, or locking funds elsewhere, are not covered by it and need to be
+		/// excluded as well. An allowlist is therefore recommended.
+		type SessionCallFilter: Contains<<Self as Config>::RuntimeCall>;
+
+		/// The priority of [`Pallet::execute`] transactions.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// The domain of the relying party passkeys are registered with, e.g. `example.com`.
+		///
+		/// Assertions are only accepted if they were made for this relying party from the
+		/// `https` origin of the same domain.
+		type PasskeyRelyingParty: Get<&'static str>;
+
+		/// The amount held from the creator of a smart account.
+		///
+		/// This covers the account itself, its session keys and an ongoing recovery.
+		#[pallet::constant]
+		type AccountDeposit: Get<BalanceOf<Self>>;
+
+		/// The maximum number of session keys of a single account.
+		#[pallet::constant]
+		type MaxSessionKeys: Get<u32>;
+
+		/// The maximum number of guardians of a single account.
+		#[pallet::constant]
+		type MaxGuardians: Get<u32>;
+
+		/// The maximum length of the proof passed to [`Pallet::execute`].
+		#[pallet::constant]
+		type MaxProofLen: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The smart accounts.
+	#[pallet::storage]
+	pub type Accounts<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, SmartAccount<T>, OptionQuery>;
+
+	/// The session keys of each smart account by their id.
+	#[pallet::storage]
+	pub type SessionKeys<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Identity,
+		T::Hash,
+		SessionKey<T>,
+		OptionQuery,
+	>;
+
+	/// The ongoing recoveries of smart accounts.
+	#[pallet::storage]
+	pub type Recoveries<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Recovery<T>, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A smart account was created.
+		Created { account: T::AccountId, depositor: T::AccountId },
+		/// A call was executed on behalf of a smart account.
+		Executed { account: T::AccountId, signer: Signer<T::Hash>, result: DispatchResult },
+		/// The owner credential of a smart account was replaced.
+		OwnerChanged { account: T::AccountId },
+		/// A session key was authorized.
+		SessionKeyAdded {
+			account: T::AccountId,
+			id: T::Hash,
+			expires_at: BlockNumberFor<T>,
+			spend_limit: BalanceOf<T>,
+		},
+		/// A session key was revoked.
+		SessionKeyRemoved { account: T::AccountId, id: T::Hash },
+		/// The guardians of a smart account were set.
+		GuardiansSet { account: T::AccountId, threshold: u32 },
+		/// A guardian initiated the recovery of a smart account.
+		RecoveryInitiated { account: T::AccountId, guardian: T::AccountId },
+		/// A guardian approved the recovery of a smart account.
+		RecoveryApproved { account: T::AccountId, guardian: T::AccountId },
+		/// A smart account was recovered and its session keys revoked.
+		RecoveryFinished { account: T::AccountId },
+		/// The recovery of a smart account was cancelled.
+		RecoveryCancelled { account: T::AccountId },
+		/// A smart account was removed.
+		Killed { account: T::AccountId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// A smart account with this creator and index already exists.
+		AlreadyExists,
+		/// The account is not a smart account.
+		NotSmartAccount,
+		/// The authorization of the call is invalid.
+		InvalidAuthorization,
+		/// The smart account cannot pay the transaction fee.
+		CannotPayFees,
+		/// The account already has the maximum number of session keys.
+		TooManySessionKeys,
+		/// The session key is already authorized.
+		SessionKeyExists,
+		/// The session key does not exist.
+		SessionKeyNotFound,
+		/// The expiry of a session key needs to be in the future.
+		InvalidExpiry,
+		/// The calls authorized by a session key exceeded its spending limit.
+		SpendLimitExceeded,
+		/// The threshold is zero or exceeds the number of guardians.
+		InvalidThreshold,
+		/// The origin is not a guardian of the account.
+		NotGuardian,
+		/// The guardian already approved the recovery.
+		AlreadyApproved,
+		/// The account is already being recovered.
+		RecoveryInProgress,
+		/// The account is not being recovered.
+		RecoveryNotFound,
+		/// The recovery lacks approvals or its delay has not passed yet.
+		RecoveryNotReady,
+		/// The credential is a custom verifier not accepted by [`Config::Verifier`].
+		UnsupportedCredential,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T>
+	where
+		<T as frame_system::Config>::RuntimeCall:
+			Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	{
+		/// Create a new smart account owned by `owner`.
+		///
+		/// The account is derived from the caller and `index` and held `AccountDeposit` from the
+		/// caller. It needs to be funded before it can pay for its transactions.
+		///
+		/// Emits `Created`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::create())]
+		pub fn create(
+			origin: OriginFor<T>,
+			owner: Credential<T::AccountId>,
+			index: u16,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_supported(&owner)?;
+			let account = Self::account_id(&who, index);
+			ensure!(!Accounts::<T>::contains_key(&account), Error::<T>::AlreadyExists);
+
+			let deposit = T::AccountDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+			frame_system::Pallet::<T>::inc_providers(&account);
+			Accounts::<T>::insert(
+				&account,
+				SmartAccount {
+					owner,
+					depositor: who.clone(),
+					deposit,
+					session_keys: 0,
+					guardians: Default::default(),
+					threshold: 0,
+					recovery_delay: Zero::zero(),
+				},
+			);
+
+			Self::deposit_event(Event::Created { account, depositor: who });
+			Ok(())
+		}
+
+		/// Dispatch `call` on behalf of the smart account `account`.
+		///
+		/// This is an unsigned extrinsic. Its validity is checked by verifying `proof` over
+		/// [`Pallet::signing_payload`] against the owner credential or the session key selected
+		/// by `signer`. `nonce` needs to be the `frame_system` nonce of the account.
+		///
+		/// The transaction fee is withdrawn from the smart account through the
+		/// `OnChargeTransaction` of `pallet_transaction_payment` like for signed extrinsics.
+		///
+		/// # Note
+		///
+		/// A failing call is still included: the nonce is incremented and the fee is charged.
+		/// Calls authorized by a session key are reverted when they exceed its spending limit.
+		/// The outcome is reported by `Executed`.
+		#[pallet::call_index(1)]
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			(Pallet::<T>::execute_weight(dispatch_info.weight, true), dispatch_info.class)
+		})]
+		pub fn execute(
+			origin: OriginFor<T>,
+			account: T::AccountId,
+			nonce: T::Nonce,
+			call: Box<<T as Config>::RuntimeCall>,
+			signer: Signer<T::Hash>,
+			proof: BoundedVec<u8, T::MaxProofLen>,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			let checked = Self::check_execution(&account, &nonce, &call, &signer, &proof)
+				.map_err(|_| Error::<T>::InvalidAuthorization)?;
+			ensure!(
+				nonce == frame_system::Pallet::<T>::account_nonce(&account),
+				Error::<T>::InvalidAuthorization
+			);
+			frame_system::Pallet::<T>::inc_account_nonce(&account);
+
+			let len = Self::execute_len(&account, &nonce, &call, &signer, &proof);
+			let info = Self::execute_dispatch_info(&call);
+			let fee =
+				pallet_transaction_payment::Pallet::<T>::compute_fee(len, &info, Zero::zero());
+			let liquidity = OnChargeOf::<T>::withdraw_fee(
+				&account,
+				call.as_ref().into_ref(),
+				&info,
+				fee,
+				Zero::zero(),
+			)
+			.map_err(|_| Error::<T>::CannotPayFees)?;
+
+			let (call_weight, result) = Self::dispatch_as(&account, *call, &checked);
+			let post_info = PostDispatchInfo {
+				actual_weight: Some(Self::execute_weight(call_weight, checked.verified_by_custom)),
+				pays_fee: Pays::Yes,
+			};
+			let actual_fee = pallet_transaction_payment::Pallet::<T>::compute_actual_fee(
+				len,
+				&info,
+				&post_info,
+				Zero::zero(),
+			);
+			// The fee was withdrawn above and can only be refunded partially at this point.
+			let _ = OnChargeOf::<T>::correct_and_deposit_fee(
+				&account,
+				&info,
+				&post_info,
+				actual_fee,
+				Zero::zero(),
+				liquidity,
+			);
+
+			Self::deposit_event(Event::Executed { account, signer, result });
+			Ok(PostDispatchInfo { actual_weight: post_info.actual_weight, pays_fee: Pays::No })
+		}
+
+		/// Replace the owner credential of the calling smart account.
+		///
+		/// Emits `OwnerChanged`.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::set_owner())]
+		pub fn set_owner(origin: OriginFor<T>, owner: Credential<T::AccountId>) -> DispatchResult {
+			let account = ensure_signed(origin)?;
+			Self::ensure_supported(&owner)?;
+			Accounts::<T>::try_mutate(&account, |details| {
+				let details = details.as_mut().ok_or(Error::<T>::NotSmartAccount)?;
+				details.owner = owner;
+				Ok::<_, DispatchError>(())
+			})?;
+			Self::deposit_event(Event::OwnerChanged { account });
+			Ok(())
+		}
+
+		/// Authorize `key` to sign transactions of the calling smart account.
+		///
+		/// The key is valid until `expires_at` and the calls it authorizes can spend up to
+		/// `spend_limit` of the account's balance in total.
+		///
+		/// Emits `SessionKeyAdded`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::add_session_key())]
+		pub fn add_session_key(
+			origin: OriginFor<T>,
+			key: KeyCredential,
+			expires_at: BlockNumberFor<T>,
+			spend_limit: BalanceOf<T>,
+		) -> DispatchResult {
+			let account = ensure_signed(origin)?;
+			ensure!(
+				expires_at > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidExpiry
+			);
+			let id = Self::session_key_id(&key);
+			ensure!(!SessionKeys::<T>::contains_key(&account, id), Error::<T>::SessionKeyExists);
+			Accounts::<T>::try_mutate(&account, |details| {
+				let details = details.as_mut().ok_or(Error::<T>::NotSmartAccount)?;
+				ensure!(
+					details.session_keys < T::MaxSessionKeys::get(),
+					Error::<T>::TooManySessionKeys
+				);
+				details.session_keys.saturating_inc();
+				Ok::<_, DispatchError>(())
+			})?;
+			SessionKeys::<T>::insert(
+				&account,
+				id,
+				SessionKey { key, expires_at, spend_limit, spent: Zero::zero() },
+			);
+
+			Self::deposit_event(Event::SessionKeyAdded { account, id, expires_at, spend_limit });
+			Ok(())
+		}
+
+		/// Revoke the session key `id` of the calling smart account.
+		///
+		/// Emits `SessionKeyRemoved`.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::remove_session_key())]
+		pub fn remove_session_key(origin: OriginFor<T>, id: T::Hash) -> DispatchResult {
+			let account = ensure_signed(origin)?;
+			SessionKeys::<T>::take(&account, id).ok_or(Error::<T>::SessionKeyNotFound)?;
+			Accounts::<T>::mutate(&account, |details| {
+				if let Some(details) = details {
+					details.session_keys.saturating_dec();
+				}
+			});
+			Self::deposit_event(Event::SessionKeyRemoved { account, id });
+			Ok(())
+		}
+
+		/// Set the guardians of the calling smart account.
+		///
+		/// Any `threshold` of `guardians` can replace the owner credential, `delay` blocks after
+		/// the recovery was initiated. Passing no guardians and a zero threshold disables
+		/// recovery. An ongoing recovery is cancelled.
+		///
+		/// Emits `GuardiansSet`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::set_guardians(guardians.len() as u32))]
+		pub fn set_guardians(
+			origin: OriginFor<T>,
+			guardians: BoundedVec<T::AccountId, T::MaxGuardians>,
+			threshold: u32,
+			delay: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let account = ensure_signed(origin)?;
+			ensure!(
+				threshold as usize <= guardians.len() && (threshold > 0 || guardians.is_empty()),
+				Error::<T>::InvalidThreshold
+			);
+			Accounts::<T>::try_mutate(&account, |details| {
+				let details = details.as_mut().ok_or(Error::<T>::NotSmartAccount)?;
+				details.guardians = guardians;
+				details.threshold = threshold;
+				details.recovery_delay = delay;
+				Ok::<_, DispatchError>(())
+			})?;
+			if Recoveries::<T>::take(&account).is_some() {
+				Self::deposit_event(Event::RecoveryCancelled { account: account.clone() });
+			}
+
+			Self::deposit_event(Event::GuardiansSet { account, threshold });
+			Ok(())
+		}
+
+		/// Propose `new_owner` as the owner credential of `account`.
+		///
+		/// The origin needs to be a guardian of `account` and counts as the first approval.
+		///
+		/// Emits `RecoveryInitiated`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::initiate_recovery(T::MaxGuardians::get()))]
+		pub fn initiate_recovery(
+			origin: OriginFor<T>,
+			account: T::AccountId,
+			new_owner: Credential<T::AccountId>,
+		) -> DispatchResult {
+			let guardian = ensure_signed(origin)?;
+			Self::ensure_supported(&new_owner)?;
+			let details = Accounts::<T>::get(&account).ok_or(Error::<T>::NotSmartAccount)?;
+			ensure!(details.guardians.contains(&guardian), Error::<T>::NotGuardian);
+			ensure!(!Recoveries::<T>::contains_key(&account), Error::<T>::RecoveryInProgress);
+
+			let approvals = BoundedVec::truncate_from(vec![guardian.clone()]);
+			Recoveries::<T>::insert(
+				&account,
+				Recovery {
+					new_owner,
+					approvals,
+					started: frame_system::Pallet::<T>::block_number(),
+				},
+			);
+
+			Self::deposit_event(Event::RecoveryInitiated { account, guardian });
+			Ok(())
+		}
+
+		/// Approve the ongoing recovery of `account` as one of its guardians.
+		///
+		/// Emits `RecoveryApproved`.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::approve_recovery(T::MaxGuardians::get()))]
+		pub fn approve_recovery(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+			let guardian = ensure_signed(origin)?;
+			let details = Accounts::<T>::get(&account).ok_or(Error::<T>::NotSmartAccount)?;
+			ensure!(details.guardians.contains(&guardian), Error::<T>::NotGuardian);
+			Recoveries::<T>::try_mutate(&account, |recovery| {
+				let recovery = recovery.as_mut().ok_or(Error::<T>::RecoveryNotFound)?;
+				ensure!(!recovery.approvals.contains(&guardian), Error::<T>::AlreadyApproved);
+				// Cannot overflow as there are at most `MaxGuardians` distinct guardians.
+				recovery
+					.approvals
+					.try_push(guardian.clone())
+					.map_err(|_| Error::<T>::AlreadyApproved)?;
+				Ok::<_, DispatchError>(())
+			})?;
+
+			Self::deposit_event(Event::RecoveryApproved { account, guardian });
+			Ok(())
+		}
+
+		/// Finish the recovery of `account` by replacing its owner credential.
+		///
+		/// Requires `threshold` approvals of current guardians and that `delay` blocks passed
+		/// since the recovery was initiated. All session keys of the account are revoked. Can be
+		/// called by anyone.
+		///
+		/// Emits `RecoveryFinished`.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::finish_recovery(T::MaxSessionKeys::get()))]
+		pub fn finish_recovery(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let mut details = Accounts::<T>::get(&account).ok_or(Error::<T>::NotSmartAccount)?;
+			let recovery = Recoveries::<T>::get(&account).ok_or(Error::<T>::RecoveryNotFound)?;
+			let approvals = recovery
+				.approvals
+				.iter()
+				.filter(|guardian| details.guardians.contains(guardian))
+				.count();
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				approvals >= details.threshold as usize &&
+					now >= recovery.started.saturating_add(details.recovery_delay),
+				Error::<T>::RecoveryNotReady
+			);
+
+			Recoveries::<T>::remove(&account);
+			let _ = SessionKeys::<T>::clear_prefix(&account, T::MaxSessionKeys::get(), None);
+			details.owner = recovery.new_owner;
+			details.session_keys = 0;
+			Accounts::<T>::insert(&account, details);
+
+			Self::deposit_event(Event::RecoveryFinished { account });
+			Ok(())
+		}
+
+		/// Cancel the ongoing recovery of the calling smart account.
+		///
+		/// Emits `RecoveryCancelled`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::cancel_recovery())]
+		pub fn cancel_recovery(origin: OriginFor<T>) -> DispatchResult {
+			let account = ensure_signed(origin)?;
+			Recoveries::<T>::take(&account).ok_or(Error::<T>::RecoveryNotFound)?;
+			Self::deposit_event(Event::RecoveryCancelled { account });
+			Ok(())
+		}
+
+		/// Remove the calling smart account.
+		///
+		/// The deposit is released to the creator. The balance of the account is left untouched
+		/// and should be transferred away beforehand as the account becomes inaccessible.
+		///
+		/// Emits `Killed`.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::kill(T::MaxSessionKeys::get()))]
+		pub fn kill(origin: OriginFor<T>) -> DispatchResult {
+			let account = ensure_signed(origin)?;
+			let details = Accounts::<T>::take(&account).ok_or(Error::<T>::NotSmartAccount)?;
+			frame_system::Pallet::<T>::dec_providers(&account)?;
+			let _ = SessionKeys::<T>::clear_prefix(&account, T::MaxSessionKeys::get(), None);
+			Recoveries::<T>::remove(&account);
+			T::Currency::unreserve(&details.depositor, details.deposit);
+
+			Self::deposit_event(Event::Killed { account });
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T>
+	where
+		<T as frame_system::Config>::RuntimeCall:
+			Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	{
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let Call::execute { account, nonce, call, signer, proof } = call else {
+				return InvalidTransaction::Call.into()
+			};
+			let checked = Self::check_execution(account, nonce, call, signer, proof)?;
+			let current_nonce = frame_system::Pallet::<T>::account_nonce(account);
+			if *nonce < current_nonce {
+				return InvalidTransaction::Stale.into()
+			}
+			Self::check_fee_payment(account, nonce, call, signer, proof)?;
+
+			// Use the same tags as `frame_system::CheckNonce` so that transactions of smart
+			// accounts and regular extrinsics of the same account are ordered by the pool.
+			let provides = vec![Encode::encode(&(account, nonce))];
+			let requires = if *nonce > current_nonce {
+				vec![Encode::encode(&(account, nonce.saturating_sub(1u32.into())))]
+			} else {
+				vec![]
+			};
+			let longevity = checked.session.map_or(TransactionLongevity::MAX, |(_, expires_at)| {
+				let now = frame_system::Pallet::<T>::block_number();
+				expires_at.saturating_sub(now).saturated_into::<TransactionLongevity>()
+			});
+			Ok(ValidTransaction {
+				priority: T::UnsignedPriority::get(),
+				requires,
+				provides,
+				longevity,
+				propagate: true,
+			})
+		}
+
+		fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+			let Call::execute { account, nonce, call, signer, proof } = call else {
+				return Err(InvalidTransaction::Call.into())
+			};
+			Self::check_execution(account, nonce, call, signer, proof)?;
+			let current_nonce = frame_system::Pallet::<T>::account_nonce(account);
+			if *nonce < current_nonce {
+				return Err(InvalidTransaction::Stale.into())
+			}
+			if *nonce > current_nonce {
+				return Err(InvalidTransaction::Future.into())
+			}
+			Self::check_fee_payment(account, nonce, call, signer, proof)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The smart account created by `who` with `index`.
+	pub fn account_id(who: &T::AccountId, index: u16) -> T::AccountId {
+		let entropy = (b"modlpy/smrtacct", who, index).using_encoded(blake2_256);
+		Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+			.expect("infinite length input; no invalid inputs for type; qed")
+	}
+
+	/// The id under which `key` is stored as a session key.
+	pub fn session_key_id(key: &KeyCredential) -> T::Hash {
+		T::Hashing::hash_of(key)
+	}
+
+	/// The message a credential signs to authorize `call` with `nonce` for `account`.
+	///
+	/// It commits to the genesis hash so that authorizations cannot be replayed on other chains.
+	pub fn signing_payload(
+		account: &T::AccountId,
+		nonce: &T::Nonce,
+		call: &<T as Config>::RuntimeCall,
+	) -> [u8; 32] {
+		let genesis = frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero());
+		(SIGNING_CONTEXT, genesis, account, nonce, call).using_encoded(blake2_256)
+	}
+
+	/// The weight of [`Pallet::execute`] dispatching a call of `call_weight`.
+	fn execute_weight(call_weight: Weight, verified_by_custom: bool) -> Weight {
+		let verification = if verified_by_custom { T::Verifier::weight() } else { Weight::zero() };
+		T::WeightInfo::execute()
+			.saturating_add(verification)
+			.saturating_add(call_weight)
+	}
+
+	fn execute_dispatch_info(call: &<T as Config>::RuntimeCall) -> DispatchInfo {
+		let call_info = call.get_dispatch_info();
+		DispatchInfo {
+			weight: Self::execute_weight(call_info.weight, true),
+			class: call_info.class,
+			pays_fee: Pays::Yes,
+		}
+	}
+
+	/// The length used to compute the fee of [`Pallet::execute`].
+	///
+	/// The extrinsic length is not known during dispatch. Its arguments make up all of it except
+	/// for the few bytes of the unsigned extrinsic envelope.
+	fn execute_len(
+		account: &T::AccountId,
+		nonce: &T::Nonce,
+		call: &<T as Config>::RuntimeCall,
+		signer: &Signer<T::Hash>,
+		proof: &[u8],
+	) -> u32 {
+		(account, nonce, call, signer, proof).encoded_size() as u32
+	}
+
+	/// Verify that `proof` authorizes `call` for `account`.
+	fn check_execution(
+		account: &T::AccountId,
+		nonce: &T::Nonce,
+		call: &<T as Config>::RuntimeCall,
+		signer: &Signer<T::Hash>,
+		proof: &[u8],
+	) -> Result<CheckedExecution<T>, TransactionValidityError> {
+		let details = Accounts::<T>::get(account).ok_or(InvalidTransaction::BadSigner)?;
+		let message = Self::signing_payload(account, nonce, call);
+		let relying_party = T::PasskeyRelyingParty::get();
+		let checked = match signer {
+			Signer::Owner => {
+				let (valid, verified_by_custom) = match &details.owner {
+					Credential::Key(key) => (key.verify(&message, proof, relying_party), false),
+					Credential::Custom(verifier) =>
+						(Self::verify_custom(verifier, account, &message, proof), true),
+				};
+				ensure!(valid, InvalidTransaction::BadProof);
+				CheckedExecution { session: None, verified_by_custom }
+			},
+			Signer::Session(id) => {
+				let session =
+					SessionKeys::<T>::get(account, id).ok_or(InvalidTransaction::BadSigner)?;
+				ensure!(
+					frame_system::Pallet::<T>::block_number() < session.expires_at,
+					InvalidTransaction::Stale
+				);
+				ensure!(
+					session.key.verify(&message, proof, relying_party),
+					InvalidTransaction::BadProof
+				);
+				CheckedExecution {
+					session: Some((*id, session.expires_at)),
+					verified_by_custom: false,
+				}
+			},
+		};
+		Ok(checked)
+	}
+
+	/// Whether the custom `verifier` accepts `proof` for `message`, discarding its state changes.
+	fn verify_custom(
+		verifier: &T::AccountId,
+		account: &T::AccountId,
+		message: &[u8; 32],
+		proof: &[u8],
+	) -> bool {
+		with_transaction(|| {
+			let valid = T::Verifier::verify(verifier, account, message, proof);
+			TransactionOutcome::Rollback(Ok::<_, DispatchError>(valid))
+		})
+		.unwrap_or(false)
+	}
+
+	/// Ensure that `credential` can authorize transactions in this runtime.
+	fn ensure_supported(credential: &Credential<T::AccountId>) -> DispatchResult {
+		if let Credential::Custom(verifier) = credential {
+			ensure!(T::Verifier::is_verifier(verifier), Error::<T>::UnsupportedCredential);
+		}
+		Ok(())
+	}
+
+	/// Check that `account` can pay the fee of [`Pallet::execute`] without withdrawing it.
+	fn check_fee_payment(
+		account: &T::AccountId,
+		nonce: &T::Nonce,
+		call: &<T as Config>::RuntimeCall,
+		signer: &Signer<T::Hash>,
+		proof: &[u8],
+	) -> Result<(), TransactionValidityError>
+	where
+		<T as frame_system::Config>::RuntimeCall:
+			Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	{
+		let len = Self::execute_len(account, nonce, call, signer, proof);
+		let info = Self::execute_dispatch_info(call);
+		let fee: FeeBalanceOf<T> =
+			pallet_transaction_payment::Pallet::<T>::compute_fee(len, &info, Zero::zero());
+		let can_pay = with_transaction(|| {
+			let withdrawn =
+				OnChargeOf::<T>::withdraw_fee(account, call.into_ref(), &info, fee, Zero::zero());
+			TransactionOutcome::Rollback(Ok::<_, DispatchError>(withdrawn.is_ok()))
+		})
+		.unwrap_or(false);
+		ensure!(can_pay, InvalidTransaction::Payment);
+		Ok(())
+	}
+
+	/// Dispatch `call` from `account` and return its actual weight and result.
+	///
+	/// Calls authorized by a session key are filtered by [`Config::SessionCallFilter`] and
+	/// reverted when the balance they spent exceeds the remaining spending limit.
+	fn dispatch_as(
+		account: &T::AccountId,
+		call: <T as Config>::RuntimeCall,
+		checked: &CheckedExecution<T>,
+	) -> (Weight, DispatchResult) {
+		let call_info = call.get_dispatch_info();
+		let mut origin: T::RuntimeOrigin = frame_system::RawOrigin::Signed(account.clone()).into();
+		let Some((id, _)) = checked.session else {
+			let result = call.dispatch(origin);
+			let weight = extract_actual_weight(&result, &call_info);
+			return (weight, result.map(|_| ()).map_err(|e| e.error))
+		};
+
+		origin.add_filter(move |c: &<T as frame_system::Config>::RuntimeCall| {
+			let c = <T as Config>::RuntimeCall::from_ref(c);
+			c.is_sub_type().is_none() && T::SessionCallFilter::contains(c)
+		});
+		let mut weight = call_info.weight;
+		let result = with_transaction(|| {
+			let before = T::Currency::total_balance(account);
+			let result = call.dispatch(origin);
+			weight = extract_actual_weight(&result, &call_info);
+			if let Err(e) = result {
+				return TransactionOutcome::Rollback(Err(e.error))
+			}
+			let spent = before.saturating_sub(T::Currency::total_balance(account));
+			let within_limit = SessionKeys::<T>::try_mutate(account, id, |session| {
+				let session = session.as_mut().ok_or(Error::<T>::SessionKeyNotFound)?;
+				let spent = session.spent.saturating_add(spent);
+				ensure!(spent <= session.spend_limit, Error::<T>::SpendLimitExceeded);
+				session.spent = spent;
+				Ok::<_, DispatchError>(())
+			});
+			match within_limit {
+				Ok(()) => TransactionOutcome::Commit(Ok(())),
+				Err(e) => TransactionOutcome::Rollback(Err(e)),
+			}
+		});
+		(weight, result)
+	}
+}
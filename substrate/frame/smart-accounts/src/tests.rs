@@ -0,0 +1,609 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Tests for Smart Accounts Pallet
+
+#![cfg(test)]
+
+use super::*;
+
+use crate as pallet_smart_accounts;
+use crate::credential::sign_with_passkey;
+use frame_support::{
+	assert_noop, assert_ok, derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64, ConstU8},
+	unsigned::ValidateUnsigned,
+	weights::IdentityFee,
+};
+use pallet_transaction_payment::FungibleAdapter;
+use sp_core::{sr25519, Pair, H256};
+use sp_runtime::{BuildStorage, DispatchError};
+
+type Block = frame_system::mocking::MockBlockU32<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		TransactionPayment: pallet_transaction_payment,
+		SmartAccounts: pallet_smart_accounts,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type BlockHashCount = ConstU32<250>;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type ReserveIdentifier = [u8; 8];
+	type AccountStore = System;
+}
+
+#[derive_impl(pallet_transaction_payment::config_preludes::TestDefaultConfig)]
+impl pallet_transaction_payment::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type OnChargeTransaction = FungibleAdapter<Balances, ()>;
+	type OperationalFeeMultiplier = ConstU8<5>;
+	type WeightToFee = IdentityFee<u64>;
+	type LengthToFee = IdentityFee<u64>;
+	type FeeMultiplierUpdate = ();
+}
+
+parameter_types! {
+	pub const RelyingParty: &'static str = "example.com";
+}
+
+/// The only verifier, accepting authorizations as long as the proof is [`APPROVED`].
+const VERIFIER: u64 = 42;
+const APPROVED: &[u8] = b"approved";
+
+pub struct TestVerifier;
+impl Verifier<u64> for TestVerifier {
+	fn is_verifier(verifier: &u64) -> bool {
+		*verifier == VERIFIER
+	}
+
+	fn weight() -> Weight {
+		Weight::from_parts(1_000_000, 0)
+	}
+
+	fn verify(verifier: &u64, _account: &u64, _message: &[u8; 32], proof: &[u8]) -> bool {
+		*verifier == VERIFIER && proof == APPROVED
+	}
+}
+
+pub struct TestSessionCallFilter;
+impl Contains<RuntimeCall> for TestSessionCallFilter {
+	fn contains(c: &RuntimeCall) -> bool {
+		matches!(c, RuntimeCall::Balances(_) | RuntimeCall::SmartAccounts(_))
+	}
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type Verifier = TestVerifier;
+	type SessionCallFilter = TestSessionCallFilter;
+	type UnsignedPriority = ConstU64<10>;
+	type PasskeyRelyingParty = RelyingParty;
+	type AccountDeposit = ConstU64<100>;
+	type MaxSessionKeys = ConstU32<3>;
+	type MaxGuardians = ConstU32<3>;
+	type MaxProofLen = ConstU32<512>;
+	type WeightInfo = ();
+}
+
+use pallet_balances::Call as BalancesCall;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 10_000_000_000_000), (2, 10), (3, 10), (4, 10)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+const FUNDS: u64 = 1_000_000_000_000;
+
+fn owner_pair() -> sr25519::Pair {
+	sr25519::Pair::from_seed(&[1; 32])
+}
+
+/// Create a smart account of account `1` owned by `owner` and fund it with [`FUNDS`].
+fn create_account(owner: Credential<u64>) -> u64 {
+	assert_ok!(SmartAccounts::create(RuntimeOrigin::signed(1), owner, 0));
+	let account = SmartAccounts::account_id(&1, 0);
+	assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), account, FUNDS));
+	account
+}
+
+fn transfer(value: u64) -> RuntimeCall {
+	RuntimeCall::Balances(BalancesCall::transfer_allow_death { dest: 2, value })
+}
+
+fn execute_call(
+	account: u64,
+	call: RuntimeCall,
+	signer: Signer<H256>,
+	sign: impl FnOnce(&[u8; 32]) -> Vec<u8>,
+) -> Call<Test> {
+	let nonce = System::account_nonce(account);
+	let proof = sign(&SmartAccounts::signing_payload(&account, &nonce, &call));
+	Call::execute {
+		account,
+		nonce,
+		call: Box::new(call),
+		signer,
+		proof: BoundedVec::truncate_from(proof),
+	}
+}
+
+fn sign_with_owner(message: &[u8; 32]) -> Vec<u8> {
+	owner_pair().sign(message).encode()
+}
+
+/// Validate and dispatch `call` the way an unsigned extrinsic is applied.
+fn apply(call: Call<Test>) -> DispatchResult {
+	<SmartAccounts as ValidateUnsigned>::pre_dispatch(&call)
+		.map_err(|_| DispatchError::Other("invalid transaction"))?;
+	RuntimeCall::SmartAccounts(call)
+		.dispatch(RuntimeOrigin::none())
+		.map(|_| ())
+		.map_err(|e| e.error)
+}
+
+fn last_executed() -> DispatchResult {
+	System::events()
+		.into_iter()
+		.rev()
+		.find_map(|record| match record.event {
+			RuntimeEvent::SmartAccounts(Event::Executed { result, .. }) => Some(result),
+			_ => None,
+		})
+		.expect("a call was executed")
+}
+
+#[test]
+fn owner_key_authorizes_calls() {
+	new_test_ext().execute_with(|| {
+		let account =
+			create_account(Credential::Key(KeyCredential::Sr25519(owner_pair().public())));
+		assert_eq!(Balances::reserved_balance(1), 100);
+
+		let call = execute_call(account, transfer(5), Signer::Owner, sign_with_owner);
+		assert_ok!(apply(call));
+		assert_ok!(last_executed());
+		assert_eq!(Balances::free_balance(2), 15);
+		assert_eq!(System::account_nonce(account), 1);
+
+		// The fee was charged on top of the transfer.
+		let balance = Balances::free_balance(account);
+		assert!(balance < FUNDS - 5);
+		assert!(balance > FUNDS / 2);
+	});
+}
+
+#[test]
+fn invalid_authorizations_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let account =
+			create_account(Credential::Key(KeyCredential::Sr25519(owner_pair().public())));
+		let validate = |call: &Call<Test>| {
+			<SmartAccounts as ValidateUnsigned>::validate_unsigned(
+				TransactionSource::External,
+				call,
+			)
+		};
+
+		// Signed by another key.
+		let call = execute_call(account, transfer(5), Signer::Owner, |message| {
+			sr25519::Pair::from_seed(&[2; 32]).sign(message).encode()
+		});
+		assert_eq!(validate(&call), InvalidTransaction::BadProof.into());
+		assert_noop!(apply(call), DispatchError::Other("invalid transaction"));
+
+		// Not a smart account.
+		let mut call = execute_call(account, transfer(5), Signer::Owner, sign_with_owner);
+		let Call::execute { account: ref mut target, .. } = call else { unreachable!() };
+		*target = 3;
+		assert_eq!(validate(&call), InvalidTransaction::BadSigner.into());
+
+		// Future nonces require their predecessor.
+		frame_system::Account::<Test>::mutate(account, |info| info.nonce = 1);
+		let call = execute_call(account, transfer(5), Signer::Owner, sign_with_owner);
+		frame_system::Account::<Test>::mutate(account, |info| info.nonce = 0);
+		let valid = validate(&call).unwrap();
+		assert_eq!(valid.priority, 10);
+		assert_eq!(valid.provides, vec![(account, 1u32).encode()]);
+		assert_eq!(valid.requires, vec![(account, 0u32).encode()]);
+
+		// Stale nonces are rejected.
+		frame_system::Account::<Test>::mutate(account, |info| info.nonce = 2);
+		assert_eq!(validate(&call), InvalidTransaction::Stale.into());
+
+		// The account needs to be able to pay the fee.
+		let poor = SmartAccounts::account_id(&1, 1);
+		assert_ok!(SmartAccounts::create(
+			RuntimeOrigin::signed(1),
+			Credential::Key(KeyCredential::Sr25519(owner_pair().public())),
+			1,
+		));
+		let call = execute_call(poor, transfer(5), Signer::Owner, sign_with_owner);
+		assert_eq!(validate(&call), InvalidTransaction::Payment.into());
+	});
+}
+
+#[test]
+fn passkeys_authorize_calls() {
+	new_test_ext().execute_with(|| {
+		let rp = RelyingParty::get();
+		let (passkey, _) = sign_with_passkey(7, &[0; 32], rp);
+		let account = create_account(Credential::Key(passkey));
+
+		// An assertion over another challenge is rejected.
+		let call = execute_call(account, transfer(5), Signer::Owner, |_| {
+			sign_with_passkey(7, &[0; 32], rp).1
+		});
+		assert_noop!(apply(call), DispatchError::Other("invalid transaction"));
+
+		// So is an assertion by another passkey.
+		let call = execute_call(account, transfer(5), Signer::Owner, |message| {
+			sign_with_passkey(8, message, rp).1
+		});
+		assert_noop!(apply(call), DispatchError::Other("invalid transaction"));
+
+		// And an assertion obtained by another website.
+		let call = execute_call(account, transfer(5), Signer::Owner, |message| {
+			sign_with_passkey(7, message, "evil.com").1
+		});
+		assert_noop!(apply(call), DispatchError::Other("invalid transaction"));
+
+		let call = execute_call(account, transfer(5), Signer::Owner, |message| {
+			sign_with_passkey(7, message, rp).1
+		});
+		assert_ok!(apply(call));
+		assert_ok!(last_executed());
+		assert_eq!(Balances::free_balance(2), 15);
+	});
+}
+
+#[test]
+fn passkey_assertions_are_bound_to_relying_party() {
+	use p256::ecdsa::{signature::Signer as _, Signature, SigningKey};
+
+	new_test_ext().execute_with(|| {
+		let rp = RelyingParty::get();
+		let (passkey, _) = sign_with_passkey(7, &[0; 32], rp);
+		let account = create_account(Credential::Key(passkey));
+		let validate = |call: &Call<Test>| {
+			<SmartAccounts as ValidateUnsigned>::validate_unsigned(
+				TransactionSource::External,
+				call,
+			)
+		};
+		// Signs a modified assertion with the key of the passkey again.
+		let modified = |message: &[u8; 32], modify: fn(&mut WebAuthnAssertion)| {
+			let proof = sign_with_passkey(7, message, rp).1;
+			let mut assertion = WebAuthnAssertion::decode(&mut &proof[..]).unwrap();
+			modify(&mut assertion);
+			let mut signed = assertion.authenticator_data.clone();
+			signed.extend(sp_io::hashing::sha2_256(&assertion.client_data_json));
+			let signature: Signature = SigningKey::from_slice(&[7; 32]).unwrap().sign(&signed);
+			assertion.signature = signature.to_der().as_bytes().to_vec();
+			assertion.encode()
+		};
+
+		// An assertion for another rp id.
+		let call = execute_call(account, transfer(5), Signer::Owner, |message| {
+			sign_with_passkey(7, message, "evil.com").1
+		});
+		assert_eq!(validate(&call), InvalidTransaction::BadProof.into());
+
+		// The authenticator data was made for another rp id, even though the client data names
+		// the right origin.
+		let call = execute_call(account, transfer(5), Signer::Owner, |message| {
+			modified(message, |assertion| {
+				assertion.authenticator_data[..32]
+					.copy_from_slice(&sp_io::hashing::sha2_256(b"evil.com"));
+			})
+		});
+		assert_eq!(validate(&call), InvalidTransaction::BadProof.into());
+
+		// The client data names another origin.
+		let call = execute_call(account, transfer(5), Signer::Owner, |message| {
+			modified(message, |assertion| {
+				let json = String::from_utf8(assertion.client_data_json.clone()).unwrap();
+				assertion.client_data_json =
+					json.replace("https://example.com", "https://evil.com").into_bytes();
+			})
+		});
+		assert_eq!(validate(&call), InvalidTransaction::BadProof.into());
+
+		// The user present flag is not set.
+		let call = execute_call(account, transfer(5), Signer::Owner, |message| {
+			modified(message, |assertion| assertion.authenticator_data[32] = 0)
+		});
+		assert_eq!(validate(&call), InvalidTransaction::BadProof.into());
+
+		// Re-signing alone keeps the assertion valid.
+		let call =
+			execute_call(account, transfer(5), Signer::Owner, |message| modified(message, |_| {}));
+		assert_ok!(validate(&call));
+	});
+}
+
+#[test]
+fn custom_verifiers_authorize_calls() {
+	new_test_ext().execute_with(|| {
+		let account = create_account(Credential::Custom(VERIFIER));
+
+		let call = execute_call(account, transfer(5), Signer::Owner, |_| b"denied".to_vec());
+		assert_noop!(apply(call), DispatchError::Other("invalid transaction"));
+
+		let call = execute_call(account, transfer(5), Signer::Owner, |_| APPROVED.to_vec());
+		assert_ok!(apply(call));
+		assert_ok!(last_executed());
+		assert_eq!(Balances::free_balance(2), 15);
+	});
+}
+
+#[test]
+fn verifier_weight_is_only_charged_for_custom_verifiers() {
+	new_test_ext().execute_with(|| {
+		let actual_weight = |account, sign: fn(&[u8; 32]) -> Vec<u8>| {
+			let call = execute_call(account, transfer(5), Signer::Owner, sign);
+			let declared = call.get_dispatch_info().weight;
+			let post_info = RuntimeCall::SmartAccounts(call).dispatch(RuntimeOrigin::none());
+			(declared, post_info.unwrap().actual_weight.unwrap())
+		};
+
+		let account = create_account(Credential::Custom(VERIFIER));
+		let (declared, actual) = actual_weight(account, |_| APPROVED.to_vec());
+		assert_eq!(actual, declared);
+
+		assert_ok!(SmartAccounts::set_owner(
+			RuntimeOrigin::signed(account),
+			Credential::Key(KeyCredential::Sr25519(owner_pair().public()))
+		));
+		let (declared, actual) = actual_weight(account, sign_with_owner);
+		assert_eq!(actual, declared - TestVerifier::weight());
+	});
+}
+
+#[test]
+fn unknown_verifiers_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let unknown = Credential::Custom(VERIFIER + 1);
+		assert_noop!(
+			SmartAccounts::create(RuntimeOrigin::signed(1), unknown.clone(), 0),
+			Error::<Test>::UnsupportedCredential
+		);
+
+		let account =
+			create_account(Credential::Key(KeyCredential::Sr25519(owner_pair().public())));
+		assert_noop!(
+			SmartAccounts::set_owner(RuntimeOrigin::signed(account), unknown.clone()),
+			Error::<Test>::UnsupportedCredential
+		);
+		assert_ok!(SmartAccounts::set_guardians(
+			RuntimeOrigin::signed(account),
+			BoundedVec::truncate_from(vec![2]),
+			1,
+			5
+		));
+		assert_noop!(
+			SmartAccounts::initiate_recovery(RuntimeOrigin::signed(2), account, unknown),
+			Error::<Test>::UnsupportedCredential
+		);
+	});
+}
+
+#[test]
+fn session_keys_are_limited() {
+	new_test_ext().execute_with(|| {
+		let account =
+			create_account(Credential::Key(KeyCredential::Sr25519(owner_pair().public())));
+		let session = sr25519::Pair::from_seed(&[3; 32]);
+		let key = KeyCredential::Sr25519(session.public());
+		let id = SmartAccounts::session_key_id(&key);
+		let sign_with_session = |message: &[u8; 32]| session.sign(message).encode();
+
+		assert_noop!(
+			SmartAccounts::add_session_key(RuntimeOrigin::signed(account), key.clone(), 1, 10),
+			Error::<Test>::InvalidExpiry
+		);
+		assert_ok!(SmartAccounts::add_session_key(RuntimeOrigin::signed(account), key, 10, 10));
+		assert_eq!(Accounts::<Test>::get(account).unwrap().session_keys, 1);
+
+		// Calls within the spending limit are executed.
+		assert_ok!(apply(execute_call(
+			account,
+			transfer(6),
+			Signer::Session(id),
+			sign_with_session
+		)));
+		assert_ok!(last_executed());
+		assert_eq!(Balances::free_balance(2), 16);
+		assert_eq!(SessionKeys::<Test>::get(account, id).unwrap().spent, 6);
+
+		// Calls exceeding it are reverted but still pay the fee.
+		let before = Balances::free_balance(account);
+		assert_ok!(apply(execute_call(
+			account,
+			transfer(5),
+			Signer::Session(id),
+			sign_with_session
+		)));
+		assert_eq!(last_executed(), Err(Error::<Test>::SpendLimitExceeded.into()));
+		assert_eq!(Balances::free_balance(2), 16);
+		assert!(Balances::free_balance(account) < before);
+		assert_eq!(System::account_nonce(account), 2);
+
+		// Session keys cannot manage the account.
+		let call = RuntimeCall::SmartAccounts(Call::remove_session_key { id });
+		assert_ok!(apply(execute_call(account, call, Signer::Session(id), sign_with_session)));
+		assert_eq!(last_executed(), Err(frame_system::Error::<Test>::CallFiltered.into()));
+
+		// Expired keys are rejected.
+		System::set_block_number(10);
+		let call = execute_call(account, transfer(1), Signer::Session(id), sign_with_session);
+		assert_eq!(
+			<SmartAccounts as ValidateUnsigned>::validate_unsigned(
+				TransactionSource::External,
+				&call
+			),
+			InvalidTransaction::Stale.into()
+		);
+
+		assert_ok!(SmartAccounts::remove_session_key(RuntimeOrigin::signed(account), id));
+		assert_eq!(Accounts::<Test>::get(account).unwrap().session_keys, 0);
+		assert_noop!(
+			SmartAccounts::remove_session_key(RuntimeOrigin::signed(account), id),
+			Error::<Test>::SessionKeyNotFound
+		);
+	});
+}
+
+#[test]
+fn guardians_recover_accounts() {
+	new_test_ext().execute_with(|| {
+		let account =
+			create_account(Credential::Key(KeyCredential::Sr25519(owner_pair().public())));
+		let session = KeyCredential::Sr25519(sr25519::Pair::from_seed(&[3; 32]).public());
+		assert_ok!(SmartAccounts::add_session_key(RuntimeOrigin::signed(account), session, 10, 10));
+		let new_owner =
+			Credential::Key(KeyCredential::Sr25519(sr25519::Pair::from_seed(&[4; 32]).public()));
+
+		assert_noop!(
+			SmartAccounts::set_guardians(
+				RuntimeOrigin::signed(account),
+				BoundedVec::truncate_from(vec![2, 3]),
+				3,
+				5
+			),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_ok!(SmartAccounts::set_guardians(
+			RuntimeOrigin::signed(account),
+			BoundedVec::truncate_from(vec![2, 3, 4]),
+			2,
+			5
+		));
+
+		assert_noop!(
+			SmartAccounts::initiate_recovery(RuntimeOrigin::signed(1), account, new_owner.clone()),
+			Error::<Test>::NotGuardian
+		);
+		assert_ok!(SmartAccounts::initiate_recovery(
+			RuntimeOrigin::signed(2),
+			account,
+			new_owner.clone()
+		));
+		assert_noop!(
+			SmartAccounts::approve_recovery(RuntimeOrigin::signed(2), account),
+			Error::<Test>::AlreadyApproved
+		);
+
+		// The recovery needs enough approvals and has to wait for the delay.
+		assert_noop!(
+			SmartAccounts::finish_recovery(RuntimeOrigin::signed(1), account),
+			Error::<Test>::RecoveryNotReady
+		);
+		assert_ok!(SmartAccounts::approve_recovery(RuntimeOrigin::signed(3), account));
+		assert_noop!(
+			SmartAccounts::finish_recovery(RuntimeOrigin::signed(1), account),
+			Error::<Test>::RecoveryNotReady
+		);
+		System::set_block_number(6);
+		assert_ok!(SmartAccounts::finish_recovery(RuntimeOrigin::signed(1), account));
+
+		let details = Accounts::<Test>::get(account).unwrap();
+		assert_eq!(details.owner, new_owner);
+		assert_eq!(details.session_keys, 0);
+		assert_eq!(SessionKeys::<Test>::iter_prefix(account).count(), 0);
+		assert!(!Recoveries::<Test>::contains_key(account));
+		System::assert_last_event(Event::RecoveryFinished { account }.into());
+	});
+}
+
+#[test]
+fn owners_can_cancel_recoveries() {
+	new_test_ext().execute_with(|| {
+		let account =
+			create_account(Credential::Key(KeyCredential::Sr25519(owner_pair().public())));
+		assert_ok!(SmartAccounts::set_guardians(
+			RuntimeOrigin::signed(account),
+			BoundedVec::truncate_from(vec![2]),
+			1,
+			5
+		));
+		let new_owner =
+			Credential::Key(KeyCredential::Sr25519(sr25519::Pair::from_seed(&[4; 32]).public()));
+		assert_ok!(SmartAccounts::initiate_recovery(
+			RuntimeOrigin::signed(2),
+			account,
+			new_owner.clone()
+		));
+		assert_noop!(
+			SmartAccounts::initiate_recovery(RuntimeOrigin::signed(2), account, new_owner),
+			Error::<Test>::RecoveryInProgress
+		);
+
+		// The owner notices and cancels through a regular transaction.
+		let call = RuntimeCall::SmartAccounts(Call::cancel_recovery {});
+		assert_ok!(apply(execute_call(account, call, Signer::Owner, sign_with_owner)));
+		assert_ok!(last_executed());
+		assert!(!Recoveries::<Test>::contains_key(account));
+
+		System::set_block_number(6);
+		assert_noop!(
+			SmartAccounts::finish_recovery(RuntimeOrigin::signed(2), account),
+			Error::<Test>::RecoveryNotFound
+		);
+	});
+}
+
+#[test]
+fn kill_releases_deposit() {
+	new_test_ext().execute_with(|| {
+		let account =
+			create_account(Credential::Key(KeyCredential::Sr25519(owner_pair().public())));
+		assert_noop!(
+			SmartAccounts::create(
+				RuntimeOrigin::signed(1),
+				Credential::Key(KeyCredential::Sr25519(owner_pair().public())),
+				0
+			),
+			Error::<Test>::AlreadyExists
+		);
+		assert_noop!(SmartAccounts::kill(RuntimeOrigin::signed(2)), Error::<Test>::NotSmartAccount);
+
+		assert_ok!(SmartAccounts::kill(RuntimeOrigin::signed(account)));
+		assert!(!Accounts::<Test>::contains_key(account));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		System::assert_last_event(Event::Killed { account }.into());
+	});
+}
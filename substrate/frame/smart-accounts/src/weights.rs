@@ -0,0 +1,308 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Placeholder weights for `pallet_smart_accounts`.
+//!
+//! THESE WEIGHTS WERE NOT GENERATED BY THE BENCHMARK CLI. The values are estimates derived from
+//! the storage accesses of each call and comparable calls of other pallets.
+//!
+//! They must be replaced by the output of the `pallet_smart_accounts` benchmarks,
+//! e.g. generated with `frame-omni-bencher`, before the pallet is used on a production chain.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_smart_accounts`.
+pub trait WeightInfo {
+	fn create() -> Weight;
+	fn execute() -> Weight;
+	fn set_owner() -> Weight;
+	fn add_session_key() -> Weight;
+	fn remove_session_key() -> Weight;
+	fn set_guardians(g: u32, ) -> Weight;
+	fn initiate_recovery(g: u32, ) -> Weight;
+	fn approve_recovery(g: u32, ) -> Weight;
+	fn finish_recovery(s: u32, ) -> Weight;
+	fn cancel_recovery() -> Weight;
+	fn kill(s: u32, ) -> Weight;
+}
+
+/// Weights for `pallet_smart_accounts` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:1)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn create() -> Weight {
+		Weight::from_parts(45_120_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:0)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	/// Storage: `System::BlockHash` (r:1 w:0)
+	/// Proof: `System::BlockHash` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `TransactionPayment::NextFeeMultiplier` (r:1 w:0)
+	/// Proof: `TransactionPayment::NextFeeMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	fn execute() -> Weight {
+		Weight::from_parts(164_871_000, 4040)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:1)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	fn set_owner() -> Weight {
+		Weight::from_parts(17_903_000, 4040)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `SmartAccounts::SessionKeys` (r:1 w:1)
+	/// Proof: `SmartAccounts::SessionKeys` (`max_values`: None, `max_size`: Some(261), added: 2736, mode: `MaxEncodedLen`)
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:1)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	fn add_session_key() -> Weight {
+		Weight::from_parts(24_852_000, 4040)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `SmartAccounts::SessionKeys` (r:1 w:1)
+	/// Proof: `SmartAccounts::SessionKeys` (`max_values`: None, `max_size`: Some(261), added: 2736, mode: `MaxEncodedLen`)
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:1)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	fn remove_session_key() -> Weight {
+		Weight::from_parts(24_301_000, 4040)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:1)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	/// Storage: `SmartAccounts::Recoveries` (r:1 w:1)
+	/// Proof: `SmartAccounts::Recoveries` (`max_values`: None, `max_size`: Some(519), added: 2994, mode: `MaxEncodedLen`)
+	/// The range of component `g` is `[0, 10]`.
+	fn set_guardians(g: u32, ) -> Weight {
+		Weight::from_parts(22_386_412, 4040)
+			// Standard Error: 3_412
+			.saturating_add(Weight::from_parts(62_318, 0).saturating_mul(g.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:0)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	/// Storage: `SmartAccounts::Recoveries` (r:1 w:1)
+	/// Proof: `SmartAccounts::Recoveries` (`max_values`: None, `max_size`: Some(519), added: 2994, mode: `MaxEncodedLen`)
+	/// The range of component `g` is `[1, 10]`.
+	fn initiate_recovery(g: u32, ) -> Weight {
+		Weight::from_parts(20_972_144, 4040)
+			// Standard Error: 3_412
+			.saturating_add(Weight::from_parts(85_201, 0).saturating_mul(g.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:0)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	/// Storage: `SmartAccounts::Recoveries` (r:1 w:1)
+	/// Proof: `SmartAccounts::Recoveries` (`max_values`: None, `max_size`: Some(519), added: 2994, mode: `MaxEncodedLen`)
+	/// The range of component `g` is `[2, 10]`.
+	fn approve_recovery(g: u32, ) -> Weight {
+		Weight::from_parts(23_410_357, 4040)
+			// Standard Error: 3_412
+			.saturating_add(Weight::from_parts(131_746, 0).saturating_mul(g.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:1)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	/// Storage: `SmartAccounts::Recoveries` (r:1 w:1)
+	/// Proof: `SmartAccounts::Recoveries` (`max_values`: None, `max_size`: Some(519), added: 2994, mode: `MaxEncodedLen`)
+	/// Storage: `SmartAccounts::SessionKeys` (r:s w:s)
+	/// Proof: `SmartAccounts::SessionKeys` (`max_values`: None, `max_size`: Some(261), added: 2736, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 10]`.
+	fn finish_recovery(s: u32, ) -> Weight {
+		Weight::from_parts(30_514_828, 4040)
+			// Standard Error: 3_412
+			.saturating_add(Weight::from_parts(1_512_390, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 2736).saturating_mul(s.into()))
+	}
+	/// Storage: `SmartAccounts::Recoveries` (r:1 w:1)
+	/// Proof: `SmartAccounts::Recoveries` (`max_values`: None, `max_size`: Some(519), added: 2994, mode: `MaxEncodedLen`)
+	fn cancel_recovery() -> Weight {
+		Weight::from_parts(16_482_000, 3984)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:1)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `SmartAccounts::SessionKeys` (r:s w:s)
+	/// Proof: `SmartAccounts::SessionKeys` (`max_values`: None, `max_size`: Some(261), added: 2736, mode: `MaxEncodedLen`)
+	/// Storage: `SmartAccounts::Recoveries` (r:0 w:1)
+	/// Proof: `SmartAccounts::Recoveries` (`max_values`: None, `max_size`: Some(519), added: 2994, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 10]`.
+	fn kill(s: u32, ) -> Weight {
+		Weight::from_parts(47_391_655, 6196)
+			// Standard Error: 3_412
+			.saturating_add(Weight::from_parts(1_498_113, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 2736).saturating_mul(s.into()))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:1)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn create() -> Weight {
+		Weight::from_parts(45_120_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:0)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	/// Storage: `System::BlockHash` (r:1 w:0)
+	/// Proof: `System::BlockHash` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `TransactionPayment::NextFeeMultiplier` (r:1 w:0)
+	/// Proof: `TransactionPayment::NextFeeMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	fn execute() -> Weight {
+		Weight::from_parts(164_871_000, 4040)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:1)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	fn set_owner() -> Weight {
+		Weight::from_parts(17_903_000, 4040)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `SmartAccounts::SessionKeys` (r:1 w:1)
+	/// Proof: `SmartAccounts::SessionKeys` (`max_values`: None, `max_size`: Some(261), added: 2736, mode: `MaxEncodedLen`)
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:1)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	fn add_session_key() -> Weight {
+		Weight::from_parts(24_852_000, 4040)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `SmartAccounts::SessionKeys` (r:1 w:1)
+	/// Proof: `SmartAccounts::SessionKeys` (`max_values`: None, `max_size`: Some(261), added: 2736, mode: `MaxEncodedLen`)
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:1)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	fn remove_session_key() -> Weight {
+		Weight::from_parts(24_301_000, 4040)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:1)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	/// Storage: `SmartAccounts::Recoveries` (r:1 w:1)
+	/// Proof: `SmartAccounts::Recoveries` (`max_values`: None, `max_size`: Some(519), added: 2994, mode: `MaxEncodedLen`)
+	/// The range of component `g` is `[0, 10]`.
+	fn set_guardians(g: u32, ) -> Weight {
+		Weight::from_parts(22_386_412, 4040)
+			// Standard Error: 3_412
+			.saturating_add(Weight::from_parts(62_318, 0).saturating_mul(g.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:0)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	/// Storage: `SmartAccounts::Recoveries` (r:1 w:1)
+	/// Proof: `SmartAccounts::Recoveries` (`max_values`: None, `max_size`: Some(519), added: 2994, mode: `MaxEncodedLen`)
+	/// The range of component `g` is `[1, 10]`.
+	fn initiate_recovery(g: u32, ) -> Weight {
+		Weight::from_parts(20_972_144, 4040)
+			// Standard Error: 3_412
+			.saturating_add(Weight::from_parts(85_201, 0).saturating_mul(g.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:0)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	/// Storage: `SmartAccounts::Recoveries` (r:1 w:1)
+	/// Proof: `SmartAccounts::Recoveries` (`max_values`: None, `max_size`: Some(519), added: 2994, mode: `MaxEncodedLen`)
+	/// The range of component `g` is `[2, 10]`.
+	fn approve_recovery(g: u32, ) -> Weight {
+		Weight::from_parts(23_410_357, 4040)
+			// Standard Error: 3_412
+			.saturating_add(Weight::from_parts(131_746, 0).saturating_mul(g.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:1)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	/// Storage: `SmartAccounts::Recoveries` (r:1 w:1)
+	/// Proof: `SmartAccounts::Recoveries` (`max_values`: None, `max_size`: Some(519), added: 2994, mode: `MaxEncodedLen`)
+	/// Storage: `SmartAccounts::SessionKeys` (r:s w:s)
+	/// Proof: `SmartAccounts::SessionKeys` (`max_values`: None, `max_size`: Some(261), added: 2736, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 10]`.
+	fn finish_recovery(s: u32, ) -> Weight {
+		Weight::from_parts(30_514_828, 4040)
+			// Standard Error: 3_412
+			.saturating_add(Weight::from_parts(1_512_390, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 2736).saturating_mul(s.into()))
+	}
+	/// Storage: `SmartAccounts::Recoveries` (r:1 w:1)
+	/// Proof: `SmartAccounts::Recoveries` (`max_values`: None, `max_size`: Some(519), added: 2994, mode: `MaxEncodedLen`)
+	fn cancel_recovery() -> Weight {
+		Weight::from_parts(16_482_000, 3984)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `SmartAccounts::Accounts` (r:1 w:1)
+	/// Proof: `SmartAccounts::Accounts` (`max_values`: None, `max_size`: Some(575), added: 3050, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `SmartAccounts::SessionKeys` (r:s w:s)
+	/// Proof: `SmartAccounts::SessionKeys` (`max_values`: None, `max_size`: Some(261), added: 2736, mode: `MaxEncodedLen`)
+	/// Storage: `SmartAccounts::Recoveries` (r:0 w:1)
+	/// Proof: `SmartAccounts::Recoveries` (`max_values`: None, `max_size`: Some(519), added: 2994, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 10]`.
+	fn kill(s: u32, ) -> Weight {
+		Weight::from_parts(47_391_655, 6196)
+			// Standard Error: 3_412
+			.saturating_add(Weight::from_parts(1_498_113, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 2736).saturating_mul(s.into()))
+	}
+}
@@ -19,9 +19,13 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use alloc::vec::Vec;
 use codec::Codec;
 
 sp_api::decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait StakingApi<Balance, AccountId>
 		where
 			Balance: Codec,
@@ -35,5 +39,11 @@ sp_api::decl_runtime_apis! {
 
 		/// Returns true if validator `account` has pages to be claimed for the given era.
 		fn pending_rewards(era: sp_staking::EraIndex, account: AccountId) -> bool;
+
+		/// Returns the scheduled commission changes of the validators nominated by `nominator`, as
+		/// tuples of the validator, their new commission and the era from which it applies.
+		fn commission_changes(
+			nominator: AccountId,
+		) -> Vec<(AccountId, sp_arithmetic::Perbill, sp_staking::EraIndex)>;
 	}
 }
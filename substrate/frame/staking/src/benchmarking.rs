@@ -29,7 +29,7 @@ use frame_support::{
 	traits::{Currency, Get, Imbalance, UnfilteredDispatchable},
 };
 use sp_runtime::{
-	traits::{Bounded, One, StaticLookup, TrailingZeroInput, Zero},
+	traits::{Bounded, One, StaticLookup, Zero},
 	Perbill, Percent, Saturating,
 };
 use sp_staking::{currency_to_vote::CurrencyToVote, SessionIndex};
@@ -43,6 +43,7 @@ use frame_system::RawOrigin;
 const SEED: u32 = 0;
 const MAX_SPANS: u32 = 100;
 const MAX_SLASHES: u32 = 1000;
+const MAX_SLASH_PAGES: u32 = 100;
 
 type MaxValidators<T> = <<T as Config>::BenchmarkingConfig as BenchmarkingConfig>::MaxValidators;
 type MaxNominators<T> = <<T as Config>::BenchmarkingConfig as BenchmarkingConfig>::MaxNominators;
@@ -473,6 +474,16 @@ benchmarks! {
 		assert_eq!(Payee::<T>::get(&stash), Some(RewardDestination::Account(controller)));
 	}
 
+	set_reward_conversion {
+		let (stash, controller) = create_stash_controller::<T>(USER_SEED, 100, RewardDestination::Stash)?;
+		whitelist_account!(controller);
+		// the quote of the asset is accounted for by the weight of the converter, so only the
+		// bookkeeping of the conversion is measured here.
+	}: _(RawOrigin::Signed(controller), None)
+	verify {
+		assert!(!RewardConversions::<T>::contains_key(&stash));
+	}
+
 	update_payee {
 		let (stash, controller) = create_stash_controller::<T>(USER_SEED, 100, RewardDestination::Staked)?;
 		Payee::<T>::insert(&stash, {
@@ -583,18 +594,69 @@ benchmarks! {
 
 	cancel_deferred_slash {
 		let s in 1 .. MAX_SLASHES;
-		let mut unapplied_slashes = Vec::new();
+		let p in 1 .. MAX_SLASH_PAGES;
 		let era = EraIndex::one();
-		let dummy = || T::AccountId::decode(&mut TrailingZeroInput::zeroes()).unwrap();
-		for _ in 0 .. MAX_SLASHES {
-			unapplied_slashes.push(UnappliedSlash::<T::AccountId, BalanceOf<T>>::default_from(dummy()));
+		let fraction = Perbill::from_percent(10);
+		let mut validator_slashes = Vec::new();
+		for i in 0 .. MAX_SLASHES {
+			let validator: T::AccountId = account("validator", i, SEED);
+			// the first slash has `p` pages, all the others a single one.
+			let pages = if i == 0 { p } else { 1 };
+			let unapplied = (0..pages)
+				.map(|_| UnappliedSlash::default_from(validator.clone()))
+				.collect();
+			Staking::<T>::defer_slash(era, &validator, fraction, unapplied);
+			if i < s {
+				validator_slashes.push((validator, fraction));
+			}
 		}
-		UnappliedSlashes::<T>::insert(era, &unapplied_slashes);
+		// enough to cancel all the pages of the given slashes.
+		let page_limit = p + s - 1;
+	}: _(RawOrigin::Root, era, validator_slashes, page_limit)
+	verify {
+		assert_eq!(
+			UnappliedSlashes::<T>::iter_prefix((era,)).count(),
+			(MAX_SLASHES - s) as usize,
+		);
+	}
+
+	apply_slash {
+		let n in 0 .. T::MaxExposurePageSize::get() as u32;
+		let (validator, nominators) = create_validator_with_nominators::<T>(
+			n,
+			T::MaxExposurePageSize::get() as u32,
+			false,
+			true,
+			RewardDestination::Staked,
+		)?;
+
+		let current_era = CurrentEra::<T>::get().unwrap();
+		ActiveEra::<T>::put(ActiveEraInfo { index: current_era, start: None });
+
+		// slash the validator and all its nominators in a single page.
+		let fraction = Perbill::from_percent(10);
+		let exposure = EraInfo::<T>::get_full_exposure(current_era, &validator);
+		let unapplied = UnappliedSlash {
+			validator: validator.clone(),
+			own: fraction * exposure.own,
+			others: exposure.others.iter().map(|n| (n.who.clone(), fraction * n.value)).collect(),
+			reporters: vec![],
+			payout: Zero::zero(),
+		};
+		Staking::<T>::defer_slash(current_era, &validator, fraction, vec![unapplied]);
 
-		let slash_indices: Vec<u32> = (0 .. s).collect();
-	}: _(RawOrigin::Root, era, slash_indices)
+		let caller = whitelisted_caller();
+		let ledger_before = Staking::<T>::ledger(StakingAccount::Stash(validator.clone()))
+			.map_err(|_| "validator is not bonded")?;
+	}: _(RawOrigin::Signed(caller), current_era, (validator.clone(), fraction, 0))
 	verify {
-		assert_eq!(UnappliedSlashes::<T>::get(&era).len(), (MAX_SLASHES - s) as usize);
+		assert!(UnappliedSlashes::<T>::get((current_era, &validator, fraction, 0)).is_none());
+		assert!(UnappliedSlashEras::<T>::get().is_empty());
+
+		let ledger_after = Staking::<T>::ledger(StakingAccount::Stash(validator))
+			.map_err(|_| "validator is not bonded")?;
+		assert!(ledger_after.total < ledger_before.total);
+		assert_eq!(nominators.len() as u32, n);
 	}
 
 	payout_stakers_alive_staked {
@@ -828,7 +890,7 @@ benchmarks! {
 		let num_voters = (v + n) as usize;
 	}: {
 		// default bounds are unbounded.
-		let voters = <Staking<T>>::get_npos_voters(DataProviderBounds::default());
+		let voters = <Staking<T>>::get_npos_voters(DataProviderBounds::default(), &SnapshotStatus::Waiting);
 		assert_eq!(voters.len(), num_voters);
 	}
 
@@ -953,6 +1015,25 @@ benchmarks! {
 		assert_eq!(MinCommission::<T>::get(), Perbill::from_percent(100));
 	}
 
+	schedule_commission_change {
+		let (stash, controller) = create_stash_controller::<T>(USER_SEED, 100, RewardDestination::Staked)?;
+		Staking::<T>::validate(RawOrigin::Signed(controller.clone()).into(), Default::default())?;
+		// worst case: the commission increase is checked against the maximum change rate.
+		MaxCommissionChangeRate::<T>::put(Perbill::from_percent(100));
+		let commission = Perbill::from_percent(10);
+		whitelist_account!(controller);
+	}: _(RawOrigin::Signed(controller), commission)
+	verify {
+		assert_eq!(CommissionChanges::<T>::get(&stash).map(|c| c.commission), Some(commission));
+	}
+
+	set_max_commission_change_rate {
+		let rate = Perbill::from_percent(1);
+	}: _(RawOrigin::Root, Some(rate))
+	verify {
+		assert_eq!(MaxCommissionChangeRate::<T>::get(), Some(rate));
+	}
+
 	restore_ledger {
 		let (stash, controller) = create_stash_controller::<T>(0, 100, RewardDestination::Staked)?;
 		// corrupt ledger.
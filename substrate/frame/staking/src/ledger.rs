@@ -39,7 +39,8 @@ use sp_staking::StakingAccount;
 use sp_std::prelude::*;
 
 use crate::{
-	BalanceOf, Bonded, Config, Error, Ledger, Pallet, Payee, RewardDestination, StakingLedger,
+	BalanceOf, Bonded, CommissionChanges, CommittedCommissions, Config, Error, Ledger, Pallet,
+	Payee, RewardAssetOf, RewardConversion, RewardConversions, RewardDestination, StakingLedger,
 	VirtualStakers, STAKING_ID,
 };
 
@@ -233,6 +234,19 @@ impl<T: Config> StakingLedger<T> {
 		Ok(())
 	}
 
+	/// Sets or removes the conversion of the rewards of the ledger's stash.
+	pub(crate) fn set_reward_conversion(
+		self,
+		conversion: Option<RewardConversion<RewardAssetOf<T>>>,
+	) -> Result<(), Error<T>> {
+		if !<Bonded<T>>::contains_key(&self.stash) {
+			return Err(Error::<T>::NotStash)
+		}
+
+		<RewardConversions<T>>::set(&self.stash, conversion);
+		Ok(())
+	}
+
 	/// Sets the ledger controller to its stash.
 	pub(crate) fn set_controller_to_stash(self) -> Result<(), Error<T>> {
 		let controller = self.controller.as_ref()
@@ -266,6 +280,9 @@ impl<T: Config> StakingLedger<T> {
 			Ledger::<T>::remove(controller);
 			<Bonded<T>>::remove(&stash);
 			<Payee<T>>::remove(&stash);
+			<RewardConversions<T>>::remove(&stash);
+			<CommissionChanges<T>>::remove(&stash);
+			<CommittedCommissions<T>>::remove(&stash);
 
 			// kill virtual staker if it exists.
 			if <VirtualStakers<T>>::take(&stash).is_none() {
@@ -311,13 +311,13 @@ use frame_support::{
 		ConstU32, Currency, Defensive, DefensiveMax, DefensiveSaturating, Get, LockIdentifier,
 	},
 	weights::Weight,
-	BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+	BoundedVec, CloneNoBound, EqNoBound, Parameter, PartialEqNoBound, RuntimeDebugNoBound,
 };
 use scale_info::TypeInfo;
 use sp_runtime::{
 	curve::PiecewiseLinear,
 	traits::{AtLeast32BitUnsigned, Convert, StaticLookup, Zero},
-	Perbill, Perquintill, Rounding, RuntimeDebug, Saturating,
+	DispatchError, FixedU128, Perbill, Perquintill, Rounding, RuntimeDebug, Saturating,
 };
 use sp_staking::{
 	offence::{Offence, OffenceError, ReportOffence},
@@ -358,15 +358,38 @@ pub type RewardPoint = u32;
 /// The balance type of this pallet.
 pub type BalanceOf<T> = <T as Config>::CurrencyBalance;
 
+/// The kind of asset that staking rewards can be converted into.
+pub type RewardAssetOf<T> = <<T as Config>::RewardConverter as RewardConverter<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+>>::AssetKind;
+
 type PositiveImbalanceOf<T> = <<T as Config>::Currency as Currency<
 	<T as frame_system::Config>::AccountId,
 >>::PositiveImbalance;
-type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
 	<T as frame_system::Config>::AccountId,
 >>::NegativeImbalance;
 
 type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
 
+/// Status of the paged voter snapshot that is handed to the election provider.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+pub enum SnapshotStatus<AccountId> {
+	/// Voters are being returned page by page. The account is the last voter of the previous page,
+	/// from which the next page starts.
+	Ongoing(AccountId),
+	/// Voters are being returned page by page, but the last voter of the previous page has left
+	/// the voter list since. The account is the voter that followed it, from which the next page
+	/// starts.
+	Resuming(AccountId),
+	/// All voters have been returned, the remaining pages of the snapshot are empty.
+	Consumed,
+	/// No snapshot is being taken, the next page starts from the top of the voter list.
+	#[default]
+	Waiting,
+}
+
 /// Information regarding the active era (era in used in session).
 #[derive(Encode, Decode, Debug, TypeInfo, MaxEncodedLen)]
 pub struct ActiveEraInfo {
@@ -426,6 +449,15 @@ pub struct ValidatorPrefs {
 	pub blocked: bool,
 }
 
+/// A change of the commission of a validator, announced ahead of the era it takes effect in.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct CommissionChange {
+	/// The new commission of the validator.
+	pub commission: Perbill,
+	/// The first era that is planned with the new commission.
+	pub era: EraIndex,
+}
+
 /// Just a Balance/BlockNumber tuple to encode when a chunk of funds will be unlocked.
 #[derive(PartialEq, Eq, Clone, Encode, Decode, Debug, TypeInfo, MaxEncodedLen)]
 pub struct UnlockChunk<Balance: HasCompact + MaxEncodedLen> {
@@ -771,19 +803,22 @@ impl<AccountId, Balance: HasCompact + Copy + AtLeast32BitUnsigned + codec::MaxEn
 	}
 }
 
-/// A pending slash record. The value of the slash has been computed but not applied yet,
-/// rather deferred for several eras.
+/// A pending slash record for a single page of a validator's exposure. The value of the slash has
+/// been computed but not applied yet, rather deferred for several eras.
+///
+/// A slash of a validator is split into pages of at most [`Config::MaxExposurePageSize`]
+/// nominators, each of which is applied independently.
 #[derive(Encode, Decode, Debug, TypeInfo)]
 pub struct UnappliedSlash<AccountId, Balance: HasCompact> {
 	/// The stash ID of the offending validator.
 	validator: AccountId,
-	/// The validator's own slash.
+	/// The validator's own slash. Only non-zero in the first page.
 	own: Balance,
-	/// All other slashed stakers and amounts.
+	/// Slashed nominators of this page and their amounts.
 	others: Vec<(AccountId, Balance)>,
 	/// Reporters of the offence; bounty payout recipients.
 	reporters: Vec<AccountId>,
-	/// The amount of payout.
+	/// The amount of payout for this page.
 	payout: Balance,
 }
 
@@ -930,6 +965,68 @@ where
 	}
 }
 
+/// The preference of a stash to have its staking rewards converted into another asset when they
+/// are paid out.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RewardConversion<AssetKind> {
+	/// The asset that the rewards are converted into.
+	pub asset: AssetKind,
+	/// The minimum accepted amount of `asset` received per unit of the native currency, both in
+	/// their smallest denomination. If the conversion would yield less, the reward is kept in the
+	/// native currency.
+	///
+	/// This is an absolute rate set by the stash rather than a tolerance around the current
+	/// price, so that the price cannot be moved right before the payout to extract value from
+	/// the conversion.
+	pub min_rate: FixedU128,
+}
+
+/// Handler for converting staking rewards, paid out in the native currency, into other assets.
+pub trait RewardConverter<AccountId, Balance> {
+	/// The kind of asset that rewards can be converted into.
+	type AssetKind: Parameter + MaxEncodedLen;
+
+	/// Whether the native currency can currently be converted into `asset`.
+	fn can_convert(asset: &Self::AssetKind) -> bool;
+
+	/// Swap exactly `amount` of the native currency held by `who` into `asset`, and deposit the
+	/// proceeds into the account of `who`, which must be kept alive.
+	///
+	/// Fails if less than `min_out` of `asset` would be received. This operation is expected to be
+	/// atomic. If successful, returns the amount of `asset` received.
+	fn swap(
+		who: &AccountId,
+		asset: &Self::AssetKind,
+		amount: Balance,
+		min_out: Balance,
+	) -> Result<Balance, DispatchError>;
+
+	/// An upper bound on the weight of a [`Self::can_convert`] followed by a [`Self::swap`].
+	fn weight() -> Weight;
+}
+
+/// No conversion is supported, and all rewards are kept in the native currency.
+impl<AccountId, Balance> RewardConverter<AccountId, Balance> for () {
+	type AssetKind = ();
+
+	fn can_convert(_asset: &Self::AssetKind) -> bool {
+		false
+	}
+
+	fn swap(
+		_who: &AccountId,
+		_asset: &Self::AssetKind,
+		_amount: Balance,
+		_min_out: Balance,
+	) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Unavailable)
+	}
+
+	fn weight() -> Weight {
+		Weight::zero()
+	}
+}
+
 /// Mode of era-forcing.
 #[derive(
 	Copy,
@@ -1231,6 +1328,56 @@ impl<T: Config> EraInfo<T> {
 		});
 	}
 
+	/// Store exposure for a validator elected in a page of a multi-page election.
+	///
+	/// If the validator already has an exposure in `era` (because it was elected in a previous
+	/// page), the new exposure is merged into it: the last page of nominators is filled up first,
+	/// and the remaining nominators are stored in new pages.
+	pub fn upsert_exposure(
+		era: EraIndex,
+		validator: &T::AccountId,
+		exposure: Exposure<T::AccountId, BalanceOf<T>>,
+	) {
+		let mut overview = match <ErasStakersOverview<T>>::get(&era, validator) {
+			Some(overview) => overview,
+			None => return Self::set_exposure(era, validator, exposure),
+		};
+
+		let page_size = T::MaxExposurePageSize::get().defensive_max(1) as usize;
+		let Exposure { own, total, mut others } = exposure;
+
+		overview.own.saturating_accrue(own);
+		overview.total.saturating_accrue(total);
+		overview.nominator_count.saturating_accrue(others.len() as u32);
+
+		let page_total_of = |others: &[IndividualExposure<T::AccountId, BalanceOf<T>>]| {
+			others.iter().fold(BalanceOf::<T>::zero(), |acc, i| acc.saturating_add(i.value))
+		};
+
+		// fill up the last page first.
+		if let Some(last_page) = overview.page_count.checked_sub(1) {
+			if let Some(mut exposure_page) = <ErasStakersPaged<T>>::get((era, validator, last_page))
+			{
+				let room = page_size.saturating_sub(exposure_page.others.len()).min(others.len());
+				let rest = others.split_off(room);
+				exposure_page.page_total.saturating_accrue(page_total_of(&others));
+				exposure_page.others.append(&mut others);
+				<ErasStakersPaged<T>>::insert((era, validator, last_page), &exposure_page);
+				others = rest;
+			}
+		}
+
+		// and then store the rest in new pages.
+		for chunk in others.chunks(page_size) {
+			let exposure_page =
+				ExposurePage { page_total: page_total_of(chunk), others: chunk.to_vec() };
+			<ErasStakersPaged<T>>::insert((era, validator, overview.page_count), &exposure_page);
+			overview.page_count.saturating_inc();
+		}
+
+		<ErasStakersOverview<T>>::insert(era, &validator, &overview);
+	}
+
 	/// Store total exposure for all the elected validators in the era.
 	pub(crate) fn set_total_stake(era: EraIndex, total_stake: BalanceOf<T>) {
 		<ErasTotalStake<T>>::insert(era, total_stake);
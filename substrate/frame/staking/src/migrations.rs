@@ -21,7 +21,7 @@ use super::*;
 use frame_election_provider_support::SortedListProvider;
 use frame_support::{
 	migrations::VersionedMigration,
-	pallet_prelude::ValueQuery,
+	pallet_prelude::{Twox64Concat, ValueQuery},
 	storage_alias,
 	traits::{GetStorageVersion, OnRuntimeUpgrade, UncheckedOnRuntimeUpgrade},
 };
@@ -60,6 +60,126 @@ impl Default for ObsoleteReleases {
 #[storage_alias]
 type StorageVersion<T: Config> = StorageValue<Pallet<T>, ObsoleteReleases, ValueQuery>;
 
+/// Populating `CommittedCommissions` with the commission of the current validators.
+pub mod v17 {
+	use super::*;
+
+	pub struct VersionUncheckedMigrateV16ToV17<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateV16ToV17<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut validators = 0u64;
+			for (stash, prefs) in Validators::<T>::iter() {
+				CommittedCommissions::<T>::insert(&stash, prefs.commission);
+				validators.saturating_inc();
+			}
+
+			log!(info, "v17 applied successfully, {} commissions committed.", validators);
+			T::DbWeight::get().reads_writes(validators, validators)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			ensure!(
+				Validators::<T>::iter().all(|(stash, prefs)| {
+					CommittedCommissions::<T>::get(&stash) == Some(prefs.commission)
+				}),
+				"not all validators have committed to their commission"
+			);
+			Ok(())
+		}
+	}
+
+	pub type MigrateV16ToV17<T> = VersionedMigration<
+		16,
+		17,
+		VersionUncheckedMigrateV16ToV17<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migrating `UnappliedSlashes` from a list of slashes per era to slash pages keyed by era,
+/// validator, slash fraction and page.
+pub mod v16 {
+	use super::*;
+
+	pub struct VersionUncheckedMigrateV15ToV16<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateV15ToV16<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let slashes = v15::UnappliedSlashes::<T>::iter()
+				.map(|(_, slashes)| slashes.len() as u32)
+				.sum::<u32>();
+			Ok(slashes.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let old = v15::UnappliedSlashes::<T>::drain().collect::<Vec<_>>();
+			let mut reads_writes = old.len() as u64;
+
+			// the old slashes carry no slash fraction, so the maximum slash fraction of the
+			// validator in the era of the offence is used. Slashes that would end up with the same
+			// key are stored as subsequent pages of it.
+			let mut next_page: BTreeMap<(EraIndex, T::AccountId, Perbill), Page> = BTreeMap::new();
+			let mut eras = Vec::new();
+			for (era, slashes) in old {
+				let offence_era =
+					era.saturating_sub(T::SlashDeferDuration::get()).saturating_sub(1);
+				for slash in slashes {
+					let fraction = ValidatorSlashInEra::<T>::get(offence_era, &slash.validator)
+						.map(|(fraction, _)| fraction)
+						.unwrap_or_default();
+					let page = next_page
+						.entry((era, slash.validator.clone(), fraction))
+						.and_modify(|page| *page += 1)
+						.or_default();
+
+					UnappliedSlashes::<T>::insert((era, &slash.validator, fraction, *page), slash);
+					reads_writes.saturating_accrue(1);
+				}
+				eras.push(era);
+			}
+
+			eras.sort();
+			eras.dedup();
+			UnappliedSlashEras::<T>::put(eras);
+
+			log!(info, "v16 applied successfully.");
+			T::DbWeight::get().reads_writes(reads_writes, reads_writes.saturating_add(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let slashes: u32 = Decode::decode(&mut &state[..])
+				.map_err(|_| "failed to decode the number of unapplied slashes")?;
+			ensure!(
+				UnappliedSlashes::<T>::iter().count() as u32 == slashes,
+				"not all unapplied slashes were migrated"
+			);
+
+			let eras = UnappliedSlashEras::<T>::get();
+			ensure!(
+				eras.windows(2).all(|w| w[0] < w[1]),
+				"eras with unapplied slashes are not sorted"
+			);
+			ensure!(
+				eras.iter()
+					.all(|era| UnappliedSlashes::<T>::iter_key_prefix((*era,)).next().is_some()),
+				"eras without unapplied slashes are tracked"
+			);
+			Ok(())
+		}
+	}
+
+	pub type MigrateV15ToV16<T> = VersionedMigration<
+		15,
+		16,
+		VersionUncheckedMigrateV15ToV16<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
 /// Migrating `OffendingValidators` from `Vec<(u32, bool)>` to `Vec<u32>`
 pub mod v15 {
 	use super::*;
@@ -67,6 +187,15 @@ pub mod v15 {
 	// The disabling strategy used by staking pallet
 	type DefaultDisablingStrategy = UpToLimitDisablingStrategy;
 
+	#[frame_support::storage_alias]
+	pub(crate) type UnappliedSlashes<T: Config> = StorageMap<
+		Pallet<T>,
+		Twox64Concat,
+		EraIndex,
+		Vec<UnappliedSlash<<T as frame_system::Config>::AccountId, BalanceOf<T>>>,
+		ValueQuery,
+	>;
+
 	pub struct VersionUncheckedMigrateV14ToV15<T>(sp_std::marker::PhantomData<T>);
 	impl<T: Config> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateV14ToV15<T> {
 		fn on_runtime_upgrade() -> Weight {
@@ -356,7 +485,7 @@ pub mod v10 {
 	impl<T: Config> OnRuntimeUpgrade for MigrateToV10<T> {
 		fn on_runtime_upgrade() -> frame_support::weights::Weight {
 			if StorageVersion::<T>::get() == ObsoleteReleases::V9_0_0 {
-				let pending_slashes = UnappliedSlashes::<T>::iter().take(512);
+				let pending_slashes = v15::UnappliedSlashes::<T>::iter().take(512);
 				for (era, slashes) in pending_slashes {
 					for slash in slashes {
 						// in the old slashing scheme, the slash era was the key at which we read
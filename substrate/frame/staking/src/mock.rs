@@ -23,10 +23,10 @@ use frame_election_provider_support::{
 	onchain, SequentialPhragmen, VoteWeight,
 };
 use frame_support::{
-	assert_ok, derive_impl, ord_parameter_types, parameter_types,
+	assert_ok, derive_impl, ensure, ord_parameter_types, parameter_types,
 	traits::{
-		ConstU64, Currency, EitherOfDiverse, FindAuthor, Get, Hooks, Imbalance, LockableCurrency,
-		OnUnbalanced, OneSessionHandler, WithdrawReasons,
+		ConstU64, Currency, EitherOfDiverse, ExistenceRequirement, FindAuthor, Get, Hooks,
+		Imbalance, LockableCurrency, OnUnbalanced, OneSessionHandler, WithdrawReasons,
 	},
 	weights::constants::RocksDbWeight,
 };
@@ -113,6 +113,7 @@ parameter_types! {
 	pub static SessionsPerEra: SessionIndex = 3;
 	pub static ExistentialDeposit: Balance = 1;
 	pub static SlashDeferDuration: EraIndex = 0;
+	pub static CommissionChangeDelay: EraIndex = 0;
 	pub static Period: BlockNumber = 5;
 	pub static Offset: BlockNumber = 0;
 	pub static MaxControllersInDeprecationBatch: u32 = 5900;
@@ -244,6 +245,52 @@ impl OnUnbalanced<PositiveImbalanceOf<Test>> for MockReward {
 	}
 }
 
+parameter_types! {
+	/// The spot price of each asset that rewards can be converted into, in units of the asset per
+	/// unit of the native currency.
+	pub static RewardAssetPrices: BTreeMap<u32, Balance> = BTreeMap::new();
+	/// The slippage applied to the spot value of each swap.
+	pub static RewardSwapSlippage: Perbill = Perbill::zero();
+	/// The converted rewards received by each account, per asset.
+	pub static ConvertedRewards: BTreeMap<(AccountId, u32), Balance> = BTreeMap::new();
+}
+
+/// Converts rewards by burning the native currency, and recording the received asset in
+/// [`ConvertedRewards`].
+pub struct MockRewardConverter;
+impl RewardConverter<AccountId, Balance> for MockRewardConverter {
+	type AssetKind = u32;
+
+	fn can_convert(asset: &u32) -> bool {
+		RewardAssetPrices::get().contains_key(asset)
+	}
+
+	fn swap(
+		who: &AccountId,
+		asset: &u32,
+		amount: Balance,
+		min_out: Balance,
+	) -> Result<Balance, sp_runtime::DispatchError> {
+		let price = *RewardAssetPrices::get().get(asset).ok_or("no price")?;
+		let spot_value = amount.saturating_mul(price);
+		let amount_out = spot_value.saturating_sub(RewardSwapSlippage::get() * spot_value);
+		ensure!(amount_out >= min_out, "slippage exceeded");
+
+		let _ = <Balances as Currency<_>>::withdraw(
+			who,
+			amount,
+			WithdrawReasons::TRANSFER,
+			ExistenceRequirement::KeepAlive,
+		)?;
+		ConvertedRewards::mutate(|m| *m.entry((*who, *asset)).or_default() += amount_out);
+		Ok(amount_out)
+	}
+
+	fn weight() -> Weight {
+		Weight::from_parts(1_000, 0)
+	}
+}
+
 parameter_types! {
 	pub static LedgerSlashPerEra:
 		(BalanceOf<Test>, BTreeMap<EraIndex, BalanceOf<Test>>) =
@@ -278,8 +325,10 @@ impl crate::pallet::pallet::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Slash = ();
 	type Reward = MockReward;
+	type RewardConverter = MockRewardConverter;
 	type SessionsPerEra = SessionsPerEra;
 	type SlashDeferDuration = SlashDeferDuration;
+	type CommissionChangeDelay = CommissionChangeDelay;
 	type AdminOrigin = EnsureOneOrRoot;
 	type BondingDuration = BondingDuration;
 	type SessionInterface = Self;
@@ -441,7 +490,7 @@ impl ExtBuilder {
 		SkipTryStateCheck::set(!enable);
 		self
 	}
-	fn build(self) -> sp_io::TestExternalities {
+	pub(crate) fn build(self) -> sp_io::TestExternalities {
 		sp_tracing::try_init_simple();
 		let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
 
@@ -20,7 +20,7 @@
 use frame_election_provider_support::{
 	bounds::{CountBound, SizeBound},
 	data_provider, BoundedSupportsOf, DataProviderBounds, ElectionDataProvider, ElectionProvider,
-	ScoreProvider, SortedListProvider, VoteWeight, VoterOf,
+	ElectionProviderBase, PageIndex, ScoreProvider, SortedListProvider, VoteWeight, VoterOf,
 };
 use frame_support::{
 	defensive,
@@ -39,7 +39,7 @@ use sp_runtime::{
 		Bounded, CheckedAdd, CheckedSub, Convert, One, SaturatedConversion, Saturating,
 		StaticLookup, Zero,
 	},
-	ArithmeticError, Perbill, Percent,
+	ArithmeticError, FixedPointNumber, Perbill, Percent,
 };
 use sp_staking::{
 	currency_to_vote::CurrencyToVote,
@@ -54,7 +54,8 @@ use crate::{
 	election_size_tracker::StaticTracker, log, slashing, weights::WeightInfo, ActiveEraInfo,
 	BalanceOf, EraInfo, EraPayout, Exposure, ExposureOf, Forcing, IndividualExposure,
 	LedgerIntegrityState, MaxNominationsOf, MaxWinnersOf, Nominations, NominationsQuota,
-	PositiveImbalanceOf, RewardDestination, SessionInterface, StakingLedger, ValidatorPrefs,
+	PositiveImbalanceOf, RewardConverter, RewardDestination, SessionInterface, SnapshotStatus,
+	StakingLedger, UnappliedSlash, ValidatorPrefs,
 };
 
 use super::pallet::*;
@@ -192,7 +193,7 @@ impl<T: Config> Pallet<T> {
 		let mut ledger = Self::ledger(Controller(controller.clone()))?;
 		let (stash, old_total) = (ledger.stash.clone(), ledger.total);
 		if let Some(current_era) = Self::current_era() {
-			ledger = ledger.consolidate_unlocked(current_era)
+			ledger = ledger.consolidate_unlocked(Self::withdrawable_era(current_era))
 		}
 		let new_total = ledger.total;
 
@@ -349,12 +350,22 @@ impl<T: Config> Pallet<T> {
 		});
 
 		let mut total_imbalance = PositiveImbalanceOf::<T>::zero();
+		// Track the number of attempted reward conversions.
+		let mut conversion_count: u32 = 0;
 		// We can now make total validator payout:
 		if let Some((imbalance, dest)) =
 			Self::make_payout(&stash, validator_staking_payout + validator_commission_payout)
 		{
-			Self::deposit_event(Event::<T>::Rewarded { stash, dest, amount: imbalance.peek() });
+			let amount = imbalance.peek();
+			Self::deposit_event(Event::<T>::Rewarded {
+				stash: stash.clone(),
+				dest: dest.clone(),
+				amount,
+			});
 			total_imbalance.subsume(imbalance);
+			if Self::maybe_convert_reward(&stash, &dest, amount) {
+				conversion_count += 1;
+			}
 		}
 
 		// Track the number of payout ops to nominators. Note:
@@ -373,20 +384,81 @@ impl<T: Config> Pallet<T> {
 			if let Some((imbalance, dest)) = Self::make_payout(&nominator.who, nominator_reward) {
 				// Note: this logic does not count payouts for `RewardDestination::None`.
 				nominator_payout_count += 1;
+				let amount = imbalance.peek();
 				let e = Event::<T>::Rewarded {
 					stash: nominator.who.clone(),
-					dest,
-					amount: imbalance.peek(),
+					dest: dest.clone(),
+					amount,
 				};
 				Self::deposit_event(e);
 				total_imbalance.subsume(imbalance);
+				if Self::maybe_convert_reward(&nominator.who, &dest, amount) {
+					conversion_count += 1;
+				}
 			}
 		}
 
 		T::Reward::on_unbalanced(total_imbalance);
 		debug_assert!(nominator_payout_count <= T::MaxExposurePageSize::get());
 
-		Ok(Some(T::WeightInfo::payout_stakers_alive_staked(nominator_payout_count)).into())
+		Ok(Some(
+			T::WeightInfo::payout_stakers_alive_staked(nominator_payout_count).saturating_add(
+				T::RewardConverter::weight().saturating_mul(conversion_count.into()),
+			),
+		)
+		.into())
+	}
+
+	/// The maximum weight of paying out a page of stakers, including the conversion of all their
+	/// rewards.
+	pub(crate) fn payout_stakers_max_weight() -> Weight {
+		let max_nominator_payouts = T::MaxExposurePageSize::get();
+		T::WeightInfo::payout_stakers_alive_staked(max_nominator_payouts).saturating_add(
+			T::RewardConverter::weight()
+				.saturating_mul(max_nominator_payouts.saturating_add(1).into()),
+		)
+	}
+
+	/// Convert a reward of `amount` that has just been paid out to `dest`, if `stash` has set a
+	/// [`crate::RewardConversion`].
+	///
+	/// Only rewards paid into the stash itself are converted, since the stash has no say over
+	/// the funds of any other payee. If the conversion fails, the reward is kept in the native
+	/// currency. Returns whether a conversion was attempted.
+	fn maybe_convert_reward(
+		stash: &T::AccountId,
+		dest: &RewardDestination<T::AccountId>,
+		amount: BalanceOf<T>,
+	) -> bool {
+		match dest {
+			RewardDestination::Stash => (),
+			RewardDestination::Account(account) if account == stash => (),
+			// staked rewards are bonded right away, rewards of other accounts are theirs to keep,
+			// and there is nothing to convert otherwise.
+			_ => return false,
+		}
+		let Some(conversion) = RewardConversions::<T>::get(stash) else { return false };
+
+		let min_out: BalanceOf<T> = conversion
+			.min_rate
+			.saturating_mul_int(amount.saturated_into::<u128>())
+			.saturated_into();
+		let converted = T::RewardConverter::swap(stash, &conversion.asset, amount, min_out);
+
+		match converted {
+			Ok(amount_out) => Self::deposit_event(Event::<T>::RewardConverted {
+				stash: stash.clone(),
+				asset: conversion.asset,
+				amount_in: amount,
+				amount_out,
+			}),
+			Err(error) => Self::deposit_event(Event::<T>::RewardConversionFailed {
+				stash: stash.clone(),
+				amount,
+				error,
+			}),
+		}
+		true
 	}
 
 	/// Chill a stash account.
@@ -526,7 +598,10 @@ impl<T: Config> Pallet<T> {
 	/// Start a new era. It does:
 	/// * Increment `active_era.index`,
 	/// * reset `active_era.start`,
-	/// * update `BondedEras` and apply slashes.
+	/// * update `BondedEras`.
+	///
+	/// Slashes that become due in the new era are applied lazily, see
+	/// [`Self::apply_unapplied_slashes`].
 	fn start_era(start_session: SessionIndex) {
 		let active_era = ActiveEra::<T>::mutate(|active_era| {
 			let new_index = active_era.as_ref().map(|info| info.index + 1).unwrap_or(0);
@@ -560,8 +635,6 @@ impl<T: Config> Pallet<T> {
 				}
 			}
 		});
-
-		Self::apply_unapplied_slashes(active_era);
 	}
 
 	/// Compute payout for era.
@@ -637,33 +710,76 @@ impl<T: Config> Pallet<T> {
 	/// Get election result from `T::ElectionProvider`.
 	/// In case election result has more than [`MinimumValidatorCount`] validator trigger a new era.
 	///
+	/// If the election provider works with multiple pages, all pages but the least significant one
+	/// are expected to have been fetched already in [`Self::do_elect_paged`]. The pages that were
+	/// not, if any, are fetched here before the least significant page. The genesis election
+	/// provider is always fetched at once.
+	///
 	/// In case a new era is planned, the new validator set is returned.
 	pub(crate) fn try_trigger_new_era(
 		start_session_index: SessionIndex,
 		is_genesis: bool,
 	) -> Option<BoundedVec<T::AccountId, MaxWinnersOf<T>>> {
-		let election_result: BoundedVec<_, MaxWinnersOf<T>> = if is_genesis {
-			let result = <T::GenesisElectionProvider>::elect().map_err(|e| {
-				log!(warn, "genesis election provider failed due to {:?}", e);
-				Self::deposit_event(Event::StakingElectionFailed);
-			});
+		// whatever happens next, this concludes the ongoing election.
+		let next_page = NextElectionPage::<T>::take()
+			.unwrap_or_else(<T::ElectionProvider as ElectionProviderBase>::msp)
+			.min(<T::ElectionProvider as ElectionProviderBase>::msp());
+
+		let elect_genesis =
+			|page: PageIndex| -> Result<BoundedSupportsOf<T::ElectionProvider>, ()> {
+				<T::GenesisElectionProvider>::elect(page)
+					.map_err(|e| log!(warn, "genesis election provider failed due to {:?}", e))
+					.map(|supports| {
+						supports
+							.into_inner()
+							.try_into()
+							// both bounds checked in integrity test to be equal
+							.defensive_unwrap_or_default()
+					})
+			};
 
-			result
-				.ok()?
-				.into_inner()
-				.try_into()
-				// both bounds checked in integrity test to be equal
-				.defensive_unwrap_or_default()
+		let election_result = if is_genesis {
+			let lsp = <T::GenesisElectionProvider as ElectionProviderBase>::lsp();
+			let msp = <T::GenesisElectionProvider as ElectionProviderBase>::msp();
+			(lsp.saturating_add(1)..=msp)
+				.rev()
+				.try_for_each(|page| {
+					elect_genesis(page).map(|supports| {
+						let exposures = Self::collect_exposures(supports);
+						Self::store_stakers_info_paged(exposures, Self::planning_era());
+					})
+				})
+				.and_then(|_| elect_genesis(lsp))
 		} else {
-			let result = <T::ElectionProvider>::elect().map_err(|e| {
-				log!(warn, "election provider failed due to {:?}", e);
+			let lsp = <T::ElectionProvider as ElectionProviderBase>::lsp();
+			// the election provider might not have been ready early enough for all pages to be
+			// fetched in the blocks leading to the election.
+			(lsp.saturating_add(1)..=next_page)
+				.rev()
+				.for_each(|page| Self::register_weight(Self::do_elect_paged(page)));
+			<T::ElectionProvider>::elect(lsp)
+				.map_err(|e| log!(warn, "election provider failed due to {:?}", e))
+		};
+
+		let election_result = match election_result {
+			Ok(election_result) => election_result,
+			Err(_) => {
+				Self::clear_election_metadata();
 				Self::deposit_event(Event::StakingElectionFailed);
-			});
-			result.ok()?
+				return None
+			},
 		};
 
 		let exposures = Self::collect_exposures(election_result);
-		if (exposures.len() as u32) < Self::minimum_validator_count().max(1) {
+		let elected_count = {
+			let electable_stashes = ElectableStashes::<T>::get();
+			exposures
+				.iter()
+				.filter(|(stash, _)| !electable_stashes.contains(stash))
+				.count()
+				.saturating_add(electable_stashes.len())
+		};
+		if (elected_count as u32) < Self::minimum_validator_count().max(1) {
 			// Session will panic if we ever return an empty validator set, thus max(1) ^^.
 			match CurrentEra::<T>::get() {
 				Some(current_era) if current_era > 0 => log!(
@@ -671,7 +787,7 @@ impl<T: Config> Pallet<T> {
 					"chain does not have enough staking candidates to operate for era {:?} ({} \
 					elected, minimum is {})",
 					CurrentEra::<T>::get().unwrap_or(0),
-					exposures.len(),
+					elected_count,
 					Self::minimum_validator_count(),
 				),
 				None => {
@@ -685,6 +801,7 @@ impl<T: Config> Pallet<T> {
 				_ => (),
 			}
 
+			Self::clear_election_metadata();
 			Self::deposit_event(Event::StakingElectionFailed);
 			return None
 		}
@@ -693,37 +810,123 @@ impl<T: Config> Pallet<T> {
 		Some(Self::trigger_new_era(start_session_index, exposures))
 	}
 
-	/// Process the output of the election.
+	/// Fetch a page of the election result from `T::ElectionProvider`, and store it for the era
+	/// that is being planned.
 	///
-	/// Store staking information for the new planned era
-	pub fn store_stakers_info(
+	/// Returns the weight consumed, excluding the one of the election provider, which is
+	/// self-weighing.
+	pub(crate) fn do_elect_paged(page: PageIndex) -> Weight {
+		match <T::ElectionProvider>::elect(page) {
+			Ok(supports) => {
+				let exposures = Self::collect_exposures(supports);
+				let exposures_count = exposures.len() as u64;
+				Self::store_stakers_info_paged(exposures, Self::planning_era());
+
+				// `CurrentEra`, `ElectableStashes` and `ErasTotalStake`, plus the overview and the
+				// last page of each exposure.
+				T::DbWeight::get().reads_writes(
+					exposures_count.saturating_mul(2).saturating_add(3),
+					exposures_count.saturating_mul(2).saturating_add(2),
+				)
+			},
+			Err(e) => {
+				log!(warn, "election provider failed to provide page {:?} due to {:?}", page, e);
+				Weight::zero()
+			},
+		}
+	}
+
+	/// The number of pages of the election result of `T::ElectionProvider`.
+	pub(crate) fn election_pages() -> PageIndex {
+		<T::ElectionProvider as ElectionProviderBase>::Pages::get()
+	}
+
+	/// The era that the next election is planning, i.e. the one after the current era.
+	pub(crate) fn planning_era() -> EraIndex {
+		CurrentEra::<T>::get().map(|era| era.saturating_add(1)).unwrap_or(0)
+	}
+
+	/// Clear the partial result of an election that did not conclude in a new era.
+	pub(crate) fn clear_election_metadata() {
+		NextElectionPage::<T>::kill();
+		if !ElectableStashes::<T>::take().is_empty() {
+			// the exposures of the pages fetched so far were already stored for the planning era.
+			Self::clear_era_information(Self::planning_era());
+		}
+	}
+
+	/// Process a page of the output of the election.
+	///
+	/// Store the exposures for `era`, merging them with the ones of the previously stored pages,
+	/// and note their stashes in [`ElectableStashes`].
+	pub(crate) fn store_stakers_info_paged(
 		exposures: BoundedVec<
 			(T::AccountId, Exposure<T::AccountId, BalanceOf<T>>),
 			MaxWinnersOf<T>,
 		>,
-		new_planned_era: EraIndex,
-	) -> BoundedVec<T::AccountId, MaxWinnersOf<T>> {
-		// Populate elected stash, stakers, exposures, and the snapshot of validator prefs.
+		era: EraIndex,
+	) {
 		let mut total_stake: BalanceOf<T> = Zero::zero();
-		let mut elected_stashes = Vec::with_capacity(exposures.len());
+		let mut electable_stashes = ElectableStashes::<T>::get();
 
 		exposures.into_iter().for_each(|(stash, exposure)| {
-			// build elected stash
-			elected_stashes.push(stash.clone());
+			// note the elected stash, if not elected in a previous page.
+			if !electable_stashes.contains(&stash) &&
+				electable_stashes.try_push(stash.clone()).is_err()
+			{
+				defensive!("election result cannot have more than `MaxWinners` stashes; qed");
+				return
+			}
 			// accumulate total stake
 			total_stake = total_stake.saturating_add(exposure.total);
 			// store staker exposure for this era
-			EraInfo::<T>::set_exposure(new_planned_era, &stash, exposure);
+			EraInfo::<T>::upsert_exposure(era, &stash, exposure);
 		});
 
-		let elected_stashes: BoundedVec<_, MaxWinnersOf<T>> = elected_stashes
-			.try_into()
-			.expect("elected_stashes.len() always equal to exposures.len(); qed");
+		EraInfo::<T>::set_total_stake(
+			era,
+			ErasTotalStake::<T>::get(era).saturating_add(total_stake),
+		);
+		ElectableStashes::<T>::put(electable_stashes);
+	}
 
-		EraInfo::<T>::set_total_stake(new_planned_era, total_stake);
+	/// Apply the scheduled commission change of the validator `stash`, if it is due by `era`.
+	pub(crate) fn apply_commission_change(stash: &T::AccountId, era: EraIndex) {
+		let Some(change) = CommissionChanges::<T>::get(stash) else { return };
+		if change.era > era {
+			return
+		}
 
-		// Collect the pref of all winners.
+		CommissionChanges::<T>::remove(stash);
+		// the minimum commission might have been raised since the change was scheduled.
+		let commission = change.commission.max(MinCommission::<T>::get());
+		Validators::<T>::mutate_exists(stash, |maybe_prefs| {
+			if let Some(prefs) = maybe_prefs {
+				prefs.commission = commission;
+			}
+		});
+		CommittedCommissions::<T>::insert(stash, commission);
+		Self::deposit_event(Event::<T>::CommissionChanged { stash: stash.clone(), commission });
+	}
+
+	/// Process the output of the election.
+	///
+	/// Store staking information for the new planned era, along with the one of the pages that
+	/// were already processed with [`Self::store_stakers_info_paged`].
+	pub fn store_stakers_info(
+		exposures: BoundedVec<
+			(T::AccountId, Exposure<T::AccountId, BalanceOf<T>>),
+			MaxWinnersOf<T>,
+		>,
+		new_planned_era: EraIndex,
+	) -> BoundedVec<T::AccountId, MaxWinnersOf<T>> {
+		// Populate elected stash, stakers, exposures, and the snapshot of validator prefs.
+		Self::store_stakers_info_paged(exposures, new_planned_era);
+		let elected_stashes = ElectableStashes::<T>::take();
+
+		// Collect the pref of all winners, with the commission changes that are due.
 		for stash in &elected_stashes {
+			Self::apply_commission_change(stash, new_planned_era);
 			let pref = Self::validators(stash);
 			<ErasValidatorPrefs<T>>::insert(&new_planned_era, stash, pref);
 		}
@@ -822,18 +1025,107 @@ impl<T: Config> Pallet<T> {
 		ErasStartSessionIndex::<T>::remove(era_index);
 	}
 
-	/// Apply previously-unapplied slashes on the beginning of a new era, after a delay.
-	fn apply_unapplied_slashes(active_era: EraIndex) {
-		let era_slashes = UnappliedSlashes::<T>::take(&active_era);
-		log!(
-			debug,
-			"found {} slashes scheduled to be executed in era {:?}",
-			era_slashes.len(),
-			active_era,
-		);
-		for slash in era_slashes {
-			let slash_era = active_era.saturating_sub(T::SlashDeferDuration::get());
-			slashing::apply_slash::<T>(slash, slash_era);
+	/// Apply a single page of the previously-unapplied slashes that are due, starting from the
+	/// earliest era.
+	///
+	/// This is called on every block, so that deferred slashes are applied lazily once they
+	/// become due. Returns the consumed weight.
+	pub(crate) fn apply_unapplied_slashes() -> Weight {
+		// reading `ActiveEra` and `UnappliedSlashEras`.
+		let weight = T::DbWeight::get().reads(2);
+
+		let active_era = match Self::active_era() {
+			Some(active_era) => active_era.index,
+			None => return weight,
+		};
+		let era = match UnappliedSlashEras::<T>::get().first().copied() {
+			Some(era) if era <= active_era => era,
+			_ => return weight,
+		};
+
+		match UnappliedSlashes::<T>::iter_key_prefix((era,)).next() {
+			Some(slash_key) => {
+				log!(debug, "applying a page of slash {:?} due in era {:?}", slash_key, era);
+				let _ = Self::do_apply_slash(era, slash_key).defensive();
+				weight.saturating_add(T::WeightInfo::apply_slash(T::MaxExposurePageSize::get()))
+			},
+			None => {
+				defensive!("eras without unapplied slashes are pruned; qed.");
+				Self::prune_unapplied_slash_era(era);
+				weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+			},
+		}
+	}
+
+	/// Apply a single page of a deferred slash that is due in `era`.
+	pub(crate) fn do_apply_slash(
+		era: EraIndex,
+		slash_key: (T::AccountId, Perbill, Page),
+	) -> DispatchResult {
+		let active_era = Self::active_era().map(|a| a.index).unwrap_or_default();
+		ensure!(era <= active_era, Error::<T>::SlashNotDue);
+
+		let (validator, fraction, page) = slash_key;
+		let unapplied = UnappliedSlashes::<T>::take((era, &validator, fraction, page))
+			.ok_or(Error::<T>::InvalidSlashRecord)?;
+
+		let slash_era = era.saturating_sub(T::SlashDeferDuration::get());
+		slashing::apply_slash::<T>(unapplied, slash_era);
+
+		Self::prune_unapplied_slash_era(era);
+		Ok(())
+	}
+
+	/// Queue the pages of a slash to be applied once `era` starts.
+	pub(crate) fn defer_slash(
+		era: EraIndex,
+		validator: &T::AccountId,
+		fraction: Perbill,
+		pages: Vec<UnappliedSlash<T::AccountId, BalanceOf<T>>>,
+	) {
+		for (page, unapplied) in pages.into_iter().enumerate() {
+			UnappliedSlashes::<T>::insert((era, validator, fraction, page as Page), unapplied);
+		}
+
+		UnappliedSlashEras::<T>::mutate(|eras| {
+			if let Err(index) = eras.binary_search(&era) {
+				eras.insert(index, era);
+			}
+		});
+	}
+
+	/// Remove `era` from `UnappliedSlashEras` if it has no unapplied slash left.
+	pub(crate) fn prune_unapplied_slash_era(era: EraIndex) {
+		if UnappliedSlashes::<T>::iter_key_prefix((era,)).next().is_none() {
+			UnappliedSlashEras::<T>::mutate(|eras| eras.retain(|e| *e != era));
+			// only left behind by cancellations that were completed by applying the slash.
+			let _ = CancelledSlashCursor::<T>::clear_prefix((era,), u32::MAX, None);
+		}
+	}
+
+	/// The latest era of which the unlocking chunks can be withdrawn.
+	///
+	/// This is the current era, unless some deferred slashes are not applied yet. Unlocking chunks
+	/// that could still be slashed by any of them are held back until they are applied.
+	///
+	/// This applies to the chunks of all stakers, whether they are exposed to the pending slashes
+	/// or not, since finding the exposed ones would require reading all unapplied slash pages. The
+	/// chunks are held back only for a short while though: pending slashes are applied one page per
+	/// block as soon as they are due, and only the chunks unlocking in the era in which the
+	/// earliest pending slash is due, or later, are held back.
+	pub(crate) fn withdrawable_era(current_era: EraIndex) -> EraIndex {
+		match UnappliedSlashEras::<T>::get().first() {
+			Some(due_era) => {
+				// the era of the offence, given the era in which its slash is due. Any chunk
+				// unlocking `BondingDuration` eras after it could have been exposed in it.
+				let offence_era =
+					due_era.saturating_sub(T::SlashDeferDuration::get()).saturating_sub(1);
+				offence_era
+					.saturating_add(T::BondingDuration::get())
+					.saturating_sub(1)
+					.min(current_era)
+			},
+			None => current_era,
 		}
 	}
 
@@ -884,11 +1176,19 @@ impl<T: Config> Pallet<T> {
 	///
 	/// `maybe_max_len` can imposes a cap on the number of voters returned;
 	///
+	/// `status` is the progress of the paged snapshot: voters are returned from the top of
+	/// `T::VoterList` if it is [`SnapshotStatus::Waiting`], right after the last voter of the
+	/// previous page if it is [`SnapshotStatus::Ongoing`], from the given voter if it is
+	/// [`SnapshotStatus::Resuming`], and none are returned once it is [`SnapshotStatus::Consumed`].
+	///
 	/// Sets `MinimumActiveStake` to the minimum active nominator stake in the returned set of
-	/// nominators.
+	/// nominators, or in all of the pages of the snapshot returned so far.
 	///
 	/// This function is self-weighing as [`DispatchClass::Mandatory`].
-	pub fn get_npos_voters(bounds: DataProviderBounds) -> Vec<VoterOf<Self>> {
+	pub fn get_npos_voters(
+		bounds: DataProviderBounds,
+		status: &SnapshotStatus<T::AccountId>,
+	) -> Vec<VoterOf<Self>> {
 		let mut voters_size_tracker: StaticTracker<Self> = StaticTracker::default();
 
 		let final_predicted_len = {
@@ -906,7 +1206,22 @@ impl<T: Config> Pallet<T> {
 		let mut nominators_taken = 0u32;
 		let mut min_active_stake = u64::MAX;
 
-		let mut sorted_voters = T::VoterList::iter();
+		let mut sorted_voters = match status {
+			SnapshotStatus::Waiting => T::VoterList::iter(),
+			SnapshotStatus::Ongoing(last) => T::VoterList::iter_from(last).unwrap_or_else(|_| {
+				// `Self::move_voter_snapshot_cursor` keeps the cursor in the list.
+				defensive!("last voter of the previous page left the voter list");
+				Box::new(sp_std::iter::empty())
+			}),
+			SnapshotStatus::Resuming(next) => match T::VoterList::iter_from(next) {
+				Ok(rest) => Box::new(sp_std::iter::once(next.clone()).chain(rest)),
+				Err(_) => {
+					defensive!("first voter of the next page left the voter list");
+					Box::new(sp_std::iter::empty())
+				},
+			},
+			SnapshotStatus::Consumed => Box::new(sp_std::iter::empty()),
+		};
 		while all_voters.len() < final_predicted_len as usize &&
 			voters_seen < (NPOS_MAX_ITERATIONS_COEFFICIENT * final_predicted_len as u32)
 		{
@@ -987,7 +1302,14 @@ impl<T: Config> Pallet<T> {
 		let min_active_stake: T::CurrencyBalance =
 			if all_voters.is_empty() { Zero::zero() } else { min_active_stake.into() };
 
-		MinimumActiveStake::<T>::put(min_active_stake);
+		match status {
+			SnapshotStatus::Waiting => MinimumActiveStake::<T>::put(min_active_stake),
+			// later pages can only lower the minimum of the pages returned before.
+			_ if !all_voters.is_empty() => MinimumActiveStake::<T>::mutate(|current| {
+				*current = (*current).min(min_active_stake)
+			}),
+			_ => (),
+		}
 
 		log!(
 			info,
@@ -1045,6 +1367,25 @@ impl<T: Config> Pallet<T> {
 		all_targets
 	}
 
+	/// Move the cursor of an ongoing paged voter snapshot off `who`, which is about to be removed
+	/// from `T::VoterList`, to the voter that follows it.
+	///
+	/// Otherwise, the next page of the snapshot could not know where to continue from.
+	fn move_voter_snapshot_cursor(who: &T::AccountId) {
+		match VoterSnapshotStatus::<T>::get() {
+			SnapshotStatus::Ongoing(cursor) | SnapshotStatus::Resuming(cursor) if cursor == *who => {
+				let status =
+					match T::VoterList::iter_from(who).ok().and_then(|mut rest| rest.next()) {
+						Some(next) => SnapshotStatus::Resuming(next),
+						// all the voters that follow `who` were returned already.
+						None => SnapshotStatus::Consumed,
+					};
+				VoterSnapshotStatus::<T>::put(status);
+			},
+			_ => (),
+		}
+	}
+
 	/// This function will add a nominator to the `Nominators` storage map,
 	/// and `VoterList`.
 	///
@@ -1078,6 +1419,7 @@ impl<T: Config> Pallet<T> {
 	pub fn do_remove_nominator(who: &T::AccountId) -> bool {
 		let outcome = if Nominators::<T>::contains_key(who) {
 			Nominators::<T>::remove(who);
+			Self::move_voter_snapshot_cursor(who);
 			let _ = T::VoterList::on_remove(who).defensive();
 			true
 		} else {
@@ -1105,6 +1447,7 @@ impl<T: Config> Pallet<T> {
 			let _ = T::VoterList::on_insert(who.clone(), Self::weight_of(who))
 				.defensive_unwrap_or_default();
 		}
+		CommittedCommissions::<T>::insert(who, prefs.commission);
 		Validators::<T>::insert(who, prefs);
 
 		debug_assert_eq!(
@@ -1123,6 +1466,7 @@ impl<T: Config> Pallet<T> {
 	pub fn do_remove_validator(who: &T::AccountId) -> bool {
 		let outcome = if Validators::<T>::contains_key(who) {
 			Validators::<T>::remove(who);
+			Self::move_voter_snapshot_cursor(who);
 			let _ = T::VoterList::on_remove(who).defensive();
 			true
 		} else {
@@ -1187,6 +1531,20 @@ impl<T: Config> Pallet<T> {
 	pub fn api_pending_rewards(era: EraIndex, account: T::AccountId) -> bool {
 		EraInfo::<T>::pending_rewards(era, &account)
 	}
+
+	pub fn api_commission_changes(
+		nominator: T::AccountId,
+	) -> Vec<(T::AccountId, Perbill, EraIndex)> {
+		Self::nominators(&nominator)
+			.map(|nominations| nominations.targets.into_inner())
+			.unwrap_or_default()
+			.into_iter()
+			.filter_map(|validator| {
+				CommissionChanges::<T>::get(&validator)
+					.map(|change| (validator, change.commission, change.era))
+			})
+			.collect()
+	}
 }
 
 impl<T: Config> ElectionDataProvider for Pallet<T> {
@@ -1199,19 +1557,42 @@ impl<T: Config> ElectionDataProvider for Pallet<T> {
 		Ok(Self::validator_count())
 	}
 
-	fn electing_voters(bounds: DataProviderBounds) -> data_provider::Result<Vec<VoterOf<Self>>> {
+	fn electing_voters(
+		bounds: DataProviderBounds,
+		page: PageIndex,
+	) -> data_provider::Result<Vec<VoterOf<Self>>> {
+		let status = VoterSnapshotStatus::<T>::get();
+		if let SnapshotStatus::Ongoing(cursor) | SnapshotStatus::Resuming(cursor) = &status {
+			if !T::VoterList::contains(cursor) {
+				defensive!("the cursor of the voter snapshot left the voter list");
+				return Err("the cursor of the voter snapshot left the voter list")
+			}
+		}
+
 		// This can never fail -- if `maybe_max_len` is `Some(_)` we handle it.
-		let voters = Self::get_npos_voters(bounds);
+		let voters = Self::get_npos_voters(bounds, &status);
 
 		debug_assert!(!bounds.exhausted(
 			SizeBound(voters.encoded_size() as u32).into(),
 			CountBound(voters.len() as u32).into()
 		));
 
+		// the least significant page concludes the snapshot, the next page starts a new one.
+		match (page, voters.last()) {
+			(0, _) => VoterSnapshotStatus::<T>::kill(),
+			(_, Some((last, _, _))) =>
+				VoterSnapshotStatus::<T>::put(SnapshotStatus::Ongoing(last.clone())),
+			(_, None) => VoterSnapshotStatus::<T>::put(SnapshotStatus::Consumed),
+		}
+
 		Ok(voters)
 	}
 
-	fn electable_targets(bounds: DataProviderBounds) -> data_provider::Result<Vec<T::AccountId>> {
+	/// Targets always fit in a single page, thus the page index is ignored.
+	fn electable_targets(
+		bounds: DataProviderBounds,
+		_page: PageIndex,
+	) -> data_provider::Result<Vec<T::AccountId>> {
 		let targets = Self::get_npos_targets(bounds);
 
 		// We can't handle this case yet -- return an error. WIP to improve handling this case in
@@ -1497,7 +1878,7 @@ where
 				continue
 			}
 
-			let unapplied = slashing::compute_slash::<T>(slashing::SlashParams {
+			let mut unapplied = slashing::compute_slash::<T>(slashing::SlashParams {
 				stash,
 				slash: *slash_fraction,
 				exposure,
@@ -1513,8 +1894,10 @@ where
 				slash_era,
 			});
 
-			if let Some(mut unapplied) = unapplied {
-				let nominators_len = unapplied.others.len() as u64;
+			if !unapplied.is_empty() {
+				let nominators_len =
+					unapplied.iter().map(|page| page.others.len() as u64).sum::<u64>();
+				let pages_len = unapplied.len() as u64;
 				let reporters_len = details.reporters.len() as u64;
 
 				{
@@ -1522,16 +1905,20 @@ where
 					let rw = upper_bound + nominators_len * upper_bound;
 					add_db_reads_writes(rw, rw);
 				}
-				unapplied.reporters = details.reporters.clone();
+				unapplied.iter_mut().for_each(|page| page.reporters = details.reporters.clone());
 				if slash_defer_duration == 0 {
 					// Apply right away.
-					slashing::apply_slash::<T>(unapplied, slash_era);
+					for page in unapplied {
+						slashing::apply_slash::<T>(page, slash_era);
+					}
 					{
 						let slash_cost = (6, 5);
 						let reward_cost = (2, 2);
 						add_db_reads_writes(
-							(1 + nominators_len) * slash_cost.0 + reward_cost.0 * reporters_len,
-							(1 + nominators_len) * slash_cost.1 + reward_cost.1 * reporters_len,
+							(1 + nominators_len) * slash_cost.0 +
+								reward_cost.0 * reporters_len * pages_len,
+							(1 + nominators_len) * slash_cost.1 +
+								reward_cost.1 * reporters_len * pages_len,
 						);
 					}
 				} else {
@@ -1544,11 +1931,13 @@ where
 						active_era,
 						slash_era + slash_defer_duration + 1,
 					);
-					UnappliedSlashes::<T>::mutate(
+					Self::defer_slash(
 						slash_era.saturating_add(slash_defer_duration).saturating_add(One::one()),
-						move |for_later| for_later.push(unapplied),
+						stash,
+						*slash_fraction,
+						unapplied,
 					);
-					add_db_reads_writes(1, 1);
+					add_db_reads_writes(1, 1 + pages_len);
 				}
 			} else {
 				add_db_reads_writes(4 /* fetch_spans */, 5 /* kick_out_if_recent */)
@@ -1979,6 +2368,8 @@ impl<T: Config> Pallet<T> {
 		Self::check_exposures()?;
 		Self::check_paged_exposures()?;
 		Self::check_count()?;
+		Self::check_unapplied_slashes()?;
+		Self::check_commission_changes()?;
 		Self::ensure_disabled_validators_sorted()
 	}
 
@@ -2058,6 +2449,23 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Invariants:
+	/// * Only bonded stashes have a scheduled commission change.
+	/// * Only bonded stashes have a committed commission.
+	fn check_commission_changes() -> Result<(), TryRuntimeError> {
+		for (stash, _) in CommissionChanges::<T>::iter() {
+			ensure!(
+				Bonded::<T>::contains_key(&stash),
+				"commission change scheduled for an unbonded stash"
+			);
+		}
+		for (stash, _) in CommittedCommissions::<T>::iter() {
+			ensure!(Bonded::<T>::contains_key(&stash), "commission committed by an unbonded stash");
+		}
+
+		Ok(())
+	}
+
 	/// Invariants:
 	/// * Number of voters in `VoterList` match that of the number of Nominators and Validators in
 	/// the system (validator is both voter and target).
@@ -2301,4 +2709,25 @@ impl<T: Config> Pallet<T> {
 		);
 		Ok(())
 	}
+
+	/// Invariants:
+	/// * `UnappliedSlashEras` is sorted and has no duplicates.
+	/// * Each era in `UnappliedSlashEras` has at least one unapplied slash page and vice versa.
+	fn check_unapplied_slashes() -> Result<(), TryRuntimeError> {
+		let eras = UnappliedSlashEras::<T>::get();
+		ensure!(
+			eras.windows(2).all(|pair| pair[0] < pair[1]),
+			"UnappliedSlashEras is not sorted and unique"
+		);
+		ensure!(
+			eras.iter()
+				.all(|era| UnappliedSlashes::<T>::iter_key_prefix((*era,)).next().is_some()),
+			"UnappliedSlashEras contains an era without unapplied slashes"
+		);
+		ensure!(
+			UnappliedSlashes::<T>::iter_keys().all(|(era, _, _, _)| eras.contains(&era)),
+			"UnappliedSlashes contains a slash of an untracked era"
+		);
+		Ok(())
+	}
 }
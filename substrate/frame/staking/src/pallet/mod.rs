@@ -19,7 +19,7 @@
 
 use codec::Codec;
 use frame_election_provider_support::{
-	ElectionProvider, ElectionProviderBase, SortedListProvider, VoteWeight,
+	ElectionProvider, ElectionProviderBase, PageIndex, SortedListProvider, VoteWeight,
 };
 use frame_support::{
 	pallet_prelude::*,
@@ -33,7 +33,7 @@ use frame_support::{
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
 use sp_runtime::{
 	traits::{SaturatedConversion, StaticLookup, Zero},
-	ArithmeticError, Perbill, Percent,
+	ArithmeticError, Perbill, Percent, Saturating,
 };
 
 use sp_staking::{
@@ -47,11 +47,12 @@ mod impls;
 pub use impls::*;
 
 use crate::{
-	slashing, weights::WeightInfo, AccountIdLookupOf, ActiveEraInfo, BalanceOf, DisablingStrategy,
-	EraPayout, EraRewardPoints, Exposure, ExposurePage, Forcing, LedgerIntegrityState,
-	MaxNominationsOf, NegativeImbalanceOf, Nominations, NominationsQuota, PositiveImbalanceOf,
-	RewardDestination, SessionInterface, StakingLedger, UnappliedSlash, UnlockChunk,
-	ValidatorPrefs,
+	slashing, weights::WeightInfo, AccountIdLookupOf, ActiveEraInfo, BalanceOf, CommissionChange,
+	DisablingStrategy, EraPayout, EraRewardPoints, Exposure, ExposurePage, Forcing,
+	LedgerIntegrityState, MaxNominationsOf, MaxWinnersOf, NegativeImbalanceOf, Nominations,
+	NominationsQuota, PositiveImbalanceOf, RewardAssetOf, RewardConversion, RewardConverter,
+	RewardDestination, SessionInterface, SnapshotStatus, StakingLedger, UnappliedSlash,
+	UnlockChunk, ValidatorPrefs,
 };
 
 // The speculative number of spans are used as an input of the weight annotation of
@@ -68,7 +69,7 @@ pub mod pallet {
 	use super::*;
 
 	/// The in-code storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(15);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(17);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -173,6 +174,12 @@ pub mod pallet {
 		/// issuance.
 		type Reward: OnUnbalanced<PositiveImbalanceOf<Self>>;
 
+		/// Handler for converting rewards into other assets when they are paid out, for stashes
+		/// that have set a [`RewardConversion`].
+		///
+		/// Use `()` to only pay out rewards in the native currency.
+		type RewardConverter: RewardConverter<Self::AccountId, BalanceOf<Self>>;
+
 		/// Number of sessions per era.
 		#[pallet::constant]
 		type SessionsPerEra: Get<SessionIndex>;
@@ -188,9 +195,19 @@ pub mod pallet {
 		#[pallet::constant]
 		type SlashDeferDuration: Get<EraIndex>;
 
+		/// Number of eras that a commission change of a validator is announced in advance.
+		///
+		/// A change scheduled while `e` is the current era is applied from era
+		/// `e + 1 + CommissionChangeDelay`. If this is not zero, validators cannot increase their
+		/// commission with `validate` anymore, but need to schedule the increase with
+		/// `schedule_commission_change`.
+		#[pallet::constant]
+		type CommissionChangeDelay: Get<EraIndex>;
+
 		/// The origin which can manage less critical staking parameters that does not require root.
 		///
-		/// Supported actions: (1) cancel deferred slash, (2) set minimum commission.
+		/// Supported actions: (1) cancel deferred slash, (2) set minimum commission, (3) set
+		/// maximum commission change rate.
 		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
 		/// Interface for interacting with a session pallet.
@@ -327,6 +344,12 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type MinCommission<T: Config> = StorageValue<_, Perbill, ValueQuery>;
 
+	/// The maximum increase of commission per era that validators can schedule.
+	///
+	/// If set to `None`, no limit exists.
+	#[pallet::storage]
+	pub type MaxCommissionChangeRate<T: Config> = StorageValue<_, Perbill, OptionQuery>;
+
 	/// Map from all (unlocked) "controller" accounts to the info regarding the staking.
 	///
 	/// Note: All the reads and mutations to this storage *MUST* be done through the methods exposed
@@ -341,6 +364,14 @@ pub mod pallet {
 	pub type Payee<T: Config> =
 		StorageMap<_, Twox64Concat, T::AccountId, RewardDestination<T::AccountId>, OptionQuery>;
 
+	/// The asset that the rewards of a stash are converted into when they are paid out. Keyed by
+	/// stash.
+	///
+	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub type RewardConversions<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, RewardConversion<RewardAssetOf<T>>, OptionQuery>;
+
 	/// The map from (wannabe) validator stash key to the preferences of that validator.
 	///
 	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
@@ -349,6 +380,22 @@ pub mod pallet {
 	pub type Validators<T: Config> =
 		CountedStorageMap<_, Twox64Concat, T::AccountId, ValidatorPrefs, ValueQuery>;
 
+	/// The commission changes that validators have scheduled, and that are not yet in effect.
+	/// Keyed by stash.
+	///
+	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub type CommissionChanges<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, CommissionChange, OptionQuery>;
+
+	/// The last commission that each validator stash has committed to, kept while the stash is
+	/// chilled so that increasing it still has to be scheduled. Keyed by stash.
+	///
+	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub type CommittedCommissions<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, Perbill, OptionQuery>;
+
 	/// The maximum validator count before we stop allowing new validators to join.
 	///
 	/// When this value is not set, no limits are enforced.
@@ -592,15 +639,48 @@ pub mod pallet {
 	#[pallet::getter(fn canceled_payout)]
 	pub type CanceledSlashPayout<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
-	/// All unapplied slashes that are queued for later.
+	/// All unapplied slash pages that are queued for later.
+	///
+	/// This is keyed first by the era in which the slash becomes due, then by the offending
+	/// validator, the slash fraction and finally the page of the validator's exposure.
 	#[pallet::storage]
 	#[pallet::unbounded]
-	pub type UnappliedSlashes<T: Config> = StorageMap<
+	pub type UnappliedSlashes<T: Config> = StorageNMap<
 		_,
-		Twox64Concat,
-		EraIndex,
-		Vec<UnappliedSlash<T::AccountId, BalanceOf<T>>>,
-		ValueQuery,
+		(
+			NMapKey<Twox64Concat, EraIndex>,
+			NMapKey<Twox64Concat, T::AccountId>,
+			NMapKey<Twox64Concat, Perbill>,
+			NMapKey<Twox64Concat, Page>,
+		),
+		UnappliedSlash<T::AccountId, BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	/// Eras in which at least one page of `UnappliedSlashes` is due, sorted in ascending order.
+	///
+	/// Pages are applied lazily, one per block, starting from the earliest era. Unbonded funds
+	/// that could still be slashed by any of these pages cannot be withdrawn, by any staker.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type UnappliedSlashEras<T: Config> = StorageValue<_, Vec<EraIndex>, ValueQuery>;
+
+	/// Where to resume the cancellation of a deferred slash whose pages could not all be removed
+	/// within the page limit of a single `cancel_deferred_slash` call.
+	///
+	/// This is keyed by the era in which the slash is due, the offending validator and the slash
+	/// fraction.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type CancelledSlashCursor<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, EraIndex>,
+			NMapKey<Twox64Concat, T::AccountId>,
+			NMapKey<Twox64Concat, Perbill>,
+		),
+		Vec<u8>,
+		OptionQuery,
 	>;
 
 	/// A mapping from still-bonded eras to the first session index of that era.
@@ -671,6 +751,30 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type ChillThreshold<T: Config> = StorageValue<_, Percent, OptionQuery>;
 
+	/// Progress of the paged voter snapshot that is being handed to the election provider.
+	///
+	/// Only relevant if the election provider works with more than one page.
+	#[pallet::storage]
+	pub(crate) type VoterSnapshotStatus<T: Config> =
+		StorageValue<_, SnapshotStatus<T::AccountId>, ValueQuery>;
+
+	/// The next page of the election result to fetch from the election provider, if an election
+	/// is ongoing.
+	///
+	/// Once it is the least significant page, all other pages have been fetched, and the least
+	/// significant one is fetched when the new era is planned.
+	#[pallet::storage]
+	pub(crate) type NextElectionPage<T: Config> = StorageValue<_, PageIndex, OptionQuery>;
+
+	/// The stashes elected in the pages of the election result fetched so far, in the order they
+	/// were elected.
+	///
+	/// Their exposures are already stored for the era being planned, and they are drained once the
+	/// era is planned.
+	#[pallet::storage]
+	pub(crate) type ElectableStashes<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, MaxWinnersOf<T>>, ValueQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
@@ -799,6 +903,25 @@ pub mod pallet {
 		ForceEra { mode: Forcing },
 		/// Report of a controller batch deprecation.
 		ControllerBatchDeprecated { failures: u32 },
+		/// A stash has set how its rewards are converted into another asset.
+		RewardConversionSet {
+			stash: T::AccountId,
+			conversion: Option<RewardConversion<RewardAssetOf<T>>>,
+		},
+		/// A reward paid out to `stash` has been converted into `asset`.
+		RewardConverted {
+			stash: T::AccountId,
+			asset: RewardAssetOf<T>,
+			amount_in: BalanceOf<T>,
+			amount_out: BalanceOf<T>,
+		},
+		/// A reward paid out to `stash` could not be converted, and has been kept in the native
+		/// currency.
+		RewardConversionFailed { stash: T::AccountId, amount: BalanceOf<T>, error: DispatchError },
+		/// A validator has scheduled a change of their commission.
+		CommissionChangeScheduled { stash: T::AccountId, change: CommissionChange },
+		/// A scheduled commission change of a validator has taken effect.
+		CommissionChanged { stash: T::AccountId, commission: Perbill },
 	}
 
 	#[pallet::error]
@@ -868,13 +991,54 @@ pub mod pallet {
 		RewardDestinationRestricted,
 		/// Not enough funds available to withdraw.
 		NotEnoughFunds,
+		/// No unapplied slash exists for the given era and key.
+		InvalidSlashRecord,
+		/// The slash is deferred and cannot be applied yet.
+		SlashNotDue,
+		/// Rewards cannot be converted into the given asset.
+		RewardConversionUnavailable,
+		/// The minimum rate of a reward conversion must be positive.
+		ZeroRewardConversionRate,
+		/// Commission increases of validators must be scheduled with `schedule_commission_change`.
+		CommissionIncreaseNotScheduled,
+		/// The commission increase exceeds `MaxCommissionChangeRate`.
+		CommissionChangeRateExceeded,
+		/// The account is not a validator.
+		NotValidator,
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
-			// just return the weight of the on_finalize.
-			T::DbWeight::get().reads(1)
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			// the weight of the on_finalize, and of checking whether a page of the election result
+			// should be fetched, including whether the election provider is ready.
+			let weight = T::DbWeight::get().reads(3);
+
+			// apply a single page of the slashes that are due, if any.
+			let weight = weight.saturating_add(Self::apply_unapplied_slashes());
+
+			// all the pages but the least significant one are fetched in the blocks leading to the
+			// election, once the election provider has gathered its input. The least significant
+			// page, and any page that could not be fetched in time, are fetched when the new era
+			// is planned.
+			let lsp = <T::ElectionProvider as ElectionProviderBase>::lsp();
+			let page = match NextElectionPage::<T>::get() {
+				Some(page) => (page > lsp).then_some(page),
+				None if Self::election_pages() > 1 &&
+					now >= <Self as ElectionDataProvider>::next_election_prediction(now)
+						.saturating_sub(Self::election_pages().saturating_sub(1).into()) &&
+					<T::ElectionProvider as ElectionProvider>::ready() =>
+					Some(<T::ElectionProvider as ElectionProviderBase>::msp()),
+				None => None,
+			};
+
+			match page {
+				Some(page) => {
+					NextElectionPage::<T>::put(page.saturating_sub(1));
+					weight.saturating_add(Self::do_elect_paged(page))
+				},
+				None => weight,
+			}
 		}
 
 		fn on_finalize(_n: BlockNumberFor<T>) {
@@ -906,6 +1070,10 @@ pub mod pallet {
 					<T::GenesisElectionProvider as ElectionProviderBase>::MaxWinners::get()
 			);
 
+			// and that both election providers work with at least one page.
+			assert!(!<T::ElectionProvider as ElectionProviderBase>::Pages::get().is_zero());
+			assert!(!<T::GenesisElectionProvider as ElectionProviderBase>::Pages::get().is_zero());
+
 			assert!(
 				T::SlashDeferDuration::get() < T::BondingDuration::get() || T::BondingDuration::get() == 0,
 				"As per documentation, slash defer duration ({}) should be less than bonding duration ({}).",
@@ -1147,9 +1315,17 @@ pub mod pallet {
 		///
 		/// Effects will be felt at the beginning of the next era.
 		///
+		/// Unless [`Config::CommissionChangeDelay`] is zero, a validator cannot increase their
+		/// commission beyond the one in [`CommittedCommissions`] with this call, even after
+		/// chilling, see [`Call::schedule_commission_change`].
+		///
 		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::validate())]
+		// `CommissionChanges` and `CommittedCommissions` are accessed on top of the benchmarked
+		// storage accesses.
+		#[pallet::weight(
+			T::WeightInfo::validate().saturating_add(T::DbWeight::get().reads_writes(2, 2))
+		)]
 		pub fn validate(origin: OriginFor<T>, prefs: ValidatorPrefs) -> DispatchResult {
 			let controller = ensure_signed(origin)?;
 
@@ -1161,6 +1337,17 @@ pub mod pallet {
 			// ensure their commission is correct.
 			ensure!(prefs.commission >= MinCommission::<T>::get(), Error::<T>::CommissionTooLow);
 
+			// nominators must be notified of commission increases ahead of time, including the
+			// ones of validators that have chilled in the meantime.
+			Self::apply_commission_change(stash, CurrentEra::<T>::get().unwrap_or(0));
+			if let Some(committed) = CommittedCommissions::<T>::get(stash) {
+				ensure!(
+					T::CommissionChangeDelay::get().is_zero() ||
+						prefs.commission <= committed.max(MinCommission::<T>::get()),
+					Error::<T>::CommissionIncreaseNotScheduled
+				);
+			}
+
 			// Only check limits if they are not already a validator.
 			if !Validators::<T>::contains_key(stash) {
 				// If this error is reached, we need to adjust the `MinValidatorBond` and start
@@ -1516,30 +1703,57 @@ pub mod pallet {
 		///
 		/// Can be called by the `T::AdminOrigin`.
 		///
-		/// Parameters: era and indices of the slashes for that era to kill.
+		/// Parameters: era in which the slashes are due, the offending validators together with
+		/// the fraction of each slash to cancel, and the maximum number of slash pages to remove.
+		/// The pages of a slash that are not applied yet are cancelled up to `page_limit` pages in
+		/// total. Any pages left over are cancelled by calling this again with the same slash.
 		#[pallet::call_index(17)]
-		#[pallet::weight(T::WeightInfo::cancel_deferred_slash(slash_indices.len() as u32))]
+		#[pallet::weight(
+			T::WeightInfo::cancel_deferred_slash(validator_slashes.len() as u32, *page_limit)
+		)]
 		pub fn cancel_deferred_slash(
 			origin: OriginFor<T>,
 			era: EraIndex,
-			slash_indices: Vec<u32>,
-		) -> DispatchResult {
+			validator_slashes: Vec<(T::AccountId, Perbill)>,
+			page_limit: u32,
+		) -> DispatchResultWithPostInfo {
 			T::AdminOrigin::ensure_origin(origin)?;
 
-			ensure!(!slash_indices.is_empty(), Error::<T>::EmptyTargets);
-			ensure!(is_sorted_and_unique(&slash_indices), Error::<T>::NotSortedAndUnique);
+			ensure!(!validator_slashes.is_empty(), Error::<T>::EmptyTargets);
 
-			let mut unapplied = UnappliedSlashes::<T>::get(&era);
-			let last_item = slash_indices[slash_indices.len() - 1];
-			ensure!((last_item as usize) < unapplied.len(), Error::<T>::InvalidSlashIndex);
+			let slashes = validator_slashes.len() as u32;
+			let mut remaining = page_limit;
+			for (validator, fraction) in validator_slashes {
+				ensure!(
+					UnappliedSlashes::<T>::iter_key_prefix((era, &validator, fraction))
+						.next()
+						.is_some(),
+					Error::<T>::InvalidSlashRecord
+				);
+				if remaining.is_zero() {
+					continue
+				}
+
+				// resume from where a previous cancellation of this slash stopped, if any.
+				let cursor = CancelledSlashCursor::<T>::take((era, &validator, fraction));
+				let result = UnappliedSlashes::<T>::clear_prefix(
+					(era, &validator, fraction),
+					remaining,
+					cursor.as_deref(),
+				);
+				remaining.saturating_reduce(result.loops);
 
-			for (removed, index) in slash_indices.into_iter().enumerate() {
-				let index = (index as usize) - removed;
-				unapplied.remove(index);
+				if let Some(cursor) = result.maybe_cursor {
+					CancelledSlashCursor::<T>::insert((era, &validator, fraction), cursor);
+				}
 			}
 
-			UnappliedSlashes::<T>::insert(&era, &unapplied);
-			Ok(())
+			Self::prune_unapplied_slash_era(era);
+			Ok(Some(T::WeightInfo::cancel_deferred_slash(
+				slashes,
+				page_limit.saturating_sub(remaining),
+			))
+			.into())
 		}
 
 		/// Pay out next page of the stakers behind a validator for the given era.
@@ -1556,7 +1770,7 @@ pub mod pallet {
 		///
 		/// If all pages are claimed, it returns an error `InvalidPage`.
 		#[pallet::call_index(18)]
-		#[pallet::weight(T::WeightInfo::payout_stakers_alive_staked(T::MaxExposurePageSize::get()))]
+		#[pallet::weight(Pallet::<T>::payout_stakers_max_weight())]
 		pub fn payout_stakers(
 			origin: OriginFor<T>,
 			validator_stash: T::AccountId,
@@ -1884,7 +2098,7 @@ pub mod pallet {
 		/// and so it should not be assumed the highest staker would be on the topmost page and vice
 		/// versa. If rewards are not claimed in [`Config::HistoryDepth`] eras, they are lost.
 		#[pallet::call_index(26)]
-		#[pallet::weight(T::WeightInfo::payout_stakers_alive_staked(T::MaxExposurePageSize::get()))]
+		#[pallet::weight(Pallet::<T>::payout_stakers_max_weight())]
 		pub fn payout_stakers_by_page(
 			origin: OriginFor<T>,
 			validator_stash: T::AccountId,
@@ -2073,10 +2287,124 @@ pub mod pallet {
 			);
 			Ok(())
 		}
-	}
-}
 
-/// Check that list is sorted and has no duplicates.
-fn is_sorted_and_unique(list: &[u32]) -> bool {
-	list.windows(2).all(|w| w[0] < w[1])
+		/// Apply a page of a deferred slash that is due but not applied yet.
+		///
+		/// Due slashes are applied lazily, one page per block. Any account can call this function
+		/// to apply a due page earlier, in which case the fee is refunded.
+		///
+		/// Parameters: era in which the slash is due, and the key of the slash page made of the
+		/// offending validator, the slash fraction and the page of the validator's exposure.
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::apply_slash(T::MaxExposurePageSize::get()))]
+		pub fn apply_slash(
+			origin: OriginFor<T>,
+			slash_era: EraIndex,
+			slash_key: (T::AccountId, Perbill, Page),
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			Self::do_apply_slash(slash_era, slash_key)?;
+			Ok(Pays::No.into())
+		}
+
+		/// (Re-)set the asset that the rewards of a stash are converted into when they are paid
+		/// out, or remove the conversion with `None`.
+		///
+		/// Rewards are paid out in the native currency as usual, and then swapped with
+		/// [`Config::RewardConverter`] in the stash. If the swap fails, or would yield less than
+		/// `min_rate` of the asset per unit of the reward, the reward is kept in the native
+		/// currency.
+		///
+		/// The conversion only applies to rewards paid into the stash, and is ignored for any
+		/// other [`RewardDestination`].
+		///
+		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
+		#[pallet::call_index(31)]
+		#[pallet::weight(
+			T::WeightInfo::set_reward_conversion().saturating_add(T::RewardConverter::weight())
+		)]
+		pub fn set_reward_conversion(
+			origin: OriginFor<T>,
+			conversion: Option<RewardConversion<RewardAssetOf<T>>>,
+		) -> DispatchResult {
+			let controller = ensure_signed(origin)?;
+			let ledger = Self::ledger(Controller(controller))?;
+			let stash = ledger.stash.clone();
+
+			if let Some(ref conversion) = conversion {
+				ensure!(
+					T::RewardConverter::can_convert(&conversion.asset),
+					Error::<T>::RewardConversionUnavailable
+				);
+				ensure!(!conversion.min_rate.is_zero(), Error::<T>::ZeroRewardConversionRate);
+			}
+
+			let _ = ledger
+				.set_reward_conversion(conversion.clone())
+				.defensive_proof("ledger was retrieved from storage, thus its bonded; qed.")?;
+
+			Self::deposit_event(Event::<T>::RewardConversionSet { stash, conversion });
+			Ok(())
+		}
+
+		/// Schedule a change of the commission of the validator of the origin controller.
+		///
+		/// The change is applied from era `current_era + 1 + CommissionChangeDelay`, and replaces
+		/// any change that is already scheduled. Increases of the commission are limited to
+		/// `MaxCommissionChangeRate` for each era that the change is announced in advance.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
+		#[pallet::call_index(32)]
+		// `CommittedCommissions` is read on top of the estimated storage accesses.
+		#[pallet::weight(
+			T::WeightInfo::schedule_commission_change().saturating_add(T::DbWeight::get().reads(1))
+		)]
+		pub fn schedule_commission_change(
+			origin: OriginFor<T>,
+			commission: Perbill,
+		) -> DispatchResult {
+			let controller = ensure_signed(origin)?;
+			let ledger = Self::ledger(Controller(controller))?;
+			let stash = ledger.stash;
+
+			ensure!(Validators::<T>::contains_key(&stash), Error::<T>::NotValidator);
+			ensure!(commission >= MinCommission::<T>::get(), Error::<T>::CommissionTooLow);
+
+			let delay = T::CommissionChangeDelay::get();
+			if let Some(rate) = MaxCommissionChangeRate::<T>::get() {
+				let max_increase =
+					Perbill::from_parts(rate.deconstruct().saturating_mul(delay.max(1)));
+				let current = CommittedCommissions::<T>::get(&stash)
+					.unwrap_or_else(|| Self::validators(&stash).commission);
+				ensure!(
+					commission.saturating_sub(current) <= max_increase,
+					Error::<T>::CommissionChangeRateExceeded
+				);
+			}
+
+			let era = CurrentEra::<T>::get().unwrap_or(0).saturating_add(1).saturating_add(delay);
+			let change = CommissionChange { commission, era };
+			CommissionChanges::<T>::insert(&stash, &change);
+
+			Self::deposit_event(Event::<T>::CommissionChangeScheduled { stash, change });
+			Ok(())
+		}
+
+		/// Sets the maximum increase of commission per era that validators can schedule, or
+		/// removes the limit with `None`.
+		///
+		/// This call has lower privilege requirements than `set_staking_config` and can be called
+		/// by the `T::AdminOrigin`. Root can always call this.
+		#[pallet::call_index(33)]
+		#[pallet::weight(T::WeightInfo::set_max_commission_change_rate())]
+		pub fn set_max_commission_change_rate(
+			origin: OriginFor<T>,
+			new: Option<Perbill>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			MaxCommissionChangeRate::<T>::set(new);
+			Ok(())
+		}
+	}
 }
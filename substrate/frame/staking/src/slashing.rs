@@ -50,9 +50,9 @@
 //! Based on research at <https://research.web3.foundation/en/latest/polkadot/slashing/npos.html>
 
 use crate::{
-	BalanceOf, Config, DisabledValidators, DisablingStrategy, Error, Exposure, NegativeImbalanceOf,
-	NominatorSlashInEra, Pallet, Perbill, SessionInterface, SpanSlash, UnappliedSlash,
-	ValidatorSlashInEra,
+	BalanceOf, Config, DisabledValidators, DisablingStrategy, Error, Exposure, IndividualExposure,
+	NegativeImbalanceOf, NominatorSlashInEra, Pallet, Perbill, SessionInterface, SpanSlash,
+	UnappliedSlash, ValidatorSlashInEra,
 };
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
@@ -222,15 +222,19 @@ pub(crate) struct SlashParams<'a, T: 'a + Config> {
 	pub(crate) reward_proportion: Perbill,
 }
 
-/// Computes a slash of a validator and nominators. It returns an unapplied
-/// record to be applied at some later point. Slashing metadata is updated in storage,
-/// since unapplied records are only rarely intended to be dropped.
+/// Computes a slash of a validator and nominators. It returns the unapplied records to be applied
+/// at some later point, one per page of at most [`Config::MaxExposurePageSize`] nominators, so
+/// that each page can be applied independently. Slashing metadata is updated in storage, since
+/// unapplied records are only rarely intended to be dropped.
 ///
-/// The pending slash record returned does not have initialized reporters. Those have
+/// The validator's own slash is always part of the first page. An empty list is returned if there
+/// is nothing to slash.
+///
+/// The pending slash records returned do not have initialized reporters. Those have
 /// to be set at a higher level, if any.
 pub(crate) fn compute_slash<T: Config>(
 	params: SlashParams<T>,
-) -> Option<UnappliedSlash<T::AccountId, BalanceOf<T>>> {
+) -> Vec<UnappliedSlash<T::AccountId, BalanceOf<T>>> {
 	let mut reward_payout = Zero::zero();
 	let mut val_slashed = Zero::zero();
 
@@ -240,7 +244,7 @@ pub(crate) fn compute_slash<T: Config>(
 		// kick out the validator even if they won't be slashed,
 		// as long as the misbehavior is from their most recent slashing span.
 		kick_out_if_recent::<T>(params);
-		return None
+		return Vec::new()
 	}
 
 	let prior_slash_p = ValidatorSlashInEra::<T>::get(&params.slash_era, params.stash)
@@ -262,7 +266,7 @@ pub(crate) fn compute_slash<T: Config>(
 		// pays out some reward even if the latest report is not max-in-era.
 		// we opt to avoid the nominator lookups and edits and leave more rewards
 		// for more drastic misbehavior.
-		return None
+		return Vec::new()
 	}
 
 	// apply slash to validator.
@@ -286,16 +290,37 @@ pub(crate) fn compute_slash<T: Config>(
 
 	add_offending_validator::<T>(&params);
 
-	let mut nominators_slashed = Vec::new();
-	reward_payout += slash_nominators::<T>(params.clone(), prior_slash_p, &mut nominators_slashed);
+	let page_size = T::MaxExposurePageSize::get().max(1) as usize;
+	let mut pages = params.exposure.others.chunks(page_size).collect::<Vec<_>>();
+	if pages.is_empty() {
+		// the validator's own slash still needs a page.
+		pages.push(&[]);
+	}
 
-	Some(UnappliedSlash {
-		validator: params.stash.clone(),
-		own: val_slashed,
-		others: nominators_slashed,
-		reporters: Vec::new(),
-		payout: reward_payout,
-	})
+	pages
+		.into_iter()
+		.enumerate()
+		.map(|(page, nominators)| {
+			let (own, mut payout) =
+				if page == 0 { (val_slashed, reward_payout) } else { (Zero::zero(), Zero::zero()) };
+
+			let mut nominators_slashed = Vec::new();
+			payout += slash_nominators::<T>(
+				params.clone(),
+				prior_slash_p,
+				nominators,
+				&mut nominators_slashed,
+			);
+
+			UnappliedSlash {
+				validator: params.stash.clone(),
+				own,
+				others: nominators_slashed,
+				reporters: Vec::new(),
+				payout,
+			}
+		})
+		.collect()
 }
 
 // doesn't apply any slash, but kicks out the validator if the misbehavior is from
@@ -340,18 +365,20 @@ fn add_offending_validator<T: Config>(params: &SlashParams<T>) {
 	debug_assert!(DisabledValidators::<T>::get().windows(2).all(|pair| pair[0] < pair[1]));
 }
 
-/// Slash nominators. Accepts general parameters and the prior slash percentage of the validator.
+/// Slash a page of nominators. Accepts general parameters and the prior slash percentage of the
+/// validator.
 ///
 /// Returns the amount of reward to pay out.
 fn slash_nominators<T: Config>(
 	params: SlashParams<T>,
 	prior_slash_p: Perbill,
+	nominators: &[IndividualExposure<T::AccountId, BalanceOf<T>>],
 	nominators_slashed: &mut Vec<(T::AccountId, BalanceOf<T>)>,
 ) -> BalanceOf<T> {
 	let mut reward_payout = Zero::zero();
 
-	nominators_slashed.reserve(params.exposure.others.len());
-	for nominator in &params.exposure.others {
+	nominators_slashed.reserve(nominators.len());
+	for nominator in nominators {
 		let stash = &nominator.who;
 		let mut nom_slashed = Zero::zero();
 
@@ -603,7 +630,7 @@ pub fn do_slash<T: Config>(
 	<Pallet<T>>::deposit_event(super::Event::<T>::Slashed { staker: stash.clone(), amount: value });
 }
 
-/// Apply a previously-unapplied slash.
+/// Apply a previously-unapplied page of a slash.
 pub(crate) fn apply_slash<T: Config>(
 	unapplied_slash: UnappliedSlash<T::AccountId, BalanceOf<T>>,
 	slash_era: EraIndex,
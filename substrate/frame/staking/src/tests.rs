@@ -34,8 +34,8 @@ use mock::*;
 use pallet_balances::Error as BalancesError;
 use sp_runtime::{
 	assert_eq_error_rate, bounded_vec,
-	traits::{BadOrigin, Dispatchable},
-	Perbill, Percent, Perquintill, Rounding, TokenError,
+	traits::{BadOrigin, Dispatchable, One},
+	FixedU128, Perbill, Percent, Perquintill, Rounding, TokenError,
 };
 use sp_staking::{
 	offence::{OffenceDetails, OnOffenceHandler},
@@ -2170,7 +2170,7 @@ fn bond_with_duplicate_vote_should_be_ignored_by_election_provider() {
 
 			// winners should be 21 and 31. Otherwise this election is taking duplicates into
 			// account.
-			let supports = <Test as Config>::ElectionProvider::elect().unwrap();
+			let supports = <Test as Config>::ElectionProvider::elect(0).unwrap();
 			assert_eq!(
 				supports,
 				vec![
@@ -2222,7 +2222,7 @@ fn bond_with_duplicate_vote_should_be_ignored_by_election_provider_elected() {
 			assert_ok!(Staking::nominate(RuntimeOrigin::signed(3), vec![21]));
 
 			// winners should be 21 and 11.
-			let supports = <Test as Config>::ElectionProvider::elect().unwrap();
+			let supports = <Test as Config>::ElectionProvider::elect(0).unwrap();
 			assert_eq!(
 				supports,
 				vec![
@@ -3205,12 +3205,17 @@ fn remove_deferred() {
 
 		// fails if empty
 		assert_noop!(
-			Staking::cancel_deferred_slash(RuntimeOrigin::root(), 1, vec![]),
+			Staking::cancel_deferred_slash(RuntimeOrigin::root(), 1, vec![], 10),
 			Error::<Test>::EmptyTargets
 		);
 
 		// cancel one of them.
-		assert_ok!(Staking::cancel_deferred_slash(RuntimeOrigin::root(), 4, vec![0]));
+		assert_ok!(Staking::cancel_deferred_slash(
+			RuntimeOrigin::root(),
+			4,
+			vec![(11, Perbill::from_percent(10))],
+			10
+		));
 
 		assert_eq!(Balances::free_balance(11), 1000);
 		assert_eq!(Balances::free_balance(101), 2000);
@@ -3286,30 +3291,277 @@ fn remove_multi_deferred() {
 			&[Perbill::from_percent(25)],
 		);
 
-		assert_eq!(UnappliedSlashes::<Test>::get(&4).len(), 5);
+		assert_eq!(UnappliedSlashes::<Test>::iter_prefix((4,)).count(), 5);
+		assert_eq!(UnappliedSlashEras::<Test>::get(), vec![4]);
 
-		// fails if list is not sorted
+		// fails if the slash is due in another era
+		assert_noop!(
+			Staking::cancel_deferred_slash(
+				RuntimeOrigin::root(),
+				1,
+				vec![(11, Perbill::from_percent(10))],
+				10
+			),
+			Error::<Test>::InvalidSlashRecord
+		);
+		// fails if there is no slash with the given fraction
 		assert_noop!(
-			Staking::cancel_deferred_slash(RuntimeOrigin::root(), 1, vec![2, 0, 4]),
-			Error::<Test>::NotSortedAndUnique
+			Staking::cancel_deferred_slash(
+				RuntimeOrigin::root(),
+				4,
+				vec![(11, Perbill::from_percent(10)), (11, Perbill::from_percent(15))],
+				10
+			),
+			Error::<Test>::InvalidSlashRecord
+		);
+		// fails if the same slash is cancelled twice
+		assert_noop!(
+			Staking::cancel_deferred_slash(
+				RuntimeOrigin::root(),
+				4,
+				vec![(11, Perbill::from_percent(10)), (11, Perbill::from_percent(10))],
+				10
+			),
+			Error::<Test>::InvalidSlashRecord
+		);
+
+		assert_ok!(Staking::cancel_deferred_slash(
+			RuntimeOrigin::root(),
+			4,
+			vec![
+				(11, Perbill::from_percent(10)),
+				(11, Perbill::from_percent(25)),
+				(69, Perbill::from_percent(25))
+			],
+			10
+		));
+
+		let slashes = UnappliedSlashes::<Test>::iter_prefix((4,))
+			.map(|((validator, _, _), slash)| {
+				assert_eq!(slash.validator, validator);
+				validator
+			})
+			.collect::<Vec<_>>();
+		assert_eq_uvec!(slashes, vec![21, 42]);
+		assert_eq!(UnappliedSlashEras::<Test>::get(), vec![4]);
+
+		// cancelling all the remaining slashes of the era stops tracking it.
+		assert_ok!(Staking::cancel_deferred_slash(
+			RuntimeOrigin::root(),
+			4,
+			vec![(21, Perbill::from_percent(10)), (42, Perbill::from_percent(25))],
+			10
+		));
+		assert_eq!(UnappliedSlashes::<Test>::iter_prefix((4,)).count(), 0);
+		assert!(UnappliedSlashEras::<Test>::get().is_empty());
+	})
+}
+
+#[test]
+fn cancel_deferred_slash_is_bounded_by_page_limit() {
+	let mut ext = ExtBuilder::default().slash_defer_duration(2).build();
+	let fraction = Perbill::from_percent(10);
+	ext.execute_with(|| {
+		// a slash of 11 split in 3 pages, due at the start of era 4.
+		let pages = (0..3).map(|_| UnappliedSlash::default_from(11)).collect();
+		Staking::defer_slash(4, &11, fraction, pages);
+	});
+	// the page limit only bounds the removal of pages that are already committed.
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		let info =
+			Staking::cancel_deferred_slash(RuntimeOrigin::root(), 4, vec![(11, fraction)], 2)
+				.unwrap();
+		assert_eq!(
+			info.actual_weight,
+			Some(<Test as Config>::WeightInfo::cancel_deferred_slash(1, 2))
+		);
+		assert_eq!(UnappliedSlashes::<Test>::iter_prefix((4,)).count(), 1);
+		assert!(CancelledSlashCursor::<Test>::contains_key((4, 11, fraction)));
+		assert_eq!(UnappliedSlashEras::<Test>::get(), vec![4]);
+
+		// the cancellation resumes where it stopped, even within the same block.
+		let info =
+			Staking::cancel_deferred_slash(RuntimeOrigin::root(), 4, vec![(11, fraction)], 2)
+				.unwrap();
+		assert_eq!(
+			info.actual_weight,
+			Some(<Test as Config>::WeightInfo::cancel_deferred_slash(1, 1))
+		);
+		assert_eq!(UnappliedSlashes::<Test>::iter_prefix((4,)).count(), 0);
+		assert!(!CancelledSlashCursor::<Test>::contains_key((4, 11, fraction)));
+		assert!(UnappliedSlashEras::<Test>::get().is_empty());
+
+		// nothing is left to cancel.
+		assert_noop!(
+			Staking::cancel_deferred_slash(RuntimeOrigin::root(), 4, vec![(11, fraction)], 2),
+			Error::<Test>::InvalidSlashRecord
+		);
+	});
+}
+
+#[test]
+fn cancel_deferred_slash_resumes_across_blocks() {
+	let mut ext = ExtBuilder::default().slash_defer_duration(2).build();
+	let fraction = Perbill::from_percent(10);
+	ext.execute_with(|| {
+		// a slash of 11 split in 5 pages, due at the start of era 4.
+		let pages = (0..5).map(|_| UnappliedSlash::default_from(11)).collect();
+		Staking::defer_slash(4, &11, fraction, pages);
+	});
+	ext.commit_all().unwrap();
+
+	// each block cancels two more pages, resuming where the previous block stopped.
+	for remaining in [3, 1] {
+		ext.execute_with(|| {
+			run_to_block(System::block_number() + 1);
+			assert_ok!(Staking::cancel_deferred_slash(
+				RuntimeOrigin::root(),
+				4,
+				vec![(11, fraction)],
+				2
+			));
+			assert_eq!(UnappliedSlashes::<Test>::iter_prefix((4,)).count(), remaining);
+			assert!(CancelledSlashCursor::<Test>::contains_key((4, 11, fraction)));
+			assert_eq!(UnappliedSlashEras::<Test>::get(), vec![4]);
+		});
+		ext.commit_all().unwrap();
+	}
+
+	ext.execute_with(|| {
+		run_to_block(System::block_number() + 1);
+		let info =
+			Staking::cancel_deferred_slash(RuntimeOrigin::root(), 4, vec![(11, fraction)], 2)
+				.unwrap();
+		assert_eq!(
+			info.actual_weight,
+			Some(<Test as Config>::WeightInfo::cancel_deferred_slash(1, 1))
+		);
+		assert_eq!(UnappliedSlashes::<Test>::iter_prefix((4,)).count(), 0);
+		assert!(!CancelledSlashCursor::<Test>::contains_key((4, 11, fraction)));
+		assert!(UnappliedSlashEras::<Test>::get().is_empty());
+	});
+}
+
+#[test]
+fn pending_slash_holds_back_unbonding_of_all_stakers() {
+	ExtBuilder::default().slash_defer_duration(2).build_and_execute(|| {
+		// one nominator per page.
+		MaxExposurePageSize::set(1);
+		for nominator in 201..=202 {
+			bond_nominator(nominator, 500, vec![11]);
+		}
+		// 301 does not back 11.
+		bond_nominator(301, 500, vec![21]);
+
+		mock::start_active_era(1);
+		let exposure = Staking::eras_stakers(active_era(), &11);
+		assert!(exposure.others.iter().all(|individual| individual.who != 301));
+
+		// 301 unbonds in the era of the offence of 11.
+		assert_ok!(Staking::unbond(RuntimeOrigin::signed(301), 100));
+		on_offence_now(
+			&[OffenceDetails { offender: (11, exposure), reporters: vec![] }],
+			&[Perbill::from_percent(10)],
+		);
+		let pages = UnappliedSlashes::<Test>::iter_prefix((4,)).count() as u64;
+		assert!(pages > 1);
+
+		// the slash is due and its first page is applied, the unbonded funds of 301 are held
+		// back nonetheless.
+		mock::start_active_era(4);
+		assert_ok!(Staking::withdraw_unbonded(RuntimeOrigin::signed(301), 0));
+		assert_eq!(
+			Ledger::<Test>::get(301).unwrap().unlocking.into_inner(),
+			vec![UnlockChunk { era: 4, value: 100 }]
+		);
+
+		// until the last page of the slash is applied.
+		run_to_block(System::block_number() + pages - 1);
+		assert!(UnappliedSlashEras::<Test>::get().is_empty());
+		assert_ok!(Staking::withdraw_unbonded(RuntimeOrigin::signed(301), 0));
+		assert!(Ledger::<Test>::get(301).unwrap().unlocking.is_empty());
+		assert_eq!(Ledger::<Test>::get(301).unwrap().total, 400);
+	})
+}
+
+#[test]
+fn deferred_slashes_are_applied_page_by_page() {
+	ExtBuilder::default().slash_defer_duration(2).build_and_execute(|| {
+		// at most two nominators per page.
+		MaxExposurePageSize::set(2);
+		for nominator in 201..=204 {
+			bond_nominator(nominator, 500, vec![11]);
+		}
+
+		mock::start_active_era(1);
+		let exposure = Staking::eras_stakers(active_era(), &11);
+		assert_eq!(exposure.others.len(), 5);
+
+		// a nominator of 11 unbonds in the era of the offence.
+		assert_ok!(Staking::unbond(RuntimeOrigin::signed(201), 100));
+
+		let fraction = Perbill::from_percent(10);
+		on_offence_now(
+			&[OffenceDetails { offender: (11, exposure), reporters: vec![] }],
+			&[fraction],
 		);
-		// fails if list is not unique
+
+		// the slash is split in 3 pages, due at the start of era 4.
+		assert_eq!(UnappliedSlashes::<Test>::iter_prefix((4,)).count(), 3);
+		assert_eq!(UnappliedSlashEras::<Test>::get(), vec![4]);
+		assert_eq!(
+			UnappliedSlashes::<Test>::get((4, 11, fraction, 0)).unwrap().own,
+			fraction * 1000
+		);
+		assert!(UnappliedSlashes::<Test>::get((4, 11, fraction, 1)).unwrap().own.is_zero());
+
+		// a slash page cannot be applied before it is due.
 		assert_noop!(
-			Staking::cancel_deferred_slash(RuntimeOrigin::root(), 1, vec![0, 2, 2]),
-			Error::<Test>::NotSortedAndUnique
+			Staking::apply_slash(RuntimeOrigin::signed(1), 4, (11, fraction, 0)),
+			Error::<Test>::SlashNotDue
 		);
-		// fails if bad index
+
+		// a single page is applied in the first block of era 4.
+		mock::start_active_era(4);
+		assert_eq!(UnappliedSlashes::<Test>::iter_prefix((4,)).count(), 2);
+
+		// the unbonded funds of era 1 can still be slashed, so they cannot be withdrawn yet.
+		assert_ok!(Staking::withdraw_unbonded(RuntimeOrigin::signed(201), 0));
+		assert_eq!(
+			Ledger::<Test>::get(201).unwrap().unlocking.into_inner(),
+			vec![UnlockChunk { era: 4, value: 100 }]
+		);
+
+		// anyone can apply a due page, free of charge.
+		let (validator, fraction, page) =
+			UnappliedSlashes::<Test>::iter_key_prefix((4,)).next().unwrap();
+		assert_eq!(
+			Staking::apply_slash(RuntimeOrigin::signed(1), 4, (validator, fraction, page)),
+			Ok(Pays::No.into())
+		);
+		assert_eq!(UnappliedSlashes::<Test>::iter_prefix((4,)).count(), 1);
+
+		// an applied page cannot be applied again.
 		assert_noop!(
-			Staking::cancel_deferred_slash(RuntimeOrigin::root(), 1, vec![1, 2, 3, 4, 5]),
-			Error::<Test>::InvalidSlashIndex
+			Staking::apply_slash(RuntimeOrigin::signed(1), 4, (validator, fraction, page)),
+			Error::<Test>::InvalidSlashRecord
 		);
 
-		assert_ok!(Staking::cancel_deferred_slash(RuntimeOrigin::root(), 4, vec![0, 2, 4]));
+		// the last page is applied in the next block.
+		run_to_block(System::block_number() + 1);
+		assert_eq!(UnappliedSlashes::<Test>::iter_prefix((4,)).count(), 0);
+		assert!(UnappliedSlashEras::<Test>::get().is_empty());
+
+		assert_eq!(Balances::free_balance(11), 900);
+		for nominator in 201..=204 {
+			assert_eq!(Balances::free_balance(nominator), 450);
+		}
 
-		let slashes = UnappliedSlashes::<Test>::get(&4);
-		assert_eq!(slashes.len(), 2);
-		assert_eq!(slashes[0].validator, 21);
-		assert_eq!(slashes[1].validator, 42);
+		// the unbonded funds can now be withdrawn.
+		assert_ok!(Staking::withdraw_unbonded(RuntimeOrigin::signed(201), 0));
+		assert!(Ledger::<Test>::get(201).unwrap().unlocking.is_empty());
 	})
 }
 
@@ -5098,14 +5350,15 @@ mod election_data_provider {
 			.build_and_execute(|| {
 				// default bounds are unbounded.
 				assert_ok!(<Staking as ElectionDataProvider>::electing_voters(
-					DataProviderBounds::default()
+					DataProviderBounds::default(),
+					0
 				));
 				assert_eq!(MinimumActiveStake::<Test>::get(), 10);
 
 				// remove staker with lower bond by limiting the number of voters and check
 				// `MinimumActiveStake` again after electing voters.
 				let bounds = ElectionBoundsBuilder::default().voters_count(5.into()).build();
-				assert_ok!(<Staking as ElectionDataProvider>::electing_voters(bounds.voters));
+				assert_ok!(<Staking as ElectionDataProvider>::electing_voters(bounds.voters, 0));
 				assert_eq!(MinimumActiveStake::<Test>::get(), 50);
 			});
 	}
@@ -5116,7 +5369,8 @@ mod election_data_provider {
 		ExtBuilder::default().has_stakers(false).build_and_execute(|| {
 			// default bounds are unbounded.
 			assert_ok!(<Staking as ElectionDataProvider>::electing_voters(
-				DataProviderBounds::default()
+				DataProviderBounds::default(),
+				0
 			));
 			assert_eq!(<Test as Config>::VoterList::count(), 0);
 			assert_eq!(MinimumActiveStake::<Test>::get(), 0);
@@ -5132,9 +5386,11 @@ mod election_data_provider {
 			assert_ok!(Staking::nominate(RuntimeOrigin::signed(4), vec![1]));
 			assert_eq!(<Test as Config>::VoterList::count(), 5);
 
-			let voters_before =
-				<Staking as ElectionDataProvider>::electing_voters(DataProviderBounds::default())
-					.unwrap();
+			let voters_before = <Staking as ElectionDataProvider>::electing_voters(
+				DataProviderBounds::default(),
+				0,
+			)
+			.unwrap();
 			assert_eq!(MinimumActiveStake::<Test>::get(), 5);
 
 			// update minimum nominator bond.
@@ -5144,9 +5400,11 @@ mod election_data_provider {
 			// lower than `MinNominatorBond`.
 			assert_eq!(<Test as Config>::VoterList::count(), 5);
 
-			let voters =
-				<Staking as ElectionDataProvider>::electing_voters(DataProviderBounds::default())
-					.unwrap();
+			let voters = <Staking as ElectionDataProvider>::electing_voters(
+				DataProviderBounds::default(),
+				0,
+			)
+			.unwrap();
 			assert_eq!(voters_before, voters);
 
 			// minimum active stake is lower than `MinNominatorBond`.
@@ -5164,6 +5422,7 @@ mod election_data_provider {
 				assert_eq!(Staking::weight_of(&101), 500);
 				let voters = <Staking as ElectionDataProvider>::electing_voters(
 					DataProviderBounds::default(),
+					0,
 				)
 				.unwrap();
 				assert_eq!(voters.len(), 5);
@@ -5179,6 +5438,7 @@ mod election_data_provider {
 
 				let voters = <Staking as ElectionDataProvider>::electing_voters(
 					DataProviderBounds::default(),
+					0,
 				)
 				.unwrap();
 				// number of returned voters decreases since ledger entry of stash 101 is now
@@ -5200,7 +5460,8 @@ mod election_data_provider {
 		ExtBuilder::default().nominate(false).build_and_execute(|| {
 			// default bounds are unbounded.
 			assert!(<Validators<Test>>::iter().map(|(x, _)| x).all(|v| Staking::electing_voters(
-				DataProviderBounds::default()
+				DataProviderBounds::default(),
+				0
 			)
 			.unwrap()
 			.into_iter()
@@ -5253,12 +5514,15 @@ mod election_data_provider {
 				// 11 is taken;
 				// we finish since the 2x limit is reached.
 				assert_eq!(
-					Staking::electing_voters(bounds_builder.voters_count(2.into()).build().voters)
-						.unwrap()
-						.iter()
-						.map(|(stash, _, _)| stash)
-						.copied()
-						.collect::<Vec<_>>(),
+					Staking::electing_voters(
+						bounds_builder.voters_count(2.into()).build().voters,
+						0
+					)
+					.unwrap()
+					.iter()
+					.map(|(stash, _, _)| stash)
+					.copied()
+					.collect::<Vec<_>>(),
 					vec![11],
 				);
 			});
@@ -5276,32 +5540,42 @@ mod election_data_provider {
 
 				// if voter count limit is less..
 				assert_eq!(
-					Staking::electing_voters(bounds_builder.voters_count(1.into()).build().voters)
-						.unwrap()
-						.len(),
+					Staking::electing_voters(
+						bounds_builder.voters_count(1.into()).build().voters,
+						0
+					)
+					.unwrap()
+					.len(),
 					1
 				);
 
 				// if voter count limit is equal..
 				assert_eq!(
-					Staking::electing_voters(bounds_builder.voters_count(5.into()).build().voters)
-						.unwrap()
-						.len(),
+					Staking::electing_voters(
+						bounds_builder.voters_count(5.into()).build().voters,
+						0
+					)
+					.unwrap()
+					.len(),
 					5
 				);
 
 				// if voter count limit is more.
 				assert_eq!(
-					Staking::electing_voters(bounds_builder.voters_count(55.into()).build().voters)
-						.unwrap()
-						.len(),
+					Staking::electing_voters(
+						bounds_builder.voters_count(55.into()).build().voters,
+						0
+					)
+					.unwrap()
+					.len(),
 					5
 				);
 
 				// if target count limit is more..
 				assert_eq!(
 					Staking::electable_targets(
-						bounds_builder.targets_count(6.into()).build().targets
+						bounds_builder.targets_count(6.into()).build().targets,
+						0
 					)
 					.unwrap()
 					.len(),
@@ -5311,7 +5585,8 @@ mod election_data_provider {
 				// if target count limit is equal..
 				assert_eq!(
 					Staking::electable_targets(
-						bounds_builder.targets_count(4.into()).build().targets
+						bounds_builder.targets_count(4.into()).build().targets,
+						0
 					)
 					.unwrap()
 					.len(),
@@ -5321,7 +5596,8 @@ mod election_data_provider {
 				// if target limit count is less, then we return an error.
 				assert_eq!(
 					Staking::electable_targets(
-						bounds_builder.targets_count(1.into()).build().targets
+						bounds_builder.targets_count(1.into()).build().targets,
+						0
 					)
 					.unwrap_err(),
 					"Target snapshot too big"
@@ -5334,30 +5610,263 @@ mod election_data_provider {
 		ExtBuilder::default().build_and_execute(|| {
 			// voters: set size bounds that allows only for 1 voter.
 			let bounds = ElectionBoundsBuilder::default().voters_size(26.into()).build();
-			let elected = Staking::electing_voters(bounds.voters).unwrap();
+			let elected = Staking::electing_voters(bounds.voters, 0).unwrap();
 			assert!(elected.encoded_size() == 26 as usize);
 			let prev_len = elected.len();
 
 			// larger size bounds means more quota for voters.
 			let bounds = ElectionBoundsBuilder::default().voters_size(100.into()).build();
-			let elected = Staking::electing_voters(bounds.voters).unwrap();
+			let elected = Staking::electing_voters(bounds.voters, 0).unwrap();
 			assert!(elected.encoded_size() <= 100 as usize);
 			assert!(elected.len() > 1 && elected.len() > prev_len);
 
 			// targets: set size bounds that allows for only one target to fit in the snapshot.
 			let bounds = ElectionBoundsBuilder::default().targets_size(10.into()).build();
-			let elected = Staking::electable_targets(bounds.targets).unwrap();
+			let elected = Staking::electable_targets(bounds.targets, 0).unwrap();
 			assert!(elected.encoded_size() == 9 as usize);
 			let prev_len = elected.len();
 
 			// larger size bounds means more space for targets.
 			let bounds = ElectionBoundsBuilder::default().targets_size(100.into()).build();
-			let elected = Staking::electable_targets(bounds.targets).unwrap();
+			let elected = Staking::electable_targets(bounds.targets, 0).unwrap();
 			assert!(elected.encoded_size() <= 100 as usize);
 			assert!(elected.len() > 1 && elected.len() > prev_len);
 		});
 	}
 
+	#[test]
+	fn paged_voters_snapshot_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			bond_nominator(61, 300, vec![11]);
+			bond_nominator(71, 200, vec![21]);
+
+			let all_voters = Staking::electing_voters(DataProviderBounds::default(), 0).unwrap();
+			assert_eq!(all_voters.len(), 6);
+			assert_eq!(VoterSnapshotStatus::<Test>::get(), SnapshotStatus::Waiting);
+
+			let bounds = ElectionBoundsBuilder::default().voters_count(2.into()).build().voters;
+
+			// the most significant page starts from the top of the voter list.
+			let page_2 = Staking::electing_voters(bounds, 2).unwrap();
+			assert_eq!(page_2, all_voters[..2].to_vec());
+			assert_eq!(
+				VoterSnapshotStatus::<Test>::get(),
+				SnapshotStatus::Ongoing(all_voters[1].0)
+			);
+
+			// and the next ones continue where the previous one stopped.
+			let page_1 = Staking::electing_voters(bounds, 1).unwrap();
+			assert_eq!(page_1, all_voters[2..4].to_vec());
+			assert_eq!(
+				VoterSnapshotStatus::<Test>::get(),
+				SnapshotStatus::Ongoing(all_voters[3].0)
+			);
+
+			// the least significant page concludes the snapshot.
+			let page_0 = Staking::electing_voters(bounds, 0).unwrap();
+			assert_eq!(page_0, all_voters[4..].to_vec());
+			assert_eq!(VoterSnapshotStatus::<Test>::get(), SnapshotStatus::Waiting);
+
+			// once all voters are returned, the remaining pages are empty.
+			assert_eq!(
+				Staking::electing_voters(DataProviderBounds::default(), 2).unwrap(),
+				all_voters
+			);
+			assert!(Staking::electing_voters(DataProviderBounds::default(), 1).unwrap().is_empty());
+			assert_eq!(VoterSnapshotStatus::<Test>::get(), SnapshotStatus::Consumed);
+			assert!(Staking::electing_voters(DataProviderBounds::default(), 0).unwrap().is_empty());
+
+			// and a new snapshot starts from the top again.
+			assert_eq!(VoterSnapshotStatus::<Test>::get(), SnapshotStatus::Waiting);
+			assert_eq!(
+				Staking::electing_voters(DataProviderBounds::default(), 0).unwrap(),
+				all_voters
+			);
+		});
+	}
+
+	#[test]
+	fn paged_voters_snapshot_resumes_after_cursor_left() {
+		ExtBuilder::default().build_and_execute(|| {
+			bond_nominator(61, 300, vec![11]);
+			bond_nominator(71, 200, vec![21]);
+
+			let all_voters = Staking::electing_voters(DataProviderBounds::default(), 0).unwrap();
+			assert_eq!(all_voters.len(), 6);
+			let bounds = ElectionBoundsBuilder::default().voters_count(2.into()).build().voters;
+
+			let page_2 = Staking::electing_voters(bounds, 2).unwrap();
+			assert_eq!(page_2, all_voters[..2].to_vec());
+
+			// the last voter of the page leaves the voter list, the next page starts from the voter
+			// that followed it.
+			Staking::chill_stash(&all_voters[1].0);
+			assert_eq!(
+				VoterSnapshotStatus::<Test>::get(),
+				SnapshotStatus::Resuming(all_voters[2].0)
+			);
+
+			// and so does it if that voter leaves too.
+			Staking::chill_stash(&all_voters[2].0);
+			assert_eq!(
+				VoterSnapshotStatus::<Test>::get(),
+				SnapshotStatus::Resuming(all_voters[3].0)
+			);
+
+			// no voter is dropped nor returned twice.
+			let page_1 = Staking::electing_voters(bounds, 1).unwrap();
+			assert_eq!(page_1, all_voters[3..5].to_vec());
+			assert_eq!(
+				VoterSnapshotStatus::<Test>::get(),
+				SnapshotStatus::Ongoing(all_voters[4].0)
+			);
+
+			let page_0 = Staking::electing_voters(bounds, 0).unwrap();
+			assert_eq!(page_0, all_voters[5..].to_vec());
+
+			// if the cursor is the last voter of the list and leaves, the snapshot is consumed.
+			assert_ok!(Staking::electing_voters(DataProviderBounds::default(), 1));
+			let last = all_voters[5].0;
+			assert_eq!(VoterSnapshotStatus::<Test>::get(), SnapshotStatus::Ongoing(last));
+			Staking::chill_stash(&last);
+			assert_eq!(VoterSnapshotStatus::<Test>::get(), SnapshotStatus::Consumed);
+			assert!(Staking::electing_voters(DataProviderBounds::default(), 0).unwrap().is_empty());
+		});
+	}
+
+	#[test]
+	#[should_panic = "Defensive failure has been triggered!"]
+	fn paged_voters_snapshot_fails_if_cursor_left() {
+		ExtBuilder::default().build_and_execute(|| {
+			let bounds = ElectionBoundsBuilder::default().voters_count(2.into()).build().voters;
+			let page_2 = Staking::electing_voters(bounds, 2).unwrap();
+
+			// the cursor leaves the voter list without going through staking.
+			assert_ok!(<Test as Config>::VoterList::on_remove(&page_2[1].0));
+			let _ = Staking::electing_voters(bounds, 1);
+		});
+	}
+
+	#[test]
+	fn upsert_exposure_merges_pages() {
+		ExtBuilder::default().has_stakers(false).build_and_execute(|| {
+			MaxExposurePageSize::set(2);
+			let individual = |who, value| IndividualExposure { who, value };
+
+			// the first page of the election result creates the exposure.
+			EraInfo::<Test>::upsert_exposure(
+				1,
+				&11,
+				Exposure {
+					own: 100,
+					total: 400,
+					others: vec![individual(1, 100), individual(2, 200)],
+				},
+			);
+			// the next ones fill up the last page, and then create new pages.
+			EraInfo::<Test>::upsert_exposure(
+				1,
+				&11,
+				Exposure {
+					own: 0,
+					total: 600,
+					others: vec![individual(3, 300), individual(4, 300)],
+				},
+			);
+			EraInfo::<Test>::upsert_exposure(
+				1,
+				&11,
+				Exposure { own: 0, total: 50, others: vec![individual(5, 50)] },
+			);
+			EraInfo::<Test>::upsert_exposure(
+				1,
+				&11,
+				Exposure { own: 0, total: 60, others: vec![individual(6, 60), individual(7, 0)] },
+			);
+
+			assert_eq!(
+				ErasStakersOverview::<Test>::get(1, 11).unwrap(),
+				PagedExposureMetadata { total: 1110, own: 100, nominator_count: 7, page_count: 4 }
+			);
+			assert_eq!(
+				ErasStakersPaged::<Test>::get((1, 11, 2)).unwrap(),
+				ExposurePage {
+					page_total: 110,
+					others: vec![individual(5, 50), individual(6, 60)]
+				}
+			);
+			assert_eq!(
+				ErasStakersPaged::<Test>::get((1, 11, 3)).unwrap(),
+				ExposurePage { page_total: 0, others: vec![individual(7, 0)] }
+			);
+			assert_eq!(
+				EraInfo::<Test>::get_full_exposure(1, &11),
+				Exposure {
+					own: 100,
+					total: 1110,
+					others: (1..=7)
+						.zip([100, 200, 300, 300, 50, 60, 0])
+						.map(|(who, value)| individual(who, value))
+						.collect(),
+				}
+			);
+		});
+	}
+
+	#[test]
+	fn previous_election_pages_are_merged_into_new_era() {
+		ExtBuilder::default().build_and_execute(|| {
+			let exposure_of_11 = EraInfo::<Test>::get_full_exposure(0, &11);
+			let partial = Exposure {
+				own: 0,
+				total: 500,
+				others: vec![IndividualExposure { who: 777, value: 500 }],
+			};
+
+			// a previous page of the election result elected 11.
+			Staking::store_stakers_info_paged(bounded_vec![(11, partial.clone())], 1);
+			assert_eq!(ElectableStashes::<Test>::get().into_inner(), vec![11]);
+
+			// then the least significant page is merged into it when the era is planned.
+			start_active_era(1);
+			assert!(ElectableStashes::<Test>::get().is_empty());
+
+			let merged = EraInfo::<Test>::get_full_exposure(1, &11);
+			assert_eq!(merged.total, exposure_of_11.total + partial.total);
+			assert_eq!(merged.own, exposure_of_11.own);
+			assert_eq!(merged.others.len(), exposure_of_11.others.len() + 1);
+			assert!(merged.others.contains(&IndividualExposure { who: 777, value: 500 }));
+			assert_eq!(
+				ErasTotalStake::<Test>::get(1),
+				ErasTotalStake::<Test>::get(0) + partial.total
+			);
+		});
+	}
+
+	#[test]
+	fn failed_election_clears_previous_pages() {
+		ExtBuilder::default().build_and_execute(|| {
+			let partial = Exposure {
+				own: 0,
+				total: 500,
+				others: vec![IndividualExposure { who: 777, value: 500 }],
+			};
+			Staking::store_stakers_info_paged(bounded_vec![(11, partial)], 1);
+			NextElectionPage::<Test>::put(1);
+			assert!(ErasStakersOverview::<Test>::get(1, 11).is_some());
+
+			// not enough validators are elected.
+			MinimumValidatorCount::<Test>::put(100);
+			assert!(Staking::try_trigger_new_era(3, false).is_none());
+
+			assert!(ElectableStashes::<Test>::get().is_empty());
+			assert!(NextElectionPage::<Test>::get().is_none());
+			assert!(ErasStakersOverview::<Test>::get(1, 11).is_none());
+			assert_eq!(ErasTotalStake::<Test>::get(1), 0);
+			assert_eq!(current_era(), 0);
+		});
+	}
+
 	#[test]
 	fn nomination_quota_checks_at_nominate_works() {
 		ExtBuilder::default().nominate(false).build_and_execute(|| {
@@ -5396,7 +5905,7 @@ mod election_data_provider {
 				// even through 61 has nomination quota of 2 at the time of the election, all the
 				// nominations (5) will be used.
 				assert_eq!(
-					Staking::electing_voters(DataProviderBounds::default())
+					Staking::electing_voters(DataProviderBounds::default(), 0)
 						.unwrap()
 						.iter()
 						.map(|(stash, _, targets)| (*stash, targets.len()))
@@ -5420,7 +5929,7 @@ mod election_data_provider {
 				// nominations of controller 70 won't be added due to voter size limit exceeded.
 				let bounds = ElectionBoundsBuilder::default().voters_size(100.into()).build();
 				assert_eq!(
-					Staking::electing_voters(bounds.voters)
+					Staking::electing_voters(bounds.voters, 0)
 						.unwrap()
 						.iter()
 						.map(|(stash, _, targets)| (*stash, targets.len()))
@@ -5437,7 +5946,7 @@ mod election_data_provider {
 				// include the electing voters of 70.
 				let bounds = ElectionBoundsBuilder::default().voters_size(1_000.into()).build();
 				assert_eq!(
-					Staking::electing_voters(bounds.voters)
+					Staking::electing_voters(bounds.voters, 0)
 						.unwrap()
 						.iter()
 						.map(|(stash, _, targets)| (*stash, targets.len()))
@@ -5922,7 +6431,7 @@ fn change_of_absolute_max_nominations() {
 			let bounds = DataProviderBounds::default();
 
 			// 3 validators and 3 nominators
-			assert_eq!(Staking::electing_voters(bounds).unwrap().len(), 3 + 3);
+			assert_eq!(Staking::electing_voters(bounds, 0).unwrap().len(), 3 + 3);
 
 			// abrupt change from 16 to 4, everyone should be fine.
 			AbsoluteMaxNominations::set(4);
@@ -5933,7 +6442,7 @@ fn change_of_absolute_max_nominations() {
 					.collect::<Vec<_>>(),
 				vec![(101, 2), (71, 3), (61, 1)]
 			);
-			assert_eq!(Staking::electing_voters(bounds).unwrap().len(), 3 + 3);
+			assert_eq!(Staking::electing_voters(bounds, 0).unwrap().len(), 3 + 3);
 
 			// abrupt change from 4 to 3, everyone should be fine.
 			AbsoluteMaxNominations::set(3);
@@ -5944,7 +6453,7 @@ fn change_of_absolute_max_nominations() {
 					.collect::<Vec<_>>(),
 				vec![(101, 2), (71, 3), (61, 1)]
 			);
-			assert_eq!(Staking::electing_voters(bounds).unwrap().len(), 3 + 3);
+			assert_eq!(Staking::electing_voters(bounds, 0).unwrap().len(), 3 + 3);
 
 			// abrupt change from 3 to 2, this should cause some nominators to be non-decodable, and
 			// thus non-existent unless if they update.
@@ -5961,7 +6470,7 @@ fn change_of_absolute_max_nominations() {
 			// but its value cannot be decoded and default is returned.
 			assert!(Nominators::<Test>::get(71).is_none());
 
-			assert_eq!(Staking::electing_voters(bounds).unwrap().len(), 3 + 2);
+			assert_eq!(Staking::electing_voters(bounds, 0).unwrap().len(), 3 + 2);
 			assert!(Nominators::<Test>::contains_key(101));
 
 			// abrupt change from 2 to 1, this should cause some nominators to be non-decodable, and
@@ -5978,7 +6487,7 @@ fn change_of_absolute_max_nominations() {
 			assert!(Nominators::<Test>::contains_key(61));
 			assert!(Nominators::<Test>::get(71).is_none());
 			assert!(Nominators::<Test>::get(61).is_some());
-			assert_eq!(Staking::electing_voters(bounds).unwrap().len(), 3 + 1);
+			assert_eq!(Staking::electing_voters(bounds, 0).unwrap().len(), 3 + 1);
 
 			// now one of them can revive themselves by re-nominating to a proper value.
 			assert_ok!(Staking::nominate(RuntimeOrigin::signed(71), vec![1]));
@@ -6020,7 +6529,7 @@ fn nomination_quota_max_changes_decoding() {
 				vec![(70, 3), (101, 2), (50, 4), (30, 4), (60, 1)]
 			);
 			// 4 validators and 4 nominators
-			assert_eq!(Staking::electing_voters(unbonded_election).unwrap().len(), 4 + 4);
+			assert_eq!(Staking::electing_voters(unbonded_election, 0).unwrap().len(), 4 + 4);
 		});
 }
 
@@ -7802,7 +8311,7 @@ mod ledger_recovery {
 			assert_eq!(Balances::balance_locked(crate::STAKING_ID, &333), lock_333_before); // OK
 			assert_eq!(Bonded::<Test>::get(&333), Some(444)); // OK
 			assert!(Payee::<Test>::get(&333).is_some()); // OK
-											 // however, ledger associated with its controller was killed.
+												// however, ledger associated with its controller was killed.
 			assert!(Ledger::<Test>::get(&444).is_none()); // NOK
 
 			// side effects on 444 - ledger, bonded, payee, lock should be completely removed.
@@ -8129,3 +8638,534 @@ mod byzantine_threshold_disabling_strategy {
 		});
 	}
 }
+
+mod reward_conversion {
+	use super::*;
+
+	fn conversion(asset: u32, min_rate: FixedU128) -> RewardConversion<u32> {
+		RewardConversion { asset, min_rate }
+	}
+
+	#[test]
+	fn set_reward_conversion_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			RewardAssetPrices::mutate(|p| p.insert(1, 2));
+
+			// only controllers can set a conversion.
+			assert_noop!(
+				Staking::set_reward_conversion(
+					RuntimeOrigin::signed(1337),
+					Some(conversion(1, FixedU128::one()))
+				),
+				Error::<Test>::NotController
+			);
+
+			// assets that cannot be converted into are rejected.
+			assert_noop!(
+				Staking::set_reward_conversion(
+					RuntimeOrigin::signed(11),
+					Some(conversion(2, FixedU128::one()))
+				),
+				Error::<Test>::RewardConversionUnavailable
+			);
+
+			// a minimum rate of zero is rejected.
+			assert_noop!(
+				Staking::set_reward_conversion(
+					RuntimeOrigin::signed(11),
+					Some(conversion(1, FixedU128::zero()))
+				),
+				Error::<Test>::ZeroRewardConversionRate
+			);
+
+			assert_ok!(Staking::set_reward_conversion(
+				RuntimeOrigin::signed(11),
+				Some(conversion(1, FixedU128::one()))
+			));
+			assert_eq!(RewardConversions::<Test>::get(&11), Some(conversion(1, FixedU128::one())));
+			assert_eq!(
+				*staking_events().last().unwrap(),
+				Event::RewardConversionSet {
+					stash: 11,
+					conversion: Some(conversion(1, FixedU128::one()))
+				}
+			);
+
+			// the conversion can be removed again.
+			assert_ok!(Staking::set_reward_conversion(RuntimeOrigin::signed(11), None));
+			assert_eq!(RewardConversions::<Test>::get(&11), None);
+			assert_eq!(
+				*staking_events().last().unwrap(),
+				Event::RewardConversionSet { stash: 11, conversion: None }
+			);
+		});
+	}
+
+	#[test]
+	fn rewards_are_converted_on_payout() {
+		ExtBuilder::default().nominate(false).build_and_execute(|| {
+			RewardAssetPrices::mutate(|p| p.insert(1, 2));
+			<Payee<Test>>::insert(&11, RewardDestination::Stash);
+			assert_ok!(Staking::set_reward_conversion(
+				RuntimeOrigin::signed(11),
+				Some(conversion(1, FixedU128::one()))
+			));
+
+			let total_payout_0 = current_total_payout_for_duration(reward_time_per_era());
+			Pallet::<Test>::reward_by_ids(vec![(11, 1)]);
+			mock::start_active_era(1);
+			let _ = staking_events_since_last_call();
+			mock::make_all_reward_payment(0);
+
+			// the native reward was swapped away.
+			assert_eq!(Balances::free_balance(11), 1000);
+			assert_eq!(ConvertedRewards::get().get(&(11, 1)), Some(&(2 * total_payout_0)));
+			assert_eq!(
+				staking_events_since_last_call(),
+				vec![
+					Event::PayoutStarted { era_index: 0, validator_stash: 11 },
+					Event::Rewarded {
+						stash: 11,
+						dest: RewardDestination::Stash,
+						amount: total_payout_0
+					},
+					Event::RewardConverted {
+						stash: 11,
+						asset: 1,
+						amount_in: total_payout_0,
+						amount_out: 2 * total_payout_0
+					},
+				]
+			);
+
+			// rewards paid into another account are not converted, since they belong to it.
+			<Payee<Test>>::insert(&11, RewardDestination::Account(21));
+			let free_21 = Balances::free_balance(21);
+			let total_payout_1 = current_total_payout_for_duration(reward_time_per_era());
+			Pallet::<Test>::reward_by_ids(vec![(11, 1)]);
+			mock::start_active_era(2);
+			let _ = staking_events_since_last_call();
+			mock::make_all_reward_payment(1);
+
+			assert_eq!(Balances::free_balance(21), free_21 + total_payout_1);
+			assert_eq!(ConvertedRewards::get().get(&(21, 1)), None);
+			assert!(!staking_events_since_last_call().iter().any(|e| matches!(
+				e,
+				Event::RewardConverted { .. } | Event::RewardConversionFailed { .. }
+			)));
+		});
+	}
+
+	#[test]
+	fn reward_is_kept_below_min_rate() {
+		ExtBuilder::default().nominate(false).build_and_execute(|| {
+			RewardAssetPrices::mutate(|p| p.insert(1, 2));
+			// the price is moved against the stash right before the payout.
+			RewardSwapSlippage::set(Perbill::from_percent(5));
+			<Payee<Test>>::insert(&11, RewardDestination::Stash);
+			assert_ok!(Staking::set_reward_conversion(
+				RuntimeOrigin::signed(11),
+				Some(conversion(1, FixedU128::from_rational(195, 100)))
+			));
+
+			let total_payout_0 = current_total_payout_for_duration(reward_time_per_era());
+			Pallet::<Test>::reward_by_ids(vec![(11, 1)]);
+			mock::start_active_era(1);
+			let _ = staking_events_since_last_call();
+			mock::make_all_reward_payment(0);
+
+			// the reward is kept in the native currency.
+			assert_eq!(Balances::free_balance(11), 1000 + total_payout_0);
+			assert!(ConvertedRewards::get().is_empty());
+			assert!(matches!(
+				staking_events_since_last_call().last().unwrap(),
+				Event::RewardConversionFailed { stash: 11, amount, .. } if *amount == total_payout_0
+			));
+
+			// with a lower minimum rate, the reward is converted.
+			assert_ok!(Staking::set_reward_conversion(
+				RuntimeOrigin::signed(11),
+				Some(conversion(1, FixedU128::from_rational(3, 2)))
+			));
+			let total_payout_1 = current_total_payout_for_duration(reward_time_per_era());
+			Pallet::<Test>::reward_by_ids(vec![(11, 1)]);
+			mock::start_active_era(2);
+			mock::make_all_reward_payment(1);
+
+			assert_eq!(Balances::free_balance(11), 1000 + total_payout_0);
+			let expected = 2 * total_payout_1 - Perbill::from_percent(5) * (2 * total_payout_1);
+			assert_eq!(ConvertedRewards::get().get(&(11, 1)), Some(&expected));
+		});
+	}
+
+	#[test]
+	fn nominator_reward_is_kept_below_min_rate() {
+		ExtBuilder::default().build_and_execute(|| {
+			RewardAssetPrices::mutate(|p| p.insert(1, 2));
+			RewardSwapSlippage::set(Perbill::from_percent(5));
+			<Payee<Test>>::insert(&101, RewardDestination::Stash);
+			// the best rate is 1.9, below the minimum rate of 101.
+			assert_ok!(Staking::set_reward_conversion(
+				RuntimeOrigin::signed(101),
+				Some(conversion(1, FixedU128::from_rational(195, 100)))
+			));
+			let free_101 = Balances::free_balance(101);
+
+			Pallet::<Test>::reward_by_ids(vec![(11, 1)]);
+			mock::start_active_era(1);
+			let _ = staking_events_since_last_call();
+			mock::make_all_reward_payment(0);
+
+			let events = staking_events_since_last_call();
+			let reward: Balance = events
+				.iter()
+				.filter_map(|e| match e {
+					Event::Rewarded { stash: 101, amount, .. } => Some(*amount),
+					_ => None,
+				})
+				.sum();
+			assert!(reward > 0);
+
+			// the reward of the nominator falls back to the native currency.
+			assert_eq!(Balances::free_balance(101), free_101 + reward);
+			assert!(ConvertedRewards::get().is_empty());
+			assert!(events.iter().any(|e| matches!(
+				e,
+				Event::RewardConversionFailed { stash: 101, amount, .. } if *amount == reward
+			)));
+			assert!(!events.iter().any(|e| matches!(e, Event::RewardConverted { .. })));
+		});
+	}
+
+	#[test]
+	fn rewards_of_other_payees_are_not_converted() {
+		ExtBuilder::default().nominate(false).build_and_execute(|| {
+			RewardAssetPrices::mutate(|p| p.insert(1, 2));
+			// 11 pays its rewards into 21, which converts its own rewards as well.
+			<Payee<Test>>::insert(&11, RewardDestination::Account(21));
+			for stash in [11, 21] {
+				assert_ok!(Staking::set_reward_conversion(
+					RuntimeOrigin::signed(stash),
+					Some(conversion(1, FixedU128::one()))
+				));
+			}
+			let free_11 = Balances::free_balance(11);
+			let free_21 = Balances::free_balance(21);
+
+			let total_payout_0 = current_total_payout_for_duration(reward_time_per_era());
+			Pallet::<Test>::reward_by_ids(vec![(11, 1)]);
+			mock::start_active_era(1);
+			let _ = staking_events_since_last_call();
+			mock::make_all_reward_payment(0);
+
+			// neither the conversion of 11 nor the one of 21 applies to the reward.
+			assert_eq!(Balances::free_balance(11), free_11);
+			assert_eq!(Balances::free_balance(21), free_21 + total_payout_0);
+			assert!(ConvertedRewards::get().is_empty());
+			assert!(!staking_events_since_last_call().iter().any(|e| matches!(
+				e,
+				Event::RewardConverted { .. } | Event::RewardConversionFailed { .. }
+			)));
+
+			// rewards paid into the stash as an account are converted though.
+			<Payee<Test>>::insert(&11, RewardDestination::Account(11));
+			let total_payout_1 = current_total_payout_for_duration(reward_time_per_era());
+			Pallet::<Test>::reward_by_ids(vec![(11, 1)]);
+			mock::start_active_era(2);
+			mock::make_all_reward_payment(1);
+
+			assert_eq!(Balances::free_balance(11), free_11);
+			assert_eq!(Balances::free_balance(21), free_21 + total_payout_0);
+			assert_eq!(ConvertedRewards::get().get(&(11, 1)), Some(&(2 * total_payout_1)));
+			assert_eq!(ConvertedRewards::get().get(&(21, 1)), None);
+		});
+	}
+
+	#[test]
+	fn staked_rewards_are_not_converted() {
+		ExtBuilder::default().nominate(false).build_and_execute(|| {
+			RewardAssetPrices::mutate(|p| p.insert(1, 2));
+			assert_eq!(Staking::payee(11.into()), Some(RewardDestination::Staked));
+			assert_ok!(Staking::set_reward_conversion(
+				RuntimeOrigin::signed(11),
+				Some(conversion(1, FixedU128::one()))
+			));
+
+			let total_payout_0 = current_total_payout_for_duration(reward_time_per_era());
+			Pallet::<Test>::reward_by_ids(vec![(11, 1)]);
+			mock::start_active_era(1);
+			let _ = staking_events_since_last_call();
+			mock::make_all_reward_payment(0);
+
+			// the reward is bonded, and nothing is converted.
+			assert_eq!(Staking::ledger(11.into()).unwrap().active, 1000 + total_payout_0);
+			assert!(ConvertedRewards::get().is_empty());
+			assert!(!staking_events_since_last_call().iter().any(|e| matches!(
+				e,
+				Event::RewardConverted { .. } | Event::RewardConversionFailed { .. }
+			)));
+		});
+	}
+
+	#[test]
+	fn reward_conversion_is_removed_with_ledger() {
+		ExtBuilder::default().build_and_execute(|| {
+			RewardAssetPrices::mutate(|p| p.insert(1, 2));
+			assert_ok!(Staking::set_reward_conversion(
+				RuntimeOrigin::signed(11),
+				Some(conversion(1, FixedU128::one()))
+			));
+			assert!(RewardConversions::<Test>::contains_key(&11));
+
+			assert_ok!(Staking::force_unstake(RuntimeOrigin::root(), 11, 0));
+			assert_eq!(Staking::bonded(&11), None);
+			assert!(!RewardConversions::<Test>::contains_key(&11));
+		});
+	}
+}
+
+mod commission_changes {
+	use super::*;
+
+	fn prefs(commission: u32) -> ValidatorPrefs {
+		ValidatorPrefs { commission: Perbill::from_percent(commission), blocked: false }
+	}
+
+	#[test]
+	fn validate_cannot_increase_commission_with_delay() {
+		ExtBuilder::default().build_and_execute(|| {
+			// without a delay, the commission can be increased right away.
+			assert_ok!(Staking::validate(RuntimeOrigin::signed(11), prefs(10)));
+			assert_eq!(Staking::validators(&11).commission, Perbill::from_percent(10));
+
+			CommissionChangeDelay::set(2);
+
+			// existing validators cannot increase their commission anymore.
+			assert_noop!(
+				Staking::validate(RuntimeOrigin::signed(11), prefs(11)),
+				Error::<Test>::CommissionIncreaseNotScheduled
+			);
+
+			// but can keep or decrease it.
+			assert_ok!(Staking::validate(RuntimeOrigin::signed(11), prefs(10)));
+			assert_ok!(Staking::validate(RuntimeOrigin::signed(11), prefs(5)));
+			assert_eq!(Staking::validators(&11).commission, Perbill::from_percent(5));
+
+			// new validators can set any commission.
+			assert!(!Validators::<Test>::contains_key(&41));
+			assert_ok!(Staking::validate(RuntimeOrigin::signed(41), prefs(50)));
+			assert_eq!(Staking::validators(&41).commission, Perbill::from_percent(50));
+		});
+	}
+
+	#[test]
+	fn chilling_does_not_bypass_commission_schedule() {
+		ExtBuilder::default().build_and_execute(|| {
+			CommissionChangeDelay::set(2);
+			let era = current_era();
+			assert_eq!(CommittedCommissions::<Test>::get(&11), Some(Perbill::zero()));
+			assert_ok!(Staking::schedule_commission_change(
+				RuntimeOrigin::signed(11),
+				Perbill::from_percent(10)
+			));
+
+			// the commission cannot be increased by chilling and validating again.
+			assert_ok!(Staking::chill(RuntimeOrigin::signed(11)));
+			assert!(!Validators::<Test>::contains_key(&11));
+			assert_noop!(
+				Staking::validate(RuntimeOrigin::signed(11), prefs(10)),
+				Error::<Test>::CommissionIncreaseNotScheduled
+			);
+
+			// validating with the committed commission is still possible.
+			assert_ok!(Staking::validate(RuntimeOrigin::signed(11), prefs(0)));
+			assert_ok!(Staking::chill(RuntimeOrigin::signed(11)));
+			assert_eq!(CommittedCommissions::<Test>::get(&11), Some(Perbill::zero()));
+
+			// once due, the scheduled increase applies to chilled validators as well.
+			start_active_era(era + 3);
+			assert_ok!(Staking::validate(RuntimeOrigin::signed(11), prefs(10)));
+			assert_eq!(Staking::validators(&11).commission, Perbill::from_percent(10));
+			assert_eq!(CommittedCommissions::<Test>::get(&11), Some(Perbill::from_percent(10)));
+			assert!(!CommissionChanges::<Test>::contains_key(&11));
+		});
+	}
+
+	#[test]
+	fn schedule_commission_change_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			CommissionChangeDelay::set(2);
+			let era = current_era();
+
+			// only validators can schedule a commission change.
+			assert_noop!(
+				Staking::schedule_commission_change(
+					RuntimeOrigin::signed(101),
+					Perbill::from_percent(10)
+				),
+				Error::<Test>::NotValidator
+			);
+			assert_noop!(
+				Staking::schedule_commission_change(
+					RuntimeOrigin::signed(1337),
+					Perbill::from_percent(10)
+				),
+				Error::<Test>::NotController
+			);
+
+			// the minimum commission is respected.
+			MinCommission::<Test>::put(Perbill::from_percent(5));
+			assert_noop!(
+				Staking::schedule_commission_change(
+					RuntimeOrigin::signed(11),
+					Perbill::from_percent(4)
+				),
+				Error::<Test>::CommissionTooLow
+			);
+
+			assert_ok!(Staking::schedule_commission_change(
+				RuntimeOrigin::signed(11),
+				Perbill::from_percent(10)
+			));
+			let change = CommissionChange { commission: Perbill::from_percent(10), era: era + 3 };
+			assert_eq!(CommissionChanges::<Test>::get(&11), Some(change.clone()));
+			assert_eq!(
+				*staking_events().last().unwrap(),
+				Event::CommissionChangeScheduled { stash: 11, change }
+			);
+
+			// a new change replaces the scheduled one.
+			assert_ok!(Staking::schedule_commission_change(
+				RuntimeOrigin::signed(11),
+				Perbill::from_percent(20)
+			));
+			assert_eq!(
+				CommissionChanges::<Test>::get(&11),
+				Some(CommissionChange { commission: Perbill::from_percent(20), era: era + 3 })
+			);
+		});
+	}
+
+	#[test]
+	fn commission_increase_is_rate_limited() {
+		ExtBuilder::default().build_and_execute(|| {
+			CommissionChangeDelay::set(2);
+
+			// only the admin origin can set the rate.
+			assert_noop!(
+				Staking::set_max_commission_change_rate(
+					RuntimeOrigin::signed(2),
+					Some(Perbill::from_percent(1))
+				),
+				BadOrigin
+			);
+			assert_ok!(Staking::set_max_commission_change_rate(
+				RuntimeOrigin::signed(1),
+				Some(Perbill::from_percent(1))
+			));
+			assert_eq!(MaxCommissionChangeRate::<Test>::get(), Some(Perbill::from_percent(1)));
+
+			// the change is announced two eras in advance, thus can increase by 2%.
+			assert_noop!(
+				Staking::schedule_commission_change(
+					RuntimeOrigin::signed(11),
+					Perbill::from_percent(3)
+				),
+				Error::<Test>::CommissionChangeRateExceeded
+			);
+			assert_ok!(Staking::schedule_commission_change(
+				RuntimeOrigin::signed(11),
+				Perbill::from_percent(2)
+			));
+
+			// decreases are not limited.
+			Validators::<Test>::mutate(&21, |p| p.commission = Perbill::from_percent(50));
+			assert_ok!(Staking::schedule_commission_change(
+				RuntimeOrigin::signed(21),
+				Perbill::zero()
+			));
+
+			// the limit can be removed.
+			assert_ok!(Staking::set_max_commission_change_rate(RuntimeOrigin::root(), None));
+			assert_ok!(Staking::schedule_commission_change(
+				RuntimeOrigin::signed(11),
+				Perbill::from_percent(100)
+			));
+		});
+	}
+
+	#[test]
+	fn scheduled_commission_is_applied_when_due() {
+		ExtBuilder::default().build_and_execute(|| {
+			CommissionChangeDelay::set(2);
+			let era = current_era();
+
+			assert_ok!(Staking::schedule_commission_change(
+				RuntimeOrigin::signed(11),
+				Perbill::from_percent(10)
+			));
+
+			// the eras that are already announced keep the old commission.
+			start_active_era(era + 1);
+			start_active_era(era + 2);
+			assert_eq!(ErasValidatorPrefs::<Test>::get(era + 2, &11).commission, Perbill::zero());
+			assert_eq!(Staking::validators(&11).commission, Perbill::zero());
+			assert!(CommissionChanges::<Test>::contains_key(&11));
+
+			let _ = staking_events_since_last_call();
+			start_active_era(era + 3);
+			assert_eq!(
+				ErasValidatorPrefs::<Test>::get(era + 3, &11).commission,
+				Perbill::from_percent(10)
+			);
+			assert_eq!(Staking::validators(&11).commission, Perbill::from_percent(10));
+			assert!(!CommissionChanges::<Test>::contains_key(&11));
+			assert!(staking_events_since_last_call().contains(&Event::CommissionChanged {
+				stash: 11,
+				commission: Perbill::from_percent(10)
+			}));
+		});
+	}
+
+	#[test]
+	fn commission_changes_api_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			CommissionChangeDelay::set(1);
+			let era = current_era();
+
+			assert_eq!(Staking::api_commission_changes(101), vec![]);
+
+			assert_ok!(Staking::schedule_commission_change(
+				RuntimeOrigin::signed(21),
+				Perbill::from_percent(10)
+			));
+			// 31 is not nominated by 101.
+			assert_ok!(Staking::schedule_commission_change(
+				RuntimeOrigin::signed(31),
+				Perbill::from_percent(20)
+			));
+
+			assert_eq!(
+				Staking::api_commission_changes(101),
+				vec![(21, Perbill::from_percent(10), era + 2)]
+			);
+			// not a nominator.
+			assert_eq!(Staking::api_commission_changes(1337), vec![]);
+		});
+	}
+
+	#[test]
+	fn commission_change_is_removed_with_ledger() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_ok!(Staking::schedule_commission_change(
+				RuntimeOrigin::signed(11),
+				Perbill::from_percent(10)
+			));
+			assert!(CommissionChanges::<Test>::contains_key(&11));
+
+			assert_ok!(Staking::force_unstake(RuntimeOrigin::root(), 11, 0));
+			assert!(!CommissionChanges::<Test>::contains_key(&11));
+			assert!(!CommittedCommissions::<Test>::contains_key(&11));
+		});
+	}
+}
@@ -42,6 +42,24 @@ pub fn expand_hooks(def: &mut Def) -> proc_macro2::TokenStream {
 		>::name::<Self>().unwrap_or("<unknown pallet name>")
 	};
 
+	// Pallets with removed storage items clean up their remaining keys with the weight that is
+	// left after their own `on_idle`.
+	let clear_removed_storage = if def.storages.iter().any(|storage| storage.removed) {
+		quote::quote! {
+			weight.saturating_add(
+				#frame_support::migrations::clear_removed_storage::<Self>(
+					remaining_weight.saturating_sub(weight),
+					<
+						<T as #frame_system::Config>::DbWeight
+						as #frame_support::traits::Get<_>
+					>::get(),
+				)
+			)
+		}
+	} else {
+		quote::quote! { weight }
+	};
+
 	let initialize_on_chain_storage_version = if let Some(in_code_version) =
 		&def.pallet_struct.storage_version
 	{
@@ -167,11 +185,15 @@ pub fn expand_hooks(def: &mut Def) -> proc_macro2::TokenStream {
 				n: #frame_system::pallet_prelude::BlockNumberFor::<T>,
 				remaining_weight: #frame_support::weights::Weight
 			) -> #frame_support::weights::Weight {
-				<
+				#frame_support::__private::sp_tracing::enter_span!(
+					#frame_support::__private::sp_tracing::trace_span!("on_idle")
+				);
+				let weight = <
 					Self as #frame_support::traits::Hooks<
 						#frame_system::pallet_prelude::BlockNumberFor::<T>
 					>
-				>::on_idle(n, remaining_weight)
+				>::on_idle(n, remaining_weight);
+				#clear_removed_storage
 			}
 		}
 
@@ -183,6 +205,9 @@ pub fn expand_hooks(def: &mut Def) -> proc_macro2::TokenStream {
 				n: #frame_system::pallet_prelude::BlockNumberFor::<T>,
 				weight: &mut #frame_support::weights::WeightMeter
 			) {
+				#frame_support::__private::sp_tracing::enter_span!(
+					#frame_support::__private::sp_tracing::trace_span!("on_poll")
+				);
 				<
 					Self as #frame_support::traits::Hooks<
 						#frame_system::pallet_prelude::BlockNumberFor::<T>
@@ -377,6 +377,11 @@ fn augment_final_docs(def: &mut Def) {
 			push_string_literal(&doc_line, storage);
 		},
 	});
+	def.storages.iter_mut().filter(|storage| storage.removed).for_each(|storage| {
+		let doc_line = "This storage item is removed. Its remaining keys are cleaned up lazily and \
+			its prefix must not be reused.";
+		push_string_literal(doc_line, storage);
+	});
 }
 
 ///
@@ -409,7 +414,9 @@ pub fn expand_storages(def: &mut Def) -> proc_macro2::TokenStream {
 	let frame_system = &def.frame_system;
 	let pallet_ident = &def.pallet_struct.pallet;
 
-	let entries_builder = def.storages.iter().map(|storage| {
+	let mut active_entries_builder = Vec::new();
+	let mut removed_entries_builder = Vec::new();
+	for storage in def.storages.iter() {
 		let no_docs = vec![];
 		let docs = if cfg!(feature = "no-metadata-docs") { &no_docs } else { &storage.docs };
 
@@ -419,7 +426,7 @@ pub fn expand_storages(def: &mut Def) -> proc_macro2::TokenStream {
 
 		let cfg_attrs = &storage.cfg_attrs;
 
-		quote::quote_spanned!(storage.attr_span =>
+		let entry_builder = quote::quote_spanned!(storage.attr_span =>
 			#(#cfg_attrs)*
 			{
 				<#full_ident as #frame_support::storage::StorageEntryMetadataBuilder>::build_metadata(
@@ -429,6 +436,42 @@ pub fn expand_storages(def: &mut Def) -> proc_macro2::TokenStream {
 					&mut entries,
 				);
 			}
+		);
+
+		if storage.removed {
+			removed_entries_builder.push(entry_builder);
+		} else {
+			active_entries_builder.push(entry_builder);
+		}
+	}
+
+	let removed_prefixes = def.storages.iter().filter(|storage| storage.removed).map(|storage| {
+		let type_use_gen = &def.type_use_generics(storage.attr_span);
+		let prefix_struct_ident = prefix_ident(storage);
+		let cfg_attrs = &storage.cfg_attrs;
+
+		let maybe_counter = match storage.metadata {
+			Metadata::CountedMap { .. } | Metadata::CountedNMap { .. } => {
+				let counter_prefix_struct_ident = counter_prefix_ident(&storage.ident);
+				quote::quote_spanned!(storage.attr_span =>
+					prefixes.push(
+						<#counter_prefix_struct_ident<#type_use_gen> as
+							#frame_support::traits::StorageInstance>::prefix_hash()
+					);
+				)
+			},
+			_ => proc_macro2::TokenStream::default(),
+		};
+
+		quote::quote_spanned!(storage.attr_span =>
+			#(#cfg_attrs)*
+			{
+				prefixes.push(
+					<#prefix_struct_ident<#type_use_gen> as
+						#frame_support::traits::StorageInstance>::prefix_hash()
+				);
+				#maybe_counter
+			}
 		)
 	});
 
@@ -837,7 +880,9 @@ pub fn expand_storages(def: &mut Def) -> proc_macro2::TokenStream {
 				// A little hacky; don't generate for cfg gated storages to not get compile errors
 				// when building "frame-feature-testing" gated storages in the "frame-support-test"
 				// crate.
-				if storage.try_decode && storage.cfg_attrs.is_empty() {
+				// Removed storages are not decoded since they are in the process of being cleaned
+				// up and their type may be outdated.
+				if storage.try_decode && storage.cfg_attrs.is_empty() && !storage.removed {
 					let ident = &storage.ident;
 					let gen = &def.type_use_generics(storage.attr_span);
 					Some(quote::quote_spanned!(storage.attr_span => #ident<#gen> ))
@@ -903,13 +948,31 @@ pub fn expand_storages(def: &mut Def) -> proc_macro2::TokenStream {
 					entries: {
 						#[allow(unused_mut)]
 						let mut entries = #frame_support::__private::sp_std::vec![];
-						#( #entries_builder )*
+						#( #active_entries_builder )*
+						entries
+					},
+					removed_entries: {
+						#[allow(unused_mut)]
+						let mut entries = #frame_support::__private::sp_std::vec![];
+						#( #removed_entries_builder )*
 						entries
 					},
 				}
 			}
 		}
 
+		impl<#type_impl_gen> #frame_support::traits::RemovedStorageInfo
+			for #pallet_ident<#type_use_gen>
+			#completed_where_clause
+		{
+			fn removed_storage_prefixes() -> #frame_support::__private::sp_std::vec::Vec<[u8; 32]> {
+				#[allow(unused_mut)]
+				let mut prefixes = #frame_support::__private::sp_std::vec::Vec::new();
+				#( #removed_prefixes )*
+				prefixes
+			}
+		}
+
 		#( #getters )*
 		#( #prefix_structs )*
 		#( #on_empty_structs )*
@@ -169,8 +169,9 @@ impl Def {
 					origin = Some(origin::OriginDef::try_from(index, item)?),
 				Some(PalletAttr::Inherent(_)) if inherent.is_none() =>
 					inherent = Some(inherent::InherentDef::try_from(index, item)?),
-				Some(PalletAttr::Storage(span)) =>
-					storages.push(storage::StorageDef::try_from(span, index, item, dev_mode)?),
+				Some(PalletAttr::Storage(span, removed)) => storages.push(
+					storage::StorageDef::try_from(span, index, item, dev_mode, removed)?,
+				),
 				Some(PalletAttr::ValidateUnsigned(_)) if validate_unsigned.is_none() => {
 					let v = validate_unsigned::ValidateUnsignedDef::try_from(index, item)?;
 					validate_unsigned = Some(v);
@@ -553,6 +554,7 @@ mod keyword {
 	syn::custom_keyword!(inherent);
 	syn::custom_keyword!(error);
 	syn::custom_keyword!(storage);
+	syn::custom_keyword!(removed);
 	syn::custom_keyword!(genesis_build);
 	syn::custom_keyword!(genesis_config);
 	syn::custom_keyword!(validate_unsigned);
@@ -615,7 +617,9 @@ enum PalletAttr {
 	RuntimeEvent(proc_macro2::Span),
 	RuntimeOrigin(proc_macro2::Span),
 	Inherent(proc_macro2::Span),
-	Storage(proc_macro2::Span),
+	/// A `#[pallet::storage]`, or a `#[pallet::storage(removed)]` for a storage item that is no
+	/// longer in use and whose keys should be cleaned up.
+	Storage(proc_macro2::Span, bool),
 	GenesisConfig(proc_macro2::Span),
 	GenesisBuild(proc_macro2::Span),
 	ValidateUnsigned(proc_macro2::Span),
@@ -640,7 +644,7 @@ impl PalletAttr {
 			Self::RuntimeEvent(span) => *span,
 			Self::RuntimeOrigin(span) => *span,
 			Self::Inherent(span) => *span,
-			Self::Storage(span) => *span,
+			Self::Storage(span, _) => *span,
 			Self::GenesisConfig(span) => *span,
 			Self::GenesisBuild(span) => *span,
 			Self::ValidateUnsigned(span) => *span,
@@ -699,7 +703,14 @@ impl syn::parse::Parse for PalletAttr {
 		} else if lookahead.peek(keyword::inherent) {
 			Ok(PalletAttr::Inherent(content.parse::<keyword::inherent>()?.span()))
 		} else if lookahead.peek(keyword::storage) {
-			Ok(PalletAttr::Storage(content.parse::<keyword::storage>()?.span()))
+			let span = content.parse::<keyword::storage>()?.span();
+			let removed = content.peek(syn::token::Paren);
+			if removed {
+				let inside_storage;
+				let _paren = syn::parenthesized!(inside_storage in content);
+				inside_storage.parse::<keyword::removed>()?;
+			}
+			Ok(PalletAttr::Storage(span, removed))
 		} else if lookahead.peek(keyword::genesis_config) {
 			Ok(PalletAttr::GenesisConfig(content.parse::<keyword::genesis_config>()?.span()))
 		} else if lookahead.peek(keyword::genesis_build) {
@@ -207,6 +207,11 @@ pub struct StorageDef {
 	pub try_decode: bool,
 	/// Whether or not a default hasher is allowed to replace `_`
 	pub use_default_hasher: bool,
+	/// Whether the storage was declared with `#[pallet::storage(removed)]`.
+	///
+	/// Removed storages keep their prefix reserved and are cleaned up lazily, but are not part
+	/// of the active storage metadata anymore.
+	pub removed: bool,
 }
 
 /// The parsed generic from the
@@ -786,6 +791,7 @@ impl StorageDef {
 		index: usize,
 		item: &mut syn::Item,
 		dev_mode: bool,
+		removed: bool,
 	) -> syn::Result<Self> {
 		let item = if let syn::Item::Type(item) = item {
 			item
@@ -797,6 +803,11 @@ impl StorageDef {
 		let PalletStorageAttrInfo { getter, rename_as, mut unbounded, whitelisted, try_decode } =
 			PalletStorageAttrInfo::from_attrs(attrs)?;
 
+		if removed && getter.is_some() {
+			let msg = "Invalid pallet::storage, a removed storage can not have a getter";
+			return Err(syn::Error::new(attr_span, msg))
+		}
+
 		// set all storages to be unbounded if dev_mode is enabled
 		unbounded |= dev_mode;
 		let cfg_attrs = helper::get_item_cfg_attrs(&item.attrs);
@@ -942,6 +953,7 @@ impl StorageDef {
 			whitelisted,
 			try_decode,
 			use_default_hasher,
+			removed,
 		})
 	}
 }
@@ -2373,6 +2373,35 @@ pub mod pallet_macros {
 	///
 	/// #### Example
 	#[doc = docify::embed!("src/lib.rs", example_storage_value_map_prefixes)]
+	/// ## Removed Storage Items
+	///
+	/// A storage item that is no longer used can be declared with `#[pallet::storage(removed)]`
+	/// instead of being deleted from the code. This keeps its prefix reserved, so that it is not
+	/// accidentally reused while old keys still linger in the state. Removed storage items are
+	/// listed after the active ones in the storage metadata, with a doc line marking them as
+	/// removed, and they are not decoded by the try-runtime checks.
+	///
+	/// The remaining keys of all removed storage items are cleaned up automatically in `on_idle`
+	/// with the weight that is left after the pallet's own `on_idle` hook. To clean them up with a
+	/// guaranteed amount of weight per block instead, add
+	/// [`ClearRemovedStorage`](frame_support::migrations::ClearRemovedStorage) to the stepped
+	/// migrations of the runtime. Once the cleanup completed, the storage item can be deleted from
+	/// the code.
+	///
+	/// ```
+	/// #[frame_support::pallet]
+	/// mod pallet {
+	///     # use frame_support::pallet_prelude::*;
+	///     # #[pallet::config]
+	///     # pub trait Config: frame_system::Config {}
+	///     # #[pallet::pallet]
+	///     # pub struct Pallet<T>(_);
+	///     /// Not used anymore.
+	///     #[pallet::storage(removed)]
+	///     pub type OldMap<T> = StorageMap<_, Twox64Concat, u32, u32>;
+	/// }
+	/// ```
+	///
 	/// ## Related Macros
 	///
 	/// The following attribute macros can be used in conjunction with the `#[storage]` macro:
@@ -28,8 +28,12 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use impl_trait_for_tuples::impl_for_tuples;
 use sp_arithmetic::traits::Bounded;
 use sp_core::Get;
-use sp_io::{hashing::twox_128, storage::clear_prefix, KillStorageResult};
-use sp_runtime::traits::Zero;
+use sp_io::{
+	hashing::{blake2_256, twox_128},
+	storage::clear_prefix,
+	KillStorageResult,
+};
+use sp_runtime::{traits::Zero, DispatchError, RuntimeDebug, Saturating};
 use sp_std::{marker::PhantomData, vec::Vec};
 
 /// Handles storage migration pallet versioning.
@@ -416,6 +420,24 @@ pub trait SteppedMigration {
 		})
 		.map_err(|()| SteppedMigrationError::Failed)?
 	}
+
+	/// Hook for testing that is run before the first step of the migration is executed.
+	///
+	/// The returned bytes are stored and passed to [`Self::post_upgrade`] once the migration
+	/// completed. This can be used to compare the state before and after the migration, even
+	/// though it spans multiple blocks.
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		Ok(Vec::new())
+	}
+
+	/// Hook for testing that is run after the migration returned its final `None` cursor.
+	///
+	/// Is passed the bytes that were returned by [`Self::pre_upgrade`].
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		Ok(())
+	}
 }
 
 /// Error that can occur during a [`SteppedMigration`].
@@ -451,6 +473,118 @@ pub struct MigrationId<const N: usize> {
 	pub version_to: u8,
 }
 
+/// A [`SteppedMigration`] that lazily removes all keys of storage items that were declared with
+/// `#[pallet::storage(removed)]`.
+///
+/// `P` is usually a single pallet or `AllPalletsWithSystem` to clean up all pallets of a runtime
+/// at once. It can be plugged into the `Migrations` of `pallet-migrations` and removes as many keys
+/// per block as the weight meter allows, assuming that each removed key costs one database
+/// write. This is only needed when the cleanup should not depend on the idle weight of blocks,
+/// since every pallet already cleans up its removed storage items in `on_idle`, see
+/// [`clear_removed_storage`].
+///
+/// The identifier of the migration depends on the set of removed prefixes. It will therefore run
+/// again once more storage items are marked as removed.
+pub struct ClearRemovedStorage<P, DbWeight>(PhantomData<(P, DbWeight)>);
+
+impl<P: crate::traits::RemovedStorageInfo, DbWeight: Get<RuntimeDbWeight>> SteppedMigration
+	for ClearRemovedStorage<P, DbWeight>
+{
+	/// The index of the prefix that is currently being cleared.
+	type Cursor = u32;
+	type Identifier = [u8; 32];
+
+	fn id() -> Self::Identifier {
+		(b"ClearRemovedStorage", P::removed_storage_prefixes()).using_encoded(blake2_256)
+	}
+
+	fn step(
+		cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+		let prefixes = P::removed_storage_prefixes();
+		let per_key = DbWeight::get().writes(1);
+		let mut index = cursor.unwrap_or_default();
+		let mut progressed = false;
+
+		loop {
+			let Some(prefix) = prefixes.get(index as usize) else { return Ok(None) };
+
+			let limit = meter
+				.remaining()
+				.checked_div_per_component(&per_key)
+				.unwrap_or(u64::MAX)
+				.min(u32::MAX as u64) as u32;
+			if limit.is_zero() {
+				return if progressed {
+					Ok(Some(index))
+				} else {
+					Err(SteppedMigrationError::InsufficientWeight { required: per_key })
+				}
+			}
+
+			let result = crate::storage::unhashed::clear_prefix(prefix, Some(limit), None);
+			meter.consume(per_key.saturating_mul(result.loops as u64));
+			progressed = true;
+
+			if result.maybe_cursor.is_some() {
+				return Ok(Some(index))
+			}
+			index.saturating_inc();
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		use crate::storage::unhashed::contains_prefixed_key;
+
+		for prefix in P::removed_storage_prefixes() {
+			if contains_prefixed_key(&prefix) {
+				log::error!("Removed storage {:?} has keys remaining after the cleanup", prefix);
+				return Err("Keys of removed storage remaining after the cleanup".into())
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// Lazily removes the remaining keys of the storage items of `P` that were declared with
+/// `#[pallet::storage(removed)]`, using at most `limit` weight.
+///
+/// The pallet macro calls this in the `on_idle` hook of every pallet with removed storage items,
+/// so that their keys are cleaned up without registering a migration. Each probed prefix costs one
+/// database read and each removed key one database write. Returns the consumed weight.
+pub fn clear_removed_storage<P: crate::traits::RemovedStorageInfo>(
+	limit: Weight,
+	db_weight: RuntimeDbWeight,
+) -> Weight {
+	let mut meter = WeightMeter::with_limit(limit);
+	let per_key = db_weight.writes(1);
+
+	for prefix in P::removed_storage_prefixes() {
+		if meter.try_consume(db_weight.reads(1)).is_err() {
+			break
+		}
+		let keys = meter
+			.remaining()
+			.checked_div_per_component(&per_key)
+			.unwrap_or(u64::MAX)
+			.min(u32::MAX as u64) as u32;
+		if keys.is_zero() {
+			break
+		}
+
+		let result = crate::storage::unhashed::clear_prefix(&prefix, Some(keys), None);
+		meter.consume(per_key.saturating_mul(result.loops as u64));
+		if result.maybe_cursor.is_some() {
+			break
+		}
+	}
+
+	meter.consumed()
+}
+
 /// Notification handler for status updates regarding Multi-Block-Migrations.
 #[impl_trait_for_tuples::impl_for_tuples(8)]
 pub trait MigrationStatusHandler {
@@ -532,6 +666,37 @@ pub enum FailedMigrationHandling {
 	Ignore,
 }
 
+/// Report of a dry-run of a runtime upgrade.
+///
+/// Returned by `frame_system_rpc_runtime_api::UpgradeDryRunApi`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct UpgradeDryRunReport<EventRecord> {
+	/// Weight consumed by the `on_runtime_upgrade` hooks and migrations.
+	pub upgrade_weight: Weight,
+	/// Weight of the first block after the upgrade before applying any extrinsic.
+	///
+	/// Includes the `upgrade_weight`, the `on_initialize` hooks and either the first step of the
+	/// multi-block migrations or the `on_poll` hooks.
+	pub block_weight: Weight,
+	/// The maximum weight of a block.
+	pub max_block_weight: Weight,
+	/// Whether the upgrade started multi-block migrations.
+	pub multi_block_migrations: bool,
+	/// Events emitted by the upgrade and the hooks.
+	pub events: Vec<EventRecord>,
+	/// Result of the `try_state` hooks of all pallets after the first block.
+	///
+	/// `None` if the runtime was not compiled with the `try-runtime` feature.
+	pub try_state: Option<Result<(), DispatchError>>,
+}
+
+impl<EventRecord> UpgradeDryRunReport<EventRecord> {
+	/// Whether the first block after the upgrade fits into the maximum block weight.
+	pub fn fits_in_block(&self) -> bool {
+		self.block_weight.all_lte(self.max_block_weight)
+	}
+}
+
 /// Something that can do multi step migrations.
 pub trait MultiStepMigrator {
 	/// Hint for whether [`Self::step`] should be called.
@@ -541,6 +706,14 @@ pub trait MultiStepMigrator {
 	///
 	/// Must gracefully handle the case that it is currently not upgrading.
 	fn step() -> Weight;
+
+	/// Whether the MBM process is stuck after a migration failed.
+	///
+	/// A stuck process is still [`Self::ongoing`], but will never complete.
+	#[cfg(feature = "try-runtime")]
+	fn is_stuck() -> bool {
+		false
+	}
 }
 
 impl MultiStepMigrator for () {
@@ -586,6 +759,18 @@ pub trait SteppedMigrations {
 		meter: &mut WeightMeter,
 	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>>;
 
+	/// Call the pre-upgrade hooks of the `n`th migration.
+	///
+	/// Is guaranteed to return `Some` if `n < Self::len()`.
+	#[cfg(feature = "try-runtime")]
+	fn nth_pre_upgrade(n: u32) -> Option<Result<Vec<u8>, sp_runtime::TryRuntimeError>>;
+
+	/// Call the post-upgrade hooks of the `n`th migration.
+	///
+	/// Is guaranteed to return `Some` if `n < Self::len()`.
+	#[cfg(feature = "try-runtime")]
+	fn nth_post_upgrade(n: u32, state: Vec<u8>) -> Option<Result<(), sp_runtime::TryRuntimeError>>;
+
 	/// The maximal encoded length across all cursors.
 	fn cursor_max_encoded_len() -> usize;
 
@@ -649,6 +834,19 @@ impl SteppedMigrations for () {
 		None
 	}
 
+	#[cfg(feature = "try-runtime")]
+	fn nth_pre_upgrade(_n: u32) -> Option<Result<Vec<u8>, sp_runtime::TryRuntimeError>> {
+		None
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn nth_post_upgrade(
+		_n: u32,
+		_state: Vec<u8>,
+	) -> Option<Result<(), sp_runtime::TryRuntimeError>> {
+		None
+	}
+
 	fn cursor_max_encoded_len() -> usize {
 		0
 	}
@@ -719,6 +917,26 @@ impl<T: SteppedMigration> SteppedMigrations for T {
 		)
 	}
 
+	#[cfg(feature = "try-runtime")]
+	fn nth_pre_upgrade(n: u32) -> Option<Result<Vec<u8>, sp_runtime::TryRuntimeError>> {
+		if n != 0 {
+			defensive!("nth_pre_upgrade should only be called with n==0");
+			return None
+		}
+
+		Some(T::pre_upgrade())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn nth_post_upgrade(n: u32, state: Vec<u8>) -> Option<Result<(), sp_runtime::TryRuntimeError>> {
+		if n != 0 {
+			defensive!("nth_post_upgrade should only be called with n==0");
+			return None
+		}
+
+		Some(T::post_upgrade(state))
+	}
+
 	fn cursor_max_encoded_len() -> usize {
 		T::Cursor::max_encoded_len()
 	}
@@ -798,6 +1016,36 @@ impl SteppedMigrations for Tuple {
 		None
 	}
 
+	#[cfg(feature = "try-runtime")]
+	fn nth_pre_upgrade(n: u32) -> Option<Result<Vec<u8>, sp_runtime::TryRuntimeError>> {
+		let mut i = 0;
+
+		for_tuples!( #(
+			if (i + Tuple::len()) > n {
+				return Tuple::nth_pre_upgrade(n - i)
+			}
+
+			i += Tuple::len();
+		)* );
+
+		None
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn nth_post_upgrade(n: u32, state: Vec<u8>) -> Option<Result<(), sp_runtime::TryRuntimeError>> {
+		let mut i = 0;
+
+		for_tuples!( #(
+			if (i + Tuple::len()) > n {
+				return Tuple::nth_post_upgrade(n - i, state)
+			}
+
+			i += Tuple::len();
+		)* );
+
+		None
+	}
+
 	fn cursor_max_encoded_len() -> usize {
 		let mut max_len = 0;
 
@@ -0,0 +1,231 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime side metering of storage accesses.
+//!
+//! All storage host functions ([`sp_io::storage`] and [`sp_io::default_child_storage`]) called
+//! while running inside of [`with_metering`] are counted, no matter if they are called through the
+//! FRAME storage abstractions or directly. The counts can be converted into `ref_time` with the
+//! benchmarked database weights of the runtime, see [`StorageAccesses::weight`].
+//!
+//! Every host call is counted, also when the key was already accessed and is served from the
+//! overlay. The counts are thus an upper bound of the database accesses done, and never lower than
+//! the accesses counted by the benchmarks for the same execution. Removing keys by prefix removes
+//! an unknown number of keys, it is counted as [`StorageAccesses::UNBOUNDED`].
+//!
+//! The host calls are observed with [`sp_runtime_interface::observer`], another observer set while
+//! metering stops the metering until it is unset again.
+//!
+//! `frame-executive` only meters the application of extrinsics when its `storage-metering` feature
+//! is enabled, since metering is of no use unless the runtime also uses an extension like
+//! `frame_system::ReclaimRefTime` with a [`BenchmarkedStorageAccesses`] implementation.
+//!
+//! # Example
+//!
+//! ```
+//! use frame_support::storage::{metering::{self, StorageAccesses}, unhashed};
+//!
+//! # sp_io::TestExternalities::default().execute_with(|| {
+//! let (_, accesses) = metering::with_metering(|| {
+//!     unhashed::put(b"key", &1u32);
+//!     unhashed::get::<u32>(b"key");
+//!     // Direct host calls are metered as well.
+//!     sp_io::storage::exists(b"key");
+//!     // Nested metering reports its own accesses and counts towards the outer metering.
+//!     let (_, inner) = metering::with_metering(|| unhashed::kill(b"key"));
+//!     assert_eq!(inner, StorageAccesses { reads: 0, writes: 1 });
+//! });
+//! assert_eq!(accesses, StorageAccesses { reads: 2, writes: 2 });
+//!
+//! // Accesses outside of `with_metering` are not counted.
+//! assert!(metering::current().is_none());
+//! # });
+//! ```
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime_interface::observer::{self, HostCallObserver};
+use sp_weights::{RuntimeDbWeight, Weight};
+
+environmental::environmental!(STORAGE_ACCESSES: StorageAccesses);
+
+/// Number of storage accesses done while metering.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct StorageAccesses {
+	/// Number of storage reads.
+	pub reads: u64,
+	/// Number of storage writes.
+	pub writes: u64,
+}
+
+impl StorageAccesses {
+	/// Accesses of unknown number, e.g. the removal of all keys with some prefix.
+	pub const UNBOUNDED: Self = Self { reads: u64::MAX, writes: u64::MAX };
+
+	/// Accesses with the given number of `reads` and `writes`.
+	pub const fn from_parts(reads: u64, writes: u64) -> Self {
+		Self { reads, writes }
+	}
+
+	/// Saturating addition of the accesses in `other`.
+	pub fn saturating_add(self, other: Self) -> Self {
+		Self {
+			reads: self.reads.saturating_add(other.reads),
+			writes: self.writes.saturating_add(other.writes),
+		}
+	}
+
+	/// Saturating multiplication of the accesses by `n`.
+	pub fn saturating_mul(self, n: u64) -> Self {
+		Self { reads: self.reads.saturating_mul(n), writes: self.writes.saturating_mul(n) }
+	}
+
+	/// The maximum of the reads and writes of both accesses.
+	pub fn max(self, other: Self) -> Self {
+		Self { reads: self.reads.max(other.reads), writes: self.writes.max(other.writes) }
+	}
+
+	/// Saturating subtraction of the accesses in `other`.
+	pub fn saturating_sub(self, other: Self) -> Self {
+		Self {
+			reads: self.reads.saturating_sub(other.reads),
+			writes: self.writes.saturating_sub(other.writes),
+		}
+	}
+
+	/// The weight of the accesses using the per operation costs of `db_weight`.
+	pub fn weight(&self, db_weight: RuntimeDbWeight) -> Weight {
+		db_weight.reads_writes(self.reads, self.writes)
+	}
+}
+
+/// The storage accesses that the benchmarked weight of a call accounts for.
+///
+/// Used to find the share of a call's weight that was spent on storage accesses, as opposed to
+/// computation which is not metered. The counts of a pallet can be generated by running its
+/// benchmarks with the `substrate/.maintain/frame-storage-accesses-template.hbs` template.
+pub trait BenchmarkedStorageAccesses<Call> {
+	/// The worst case storage accesses of `call` as assumed by its benchmarked weight.
+	///
+	/// Returns `None` if unknown.
+	fn benchmarked_accesses(call: &Call) -> Option<StorageAccesses>;
+}
+
+impl<Call> BenchmarkedStorageAccesses<Call> for () {
+	fn benchmarked_accesses(_: &Call) -> Option<StorageAccesses> {
+		None
+	}
+}
+
+/// Run `f` while counting all storage accesses it does.
+///
+/// Returns the result of `f` and the accesses done by `f`. Nested calls count towards all outer
+/// metering as well.
+pub fn with_metering<R>(f: impl FnOnce() -> R) -> (R, StorageAccesses) {
+	match current() {
+		Some(before) => {
+			let res = f();
+			let after = current().unwrap_or(before);
+			(res, after.saturating_sub(before))
+		},
+		None => {
+			let mut accesses = StorageAccesses::default();
+			let res =
+				STORAGE_ACCESSES::using(&mut accesses, || observer::observe(&mut StorageMeter, f));
+			(res, accesses)
+		},
+	}
+}
+
+/// The accesses counted so far by the outermost [`with_metering`].
+///
+/// Returns `None` if storage accesses are not metered currently.
+pub fn current() -> Option<StorageAccesses> {
+	STORAGE_ACCESSES::with(|accesses| *accesses)
+}
+
+/// Counts the storage host calls into `STORAGE_ACCESSES`.
+struct StorageMeter;
+
+impl HostCallObserver for StorageMeter {
+	fn on_host_call(&mut self, interface: &'static str, function: &'static str) {
+		if interface != "sp_io::storage" && interface != "sp_io::default_child_storage" {
+			return
+		}
+		let noted = match function {
+			"get" | "read" | "exists" | "next_key" => StorageAccesses::from_parts(1, 0),
+			"set" | "clear" | "append" => StorageAccesses::from_parts(0, 1),
+			"clear_prefix" | "storage_kill" => StorageAccesses::UNBOUNDED,
+			_ => return,
+		};
+		STORAGE_ACCESSES::with(|accesses| *accesses = accesses.saturating_add(noted));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::storage::{child, unhashed};
+	use sp_core::storage::ChildInfo;
+
+	#[test]
+	fn meters_direct_host_calls() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let (_, accesses) = with_metering(|| {
+				sp_io::storage::set(b"key", b"value");
+				sp_io::storage::append(b"list", 1u8.encode());
+				sp_io::storage::get(b"key");
+				sp_io::storage::next_key(b"key");
+				sp_io::storage::root(sp_runtime::StateVersion::V1);
+				sp_io::hashing::twox_128(b"key");
+			});
+
+			assert_eq!(accesses, StorageAccesses { reads: 2, writes: 2 });
+		})
+	}
+
+	#[test]
+	fn meters_child_storage() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let child_info = ChildInfo::new_default(b"child");
+			let (_, accesses) = with_metering(|| {
+				child::put(&child_info, b"key", &1u32);
+				child::get::<u32>(&child_info, b"key");
+				child::exists(&child_info, b"key");
+				child::kill(&child_info, b"key");
+			});
+
+			assert_eq!(accesses, StorageAccesses { reads: 2, writes: 2 });
+		})
+	}
+
+	#[test]
+	fn prefix_removal_is_unbounded() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			unhashed::put(b"prefix1", &1u32);
+			let (_, accesses) = with_metering(|| {
+				let (_, inner) = with_metering(|| {
+					let _ = unhashed::clear_prefix(b"prefix", Some(1), None);
+				});
+				assert_eq!(inner, StorageAccesses::UNBOUNDED);
+				unhashed::get::<u32>(b"prefix1");
+			});
+
+			assert_eq!(accesses, StorageAccesses::UNBOUNDED);
+		})
+	}
+}
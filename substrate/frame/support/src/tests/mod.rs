@@ -676,6 +676,7 @@ fn expected_metadata() -> PalletStorageMetadataIR {
 				docs: vec![" Numbers to be added into the total."],
 			},
 		],
+		removed_entries: vec![],
 	}
 }
 
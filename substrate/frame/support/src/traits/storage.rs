@@ -107,6 +107,29 @@ pub struct StorageInfo {
 	pub max_size: Option<u32>,
 }
 
+/// Provides the prefixes of all storage items that were declared with
+/// `#[pallet::storage(removed)]`.
+///
+/// Implemented by the pallet macro for every pallet. Can be used with
+/// [`ClearRemovedStorage`](crate::migrations::ClearRemovedStorage) to lazily clean up the keys of
+/// removed storage items, either of a single pallet or of `AllPalletsWithSystem`.
+pub trait RemovedStorageInfo {
+	/// The final prefixes (`twox128(pallet_prefix) ++ twox128(STORAGE_PREFIX)`) of all removed
+	/// storage items.
+	fn removed_storage_prefixes() -> Vec<[u8; 32]>;
+}
+
+#[cfg_attr(all(not(feature = "tuples-96"), not(feature = "tuples-128")), impl_for_tuples(64))]
+#[cfg_attr(all(feature = "tuples-96", not(feature = "tuples-128")), impl_for_tuples(96))]
+#[cfg_attr(feature = "tuples-128", impl_for_tuples(128))]
+impl RemovedStorageInfo for Tuple {
+	fn removed_storage_prefixes() -> Vec<[u8; 32]> {
+		let mut res = vec![];
+		for_tuples!( #( res.extend(Tuple::removed_storage_prefixes()); )* );
+		res
+	}
+}
+
 /// A trait to give information about storage.
 ///
 /// It can be used to calculate PoV worst case size.
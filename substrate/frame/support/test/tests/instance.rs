@@ -466,6 +466,7 @@ fn expected_metadata() -> PalletStorageMetadataIR {
 				docs: vec![],
 			},
 		],
+		removed_entries: vec![],
 	}
 }
 
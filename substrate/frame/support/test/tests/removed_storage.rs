@@ -0,0 +1,213 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for `#[pallet::storage(removed)]` and [`ClearRemovedStorage`].
+
+use frame_support::{
+	construct_runtime, derive_impl,
+	migrations::{ClearRemovedStorage, SteppedMigration, SteppedMigrationError},
+	storage::unhashed,
+	traits::{OnIdle, RemovedStorageInfo, StorageInstance},
+	weights::{constants::RocksDbWeight, Weight, WeightMeter},
+};
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+#[frame_support::pallet]
+mod pallet {
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {}
+
+	#[pallet::storage]
+	pub type Active<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Old map that is not used anymore.
+	#[pallet::storage(removed)]
+	pub type OldMap<T> = StorageMap<_, Twox64Concat, u32, u32>;
+
+	#[pallet::storage(removed)]
+	pub type OldCountedMap<T> = CountedStorageMap<_, Twox64Concat, u32, u32>;
+}
+
+impl pallet::Config for Runtime {}
+
+construct_runtime!(
+	pub enum Runtime
+	{
+		System: frame_system,
+		Example: pallet,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {
+	type Block = Block;
+	type DbWeight = RocksDbWeight;
+}
+
+type Cleanup = ClearRemovedStorage<Example, RocksDbWeight>;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+	sp_io::TestExternalities::from(storage)
+}
+
+fn old_map_prefix() -> [u8; 32] {
+	pallet::_GeneratedPrefixForStorageOldMap::<Runtime>::prefix_hash()
+}
+
+#[test]
+fn removed_storage_metadata_works() {
+	let metadata = Example::storage_metadata();
+
+	let entries = metadata.entries.iter().map(|e| e.name).collect::<Vec<_>>();
+	let removed = metadata.removed_entries.iter().map(|e| e.name).collect::<Vec<_>>();
+
+	assert_eq!(entries, vec!["Active"]);
+	assert_eq!(removed, vec!["OldMap", "OldCountedMap", "CounterForOldCountedMap"]);
+}
+
+#[test]
+fn removed_storage_is_exposed_in_versioned_metadata() {
+	let metadata = frame_metadata::v14::PalletStorageMetadata::from(Example::storage_metadata());
+
+	let entries = metadata.entries.iter().map(|e| e.name).collect::<Vec<_>>();
+	assert_eq!(entries, vec!["Active", "OldMap", "OldCountedMap", "CounterForOldCountedMap"]);
+
+	#[cfg(not(feature = "no-metadata-docs"))]
+	{
+		let old_map = &metadata.entries[1];
+		assert_eq!(old_map.docs[0], " Old map that is not used anymore.");
+		assert!(old_map.docs.last().unwrap().starts_with("This storage item is removed."));
+	}
+}
+
+#[test]
+fn removed_storage_prefixes_works() {
+	new_test_ext().execute_with(|| {
+		let prefixes = <Example as RemovedStorageInfo>::removed_storage_prefixes();
+
+		// The counter of the counted map is removed as well.
+		assert_eq!(prefixes.len(), 3);
+		assert_eq!(prefixes[0], old_map_prefix());
+		// Aggregates over tuples.
+		assert_eq!(
+			<(System, Example) as RemovedStorageInfo>::removed_storage_prefixes(),
+			prefixes
+		);
+		assert!(<System as RemovedStorageInfo>::removed_storage_prefixes().is_empty());
+	});
+}
+
+#[test]
+fn clear_removed_storage_works() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		for i in 0..10 {
+			pallet::OldMap::<Runtime>::insert(i, i);
+			pallet::OldCountedMap::<Runtime>::insert(i, i);
+		}
+		pallet::Active::<Runtime>::put(1);
+	});
+	ext.commit_all().unwrap();
+
+	// Enough weight to remove three keys per step.
+	let per_step = RocksDbWeight::get().writes(3);
+	let mut cursor = None;
+	let mut steps = 0;
+	loop {
+		cursor = ext.execute_with(|| {
+			let mut meter = WeightMeter::with_limit(per_step);
+			Cleanup::step(cursor, &mut meter).unwrap()
+		});
+		// Every step happens in its own block.
+		ext.commit_all().unwrap();
+		steps += 1;
+
+		if cursor.is_none() {
+			break
+		}
+	}
+
+	ext.execute_with(|| {
+		// 21 keys in total, removing three per step.
+		assert_eq!(steps, 7);
+		assert!(!unhashed::contains_prefixed_key(&old_map_prefix()));
+		assert_eq!(pallet::OldCountedMap::<Runtime>::iter().count(), 0);
+		assert_eq!(pallet::OldCountedMap::<Runtime>::count(), 0);
+		// Active storage is not touched.
+		assert_eq!(pallet::Active::<Runtime>::get(), 1);
+	});
+}
+
+#[test]
+fn clear_removed_storage_insufficient_weight_errors() {
+	new_test_ext().execute_with(|| {
+		pallet::OldMap::<Runtime>::insert(1, 1);
+
+		let mut meter = WeightMeter::with_limit(Weight::zero());
+		assert!(matches!(
+			Cleanup::step(None, &mut meter),
+			Err(SteppedMigrationError::InsufficientWeight { .. })
+		));
+		assert_eq!(pallet::OldMap::<Runtime>::get(1), Some(1));
+	});
+}
+
+#[test]
+fn on_idle_clears_removed_storage() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		for i in 0..10 {
+			pallet::OldMap::<Runtime>::insert(i, i);
+			pallet::OldCountedMap::<Runtime>::insert(i, i);
+		}
+		pallet::Active::<Runtime>::put(1);
+	});
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		// Not enough weight to probe a single prefix.
+		assert_eq!(Example::on_idle(1, Weight::zero()), Weight::zero());
+		assert_eq!(pallet::OldMap::<Runtime>::iter().count(), 10);
+
+		// One read for the prefix and four removed keys.
+		let limit = RocksDbWeight::get().reads_writes(1, 4);
+		assert_eq!(Example::on_idle(1, limit), limit);
+		assert_eq!(pallet::OldMap::<Runtime>::iter().count(), 6);
+	});
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		let used = Example::on_idle(2, Weight::MAX);
+		// Three prefixes probed and the remaining 17 keys removed.
+		assert_eq!(used, RocksDbWeight::get().reads_writes(3, 17));
+		assert!(!unhashed::contains_prefixed_key(&old_map_prefix()));
+		assert_eq!(pallet::OldCountedMap::<Runtime>::iter().count(), 0);
+		assert_eq!(pallet::OldCountedMap::<Runtime>::count(), 0);
+		assert_eq!(pallet::Active::<Runtime>::get(), 1);
+
+		// Nothing left to remove.
+		assert_eq!(Example::on_idle(3, Weight::MAX), RocksDbWeight::get().reads(3));
+	});
+}
@@ -23,6 +23,8 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub use frame_support::migrations::UpgradeDryRunReport;
+
 sp_api::decl_runtime_apis! {
 	/// The API to query account nonce.
 	pub trait AccountNonceApi<AccountId, Nonce> where
@@ -32,4 +34,16 @@ sp_api::decl_runtime_apis! {
 		/// Get current account nonce of given `AccountId`.
 		fn account_nonce(account: AccountId) -> Nonce;
 	}
+
+	/// The API to dry-run a runtime upgrade.
+	///
+	/// It is meant to be implemented by the runtime that will be upgraded *to* and to be called
+	/// with the code of this runtime on top of the state of the current chain, see the
+	/// `state_dryRunRuntimeUpgrade` RPC. State changes done by the call are always discarded.
+	pub trait UpgradeDryRunApi<EventRecord> where
+		EventRecord: codec::Codec,
+	{
+		/// Run the runtime upgrade and the hooks of the first block after it.
+		fn dry_run_upgrade() -> UpgradeDryRunReport<EventRecord>;
+	}
 }
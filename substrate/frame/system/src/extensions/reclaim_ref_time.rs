@@ -0,0 +1,286 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Config, LOG_TARGET};
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::{DispatchInfo, PostDispatchInfo},
+	storage::metering::{self, BenchmarkedStorageAccesses, StorageAccesses},
+	traits::Get,
+	CloneNoBound, DefaultNoBound, EqNoBound, PartialEqNoBound,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension},
+	transaction_validity::TransactionValidityError,
+	DispatchResult,
+};
+use sp_std::marker::PhantomData;
+use sp_weights::Weight;
+
+/// Reclaim of the unused storage share of the `ref_time` of a dispatch.
+///
+/// `Accesses` tells which storage accesses the benchmarked weight of a call accounts for. The
+/// storage accesses actually done while dispatching the call are metered by
+/// [`frame_support::storage::metering`]. The benchmarked accesses that did not happen are priced
+/// with the database weights of the runtime ([`Config::DbWeight`]) and refunded to the block
+/// weight. The computational share of the weight is never refunded. Since the metering counts
+/// every storage host call, the refund never exceeds what the benchmarks would have measured for
+/// the same execution, and calls removing keys by prefix are not refunded at all.
+///
+/// Calls returning an `actual_weight` below their benchmarked weight already did their own
+/// accounting, nothing is reclaimed for them. The extension never charges more than the
+/// benchmarked weight and does not change the fee of the transaction.
+///
+/// Should be placed after [`CheckWeight`](crate::CheckWeight) in the signed extensions of the
+/// runtime. Storage accesses are only metered when the extrinsic is applied by `frame-executive`
+/// with its `storage-metering` feature enabled, otherwise nothing is reclaimed. The same holds for
+/// calls that `Accesses` has no counts for, so with `Accesses = ()` the extension is inert.
+#[derive(Encode, Decode, CloneNoBound, EqNoBound, PartialEqNoBound, DefaultNoBound, TypeInfo)]
+#[scale_info(skip_type_params(T, Accesses))]
+pub struct ReclaimRefTime<T, Accesses>(PhantomData<(T, Accesses)>);
+
+impl<
+		T: Config + Send + Sync,
+		Accesses: BenchmarkedStorageAccesses<T::RuntimeCall> + Send + Sync,
+	> ReclaimRefTime<T, Accesses>
+{
+	/// Create a new `ReclaimRefTime` instance.
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+
+	/// The `ref_time` to refund for a dispatch that did `metered` out of `benchmarked` accesses.
+	fn reclaimable(
+		metered: StorageAccesses,
+		benchmarked: StorageAccesses,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		post_info: &PostDispatchInfoOf<T::RuntimeCall>,
+	) -> u64
+	where
+		T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	{
+		// The storage share of a weight returned by the dispatch is unknown, it may already
+		// exclude the accesses that did not happen.
+		if post_info.calc_unspent(info).ref_time() > 0 {
+			return 0
+		}
+		let unused = benchmarked.saturating_sub(metered).weight(T::DbWeight::get()).ref_time();
+		unused.min(info.weight.ref_time())
+	}
+}
+
+impl<T: Config + Send + Sync, Accesses> sp_std::fmt::Debug for ReclaimRefTime<T, Accesses> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "ReclaimRefTime")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T, Accesses> SignedExtension for ReclaimRefTime<T, Accesses>
+where
+	T: Config + Send + Sync,
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	Accesses: BenchmarkedStorageAccesses<T::RuntimeCall> + Send + Sync + 'static,
+{
+	type AccountId = T::AccountId;
+	type Call = T::RuntimeCall;
+	type AdditionalSigned = ();
+	/// The storage accesses metered before the dispatch and the benchmarked accesses of the call.
+	type Pre = Option<(StorageAccesses, StorageAccesses)>;
+	const IDENTIFIER: &'static str = "ReclaimRefTime";
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn pre_dispatch(
+		self,
+		_who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		Ok(metering::current().zip(Accesses::benchmarked_accesses(call)))
+	}
+
+	fn post_dispatch(
+		pre: Option<Self::Pre>,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		_len: usize,
+		_result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		let Some(Some((before, benchmarked))) = pre else { return Ok(()) };
+		let Some(after) = metering::current() else {
+			log::debug!(
+				target: LOG_TARGET,
+				"Storage metering enabled during pre-dispatch, now disabled. This should not happen."
+			);
+			return Ok(())
+		};
+
+		let accesses = after.saturating_sub(before);
+		let reclaimable = Self::reclaimable(accesses, benchmarked, info, post_info);
+		log::trace!(
+			target: LOG_TARGET,
+			"Reclaiming ref_time: {reclaimable}, metered: {accesses:?}, benchmarked: {benchmarked:?}",
+		);
+		if reclaimable > 0 {
+			crate::BlockWeight::<T>::mutate(|current| {
+				current.reduce(Weight::from_parts(reclaimable, 0), info.class)
+			});
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		mock::{new_test_ext, RuntimeCall, Test, CALL},
+		BlockWeight, DispatchClass,
+	};
+	use frame_support::{assert_ok, parameter_types, storage::unhashed};
+
+	parameter_types! {
+		pub static Benchmarked: Option<StorageAccesses> =
+			Some(StorageAccesses { reads: 4, writes: 3 });
+	}
+
+	impl BenchmarkedStorageAccesses<RuntimeCall> for Benchmarked {
+		fn benchmarked_accesses(_: &RuntimeCall) -> Option<StorageAccesses> {
+			Benchmarked::get()
+		}
+	}
+
+	type Reclaim = ReclaimRefTime<Test, Benchmarked>;
+
+	fn dispatch(info: &DispatchInfo, post_info: &PostDispatchInfo, call: impl FnOnce()) {
+		metering::with_metering(|| {
+			let pre = Reclaim::new().pre_dispatch(&1, CALL, info, 0).unwrap();
+			call();
+			assert_ok!(Reclaim::post_dispatch(Some(pre), info, post_info, 0, &Ok(())));
+		});
+	}
+
+	fn block_ref_time() -> u64 {
+		BlockWeight::<Test>::get().get(DispatchClass::Normal).ref_time()
+	}
+
+	fn set_block_ref_time(ref_time: u64) {
+		BlockWeight::<Test>::mutate(|w| {
+			w.set(Weight::from_parts(ref_time, 0), DispatchClass::Normal)
+		});
+	}
+
+	#[test]
+	fn reclaims_unused_storage_accesses() {
+		new_test_ext().execute_with(|| {
+			set_block_ref_time(1000);
+			let info = DispatchInfo { weight: Weight::from_parts(500, 0), ..Default::default() };
+
+			// Out of 4 reads (10 each) and 3 writes (100 each) only one of each happened.
+			dispatch(&info, &Default::default(), || {
+				unhashed::put(b"key", &1u32);
+				let _ = unhashed::get::<u32>(b"key");
+			});
+
+			assert_eq!(block_ref_time(), 1000 - 230);
+		})
+	}
+
+	#[test]
+	fn never_reclaims_computation() {
+		new_test_ext().execute_with(|| {
+			set_block_ref_time(1000);
+			let info = DispatchInfo { weight: Weight::from_parts(500, 0), ..Default::default() };
+
+			// All benchmarked accesses happened, the remaining weight is computation.
+			dispatch(&info, &Default::default(), || {
+				for i in 0u32..3 {
+					unhashed::put(&i.encode(), &i);
+					let _ = unhashed::get::<u32>(&i.encode());
+				}
+				let _ = unhashed::get::<u32>(b"key");
+			});
+
+			assert_eq!(block_ref_time(), 1000);
+		})
+	}
+
+	#[test]
+	fn never_reclaims_more_than_benchmarked() {
+		new_test_ext().execute_with(|| {
+			set_block_ref_time(1000);
+			let info = DispatchInfo { weight: Weight::from_parts(100, 0), ..Default::default() };
+
+			dispatch(&info, &Default::default(), || {});
+
+			assert_eq!(block_ref_time(), 1000 - 100);
+		})
+	}
+
+	#[test]
+	fn skips_dispatches_returning_actual_weight() {
+		new_test_ext().execute_with(|| {
+			set_block_ref_time(1000);
+			let info = DispatchInfo { weight: Weight::from_parts(500, 0), ..Default::default() };
+			let post_info = PostDispatchInfo {
+				actual_weight: Some(Weight::from_parts(200, 0)),
+				pays_fee: Default::default(),
+			};
+
+			dispatch(&info, &post_info, || unhashed::put(b"key", &1u32));
+
+			assert_eq!(block_ref_time(), 1000);
+		})
+	}
+
+	#[test]
+	fn does_nothing_without_benchmarked_accesses() {
+		new_test_ext().execute_with(|| {
+			set_block_ref_time(1000);
+			Benchmarked::set(None);
+			let info = DispatchInfo { weight: Weight::from_parts(500, 0), ..Default::default() };
+
+			dispatch(&info, &Default::default(), || {});
+
+			assert_eq!(block_ref_time(), 1000);
+		})
+	}
+
+	#[test]
+	fn does_nothing_without_metering() {
+		new_test_ext().execute_with(|| {
+			set_block_ref_time(1000);
+			let info = DispatchInfo { weight: Weight::from_parts(500, 0), ..Default::default() };
+
+			let pre = Reclaim::new().pre_dispatch(&1, CALL, &info, 0).unwrap();
+			assert!(pre.is_none());
+			assert_ok!(Reclaim::post_dispatch(Some(pre), &info, &Default::default(), 0, &Ok(())));
+
+			assert_eq!(block_ref_time(), 1000);
+		})
+	}
+}
@@ -17,8 +17,95 @@
 
 //! Supporting types for try-runtime, testing and dry-running commands.
 
+use codec::{Decode, Encode};
 pub use frame_support::traits::{TryStateSelect, UpgradeCheckSelect};
 use frame_support::weights::Weight;
+use sp_runtime::RuntimeDebug;
+use sp_runtime_interface::runtime_interface;
+use sp_std::vec::Vec;
+
+#[cfg(feature = "std")]
+use sp_externalities::ExternalitiesExt;
+#[cfg(feature = "std")]
+use sp_trie::proof_size_extension::ProofSizeExt;
+
+/// Interface that provides access to the size of the storage proof that is recorded while
+/// dry-running a runtime upgrade.
+///
+/// The host that executes the runtime must provide [`storage_proof::HostFunctions`].
+#[runtime_interface]
+pub trait StorageProof {
+	/// The size of the storage proof that was recorded so far.
+	///
+	/// Returns `None` if no `ProofSizeExt` is registered, i.e. if no proof is recorded.
+	fn recorded_size(&mut self) -> Option<u64> {
+		self.extension::<ProofSizeExt>().map(|ext| ext.storage_proof_size())
+	}
+}
+
+/// Weight report of a single block that was spent in the multi-block migration mode.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct MbmBlockReport {
+	/// The number of the simulated block.
+	pub number: u64,
+	/// The weight that was consumed by the `MultiBlockMigrator` in this block.
+	pub migration_weight: Weight,
+	/// The total weight of the block, including all hooks.
+	pub block_weight: Weight,
+	/// The size of the storage proof that the `MultiBlockMigrator` recorded in this block.
+	///
+	/// `None` if no storage proof is recorded, see [`storage_proof::recorded_size`]. The proof is
+	/// recorded over the whole simulation, so trie nodes that were already recorded by an earlier
+	/// block are not counted again.
+	pub migration_proof_size: Option<u64>,
+	/// The size of the storage proof that the whole block recorded, including all hooks.
+	///
+	/// See [`Self::migration_proof_size`].
+	pub block_proof_size: Option<u64>,
+}
+
+impl MbmBlockReport {
+	/// Whether the block exceeded `max_block_weight` in any dimension, or recorded a storage proof
+	/// larger than its proof size.
+	pub fn exceeds(&self, max_block_weight: Weight) -> bool {
+		self.block_weight.any_gt(max_block_weight) ||
+			self.block_proof_size.map_or(false, |size| size > max_block_weight.proof_size())
+	}
+}
+
+/// Report of the simulation of all multi-block migrations of a runtime upgrade.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct MbmSimulationReport {
+	/// One entry per block that was executed while the migrations were ongoing.
+	pub blocks: Vec<MbmBlockReport>,
+	/// The maximal weight of a block, as configured by the runtime.
+	pub max_block_weight: Weight,
+	/// Whether all migrations completed within the simulated blocks.
+	///
+	/// This is `false` if the chain would stay in the forced-migration mode for longer than the
+	/// number of blocks that the caller allowed.
+	pub completed: bool,
+}
+
+impl MbmSimulationReport {
+	/// The blocks in which the total weight exceeded [`Self::max_block_weight`] in any dimension,
+	/// or whose recorded storage proof exceeded its proof size.
+	pub fn overweight_blocks(&self) -> impl Iterator<Item = &MbmBlockReport> {
+		self.blocks.iter().filter(|b| b.exceeds(self.max_block_weight))
+	}
+
+	/// The largest weight that the migrations consumed in a single block, per dimension.
+	pub fn max_migration_weight(&self) -> Weight {
+		self.blocks.iter().fold(Weight::zero(), |acc, b| acc.max(b.migration_weight))
+	}
+
+	/// The largest storage proof that the migrations recorded in a single block.
+	///
+	/// `None` if no storage proof was recorded.
+	pub fn max_migration_proof_size(&self) -> Option<u64> {
+		self.blocks.iter().filter_map(|b| b.migration_proof_size).max()
+	}
+}
 
 sp_api::decl_runtime_apis! {
 	/// Runtime api for testing the execution of a runtime upgrade.
@@ -46,5 +133,29 @@ sp_api::decl_runtime_apis! {
 			signature_check: bool,
 			try_state: TryStateSelect,
 		) -> Weight;
+
+		/// Dry-run runtime upgrades including all multi-block migrations.
+		///
+		/// Same as `on_runtime_upgrade`, but then continues to build empty blocks until the
+		/// multi-block migrations completed or `max_blocks` blocks were built. The weight of each
+		/// of these blocks is reported, as well as whether the migrations completed in time.
+		///
+		/// The actual size of the storage proof of each block is reported as well if the state
+		/// backend records a proof and a `ProofSizeExt` of its recorder is registered, like the
+		/// block builder does. The host must then provide [`storage_proof::HostFunctions`].
+		///
+		/// The `pre_upgrade` and `post_upgrade` hooks of the stepped migrations are executed when
+		/// they start and complete respectively. If `checks` includes `try_state`, then the
+		/// `try_state` hooks of all pallets are executed after every block.
+		///
+		/// The `on-runtime-upgrade` command of `try-runtime-cli` only calls `on_runtime_upgrade`.
+		/// To simulate the multi-block migrations, call `TryRuntime_on_runtime_upgrade_mbms` with
+		/// the SCALE encoded `(checks, max_blocks)` on a state snapshot, for example by building
+		/// the externalities with `frame-remote-externalities` and executing the call with
+		/// `sp_state_machine::StateMachine`. The result decodes as [`MbmSimulationReport`]. The
+		/// runtime must implement version 2 of this API, which can be checked in the `apis` of
+		/// its `RuntimeVersion`.
+		#[api_version(2)]
+		fn on_runtime_upgrade_mbms(checks: UpgradeCheckSelect, max_blocks: u32) -> MbmSimulationReport;
 	}
 }
@@ -0,0 +1,157 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Twisted ElGamal encryption of amounts.
+
+use crate::{compress, h, point, Error, G};
+use codec::{Decode, Encode, MaxEncodedLen};
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar, traits::Identity};
+use rand_core::{CryptoRng, RngCore};
+use scale_info::TypeInfo;
+
+/// The public key of a confidential account.
+///
+/// This is the compressed point `s⁻¹·H` for the secret key `s`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct PublicKey(pub [u8; 32]);
+
+impl PublicKey {
+	/// The key as a point. The identity is rejected since it encrypts every amount in the clear.
+	pub(crate) fn decompress(&self) -> Result<RistrettoPoint, Error> {
+		let key = point(&self.0)?;
+		if key == RistrettoPoint::identity() {
+			return Err(Error::InvalidPoint)
+		}
+		Ok(key)
+	}
+}
+
+/// The secret key of a confidential account.
+#[derive(Clone)]
+pub struct SecretKey(pub(crate) Scalar);
+
+impl SecretKey {
+	/// Derive a secret key from `seed`.
+	pub fn from_seed(seed: &[u8]) -> Self {
+		Self(Scalar::from_bytes_mod_order_wide(&sp_crypto_hashing::blake2_512(seed)))
+	}
+
+	/// Generate a random secret key.
+	pub fn generate<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+		Self(Scalar::random(rng))
+	}
+
+	/// The public key of this secret key.
+	pub fn public(&self) -> PublicKey {
+		PublicKey(compress(&self.public_point()))
+	}
+
+	pub(crate) fn public_point(&self) -> RistrettoPoint {
+		self.0.invert() * h()
+	}
+
+	/// Decrypt `ciphertext` if it encrypts an amount of at most `max`.
+	///
+	/// This searches all amounts up to `max` and is therefore only practical for small amounts.
+	/// Wallets are expected to keep track of their available balance themselves.
+	pub fn decrypt(&self, ciphertext: &Ciphertext, max: u64) -> Option<u64> {
+		let (commitment, handle) = ciphertext.decompress().ok()?;
+		let target = commitment - self.0 * handle;
+		let mut candidate = RistrettoPoint::identity();
+		for amount in 0..=max {
+			if candidate == target {
+				return Some(amount)
+			}
+			candidate += G;
+		}
+		None
+	}
+}
+
+/// An amount encrypted under a [`PublicKey`] `P`.
+///
+/// This is the pair `(v·G + r·H, r·P)` for the amount `v` and the random opening `r`. The default
+/// value encrypts zero.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, Debug, TypeInfo, MaxEncodedLen)]
+pub struct Ciphertext {
+	/// The Pedersen commitment `v·G + r·H`.
+	pub commitment: [u8; 32],
+	/// The decryption handle `r·P`.
+	pub handle: [u8; 32],
+}
+
+impl Ciphertext {
+	pub(crate) fn new(commitment: &RistrettoPoint, handle: &RistrettoPoint) -> Self {
+		Self { commitment: compress(commitment), handle: compress(handle) }
+	}
+
+	pub(crate) fn decompress(&self) -> Result<(RistrettoPoint, RistrettoPoint), Error> {
+		Ok((point(&self.commitment)?, point(&self.handle)?))
+	}
+
+	/// The encryption of the sum of the amounts encrypted by `self` and `other`.
+	pub fn add_ciphertext(&self, other: &Self) -> Result<Self, Error> {
+		let (commitment, handle) = self.decompress()?;
+		let (other_commitment, other_handle) = other.decompress()?;
+		Ok(Self::new(&(commitment + other_commitment), &(handle + other_handle)))
+	}
+
+	/// The encryption of the amount encrypted by `self` minus the one encrypted by `other`.
+	pub fn sub_ciphertext(&self, other: &Self) -> Result<Self, Error> {
+		let (commitment, handle) = self.decompress()?;
+		let (other_commitment, other_handle) = other.decompress()?;
+		Ok(Self::new(&(commitment - other_commitment), &(handle - other_handle)))
+	}
+
+	/// The encryption of the amount encrypted by `self` plus the public `amount`.
+	pub fn add_amount(&self, amount: u64) -> Result<Self, Error> {
+		let commitment = point(&self.commitment)?;
+		Ok(Self { commitment: compress(&(commitment + Scalar::from(amount) * G)), ..*self })
+	}
+
+	/// The encryption of the amount encrypted by `self` minus the public `amount`.
+	pub fn sub_amount(&self, amount: u64) -> Result<Self, Error> {
+		let commitment = point(&self.commitment)?;
+		Ok(Self { commitment: compress(&(commitment - Scalar::from(amount) * G)), ..*self })
+	}
+}
+
+/// An amount encrypted for both parties of a transfer.
+///
+/// Both ciphertexts share the commitment and thereby the opening. [`crate::ValidityProof`] proves
+/// that this is the case.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct TransferAmount {
+	/// The Pedersen commitment to the amount.
+	pub commitment: [u8; 32],
+	/// The decryption handle of the sender.
+	pub sender_handle: [u8; 32],
+	/// The decryption handle of the receiver.
+	pub receiver_handle: [u8; 32],
+}
+
+impl TransferAmount {
+	/// The amount encrypted under the key of the sender.
+	pub fn sender_ciphertext(&self) -> Ciphertext {
+		Ciphertext { commitment: self.commitment, handle: self.sender_handle }
+	}
+
+	/// The amount encrypted under the key of the receiver.
+	pub fn receiver_ciphertext(&self) -> Ciphertext {
+		Ciphertext { commitment: self.commitment, handle: self.receiver_handle }
+	}
+}
@@ -0,0 +1,124 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(missing_docs)]
+
+//! Primitives for confidential asset balances.
+//!
+//! Amounts are encrypted with twisted ElGamal over the Ristretto group: a [`Ciphertext`] of the
+//! amount `v` under the [`PublicKey`] `P` is the pair `(v·G + r·H, r·P)`. Its first element is a
+//! Pedersen commitment to `v`, which allows the zero-knowledge proofs of this crate to reason
+//! about encrypted and committed amounts alike. Ciphertexts under the same key can be added and
+//! subtracted without knowing the amounts they encrypt.
+//!
+//! The chain only ever verifies proofs. Creating them requires the [`SecretKey`] of the account
+//! and happens in the wallet:
+//!
+//! - [`PubkeyValidityProof`] shows that the owner of a public key knows its secret key.
+//! - [`TransferProof`] shows that a [`TransferAmount`] encrypts the same non-negative amount for
+//!   the sender and the receiver, and that the sender's balance stays non-negative.
+//! - [`WithdrawProof`] shows that the balance stays non-negative after withdrawing a public amount.
+//!
+//! All proofs take a `context` which is bound into their transcript. Verifiers pass the same
+//! context, e.g. the asset and the accounts involved, to prevent proofs from being replayed
+//! elsewhere.
+
+mod elgamal;
+mod proofs;
+#[cfg(test)]
+mod tests;
+
+pub use elgamal::{Ciphertext, PublicKey, SecretKey, TransferAmount};
+pub use proofs::{
+	BitProof, EqualityProof, PubkeyValidityProof, RangeProof, TransferProof, ValidityProof,
+	WithdrawProof,
+};
+
+use curve25519_dalek::{
+	constants::RISTRETTO_BASEPOINT_POINT as G,
+	ristretto::{CompressedRistretto, RistrettoPoint},
+	scalar::Scalar,
+};
+use merlin::Transcript;
+
+/// Errors of decoding or verifying encrypted amounts and proofs.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+	/// A point is not a canonically encoded Ristretto point.
+	InvalidPoint,
+	/// A scalar is not canonically encoded.
+	InvalidScalar,
+	/// A proof does not verify.
+	VerificationFailed,
+	/// The balance is lower than the amount a proof was requested for.
+	InsufficientBalance,
+}
+
+/// The generator which blinds Pedersen commitments.
+///
+/// Nobody knows its discrete logarithm with respect to `G`.
+fn h() -> RistrettoPoint {
+	RistrettoPoint::from_uniform_bytes(&sp_crypto_hashing::blake2_512(
+		b"sp-confidential-assets/pedersen-blinding",
+	))
+}
+
+fn point(bytes: &[u8; 32]) -> Result<RistrettoPoint, Error> {
+	CompressedRistretto(*bytes).decompress().ok_or(Error::InvalidPoint)
+}
+
+fn scalar(bytes: &[u8; 32]) -> Result<Scalar, Error> {
+	Option::from(Scalar::from_canonical_bytes(*bytes)).ok_or(Error::InvalidScalar)
+}
+
+fn compress(point: &RistrettoPoint) -> [u8; 32] {
+	point.compress().to_bytes()
+}
+
+fn check(condition: bool) -> Result<(), Error> {
+	if condition {
+		Ok(())
+	} else {
+		Err(Error::VerificationFailed)
+	}
+}
+
+/// Start the transcript of the proof `label` bound to `context`.
+fn transcript(label: &'static [u8], context: &[u8]) -> Transcript {
+	let mut transcript = Transcript::new(b"sp-confidential-assets");
+	transcript.append_message(b"dom-sep", label);
+	transcript.append_message(b"context", context);
+	transcript
+}
+
+trait TranscriptProtocol {
+	fn append_point(&mut self, label: &'static [u8], point: &[u8; 32]);
+	fn challenge_scalar(&mut self, label: &'static [u8]) -> Scalar;
+}
+
+impl TranscriptProtocol for Transcript {
+	fn append_point(&mut self, label: &'static [u8], point: &[u8; 32]) {
+		self.append_message(label, point);
+	}
+
+	fn challenge_scalar(&mut self, label: &'static [u8]) -> Scalar {
+		let mut bytes = [0; 64];
+		self.challenge_bytes(label, &mut bytes);
+		Scalar::from_bytes_mod_order_wide(&bytes)
+	}
+}
@@ -0,0 +1,533 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Zero-knowledge proofs about encrypted amounts.
+//!
+//! All proofs are sigma protocols made non-interactive with a merlin transcript. The parts of a
+//! [`TransferProof`] or [`WithdrawProof`] share one transcript, which binds them to each other, to
+//! the public inputs and to the context.
+
+use crate::{
+	check, compress, h, point, scalar, transcript, Ciphertext, Error, PublicKey, SecretKey,
+	TranscriptProtocol, TransferAmount, G,
+};
+use codec::{Decode, Encode, MaxEncodedLen};
+use curve25519_dalek::{
+	ristretto::RistrettoPoint,
+	scalar::Scalar,
+	traits::{Identity, VartimeMultiscalarMul},
+};
+use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
+use scale_info::TypeInfo;
+
+/// The number of bits covered by a [`RangeProof`].
+const BITS: usize = 64;
+
+/// Proof that the owner of a [`PublicKey`] `P` knows its secret key `s`, i.e. that `s·P = H`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct PubkeyValidityProof {
+	y: [u8; 32],
+	z: [u8; 32],
+}
+
+impl PubkeyValidityProof {
+	/// Prove knowledge of `secret`.
+	pub fn new<R: RngCore + CryptoRng>(secret: &SecretKey, context: &[u8], rng: &mut R) -> Self {
+		let public = secret.public_point();
+		let mut transcript = transcript(b"pubkey-validity", context);
+		transcript.append_point(b"P", &compress(&public));
+
+		let k = Scalar::random(rng);
+		let y = compress(&(k * public));
+		transcript.append_point(b"Y", &y);
+		let c = transcript.challenge_scalar(b"c");
+
+		Self { y, z: (c * secret.0 + k).to_bytes() }
+	}
+
+	/// Verify that the owner of `public` knows its secret key.
+	pub fn verify(&self, public: &PublicKey, context: &[u8]) -> Result<(), Error> {
+		let p = public.decompress()?;
+		let y = point(&self.y)?;
+		let z = scalar(&self.z)?;
+
+		let mut transcript = transcript(b"pubkey-validity", context);
+		transcript.append_point(b"P", &public.0);
+		transcript.append_point(b"Y", &self.y);
+		let c = transcript.challenge_scalar(b"c");
+
+		check(z * p == c * h() + y)
+	}
+}
+
+/// Proof that a [`Ciphertext`] under the key of the prover and a Pedersen commitment hide the
+/// same amount.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct EqualityProof {
+	y0: [u8; 32],
+	y1: [u8; 32],
+	y2: [u8; 32],
+	z_s: [u8; 32],
+	z_x: [u8; 32],
+	z_r: [u8; 32],
+}
+
+impl EqualityProof {
+	/// Prove that the ciphertext with `handle` under the key of `secret` encrypts `amount`, which
+	/// is also committed to with `opening`.
+	fn new<R: RngCore + CryptoRng>(
+		transcript: &mut Transcript,
+		secret: &SecretKey,
+		handle: &RistrettoPoint,
+		amount: u64,
+		opening: &Scalar,
+		rng: &mut R,
+	) -> Self {
+		let (y_s, y_x, y_r) = (Scalar::random(rng), Scalar::random(rng), Scalar::random(rng));
+		let y0 = compress(&(y_s * secret.public_point()));
+		let y1 = compress(&(y_x * G + y_s * handle));
+		let y2 = compress(&(y_x * G + y_r * h()));
+		transcript.append_point(b"Y0", &y0);
+		transcript.append_point(b"Y1", &y1);
+		transcript.append_point(b"Y2", &y2);
+		let c = transcript.challenge_scalar(b"c");
+
+		Self {
+			y0,
+			y1,
+			y2,
+			z_s: (c * secret.0 + y_s).to_bytes(),
+			z_x: (c * Scalar::from(amount) + y_x).to_bytes(),
+			z_r: (c * opening + y_r).to_bytes(),
+		}
+	}
+
+	/// Verify that the ciphertext `(ciphertext, handle)` under `public` hides the same amount as
+	/// `commitment`.
+	fn verify(
+		&self,
+		transcript: &mut Transcript,
+		public: &RistrettoPoint,
+		ciphertext: &RistrettoPoint,
+		handle: &RistrettoPoint,
+		commitment: &RistrettoPoint,
+	) -> Result<(), Error> {
+		let (y0, y1, y2) = (point(&self.y0)?, point(&self.y1)?, point(&self.y2)?);
+		let (z_s, z_x, z_r) = (scalar(&self.z_s)?, scalar(&self.z_x)?, scalar(&self.z_r)?);
+		transcript.append_point(b"Y0", &self.y0);
+		transcript.append_point(b"Y1", &self.y1);
+		transcript.append_point(b"Y2", &self.y2);
+		let c = transcript.challenge_scalar(b"c");
+
+		check(z_s * public == c * h() + y0)?;
+		check(z_x * G + z_s * handle == c * ciphertext + y1)?;
+		check(z_x * G + z_r * h() == c * commitment + y2)
+	}
+}
+
+/// Proof that a [`TransferAmount`] is well formed, i.e. that both of its handles were created with
+/// the opening of its commitment.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct ValidityProof {
+	y0: [u8; 32],
+	y1: [u8; 32],
+	y2: [u8; 32],
+	z_x: [u8; 32],
+	z_r: [u8; 32],
+}
+
+impl ValidityProof {
+	fn new<R: RngCore + CryptoRng>(
+		transcript: &mut Transcript,
+		amount: u64,
+		opening: &Scalar,
+		sender: &RistrettoPoint,
+		receiver: &RistrettoPoint,
+		rng: &mut R,
+	) -> Self {
+		let (y_x, y_r) = (Scalar::random(rng), Scalar::random(rng));
+		let y0 = compress(&(y_x * G + y_r * h()));
+		let y1 = compress(&(y_r * sender));
+		let y2 = compress(&(y_r * receiver));
+		transcript.append_point(b"Y0", &y0);
+		transcript.append_point(b"Y1", &y1);
+		transcript.append_point(b"Y2", &y2);
+		let c = transcript.challenge_scalar(b"c");
+
+		Self {
+			y0,
+			y1,
+			y2,
+			z_x: (c * Scalar::from(amount) + y_x).to_bytes(),
+			z_r: (c * opening + y_r).to_bytes(),
+		}
+	}
+
+	fn verify(
+		&self,
+		transcript: &mut Transcript,
+		sender: &RistrettoPoint,
+		receiver: &RistrettoPoint,
+		commitment: &RistrettoPoint,
+		sender_handle: &RistrettoPoint,
+		receiver_handle: &RistrettoPoint,
+	) -> Result<(), Error> {
+		let (y0, y1, y2) = (point(&self.y0)?, point(&self.y1)?, point(&self.y2)?);
+		let (z_x, z_r) = (scalar(&self.z_x)?, scalar(&self.z_r)?);
+		transcript.append_point(b"Y0", &self.y0);
+		transcript.append_point(b"Y1", &self.y1);
+		transcript.append_point(b"Y2", &self.y2);
+		let c = transcript.challenge_scalar(b"c");
+
+		check(z_x * G + z_r * h() == c * commitment + y0)?;
+		check(z_r * sender == c * sender_handle + y1)?;
+		check(z_r * receiver == c * receiver_handle + y2)
+	}
+}
+
+/// Proof that a commitment hides either zero or one.
+///
+/// This is an OR composition of two proofs of knowledge of the opening `r` of either `C = r·H`
+/// or `C - G = r·H`. The prover simulates the branch it does not know.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct BitProof {
+	commitment: [u8; 32],
+	c0: [u8; 32],
+	c1: [u8; 32],
+	z0: [u8; 32],
+	z1: [u8; 32],
+}
+
+impl BitProof {
+	fn new<R: RngCore + CryptoRng>(
+		transcript: &mut Transcript,
+		bit: bool,
+		opening: &Scalar,
+		rng: &mut R,
+	) -> Self {
+		let h = h();
+		let commitment = if bit { G + opening * h } else { opening * h };
+		let statements = [commitment, commitment - G];
+		let (real, simulated) = if bit { (1, 0) } else { (0, 1) };
+
+		let mut c = [Scalar::ZERO; 2];
+		let mut z = [Scalar::ZERO; 2];
+		let mut a = [RistrettoPoint::identity(); 2];
+		c[simulated] = Scalar::random(rng);
+		z[simulated] = Scalar::random(rng);
+		a[simulated] = z[simulated] * h - c[simulated] * statements[simulated];
+		let k = Scalar::random(rng);
+		a[real] = k * h;
+
+		let commitment = compress(&commitment);
+		transcript.append_point(b"C", &commitment);
+		transcript.append_point(b"A0", &compress(&a[0]));
+		transcript.append_point(b"A1", &compress(&a[1]));
+		let challenge = transcript.challenge_scalar(b"c");
+		c[real] = challenge - c[simulated];
+		z[real] = k + c[real] * opening;
+
+		Self {
+			commitment,
+			c0: c[0].to_bytes(),
+			c1: c[1].to_bytes(),
+			z0: z[0].to_bytes(),
+			z1: z[1].to_bytes(),
+		}
+	}
+
+	/// Verify the proof and return the bit commitment.
+	fn verify(
+		&self,
+		transcript: &mut Transcript,
+		h: &RistrettoPoint,
+	) -> Result<RistrettoPoint, Error> {
+		let commitment = point(&self.commitment)?;
+		let (c0, c1) = (scalar(&self.c0)?, scalar(&self.c1)?);
+		let (z0, z1) = (scalar(&self.z0)?, scalar(&self.z1)?);
+
+		// `A0 = z0·H - c0·C` and `A1 = z1·H - c1·(C - G)`.
+		let a0 = RistrettoPoint::vartime_multiscalar_mul([z0, -c0], [*h, commitment]);
+		let a1 = RistrettoPoint::vartime_multiscalar_mul([z1, -c1, c1], [*h, commitment, G]);
+		transcript.append_point(b"C", &self.commitment);
+		transcript.append_point(b"A0", &compress(&a0));
+		transcript.append_point(b"A1", &compress(&a1));
+		let challenge = transcript.challenge_scalar(b"c");
+
+		check(c0 + c1 == challenge)?;
+		Ok(commitment)
+	}
+}
+
+/// Proof that a Pedersen commitment hides an amount below `2^64`.
+///
+/// The amount is split into commitments to its bits which add up to the commitment. Each of them
+/// carries a [`BitProof`]. This is larger than a Bulletproof but only needs plain group
+/// operations.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct RangeProof {
+	bits: [BitProof; BITS],
+}
+
+impl RangeProof {
+	fn new<R: RngCore + CryptoRng>(
+		transcript: &mut Transcript,
+		amount: u64,
+		opening: &Scalar,
+		rng: &mut R,
+	) -> Self {
+		// The openings of the bits need to add up to `opening` with the same weights as the bits.
+		let mut openings = [Scalar::ZERO; BITS];
+		let mut sum = Scalar::ZERO;
+		for (i, bit_opening) in openings.iter_mut().enumerate().take(BITS - 1) {
+			*bit_opening = Scalar::random(rng);
+			sum += power_of_two(i) * *bit_opening;
+		}
+		openings[BITS - 1] = (opening - sum) * power_of_two(BITS - 1).invert();
+
+		let bits = core::array::from_fn(|i| {
+			BitProof::new(transcript, (amount >> i) & 1 == 1, &openings[i], rng)
+		});
+		Self { bits }
+	}
+
+	fn verify(
+		&self,
+		transcript: &mut Transcript,
+		commitment: &RistrettoPoint,
+	) -> Result<(), Error> {
+		let h = h();
+		let mut bits = [RistrettoPoint::identity(); BITS];
+		for (bit, proof) in bits.iter_mut().zip(self.bits.iter()) {
+			*bit = proof.verify(transcript, &h)?;
+		}
+
+		let sum = RistrettoPoint::vartime_multiscalar_mul((0..BITS).map(power_of_two), bits);
+		check(sum == *commitment)
+	}
+}
+
+fn power_of_two(exponent: usize) -> Scalar {
+	Scalar::from(1u64 << exponent)
+}
+
+/// Proof of a confidential transfer.
+///
+/// It proves that the [`TransferAmount`] is well formed and hides an amount below `2^64`, and
+/// that the available balance of the sender minus this amount is a non-negative amount below
+/// `2^64` as well.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct TransferProof {
+	new_balance: [u8; 32],
+	validity: ValidityProof,
+	amount_range: RangeProof,
+	equality: EqualityProof,
+	balance_range: RangeProof,
+}
+
+impl TransferProof {
+	/// Prove a transfer of `amount` to `receiver`.
+	///
+	/// `available` is the available balance of `secret` and encrypts `balance`.
+	pub fn new<R: RngCore + CryptoRng>(
+		secret: &SecretKey,
+		receiver: &PublicKey,
+		available: &Ciphertext,
+		balance: u64,
+		amount: u64,
+		context: &[u8],
+		rng: &mut R,
+	) -> Result<(TransferAmount, Self), Error> {
+		let new_balance = balance.checked_sub(amount).ok_or(Error::InsufficientBalance)?;
+		let sender = secret.public_point();
+		let receiver_point = receiver.decompress()?;
+
+		let opening = Scalar::random(rng);
+		let transfer = TransferAmount {
+			commitment: compress(&(Scalar::from(amount) * G + opening * h())),
+			sender_handle: compress(&(opening * sender)),
+			receiver_handle: compress(&(opening * receiver_point)),
+		};
+		let (_, new_handle) =
+			available.sub_ciphertext(&transfer.sender_ciphertext())?.decompress()?;
+		let new_opening = Scalar::random(rng);
+		let new_commitment = compress(&(Scalar::from(new_balance) * G + new_opening * h()));
+
+		let mut transcript = transfer_transcript(
+			&secret.public(),
+			receiver,
+			available,
+			&transfer,
+			&new_commitment,
+			context,
+		);
+		let validity =
+			ValidityProof::new(&mut transcript, amount, &opening, &sender, &receiver_point, rng);
+		let amount_range = RangeProof::new(&mut transcript, amount, &opening, rng);
+		let equality = EqualityProof::new(
+			&mut transcript,
+			secret,
+			&new_handle,
+			new_balance,
+			&new_opening,
+			rng,
+		);
+		let balance_range = RangeProof::new(&mut transcript, new_balance, &new_opening, rng);
+
+		Ok((
+			transfer,
+			Self { new_balance: new_commitment, validity, amount_range, equality, balance_range },
+		))
+	}
+
+	/// Verify a transfer of `amount` from `sender` with the `available` balance to `receiver`.
+	pub fn verify(
+		&self,
+		sender: &PublicKey,
+		receiver: &PublicKey,
+		available: &Ciphertext,
+		amount: &TransferAmount,
+		context: &[u8],
+	) -> Result<(), Error> {
+		let sender_point = sender.decompress()?;
+		let receiver_point = receiver.decompress()?;
+		let commitment = point(&amount.commitment)?;
+		let sender_handle = point(&amount.sender_handle)?;
+		let receiver_handle = point(&amount.receiver_handle)?;
+		let (new_ciphertext, new_handle) =
+			available.sub_ciphertext(&amount.sender_ciphertext())?.decompress()?;
+		let new_commitment = point(&self.new_balance)?;
+
+		let mut transcript =
+			transfer_transcript(sender, receiver, available, amount, &self.new_balance, context);
+		self.validity.verify(
+			&mut transcript,
+			&sender_point,
+			&receiver_point,
+			&commitment,
+			&sender_handle,
+			&receiver_handle,
+		)?;
+		self.amount_range.verify(&mut transcript, &commitment)?;
+		self.equality.verify(
+			&mut transcript,
+			&sender_point,
+			&new_ciphertext,
+			&new_handle,
+			&new_commitment,
+		)?;
+		self.balance_range.verify(&mut transcript, &new_commitment)
+	}
+}
+
+fn transfer_transcript(
+	sender: &PublicKey,
+	receiver: &PublicKey,
+	available: &Ciphertext,
+	amount: &TransferAmount,
+	new_balance: &[u8; 32],
+	context: &[u8],
+) -> Transcript {
+	let mut transcript = transcript(b"transfer", context);
+	transcript.append_point(b"sender", &sender.0);
+	transcript.append_point(b"receiver", &receiver.0);
+	transcript.append_point(b"available-commitment", &available.commitment);
+	transcript.append_point(b"available-handle", &available.handle);
+	transcript.append_point(b"amount-commitment", &amount.commitment);
+	transcript.append_point(b"sender-handle", &amount.sender_handle);
+	transcript.append_point(b"receiver-handle", &amount.receiver_handle);
+	transcript.append_point(b"new-balance", new_balance);
+	transcript
+}
+
+/// Proof of a withdrawal of a public amount from a confidential balance.
+///
+/// It proves that the available balance minus the amount is a non-negative amount below `2^64`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct WithdrawProof {
+	new_balance: [u8; 32],
+	equality: EqualityProof,
+	range: RangeProof,
+}
+
+impl WithdrawProof {
+	/// Prove a withdrawal of `amount`.
+	///
+	/// `available` is the available balance of `secret` and encrypts `balance`.
+	pub fn new<R: RngCore + CryptoRng>(
+		secret: &SecretKey,
+		available: &Ciphertext,
+		balance: u64,
+		amount: u64,
+		context: &[u8],
+		rng: &mut R,
+	) -> Result<Self, Error> {
+		let new_balance = balance.checked_sub(amount).ok_or(Error::InsufficientBalance)?;
+		let (_, handle) = available.decompress()?;
+		let new_opening = Scalar::random(rng);
+		let new_commitment = compress(&(Scalar::from(new_balance) * G + new_opening * h()));
+
+		let mut transcript =
+			withdraw_transcript(&secret.public(), available, amount, &new_commitment, context);
+		let equality =
+			EqualityProof::new(&mut transcript, secret, &handle, new_balance, &new_opening, rng);
+		let range = RangeProof::new(&mut transcript, new_balance, &new_opening, rng);
+
+		Ok(Self { new_balance: new_commitment, equality, range })
+	}
+
+	/// Verify a withdrawal of `amount` from the `available` balance of `public`.
+	pub fn verify(
+		&self,
+		public: &PublicKey,
+		available: &Ciphertext,
+		amount: u64,
+		context: &[u8],
+	) -> Result<(), Error> {
+		let public_point = public.decompress()?;
+		let (new_ciphertext, handle) = available.sub_amount(amount)?.decompress()?;
+		let new_commitment = point(&self.new_balance)?;
+
+		let mut transcript =
+			withdraw_transcript(public, available, amount, &self.new_balance, context);
+		self.equality.verify(
+			&mut transcript,
+			&public_point,
+			&new_ciphertext,
+			&handle,
+			&new_commitment,
+		)?;
+		self.range.verify(&mut transcript, &new_commitment)
+	}
+}
+
+fn withdraw_transcript(
+	public: &PublicKey,
+	available: &Ciphertext,
+	amount: u64,
+	new_balance: &[u8; 32],
+	context: &[u8],
+) -> Transcript {
+	let mut transcript = transcript(b"withdraw", context);
+	transcript.append_point(b"public", &public.0);
+	transcript.append_point(b"available-commitment", &available.commitment);
+	transcript.append_point(b"available-handle", &available.handle);
+	transcript.append_message(b"amount", &amount.to_le_bytes());
+	transcript.append_point(b"new-balance", new_balance);
+	transcript
+}
@@ -0,0 +1,226 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use codec::{Decode, Encode};
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+fn rng() -> ChaCha20Rng {
+	ChaCha20Rng::from_seed([0; 32])
+}
+
+/// `value` with the lowest bit of its encoded byte at `offset` flipped.
+fn flip_bit<T: Encode + Decode>(value: &T, offset: usize) -> T {
+	let mut bytes = value.encode();
+	bytes[offset] ^= 1;
+	T::decode(&mut &bytes[..]).expect("all fields are fixed size arrays; qed")
+}
+
+/// `value` with the encoded bytes at `range` replaced by those of `other`.
+fn splice<T: Encode + Decode>(value: &T, other: &T, range: core::ops::Range<usize>) -> T {
+	let mut bytes = value.encode();
+	bytes[range.clone()].copy_from_slice(&other.encode()[range]);
+	T::decode(&mut &bytes[..]).expect("all fields are fixed size arrays; qed")
+}
+
+#[test]
+fn ciphertexts_are_homomorphic() {
+	let alice = SecretKey::from_seed(b"alice");
+	let balance = Ciphertext::default().add_amount(50).unwrap();
+	assert_eq!(alice.decrypt(&balance, 100), Some(50));
+	assert_eq!(alice.decrypt(&balance.sub_amount(20).unwrap(), 100), Some(30));
+	assert_eq!(alice.decrypt(&balance.add_ciphertext(&balance).unwrap(), 100), Some(100));
+	assert_eq!(alice.decrypt(&balance.sub_ciphertext(&balance).unwrap(), 100), Some(0));
+	assert_eq!(alice.decrypt(&balance, 49), None);
+}
+
+#[test]
+fn pubkey_validity_proof_works() {
+	let alice = SecretKey::from_seed(b"alice");
+	let bob = SecretKey::from_seed(b"bob");
+	let proof = PubkeyValidityProof::new(&alice, b"context", &mut rng());
+
+	assert_eq!(proof.verify(&alice.public(), b"context"), Ok(()));
+	assert_eq!(proof.verify(&alice.public(), b"other"), Err(Error::VerificationFailed));
+	assert_eq!(proof.verify(&bob.public(), b"context"), Err(Error::VerificationFailed));
+	assert_eq!(proof.verify(&PublicKey([0xff; 32]), b"context"), Err(Error::InvalidPoint));
+}
+
+#[test]
+fn transfer_proof_works() {
+	let (alice, bob) = (SecretKey::from_seed(b"alice"), SecretKey::from_seed(b"bob"));
+	let available = Ciphertext::default().add_amount(50).unwrap();
+	let (amount, proof) =
+		TransferProof::new(&alice, &bob.public(), &available, 50, 20, b"context", &mut rng())
+			.unwrap();
+
+	assert_eq!(
+		proof.verify(&alice.public(), &bob.public(), &available, &amount, b"context"),
+		Ok(())
+	);
+	let new_balance = available.sub_ciphertext(&amount.sender_ciphertext()).unwrap();
+	assert_eq!(alice.decrypt(&new_balance, 100), Some(30));
+	assert_eq!(bob.decrypt(&amount.receiver_ciphertext(), 100), Some(20));
+
+	// The proof is bound to its context and inputs.
+	assert!(proof
+		.verify(&alice.public(), &bob.public(), &available, &amount, b"other")
+		.is_err());
+	let other = SecretKey::from_seed(b"charlie").public();
+	assert!(proof.verify(&alice.public(), &other, &available, &amount, b"context").is_err());
+	let more = available.add_amount(1).unwrap();
+	assert!(proof
+		.verify(&alice.public(), &bob.public(), &more, &amount, b"context")
+		.is_err());
+	let tampered = TransferAmount { receiver_handle: amount.sender_handle, ..amount };
+	assert!(proof
+		.verify(&alice.public(), &bob.public(), &available, &tampered, b"context")
+		.is_err());
+}
+
+#[test]
+fn transfer_proof_requires_sufficient_balance() {
+	let (alice, bob) = (SecretKey::from_seed(b"alice"), SecretKey::from_seed(b"bob"));
+	let available = Ciphertext::default().add_amount(50).unwrap();
+	assert_eq!(
+		TransferProof::new(&alice, &bob.public(), &available, 50, 51, b"", &mut rng()).err(),
+		Some(Error::InsufficientBalance)
+	);
+
+	// Lying about the balance produces a proof which does not verify.
+	let (amount, proof) =
+		TransferProof::new(&alice, &bob.public(), &available, 100, 80, b"", &mut rng()).unwrap();
+	assert_eq!(
+		proof.verify(&alice.public(), &bob.public(), &available, &amount, b""),
+		Err(Error::VerificationFailed)
+	);
+}
+
+#[test]
+fn withdraw_proof_works() {
+	let alice = SecretKey::from_seed(b"alice");
+	let available = Ciphertext::default().add_amount(50).unwrap();
+	let proof = WithdrawProof::new(&alice, &available, 50, 50, b"context", &mut rng()).unwrap();
+
+	assert_eq!(proof.verify(&alice.public(), &available, 50, b"context"), Ok(()));
+	assert!(proof.verify(&alice.public(), &available, 40, b"context").is_err());
+	assert!(proof.verify(&alice.public(), &available, 50, b"other").is_err());
+
+	let proof = WithdrawProof::new(&alice, &available, 60, 60, b"context", &mut rng()).unwrap();
+	assert_eq!(
+		proof.verify(&alice.public(), &available, 60, b"context"),
+		Err(Error::VerificationFailed)
+	);
+}
+
+#[test]
+fn identity_keys_are_rejected() {
+	let identity = PublicKey([0; 32]);
+	let proof = PubkeyValidityProof::new(&SecretKey::from_seed(b"alice"), b"", &mut rng());
+	assert_eq!(proof.verify(&identity, b""), Err(Error::InvalidPoint));
+
+	let alice = SecretKey::from_seed(b"alice");
+	let available = Ciphertext::default().add_amount(50).unwrap();
+	let (amount, proof) =
+		TransferProof::new(&alice, &alice.public(), &available, 50, 20, b"", &mut rng()).unwrap();
+	assert_eq!(
+		proof.verify(&alice.public(), &identity, &available, &amount, b""),
+		Err(Error::InvalidPoint)
+	);
+}
+
+#[test]
+fn non_canonical_scalars_are_rejected() {
+	let alice = SecretKey::from_seed(b"alice");
+	let proof = PubkeyValidityProof::new(&alice, b"", &mut rng());
+	// The response `z` is the second field. Adding the group order to it would otherwise yield
+	// a second valid encoding of the same proof.
+	let mut bytes = proof.encode();
+	bytes[32..64].copy_from_slice(&[0xff; 32]);
+	let malleated = PubkeyValidityProof::decode(&mut &bytes[..]).unwrap();
+	assert_eq!(malleated.verify(&alice.public(), b""), Err(Error::InvalidScalar));
+}
+
+#[test]
+fn tampered_proofs_are_rejected() {
+	let (alice, bob) = (SecretKey::from_seed(b"alice"), SecretKey::from_seed(b"bob"));
+	let available = Ciphertext::default().add_amount(50).unwrap();
+
+	let proof = PubkeyValidityProof::new(&alice, b"", &mut rng());
+	for offset in 0..proof.encoded_size() {
+		assert!(flip_bit(&proof, offset).verify(&alice.public(), b"").is_err());
+	}
+
+	// Every field of the large proofs is covered by the stride.
+	let (amount, proof) =
+		TransferProof::new(&alice, &bob.public(), &available, 50, 20, b"", &mut rng()).unwrap();
+	for offset in (0..proof.encoded_size()).step_by(31) {
+		let tampered = flip_bit(&proof, offset);
+		assert!(tampered
+			.verify(&alice.public(), &bob.public(), &available, &amount, b"")
+			.is_err());
+	}
+	for offset in 0..amount.encoded_size() {
+		let tampered = flip_bit(&amount, offset);
+		assert!(proof
+			.verify(&alice.public(), &bob.public(), &available, &tampered, b"")
+			.is_err());
+	}
+
+	let proof = WithdrawProof::new(&alice, &available, 50, 20, b"", &mut rng()).unwrap();
+	for offset in (0..proof.encoded_size()).step_by(31) {
+		assert!(flip_bit(&proof, offset).verify(&alice.public(), &available, 20, b"").is_err());
+	}
+}
+
+#[test]
+fn spliced_proofs_are_rejected() {
+	let (alice, bob) = (SecretKey::from_seed(b"alice"), SecretKey::from_seed(b"bob"));
+	let available = Ciphertext::default().add_amount(50).unwrap();
+	let mut rng = rng();
+	let (amount, proof) =
+		TransferProof::new(&alice, &bob.public(), &available, 50, 20, b"", &mut rng).unwrap();
+	let (_, other) =
+		TransferProof::new(&alice, &bob.public(), &available, 50, 20, b"", &mut rng).unwrap();
+
+	// The layout is `new_balance`, `validity`, `amount_range`, `equality` and `balance_range`,
+	// where a range proof is 64 bit proofs of five 32 byte fields each.
+	let range_proof = 64 * 5 * 32;
+	let amount_range = 32 + 5 * 32..32 + 5 * 32 + range_proof;
+	let balance_range = proof.encoded_size() - range_proof..proof.encoded_size();
+	for range in [0..32, 32..32 + 5 * 32, amount_range, balance_range] {
+		let spliced = splice(&proof, &other, range);
+		assert!(spliced
+			.verify(&alice.public(), &bob.public(), &available, &amount, b"")
+			.is_err());
+	}
+}
+
+#[test]
+fn proofs_cannot_be_replayed() {
+	let alice = SecretKey::from_seed(b"alice");
+	let available = Ciphertext::default().add_amount(50).unwrap();
+	let proof = WithdrawProof::new(&alice, &available, 50, 20, b"", &mut rng()).unwrap();
+	assert_eq!(proof.verify(&alice.public(), &available, 20, b""), Ok(()));
+
+	// Once applied, the balance changed and the proof no longer matches it.
+	let remaining = available.sub_amount(20).unwrap();
+	assert!(proof.verify(&alice.public(), &remaining, 20, b"").is_err());
+	// Neither does it verify for another key with the same balance.
+	let bob = SecretKey::from_seed(b"bob");
+	assert!(proof.verify(&bob.public(), &available, 20, b"").is_err());
+}
@@ -219,6 +219,12 @@ pub struct PalletStorageMetadataIR<T: Form = MetaForm> {
 	pub prefix: T::String,
 	/// Metadata for all storage entries.
 	pub entries: Vec<StorageEntryMetadataIR<T>>,
+	/// Metadata for all storage entries that were removed.
+	///
+	/// Their prefixes stay reserved and the remaining keys are cleaned up lazily. They are not
+	/// part of the storage API anymore and therefore not included in `entries`. The versioned
+	/// metadata has no dedicated field for them, so they are appended to its storage entries.
+	pub removed_entries: Vec<StorageEntryMetadataIR<T>>,
 }
 
 impl IntoPortable for PalletStorageMetadataIR {
@@ -228,6 +234,7 @@ impl IntoPortable for PalletStorageMetadataIR {
 		PalletStorageMetadataIR {
 			prefix: self.prefix.into_portable(registry),
 			entries: registry.map_into_portable(self.entries),
+			removed_entries: registry.map_into_portable(self.removed_entries),
 		}
 	}
 }
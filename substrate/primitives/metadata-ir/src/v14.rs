@@ -108,7 +108,14 @@ impl From<PalletStorageMetadataIR> for PalletStorageMetadata {
 	fn from(ir: PalletStorageMetadataIR) -> Self {
 		PalletStorageMetadata {
 			prefix: ir.prefix,
-			entries: ir.entries.into_iter().map(Into::into).collect(),
+			// Removed entries are still exposed to clients, so that they can decode the keys that
+			// are left until the cleanup completed. Their docs mark them as removed.
+			entries: ir
+				.entries
+				.into_iter()
+				.chain(ir.removed_entries.into_iter())
+				.map(Into::into)
+				.collect(),
 		}
 	}
 }
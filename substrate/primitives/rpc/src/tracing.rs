@@ -44,6 +44,57 @@ pub struct BlockTrace {
 	pub events: Vec<Event>,
 }
 
+/// Proof size attribution of all spans recorded while re-executing a block.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockProofSizeTrace {
+	/// Hash of the block being traced
+	pub block_hash: String,
+	/// Parent hash
+	pub parent_hash: String,
+	/// Module targets that were recorded by the tracing subscriber.
+	/// Empty string means record all targets.
+	pub tracing_targets: String,
+	/// Estimated encoded size of the storage proof of the whole block.
+	pub proof_size: u64,
+	/// Vec of tracing spans with their proof size contribution
+	pub spans: Vec<ProofSizeSpan>,
+}
+
+/// A tracing span together with the storage it accessed.
+///
+/// All values are inclusive, i.e. they contain the contribution of all child spans.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofSizeSpan {
+	/// id for this span
+	pub id: u64,
+	/// id of the parent span, if any
+	pub parent_id: Option<u64>,
+	/// Name of this span
+	pub name: String,
+	/// Target, typically module
+	pub target: String,
+	/// Indicates if the span is from wasm
+	pub wasm: bool,
+	/// Growth of the estimated storage proof size while this span was entered.
+	pub proof_size: u64,
+	/// Storage keys that were added to the storage proof by this span.
+	pub reads: Vec<StorageAccess>,
+	/// Storage keys that were written by this span.
+	pub writes: Vec<StorageAccess>,
+}
+
+/// A single storage key accessed while tracing a block.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageAccess {
+	/// Hex encoded storage key
+	pub key: String,
+	/// Storage item the key belongs to in the form of `Pallet::Item`, if known.
+	pub storage: Option<String>,
+}
+
 /// Represents a tracing event, complete with recorded data.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -99,3 +150,13 @@ pub enum TraceBlockResponse {
 	/// Successful block tracing response
 	BlockTrace(BlockTrace),
 }
+
+/// Response for the `state_traceBlockProofSize` RPC.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum TraceBlockProofSizeResponse {
+	/// Error block tracing response
+	TraceError(TraceError),
+	/// Successful block tracing response
+	BlockProofSizeTrace(BlockProofSizeTrace),
+}
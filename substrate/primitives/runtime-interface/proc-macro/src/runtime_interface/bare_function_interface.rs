@@ -27,7 +27,9 @@
 //!
 //! [`function_for_method`] generates the bare
 //! function per trait method. Each bare function contains both implementations. The implementations
-//! are feature-gated, so that one is compiled for the native and the other for the wasm side.
+//! are feature-gated, so that one is compiled for the native and the other for the wasm side. Both
+//! implementations notify the host call observer of the runtime interface crate before doing the
+//! call.
 
 use crate::utils::{
 	create_exchangeable_host_function_ident, create_function_ident_with_version,
@@ -96,6 +98,8 @@ fn function_no_std_impl(
 	is_wasm_only: bool,
 ) -> Result<TokenStream> {
 	let function_name = &method.sig.ident;
+	let function_name_str = function_name.to_string();
+	let crate_ = generate_crate_access();
 	let host_function_name = create_exchangeable_host_function_ident(&method.sig.ident);
 	let args = get_function_arguments(&method.sig);
 	let arg_names = get_function_argument_names(&method.sig);
@@ -133,6 +137,7 @@ fn function_no_std_impl(
 		#[cfg(not(feature = "std"))]
 		#( #attrs )*
 		pub fn #function_name( #( #args, )* ) #return_value {
+			#crate_::observer::note_host_call(module_path!(), #function_name_str);
 			// Call the host function
 			#host_function_name.get()( #( #arg_names, )* )
 			#maybe_unreachable
@@ -145,6 +150,8 @@ fn function_no_std_impl(
 /// This should generate simple `fn func(..) { func_version_<latest_version>(..) }`.
 fn function_std_latest_impl(method: &TraitItemFn, latest_version: u32) -> Result<TokenStream> {
 	let function_name = &method.sig.ident;
+	let function_name_str = function_name.to_string();
+	let crate_ = generate_crate_access();
 	let args = get_function_arguments(&method.sig).map(FnArg::Typed);
 	let arg_names = get_function_argument_names(&method.sig).collect::<Vec<_>>();
 	let return_value = &method.sig.output;
@@ -156,6 +163,7 @@ fn function_std_latest_impl(method: &TraitItemFn, latest_version: u32) -> Result
 		#[cfg(feature = "std")]
 		#( #attrs )*
 		pub fn #function_name( #( #args, )* ) #return_value {
+			#crate_::observer::note_host_call(module_path!(), #function_name_str);
 			#latest_function_name(
 				#( #arg_names, )*
 			)
@@ -382,6 +382,7 @@ pub mod polkavm;
 #[cfg(feature = "std")]
 pub mod host;
 pub(crate) mod impls;
+pub mod observer;
 pub mod pass_by;
 #[cfg(any(not(feature = "std"), doc))]
 pub mod wasm;
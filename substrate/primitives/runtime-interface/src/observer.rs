@@ -0,0 +1,47 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Observing the runtime interface functions called by the runtime.
+//!
+//! Every bare function generated by [`runtime_interface`](crate::runtime_interface) notifies the
+//! [`HostCallObserver`] set with [`observe`] before calling into the host. This lets the runtime
+//! learn which host functions some code calls, for example to meter its storage accesses, no matter
+//! which abstraction the code uses to reach the host.
+
+environmental::environmental!(observer: trait HostCallObserver);
+
+/// Something that is notified about the host calls done by the runtime.
+pub trait HostCallObserver {
+	/// Called before `function` of the runtime interface declared in the module `interface` is
+	/// called, e.g. `("sp_io::storage", "get")`.
+	///
+	/// Must not call any runtime interface function itself.
+	fn on_host_call(&mut self, interface: &'static str, function: &'static str);
+}
+
+/// Run `f` while notifying `observer` about all host calls done by `f`.
+///
+/// An observer set by an outer call is not notified while `f` is running.
+pub fn observe<R>(observer: &mut dyn HostCallObserver, f: impl FnOnce() -> R) -> R {
+	observer::using(observer, f)
+}
+
+/// Notify the current observer, if any, about a call to `function` of `interface`.
+#[doc(hidden)]
+pub fn note_host_call(interface: &'static str, function: &'static str) {
+	observer::with(|observer| observer.on_host_call(interface, function));
+}
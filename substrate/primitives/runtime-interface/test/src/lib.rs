@@ -302,3 +302,50 @@ fn test_returning_option_bytes_from_a_host_function_is_efficient() {
 	// deserializing `Option<Vec<u8>>`.
 	assert_eq!(stats_bytes.bytes_allocated_sum + 16 * 1024 + 8, stats_vec.bytes_allocated_sum);
 }
+
+#[derive(Default)]
+struct HostCallRecorder(Vec<(&'static str, &'static str)>);
+
+impl observer::HostCallObserver for HostCallRecorder {
+	fn on_host_call(&mut self, interface: &'static str, function: &'static str) {
+		self.0.push((interface, function));
+	}
+}
+
+#[test]
+fn host_calls_are_observed() {
+	let mut recorder = HostCallRecorder::default();
+	sp_io::TestExternalities::default().execute_with(|| {
+		sp_io::storage::set(b"key", b"value");
+		observer::observe(&mut recorder, || {
+			sp_io::storage::get(b"key");
+			sp_io::default_child_storage::clear(b"child", b"key");
+			sp_io::hashing::blake2_256(b"key");
+		});
+		sp_io::storage::clear(b"key");
+	});
+
+	assert_eq!(
+		recorder.0,
+		vec![
+			("sp_io::storage", "get"),
+			("sp_io::default_child_storage", "clear"),
+			("sp_io::hashing", "blake2_256"),
+		],
+	);
+}
+
+#[test]
+fn inner_host_call_observer_replaces_outer_one() {
+	let mut outer = HostCallRecorder::default();
+	let mut inner = HostCallRecorder::default();
+	sp_io::TestExternalities::default().execute_with(|| {
+		observer::observe(&mut outer, || {
+			observer::observe(&mut inner, || sp_io::storage::exists(b"key"));
+			sp_io::storage::exists(b"key");
+		});
+	});
+
+	assert_eq!(outer.0, vec![("sp_io::storage", "exists")]);
+	assert_eq!(inner.0, vec![("sp_io::storage", "exists")]);
+}
@@ -0,0 +1,231 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Signing and submitting solutions to the node.
+
+use crate::LOG_TARGET;
+use codec::Encode;
+use frame_system::offchain::CreateSignedTransaction;
+use kitchensink_runtime::{Runtime, RuntimeCall, UncheckedExtrinsic};
+use node_primitives::{AccountId, BlockNumber, Hash, Header, Nonce};
+use pallet_election_provider_multi_phase::{RawSolution, SolutionOf};
+use sp_core::{
+	crypto::{KeyTypeId, Ss58Codec},
+	sr25519, Bytes, Pair,
+};
+use sp_io::TestExternalities;
+use sp_keystore::{testing::MemoryKeystore, Keystore, KeystoreExt};
+use sp_runtime::MultiSigner;
+use sp_version::RuntimeVersion;
+use substrate_rpc_client::{rpc_params, ws_client, ClientT, Error};
+
+/// The key type of the signer in the keystore that submissions are signed with.
+const KEY_TYPE: KeyTypeId = KeyTypeId(*b"emin");
+
+mod crypto {
+	use super::KEY_TYPE;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	/// The signer of the submissions.
+	pub struct SignerId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for SignerId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+/// Describe a failed call of the RPC `method`.
+fn rpc_error(method: &'static str) -> impl Fn(Error) -> String {
+	move |e| format!("`{}` failed: {:?}", method, e)
+}
+
+/// Sign `raw_solution` with the account of the secret URI `suri`, and submit it to the node at
+/// `uri`.
+///
+/// The extrinsic is mortal from the block `at` that the snapshot was downloaded at. The runtime of
+/// the node at that block must be the one that the miner is built for.
+pub(crate) async fn submit_solution(
+	uri: &str,
+	at: Hash,
+	suri: &str,
+	raw_solution: RawSolution<SolutionOf<Runtime>>,
+) -> Result<Hash, String> {
+	let client = ws_client(uri).await?;
+
+	let genesis_hash: Hash = client
+		.request("chain_getBlockHash", rpc_params![0])
+		.await
+		.map_err(rpc_error("chain_getBlockHash"))?;
+	let header: Header = client
+		.request("chain_getHeader", rpc_params![at])
+		.await
+		.map_err(rpc_error("chain_getHeader"))?;
+	let version: RuntimeVersion = client
+		.request("state_getRuntimeVersion", rpc_params![at])
+		.await
+		.map_err(rpc_error("state_getRuntimeVersion"))?;
+	// the extrinsic is signed with the signed extensions and the version of the runtime that the
+	// miner is built for, which is only valid for the same runtime on the node.
+	let expected = &kitchensink_runtime::VERSION;
+	if version.spec_version != expected.spec_version ||
+		version.transaction_version != expected.transaction_version
+	{
+		return Err(format!(
+			"the runtime of the node has spec version {} and transaction version {}, but the \
+			 miner is built for spec version {} and transaction version {}",
+			version.spec_version,
+			version.transaction_version,
+			expected.spec_version,
+			expected.transaction_version,
+		))
+	}
+
+	let signer = sr25519::Pair::from_string(suri, None)
+		.map_err(|e| format!("invalid secret URI: {:?}", e))?;
+	let who = AccountId::from(signer.public());
+	let nonce: Nonce = client
+		.request("system_accountNextIndex", rpc_params![who.to_ss58check()])
+		.await
+		.map_err(rpc_error("system_accountNextIndex"))?;
+	log::debug!(target: LOG_TARGET, "submitting the solution from {} with nonce {}", who, nonce);
+
+	let call = RuntimeCall::ElectionProviderMultiPhase(
+		pallet_election_provider_multi_phase::Call::submit { raw_solution: Box::new(raw_solution) },
+	);
+	let extrinsic = create_extrinsic(call, suri, nonce, genesis_hash, at, header.number)?;
+
+	client
+		.request("author_submitExtrinsic", rpc_params![Bytes(extrinsic.encode())])
+		.await
+		.map_err(rpc_error("author_submitExtrinsic"))
+}
+
+/// Create an extrinsic of `call`, signed by the account of the secret URI `suri`.
+///
+/// The extrinsic is created by the runtime itself, like the transactions of its offchain workers,
+/// so that its signed extensions always match the ones of the runtime. The chain state that they
+/// depend on is provided by [`chain_state`], together with a keystore that holds the signer.
+fn create_extrinsic(
+	call: RuntimeCall,
+	suri: &str,
+	nonce: Nonce,
+	genesis_hash: Hash,
+	best_hash: Hash,
+	best_number: BlockNumber,
+) -> Result<UncheckedExtrinsic, String> {
+	let public = sr25519::Pair::from_string(suri, None)
+		.map_err(|e| format!("invalid secret URI: {:?}", e))?
+		.public();
+	let keystore = MemoryKeystore::new();
+	keystore
+		.insert(KEY_TYPE, suri, public.as_ref())
+		.map_err(|_| "failed to add the signer to the keystore")?;
+
+	let mut ext = chain_state(genesis_hash, best_hash, best_number);
+	ext.register_extension(KeystoreExt::new(keystore));
+	ext.execute_with(|| {
+		let (call, (address, signature, extra)) = <Runtime as CreateSignedTransaction<
+			RuntimeCall,
+		>>::create_transaction::<crypto::SignerId>(
+			call,
+			MultiSigner::from(public),
+			AccountId::from(public),
+			nonce,
+		)
+		.ok_or("failed to sign the extrinsic")?;
+		Ok(UncheckedExtrinsic::new_signed(call, address, signature, extra))
+	})
+}
+
+/// Externalities with the state of the chain that the signed extensions of an extrinsic depend
+/// on, when the extrinsic is validated on top of the best block `best_number`.
+fn chain_state(genesis_hash: Hash, best_hash: Hash, best_number: BlockNumber) -> TestExternalities {
+	let mut ext = TestExternalities::new_empty();
+	ext.execute_with(|| {
+		frame_system::BlockHash::<Runtime>::insert(0, genesis_hash);
+		frame_system::BlockHash::<Runtime>::insert(best_number, best_hash);
+		frame_system::Pallet::<Runtime>::set_block_number(best_number + 1);
+	});
+	ext
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Decode;
+	use sp_runtime::traits::Checkable;
+
+	const SURI: &str = "//Alice";
+
+	fn remark() -> RuntimeCall {
+		RuntimeCall::System(frame_system::Call::remark { remark: vec![1, 2, 3] })
+	}
+
+	#[test]
+	fn extrinsic_is_signed_for_the_chain() {
+		let (genesis_hash, best_hash, best_number) =
+			(Hash::repeat_byte(1), Hash::repeat_byte(2), 42);
+		let extrinsic =
+			create_extrinsic(remark(), SURI, 7, genesis_hash, best_hash, best_number).unwrap();
+
+		// the extrinsic is encoded as the node expects it.
+		let encoded = extrinsic.encode();
+		let decoded = UncheckedExtrinsic::decode(&mut &encoded[..]).unwrap();
+		assert_eq!(decoded, extrinsic);
+
+		// and its signature is valid on top of the best block.
+		let checked = chain_state(genesis_hash, best_hash, best_number)
+			.execute_with(|| decoded.check(&frame_system::ChainContext::<Runtime>::default()))
+			.unwrap();
+		let alice = AccountId::from(sr25519::Pair::from_string(SURI, None).unwrap().public());
+		assert_eq!(checked.signed.map(|(who, _)| who), Some(alice));
+		assert_eq!(checked.function, remark());
+	}
+
+	#[test]
+	fn extrinsic_is_not_valid_on_another_chain() {
+		let (best_hash, best_number) = (Hash::repeat_byte(2), 42);
+		let extrinsic =
+			create_extrinsic(remark(), SURI, 0, Hash::repeat_byte(1), best_hash, best_number)
+				.unwrap();
+
+		let is_valid = |genesis_hash, best_hash| {
+			chain_state(genesis_hash, best_hash, best_number).execute_with(|| {
+				extrinsic
+					.clone()
+					.check(&frame_system::ChainContext::<Runtime>::default())
+					.is_ok()
+			})
+		};
+		assert!(is_valid(Hash::repeat_byte(1), best_hash));
+		// another genesis.
+		assert!(!is_valid(Hash::repeat_byte(3), best_hash));
+		// another fork.
+		assert!(!is_valid(Hash::repeat_byte(1), Hash::repeat_byte(3)));
+	}
+
+	#[test]
+	fn invalid_secret_uri_is_rejected() {
+		assert!(create_extrinsic(remark(), "no secret", 0, Hash::zero(), Hash::zero(), 0).is_err());
+	}
+}
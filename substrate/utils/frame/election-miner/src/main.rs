@@ -0,0 +1,129 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Standalone miner of election solutions for `pallet-election-provider-multi-phase`.
+//!
+//! Unlike the unsigned miner of the pallet, this does not run in the offchain worker of a
+//! validator. Instead, it connects to a node over RPC, downloads the snapshot of the ongoing
+//! election, mines a solution with one of the solvers of `sp-npos-elections`, trims it to the
+//! limits of the runtime and checks its feasibility, and finally submits it as a signed solution.
+//!
+//! With `--dry-run`, the solution is mined and checked, but not submitted.
+
+mod extrinsic;
+mod miner;
+
+use clap::{Parser, ValueEnum};
+use env_logger::Env;
+use frame_election_provider_support::{PhragMMS, SequentialPhragmen};
+use frame_support::parameter_types;
+use kitchensink_runtime::Runtime;
+use node_primitives::{AccountId, Hash};
+use pallet_election_provider_multi_phase::SolutionAccuracyOf;
+use sp_core::{sr25519, Pair};
+use sp_npos_elections::BalancingConfig;
+
+pub(crate) const LOG_TARGET: &str = "election-miner";
+
+parameter_types! {
+	/// The balancing of the solvers, as given on the command line.
+	pub static Balancing: Option<BalancingConfig> = None;
+}
+
+/// The algorithm used to mine a solution.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Solver {
+	/// Sequential Phragmen.
+	SeqPhragmen,
+	/// PhragMMS.
+	PhragMms,
+}
+
+#[derive(Debug, Parser)]
+#[command(author, version, about)]
+struct Opt {
+	/// The RPC endpoint of the node.
+	#[arg(long, default_value = "ws://127.0.0.1:9944")]
+	uri: String,
+
+	/// The block at which the snapshot is downloaded. Latest finalized block if not provided.
+	#[arg(long)]
+	at: Option<Hash>,
+
+	/// The algorithm used to mine the solution.
+	#[arg(long, value_enum, default_value_t = Solver::SeqPhragmen)]
+	solver: Solver,
+
+	/// The number of balancing iterations applied to the mined solution.
+	#[arg(long, default_value_t = 10)]
+	balancing_iterations: usize,
+
+	/// The secret URI of the account that signs the submission and pays its deposit, e.g.
+	/// `//Alice`.
+	#[arg(long)]
+	suri: String,
+
+	/// Mine and check the solution, but do not submit it.
+	#[arg(long)]
+	dry_run: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), String> {
+	env_logger::Builder::from_env(Env::default().default_filter_or("info")).init();
+	let Opt { uri, at, solver, balancing_iterations, suri, dry_run } = Opt::parse();
+
+	// fail early on an invalid secret URI, before the solution is mined.
+	sr25519::Pair::from_string(&suri, None).map_err(|e| format!("invalid secret URI: {:?}", e))?;
+
+	let mut ext = miner::download_snapshot(&uri, at).await?;
+	let at = ext.block_hash;
+
+	Balancing::set(
+		(balancing_iterations > 0)
+			.then(|| BalancingConfig { iterations: balancing_iterations, tolerance: 0 }),
+	);
+	let (raw_solution, is_better) = ext.execute_with(|| {
+		let raw_solution = match solver {
+			Solver::SeqPhragmen => miner::mine_solution::<
+				SequentialPhragmen<AccountId, SolutionAccuracyOf<Runtime>, Balancing>,
+			>(dry_run),
+			Solver::PhragMms => miner::mine_solution::<
+				PhragMMS<AccountId, SolutionAccuracyOf<Runtime>, Balancing>,
+			>(dry_run),
+		}?;
+		let is_better = miner::improves_queued_solutions(raw_solution.score);
+		Ok::<_, String>((raw_solution, is_better))
+	})?;
+
+	if dry_run {
+		log::info!(target: LOG_TARGET, "dry run, the solution is not submitted.");
+		return Ok(())
+	}
+
+	if !is_better {
+		log::info!(
+			target: LOG_TARGET,
+			"the solution does not improve the queued signed solutions, not submitting it.",
+		);
+		return Ok(())
+	}
+
+	let hash = extrinsic::submit_solution(&uri, at, &suri, raw_solution).await?;
+	log::info!(target: LOG_TARGET, "submitted the solution in extrinsic {:?}", hash);
+	Ok(())
+}
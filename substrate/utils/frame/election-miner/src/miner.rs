@@ -0,0 +1,202 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Downloading the snapshot of an election, and mining a solution for it.
+
+use crate::LOG_TARGET;
+use frame_election_provider_support::NposSolver;
+use frame_remote_externalities::{Builder, Mode, OnlineConfig, RemoteExternalities};
+use kitchensink_runtime::Runtime;
+use node_primitives::{AccountId, Block, Hash};
+use pallet_election_provider_multi_phase::{
+	CurrentPhase, DesiredTargets, ElectionCompute, Miner, MinimumUntrustedScore, RawSolution,
+	Round, SignedSubmissionIndices, Snapshot, SolutionOf,
+};
+use sp_npos_elections::ElectionScore;
+use sp_runtime::{traits::BlakeTwo256, Perbill};
+
+/// The name of the election provider pallet in the runtime.
+const PALLET_NAME: &str = "ElectionProviderMultiPhase";
+
+/// Download the storage of the election provider pallet at block `at`, or at the latest finalized
+/// block if `None`.
+pub(crate) async fn download_snapshot(
+	uri: &str,
+	at: Option<Hash>,
+) -> Result<RemoteExternalities<BlakeTwo256>, String> {
+	log::info!(target: LOG_TARGET, "downloading the election snapshot from {}", uri);
+	Builder::<Block>::new()
+		.mode(Mode::Online(OnlineConfig {
+			transport: uri.to_owned().into(),
+			at,
+			pallets: vec![PALLET_NAME.to_owned()],
+			child_trie: false,
+			..Default::default()
+		}))
+		.build()
+		.await
+		.map_err(|e| format!("failed to download the snapshot: {}", e))
+}
+
+/// Mine a solution for the snapshot of the ongoing election with the solver `S`, trimmed to the
+/// length and weight limits of the runtime.
+///
+/// The solution is checked for feasibility against the snapshot. Unless `force`, the signed phase
+/// must be open.
+///
+/// Must be called within the externalities returned by [`download_snapshot`].
+pub(crate) fn mine_solution<S>(force: bool) -> Result<RawSolution<SolutionOf<Runtime>>, String>
+where
+	S: NposSolver<AccountId = AccountId>,
+{
+	let phase = CurrentPhase::<Runtime>::get();
+	if !phase.is_signed() && !force {
+		return Err(format!("the signed phase is not open, current phase is {:?}", phase))
+	}
+
+	let snapshot = Snapshot::<Runtime>::get().ok_or("no election snapshot is available")?;
+	let desired_targets =
+		DesiredTargets::<Runtime>::get().ok_or("the desired targets are not available")?;
+	let round = Round::<Runtime>::get();
+
+	let (solution, score, size, trimming) = Miner::<Runtime>::mine_solution_with_snapshot::<S>(
+		snapshot.voters.clone(),
+		snapshot.targets.clone(),
+		desired_targets,
+	)
+	.map_err(|e| format!("failed to mine a solution: {:?}", e))?;
+
+	log::info!(
+		target: LOG_TARGET,
+		"mined a solution for round {} with {} voters and {} targets, score {:?}",
+		round,
+		size.voters,
+		size.targets,
+		score,
+	);
+	if trimming.is_trimmed() {
+		log::warn!(
+			target: LOG_TARGET,
+			"the solution was trimmed by {} voters for weight and {} voters for length",
+			trimming.trimmed_weight(),
+			trimming.trimmed_length(),
+		);
+	}
+
+	let raw_solution = RawSolution { solution, score, round };
+	Miner::<Runtime>::feasibility_check(
+		raw_solution.clone(),
+		ElectionCompute::Signed,
+		desired_targets,
+		snapshot,
+		round,
+		MinimumUntrustedScore::<Runtime>::get(),
+	)
+	.map_err(|e| format!("the mined solution is not feasible: {:?}", e))?;
+
+	Ok(raw_solution)
+}
+
+/// Whether a solution with `score` is strictly better than all the queued signed solutions.
+///
+/// Must be called within the externalities returned by [`download_snapshot`].
+pub(crate) fn improves_queued_solutions(score: ElectionScore) -> bool {
+	SignedSubmissionIndices::<Runtime>::get()
+		.into_iter()
+		.all(|(queued, _, _)| score.strict_threshold_better(queued, Perbill::zero()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_election_provider_support::SequentialPhragmen;
+	use node_primitives::BlockNumber;
+	use pallet_election_provider_multi_phase::{Phase, RoundSnapshot, SolutionAccuracyOf};
+	use sp_io::TestExternalities;
+
+	type Solver = SequentialPhragmen<AccountId, SolutionAccuracyOf<Runtime>>;
+
+	fn account(i: u8) -> AccountId {
+		AccountId::from([i; 32])
+	}
+
+	/// Externalities with the snapshot of an election of 2 out of 3 targets by 4 nominators, in
+	/// `phase`.
+	fn election(phase: Phase<BlockNumber>) -> TestExternalities {
+		let mut ext = TestExternalities::new_empty();
+		ext.execute_with(|| {
+			let targets = vec![account(1), account(2), account(3)];
+			let voters = vec![
+				(account(10), 100, vec![account(1), account(2)]),
+				(account(11), 200, vec![account(2), account(3)]),
+				(account(12), 300, vec![account(1)]),
+				(account(13), 400, vec![account(3), account(1)]),
+			]
+			.into_iter()
+			.map(|(who, stake, votes)| (who, stake, votes.try_into().unwrap()))
+			.collect();
+
+			Snapshot::<Runtime>::put(RoundSnapshot { voters, targets });
+			DesiredTargets::<Runtime>::put(2);
+			CurrentPhase::<Runtime>::put(phase);
+		});
+		ext
+	}
+
+	#[test]
+	fn mines_feasible_solution() {
+		election(Phase::Signed).execute_with(|| {
+			let raw_solution = mine_solution::<Solver>(false).unwrap();
+			assert_eq!(raw_solution.round, Round::<Runtime>::get());
+			assert!(raw_solution.score.minimal_stake > 0);
+			assert!(raw_solution.score.sum_stake <= 1_000);
+		});
+	}
+
+	#[test]
+	fn mining_requires_signed_phase_unless_forced() {
+		election(Phase::Off).execute_with(|| {
+			assert!(mine_solution::<Solver>(false).is_err());
+			assert!(mine_solution::<Solver>(true).is_ok());
+		});
+	}
+
+	#[test]
+	fn infeasible_solution_is_rejected() {
+		election(Phase::Signed).execute_with(|| {
+			MinimumUntrustedScore::<Runtime>::put(ElectionScore {
+				minimal_stake: u128::MAX,
+				..Default::default()
+			});
+			assert!(mine_solution::<Solver>(false).unwrap_err().contains("not feasible"));
+		});
+	}
+
+	#[test]
+	fn solution_must_improve_queued_solutions() {
+		election(Phase::Signed).execute_with(|| {
+			let score = mine_solution::<Solver>(false).unwrap().score;
+			assert!(improves_queued_solutions(score));
+
+			// a solution with the same score is queued.
+			SignedSubmissionIndices::<Runtime>::put(
+				frame_support::BoundedVec::try_from(vec![(score, 1, 0)]).unwrap(),
+			);
+			assert!(!improves_queued_solutions(score));
+		});
+	}
+}